@@ -7,14 +7,14 @@ use bevy_ecs::{
 };
 
 use naia_bevy_shared::{
-    Channel, EntityAndGlobalEntityConverter, EntityAuthStatus, EntityDoesNotExistError,
+    AuthorityError, BandwidthProfile, Channel, EntityAndGlobalEntityConverter, EntityAuthStatus, EntityDoesNotExistError,
     GlobalEntity, Message, Request, Response, ResponseReceiveKey, ResponseSendKey, Tick,
 };
 use naia_client::{
     shared::{GameInstant, SocketConfig},
     transport::Socket,
     Client as NaiaClient, ConnectionStats, ConnectionStatus, EntityPriorityMut, EntityPriorityRef,
-    NaiaClientError,
+    NaiaClientError, RenderTime,
 };
 
 use crate::Publicity;
@@ -155,6 +155,10 @@ impl<'w, T: Send + Sync + 'static> Client<'w, T> {
         self.client.client.server_interpolation()
     }
 
+    pub fn render_time(&self, delay_ticks: f32) -> Option<RenderTime> {
+        self.client.client.render_time(delay_ticks)
+    }
+
     // Entity Registration
 
     pub(crate) fn enable_replication(&mut self, entity: &Entity) {
@@ -177,6 +181,19 @@ impl<'w, T: Send + Sync + 'static> Client<'w, T> {
         let _ = self.client.client.entity_release_authority(entity);
     }
 
+    /// Requests authority over every entity in `entities` as one
+    /// transaction, sent over channel `C`. The outcome arrives as a single
+    /// [`EntityAuthGroupGrantedEvent`](crate::events::EntityAuthGroupGrantedEvent)
+    /// or [`EntityAuthGroupDeniedEvent`](crate::events::EntityAuthGroupDeniedEvent).
+    pub fn request_authority_group<C: Channel>(
+        &mut self,
+        entities: &[Entity],
+    ) -> Result<(), AuthorityError> {
+        self.client
+            .client
+            .entity_request_authority_group::<C>(entities)
+    }
+
     pub(crate) fn entity_authority_status(&self, entity: &Entity) -> Option<EntityAuthStatus> {
         self.client.client.entity_authority_status(entity)
     }
@@ -194,6 +211,26 @@ impl<'w, T: Send + Sync + 'static> Client<'w, T> {
     pub fn connection_stats(&self) -> Option<ConnectionStats> {
         self.client.client.connection_stats()
     }
+
+    pub fn bandwidth_profile(&self) -> Option<BandwidthProfile> {
+        self.client.client.bandwidth_profile()
+    }
+
+    pub fn reset_bandwidth_profile(&mut self) {
+        self.client.client.reset_bandwidth_profile();
+    }
+
+    pub fn uses_compression_dictionary(&self) -> bool {
+        self.client.client.uses_compression_dictionary()
+    }
+
+    pub fn take_trained_dictionary(&mut self) -> Option<Vec<u8>> {
+        self.client.client.take_trained_dictionary()
+    }
+
+    pub fn dictionary_training_progress(&self) -> Option<f32> {
+        self.client.client.dictionary_training_progress()
+    }
 }
 
 impl<'w, T: Send + Sync + 'static> EntityAndGlobalEntityConverter<Entity> for Client<'w, T> {
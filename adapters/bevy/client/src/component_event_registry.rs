@@ -54,8 +54,9 @@ impl<T: Send + Sync + 'static> ComponentEventRegistry<T> {
 
             for (kind, entities) in inserts {
                 // trigger bundle events
+                let bundle_entities = entities.iter().map(|(_, entity)| *entity).collect();
                 self.bundle_registry
-                    .process_inserts(world, &kind, &entities);
+                    .process_inserts(world, &kind, &bundle_entities);
 
                 // trigger component events
                 if let Some(component_handler) = self.component_handlers.get_mut(&kind) {
@@ -93,7 +94,7 @@ impl<T: Send + Sync + 'static> ComponentEventRegistry<T> {
 }
 
 trait ComponentEventHandler: Send + Sync {
-    fn handle_inserts(&mut self, world: &mut World, entities: Vec<Entity>);
+    fn handle_inserts(&mut self, world: &mut World, entities: Vec<(Tick, Entity)>);
     fn handle_updates(&mut self, world: &mut World, entities: Vec<(Tick, Entity)>);
     fn handle_removes(&mut self, world: &mut World, entities: Vec<(Entity, Box<dyn Replicate>)>);
 }
@@ -119,8 +120,8 @@ impl<T: Send + Sync + 'static, R: Replicate> ComponentEventHandlerImpl<T, R> {
 impl<T: Send + Sync + 'static, R: Replicate> ComponentEventHandler
     for ComponentEventHandlerImpl<T, R>
 {
-    fn handle_inserts(&mut self, world: &mut World, entities: Vec<Entity>) {
-        for entity in entities {
+    fn handle_inserts(&mut self, world: &mut World, entities: Vec<(Tick, Entity)>) {
+        for (tick, entity) in entities {
             // D13 resource translation: if user registered
             // InsertResourceEvent<T, R> via add_resource_events, route
             // to that stream instead of the component-event stream.
@@ -133,7 +134,7 @@ impl<T: Send + Sync + 'static, R: Replicate> ComponentEventHandler
             }
             world
                 .resource_mut::<Messages<InsertComponentEvent<T, R>>>()
-                .write(InsertComponentEvent::<T, R>::new(entity));
+                .write(InsertComponentEvent::<T, R>::new(tick, entity));
         }
     }
 
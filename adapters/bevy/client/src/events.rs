@@ -252,14 +252,16 @@ impl<T> DespawnEntityEvent<T> {
 
 #[derive(bevy_ecs::message::Message)]
 pub struct InsertComponentEvent<T: Send + Sync + 'static, C: Replicate> {
+    pub tick: Tick,
     pub entity: Entity,
     phantom_t: PhantomData<T>,
     phantom_c: PhantomData<C>,
 }
 
 impl<T: Send + Sync + 'static, C: Replicate> InsertComponentEvent<T, C> {
-    pub fn new(entity: Entity) -> Self {
+    pub fn new(tick: Tick, entity: Entity) -> Self {
         Self {
+            tick,
             entity,
             phantom_t: PhantomData,
             phantom_c: PhantomData,
@@ -439,6 +441,54 @@ impl<T> EntityAuthGrantedEvent<T> {
     }
 }
 
+// EntityAuthQueueGrantedEvent
+#[derive(bevy_ecs::message::Message)]
+pub struct EntityAuthQueueGrantedEvent<T> {
+    pub entity: Entity,
+    phantom_t: PhantomData<T>,
+}
+
+impl<T> EntityAuthQueueGrantedEvent<T> {
+    pub fn new(entity: Entity) -> Self {
+        Self {
+            entity,
+            phantom_t: PhantomData,
+        }
+    }
+}
+
+// EntityAuthGroupGrantedEvent
+#[derive(bevy_ecs::message::Message)]
+pub struct EntityAuthGroupGrantedEvent<T> {
+    pub entities: Vec<Entity>,
+    phantom_t: PhantomData<T>,
+}
+
+impl<T> EntityAuthGroupGrantedEvent<T> {
+    pub fn new(entities: Vec<Entity>) -> Self {
+        Self {
+            entities,
+            phantom_t: PhantomData,
+        }
+    }
+}
+
+// EntityAuthGroupDeniedEvent
+#[derive(bevy_ecs::message::Message)]
+pub struct EntityAuthGroupDeniedEvent<T> {
+    pub entities: Vec<Entity>,
+    phantom_t: PhantomData<T>,
+}
+
+impl<T> EntityAuthGroupDeniedEvent<T> {
+    pub fn new(entities: Vec<Entity>) -> Self {
+        Self {
+            entities,
+            phantom_t: PhantomData,
+        }
+    }
+}
+
 // EntityAuthDeniedEvent
 #[derive(bevy_ecs::message::Message)]
 pub struct EntityAuthDeniedEvent<T> {
@@ -0,0 +1,166 @@
+use std::{
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+};
+
+use bevy_app::{App, Plugin as PluginType, Update};
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    message::MessageReader,
+    resource::Resource,
+    schedule::IntoScheduleConfigs,
+    system::{Commands, Query, Res, ResMut},
+};
+
+use naia_bevy_shared::{HandleWorldEvents, Replicate};
+use naia_client::{Interpolate, InterpolationConfig, SnapshotInterpolation};
+
+use crate::{
+    app_ext::AppRegisterComponentEvents,
+    client::Client,
+    events::{
+        DespawnEntityEvent, DisconnectEvent, InsertComponentEvent, RemoveComponentEvent,
+        UpdateComponentEvent,
+    },
+};
+
+/// The render-time value of replicated component `C`, written every frame by
+/// [`InterpolationPlugin`]. Draw from this instead of `C` itself.
+#[derive(Component, Clone)]
+pub struct Interpolated<C: Send + Sync + 'static>(pub C);
+
+/// Snapshot history of `C` for client tag `T`, maintained by
+/// [`InterpolationPlugin`].
+#[derive(Resource)]
+pub struct InterpolationBuffer<T: Send + Sync + 'static, C: Replicate + Interpolate + Component> {
+    interpolation: SnapshotInterpolation<Entity, C>,
+    phantom_t: PhantomData<T>,
+}
+
+impl<T: Send + Sync + 'static, C: Replicate + Interpolate + Component> Deref
+    for InterpolationBuffer<T, C>
+{
+    type Target = SnapshotInterpolation<Entity, C>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.interpolation
+    }
+}
+
+impl<T: Send + Sync + 'static, C: Replicate + Interpolate + Component> DerefMut
+    for InterpolationBuffer<T, C>
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.interpolation
+    }
+}
+
+/// Bevy plugin that interpolates server-replicated component `C` for render.
+///
+/// Records every received value of `C` into an [`InterpolationBuffer`] and,
+/// each frame, writes the value at the delayed render time into an
+/// [`Interpolated<C>`] component on the same entity, removing it again
+/// when `C` is removed. Add it after the main client
+/// [`Plugin`](crate::Plugin) for the same tag `T`.
+///
+/// Both systems run in [`HandleWorldEvents`]; order your own readers of
+/// `Interpolated<C>` after that set.
+pub struct InterpolationPlugin<T, C> {
+    config: InterpolationConfig,
+    phantom: PhantomData<(T, C)>,
+}
+
+impl<T, C> InterpolationPlugin<T, C> {
+    /// Creates the plugin with the given interpolation tuning.
+    pub fn new(config: InterpolationConfig) -> Self {
+        Self {
+            config,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<T, C> Default for InterpolationPlugin<T, C> {
+    fn default() -> Self {
+        Self::new(InterpolationConfig::default())
+    }
+}
+
+impl<T: Send + Sync + 'static, C: Replicate + Interpolate + Component> PluginType
+    for InterpolationPlugin<T, C>
+{
+    fn build(&self, app: &mut App) {
+        app.add_component_events::<T, C>()
+            .insert_resource(InterpolationBuffer::<T, C> {
+                interpolation: SnapshotInterpolation::new(self.config.clone()),
+                phantom_t: PhantomData,
+            })
+            .add_systems(
+                Update,
+                (record_snapshots::<T, C>, write_interpolated::<T, C>)
+                    .chain()
+                    .in_set(HandleWorldEvents),
+            );
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn record_snapshots<T: Send + Sync + 'static, C: Replicate + Interpolate + Component>(
+    mut commands: Commands,
+    mut buffer: ResMut<InterpolationBuffer<T, C>>,
+    mut insert_events: MessageReader<InsertComponentEvent<T, C>>,
+    mut update_events: MessageReader<UpdateComponentEvent<T, C>>,
+    mut remove_events: MessageReader<RemoveComponentEvent<T, C>>,
+    mut despawn_events: MessageReader<DespawnEntityEvent<T>>,
+    mut disconnect_events: MessageReader<DisconnectEvent<T>>,
+    query: Query<&C>,
+) {
+    if disconnect_events.read().count() > 0 {
+        buffer.clear();
+    }
+    let removed = remove_events.read().map(|event| event.entity);
+    let despawned = despawn_events.read().map(|event| event.entity);
+    for entity in removed.chain(despawned) {
+        buffer.remove_entity(&entity);
+        if let Ok(mut entity_commands) = commands.get_entity(entity) {
+            entity_commands.try_remove::<Interpolated<C>>();
+        }
+    }
+
+    for event in insert_events.read() {
+        if let Ok(component) = query.get(event.entity) {
+            buffer.record(event.entity, event.tick, component.clone());
+        }
+    }
+    for event in update_events.read() {
+        if let Ok(component) = query.get(event.entity) {
+            buffer.record(event.entity, event.tick, component.clone());
+        }
+    }
+}
+
+fn write_interpolated<T: Send + Sync + 'static, C: Replicate + Interpolate + Component>(
+    mut commands: Commands,
+    client: Client<T>,
+    buffer: Res<InterpolationBuffer<T, C>>,
+    mut query: Query<Option<&mut Interpolated<C>>, bevy_ecs::query::With<C>>,
+) {
+    let Some(render_time) = client.render_time(buffer.config().delay_ticks) else {
+        return;
+    };
+    for entity in buffer.entities() {
+        let Ok(existing) = query.get_mut(*entity) else {
+            continue;
+        };
+        let Some(value) = buffer.sample(entity, render_time) else {
+            continue;
+        };
+        match existing {
+            Some(mut interpolated) => interpolated.0 = value,
+            None => {
+                commands.entity(*entity).insert(Interpolated(value));
+            }
+        }
+    }
+}
@@ -64,6 +64,8 @@
 //! | [`DefaultClientTag`] | Phantom type for single-client apps |
 //! | [`CommandsExt`] | Extension methods on [`Commands`] for replication |
 //! | [`ClientCommandsExt`] | Client-only extension methods on [`Commands`] |
+//! | [`PredictionPlugin`] | Automatic prediction rollback and replay via [`PredictionStep`] |
+//! | [`InterpolationPlugin`] | Render-time snapshot interpolation of a replicated component |
 //! | [`events`] | Bevy events mirroring naia world events |
 //!
 //! [`Commands`]: bevy_ecs::system::Commands
@@ -75,7 +77,9 @@ pub use naia_bevy_shared::{
 };
 pub use naia_client::{
     shared::{default_channels, Instant, Message, ResponseReceiveKey},
-    transport, ClientConfig, CommandHistory, JitterBufferType, NaiaClientError, Publicity,
+    transport, ClientConfig, CommandHistory, Interpolate, InterpolationConfig, JitterBufferType,
+    NaiaClientError, Predict, PredictionConfig, PredictionManager, Publicity, RenderTime,
+    Rollback, SnapshotInterpolation,
 };
 
 pub mod events;
@@ -86,7 +90,9 @@ mod client;
 mod commands;
 mod component_event_registry;
 mod components;
+mod interpolation;
 mod plugin;
+mod prediction;
 mod resource_sync;
 mod systems;
 
@@ -94,7 +100,11 @@ pub use app_ext::AppRegisterComponentEvents;
 pub use client::{Client, ClientWrapper};
 pub use commands::{CommandsExt, ClientCommandsExt};
 pub use components::{ClientOwned, ServerOwned};
+pub use interpolation::{Interpolated, InterpolationBuffer, InterpolationPlugin};
 pub use plugin::Plugin;
+pub use prediction::{
+    Prediction, PredictionInput, PredictionPlugin, PredictionStep, Smoothed,
+};
 
 /// Phantom tag type for single-client Bevy apps.
 ///
@@ -17,8 +17,10 @@ use super::{
     client::ClientWrapper,
     events::{
         ClientTickEvent, ConnectEvent, DespawnEntityEvent, DisconnectEvent, EntityAuthDeniedEvent,
-        EntityAuthGrantedEvent, EntityAuthResetEvent, ErrorEvent, MessageEvents,
-        PublishEntityEvent, RejectEvent, ServerTickEvent, SpawnEntityEvent, UnpublishEntityEvent,
+        EntityAuthGrantedEvent, EntityAuthGroupDeniedEvent, EntityAuthGroupGrantedEvent,
+        EntityAuthQueueGrantedEvent, EntityAuthResetEvent, ErrorEvent,
+        MessageEvents, PublishEntityEvent, RejectEvent, ServerTickEvent, SpawnEntityEvent,
+        UnpublishEntityEvent,
     },
     systems::{
         process_packets, receive_packets, send_packets, send_packets_init, translate_tick_events,
@@ -109,6 +111,9 @@ impl<T: Sync + Send + 'static> PluginType for Plugin<T> {
             .add_message::<PublishEntityEvent<T>>()
             .add_message::<UnpublishEntityEvent<T>>()
             .add_message::<EntityAuthGrantedEvent<T>>()
+            .add_message::<EntityAuthQueueGrantedEvent<T>>()
+            .add_message::<EntityAuthGroupGrantedEvent<T>>()
+            .add_message::<EntityAuthGroupDeniedEvent<T>>()
             .add_message::<EntityAuthDeniedEvent<T>>()
             .add_message::<EntityAuthResetEvent<T>>()
             // SYSTEM SETS //
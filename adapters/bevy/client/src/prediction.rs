@@ -0,0 +1,364 @@
+use std::{
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+};
+
+use bevy_app::{App, Plugin as PluginType, Update};
+use bevy_ecs::{
+    component::{Component, Mutable},
+    entity::Entity,
+    message::MessageReader,
+    query::With,
+    resource::Resource,
+    schedule::{IntoScheduleConfigs, ScheduleLabel},
+    system::{Commands, Query, ResMut},
+    world::World,
+};
+
+use naia_bevy_shared::{HandleWorldEvents, Replicate, Tick, WorldUpdate};
+use naia_client::{Interpolate, Predict, PredictionConfig, PredictionManager, RenderTime};
+
+use crate::{
+    app_ext::AppRegisterComponentEvents,
+    client::{Client, ClientWrapper},
+    events::{DespawnEntityEvent, DisconnectEvent, UpdateComponentEvent},
+};
+
+/// Schedule run once per simulated tick by [`PredictionPlugin`], both for
+/// live prediction and for replay after a rollback. Add the systems that
+/// apply a command to predicted entities here; they read the command from
+/// [`PredictionInput`].
+#[derive(ScheduleLabel, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PredictionStep;
+
+/// The command being simulated while [`PredictionStep`] runs.
+#[derive(Resource)]
+pub struct PredictionInput<C: Send + Sync + 'static> {
+    /// The client tick the command was recorded for.
+    pub tick: Tick,
+    /// The command to apply.
+    pub command: C,
+    /// `true` while replaying after a rollback rather than predicting live.
+    pub replaying: bool,
+}
+
+/// The [`PredictionManager`] for client tag `T` and command `C`. Pair
+/// entities and record commands through it; [`PredictionPlugin`] does the
+/// rest.
+#[derive(Resource)]
+pub struct Prediction<T: Send + Sync + 'static, C: Clone + Send + Sync + 'static> {
+    manager: PredictionManager<Entity, C>,
+    phantom_t: PhantomData<T>,
+}
+
+impl<T: Send + Sync + 'static, C: Clone + Send + Sync + 'static> Deref for Prediction<T, C> {
+    type Target = PredictionManager<Entity, C>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.manager
+    }
+}
+
+impl<T: Send + Sync + 'static, C: Clone + Send + Sync + 'static> DerefMut for Prediction<T, C> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.manager
+    }
+}
+
+/// The render value of a predicted component registered with
+/// [`PredictionPlugin::add_smoothed_component`], with rollback corrections
+/// blended away over `smoothing_ticks`. Draw from this instead of `S`.
+#[derive(Component, Clone)]
+pub struct Smoothed<S: Send + Sync + 'static>(pub S);
+
+#[derive(Resource)]
+struct PredictedComponents<T, C> {
+    snap: Vec<fn(&mut World)>,
+    record: Vec<fn(&mut World, Tick)>,
+    phantom: PhantomData<(T, C)>,
+}
+
+/// Bevy plugin that automates client-side prediction for command type `C`.
+///
+/// Each frame, after [`HandleWorldEvents`], the plugin checks confirmed
+/// values of every registered component against what was predicted. On a
+/// misprediction it snaps all predicted entities to their confirmed
+/// counterparts and replays every later command through [`PredictionStep`];
+/// otherwise it runs [`PredictionStep`] once per newly recorded command.
+///
+/// Record commands with [`Prediction::record_command`] in your
+/// `ClientTickEvent` handler and pair entities with
+/// [`Prediction::add_entity`]. Add it after the main client
+/// [`Plugin`](crate::Plugin) for the same tag `T`.
+pub struct PredictionPlugin<T, C> {
+    config: PredictionConfig,
+    components: Vec<fn(&mut App)>,
+    phantom: PhantomData<(T, C)>,
+}
+
+impl<T: Send + Sync + 'static, C: Clone + Send + Sync + 'static> PredictionPlugin<T, C> {
+    /// Creates the plugin with the given prediction tuning.
+    pub fn new(config: PredictionConfig) -> Self {
+        Self {
+            config,
+            components: Vec::new(),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Tracks component `S` on predicted entities: its predicted values are
+    /// checked against server updates and it is snapped on rollback.
+    pub fn add_component<S>(mut self) -> Self
+    where
+        S: Replicate + Predict + Component<Mutability = Mutable>,
+    {
+        self.components.push(register_component::<T, C, S, false>);
+        self
+    }
+
+    /// Like [`add_component`](Self::add_component), and also writes a
+    /// [`Smoothed<S>`] render value that hides rollback corrections.
+    pub fn add_smoothed_component<S>(mut self) -> Self
+    where
+        S: Replicate + Predict + Interpolate + Component<Mutability = Mutable>,
+    {
+        self.components.push(register_smoothed_component::<T, C, S>);
+        self
+    }
+}
+
+impl<T: Send + Sync + 'static, C: Clone + Send + Sync + 'static> PluginType
+    for PredictionPlugin<T, C>
+{
+    fn build(&self, app: &mut App) {
+        app.init_schedule(PredictionStep)
+            .insert_resource(Prediction::<T, C> {
+                manager: PredictionManager::new(self.config.clone()),
+                phantom_t: PhantomData,
+            })
+            .insert_resource(PredictedComponents::<T, C> {
+                snap: Vec::new(),
+                record: Vec::new(),
+                phantom: PhantomData,
+            })
+            .add_systems(
+                Update,
+                (forget_entities::<T, C>, run_prediction::<T, C>)
+                    .chain()
+                    .after(HandleWorldEvents)
+                    .before(WorldUpdate),
+            );
+        for register in &self.components {
+            register(app);
+        }
+    }
+}
+
+fn register_component<T, C, S, const SMOOTHED: bool>(app: &mut App)
+where
+    T: Send + Sync + 'static,
+    C: Clone + Send + Sync + 'static,
+    S: Replicate + Predict + Component<Mutability = Mutable>,
+{
+    app.add_component_events::<T, S>().add_systems(
+        Update,
+        confirm_component::<T, C, S>.in_set(HandleWorldEvents),
+    );
+    let mut components = app.world_mut().resource_mut::<PredictedComponents<T, C>>();
+    if !SMOOTHED {
+        components.snap.push(snap_component::<T, C, S>);
+    }
+    components.record.push(record_component::<T, C, S>);
+}
+
+fn register_smoothed_component<T, C, S>(app: &mut App)
+where
+    T: Send + Sync + 'static,
+    C: Clone + Send + Sync + 'static,
+    S: Replicate + Predict + Interpolate + Component<Mutability = Mutable>,
+{
+    register_component::<T, C, S, true>(app);
+    app.world_mut()
+        .resource_mut::<PredictedComponents<T, C>>()
+        .snap
+        .push(snap_smoothed_component::<T, C, S>);
+    app.add_systems(
+        Update,
+        write_smoothed::<T, C, S>
+            .after(run_prediction::<T, C>)
+            .before(WorldUpdate),
+    );
+}
+
+fn confirm_component<T, C, S>(
+    mut prediction: ResMut<Prediction<T, C>>,
+    mut update_events: MessageReader<UpdateComponentEvent<T, S>>,
+    query: Query<&S>,
+) where
+    T: Send + Sync + 'static,
+    C: Clone + Send + Sync + 'static,
+    S: Replicate + Predict + Component,
+{
+    for event in update_events.read() {
+        if let Ok(confirmed) = query.get(event.entity) {
+            prediction.confirm(&event.entity, event.tick, confirmed);
+        }
+    }
+}
+
+fn forget_entities<T, C>(
+    mut prediction: ResMut<Prediction<T, C>>,
+    mut despawn_events: MessageReader<DespawnEntityEvent<T>>,
+    mut disconnect_events: MessageReader<DisconnectEvent<T>>,
+) where
+    T: Send + Sync + 'static,
+    C: Clone + Send + Sync + 'static,
+{
+    if disconnect_events.read().count() > 0 {
+        prediction.clear();
+    }
+    for event in despawn_events.read() {
+        prediction.remove_entity(&event.entity);
+    }
+}
+
+fn run_prediction<T, C>(world: &mut World)
+where
+    T: Send + Sync + 'static,
+    C: Clone + Send + Sync + 'static,
+{
+    let (steps, replaying) = {
+        let mut prediction = world.resource_mut::<Prediction<T, C>>();
+        match prediction.take_rollback() {
+            Some(rollback) => (rollback.replays, true),
+            None => (prediction.take_steps(), false),
+        }
+    };
+
+    let (snap, record) = {
+        let components = world.resource::<PredictedComponents<T, C>>();
+        (components.snap.clone(), components.record.clone())
+    };
+
+    if replaying {
+        for snap in &snap {
+            snap(world);
+        }
+    }
+
+    for (tick, command) in steps {
+        world.insert_resource(PredictionInput {
+            tick,
+            command,
+            replaying,
+        });
+        world.run_schedule(PredictionStep);
+        for record in &record {
+            record(world, tick);
+        }
+    }
+    world.remove_resource::<PredictionInput<C>>();
+}
+
+fn snap_component<T, C, S>(world: &mut World)
+where
+    T: Send + Sync + 'static,
+    C: Clone + Send + Sync + 'static,
+    S: Replicate + Component<Mutability = Mutable>,
+{
+    let pairs: Vec<(Entity, Entity)> = world.resource::<Prediction<T, C>>().entities().collect();
+    for (confirmed, predicted) in pairs {
+        let Some(confirmed_value) = world.get::<S>(confirmed).map(|value| value.copy_to_box())
+        else {
+            continue;
+        };
+        if let Some(mut predicted_value) = world.get_mut::<S>(predicted) {
+            predicted_value.mirror(confirmed_value.as_ref());
+        }
+    }
+}
+
+fn snap_smoothed_component<T, C, S>(world: &mut World)
+where
+    T: Send + Sync + 'static,
+    C: Clone + Send + Sync + 'static,
+    S: Replicate + Interpolate + Component<Mutability = Mutable>,
+{
+    let Some(now) = client_now::<T>(world) else {
+        snap_component::<T, C, S>(world);
+        return;
+    };
+    let pairs: Vec<(Entity, Entity)> = world.resource::<Prediction<T, C>>().entities().collect();
+    let mut pre_rollback = Vec::new();
+    for (_, predicted) in &pairs {
+        let rendered = world
+            .get::<Smoothed<S>>(*predicted)
+            .map(|smoothed| smoothed.0.clone())
+            .or_else(|| world.get::<S>(*predicted).cloned());
+        if let Some(rendered) = rendered {
+            pre_rollback.push((*predicted, rendered));
+        }
+    }
+    snap_component::<T, C, S>(world);
+    let mut prediction = world.resource_mut::<Prediction<T, C>>();
+    for (predicted, rendered) in pre_rollback {
+        prediction.begin_smoothing(&predicted, rendered, now);
+    }
+}
+
+fn record_component<T, C, S>(world: &mut World, tick: Tick)
+where
+    T: Send + Sync + 'static,
+    C: Clone + Send + Sync + 'static,
+    S: Predict + Component,
+{
+    let predicted: Vec<(Entity, S)> = world
+        .resource::<Prediction<T, C>>()
+        .entities()
+        .filter_map(|(_, predicted)| Some((predicted, world.get::<S>(predicted)?.clone())))
+        .collect();
+    let mut prediction = world.resource_mut::<Prediction<T, C>>();
+    for (entity, value) in predicted {
+        prediction.record_predicted(&entity, tick, value);
+    }
+}
+
+fn write_smoothed<T, C, S>(
+    mut commands: Commands,
+    client: Client<T>,
+    mut prediction: ResMut<Prediction<T, C>>,
+    mut query: Query<(&S, Option<&mut Smoothed<S>>), With<S>>,
+) where
+    T: Send + Sync + 'static,
+    C: Clone + Send + Sync + 'static,
+    S: Replicate + Interpolate + Component,
+{
+    let (Some(tick), Some(fraction)) = (client.client_tick(), client.client_interpolation()) else {
+        return;
+    };
+    let now = RenderTime { tick, fraction };
+    let predicted: Vec<Entity> = prediction
+        .entities()
+        .map(|(_, predicted)| predicted)
+        .collect();
+    for entity in predicted {
+        let Ok((current, existing)) = query.get_mut(entity) else {
+            continue;
+        };
+        let value = prediction.smoothed(&entity, current, now);
+        match existing {
+            Some(mut smoothed) => smoothed.0 = value,
+            None => {
+                commands.entity(entity).insert(Smoothed(value));
+            }
+        }
+    }
+}
+
+fn client_now<T: Send + Sync + 'static>(world: &World) -> Option<RenderTime> {
+    let client = &world.resource::<ClientWrapper<T>>().client;
+    Some(RenderTime {
+        tick: client.client_tick()?,
+        fraction: client.client_interpolation()?,
+    })
+}
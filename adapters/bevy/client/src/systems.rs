@@ -15,17 +15,19 @@ use naia_bevy_shared::{
 mod naia_events {
     pub use naia_client::{
         ClientTickEvent, ConnectEvent, DespawnEntityEvent, DisconnectEvent, EntityAuthDeniedEvent,
-        EntityAuthGrantedEvent, EntityAuthResetEvent, ErrorEvent, PublishEntityEvent, RejectEvent,
-        ServerTickEvent, SpawnEntityEvent, UnpublishEntityEvent,
+        EntityAuthGrantedEvent, EntityAuthGroupDeniedEvent, EntityAuthGroupGrantedEvent,
+        EntityAuthQueueGrantedEvent, EntityAuthResetEvent, ErrorEvent,
+        PublishEntityEvent, RejectEvent, ServerTickEvent, SpawnEntityEvent, UnpublishEntityEvent,
     };
 }
 
 mod bevy_events {
     pub use crate::events::{
         ClientTickEvent, ConnectEvent, DespawnEntityEvent, DisconnectEvent, EntityAuthDeniedEvent,
-        EntityAuthGrantedEvent, EntityAuthResetEvent, ErrorEvent, MessageEvents,
-        PublishEntityEvent, RejectEvent, RequestEvents, ServerTickEvent, SpawnEntityEvent,
-        UnpublishEntityEvent,
+        EntityAuthGrantedEvent, EntityAuthGroupDeniedEvent, EntityAuthGroupGrantedEvent,
+        EntityAuthQueueGrantedEvent, EntityAuthResetEvent, ErrorEvent,
+        MessageEvents, PublishEntityEvent, RejectEvent, RequestEvents, ServerTickEvent,
+        SpawnEntityEvent, UnpublishEntityEvent,
     };
 }
 
@@ -255,6 +257,60 @@ pub fn translate_world_events<T: Send + Sync + 'static>(world: &mut World) {
                 }
             }
 
+            // Entity Auth Queue Granted Event
+            if events.has::<naia_events::EntityAuthQueueGrantedEvent>() {
+                let mut event_writer = world
+                    .get_resource_mut::<Messages<bevy_events::EntityAuthQueueGrantedEvent<T>>>()
+                    .unwrap();
+                let mut auth_granted_entities = Vec::new();
+                for entity in events.read::<naia_events::EntityAuthQueueGrantedEvent>() {
+                    auth_granted_entities.push(entity);
+                    event_writer.write(bevy_events::EntityAuthQueueGrantedEvent::<T>::new(entity));
+                }
+                for entity in auth_granted_entities {
+                    if world.get_entity(entity).is_ok() {
+                        world.entity_mut(entity).insert(HostOwned::new::<T>());
+                    } else {
+                        warn!(
+                            "Granted auth to an entity that no longer exists! {:?}",
+                            entity
+                        );
+                    }
+                }
+            }
+
+            // Entity Auth Group Granted Event
+            if events.has::<naia_events::EntityAuthGroupGrantedEvent>() {
+                let mut event_writer = world
+                    .get_resource_mut::<Messages<bevy_events::EntityAuthGroupGrantedEvent<T>>>()
+                    .unwrap();
+                let mut auth_granted_entities = Vec::new();
+                for entities in events.read::<naia_events::EntityAuthGroupGrantedEvent>() {
+                    auth_granted_entities.extend(entities.iter().copied());
+                    event_writer.write(bevy_events::EntityAuthGroupGrantedEvent::<T>::new(entities));
+                }
+                for entity in auth_granted_entities {
+                    if world.get_entity(entity).is_ok() {
+                        world.entity_mut(entity).insert(HostOwned::new::<T>());
+                    } else {
+                        warn!(
+                            "Granted auth to an entity that no longer exists! {:?}",
+                            entity
+                        );
+                    }
+                }
+            }
+
+            // Entity Auth Group Denied Event
+            if events.has::<naia_events::EntityAuthGroupDeniedEvent>() {
+                let mut event_writer = world
+                    .get_resource_mut::<Messages<bevy_events::EntityAuthGroupDeniedEvent<T>>>()
+                    .unwrap();
+                for entities in events.read::<naia_events::EntityAuthGroupDeniedEvent>() {
+                    event_writer.write(bevy_events::EntityAuthGroupDeniedEvent::<T>::new(entities));
+                }
+            }
+
             // Entity Auth Denied Event
             if events.has::<naia_events::EntityAuthDeniedEvent>() {
                 let mut event_writer = world
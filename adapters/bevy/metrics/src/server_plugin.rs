@@ -4,7 +4,9 @@ use bevy_app::{App, Plugin, Update};
 use bevy_ecs::prelude::IntoScheduleConfigs;
 use naia_bevy_server::{BigMapKey, Server};
 use naia_bevy_shared::SendPackets;
-use naia_metrics::{emit_server_aggregates, emit_server_connection_stats};
+use naia_metrics::{
+    emit_server_aggregates, emit_server_connection_stats, emit_server_tick_duration,
+};
 
 /// Bevy plugin that emits naia server metrics once per tick, immediately
 /// after naia's [`SendPackets`] system.
@@ -22,6 +24,7 @@ fn emit_server_metrics(server: Server) {
         server.entity_count(),
         server.room_count(),
     );
+    emit_server_tick_duration(server.average_tick_duration());
     for user_key in server.user_keys() {
         if let Some(stats) = server.connection_stats(&user_key) {
             emit_server_connection_stats(&stats, user_key.to_u64());
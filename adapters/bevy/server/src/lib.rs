@@ -83,7 +83,9 @@ pub use naia_server::{
         FileBitWriter, ResponseReceiveKey, SerdeErr, SignedInteger, SignedVariableInteger,
         SocketConfig, UnsignedInteger, UnsignedVariableInteger,
     },
-    transport, ReplicationConfig, RoomKey, SerdeBevy as Serde, ServerConfig, UserKey,
+    transport, AuthorityHolder, AuthorityPolicy, EntityAuthoritySnapshot, ReplicationConfig,
+    RoomKey, RoomSnapshot, SerdeBevy as Serde, ServerConfig, ServerSnapshot,
+    SpatialInterestConfig, SpatialPosition, UpdateMode, UpdateValidation, UserKey, UserSnapshot,
 };
 
 pub mod events;
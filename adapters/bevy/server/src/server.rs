@@ -1,6 +1,7 @@
 use std::time::Duration;
 
 use bevy_ecs::{
+    component::Mutable,
     entity::Entity,
     resource::Resource,
     system::{ResMut, SystemParam},
@@ -10,12 +11,13 @@ use bevy_ecs::{
 use naia_server::{
     shared::SocketConfig, transport::Socket, ConnectionStats, EntityOwner, EntityPriorityMut,
     EntityPriorityRef, Events, Historian, NaiaServerError, ReplicationConfig, RoomKey, RoomMut,
-    RoomRef, Server as NaiaServer, TickBufferMessages, TickEvents, UserKey, UserMut, UserRef,
-    UserScopeMut, UserScopeRef, WorldServer as NaiaWorldServer, WorldServer,
+    RoomRef, Server as NaiaServer, ServerSnapshot, SpatialInterest, SpatialInterestConfig, SpatialPosition,
+    TickBufferMessages, TickEvents, UpdateValidation, UserKey, UserMut, UserRef, UserScopeMut,
+    UserScopeRef, WorldServer as NaiaWorldServer, WorldServer,
 };
 
 use naia_bevy_shared::{
-    Channel, ComponentKind, EntityAndGlobalEntityConverter, EntityAuthStatus,
+    AuthorityError, BandwidthProfile, Channel, ComponentKind, EntityAndGlobalEntityConverter, EntityAuthStatus,
     EntityDoesNotExistError, GlobalEntity, Instant, Message, ReplicatedResource, Request, Response,
     ResponseReceiveKey, ResponseSendKey, Tick, WorldMutType, WorldRefType,
 };
@@ -280,6 +282,15 @@ impl<'w> Server<'w> {
         }
     }
 
+    pub fn accept_observer_connection(&mut self, user_key: &UserKey) {
+        match &mut *self.server_impl {
+            ServerImpl::WorldOnly(_server) => {
+                panic!("WorldOnly Servers do not support this function")
+            }
+            ServerImpl::Full(server) => server.accept_observer_connection(user_key),
+        }
+    }
+
     pub fn reject_connection(&mut self, user_key: &UserKey) {
         match &mut *self.server_impl {
             ServerImpl::WorldOnly(_server) => {
@@ -436,6 +447,20 @@ impl<'w> Server<'w> {
         }
     }
 
+    pub fn observer_keys(&self) -> Vec<UserKey> {
+        match &*self.server_impl {
+            ServerImpl::WorldOnly(server) => server.observer_keys(),
+            ServerImpl::Full(server) => server.observer_keys(),
+        }
+    }
+
+    pub fn observer_count(&self) -> usize {
+        match &*self.server_impl {
+            ServerImpl::WorldOnly(server) => server.observer_count(),
+            ServerImpl::Full(server) => server.observer_count(),
+        }
+    }
+
     pub fn entity_count(&self) -> usize {
         match &*self.server_impl {
             ServerImpl::WorldOnly(server) => server.entity_count(),
@@ -588,6 +613,48 @@ impl<'w> Server<'w> {
         }
     }
 
+    pub fn bandwidth_profile(&self, user_key: &UserKey) -> Option<BandwidthProfile> {
+        match &*self.server_impl {
+            ServerImpl::WorldOnly(server) => server.bandwidth_profile(user_key),
+            ServerImpl::Full(server) => server.bandwidth_profile(user_key),
+        }
+    }
+
+    pub fn global_bandwidth_profile(&self) -> BandwidthProfile {
+        match &*self.server_impl {
+            ServerImpl::WorldOnly(server) => server.global_bandwidth_profile(),
+            ServerImpl::Full(server) => server.global_bandwidth_profile(),
+        }
+    }
+
+    pub fn reset_bandwidth_profiles(&mut self) {
+        match &mut *self.server_impl {
+            ServerImpl::WorldOnly(server) => server.reset_bandwidth_profiles(),
+            ServerImpl::Full(server) => server.reset_bandwidth_profiles(),
+        }
+    }
+
+    pub fn snapshot(&self) -> ServerSnapshot<Entity> {
+        match &*self.server_impl {
+            ServerImpl::WorldOnly(server) => server.snapshot(),
+            ServerImpl::Full(server) => server.snapshot(),
+        }
+    }
+
+    pub fn take_trained_dictionary(&mut self) -> Option<Vec<u8>> {
+        match &mut *self.server_impl {
+            ServerImpl::WorldOnly(server) => server.take_trained_dictionary(),
+            ServerImpl::Full(server) => server.take_trained_dictionary(),
+        }
+    }
+
+    pub fn dictionary_training_progress(&self) -> Option<f32> {
+        match &*self.server_impl {
+            ServerImpl::WorldOnly(server) => server.dictionary_training_progress(),
+            ServerImpl::Full(server) => server.dictionary_training_progress(),
+        }
+    }
+
     pub fn enable_historian(&mut self, max_ticks: u16) {
         match &mut *self.server_impl {
             ServerImpl::WorldOnly(server) => server.enable_historian(max_ticks),
@@ -609,6 +676,96 @@ impl<'w> Server<'w> {
         }
     }
 
+    pub fn historian_mut(&mut self) -> Option<&mut Historian> {
+        match &mut *self.server_impl {
+            ServerImpl::WorldOnly(server) => server.historian_mut(),
+            ServerImpl::Full(server) => server.historian_mut(),
+        }
+    }
+
+    pub fn rewind_world<W: WorldMutType<Entity>, R>(
+        &self,
+        world: W,
+        tick: Tick,
+        fraction: f32,
+        f: impl FnOnce(&mut W) -> R,
+    ) -> Option<R> {
+        match &*self.server_impl {
+            ServerImpl::WorldOnly(server) => server.rewind_world(world, tick, fraction, f),
+            ServerImpl::Full(server) => server.rewind_world(world, tick, fraction, f),
+        }
+    }
+
+    pub fn enable_spatial_interest<R>(&mut self, config: SpatialInterestConfig)
+    where
+        R: Replicate + SpatialPosition + bevy_ecs::component::Component<Mutability = Mutable>,
+    {
+        match &mut *self.server_impl {
+            ServerImpl::WorldOnly(server) => server.enable_spatial_interest::<R>(config),
+            ServerImpl::Full(server) => server.enable_spatial_interest::<R>(config),
+        }
+    }
+
+    pub fn disable_spatial_interest(&mut self) {
+        match &mut *self.server_impl {
+            ServerImpl::WorldOnly(server) => server.disable_spatial_interest(),
+            ServerImpl::Full(server) => server.disable_spatial_interest(),
+        }
+    }
+
+    pub fn spatial_interest(&self) -> Option<&SpatialInterest> {
+        match &*self.server_impl {
+            ServerImpl::WorldOnly(server) => server.spatial_interest(),
+            ServerImpl::Full(server) => server.spatial_interest(),
+        }
+    }
+
+    pub fn set_user_viewpoint(&mut self, user_key: &UserKey, x: f32, y: f32) {
+        match &mut *self.server_impl {
+            ServerImpl::WorldOnly(server) => server.set_user_viewpoint(user_key, x, y),
+            ServerImpl::Full(server) => server.set_user_viewpoint(user_key, x, y),
+        }
+    }
+
+    pub fn set_user_viewpoint_entity(&mut self, user_key: &UserKey, entity: &Entity) {
+        match &mut *self.server_impl {
+            ServerImpl::WorldOnly(server) => server.set_user_viewpoint_entity(user_key, entity),
+            ServerImpl::Full(server) => server.set_user_viewpoint_entity(user_key, entity),
+        }
+    }
+
+    pub fn clear_user_viewpoint(&mut self, user_key: &UserKey) {
+        match &mut *self.server_impl {
+            ServerImpl::WorldOnly(server) => server.clear_user_viewpoint(user_key),
+            ServerImpl::Full(server) => server.clear_user_viewpoint(user_key),
+        }
+    }
+
+    // Update Validation
+
+    pub fn set_component_validator<R, C>(
+        &mut self,
+        validator: impl Fn(&UserKey, &R, &mut R) -> UpdateValidation + Send + Sync + 'static,
+    ) where
+        R: Replicate + bevy_ecs::component::Component<Mutability = Mutable>,
+        C: Channel,
+    {
+        match &mut *self.server_impl {
+            ServerImpl::WorldOnly(server) => server.set_component_validator::<R, C>(validator),
+            ServerImpl::Full(server) => server.set_component_validator::<R, C>(validator),
+        }
+    }
+
+    pub fn remove_component_validator<R>(&mut self) -> bool
+    where
+        R: Replicate + bevy_ecs::component::Component<Mutability = Mutable>,
+    {
+        match &mut *self.server_impl {
+            ServerImpl::WorldOnly(server) => server.remove_component_validator::<R>(),
+            ServerImpl::Full(server) => server.remove_component_validator::<R>(),
+        }
+    }
+
     // Entity Replication
 
     pub(crate) fn enable_replication(&mut self, entity: &Entity) {
@@ -668,6 +825,28 @@ impl<'w> Server<'w> {
         }
     }
 
+    /// Grants `user_key` authority over every entity in `entities`, or over
+    /// none of them.
+    pub fn give_authority_group(
+        &mut self,
+        user_key: &UserKey,
+        entities: &[Entity],
+    ) -> Result<(), AuthorityError> {
+        match &mut *self.server_impl {
+            ServerImpl::WorldOnly(server) => server.entity_give_authority_group(user_key, entities),
+            ServerImpl::Full(server) => server.give_authority_group(user_key, entities),
+        }
+    }
+
+    /// Takes authority over every entity in `entities` back to the server, or
+    /// over none of them.
+    pub fn take_authority_group(&mut self, entities: &[Entity]) -> Result<(), AuthorityError> {
+        match &mut *self.server_impl {
+            ServerImpl::WorldOnly(server) => server.entity_take_authority_group(entities),
+            ServerImpl::Full(server) => server.take_authority_group(entities),
+        }
+    }
+
     pub(crate) fn entity_authority_status(&self, _entity: &Entity) -> Option<EntityAuthStatus> {
         todo!("entity_authority_status requires world access; use ServerImpl directly in exclusive systems")
     }
@@ -3,20 +3,23 @@ pub use bevy_ecs;
 pub use naia_shared::{
     sequence_greater_than, sequence_less_than, wrapping_diff, AuthorityError, BitReader, BitWrite,
     BitWriter,
-    BandwidthConfig, Channel, ChannelDirection, ChannelKind, ChannelMode, ComponentFieldUpdate,
-    ComponentKind, ComponentKinds, ComponentUpdate, CompressionConfig, CompressionMode,
-    ConstBitLength, DiffMask, EntityAndGlobalEntityConverter, EntityAuthAccessor,
-    EntityAuthStatus, EntityDoesNotExistError, EntityProperty, FakeEntityConverter, FileBitWriter,
+    BandwidthConfig, BandwidthProfile, Channel, ChannelDirection, ChannelKind, ChannelMode, CollectionAcks, ComponentFieldUpdate,
+    ComponentKind, ComponentKinds, ComponentUpdate, CompressionCapabilities, CompressionConfig, CompressionMode, CongestionControlConfig,
+    ConstBitLength, DictionaryId, DiffMask, EntityAndGlobalEntityConverter, EntityAuthAccessor,
+    EntityAuthStatus, EntityDoesNotExistError, EntityProperty, EntitySet, FakeEntityConverter, FileBitWriter,
     GameInstant, GlobalEntity, HostEntity, HostEntityAuthStatus, Instant, LinkConditionerConfig,
     LocalEntityAndGlobalEntityConverter, LocalEntityAndGlobalEntityConverterMut, LocalEntityMap,
     MessageBevy as Message, MessageBuilder, MessageContainer, MessageKind, MessageKinds, Named,
-    OwnedBitReader, Property, PropertyMutate, PropertyMutator, Random, ReliableSettings,
-    RemoteEntity, ReplicaDynMut, ReplicaDynRef, ReplicateBevy as Replicate, ReplicateBuilder,
-    Request, ResourceAlreadyExists, ResourceKinds, ResourceRegistry, Response, ResponseReceiveKey,
-    ResponseSendKey, SerdeBevyShared as Serde, SerdeErr, SerdeFloatConversion,
-    SerdeIntegerConversion, SignedFloat, SignedInteger, SignedVariableFloat, SignedVariableInteger,
-    Tick, TickBufferSettings, Timer, UnsignedFloat, UnsignedInteger, UnsignedVariableFloat,
-    UnsignedVariableInteger, WorldMutType, WorldRefType, MTU_SIZE_BYTES,
+    MapChange, NormalizedVector, OwnedBitReader, PacketCodec, Property, PropertyMap, PropertyMutate, PropertyMutator,
+    PropertyVec, Quantize,
+    QuantizedFloat, Random, ReliableSettings, RemoteEntity, ReplicaDynMut, ReplicaDynRef,
+    ReplicateBevy as Replicate, ReplicateBuilder, Request, ResourceAlreadyExists, ResourceKinds,
+    ResourceRegistry, Response, ResponseReceiveKey, ResponseSendKey, SentBits, SerdeBevyShared as Serde,
+    SerdeErr, SerdeFloatConversion, SerdeIntegerConversion, SignedFloat, SignedInteger,
+    SignedVariableFloat, SignedVariableInteger, SmallestThree, Tick, TickBufferSettings, Timer,
+    UnsignedFloat, UnsignedInteger, UnsignedVariableFloat, UnsignedVariableInteger, VecChange,
+    WorldMutType,
+    WorldRefType, MTU_SIZE_BYTES,
 };
 
 mod bundle;
@@ -43,6 +46,8 @@ pub use world_op_command::WorldOpCommand;
 /// via `naia-bevy-server` / `naia-bevy-client`).
 #[cfg(all(feature = "test_time", not(target_arch = "wasm32")))]
 pub use naia_shared::TestClock;
+#[cfg(feature = "serde_messages")]
+pub use naia_shared::{SerdeMessage, SerdeMessageError, SerdePayload};
 pub use change_detection::HostSyncEvent;
 pub use component_access::{AppTag, ComponentAccess, ComponentAccessor};
 pub use components::{HostOwned, HostOwnedMap};
@@ -1,10 +1,18 @@
-use std::{any::Any, collections::VecDeque, hash::Hash, net::SocketAddr, time::Duration};
+use std::{
+    any::Any,
+    collections::{HashSet, VecDeque},
+    hash::Hash,
+    net::SocketAddr,
+    time::Duration,
+};
 
 use log::{debug, info, warn};
 
 use naia_shared::{
     handshake::{HandshakeHeader, RejectReason},
-    AuthorityError, BitWriter, Channel, ChannelKind, ComponentKind, ConnectionStats,
+    AuthorityError, AuthorityGroupRequest, BandwidthProfile, BitWriter, Channel, ChannelKind,
+    ComponentKind, ConnectionStats,
+    DemoRecorder, PacketCodec,
     EntityAndGlobalEntityConverter,
     EntityAuthStatus, EntityDoesNotExistError, EntityEvent, EntityPriorityMut, EntityPriorityRef,
     FakeEntityConverter, GameInstant, GlobalEntity, GlobalEntityMap, GlobalEntitySpawner,
@@ -22,10 +30,12 @@ use super::{
 use crate::{
     connection::{base_time_manager::BaseTimeManager, connection::Connection, io::Io},
     handshake::{HandshakeManager, HandshakeResult, Handshaker},
+    interpolation::RenderTime,
     tick_events::TickEvents,
     transport::{IdentityReceiverResult, Socket},
     world::{
         entity_mut::EntityMut, entity_owner::EntityOwner, entity_ref::EntityRef,
+        authority_groups::{AuthorityGroups, GroupProgress},
         global_world_manager::GlobalWorldManager,
     },
     Publicity,
@@ -69,6 +79,10 @@ pub struct Client<E: Copy + Eq + Hash + Send + Sync> {
     // World
     global_world_manager: GlobalWorldManager,
     global_entity_map: GlobalEntityMap<E>,
+    // Delegated entities whose authority request the server has queued
+    queued_authority_requests: HashSet<GlobalEntity>,
+    // Group authority requests awaiting an answer for every member
+    authority_groups: AuthorityGroups,
     // Events
     incoming_world_events: Events<E>,
     incoming_tick_events: TickEvents,
@@ -81,6 +95,9 @@ pub struct Client<E: Copy + Eq + Hash + Send + Sync> {
     // resource events and maintain the bevy-Resource side. See
     // `_AGENTS/RESOURCES_PLAN.md` §A1 + `RESOURCES_AUDIT.md`.
     resource_registry: naia_shared::ResourceRegistry,
+    // Demo recording of everything received from the server. None unless
+    // start_demo_recording() is called.
+    demo_recorder: Option<DemoRecorder>,
 }
 
 impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
@@ -111,6 +128,7 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
             client_config.send_handshake_interval,
             client_config.ping_interval,
             client_config.handshake_pings,
+            Io::compression_capabilities(&protocol.compression),
         );
 
         let compression_config = protocol.compression.clone();
@@ -135,11 +153,14 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
             // World
             global_world_manager: GlobalWorldManager::new(),
             global_entity_map: GlobalEntityMap::new(),
+            queued_authority_requests: HashSet::new(),
+            authority_groups: AuthorityGroups::new(),
             // Events
             incoming_world_events: Events::new(),
             incoming_tick_events: TickEvents::new(),
             priority: UserPriorityState::new(),
             resource_registry: naia_shared::ResourceRegistry::new(),
+            demo_recorder: None,
         }
     }
 
@@ -355,9 +376,11 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
             &mut world,
             now,
             &mut self.incoming_world_events,
+            self.demo_recorder.as_mut(),
         );
 
         self.process_entity_events(&mut world, entity_events);
+        self.record_demo_tick(&world);
     }
 
     /// Drains and returns all accumulated world events since the last call.
@@ -1046,6 +1069,71 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
         result
     }
 
+    /// Requests authority over several delegated entities as one
+    /// transaction, sent over channel `C`.
+    ///
+    /// The server grants every entity or none of them, and the client
+    /// receives a single [`EntityAuthGroupGrantedEvent`] or
+    /// [`EntityAuthGroupDeniedEvent`] instead of per-entity events. Nothing is
+    /// requested if any entity can't be requested locally.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `C` is not a reliable channel the client can send on.
+    ///
+    /// [`EntityAuthGroupGrantedEvent`]: crate::events::EntityAuthGroupGrantedEvent
+    /// [`EntityAuthGroupDeniedEvent`]: crate::events::EntityAuthGroupDeniedEvent
+    pub fn entity_request_authority_group<C: Channel>(
+        &mut self,
+        world_entities: &[E],
+    ) -> Result<(), AuthorityError> {
+        self.check_client_authoritative_allowed();
+
+        let channel_kind = ChannelKind::of::<C>();
+        let channel_settings = self.protocol.channel_kinds.channel(&channel_kind);
+        if !channel_settings.reliable() || !channel_settings.can_send_to_server() {
+            panic!("Authority group requests can only be sent over Reliable Channels that can send to the Server");
+        }
+
+        let mut global_entities = Vec::with_capacity(world_entities.len());
+        for world_entity in world_entities {
+            let Ok(global_entity) = self.global_entity_map.entity_to_global_entity(world_entity)
+            else {
+                return Err(AuthorityError::NotInScope);
+            };
+            if !global_entities.contains(&global_entity) {
+                global_entities.push(global_entity);
+            }
+        }
+        if global_entities.is_empty() {
+            return Ok(());
+        }
+
+        let Some(connection) = &mut self.server_connection else {
+            return Err(AuthorityError::NotInScope);
+        };
+
+        // 1. Set local authority status for every Entity, or none
+        self.global_world_manager
+            .entity_request_authority_group(&global_entities)?;
+
+        // 2. Send the group to the Server as a single message
+        let mut converter = connection
+            .base
+            .world_manager
+            .entity_converter_mut(&self.global_world_manager);
+        let request = AuthorityGroupRequest::new(&global_entities, &mut converter);
+        connection.base.message_manager.send_message(
+            &self.protocol.message_kinds,
+            &mut converter,
+            &channel_kind,
+            MessageContainer::new(Box::new(request)),
+        );
+        self.authority_groups.insert(global_entities);
+
+        Ok(())
+    }
+
     /// Releases the client's authority over the given entity back to the
     /// server.
     ///
@@ -1195,6 +1283,19 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
         None
     }
 
+    /// Returns the point on the server timeline `delay_ticks` behind the
+    /// current server receive tick, for sampling a
+    /// [`SnapshotInterpolation`](crate::SnapshotInterpolation). Returns `None`
+    /// if not connected.
+    pub fn render_time(&self, delay_ticks: f32) -> Option<RenderTime> {
+        let connection = self.server_connection.as_ref()?;
+        Some(RenderTime::delayed(
+            connection.time_manager.client_receiving_tick,
+            connection.time_manager.server_interpolation(),
+            delay_ticks,
+        ))
+    }
+
     // Diagnostics ───────────────────────────────────────────────────────────
 
     /// Returns the rolling-average outgoing bandwidth to the server
@@ -1226,9 +1327,134 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
             packet_loss_pct,
             kbps_sent: self.io.outgoing_bandwidth(),
             kbps_recv: self.io.incoming_bandwidth(),
+            kbps_target: (conn.base.bandwidth_target_bytes_per_sec() * 8.0 / 1000.0) as f32,
+            channels: conn.channel_stats(&self.protocol.channel_kinds),
         })
     }
 
+    /// Returns where the bits sent to the server went, or `None` if not
+    /// connected or `ConnectionConfig::bandwidth_profiling` is off.
+    pub fn bandwidth_profile(&self) -> Option<BandwidthProfile> {
+        let conn = self.server_connection.as_ref()?;
+        let profiler = conn.base.bandwidth_profiler.as_ref()?;
+        Some(profiler.profile(
+            &self.protocol.component_kinds,
+            &self.protocol.channel_kinds,
+        ))
+    }
+
+    /// Clears the bandwidth profile of the connection to the server.
+    pub fn reset_bandwidth_profile(&mut self) {
+        if let Some(profiler) = self
+            .server_connection
+            .as_mut()
+            .and_then(|conn| conn.base.bandwidth_profiler.as_mut())
+        {
+            profiler.reset();
+        }
+    }
+
+    // Compression ───────────────────────────────────────────────────────────
+
+    /// Returns the codec packets to the server are compressed with, as
+    /// negotiated during the handshake. `PacketCodec::Uncompressed` before
+    /// connecting, or when the two builds share no codec.
+    pub fn compression_codec(&self) -> PacketCodec {
+        self.io.codec()
+    }
+
+    /// Returns `true` if packets to the server are compressed with the
+    /// client-to-server dictionary, i.e. the server reported holding the same
+    /// one during the handshake. `false` before connecting, without a
+    /// `CompressionMode::Dictionary`, or on a mismatch — in which case the
+    /// client falls back to plain compression at the same level.
+    pub fn uses_compression_dictionary(&self) -> bool {
+        self.io.codec() == PacketCodec::ZstdDictionary
+    }
+
+    /// Takes the dictionary trained from packets sent to the server in
+    /// `CompressionMode::Training`. Returns `None` until the target number
+    /// of samples has been recorded, and after the dictionary has been taken.
+    ///
+    /// Store the bytes and pass them to `CompressionMode::Dictionary` on both
+    /// the client and the server in a later build.
+    pub fn take_trained_dictionary(&mut self) -> Option<Vec<u8>> {
+        self.io.take_trained_dictionary()
+    }
+
+    /// Fraction of the `CompressionMode::Training` sample target recorded so
+    /// far, from `0.0` to `1.0`. `None` when not training.
+    pub fn dictionary_training_progress(&self) -> Option<f32> {
+        self.io.dictionary_training_progress()
+    }
+
+    // Demo recording ────────────────────────────────────────────────────────
+
+    /// Starts recording everything received from the server: entity spawns
+    /// and despawns, component inserts, updates and removes, messages, and
+    /// the server tick each arrived on.
+    ///
+    /// The world is diffed against the previous recording at the end of
+    /// every [`process_all_packets`](Client::process_all_packets), so the
+    /// demo holds one frame per server tick. Does nothing if already
+    /// recording.
+    pub fn start_demo_recording(&mut self) {
+        if self.demo_recorder.is_none() {
+            self.demo_recorder = Some(DemoRecorder::new(
+                self.protocol_id,
+                self.protocol.tick_interval,
+            ));
+        }
+    }
+
+    /// Stops recording and returns the demo bytes, ready to be written to a
+    /// file and played back with [`DemoPlayer`](crate::DemoPlayer). Returns
+    /// `None` if no recording was in progress.
+    pub fn stop_demo_recording(&mut self) -> Option<Vec<u8>> {
+        self.demo_recorder.take().map(DemoRecorder::finish)
+    }
+
+    /// Returns `true` if a demo recording is in progress.
+    pub fn is_recording_demo(&self) -> bool {
+        self.demo_recorder.is_some()
+    }
+
+    fn record_demo_tick<W: WorldRefType<E>>(&mut self, world: &W) {
+        let Some(tick) = self.server_tick() else {
+            return;
+        };
+        let Some(recorder) = self.demo_recorder.as_mut() else {
+            return;
+        };
+        let entities = self
+            .global_world_manager
+            .entities()
+            .into_iter()
+            .filter(|global_entity| {
+                self.global_world_manager.entity_owner(global_entity) == Some(EntityOwner::Server)
+            })
+            .filter_map(|global_entity| {
+                let world_entity = self
+                    .global_entity_map
+                    .global_entity_to_entity(&global_entity)
+                    .ok()?;
+                let kinds = self
+                    .global_world_manager
+                    .component_kinds(&global_entity)
+                    .unwrap_or_default();
+                Some((global_entity, world_entity, kinds))
+            })
+            .collect();
+        recorder.record_tick(
+            &self.protocol.component_kinds,
+            &self.protocol.message_kinds,
+            &self.protocol.channel_kinds,
+            tick,
+            world,
+            entities,
+        );
+    }
+
     // Crate-Public methods
 
     /// Despawns the Entity, if it exists.
@@ -1601,6 +1827,16 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
             .entity_authority_status(global_entity)
             == Some(EntityAuthStatus::Granted);
 
+        // The server can't answer a pending group member once it's no longer
+        // delegated, so losing delegation is its answer
+        if self
+            .global_world_manager
+            .entity_authority_status(global_entity)
+            == Some(EntityAuthStatus::Requested)
+        {
+            self.resolve_authority_group_member(global_entity, false);
+        }
+
         // Clear delegation + authority semantics
         self.global_world_manager
             .entity_disable_delegation(global_entity);
@@ -1673,8 +1909,34 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
         //     old_auth_status, new_auth_status
         // );
 
+        // Members of a group request are reported once the whole group is answered
+        if old_auth_status == EntityAuthStatus::Requested
+            && new_auth_status != EntityAuthStatus::Requested
+            && self.resolve_authority_group_member(
+                global_entity,
+                new_auth_status == EntityAuthStatus::Granted,
+            )
+        {
+            return;
+        }
+
+        // A queued request is only pending while Requested
+        let was_queued = new_auth_status != EntityAuthStatus::Requested
+            && self.queued_authority_requests.remove(global_entity);
+
         // Updated Host Manager
         match (old_auth_status, new_auth_status) {
+            // A queued request reached the front of the line
+            (EntityAuthStatus::Requested, EntityAuthStatus::Granted) if was_queued => {
+                self.server_connection
+                    .as_mut()
+                    .unwrap()
+                    .base
+                    .world_manager
+                    .register_authed_entity(&self.global_world_manager, global_entity);
+                self.incoming_world_events
+                    .push_auth_queue_grant(*world_entity);
+            }
             // Grant authority (from any state)
             (EntityAuthStatus::Requested, EntityAuthStatus::Granted)
             | (EntityAuthStatus::Denied, EntityAuthStatus::Granted)
@@ -1736,6 +1998,17 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
                 self.global_world_manager
                     .entity_update_authority(global_entity, EntityAuthStatus::Available);
             }
+            // The server queued our request (acknowledged as Requested)
+            (EntityAuthStatus::Requested, EntityAuthStatus::Requested)
+            | (EntityAuthStatus::Denied, EntityAuthStatus::Requested) => {
+                self.queued_authority_requests.insert(*global_entity);
+            }
+            (EntityAuthStatus::Releasing, EntityAuthStatus::Requested) => {
+                // Queued before our release arrived; the server will follow
+                // up with Denied once it processes the release
+                self.global_world_manager
+                    .entity_update_authority(global_entity, EntityAuthStatus::Releasing);
+            }
             // Available → Denied. Fires when another client (or the server)
             // takes authority for an entity that this client had been free to
             // request. Per contract `entity-delegation-15`: every transition
@@ -1752,7 +2025,6 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
             (EntityAuthStatus::Available, EntityAuthStatus::Available)
             | (EntityAuthStatus::Denied, EntityAuthStatus::Denied)
             | (EntityAuthStatus::Granted, EntityAuthStatus::Granted)
-            | (EntityAuthStatus::Requested, EntityAuthStatus::Requested)
             | (EntityAuthStatus::Releasing, EntityAuthStatus::Releasing) => {
                 // Idempotent — same-state transitions are no-ops. The grant/take/release
                 // side effects (register, deregister, push_auth_grant, push_auth_reset)
@@ -1771,6 +2043,42 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
         }
     }
 
+    /// Records the server's answer for a member of a pending group request,
+    /// firing the group event once every member is answered. Returns `false`
+    /// if the entity isn't part of a pending group.
+    fn resolve_authority_group_member(
+        &mut self,
+        global_entity: &GlobalEntity,
+        granted: bool,
+    ) -> bool {
+        let Some(progress) = self.authority_groups.resolve(global_entity, granted) else {
+            return false;
+        };
+        self.queued_authority_requests.remove(global_entity);
+        if granted {
+            self.server_connection
+                .as_mut()
+                .unwrap()
+                .base
+                .world_manager
+                .register_authed_entity(&self.global_world_manager, global_entity);
+        }
+        if let GroupProgress::Complete { entities, granted } = progress {
+            let world_entities = entities
+                .iter()
+                .filter_map(|entity| self.global_entity_map.global_entity_to_entity(entity).ok())
+                .collect();
+            if granted {
+                self.incoming_world_events
+                    .push_auth_group_grant(world_entities);
+            } else {
+                self.incoming_world_events
+                    .push_auth_group_deny(world_entities);
+            }
+        }
+        true
+    }
+
     // Private methods
 
     fn check_client_authoritative_allowed(&self) {
@@ -1846,8 +2154,9 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
             match self.io.recv_reader() {
                 Ok(Some(mut reader)) => {
                     match self.handshake_manager.recv(&mut reader) {
-                        Some(HandshakeResult::Connected(time_manager)) => {
+                        Some(HandshakeResult::Connected(time_manager, server_compression)) => {
                             // new connect!
+                            self.io.negotiate_codec(&server_compression);
                             self.server_connection = Some(Connection::new(
                                 &self.client_config.connection,
                                 &self.protocol.channel_kinds,
@@ -2096,6 +2405,7 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
             self.client_config.send_handshake_interval,
             self.client_config.ping_interval,
             self.client_config.handshake_pings,
+            Io::compression_capabilities(&self.protocol.compression),
         ));
 
         self.manual_disconnect = false;
@@ -2219,7 +2529,7 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
                         CLIENT_SCOPE_APPLIED_REMOVE_E1.fetch_add(1, Ordering::Relaxed);
                     }
                 }
-                EntityEvent::InsertComponent(global_entity, component_kind) => {
+                EntityEvent::InsertComponent(tick, global_entity, component_kind) => {
                     let world_entity = self
                         .global_entity_map
                         .global_entity_to_entity(&global_entity)
@@ -2234,7 +2544,7 @@ impl<E: Copy + Eq + Hash + Send + Sync> Client<E> {
                         let _ = self.resource_registry.insert_raw(type_id, global_entity);
                     }
                     self.incoming_world_events
-                        .push_insert(world_entity, component_kind);
+                        .push_insert(tick, world_entity, component_kind);
 
                     if !self
                         .global_world_manager
@@ -44,15 +44,21 @@ impl<T: Clone> CommandHistory<T> {
         self.buffer.push_back((command_tick, new_command));
     }
 
-    fn remove_to_and_including(&mut self, index: Tick) {
-        loop {
-            let back_index = match self.buffer.front() {
-                Some((index, _)) => *index,
-                None => {
-                    return;
-                }
-            };
-            if sequence_greater_than(back_index, index) {
+    /// Drops every command at or before `index`.
+    pub(crate) fn remove_to_and_including(&mut self, index: Tick) {
+        let Some((newest, _)) = self.buffer.back() else {
+            return;
+        };
+        let newest = *newest;
+        if !sequence_greater_than(newest, index) {
+            self.buffer.clear();
+            return;
+        }
+        // Measure back from the newest command, so a history spanning more
+        // than half the tick range still compares correctly
+        let index_age = newest.wrapping_sub(index);
+        while let Some((tick, _)) = self.buffer.front() {
+            if newest.wrapping_sub(*tick) < index_age {
                 return;
             }
             self.buffer.pop_front();
@@ -74,3 +80,44 @@ impl<T: Clone> CommandHistory<T> {
         self.buffer.back().map(|(tick, _)| *tick)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn history(ticks: impl IntoIterator<Item = Tick>) -> CommandHistory<()> {
+        let mut history = CommandHistory::default();
+        for tick in ticks {
+            history.insert(tick, ());
+        }
+        history
+    }
+
+    fn ticks(history: &mut CommandHistory<()>, start_tick: Tick) -> Vec<Tick> {
+        history
+            .replays(&start_tick)
+            .into_iter()
+            .map(|(tick, _)| tick)
+            .collect()
+    }
+
+    #[test]
+    fn replays_across_wraparound() {
+        let mut history = history([65533, 65534, 65535, 0, 1]);
+        assert_eq!(ticks(&mut history, 65535), vec![0, 1]);
+        assert_eq!(ticks(&mut history, 0), vec![1]);
+    }
+
+    #[test]
+    fn prunes_history_longer_than_half_the_tick_range() {
+        let mut history = history((0..40_000).step_by(1000).chain([40_000]));
+        assert_eq!(ticks(&mut history, 39_999), vec![40_000]);
+    }
+
+    #[test]
+    fn start_tick_past_newest_clears_history() {
+        let mut history = history([10, 11]);
+        assert!(ticks(&mut history, 12).is_empty());
+        assert!(history.most_recent_tick().is_none());
+    }
+}
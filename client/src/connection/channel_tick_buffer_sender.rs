@@ -4,8 +4,9 @@ use log::warn;
 
 use naia_shared::{
     sequence_greater_than, sequence_less_than, wrapping_diff, BitWrite, BitWriter,
-    LocalEntityAndGlobalEntityConverterMut, MessageContainer, MessageKinds, Serde,
-    ShortMessageIndex, Tick, TickBufferSettings, UnsignedVariableInteger,
+    ChannelCounters, ChannelStats, LocalEntityAndGlobalEntityConverterMut, MessageContainer,
+    MessageKinds, SenderStats, Serde, ShortMessageIndex, Tick, TickBufferSettings,
+    UnsignedVariableInteger,
 };
 
 pub struct ChannelTickBufferSender {
@@ -13,6 +14,8 @@ pub struct ChannelTickBufferSender {
     outgoing_messages: VecDeque<(Tick, Vec<(ShortMessageIndex, MessageContainer)>)>,
     last_sent: Tick,
     never_sent: bool,
+    message_capacity: usize,
+    counters: ChannelCounters,
 }
 
 impl ChannelTickBufferSender {
@@ -22,14 +25,18 @@ impl ChannelTickBufferSender {
             outgoing_messages: VecDeque::new(),
             last_sent: 0,
             never_sent: true,
+            message_capacity: settings.message_capacity,
+            counters: ChannelCounters::new(),
         }
     }
 
     pub fn collect_messages(&mut self, client_sending_tick: &Tick, server_receivable_tick: &Tick) {
         if sequence_greater_than(*client_sending_tick, self.last_sent) || self.never_sent {
             // Remove messages that would never be able to reach the Server
-            self.sending_messages
+            let expired = self
+                .sending_messages
                 .pop_back_until_excluding(server_receivable_tick);
+            self.counters.record_dropped_expired(expired);
 
             self.last_sent = *client_sending_tick;
             self.never_sent = true;
@@ -48,7 +55,10 @@ impl ChannelTickBufferSender {
     }
 
     pub fn send_message(&mut self, host_tick: &Tick, message: MessageContainer) {
-        self.sending_messages.push(*host_tick, message);
+        if let Some(evicted) = self.sending_messages.push(*host_tick, message) {
+            self.counters.record_sent(1);
+            self.counters.record_dropped_backpressure(evicted);
+        }
     }
 
     pub fn has_messages(&self) -> bool {
@@ -168,6 +178,18 @@ impl ChannelTickBufferSender {
         self.sending_messages.remove_message(tick, message_index);
     }
 
+    pub fn record_bits_sent(&mut self, bits: u32) {
+        self.counters.record_bits_sent(bits);
+    }
+
+    pub fn channel_stats(&self) -> ChannelStats {
+        let sender = SenderStats {
+            queue_depth: self.sending_messages.pending(),
+            ..Default::default()
+        };
+        self.counters.stats(Some(sender), Some(self.message_capacity))
+    }
+
     fn warn_overflow(
         &self,
         messages: &Vec<(ShortMessageIndex, MessageContainer)>,
@@ -223,6 +245,11 @@ impl MessageMap {
     pub fn len(&self) -> usize {
         self.list.len()
     }
+
+    /// Messages not yet delivered
+    pub fn pending(&self) -> usize {
+        self.list.iter().flatten().count()
+    }
 }
 
 // OutgoingMessages
@@ -243,20 +270,22 @@ impl OutgoingMessages {
         }
     }
 
-    // should only push increasing ticks of messages
-    pub fn push(&mut self, message_tick: Tick, message: MessageContainer) {
+    // should only push increasing ticks of messages. Returns how many
+    // undelivered messages were pruned to make room, or `None` if the message
+    // was rejected
+    pub fn push(&mut self, message_tick: Tick, message: MessageContainer) -> Option<usize> {
         if let Some((front_tick, msg_map)) = self.buffer.front_mut() {
             if message_tick == *front_tick {
                 // been here before, cool
                 msg_map.insert(message);
-                return;
+                return Some(0);
             }
 
             if sequence_less_than(message_tick, *front_tick) {
                 warn!("This method should always receive increasing or equal Ticks! \
                 Received Tick: {message_tick} after receiving {front_tick}. \
                 Possibly try ensuring that Client.send_message() is only called on this channel once per Tick?");
-                return;
+                return None;
             }
         } else {
             // nothing is in here
@@ -267,25 +296,38 @@ impl OutgoingMessages {
         self.buffer.push_front((message_tick, msg_map));
 
         // a good time to prune down this list
+        let mut pruned = 0;
         while self.buffer.len() > self.capacity {
-            self.buffer.pop_back();
+            if let Some((_, msg_map)) = self.buffer.pop_back() {
+                pruned += msg_map.pending();
+            }
         }
+        Some(pruned)
     }
 
-    pub fn pop_back_until_excluding(&mut self, until_tick: &Tick) {
+    // Returns how many undelivered messages were removed
+    pub fn pop_back_until_excluding(&mut self, until_tick: &Tick) -> usize {
+        let mut removed = 0;
         loop {
             if let Some((old_tick, _)) = self.buffer.back() {
                 if sequence_less_than(*until_tick, *old_tick) {
-                    return;
+                    return removed;
                 }
             } else {
-                return;
+                return removed;
             }
 
-            self.buffer.pop_back();
+            if let Some((_, msg_map)) = self.buffer.pop_back() {
+                removed += msg_map.pending();
+            }
         }
     }
 
+    /// Messages not yet delivered, across all ticks
+    pub fn pending(&self) -> usize {
+        self.buffer.iter().map(|(_, msg_map)| msg_map.pending()).sum()
+    }
+
     pub fn remove_message(&mut self, tick: &Tick, message_index: &ShortMessageIndex) {
         let mut index = self.buffer.len();
 
@@ -1,12 +1,14 @@
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
 use std::hash::Hash;
 
 use log::{debug, warn};
 
 use naia_shared::{
-    BaseConnection, BitReader, BitWriter, ChannelKinds, ComponentKind, ComponentKinds,
-    ConnectionConfig, EntityAndGlobalEntityConverter, EntityCommand, EntityEvent, GlobalEntity,
-    GlobalEntitySpawner, HostType, Instant, MessageIndex, MessageKinds, PacketType, Protocol,
+    BaseConnection, BitReader, BitWriter, ChannelKinds, ChannelStats, ComponentCorrection, ComponentKind,
+    ComponentKinds, ConnectionConfig, DemoRecorder, EntityAndGlobalEntityConverter, EntityCommand,
+    EntityEvent,
+    GlobalEntity, GlobalEntitySpawner, HostType, Instant, MessageContainer, MessageIndex,
+    MessageKind, MessageKinds, PacketType, Protocol,
     Serde, SerdeErr, StandardHeader, Tick, Timer, WorldMutType, WorldRefType, MTU_SIZE_BYTES,
 };
 
@@ -152,6 +154,7 @@ impl Connection {
     }
 
     /// Receive & process messages / entity actions / entity updates and emit events for them
+    #[allow(clippy::too_many_arguments)]
     pub fn process_packets<E: Copy + Eq + Hash + Send + Sync, W: WorldMutType<E>>(
         &mut self,
         global_entity_map: &mut dyn GlobalEntitySpawner<E>,
@@ -160,6 +163,7 @@ impl Connection {
         world: &mut W,
         now: &Instant,
         incoming_events: &mut Events<E>,
+        mut demo_recorder: Option<&mut DemoRecorder>,
     ) -> Vec<EntityEvent> {
         // Receive Message Events
         let (entity_converter, entity_waitlist) =
@@ -170,8 +174,22 @@ impl Connection {
             entity_converter,
             entity_waitlist,
         );
+        let correction_kind = MessageKind::of::<ComponentCorrection>();
         for (channel_kind, messages) in messages {
             for message in messages {
+                if message.kind() == correction_kind {
+                    self.apply_component_correction(
+                        global_entity_map,
+                        global_world_manager,
+                        protocol,
+                        world,
+                        message,
+                    );
+                    continue;
+                }
+                if let Some(recorder) = demo_recorder.as_deref_mut() {
+                    recorder.record_message(&channel_kind, message.clone());
+                }
                 incoming_events.push_message(&channel_kind, message);
             }
         }
@@ -199,10 +217,51 @@ impl Connection {
             global_world_manager,
             &protocol.component_kinds,
             world,
+            None,
             now,
         )
     }
 
+    /// Overwrite a component with the server's value after the server
+    /// rejected or clamped an update this client sent for it
+    fn apply_component_correction<E: Copy + Eq + Hash + Send + Sync, W: WorldMutType<E>>(
+        &self,
+        global_entity_map: &dyn GlobalEntitySpawner<E>,
+        global_world_manager: &GlobalWorldManager,
+        protocol: &Protocol,
+        world: &mut W,
+        message: MessageContainer,
+    ) {
+        let Ok(correction) = message.to_boxed_any().downcast::<ComponentCorrection>() else {
+            return;
+        };
+        let Some(global_entity) = correction.entity.get_inner() else {
+            return;
+        };
+        let Ok(world_entity) = global_entity_map.global_entity_to_entity(&global_entity) else {
+            return;
+        };
+        // A delegated entity's components can only be written while this client holds authority
+        if global_world_manager.entity_is_delegated(&global_entity)
+            && !global_world_manager.entity_can_mutate(&global_entity)
+        {
+            return;
+        }
+        let component = match correction.read_component(
+            &protocol.component_kinds,
+            self.base.world_manager.entity_converter(),
+        ) {
+            Ok(component) => component,
+            Err(_) => {
+                warn!("Failed to read component correction from server");
+                return;
+            }
+        };
+        if let Some(mut current) = world.component_mut_of_kind(&world_entity, &component.kind()) {
+            current.mirror(component.as_ref());
+        }
+    }
+
     // Outgoing data
 
     /// Collect and send any outgoing packets from client to server
@@ -227,7 +286,13 @@ impl Connection {
             .take_outgoing_events(now, &rtt_millis, world, converter, global_world_manager);
 
         // Phase A: tick the outbound token-bucket bandwidth accumulator
-        // before the send cycle. Refreshes budget + one-packet overshoot.
+        // before the send cycle. Refreshes budget + one-packet overshoot,
+        // after letting the congestion controller (if enabled) set the rate.
+        self.base.update_congestion_control(
+            now,
+            self.time_manager.rtt(),
+            self.time_manager.jitter(),
+        );
         self.base.accumulate_bandwidth(now);
 
         let mut any_sent = false;
@@ -293,6 +358,8 @@ impl Connection {
                 update_events,
             );
 
+            self.base.profile_packet(&writer);
+
             // send packet, measuring actual size before the move so we can
             // spend exactly what went on the wire.
             let packet = writer.to_packet();
@@ -380,4 +447,11 @@ impl Connection {
     pub fn process_received_commands(&mut self) {
         self.base.world_manager.process_delivered_commands();
     }
+
+    /// Returns the traffic of every channel, by channel protocol name
+    pub fn channel_stats(&self, channel_kinds: &ChannelKinds) -> BTreeMap<String, ChannelStats> {
+        let mut stats = self.base.message_manager.channel_stats(channel_kinds);
+        stats.extend(self.tick_buffer.channel_stats(channel_kinds));
+        stats
+    }
 }
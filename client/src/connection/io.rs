@@ -1,7 +1,9 @@
 use std::{net::SocketAddr, time::Duration};
 
 use naia_shared::{
-    BandwidthMonitor, BitReader, CompressionConfig, Decoder, Encoder, OutgoingPacket,
+    record_packet_received, record_packet_sent, BandwidthMonitor, BitReader,
+    CompressionCapabilities, CompressionConfig, CompressionMode, Decoder, Encoder, HostType,
+    OutgoingPacket, PacketCodec,
 };
 
 use crate::{
@@ -20,6 +22,9 @@ pub struct Io {
     incoming_bandwidth_monitor: Option<BandwidthMonitor>,
     outgoing_encoder: Option<Encoder>,
     incoming_decoder: Option<Decoder>,
+    outgoing_compression: Option<CompressionMode>,
+    /// Client-to-server codec, as negotiated in the handshake
+    codec: PacketCodec,
 }
 
 impl Io {
@@ -30,12 +35,10 @@ impl Io {
         let outgoing_bandwidth_monitor = bandwidth_measure_duration.map(BandwidthMonitor::new);
         let incoming_bandwidth_monitor = bandwidth_measure_duration.map(BandwidthMonitor::new);
 
-        let outgoing_encoder = compression_config.as_ref().and_then(|config| {
-            config
-                .client_to_server
-                .as_ref()
-                .map(|mode| Encoder::new(mode.clone()))
-        });
+        let outgoing_compression = compression_config
+            .as_ref()
+            .and_then(|config| config.client_to_server.clone());
+        let outgoing_encoder = outgoing_compression.clone().map(Encoder::new);
         let incoming_decoder = compression_config.as_ref().and_then(|config| {
             config
                 .server_to_client
@@ -52,6 +55,8 @@ impl Io {
             incoming_bandwidth_monitor,
             outgoing_encoder,
             incoming_decoder,
+            outgoing_compression,
+            codec: PacketCodec::default(),
         }
     }
 
@@ -93,19 +98,57 @@ impl Io {
         id_result
     }
 
+    /// What this client's decoder can read, reported to the server during
+    /// the handshake
+    pub fn compression_capabilities(
+        compression_config: &Option<CompressionConfig>,
+    ) -> CompressionCapabilities {
+        CompressionCapabilities::local(
+            compression_config
+                .as_ref()
+                .and_then(|config| config.server_to_client.as_ref()),
+        )
+    }
+
+    /// Picks the codec for outgoing packets from what the server reported
+    /// its decoder can read
+    pub fn negotiate_codec(&mut self, server: &CompressionCapabilities) {
+        if let Some(mode) = &self.outgoing_compression {
+            self.codec = mode.negotiate(server);
+        }
+    }
+
+    /// The codec outgoing packets are compressed with
+    pub fn codec(&self) -> PacketCodec {
+        self.codec
+    }
+
+    /// Takes the dictionary trained from outgoing packets in
+    /// `CompressionMode::Training`, once training has completed
+    pub fn take_trained_dictionary(&mut self) -> Option<Vec<u8>> {
+        self.outgoing_encoder.as_mut()?.take_trained_dictionary()
+    }
+
+    /// Fraction of the training sample target recorded so far, in
+    /// `CompressionMode::Training`
+    pub fn dictionary_training_progress(&self) -> Option<f32> {
+        self.outgoing_encoder.as_ref()?.training_progress()
+    }
+
     pub fn send_packet(&mut self, packet: OutgoingPacket) -> Result<(), NaiaClientError> {
         // get payload
         let mut payload = packet.slice();
 
         // Compression
         if let Some(encoder) = &mut self.outgoing_encoder {
-            payload = encoder.encode(payload);
+            payload = encoder.encode(payload, self.codec);
         }
 
         // Bandwidth monitoring
         if let Some(monitor) = &mut self.outgoing_bandwidth_monitor {
             monitor.record_packet(payload.len());
         }
+        record_packet_sent(HostType::Client, payload.len());
 
         self.packet_sender
             .as_mut()
@@ -126,6 +169,7 @@ impl Io {
             if let Some(monitor) = &mut self.incoming_bandwidth_monitor {
                 monitor.record_packet(payload.len());
             }
+            record_packet_received(HostType::Client, payload.len());
 
             // Decompression
             if let Some(decoder) = &mut self.incoming_decoder {
@@ -1,8 +1,8 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use naia_shared::{
-    BaseConnection, BitWriter, ChannelKind, ChannelKinds, ChannelMode, MessageContainer,
-    PacketIndex, PacketNotifiable, Protocol, Serde, ShortMessageIndex, Tick,
+    BaseConnection, BitWriter, ChannelKind, ChannelKinds, ChannelMode, ChannelStats,
+    MessageContainer, PacketIndex, PacketNotifiable, Protocol, Serde, ShortMessageIndex, Tick,
 };
 
 use crate::{
@@ -61,6 +61,21 @@ impl TickBufferSender {
         false
     }
 
+    /// Returns the traffic of every tick-buffered channel, by channel
+    /// protocol name.
+    pub fn channel_stats(&self, channel_kinds: &ChannelKinds) -> BTreeMap<String, ChannelStats> {
+        self.channel_senders
+            .iter()
+            .map(|(channel_kind, channel)| {
+                let name = channel_kinds
+                    .channel_name(channel_kind)
+                    .unwrap_or("unknown")
+                    .to_string();
+                (name, channel.channel_stats())
+            })
+            .collect()
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub fn write_messages(
         &mut self,
@@ -95,6 +110,8 @@ impl TickBufferSender {
                 break;
             }
 
+            let start_bits = writer.bits_written();
+
             // reserve MessageContinue bit
             writer.reserve_bits(1);
             // write ChannelContinue bit
@@ -119,6 +136,12 @@ impl TickBufferSender {
             // write MessageContinue finish bit, release
             writer.release_bits(1);
             false.ser(writer);
+
+            let channel_bits = writer.bits_written() - start_bits;
+            channel.record_bits_sent(channel_bits);
+            if let Some(profiler) = &mut connection.bandwidth_profiler {
+                profiler.record_channel(channel_kind, channel_bits);
+            }
         }
 
         // write ChannelContinue finish bit, release
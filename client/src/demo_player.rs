@@ -0,0 +1,391 @@
+//! Playback of recorded demos without a server.
+//!
+//! A demo is the replication stream one user received, recorded on the
+//! server or on the client with a [`DemoRecorder`]. [`DemoPlayer`] feeds it
+//! into a world frame by frame, as if it were arriving from a live server:
+//! entities are spawned, components inserted, updated and removed through
+//! [`WorldMutType`], and the same [`Events`] and [`TickEvents`] a [`Client`]
+//! produces are queued for the application to drain.
+//!
+//! ```no_run
+//! # use std::time::Duration;
+//! # use naia_client::{DemoPlayer, SpawnEntityEvent};
+//! # use naia_shared::{Protocol, WorldMutType};
+//! # fn example<W: WorldMutType<u32> + Copy>(protocol: Protocol, bytes: &[u8], world: W) {
+//! let mut player = DemoPlayer::<u32>::new(protocol, bytes).expect("demo was recorded with this protocol");
+//! player.set_speed(2.0);
+//!
+//! // Each frame, instead of the client loop:
+//! player.update(world, Duration::from_millis(16));
+//! let mut events = player.take_world_events();
+//! for entity in events.read::<SpawnEntityEvent>() {
+//!     // render it
+//! }
+//! let ticks = player.take_tick_events();
+//! # }
+//! ```
+//!
+//! [`DemoRecorder`]: naia_shared::DemoRecorder
+//! [`Client`]: crate::Client
+
+use std::{collections::HashMap, hash::Hash, time::Duration};
+
+use log::warn;
+
+use naia_shared::{
+    wrapping_diff, DemoError, DemoFile, DemoOp, EntityAndGlobalEntityConverter,
+    EntityDoesNotExistError, GlobalEntity, GlobalEntityMap, GlobalEntitySpawner, HostEntity,
+    LocalEntityAndGlobalEntityConverter, OwnedLocalEntity, Protocol, RemoteEntity, Tick,
+    WorldMutType,
+};
+
+use crate::{Events, TickEvents};
+
+/// Fastest play speed [`DemoPlayer::set_speed`] accepts.
+const MAX_SPEED: f32 = 64.0;
+
+/// Plays a recorded demo into a world, with pause, seek and play-speed
+/// control.
+///
+/// Relations between replayed entities resolve through the player, so pass
+/// it wherever a [`Client`](crate::Client) would be passed as the entity
+/// converter, e.g. `entity_property.get(&player)`.
+pub struct DemoPlayer<E: Copy + Eq + Hash + Send + Sync> {
+    protocol: Protocol,
+    demo: DemoFile,
+    /// Time of each frame, relative to the first
+    offsets: Vec<Duration>,
+    next_frame: usize,
+    position: Duration,
+    speed: f32,
+    paused: bool,
+    entities: HashMap<u16, E>,
+    global_entity_map: GlobalEntityMap<E>,
+    incoming_world_events: Events<E>,
+    incoming_tick_events: TickEvents,
+}
+
+impl<E: Copy + Eq + Hash + Send + Sync> DemoPlayer<E> {
+    /// Loads a demo recorded under `protocol`. Playback starts unpaused at
+    /// the beginning, at normal speed.
+    pub fn new<P: Into<Protocol>>(protocol: P, bytes: &[u8]) -> Result<Self, DemoError> {
+        let mut protocol: Protocol = protocol.into();
+        protocol.lock();
+        let demo = DemoFile::read(bytes, protocol.protocol_id())?;
+
+        let mut offsets = Vec::new();
+        let mut previous: Option<(Tick, Duration)> = None;
+        for frame in demo.frames() {
+            let offset = match previous {
+                None => Duration::ZERO,
+                Some((tick, offset)) => {
+                    let ticks = wrapping_diff(tick, frame.tick()).max(0) as u32;
+                    offset + demo.tick_interval() * ticks
+                }
+            };
+            offsets.push(offset);
+            previous = Some((frame.tick(), offset));
+        }
+
+        Ok(Self {
+            protocol,
+            demo,
+            offsets,
+            next_frame: 0,
+            position: Duration::ZERO,
+            speed: 1.0,
+            paused: false,
+            entities: HashMap::new(),
+            global_entity_map: GlobalEntityMap::new(),
+            incoming_world_events: Events::new(),
+            incoming_tick_events: TickEvents::new(),
+        })
+    }
+
+    /// Advances playback by `elapsed` (scaled by the play speed) and applies
+    /// every frame that falls due to `world`. Does nothing while paused.
+    pub fn update<W: WorldMutType<E>>(&mut self, mut world: W, elapsed: Duration) {
+        if self.paused {
+            return;
+        }
+        let advance = Duration::try_from_secs_f64(elapsed.as_secs_f64() * f64::from(self.speed))
+            .unwrap_or(Duration::MAX);
+        self.position = self.position.saturating_add(advance).min(self.duration());
+        self.apply_due_frames(&mut world, true);
+    }
+
+    /// Jumps to `position` from the start of the demo.
+    ///
+    /// Every replayed entity is despawned, the demo is replayed silently up
+    /// to `position`, and the entities alive at that point are reported as
+    /// fresh spawns and inserts. Messages and ticks of the skipped frames
+    /// are not reported.
+    pub fn seek<W: WorldMutType<E>>(&mut self, mut world: W, position: Duration) {
+        let mut ids: Vec<u16> = self.entities.keys().copied().collect();
+        ids.sort();
+        for id in ids {
+            let world_entity = self.entities.remove(&id).unwrap();
+            self.incoming_world_events.push_despawn(world_entity);
+            self.global_entity_map.despawn_by_world(&world_entity);
+            world.despawn_entity(&world_entity);
+        }
+
+        self.next_frame = 0;
+        self.position = position.min(self.duration());
+        self.apply_due_frames(&mut world, false);
+
+        let tick = self.server_tick().unwrap_or_default();
+        let mut ids: Vec<u16> = self.entities.keys().copied().collect();
+        ids.sort();
+        for id in ids {
+            let world_entity = self.entities[&id];
+            self.incoming_world_events.push_spawn(world_entity);
+            for component_kind in world.component_kinds(&world_entity) {
+                self.incoming_world_events
+                    .push_insert(tick, world_entity, component_kind);
+            }
+        }
+    }
+
+    /// Resumes playback.
+    pub fn play(&mut self) {
+        self.paused = false;
+    }
+
+    /// Pauses playback; [`update`](Self::update) leaves the world untouched
+    /// until [`play`](Self::play) is called.
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    /// Returns `true` if playback is paused.
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Sets the play speed: `1.0` is real time, `0.5` half speed, `2.0`
+    /// double speed. Speeds are clamped to `0.0..=64.0`; a NaN or infinite
+    /// speed is ignored.
+    pub fn set_speed(&mut self, speed: f32) {
+        if !speed.is_finite() {
+            return;
+        }
+        self.speed = speed.clamp(0.0, MAX_SPEED);
+    }
+
+    /// Returns the play speed.
+    pub fn speed(&self) -> f32 {
+        self.speed
+    }
+
+    /// Returns the current playback position, from the start of the demo.
+    pub fn position(&self) -> Duration {
+        self.position
+    }
+
+    /// Returns the length of the demo, from its first frame to its last.
+    pub fn duration(&self) -> Duration {
+        self.offsets.last().copied().unwrap_or_default()
+    }
+
+    /// Returns `true` once every frame has been applied.
+    pub fn is_finished(&self) -> bool {
+        self.next_frame >= self.offsets.len()
+    }
+
+    /// Returns the server tick of the most recently applied frame.
+    pub fn server_tick(&self) -> Option<Tick> {
+        self.next_frame
+            .checked_sub(1)
+            .map(|index| self.demo.frames()[index].tick())
+    }
+
+    /// Returns the server's tick interval while the demo was recorded.
+    pub fn tick_duration(&self) -> Duration {
+        self.demo.tick_interval()
+    }
+
+    /// Drains and returns the world events produced since the last call,
+    /// as [`Client::take_world_events`](crate::Client::take_world_events)
+    /// would.
+    pub fn take_world_events(&mut self) -> Events<E> {
+        std::mem::take(&mut self.incoming_world_events)
+    }
+
+    /// Drains and returns a server tick event for each frame applied since
+    /// the last call.
+    pub fn take_tick_events(&mut self) -> TickEvents {
+        std::mem::take(&mut self.incoming_tick_events)
+    }
+
+    fn apply_due_frames<W: WorldMutType<E>>(&mut self, world: &mut W, report: bool) {
+        let mut skipped_world_events = Events::new();
+        let mut skipped_tick_events = TickEvents::new();
+        while self.next_frame < self.offsets.len() && self.offsets[self.next_frame] <= self.position
+        {
+            let index = self.next_frame;
+            self.next_frame += 1;
+            let Self {
+                protocol,
+                demo,
+                entities,
+                global_entity_map,
+                incoming_world_events,
+                incoming_tick_events,
+                ..
+            } = self;
+            let (world_events, tick_events) = if report {
+                (incoming_world_events, incoming_tick_events)
+            } else {
+                (&mut skipped_world_events, &mut skipped_tick_events)
+            };
+            let frame = &demo.frames()[index];
+            let tick = frame.tick();
+            let mut reader = frame.reader();
+            loop {
+                let converter = PlaybackConverter {
+                    entities,
+                    global_entity_map,
+                };
+                let op = match reader.next_op(protocol, &converter) {
+                    Ok(Some(op)) => op,
+                    Ok(None) => break,
+                    Err(_) => {
+                        warn!("Malformed demo frame at tick {}, skipping the rest of it", tick);
+                        break;
+                    }
+                };
+                match op {
+                    DemoOp::Spawn(id) => {
+                        let world_entity = world.spawn_entity();
+                        global_entity_map.spawn(world_entity, None);
+                        entities.insert(id, world_entity);
+                        world_events.push_spawn(world_entity);
+                    }
+                    DemoOp::Despawn(id) => {
+                        let Some(world_entity) = entities.remove(&id) else {
+                            continue;
+                        };
+                        world_events.push_despawn(world_entity);
+                        global_entity_map.despawn_by_world(&world_entity);
+                        world.despawn_entity(&world_entity);
+                    }
+                    DemoOp::Insert(id, component) => {
+                        let Some(world_entity) = entities.get(&id).copied() else {
+                            continue;
+                        };
+                        let component_kind = component.kind();
+                        world.insert_boxed_component(&world_entity, component);
+                        world_events.push_insert(tick, world_entity, component_kind);
+                    }
+                    DemoOp::Update(id, update) => {
+                        let Some(world_entity) = entities.get(&id).copied() else {
+                            continue;
+                        };
+                        let component_kind = update.kind;
+                        let converter = PlaybackConverter {
+                            entities,
+                            global_entity_map,
+                        };
+                        if world
+                            .component_apply_update(&converter, &world_entity, &component_kind, update)
+                            .is_err()
+                        {
+                            warn!("Could not apply demo update at tick {}", tick);
+                            continue;
+                        }
+                        world_events.push_update(tick, world_entity, component_kind);
+                    }
+                    DemoOp::Remove(id, component_kind) => {
+                        let Some(world_entity) = entities.get(&id).copied() else {
+                            continue;
+                        };
+                        if let Some(component) =
+                            world.remove_component_of_kind(&world_entity, &component_kind)
+                        {
+                            world_events.push_remove(world_entity, component);
+                        }
+                    }
+                    DemoOp::Message(channel_kind, message) => {
+                        world_events.push_message(&channel_kind, message);
+                    }
+                }
+            }
+            tick_events.push_server_tick(tick);
+        }
+    }
+}
+
+impl<E: Copy + Eq + Hash + Send + Sync> EntityAndGlobalEntityConverter<E> for DemoPlayer<E> {
+    fn global_entity_to_entity(
+        &self,
+        global_entity: &GlobalEntity,
+    ) -> Result<E, EntityDoesNotExistError> {
+        self.global_entity_map.global_entity_to_entity(global_entity)
+    }
+
+    fn entity_to_global_entity(&self, entity: &E) -> Result<GlobalEntity, EntityDoesNotExistError> {
+        self.global_entity_map.entity_to_global_entity(entity)
+    }
+}
+
+/// Resolves the recorded entity ids in a demo frame, which arrive as
+/// [`RemoteEntity`]s, to the entities the player spawned for them.
+struct PlaybackConverter<'a, E: Copy + Eq + Hash + Send + Sync> {
+    entities: &'a HashMap<u16, E>,
+    global_entity_map: &'a GlobalEntityMap<E>,
+}
+
+impl<E: Copy + Eq + Hash + Send + Sync> LocalEntityAndGlobalEntityConverter
+    for PlaybackConverter<'_, E>
+{
+    fn global_entity_to_host_entity(
+        &self,
+        _: &GlobalEntity,
+    ) -> Result<HostEntity, EntityDoesNotExistError> {
+        Err(EntityDoesNotExistError)
+    }
+
+    fn global_entity_to_remote_entity(
+        &self,
+        _: &GlobalEntity,
+    ) -> Result<RemoteEntity, EntityDoesNotExistError> {
+        Err(EntityDoesNotExistError)
+    }
+
+    fn global_entity_to_owned_entity(
+        &self,
+        _: &GlobalEntity,
+    ) -> Result<OwnedLocalEntity, EntityDoesNotExistError> {
+        Err(EntityDoesNotExistError)
+    }
+
+    fn host_entity_to_global_entity(
+        &self,
+        _: &HostEntity,
+    ) -> Result<GlobalEntity, EntityDoesNotExistError> {
+        Err(EntityDoesNotExistError)
+    }
+
+    fn static_host_entity_to_global_entity(
+        &self,
+        _: &HostEntity,
+    ) -> Result<GlobalEntity, EntityDoesNotExistError> {
+        Err(EntityDoesNotExistError)
+    }
+
+    fn remote_entity_to_global_entity(
+        &self,
+        remote_entity: &RemoteEntity,
+    ) -> Result<GlobalEntity, EntityDoesNotExistError> {
+        let world_entity = self
+            .entities
+            .get(&remote_entity.value())
+            .ok_or(EntityDoesNotExistError)?;
+        self.global_entity_map.entity_to_global_entity(world_entity)
+    }
+
+    fn apply_entity_redirect(&self, entity: &OwnedLocalEntity) -> OwnedLocalEntity {
+        *entity
+    }
+}
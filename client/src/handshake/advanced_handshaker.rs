@@ -3,8 +3,8 @@ use std::time::Duration;
 use log::warn;
 
 use naia_shared::{
-    handshake::HandshakeHeader, BitReader, BitWriter, IdentityToken, OutgoingPacket, PacketType,
-    ProtocolId, Serde, StandardHeader, Timer, Timestamp as stamp_time,
+    handshake::HandshakeHeader, BitReader, BitWriter, CompressionCapabilities, IdentityToken,
+    OutgoingPacket, PacketType, ProtocolId, Serde, StandardHeader, Timer, Timestamp as stamp_time,
 };
 
 use crate::{
@@ -51,6 +51,8 @@ pub struct HandshakeManager {
     identity_token: Option<IdentityToken>,
     pre_connection_timestamp: Timestamp,
     pre_connection_digest: Option<Vec<u8>>,
+    compression_capabilities: CompressionCapabilities,
+    server_compression: CompressionCapabilities,
 }
 
 impl Handshaker for HandshakeManager {
@@ -179,6 +181,7 @@ impl HandshakeManager {
         send_interval: Duration,
         ping_interval: Duration,
         handshake_pings: u8,
+        compression_capabilities: CompressionCapabilities,
     ) -> Self {
         let mut handshake_timer = Timer::new(send_interval);
         handshake_timer.ring_manual();
@@ -194,6 +197,8 @@ impl HandshakeManager {
             connection_state: HandshakeState::AwaitingChallengeResponse,
             ping_interval,
             handshake_pings,
+            compression_capabilities,
+            server_compression: CompressionCapabilities::default(),
         }
     }
 
@@ -205,6 +210,7 @@ impl HandshakeManager {
 
         self.pre_connection_timestamp.ser(&mut writer);
         identity_token.ser(&mut writer);
+        self.compression_capabilities.ser(&mut writer);
 
         writer
     }
@@ -225,6 +231,7 @@ impl HandshakeManager {
                 }
                 let digest_bytes = digest_bytes_result.unwrap();
                 self.pre_connection_digest = Some(digest_bytes);
+                self.server_compression = CompressionCapabilities::de(reader).unwrap_or_default();
 
                 self.connection_state = HandshakeState::AwaitingValidateResponse;
             }
@@ -269,7 +276,10 @@ impl HandshakeManager {
             return None;
         };
 
-        return Some(HandshakeResult::Connected(Box::new(time_manager)));
+        return Some(HandshakeResult::Connected(
+            Box::new(time_manager),
+            self.server_compression,
+        ));
     }
 
     fn write_signed_timestamp(&self, writer: &mut BitWriter) {
@@ -1,6 +1,9 @@
 mod handshake_time_manager;
 
-use naia_shared::{handshake::RejectReason, BitReader, BitWriter, IdentityToken, OutgoingPacket};
+use naia_shared::{
+    handshake::RejectReason, BitReader, BitWriter, CompressionCapabilities, IdentityToken,
+    OutgoingPacket,
+};
 
 use crate::connection::time_manager::TimeManager;
 
@@ -15,7 +18,8 @@ cfg_if! {
 }
 
 pub enum HandshakeResult {
-    Connected(Box<TimeManager>),
+    /// Carries the compression capabilities the server reported
+    Connected(Box<TimeManager>, CompressionCapabilities),
     Rejected(RejectReason),
 }
 
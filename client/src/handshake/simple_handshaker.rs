@@ -3,8 +3,8 @@ use std::time::Duration;
 use log::warn;
 
 use naia_shared::{
-    handshake::HandshakeHeader, BitReader, BitWriter, IdentityToken, OutgoingPacket, PacketType,
-    ProtocolId, Serde, StandardHeader, Timer,
+    handshake::HandshakeHeader, BitReader, BitWriter, CompressionCapabilities, IdentityToken,
+    OutgoingPacket, PacketType, ProtocolId, Serde, StandardHeader, Timer,
 };
 
 use crate::{
@@ -45,6 +45,8 @@ pub struct HandshakeManager {
     identity_token: Option<IdentityToken>,
     ping_interval: Duration,
     handshake_pings: u8,
+    compression_capabilities: CompressionCapabilities,
+    server_compression: CompressionCapabilities,
 }
 
 impl Handshaker for HandshakeManager {
@@ -168,6 +170,7 @@ impl HandshakeManager {
         send_interval: Duration,
         ping_interval: Duration,
         handshake_pings: u8,
+        compression_capabilities: CompressionCapabilities,
     ) -> Self {
         let mut handshake_timer = Timer::new(send_interval);
         handshake_timer.ring_manual();
@@ -179,6 +182,8 @@ impl HandshakeManager {
             connection_state: HandshakeState::AwaitingIdentifyResponse,
             ping_interval,
             handshake_pings,
+            compression_capabilities,
+            server_compression: CompressionCapabilities::default(),
         }
     }
 
@@ -189,13 +194,15 @@ impl HandshakeManager {
         HandshakeHeader::ClientIdentifyRequest(self.protocol_id).ser(&mut writer);
 
         identity_token.ser(&mut writer);
+        self.compression_capabilities.ser(&mut writer);
 
         writer
     }
 
     // Step 2 of Handshake
-    fn recv_identify_response(&mut self, _reader: &mut BitReader) {
+    fn recv_identify_response(&mut self, reader: &mut BitReader) {
         if self.connection_state == HandshakeState::AwaitingIdentifyResponse {
+            self.server_compression = CompressionCapabilities::de(reader).unwrap_or_default();
             self.connection_state = HandshakeState::TimeSync(HandshakeTimeManager::new(
                 self.ping_interval,
                 self.handshake_pings,
@@ -220,6 +227,9 @@ impl HandshakeManager {
             return None;
         };
 
-        Some(HandshakeResult::Connected(Box::new(time_manager)))
+        Some(HandshakeResult::Connected(
+            Box::new(time_manager),
+            self.server_compression,
+        ))
     }
 }
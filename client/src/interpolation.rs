@@ -0,0 +1,332 @@
+//! Snapshot interpolation for server-replicated components.
+//!
+//! The server sends component state at tick granularity while the client
+//! renders at frame granularity. [`SnapshotInterpolation`] keeps a short
+//! per-entity history of received values, keyed by server tick, and answers
+//! "what did this component look like at render time *t*?", where *t* trails
+//! the newest received tick by a configurable delay so there is almost always
+//! a later snapshot to blend towards.
+//!
+//! ```no_run
+//! # use naia_client::{Client, Interpolate, InterpolationConfig, SnapshotInterpolation};
+//! # use naia_shared::Tick;
+//! # #[derive(Clone)] struct Position { x: f32, y: f32 }
+//! # impl Interpolate for Position {
+//! #     fn interpolate(&self, next: &Self, fraction: f32) -> Self {
+//! #         Position { x: self.x.interpolate(&next.x, fraction), y: self.y.interpolate(&next.y, fraction) }
+//! #     }
+//! # }
+//! # fn example(client: &Client<u32>, entity: u32, tick: Tick, received: Position) {
+//! let mut positions = SnapshotInterpolation::<u32, Position>::new(InterpolationConfig::default());
+//!
+//! // On InsertComponentEvent / UpdateComponentEvent:
+//! positions.record(entity, tick, received);
+//!
+//! // Each render frame:
+//! if let Some(render_time) = client.render_time(positions.config().delay_ticks) {
+//!     let _drawn: Option<Position> = positions.sample(&entity, render_time);
+//! }
+//! # }
+//! ```
+
+use std::{collections::HashMap, collections::VecDeque, hash::Hash};
+
+use naia_shared::{wrapping_diff, Interpolate, Tick};
+
+/// Tuning for [`SnapshotInterpolation`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct InterpolationConfig {
+    /// How far behind the current server tick rendering happens, in ticks.
+    /// Larger values tolerate more packet loss at the cost of latency.
+    pub delay_ticks: f32,
+    /// How far past the newest snapshot a value may be extrapolated, in
+    /// ticks, before it is held. `0.0` disables extrapolation.
+    pub max_extrapolation_ticks: f32,
+    /// How many ticks of history to keep behind the newest snapshot of each
+    /// entity. Older snapshots are discarded.
+    pub history_ticks: u16,
+}
+
+impl Default for InterpolationConfig {
+    fn default() -> Self {
+        Self {
+            delay_ticks: 2.0,
+            max_extrapolation_ticks: 1.0,
+            history_ticks: 32,
+        }
+    }
+}
+
+/// A point on a tick timeline between two ticks: `tick + fraction`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RenderTime {
+    /// The whole tick at or before the render point.
+    pub tick: Tick,
+    /// Progress towards the following tick, in `[0.0, 1.0)`.
+    pub fraction: f32,
+}
+
+impl RenderTime {
+    /// The point `delay_ticks` behind `server_tick + server_interpolation`.
+    pub fn delayed(server_tick: Tick, server_interpolation: f32, delay_ticks: f32) -> Self {
+        let offset = server_interpolation - delay_ticks;
+        let whole = offset.floor();
+        Self {
+            tick: server_tick.wrapping_add(whole as i16 as u16),
+            fraction: offset - whole,
+        }
+    }
+
+    // Signed distance in ticks from this render time to `tick`.
+    fn ticks_until(&self, tick: Tick) -> f32 {
+        f32::from(wrapping_diff(self.tick, tick)) - self.fraction
+    }
+
+    // Signed distance in ticks from `earlier` to this render time.
+    pub(crate) fn ticks_since(&self, earlier: &RenderTime) -> f32 {
+        f32::from(wrapping_diff(earlier.tick, self.tick)) + self.fraction - earlier.fraction
+    }
+}
+
+/// Per-entity snapshot history of an [`Interpolate`] component.
+///
+/// Feed it from insert/update events with [`record`](Self::record), drop
+/// despawned entities with [`remove_entity`](Self::remove_entity), and read
+/// render values with [`sample`](Self::sample).
+pub struct SnapshotInterpolation<E: Copy + Eq + Hash, C: Interpolate> {
+    config: InterpolationConfig,
+    buffers: HashMap<E, VecDeque<(Tick, C)>>,
+}
+
+impl<E: Copy + Eq + Hash, C: Interpolate> SnapshotInterpolation<E, C> {
+    /// Creates an empty history with the given tuning.
+    pub fn new(config: InterpolationConfig) -> Self {
+        Self {
+            config,
+            buffers: HashMap::new(),
+        }
+    }
+
+    /// The tuning this history was created with.
+    pub fn config(&self) -> &InterpolationConfig {
+        &self.config
+    }
+
+    /// Records `value` as the state of `entity` at server `tick`.
+    ///
+    /// Snapshots may arrive out of order; a snapshot for a tick already held
+    /// replaces it, and one older than the history window is ignored.
+    pub fn record(&mut self, entity: E, tick: Tick, value: C) {
+        let history_ticks = i32::from(self.config.history_ticks);
+        let buffer = self.buffers.entry(entity).or_default();
+
+        if let Some((newest, _)) = buffer.back() {
+            if i32::from(wrapping_diff(tick, *newest)) > history_ticks {
+                return;
+            }
+        }
+
+        // Walk back from the newest entry to find the insertion point.
+        let mut index = buffer.len();
+        while index > 0 {
+            let existing = buffer[index - 1].0;
+            let diff = wrapping_diff(existing, tick);
+            if diff == 0 {
+                buffer[index - 1].1 = value;
+                return;
+            }
+            if diff > 0 {
+                break;
+            }
+            index -= 1;
+        }
+        buffer.insert(index, (tick, value));
+
+        let newest = buffer.back().map(|(tick, _)| *tick).unwrap();
+        while let Some((oldest, _)) = buffer.front() {
+            if i32::from(wrapping_diff(*oldest, newest)) <= history_ticks {
+                break;
+            }
+            buffer.pop_front();
+        }
+    }
+
+    /// Forgets all history for `entity`.
+    pub fn remove_entity(&mut self, entity: &E) {
+        self.buffers.remove(entity);
+    }
+
+    /// Forgets all history, e.g. on disconnect.
+    pub fn clear(&mut self) {
+        self.buffers.clear();
+    }
+
+    /// Number of entities with at least one snapshot.
+    pub fn entities_count(&self) -> usize {
+        self.buffers.len()
+    }
+
+    /// Number of snapshots held for `entity`.
+    pub fn snapshots_count(&self, entity: &E) -> usize {
+        self.buffers.get(entity).map_or(0, VecDeque::len)
+    }
+
+    /// Iterates the entities with at least one snapshot.
+    pub fn entities(&self) -> impl Iterator<Item = &E> {
+        self.buffers.keys()
+    }
+
+    /// Returns the value of `entity` at `render_time`, or `None` if no
+    /// snapshot of it has been recorded.
+    ///
+    /// - Between two snapshots the value is blended, unless
+    ///   [`Interpolate::should_snap`] reports a discontinuity, in which case
+    ///   the earlier value is held until the later tick is reached.
+    /// - Before the oldest snapshot the oldest value is held.
+    /// - Past the newest snapshot the last two snapshots are extrapolated for
+    ///   at most `max_extrapolation_ticks`, then the value is held.
+    pub fn sample(&self, entity: &E, render_time: RenderTime) -> Option<C> {
+        let buffer = self.buffers.get(entity)?;
+
+        // Index of the first snapshot strictly after the render time.
+        let next_index = buffer
+            .iter()
+            .position(|(tick, _)| render_time.ticks_until(*tick) > 0.0)
+            .unwrap_or(buffer.len());
+
+        if next_index == 0 {
+            return buffer.front().map(|(_, value)| value.clone());
+        }
+
+        let (prev_tick, prev_value) = &buffer[next_index - 1];
+        let elapsed = -render_time.ticks_until(*prev_tick);
+
+        if let Some((next_tick, next_value)) = buffer.get(next_index) {
+            if prev_value.should_snap(next_value) {
+                return Some(prev_value.clone());
+            }
+            let span = f32::from(wrapping_diff(*prev_tick, *next_tick));
+            return Some(prev_value.interpolate(next_value, elapsed / span));
+        }
+
+        // Past the newest snapshot: extrapolate from the last two.
+        let extrapolation = elapsed.min(self.config.max_extrapolation_ticks);
+        if extrapolation <= 0.0 || next_index < 2 {
+            return Some(prev_value.clone());
+        }
+        let (before_tick, before_value) = &buffer[next_index - 2];
+        if before_value.should_snap(prev_value) {
+            return Some(prev_value.clone());
+        }
+        let span = f32::from(wrapping_diff(*before_tick, *prev_tick));
+        Some(before_value.interpolate(prev_value, 1.0 + extrapolation / span))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct Teleporting(f32);
+
+    impl Interpolate for Teleporting {
+        fn interpolate(&self, next: &Self, fraction: f32) -> Self {
+            Teleporting(self.0.interpolate(&next.0, fraction))
+        }
+
+        fn should_snap(&self, next: &Self) -> bool {
+            (next.0 - self.0).abs() > 100.0
+        }
+    }
+
+    fn at(tick: Tick, fraction: f32) -> RenderTime {
+        RenderTime { tick, fraction }
+    }
+
+    fn approx(actual: Option<f32>, expected: f32) {
+        let actual = actual.expect("sample exists");
+        assert!((actual - expected).abs() < 1e-4, "{actual} != {expected}");
+    }
+
+    #[test]
+    fn render_time_applies_delay_across_tick_boundaries() {
+        assert_eq!(RenderTime::delayed(10, 0.5, 2.0), at(8, 0.5));
+        assert_eq!(RenderTime::delayed(10, 0.25, 0.5), at(9, 0.75));
+        assert_eq!(RenderTime::delayed(1, 0.0, 2.0), at(u16::MAX, 0.0));
+    }
+
+    #[test]
+    fn blends_between_surrounding_snapshots() {
+        let mut interp = SnapshotInterpolation::<u32, f32>::new(InterpolationConfig::default());
+        interp.record(1, 10, 0.0);
+        interp.record(1, 12, 20.0);
+
+        approx(interp.sample(&1, at(10, 0.0)), 0.0);
+        approx(interp.sample(&1, at(11, 0.0)), 10.0);
+        approx(interp.sample(&1, at(11, 0.5)), 15.0);
+        assert_eq!(interp.sample(&2, at(11, 0.0)), None);
+    }
+
+    #[test]
+    fn holds_oldest_before_history_and_extrapolates_bounded() {
+        let mut interp = SnapshotInterpolation::<u32, f32>::new(InterpolationConfig {
+            max_extrapolation_ticks: 1.0,
+            ..Default::default()
+        });
+        interp.record(1, 10, 0.0);
+        interp.record(1, 11, 10.0);
+
+        approx(interp.sample(&1, at(5, 0.0)), 0.0);
+        approx(interp.sample(&1, at(11, 0.5)), 15.0);
+        approx(interp.sample(&1, at(14, 0.0)), 20.0);
+    }
+
+    #[test]
+    fn single_snapshot_is_held() {
+        let mut interp = SnapshotInterpolation::<u32, f32>::new(InterpolationConfig::default());
+        interp.record(1, 10, 3.0);
+        approx(interp.sample(&1, at(20, 0.0)), 3.0);
+    }
+
+    #[test]
+    fn snapping_holds_until_the_teleport_tick() {
+        let mut interp =
+            SnapshotInterpolation::<u32, Teleporting>::new(InterpolationConfig::default());
+        interp.record(1, 10, Teleporting(0.0));
+        interp.record(1, 11, Teleporting(500.0));
+
+        assert_eq!(interp.sample(&1, at(10, 0.9)), Some(Teleporting(0.0)));
+        assert_eq!(interp.sample(&1, at(11, 0.0)), Some(Teleporting(500.0)));
+        // No extrapolation across a discontinuity.
+        assert_eq!(interp.sample(&1, at(11, 0.5)), Some(Teleporting(500.0)));
+    }
+
+    #[test]
+    fn out_of_order_records_are_sorted_and_old_ones_pruned() {
+        let mut interp = SnapshotInterpolation::<u32, f32>::new(InterpolationConfig {
+            history_ticks: 4,
+            ..Default::default()
+        });
+        interp.record(1, 12, 20.0);
+        interp.record(1, 10, 0.0);
+        approx(interp.sample(&1, at(11, 0.0)), 10.0);
+
+        interp.record(1, 12, 40.0);
+        approx(interp.sample(&1, at(11, 0.0)), 20.0);
+        assert_eq!(interp.snapshots_count(&1), 2);
+
+        interp.record(1, 20, 0.0);
+        assert_eq!(interp.snapshots_count(&1), 1);
+        interp.record(1, 13, 0.0);
+        assert_eq!(interp.snapshots_count(&1), 1);
+    }
+
+    #[test]
+    fn wraps_around_tick_overflow() {
+        let mut interp = SnapshotInterpolation::<u32, f32>::new(InterpolationConfig::default());
+        interp.record(1, u16::MAX, 0.0);
+        interp.record(1, 1, 20.0);
+        approx(interp.sample(&1, at(0, 0.0)), 10.0);
+    }
+}
@@ -55,6 +55,9 @@
 //! | [`EntityRef`] | Read-only entity handle |
 //! | [`Publicity`] | The three visibility states (Private / Public / Delegated) |
 //! | [`CommandHistory`] | Rollback buffer for client-prediction |
+//! | [`PredictionManager`] | Automatic prediction rollback and replay |
+//! | [`SnapshotInterpolation`] | Per-entity snapshot history for render-time interpolation |
+//! | [`DemoPlayer`] | Plays back a recorded demo without a server |
 //! | [`ConnectionStatus`](client::ConnectionStatus) | Lifecycle state |
 
 #![deny(
@@ -79,12 +82,16 @@ pub mod shared {
     };
 }
 
-pub use naia_shared::{ConnectionStats, DisconnectReason, EntityPriorityMut, EntityPriorityRef};
+pub use naia_shared::{
+    BandwidthProfile, ChannelStats, ConnectionStats, DemoError, DisconnectReason,
+    EntityPriorityMut, EntityPriorityRef, SentBits,
+};
 
 mod client;
 mod client_config;
 mod command_history;
 mod connection;
+mod demo_player;
 /// Internal e2e-debug atomic counters used by the test harness.
 pub mod counters;
 mod error;
@@ -125,6 +132,8 @@ pub extern "Rust" fn client_processed_spawn_increment() {
     CLIENT_PROCESSED_SPAWN.fetch_add(1, Ordering::Relaxed);
 }
 mod handshake;
+mod interpolation;
+mod prediction;
 mod request;
 mod tick_events;
 mod world;
@@ -140,7 +149,11 @@ pub use client::{Client, ConnectionStatus};
 pub use client_config::ClientConfig;
 pub use command_history::CommandHistory;
 pub use connection::jitter_buffer::JitterBufferType;
+pub use demo_player::DemoPlayer;
 pub use error::NaiaClientError;
+pub use interpolation::{InterpolationConfig, RenderTime, SnapshotInterpolation};
+pub use naia_shared::Interpolate;
+pub use prediction::{Predict, PredictionConfig, PredictionManager, Rollback};
 pub use tick_events::{ClientTickEvent, ServerTickEvent, TickEvent, TickEvents};
 pub use world::{
     entity_mut::EntityMut, entity_owner::EntityOwner, entity_ref::EntityRef,
@@ -148,7 +161,8 @@ pub use world::{
 };
 pub use world_events::{
     ConnectEvent, DespawnEntityEvent, DisconnectEvent, EntityAuthDeniedEvent,
-    EntityAuthGrantedEvent, EntityAuthResetEvent, ErrorEvent, InsertComponentEvent, MessageEvent,
-    PublishEntityEvent, RejectEvent, RemoveComponentEvent, RequestEvent, SpawnEntityEvent,
+    EntityAuthGrantedEvent, EntityAuthGroupDeniedEvent, EntityAuthGroupGrantedEvent,
+    EntityAuthQueueGrantedEvent, EntityAuthResetEvent, ErrorEvent,
+    InsertComponentEvent, MessageEvent, PublishEntityEvent, RejectEvent, RemoveComponentEvent, RequestEvent, SpawnEntityEvent,
     UnpublishEntityEvent, UpdateComponentEvent, WorldEvent, Events,
 };
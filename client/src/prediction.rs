@@ -0,0 +1,680 @@
+//! Client-side prediction with automatic rollback and replay.
+//!
+//! [`PredictionManager`] packages the pattern described in
+//! `docs/PREDICTION.md`: each locally predicted entity is paired with the
+//! confirmed, server-replicated entity it mirrors; every command is recorded
+//! in a [`CommandHistory`] and simulated immediately; the predicted value of
+//! each tracked component is remembered per tick; and when the server
+//! confirms a tick whose value differs from what was predicted the predicted
+//! entities are snapped to the confirmed state and every later command is
+//! replayed.
+//!
+//! ```no_run
+//! # use naia_client::{PredictionConfig, PredictionManager, Predict};
+//! # use naia_shared::{Tick, WorldMutType};
+//! # #[derive(Clone, PartialEq)] struct Position(f32);
+//! # impl Predict for Position {
+//! #     fn mispredicted(&self, confirmed: &Self) -> bool { self != confirmed }
+//! # }
+//! # #[derive(Clone)] struct Command;
+//! # fn apply<W>(_world: &mut W, _tick: Tick, _command: &Command) -> Position { Position(0.0) }
+//! # fn example<W: WorldMutType<u32>>(mut world: W, tick: Tick, predicted: u32, confirmed: u32, server_value: Position) {
+//! let mut prediction = PredictionManager::<u32, Command>::new(PredictionConfig::default());
+//! prediction.add_entity(confirmed, predicted);
+//!
+//! // On each client tick:
+//! prediction.record_command(tick, Command);
+//!
+//! // On each UpdateComponentEvent for the confirmed entity:
+//! prediction.confirm(&confirmed, tick, &server_value);
+//!
+//! // Once per frame, after events are handled:
+//! prediction.simulate(&mut world, |prediction, world, tick, command| {
+//!     let position = apply(world, tick, command);
+//!     prediction.record_predicted(&predicted, tick, position);
+//! });
+//! # }
+//! ```
+
+use std::{
+    any::{Any, TypeId},
+    collections::{HashMap, VecDeque},
+    hash::Hash,
+};
+
+use naia_shared::{sequence_greater_than, wrapping_diff, Interpolate, Tick, WorldMutType};
+
+use crate::{
+    command_history::CommandHistory,
+    interpolation::RenderTime,
+};
+
+/// A predicted component whose value can be checked against the server.
+pub trait Predict: Clone + Send + Sync + 'static {
+    /// Returns `true` if this predicted value is far enough from the
+    /// `confirmed` server value to require a rollback.
+    fn mispredicted(&self, confirmed: &Self) -> bool;
+}
+
+/// Tuning for [`PredictionManager`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct PredictionConfig {
+    /// How many ticks of commands and predicted component values to keep.
+    /// Should cover at least twice the worst expected RTT in ticks.
+    pub history_ticks: u16,
+    /// How many ticks a visual correction takes to blend away after a
+    /// rollback. `0.0` disables smoothing.
+    pub smoothing_ticks: f32,
+}
+
+impl Default for PredictionConfig {
+    fn default() -> Self {
+        Self {
+            history_ticks: 128,
+            smoothing_ticks: 0.0,
+        }
+    }
+}
+
+/// A rollback taken from a [`PredictionManager`]: the confirmed tick to
+/// restart from and every command recorded after it, oldest first.
+pub struct Rollback<C> {
+    /// The latest tick whose confirmed state replaces the prediction.
+    pub from_tick: Tick,
+    /// The commands to replay, oldest first.
+    pub replays: Vec<(Tick, C)>,
+}
+
+trait ErasedHistory<E>: Send + Sync {
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+    fn remove_entity(&mut self, entity: &E);
+    fn discard_after(&mut self, tick: Tick);
+}
+
+struct ComponentHistory<E, S> {
+    values: HashMap<E, VecDeque<(Tick, S)>>,
+}
+
+impl<E: Copy + Eq + Hash + Send + Sync + 'static, S: Predict> ErasedHistory<E>
+    for ComponentHistory<E, S>
+{
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn remove_entity(&mut self, entity: &E) {
+        self.values.remove(entity);
+    }
+
+    fn discard_after(&mut self, tick: Tick) {
+        for buffer in self.values.values_mut() {
+            while let Some((newest, _)) = buffer.back() {
+                if !sequence_greater_than(*newest, tick) {
+                    break;
+                }
+                buffer.pop_back();
+            }
+        }
+    }
+}
+
+/// Tracks predicted/confirmed entity pairs, their command history and
+/// per-component predicted values, and decides when to roll back.
+///
+/// `E` is the world's entity key, `C` the command type replayed on rollback.
+pub struct PredictionManager<E: Copy + Eq + Hash + Send + Sync + 'static, C: Clone> {
+    config: PredictionConfig,
+    commands: CommandHistory<C>,
+    pending_steps: Vec<(Tick, C)>,
+    confirmed_to_predicted: HashMap<E, E>,
+    predicted_to_confirmed: HashMap<E, E>,
+    histories: HashMap<TypeId, Box<dyn ErasedHistory<E>>>,
+    corrections: HashMap<TypeId, Box<dyn Any + Send + Sync>>,
+    rollback_tick: Option<Tick>,
+    latest_confirmed_tick: Option<Tick>,
+    misprediction_count: u64,
+}
+
+impl<E: Copy + Eq + Hash + Send + Sync + 'static, C: Clone> PredictionManager<E, C> {
+    /// Creates an empty manager with the given tuning.
+    pub fn new(config: PredictionConfig) -> Self {
+        Self {
+            config,
+            commands: CommandHistory::default(),
+            pending_steps: Vec::new(),
+            confirmed_to_predicted: HashMap::new(),
+            predicted_to_confirmed: HashMap::new(),
+            histories: HashMap::new(),
+            corrections: HashMap::new(),
+            rollback_tick: None,
+            latest_confirmed_tick: None,
+            misprediction_count: 0,
+        }
+    }
+
+    /// The tuning this manager was created with.
+    pub fn config(&self) -> &PredictionConfig {
+        &self.config
+    }
+
+    // Entities
+
+    /// Pairs a locally `predicted` entity with the server-replicated
+    /// `confirmed` entity it mirrors (typically created with
+    /// `local_duplicate()`).
+    pub fn add_entity(&mut self, confirmed: E, predicted: E) {
+        self.remove_entity(&confirmed);
+        self.confirmed_to_predicted.insert(confirmed, predicted);
+        self.predicted_to_confirmed.insert(predicted, confirmed);
+    }
+
+    /// Stops predicting the pair whose confirmed entity is `confirmed`,
+    /// returning the predicted entity.
+    pub fn remove_entity(&mut self, confirmed: &E) -> Option<E> {
+        let predicted = self.confirmed_to_predicted.remove(confirmed)?;
+        self.predicted_to_confirmed.remove(&predicted);
+        for history in self.histories.values_mut() {
+            history.remove_entity(&predicted);
+        }
+        Some(predicted)
+    }
+
+    /// The predicted counterpart of `confirmed`, if paired.
+    pub fn predicted_entity(&self, confirmed: &E) -> Option<E> {
+        self.confirmed_to_predicted.get(confirmed).copied()
+    }
+
+    /// The confirmed counterpart of `predicted`, if paired.
+    pub fn confirmed_entity(&self, predicted: &E) -> Option<E> {
+        self.predicted_to_confirmed.get(predicted).copied()
+    }
+
+    /// Iterates `(confirmed, predicted)` pairs.
+    pub fn entities(&self) -> impl Iterator<Item = (E, E)> + '_ {
+        self.confirmed_to_predicted
+            .iter()
+            .map(|(confirmed, predicted)| (*confirmed, *predicted))
+    }
+
+    // Commands
+
+    /// Records `command` for client `tick` and queues it for the next
+    /// [`simulate`](Self::simulate). Returns `false`, recording nothing, if
+    /// `tick` is not later than the last recorded tick. Commands older than
+    /// `history_ticks` are dropped.
+    pub fn record_command(&mut self, tick: Tick, command: C) -> bool {
+        if !self.commands.can_insert(&tick) {
+            return false;
+        }
+        self.commands.insert(tick, command.clone());
+        self.pending_steps.push((tick, command));
+        let oldest_kept = tick.wrapping_sub(self.config.history_ticks);
+        self.commands
+            .remove_to_and_including(oldest_kept.wrapping_sub(1));
+        true
+    }
+
+    /// The buffered command history.
+    pub fn command_history(&self) -> &CommandHistory<C> {
+        &self.commands
+    }
+
+    // Components
+
+    /// Records the value `S` had on `predicted` after simulating `tick`.
+    pub fn record_predicted<S: Predict>(&mut self, predicted: &E, tick: Tick, value: S) {
+        let history_ticks = i32::from(self.config.history_ticks);
+        let buffer = self
+            .history_mut::<S>()
+            .values
+            .entry(*predicted)
+            .or_default();
+        buffer.retain(|(existing, _)| *existing != tick);
+        buffer.push_back((tick, value));
+        while let Some((oldest, _)) = buffer.front() {
+            if i32::from(wrapping_diff(*oldest, tick)) <= history_ticks {
+                break;
+            }
+            buffer.pop_front();
+        }
+    }
+
+    /// The value `S` was predicted to have on `predicted` at `tick`.
+    pub fn predicted_value<S: Predict>(&self, predicted: &E, tick: Tick) -> Option<&S> {
+        self.history::<S>()?
+            .values
+            .get(predicted)?
+            .iter()
+            .find(|(existing, _)| *existing == tick)
+            .map(|(_, value)| value)
+    }
+
+    /// Compares the server's `value` of `S` on `confirmed` at `tick` with the
+    /// value predicted for that tick, and schedules a rollback if they differ
+    /// or nothing was predicted. Returns `true` if a rollback was scheduled.
+    ///
+    /// A rollback restarts from the latest confirmed tick, since that is the
+    /// state confirmed entities hold, so corrections from the same frame are
+    /// batched into one rollback from the latest of their ticks. Commands up
+    /// to that tick are no longer needed for a replay and are dropped.
+    pub fn confirm<S: Predict>(&mut self, confirmed: &E, tick: Tick, value: &S) -> bool {
+        let Some(predicted) = self.predicted_entity(confirmed) else {
+            return false;
+        };
+        let latest = match self.latest_confirmed_tick {
+            Some(latest) if !sequence_greater_than(tick, latest) => latest,
+            _ => tick,
+        };
+        self.latest_confirmed_tick = Some(latest);
+
+        let mispredicted = self
+            .predicted_value::<S>(&predicted, tick)
+            .is_none_or(|predicted_value| predicted_value.mispredicted(value));
+        if mispredicted {
+            self.misprediction_count += 1;
+        }
+        if mispredicted || self.rollback_tick.is_some() {
+            self.rollback_tick = Some(latest);
+        }
+
+        self.commands.remove_to_and_including(latest);
+        mispredicted
+    }
+
+    /// The tick a rollback is scheduled from, if any.
+    pub fn pending_rollback(&self) -> Option<Tick> {
+        self.rollback_tick
+    }
+
+    /// Number of mispredictions detected since creation.
+    pub fn misprediction_count(&self) -> u64 {
+        self.misprediction_count
+    }
+
+    // Simulation
+
+    /// Takes the scheduled rollback, if any. Predicted values recorded after
+    /// its tick are discarded and queued steps are dropped, since the replay
+    /// covers them. The caller must snap predicted entities to their
+    /// confirmed state and run every replay in order.
+    pub fn take_rollback(&mut self) -> Option<Rollback<C>> {
+        let from_tick = self.rollback_tick.take()?;
+        for history in self.histories.values_mut() {
+            history.discard_after(from_tick);
+        }
+        self.pending_steps.clear();
+        Some(Rollback {
+            from_tick,
+            replays: self.commands.replays(&from_tick),
+        })
+    }
+
+    /// Takes commands recorded since the last call, for live simulation.
+    pub fn take_steps(&mut self) -> Vec<(Tick, C)> {
+        std::mem::take(&mut self.pending_steps)
+    }
+
+    /// Runs `step` for every command that needs simulating: after a rollback
+    /// every predicted entity is first snapped to its confirmed counterpart
+    /// and all later commands are replayed, otherwise newly recorded commands
+    /// are simulated. Returns `true` if a rollback happened.
+    ///
+    /// `step` should apply the command to the predicted entities and call
+    /// [`record_predicted`](Self::record_predicted) for each tracked
+    /// component.
+    pub fn simulate<W: WorldMutType<E>>(
+        &mut self,
+        world: &mut W,
+        step: impl FnMut(&mut Self, &mut W, Tick, &C),
+    ) -> bool {
+        self.simulate_with(
+            world,
+            |world, predicted, confirmed| world.mirror_entities(predicted, confirmed),
+            step,
+        )
+    }
+
+    // `simulate` with the snap to confirmed state supplied by the caller
+    fn simulate_with<W>(
+        &mut self,
+        world: &mut W,
+        mut mirror: impl FnMut(&mut W, &E, &E),
+        mut step: impl FnMut(&mut Self, &mut W, Tick, &C),
+    ) -> bool {
+        let (steps, rolled_back) = match self.take_rollback() {
+            Some(rollback) => {
+                for (confirmed, predicted) in self.entities().collect::<Vec<_>>() {
+                    mirror(world, &predicted, &confirmed);
+                }
+                (rollback.replays, true)
+            }
+            None => (self.take_steps(), false),
+        };
+        for (tick, command) in steps {
+            step(self, world, tick, &command);
+        }
+        rolled_back
+    }
+
+    // Smoothing
+
+    /// Starts blending the rendered `S` of `predicted` away from
+    /// `pre_rollback`, its value just before a rollback, beginning at `now`.
+    /// A no-op when smoothing is disabled.
+    pub fn begin_smoothing<S: Interpolate + Send + Sync + 'static>(
+        &mut self,
+        predicted: &E,
+        pre_rollback: S,
+        now: RenderTime,
+    ) {
+        if self.config.smoothing_ticks <= 0.0 {
+            return;
+        }
+        self.corrections
+            .entry(TypeId::of::<S>())
+            .or_insert_with(|| Box::new(HashMap::<E, (S, RenderTime)>::new()))
+            .downcast_mut::<HashMap<E, (S, RenderTime)>>()
+            .expect("correction store type")
+            .insert(*predicted, (pre_rollback, now));
+    }
+
+    /// The value of `S` to render for `predicted` at `now`, given its
+    /// simulated `current` value. Blends from the pre-rollback value towards
+    /// `current` over `smoothing_ticks`, then returns `current`.
+    pub fn smoothed<S: Interpolate + Send + Sync + 'static>(
+        &mut self,
+        predicted: &E,
+        current: &S,
+        now: RenderTime,
+    ) -> S {
+        let smoothing_ticks = self.config.smoothing_ticks;
+        let Some(corrections) = self
+            .corrections
+            .get_mut(&TypeId::of::<S>())
+            .and_then(|store| store.downcast_mut::<HashMap<E, (S, RenderTime)>>())
+        else {
+            return current.clone();
+        };
+        let Some((pre_rollback, started)) = corrections.get(predicted) else {
+            return current.clone();
+        };
+        let progress = now.ticks_since(started) / smoothing_ticks;
+        if progress >= 1.0 || current.should_snap(pre_rollback) {
+            corrections.remove(predicted);
+            return current.clone();
+        }
+        pre_rollback.interpolate(current, progress.max(0.0))
+    }
+
+    /// Forgets all pairs, history and pending work, e.g. on disconnect.
+    pub fn clear(&mut self) {
+        self.commands = CommandHistory::default();
+        self.pending_steps.clear();
+        self.confirmed_to_predicted.clear();
+        self.predicted_to_confirmed.clear();
+        self.histories.clear();
+        self.corrections.clear();
+        self.rollback_tick = None;
+        self.latest_confirmed_tick = None;
+    }
+
+    fn history<S: Predict>(&self) -> Option<&ComponentHistory<E, S>> {
+        self.histories
+            .get(&TypeId::of::<S>())?
+            .as_any()
+            .downcast_ref::<ComponentHistory<E, S>>()
+    }
+
+    fn history_mut<S: Predict>(&mut self) -> &mut ComponentHistory<E, S> {
+        self.histories
+            .entry(TypeId::of::<S>())
+            .or_insert_with(|| {
+                Box::new(ComponentHistory::<E, S> {
+                    values: HashMap::new(),
+                })
+            })
+            .as_any_mut()
+            .downcast_mut::<ComponentHistory<E, S>>()
+            .expect("component history type")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct Position(f32);
+
+    impl Predict for Position {
+        fn mispredicted(&self, confirmed: &Self) -> bool {
+            (self.0 - confirmed.0).abs() > 0.01
+        }
+    }
+
+    impl Interpolate for Position {
+        fn interpolate(&self, next: &Self, fraction: f32) -> Self {
+            Position(self.0.interpolate(&next.0, fraction))
+        }
+    }
+
+    const CONFIRMED: u32 = 1;
+    const PREDICTED: u32 = 2;
+
+    fn manager() -> PredictionManager<u32, f32> {
+        let mut manager = PredictionManager::new(PredictionConfig::default());
+        manager.add_entity(CONFIRMED, PREDICTED);
+        manager
+    }
+
+    // Simulates every queued step by adding the command to a running value.
+    fn run(manager: &mut PredictionManager<u32, f32>, position: &mut f32) {
+        for (tick, command) in manager.take_steps() {
+            *position += command;
+            manager.record_predicted(&PREDICTED, tick, Position(*position));
+        }
+    }
+
+    #[test]
+    fn matching_confirmation_does_not_roll_back() {
+        let mut manager = manager();
+        let mut position = 0.0;
+        for tick in 1..=3 {
+            manager.record_command(tick, 1.0);
+        }
+        run(&mut manager, &mut position);
+
+        assert!(!manager.confirm(&CONFIRMED, 2, &Position(2.0)));
+        assert!(manager.take_rollback().is_none());
+        assert_eq!(manager.misprediction_count(), 0);
+    }
+
+    #[test]
+    fn misprediction_replays_later_commands() {
+        let mut manager = manager();
+        let mut position = 0.0;
+        for tick in 1..=4 {
+            manager.record_command(tick, 1.0);
+        }
+        run(&mut manager, &mut position);
+
+        assert!(manager.confirm(&CONFIRMED, 2, &Position(5.0)));
+        let rollback = manager.take_rollback().expect("rollback scheduled");
+        assert_eq!(rollback.from_tick, 2);
+        assert_eq!(
+            rollback
+                .replays
+                .iter()
+                .map(|(tick, _)| *tick)
+                .collect::<Vec<_>>(),
+            vec![3, 4]
+        );
+        // Predictions after the rollback tick are discarded for re-recording.
+        assert!(manager.predicted_value::<Position>(&PREDICTED, 3).is_none());
+        assert!(manager.predicted_value::<Position>(&PREDICTED, 2).is_some());
+    }
+
+    #[test]
+    fn corrections_batch_to_latest_tick() {
+        let mut manager = manager();
+        let mut position = 0.0;
+        for tick in 1..=5 {
+            manager.record_command(tick, 1.0);
+        }
+        run(&mut manager, &mut position);
+
+        // The server held still for a tick, so every prediction from tick 2
+        // on is one ahead; the confirmed entity holds the tick 4 state.
+        manager.confirm(&CONFIRMED, 4, &Position(3.0));
+        manager.confirm(&CONFIRMED, 2, &Position(1.0));
+        manager.confirm(&CONFIRMED, 3, &Position(2.0));
+        assert_eq!(manager.pending_rollback(), Some(4));
+        assert_eq!(manager.misprediction_count(), 3);
+
+        // Snapping to tick 4 and replaying tick 5 once lands one step on
+        let mut world = HashMap::from([(CONFIRMED, 3.0), (PREDICTED, position)]);
+        let rolled_back = manager.simulate_with(
+            &mut world,
+            |world, predicted, confirmed| {
+                let value = world[confirmed];
+                world.insert(*predicted, value);
+            },
+            |manager, world, tick, command| {
+                let position = world.get_mut(&PREDICTED).unwrap();
+                *position += command;
+                let value = Position(*position);
+                manager.record_predicted(&PREDICTED, tick, value);
+            },
+        );
+        assert!(rolled_back);
+        assert_eq!(world[&PREDICTED], 4.0);
+    }
+
+    #[test]
+    fn unpaired_or_unpredicted_confirmations() {
+        let mut manager = manager();
+        assert!(!manager.confirm(&99, 1, &Position(0.0)));
+        // Nothing predicted for this tick: roll back to be safe.
+        assert!(manager.confirm(&CONFIRMED, 1, &Position(0.0)));
+    }
+
+    #[test]
+    fn rejects_stale_commands() {
+        let mut manager = manager();
+        assert!(manager.record_command(5, 1.0));
+        assert!(!manager.record_command(5, 1.0));
+        assert!(!manager.record_command(4, 1.0));
+        assert_eq!(manager.take_steps().len(), 1);
+    }
+
+    #[test]
+    fn confirmation_prunes_replayed_commands() {
+        let mut manager = manager();
+        let mut position = 0.0;
+        for tick in 1..=5 {
+            manager.record_command(tick, 1.0);
+        }
+        run(&mut manager, &mut position);
+
+        assert!(!manager.confirm(&CONFIRMED, 3, &Position(3.0)));
+        assert_eq!(manager.command_history().most_recent_tick(), Some(5));
+        assert!(manager.confirm(&CONFIRMED, 4, &Position(0.0)));
+        let rollback = manager.take_rollback().expect("rollback scheduled");
+        assert_eq!(
+            rollback
+                .replays
+                .iter()
+                .map(|(tick, _)| *tick)
+                .collect::<Vec<_>>(),
+            vec![5]
+        );
+    }
+
+    #[test]
+    fn command_history_is_trimmed_to_history_ticks() {
+        let mut manager = PredictionManager::<u32, f32>::new(PredictionConfig {
+            history_ticks: 4,
+            ..Default::default()
+        });
+        manager.add_entity(CONFIRMED, PREDICTED);
+        for tick in 1..=10 {
+            manager.record_command(tick, 1.0);
+        }
+
+        assert!(manager.confirm(&CONFIRMED, 1, &Position(0.0)));
+        let rollback = manager.take_rollback().expect("rollback scheduled");
+        assert_eq!(
+            rollback
+                .replays
+                .iter()
+                .map(|(tick, _)| *tick)
+                .collect::<Vec<_>>(),
+            vec![6, 7, 8, 9, 10]
+        );
+    }
+
+    #[test]
+    fn rollback_across_tick_wraparound() {
+        let mut manager = manager();
+        let mut position = 0.0;
+        for tick in [65534, 65535, 0, 1] {
+            manager.record_command(tick, 1.0);
+        }
+        run(&mut manager, &mut position);
+
+        assert!(!manager.confirm(&CONFIRMED, 65534, &Position(1.0)));
+        assert!(manager.confirm(&CONFIRMED, 65535, &Position(0.0)));
+        assert!(manager.confirm(&CONFIRMED, 0, &Position(0.0)));
+        assert_eq!(manager.pending_rollback(), Some(0));
+        let rollback = manager.take_rollback().expect("rollback scheduled");
+        assert_eq!(
+            rollback
+                .replays
+                .iter()
+                .map(|(tick, _)| *tick)
+                .collect::<Vec<_>>(),
+            vec![1]
+        );
+        assert!(manager.predicted_value::<Position>(&PREDICTED, 1).is_none());
+        assert!(manager.predicted_value::<Position>(&PREDICTED, 0).is_some());
+    }
+
+    #[test]
+    fn smoothing_blends_from_pre_rollback_value() {
+        let mut manager = PredictionManager::<u32, f32>::new(PredictionConfig {
+            smoothing_ticks: 4.0,
+            ..Default::default()
+        });
+        let start = RenderTime {
+            tick: 10,
+            fraction: 0.0,
+        };
+        manager.begin_smoothing(&PREDICTED, Position(0.0), start);
+
+        let halfway = RenderTime {
+            tick: 12,
+            fraction: 0.0,
+        };
+        assert_eq!(
+            manager.smoothed(&PREDICTED, &Position(8.0), halfway),
+            Position(4.0)
+        );
+        let done = RenderTime {
+            tick: 14,
+            fraction: 0.0,
+        };
+        assert_eq!(
+            manager.smoothed(&PREDICTED, &Position(8.0), done),
+            Position(8.0)
+        );
+        assert_eq!(
+            manager.smoothed(&PREDICTED, &Position(9.0), halfway),
+            Position(9.0)
+        );
+    }
+}
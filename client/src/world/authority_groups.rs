@@ -0,0 +1,121 @@
+use std::collections::HashMap;
+
+use naia_shared::GlobalEntity;
+
+/// Progress of a pending authority group after one member was answered
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum GroupProgress {
+    /// Other members are still waiting on the server
+    Pending,
+    /// Every member has been answered; `granted` is true only if all were
+    Complete {
+        entities: Vec<GlobalEntity>,
+        granted: bool,
+    },
+}
+
+struct PendingGroup {
+    entities: Vec<GlobalEntity>,
+    waiting: usize,
+    granted: bool,
+}
+
+/// Tracks this client's outstanding group authority requests, so the
+/// server's per-entity answers can be reported as a single event
+pub(crate) struct AuthorityGroups {
+    next_id: u32,
+    entity_to_group: HashMap<GlobalEntity, u32>,
+    groups: HashMap<u32, PendingGroup>,
+}
+
+impl AuthorityGroups {
+    pub(crate) fn new() -> Self {
+        Self {
+            next_id: 0,
+            entity_to_group: HashMap::new(),
+            groups: HashMap::new(),
+        }
+    }
+
+    /// Starts waiting on answers for `entities`, which must not be empty or
+    /// already pending
+    pub(crate) fn insert(&mut self, entities: Vec<GlobalEntity>) {
+        let id = self.next_id;
+        self.next_id = self.next_id.wrapping_add(1);
+        for entity in &entities {
+            self.entity_to_group.insert(*entity, id);
+        }
+        self.groups.insert(
+            id,
+            PendingGroup {
+                waiting: entities.len(),
+                entities,
+                granted: true,
+            },
+        );
+    }
+
+    /// Records the server's answer for `entity`. Returns `None` if the entity
+    /// isn't part of a pending group.
+    pub(crate) fn resolve(
+        &mut self,
+        entity: &GlobalEntity,
+        granted: bool,
+    ) -> Option<GroupProgress> {
+        let id = self.entity_to_group.remove(entity)?;
+        let group = self.groups.get_mut(&id)?;
+        group.waiting -= 1;
+        group.granted &= granted;
+        if group.waiting > 0 {
+            return Some(GroupProgress::Pending);
+        }
+        let group = self.groups.remove(&id)?;
+        Some(GroupProgress::Complete {
+            entities: group.entities,
+            granted: group.granted,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use naia_shared::BigMapKey;
+
+    use super::*;
+
+    fn ge(n: u64) -> GlobalEntity {
+        GlobalEntity::from_u64(n)
+    }
+
+    #[test]
+    fn group_completes_once_every_member_is_answered() {
+        let mut groups = AuthorityGroups::new();
+        groups.insert(vec![ge(1), ge(2)]);
+
+        assert_eq!(groups.resolve(&ge(3), true), None);
+        assert_eq!(groups.resolve(&ge(1), true), Some(GroupProgress::Pending));
+        assert_eq!(
+            groups.resolve(&ge(2), true),
+            Some(GroupProgress::Complete {
+                entities: vec![ge(1), ge(2)],
+                granted: true,
+            })
+        );
+        assert_eq!(groups.resolve(&ge(1), true), None);
+    }
+
+    #[test]
+    fn group_is_denied_if_any_member_is() {
+        let mut groups = AuthorityGroups::new();
+        groups.insert(vec![ge(1), ge(2)]);
+
+        assert_eq!(groups.resolve(&ge(1), false), Some(GroupProgress::Pending));
+        assert_eq!(
+            groups.resolve(&ge(2), true),
+            Some(GroupProgress::Complete {
+                entities: vec![ge(1), ge(2)],
+                granted: false,
+            })
+        );
+    }
+}
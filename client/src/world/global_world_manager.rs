@@ -305,10 +305,39 @@ impl GlobalWorldManager {
             .map(|host_status| host_status.status())
     }
 
+    pub(crate) fn entity_can_mutate(&self, global_entity: &GlobalEntity) -> bool {
+        self.auth_handler
+            .auth_status(global_entity)
+            .is_some_and(|host_status| host_status.can_mutate())
+    }
+
     pub(crate) fn entity_request_authority(
         &mut self,
         global_entity: &GlobalEntity,
     ) -> Result<(), AuthorityError> {
+        self.check_request_authority(global_entity)?;
+        self.auth_handler
+            .set_auth_status(global_entity, EntityAuthStatus::Requested);
+        Ok(())
+    }
+
+    /// Requests authority over every entity in `global_entities`, or over
+    /// none of them if any can't be requested.
+    pub(crate) fn entity_request_authority_group(
+        &mut self,
+        global_entities: &[GlobalEntity],
+    ) -> Result<(), AuthorityError> {
+        for global_entity in global_entities {
+            self.check_request_authority(global_entity)?;
+        }
+        for global_entity in global_entities {
+            self.auth_handler
+                .set_auth_status(global_entity, EntityAuthStatus::Requested);
+        }
+        Ok(())
+    }
+
+    fn check_request_authority(&self, global_entity: &GlobalEntity) -> Result<(), AuthorityError> {
         if !self.has_entity(global_entity) {
             // Entity is not in scope — client has no record of it at all.
             return Err(AuthorityError::NotInScope);
@@ -325,8 +354,6 @@ impl GlobalWorldManager {
             // Authority is not Available (e.g. already Requested or Granted).
             return Err(AuthorityError::NotAvailable);
         }
-        self.auth_handler
-            .set_auth_status(global_entity, EntityAuthStatus::Requested);
         Ok(())
     }
 
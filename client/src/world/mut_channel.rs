@@ -27,4 +27,8 @@ impl MutChannelType for MutChannelData {
     fn send(&self, diff: u8) {
         self.receiver.mutate(diff);
     }
+
+    fn receivers(&self) -> Vec<MutReceiver> {
+        vec![self.receiver.clone()]
+    }
 }
@@ -24,9 +24,12 @@ pub struct Events<E: Hash + Copy + Eq + Sync + Send> {
     publishes: Vec<E>,
     unpublishes: Vec<E>,
     auth_grants: Vec<E>,
+    auth_queue_grants: Vec<E>,
     auth_denies: Vec<E>,
+    auth_group_grants: Vec<Vec<E>>,
+    auth_group_denies: Vec<Vec<E>>,
     auth_resets: Vec<E>,
-    inserts: HashMap<ComponentKind, Vec<E>>,
+    inserts: HashMap<ComponentKind, Vec<(Tick, E)>>,
     removes: RemovesMap<E>,
     updates: HashMap<ComponentKind, Vec<(Tick, E)>>,
     empty: bool,
@@ -52,7 +55,10 @@ impl<E: Hash + Copy + Eq + Sync + Send> Events<E> {
             publishes: Vec::new(),
             unpublishes: Vec::new(),
             auth_grants: Vec::new(),
+            auth_queue_grants: Vec::new(),
             auth_denies: Vec::new(),
+            auth_group_grants: Vec::new(),
+            auth_group_denies: Vec::new(),
             auth_resets: Vec::new(),
             inserts: HashMap::new(),
             removes: HashMap::new(),
@@ -106,7 +112,7 @@ impl<E: Hash + Copy + Eq + Sync + Send> Events<E> {
         !self.inserts.is_empty()
     }
     /// Takes all queued component-insert events; prefer `read::<InsertComponentEvent<C>>()` in application code.
-    pub fn take_inserts(&mut self) -> Option<HashMap<ComponentKind, Vec<E>>> {
+    pub fn take_inserts(&mut self) -> Option<HashMap<ComponentKind, Vec<(Tick, E)>>> {
         if self.inserts.is_empty() {
             None
         } else {
@@ -221,20 +227,40 @@ impl<E: Hash + Copy + Eq + Sync + Send> Events<E> {
         self.empty = false;
     }
 
+    pub(crate) fn push_auth_queue_grant(&mut self, world_entity: E) {
+        self.auth_queue_grants.push(world_entity);
+        self.empty = false;
+    }
+
     pub(crate) fn push_auth_deny(&mut self, world_entity: E) {
         self.auth_denies.push(world_entity);
         self.empty = false;
     }
 
+    pub(crate) fn push_auth_group_grant(&mut self, world_entities: Vec<E>) {
+        self.auth_group_grants.push(world_entities);
+        self.empty = false;
+    }
+
+    pub(crate) fn push_auth_group_deny(&mut self, world_entities: Vec<E>) {
+        self.auth_group_denies.push(world_entities);
+        self.empty = false;
+    }
+
     pub(crate) fn push_auth_reset(&mut self, world_entity: E) {
         self.auth_resets.push(world_entity);
         self.empty = false;
     }
 
-    pub(crate) fn push_insert(&mut self, world_entity: E, component_kind: ComponentKind) {
-        self.inserts.entry(component_kind).or_insert_with(|| Vec::new());
+    pub(crate) fn push_insert(
+        &mut self,
+        tick: Tick,
+        world_entity: E,
+        component_kind: ComponentKind,
+    ) {
+        self.inserts.entry(component_kind).or_default();
         let list = self.inserts.get_mut(&component_kind).unwrap();
-        list.push(world_entity);
+        list.push((tick, world_entity));
         self.empty = false;
     }
 
@@ -270,7 +296,10 @@ impl<E: Hash + Copy + Eq + Sync + Send> Events<E> {
         self.publishes.clear();
         self.unpublishes.clear();
         self.auth_grants.clear();
+        self.auth_queue_grants.clear();
         self.auth_denies.clear();
+        self.auth_group_grants.clear();
+        self.auth_group_denies.clear();
         self.auth_resets.clear();
         self.inserts.clear();
         self.removes.clear();
@@ -506,6 +535,22 @@ impl<E: Hash + Copy + Eq + Sync + Send> WorldEvent<E> for EntityAuthGrantedEvent
     }
 }
 
+/// Fires when a queued authority request reaches the front of the entity's
+/// queue and the server grants it. Fires in place of [`EntityAuthGrantedEvent`].
+pub struct EntityAuthQueueGrantedEvent;
+impl<E: Hash + Copy + Eq + Sync + Send> WorldEvent<E> for EntityAuthQueueGrantedEvent {
+    type Iter = IntoIter<E>;
+
+    fn iter(events: &mut Events<E>) -> Self::Iter {
+        let list = std::mem::take(&mut events.auth_queue_grants);
+        IntoIterator::into_iter(list)
+    }
+
+    fn has(events: &Events<E>) -> bool {
+        !events.auth_queue_grants.is_empty()
+    }
+}
+
 /// Fires when the server reclaims authority over an entity that was previously delegated to this client.
 pub struct EntityAuthResetEvent;
 impl<E: Hash + Copy + Eq + Sync + Send> WorldEvent<E> for EntityAuthResetEvent {
@@ -536,6 +581,38 @@ impl<E: Hash + Copy + Eq + Sync + Send> WorldEvent<E> for EntityAuthDeniedEvent
     }
 }
 
+/// Fires when the server grants every entity of a group authority request;
+/// yields the group's world entities.
+pub struct EntityAuthGroupGrantedEvent;
+impl<E: Hash + Copy + Eq + Sync + Send> WorldEvent<E> for EntityAuthGroupGrantedEvent {
+    type Iter = IntoIter<Vec<E>>;
+
+    fn iter(events: &mut Events<E>) -> Self::Iter {
+        let list = std::mem::take(&mut events.auth_group_grants);
+        IntoIterator::into_iter(list)
+    }
+
+    fn has(events: &Events<E>) -> bool {
+        !events.auth_group_grants.is_empty()
+    }
+}
+
+/// Fires when the server denies a group authority request; none of the
+/// group's entities were granted. Yields the group's world entities.
+pub struct EntityAuthGroupDeniedEvent;
+impl<E: Hash + Copy + Eq + Sync + Send> WorldEvent<E> for EntityAuthGroupDeniedEvent {
+    type Iter = IntoIter<Vec<E>>;
+
+    fn iter(events: &mut Events<E>) -> Self::Iter {
+        let list = std::mem::take(&mut events.auth_group_denies);
+        IntoIterator::into_iter(list)
+    }
+
+    fn has(events: &Events<E>) -> bool {
+        !events.auth_group_denies.is_empty()
+    }
+}
+
 /// Fires when component `C` is inserted on a replicated entity; yields the world entity `E`.
 pub struct InsertComponentEvent<C: Replicate> {
     phantom_c: PhantomData<C>,
@@ -546,7 +623,8 @@ impl<E: Hash + Copy + Eq + Sync + Send, C: Replicate> WorldEvent<E> for InsertCo
     fn iter(events: &mut Events<E>) -> Self::Iter {
         let component_kind: ComponentKind = ComponentKind::of::<C>();
         if let Some(boxed_list) = events.inserts.remove(&component_kind) {
-            return IntoIterator::into_iter(boxed_list);
+            let entities: Vec<E> = boxed_list.into_iter().map(|(_, entity)| entity).collect();
+            return IntoIterator::into_iter(entities);
         }
 
         IntoIterator::into_iter(Vec::new())
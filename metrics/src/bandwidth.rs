@@ -0,0 +1,86 @@
+use crate::names;
+use metrics::Label;
+use naia_shared::BandwidthProfile;
+
+/// Label value used for entity command retransmissions in
+/// [`names::BANDWIDTH_RETRANSMITTED_BITS`], alongside the channel names.
+pub const ENTITY_COMMANDS_LABEL: &str = "entity_commands";
+
+/// Emit a bandwidth profile as counters: packets, bits per section (header,
+/// acks, messages, entity commands, entity updates), per channel, per
+/// component and per component field, and retransmitted bits.
+///
+/// Pass `Some(user_key.to_u64())` with `Server::bandwidth_profile`, or
+/// `None` with `Server::global_bandwidth_profile` / `Client::bandwidth_profile`.
+/// Per-entity bits are not emitted: entity ids would make label cardinality
+/// unbounded. Use `BandwidthProfile::top_entities` instead.
+///
+/// Building a profile walks every recorded entity, so call this on a
+/// reporting interval rather than every tick.
+pub fn emit_bandwidth_profile(profile: &BandwidthProfile, user_id: Option<u64>) {
+    let labels = |extra: &[(&'static str, String)]| {
+        let mut labels: Vec<Label> = user_id
+            .map(|id| Label::new("user_id", id.to_string()))
+            .into_iter()
+            .collect();
+        labels.extend(
+            extra
+                .iter()
+                .map(|(key, value)| Label::new(*key, value.clone())),
+        );
+        labels
+    };
+
+    metrics::counter!(names::BANDWIDTH_PACKETS, labels(&[])).absolute(profile.packets);
+
+    let message_bits: u64 = profile.channels.values().map(|bits| bits.bits).sum();
+    for (section, bits) in [
+        ("header", profile.header_bits),
+        ("acks", profile.ack_bits),
+        ("messages", message_bits),
+        ("entity_commands", profile.entity_commands.bits),
+        ("entity_updates", profile.entity_update_bits),
+    ] {
+        metrics::counter!(
+            names::BANDWIDTH_SECTION_BITS,
+            labels(&[("section", section.to_string())])
+        )
+        .absolute(bits);
+    }
+
+    for (channel, bits) in &profile.channels {
+        metrics::counter!(
+            names::BANDWIDTH_CHANNEL_BITS,
+            labels(&[("channel", channel.clone())])
+        )
+        .absolute(bits.bits);
+        metrics::counter!(
+            names::BANDWIDTH_RETRANSMITTED_BITS,
+            labels(&[("channel", channel.clone())])
+        )
+        .absolute(bits.retransmitted_bits);
+    }
+    metrics::counter!(
+        names::BANDWIDTH_RETRANSMITTED_BITS,
+        labels(&[("channel", ENTITY_COMMANDS_LABEL.to_string())])
+    )
+    .absolute(profile.entity_commands.retransmitted_bits);
+
+    for (component, bits) in &profile.components {
+        metrics::counter!(
+            names::BANDWIDTH_COMPONENT_BITS,
+            labels(&[("component", component.clone())])
+        )
+        .absolute(*bits);
+    }
+    for ((component, field), bits) in &profile.fields {
+        metrics::counter!(
+            names::BANDWIDTH_FIELD_BITS,
+            labels(&[
+                ("component", component.clone()),
+                ("field", field.to_string())
+            ])
+        )
+        .absolute(*bits);
+    }
+}
@@ -0,0 +1,44 @@
+use std::collections::BTreeMap;
+
+use metrics::Label;
+use naia_shared::ChannelStats;
+
+use crate::names;
+
+/// Emit per-channel counters and gauges, labelled by channel and, on the
+/// server, by user.
+pub(crate) fn emit_channel_stats(channels: &BTreeMap<String, ChannelStats>, user_id: Option<&str>) {
+    for (channel, stats) in channels {
+        let labels = |extra: Option<(&'static str, &'static str)>| {
+            let mut labels: Vec<Label> = user_id
+                .map(|id| Label::new("user_id", id.to_string()))
+                .into_iter()
+                .collect();
+            labels.push(Label::new("channel", channel.clone()));
+            labels.extend(extra.map(|(key, value)| Label::new(key, value)));
+            labels
+        };
+
+        metrics::counter!(names::CHANNEL_MESSAGES_SENT, labels(None)).absolute(stats.messages_sent);
+        metrics::counter!(names::CHANNEL_MESSAGES_RECEIVED, labels(None))
+            .absolute(stats.messages_received);
+        metrics::counter!(names::CHANNEL_BYTES_SENT, labels(None)).absolute(stats.bytes_sent);
+        metrics::counter!(names::CHANNEL_BYTES_RECEIVED, labels(None))
+            .absolute(stats.bytes_received);
+        metrics::counter!(names::CHANNEL_RETRANSMISSIONS, labels(None))
+            .absolute(stats.retransmissions);
+        metrics::counter!(
+            names::CHANNEL_DROPPED,
+            labels(Some(("reason", "backpressure")))
+        )
+        .absolute(stats.dropped_backpressure);
+        metrics::counter!(names::CHANNEL_DROPPED, labels(Some(("reason", "expired"))))
+            .absolute(stats.dropped_expired);
+        metrics::gauge!(names::CHANNEL_QUEUE_DEPTH, labels(None)).set(stats.queue_depth as f64);
+        if let Some(max_queue_depth) = stats.max_queue_depth {
+            metrics::gauge!(names::CHANNEL_MAX_QUEUE_DEPTH, labels(None))
+                .set(max_queue_depth as f64);
+        }
+        metrics::gauge!(names::CHANNEL_AVG_ACK_MS, labels(None)).set(stats.avg_ack_ms as f64);
+    }
+}
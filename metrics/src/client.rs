@@ -1,9 +1,10 @@
 use naia_shared::ConnectionStats;
-use crate::names;
+use crate::{channel::emit_channel_stats, names};
 
-/// Emit the five client-side connection gauges.
+/// Emit the seven client-side connection gauges, and the `naia_channel_*`
+/// metrics of each channel.
 ///
-/// The client has exactly one connection, so no label is needed.
+/// The client has exactly one connection, so no `user_id` label is needed.
 /// Call once per tick after [`Client::send_all_packets`].
 pub fn emit_client_connection_stats(stats: &ConnectionStats) {
     metrics::gauge!(names::CLIENT_CONN_RTT_MS).set(stats.rtt_ms as f64);
@@ -12,4 +13,6 @@ pub fn emit_client_connection_stats(stats: &ConnectionStats) {
     metrics::gauge!(names::CLIENT_CONN_PACKET_LOSS).set(stats.packet_loss_pct as f64);
     metrics::gauge!(names::CLIENT_CONN_KBPS_SENT).set(stats.kbps_sent as f64);
     metrics::gauge!(names::CLIENT_CONN_KBPS_RECV).set(stats.kbps_recv as f64);
+    metrics::gauge!(names::CLIENT_CONN_KBPS_TARGET).set(stats.kbps_target as f64);
+    emit_channel_stats(&stats.channels, None);
 }
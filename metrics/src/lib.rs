@@ -14,6 +14,7 @@
 //!     server.entity_count(),
 //!     server.room_count(),
 //! );
+//! naia_metrics::emit_server_tick_duration(server.average_tick_duration());
 //! for user_key in server.user_keys() {
 //!     if let Some(stats) = server.connection_stats(&user_key) {
 //!         naia_metrics::emit_server_connection_stats(&stats, user_key.to_u64());
@@ -21,12 +22,48 @@
 //! }
 //! ```
 //!
+//! Connection stats include each channel's traffic, emitted as
+//! `naia_channel_*` metrics labelled by `channel`.
+//!
 //! For Bevy apps, use [`naia-bevy-metrics`] instead — it handles emission
 //! automatically via a plugin.
+//!
+//! # Bandwidth profiles
+//!
+//! With `ConnectionConfig::bandwidth_profiling` set, export where the bits
+//! went on a reporting interval:
+//!
+//! ```rust,ignore
+//! naia_metrics::emit_bandwidth_profile(&server.global_bandwidth_profile(), None);
+//! ```
+//!
+//! # Prometheus endpoint
+//!
+//! With the `prometheus` feature, [`PrometheusExporter::install`] collects
+//! everything above and serves it in the Prometheus text format from a local
+//! HTTP endpoint, with no other exporter needed:
+//!
+//! ```rust,ignore
+//! let exporter = naia_metrics::PrometheusExporter::install(PrometheusConfig::default())?;
+//! // On disconnect, drop the user's series:
+//! exporter.forget_user(user_key.to_u64());
+//! ```
 
 pub mod names;
+mod bandwidth;
+mod channel;
 mod server;
 mod client;
+#[cfg(feature = "prometheus")]
+mod prometheus;
 
-pub use server::{emit_server_aggregates, emit_server_connection_stats};
+pub use bandwidth::{emit_bandwidth_profile, ENTITY_COMMANDS_LABEL};
+pub use server::{
+    emit_server_aggregates, emit_server_connection_stats, emit_server_tick_duration,
+};
 pub use client::emit_client_connection_stats;
+#[cfg(feature = "prometheus")]
+pub use prometheus::{
+    LabelPolicy, PrometheusConfig, PrometheusError, PrometheusExporter, PrometheusRecorder,
+    OTHER_USERS_LABEL,
+};
@@ -3,6 +3,9 @@ pub const SERVER_CONNECTED_USERS: &str = "naia_server_connected_users";
 pub const SERVER_TOTAL_ENTITIES:  &str = "naia_server_total_entities";
 pub const SERVER_TOTAL_ROOMS:     &str = "naia_server_total_rooms";
 
+// Server tick histogram (no label), in milliseconds
+pub const SERVER_TICK_DURATION_MS: &str = "naia_server_tick_duration_ms";
+
 // Server per-connection metrics (label: user_id)
 pub const SERVER_CONN_RTT_MS:      &str = "naia_server_conn_rtt_ms";
 pub const SERVER_CONN_RTT_P99_MS:  &str = "naia_server_conn_rtt_p99_ms";
@@ -10,6 +13,7 @@ pub const SERVER_CONN_JITTER_MS:   &str = "naia_server_conn_jitter_ms";
 pub const SERVER_CONN_PACKET_LOSS: &str = "naia_server_conn_packet_loss";
 pub const SERVER_CONN_KBPS_SENT:   &str = "naia_server_conn_kbps_sent";
 pub const SERVER_CONN_KBPS_RECV:   &str = "naia_server_conn_kbps_recv";
+pub const SERVER_CONN_KBPS_TARGET: &str = "naia_server_conn_kbps_target";
 
 // Server replication counters (no label — server-wide totals)
 pub use naia_shared::{
@@ -20,6 +24,9 @@ pub use naia_shared::{
     SERVER_COMPONENT_REMOVES_TOTAL,
 };
 
+// Packet size histogram, in bytes on the wire (labels: host, direction)
+pub use naia_shared::PACKET_SIZE_BYTES;
+
 // Client connection metrics (no label — one connection per process)
 pub const CLIENT_CONN_RTT_MS:      &str = "naia_client_conn_rtt_ms";
 pub const CLIENT_CONN_RTT_P99_MS:  &str = "naia_client_conn_rtt_p99_ms";
@@ -27,3 +34,25 @@ pub const CLIENT_CONN_JITTER_MS:   &str = "naia_client_conn_jitter_ms";
 pub const CLIENT_CONN_PACKET_LOSS: &str = "naia_client_conn_packet_loss";
 pub const CLIENT_CONN_KBPS_SENT:   &str = "naia_client_conn_kbps_sent";
 pub const CLIENT_CONN_KBPS_RECV:   &str = "naia_client_conn_kbps_recv";
+pub const CLIENT_CONN_KBPS_TARGET: &str = "naia_client_conn_kbps_target";
+
+// Bandwidth profile counters (labels: user_id when per-user, plus the one
+// named on each line)
+pub const BANDWIDTH_PACKETS:              &str = "naia_bandwidth_packets_total";
+pub const BANDWIDTH_SECTION_BITS:         &str = "naia_bandwidth_section_bits_total";       // section
+pub const BANDWIDTH_CHANNEL_BITS:         &str = "naia_bandwidth_channel_bits_total";       // channel
+pub const BANDWIDTH_RETRANSMITTED_BITS:   &str = "naia_bandwidth_retransmitted_bits_total"; // channel
+pub const BANDWIDTH_COMPONENT_BITS:       &str = "naia_bandwidth_component_bits_total";     // component
+pub const BANDWIDTH_FIELD_BITS:           &str = "naia_bandwidth_field_bits_total";         // component, field
+
+// Per-channel metrics (labels: user_id on the server, channel, plus the one
+// named on each line)
+pub const CHANNEL_MESSAGES_SENT:      &str = "naia_channel_messages_sent_total";
+pub const CHANNEL_MESSAGES_RECEIVED:  &str = "naia_channel_messages_received_total";
+pub const CHANNEL_BYTES_SENT:         &str = "naia_channel_bytes_sent_total";
+pub const CHANNEL_BYTES_RECEIVED:     &str = "naia_channel_bytes_received_total";
+pub const CHANNEL_RETRANSMISSIONS:    &str = "naia_channel_retransmissions_total";
+pub const CHANNEL_DROPPED:            &str = "naia_channel_dropped_total";          // reason
+pub const CHANNEL_QUEUE_DEPTH:        &str = "naia_channel_queue_depth";
+pub const CHANNEL_MAX_QUEUE_DEPTH:    &str = "naia_channel_max_queue_depth";
+pub const CHANNEL_AVG_ACK_MS:         &str = "naia_channel_avg_ack_ms";
@@ -0,0 +1,55 @@
+use std::net::TcpListener;
+
+use naia_shared::{serve_http_endpoint, HttpResponse};
+
+use super::PrometheusRecorder;
+
+/// Answers scrapes one connection at a time, for as long as the process runs.
+pub(crate) fn serve(listener: TcpListener, path: &str, recorder: &PrometheusRecorder) {
+    serve_http_endpoint(listener, |target_path| {
+        if target_path == path {
+            HttpResponse::ok("text/plain; version=0.0.4", recorder.render())
+        } else {
+            HttpResponse::not_found()
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{Read, Write},
+        net::TcpStream,
+    };
+
+    use super::*;
+    use crate::prometheus::{PrometheusConfig, PrometheusExporter};
+
+    fn get(exporter: &PrometheusExporter, path: &str) -> String {
+        let mut stream = TcpStream::connect(exporter.local_addr()).unwrap();
+        write!(stream, "GET {path} HTTP/1.1\r\nHost: localhost\r\n\r\n").unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        response
+    }
+
+    #[test]
+    fn serves_metrics_path_only() {
+        let config = PrometheusConfig {
+            listen_addr: ([127, 0, 0, 1], 0).into(),
+            ..Default::default()
+        };
+        let recorder = PrometheusRecorder::new(&config);
+        let exporter = PrometheusExporter::serve(config, recorder.clone()).unwrap();
+        metrics::with_local_recorder(&recorder, || {
+            metrics::gauge!(crate::names::SERVER_CONNECTED_USERS).set(2.0);
+        });
+
+        let response = get(&exporter, "/metrics");
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(response.ends_with("naia_server_connected_users 2\n"));
+
+        let response = get(&exporter, "/other");
+        assert!(response.starts_with("HTTP/1.1 404 Not Found\r\n"));
+    }
+}
@@ -0,0 +1,176 @@
+//! A local HTTP endpoint serving naia's metrics in the Prometheus text
+//! format, for apps that don't bring their own `metrics` exporter.
+//!
+//! [`PrometheusExporter::install`] sets a [`PrometheusRecorder`] as the
+//! global `metrics` recorder and serves it from a background thread. Everything
+//! naia emits through the facade is collected: the `SERVER_*` aggregates and
+//! replication counters, per-connection and per-channel metrics, and the
+//! tick duration and packet size histograms.
+
+mod http;
+mod recorder;
+
+use std::{
+    fmt, io,
+    net::{SocketAddr, TcpListener},
+    thread,
+};
+
+pub use recorder::{PrometheusRecorder, OTHER_USERS_LABEL};
+
+use crate::names;
+
+/// What to do with metrics labelled by `user_id`, which otherwise add a
+/// series per connected user.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LabelPolicy {
+    /// Keep `user_id` labels for up to `max_users` users. Further users share
+    /// series labelled `user_id="other"` until
+    /// [`PrometheusExporter::forget_user`] frees a slot: their counters report
+    /// the highest value and their gauges the last one written.
+    PerUser { max_users: usize },
+    /// Drop every series with a `user_id` label, serving server-wide metrics
+    /// only.
+    ServerOnly,
+}
+
+impl Default for LabelPolicy {
+    fn default() -> Self {
+        Self::PerUser { max_users: 64 }
+    }
+}
+
+/// Configuration of the Prometheus endpoint.
+#[derive(Clone, Debug)]
+pub struct PrometheusConfig {
+    /// Address to listen on. Defaults to `127.0.0.1:9464`, reachable from
+    /// this machine only.
+    pub listen_addr: SocketAddr,
+    /// Path serving the metrics. Other paths get a 404.
+    pub path: String,
+    /// How `user_id` labels are kept.
+    pub label_policy: LabelPolicy,
+    /// Bucket upper bounds of the tick duration histogram, in milliseconds.
+    pub tick_duration_buckets: Vec<f64>,
+    /// Bucket upper bounds of the packet size histogram, in bytes.
+    pub packet_size_buckets: Vec<f64>,
+    /// Bucket upper bounds of any other histogram.
+    pub default_buckets: Vec<f64>,
+}
+
+impl Default for PrometheusConfig {
+    fn default() -> Self {
+        Self {
+            listen_addr: SocketAddr::from(([127, 0, 0, 1], 9464)),
+            path: "/metrics".to_string(),
+            label_policy: LabelPolicy::default(),
+            tick_duration_buckets: vec![1.0, 2.0, 5.0, 10.0, 16.0, 25.0, 33.0, 50.0, 100.0, 250.0],
+            packet_size_buckets: vec![
+                32.0, 64.0, 128.0, 256.0, 384.0, 512.0, 768.0, 1024.0, 1200.0, 1500.0,
+            ],
+            default_buckets: vec![
+                0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+            ],
+        }
+    }
+}
+
+impl PrometheusConfig {
+    pub(crate) fn buckets(&self, name: &str) -> &[f64] {
+        match name {
+            names::SERVER_TICK_DURATION_MS => &self.tick_duration_buckets,
+            names::PACKET_SIZE_BYTES => &self.packet_size_buckets,
+            _ => &self.default_buckets,
+        }
+    }
+}
+
+/// Errors starting the Prometheus endpoint.
+#[derive(Debug)]
+pub enum PrometheusError {
+    /// The listen address couldn't be bound, or the serving thread couldn't
+    /// be spawned.
+    Io(io::Error),
+    /// Another `metrics` recorder is already installed globally.
+    RecorderAlreadyInstalled,
+}
+
+impl fmt::Display for PrometheusError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "Prometheus endpoint failed to start: {error}"),
+            Self::RecorderAlreadyInstalled => {
+                write!(f, "a global metrics recorder is already installed")
+            }
+        }
+    }
+}
+
+impl std::error::Error for PrometheusError {}
+
+impl From<io::Error> for PrometheusError {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+/// A running Prometheus endpoint. The serving thread runs for the rest of the
+/// process.
+pub struct PrometheusExporter {
+    recorder: PrometheusRecorder,
+    local_addr: SocketAddr,
+}
+
+impl PrometheusExporter {
+    /// Installs a [`PrometheusRecorder`] as the global `metrics` recorder and
+    /// starts serving it. Call once at startup, before naia emits anything.
+    pub fn install(config: PrometheusConfig) -> Result<Self, PrometheusError> {
+        let recorder = PrometheusRecorder::new(&config);
+        let listener = TcpListener::bind(config.listen_addr)?;
+        metrics::set_global_recorder(recorder.clone())
+            .map_err(|_| PrometheusError::RecorderAlreadyInstalled)?;
+        Self::spawn(listener, config.path, recorder)
+    }
+
+    /// Starts serving `recorder` without installing it globally, for apps
+    /// that install it themselves, for example behind a fanout recorder.
+    pub fn serve(
+        config: PrometheusConfig,
+        recorder: PrometheusRecorder,
+    ) -> Result<Self, PrometheusError> {
+        let listener = TcpListener::bind(config.listen_addr)?;
+        Self::spawn(listener, config.path, recorder)
+    }
+
+    fn spawn(
+        listener: TcpListener,
+        path: String,
+        recorder: PrometheusRecorder,
+    ) -> Result<Self, PrometheusError> {
+        let local_addr = listener.local_addr()?;
+        let served = recorder.clone();
+        thread::Builder::new()
+            .name("naia-prometheus".to_string())
+            .spawn(move || http::serve(listener, &path, &served))?;
+        Ok(Self {
+            recorder,
+            local_addr,
+        })
+    }
+
+    /// The address the endpoint is listening on.
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    /// The recorder being served.
+    pub fn recorder(&self) -> &PrometheusRecorder {
+        &self.recorder
+    }
+
+    /// Removes a disconnected user's series and frees its slot under
+    /// [`LabelPolicy::PerUser`]. `user_id` is `UserKey::to_u64()`.
+    pub fn forget_user(&self, user_id: u64) {
+        self.recorder.forget_user(user_id);
+    }
+}
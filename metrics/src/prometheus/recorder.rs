@@ -0,0 +1,401 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Write,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use metrics::{
+    Counter, Gauge, Histogram, HistogramFn, Key, KeyName, Metadata, Recorder, SharedString, Unit,
+};
+
+use super::{LabelPolicy, PrometheusConfig};
+
+/// The `user_id` label value shared by users beyond
+/// [`LabelPolicy::PerUser`]'s `max_users`.
+pub const OTHER_USERS_LABEL: &str = "other";
+
+const USER_LABEL: &str = "user_id";
+
+type Labels = Vec<(String, String)>;
+
+/// A `metrics` recorder keeping the latest value of every series, rendered
+/// in the Prometheus text format by [`PrometheusRecorder::render`].
+#[derive(Clone)]
+pub struct PrometheusRecorder {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    config: PrometheusConfig,
+    state: Mutex<State>,
+}
+
+#[derive(Default)]
+struct State {
+    families: BTreeMap<String, Family>,
+    descriptions: BTreeMap<String, String>,
+    users: BTreeSet<String>,
+}
+
+struct Family {
+    kind: Kind,
+    series: BTreeMap<Labels, Series>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Counter,
+    Gauge,
+    Histogram,
+}
+
+impl Kind {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Counter => "counter",
+            Self::Gauge => "gauge",
+            Self::Histogram => "histogram",
+        }
+    }
+}
+
+#[derive(Clone)]
+enum Series {
+    Counter(Arc<AtomicU64>),
+    Gauge(Arc<AtomicU64>),
+    Histogram(Arc<Buckets>),
+}
+
+struct Buckets {
+    bounds: Vec<f64>,
+    counts: Mutex<BucketCounts>,
+}
+
+#[derive(Default)]
+struct BucketCounts {
+    // Per bucket, not cumulative. The last entry counts values above every
+    // bound.
+    counts: Vec<u64>,
+    sum: f64,
+    count: u64,
+}
+
+impl HistogramFn for Buckets {
+    fn record(&self, value: f64) {
+        let bucket = self
+            .bounds
+            .iter()
+            .position(|bound| value <= *bound)
+            .unwrap_or(self.bounds.len());
+        let mut counts = self.counts.lock().unwrap();
+        counts.counts[bucket] += 1;
+        counts.sum += value;
+        counts.count += 1;
+    }
+}
+
+impl PrometheusRecorder {
+    /// Creates an empty recorder with `config`'s label policy and buckets.
+    pub fn new(config: &PrometheusConfig) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                config: config.clone(),
+                state: Mutex::new(State::default()),
+            }),
+        }
+    }
+
+    /// Removes a disconnected user's series and frees its slot under
+    /// [`LabelPolicy::PerUser`]. `user_id` is `UserKey::to_u64()`.
+    pub fn forget_user(&self, user_id: u64) {
+        let user_id = user_id.to_string();
+        let mut state = self.inner.state.lock().unwrap();
+        state.users.remove(&user_id);
+        for family in state.families.values_mut() {
+            family.series.retain(|labels, _| {
+                !labels
+                    .iter()
+                    .any(|(key, value)| key == USER_LABEL && *value == user_id)
+            });
+        }
+    }
+
+    /// Every series in the Prometheus text exposition format.
+    pub fn render(&self) -> String {
+        let state = self.inner.state.lock().unwrap();
+        let mut output = String::new();
+        for (name, family) in &state.families {
+            if family.series.is_empty() {
+                continue;
+            }
+            if let Some(description) = state.descriptions.get(name) {
+                let _ = writeln!(output, "# HELP {name} {}", escape_help(description));
+            }
+            let _ = writeln!(output, "# TYPE {name} {}", family.kind.as_str());
+            for (labels, series) in &family.series {
+                match series {
+                    Series::Counter(value) => {
+                        let value = value.load(Ordering::Acquire);
+                        let _ = writeln!(output, "{name}{} {value}", format_labels(labels, None));
+                    }
+                    Series::Gauge(value) => {
+                        let value = f64::from_bits(value.load(Ordering::Acquire));
+                        let _ = writeln!(
+                            output,
+                            "{name}{} {}",
+                            format_labels(labels, None),
+                            format_value(value)
+                        );
+                    }
+                    Series::Histogram(buckets) => {
+                        render_histogram(&mut output, name, labels, buckets)
+                    }
+                }
+            }
+        }
+        output
+    }
+
+    fn describe(&self, key: KeyName, description: SharedString) {
+        let mut state = self.inner.state.lock().unwrap();
+        state
+            .descriptions
+            .insert(key.as_str().to_string(), description.into_owned());
+    }
+
+    fn register(&self, key: &Key, kind: Kind) -> Option<Series> {
+        let mut state = self.inner.state.lock().unwrap();
+        let labels = self.labels(key, &mut state.users)?;
+        let family = state
+            .families
+            .entry(key.name().to_string())
+            .or_insert_with(|| Family {
+                kind,
+                series: BTreeMap::new(),
+            });
+        // A name registered as another kind can't be rendered alongside it
+        if family.kind != kind {
+            return None;
+        }
+        let series = family.series.entry(labels).or_insert_with(|| match kind {
+            Kind::Counter => Series::Counter(Arc::new(AtomicU64::new(0))),
+            Kind::Gauge => Series::Gauge(Arc::new(AtomicU64::new(0.0_f64.to_bits()))),
+            Kind::Histogram => {
+                let bounds = self.inner.config.buckets(key.name()).to_vec();
+                let counts = BucketCounts {
+                    counts: vec![0; bounds.len() + 1],
+                    ..Default::default()
+                };
+                Series::Histogram(Arc::new(Buckets {
+                    bounds,
+                    counts: Mutex::new(counts),
+                }))
+            }
+        });
+        Some(series.clone())
+    }
+
+    /// The key's labels after applying the label policy, or `None` if the
+    /// series is dropped.
+    fn labels(&self, key: &Key, users: &mut BTreeSet<String>) -> Option<Labels> {
+        let mut labels = Labels::new();
+        for label in key.labels() {
+            let mut value = label.value().to_string();
+            if label.key() == USER_LABEL {
+                match self.inner.config.label_policy {
+                    LabelPolicy::ServerOnly => return None,
+                    LabelPolicy::PerUser { max_users } => {
+                        if !users.contains(&value) {
+                            if users.len() < max_users {
+                                users.insert(value.clone());
+                            } else {
+                                value = OTHER_USERS_LABEL.to_string();
+                            }
+                        }
+                    }
+                }
+            }
+            labels.push((label.key().to_string(), value));
+        }
+        Some(labels)
+    }
+}
+
+impl Recorder for PrometheusRecorder {
+    fn describe_counter(&self, key: KeyName, _unit: Option<Unit>, description: SharedString) {
+        self.describe(key, description);
+    }
+
+    fn describe_gauge(&self, key: KeyName, _unit: Option<Unit>, description: SharedString) {
+        self.describe(key, description);
+    }
+
+    fn describe_histogram(&self, key: KeyName, _unit: Option<Unit>, description: SharedString) {
+        self.describe(key, description);
+    }
+
+    fn register_counter(&self, key: &Key, _metadata: &Metadata<'_>) -> Counter {
+        match self.register(key, Kind::Counter) {
+            Some(Series::Counter(value)) => Counter::from_arc(value),
+            _ => Counter::noop(),
+        }
+    }
+
+    fn register_gauge(&self, key: &Key, _metadata: &Metadata<'_>) -> Gauge {
+        match self.register(key, Kind::Gauge) {
+            Some(Series::Gauge(value)) => Gauge::from_arc(value),
+            _ => Gauge::noop(),
+        }
+    }
+
+    fn register_histogram(&self, key: &Key, _metadata: &Metadata<'_>) -> Histogram {
+        match self.register(key, Kind::Histogram) {
+            Some(Series::Histogram(buckets)) => Histogram::from_arc(buckets),
+            _ => Histogram::noop(),
+        }
+    }
+}
+
+fn render_histogram(output: &mut String, name: &str, labels: &Labels, buckets: &Buckets) {
+    let counts = buckets.counts.lock().unwrap();
+    let mut cumulative = 0;
+    for (bound, count) in buckets.bounds.iter().zip(&counts.counts) {
+        cumulative += count;
+        let le = format_value(*bound);
+        let _ = writeln!(
+            output,
+            "{name}_bucket{} {cumulative}",
+            format_labels(labels, Some(&le))
+        );
+    }
+    let _ = writeln!(
+        output,
+        "{name}_bucket{} {}",
+        format_labels(labels, Some("+Inf")),
+        counts.count
+    );
+    let _ = writeln!(
+        output,
+        "{name}_sum{} {}",
+        format_labels(labels, None),
+        format_value(counts.sum)
+    );
+    let _ = writeln!(
+        output,
+        "{name}_count{} {}",
+        format_labels(labels, None),
+        counts.count
+    );
+}
+
+fn format_labels(labels: &Labels, le: Option<&str>) -> String {
+    if labels.is_empty() && le.is_none() {
+        return String::new();
+    }
+    let mut pairs: Vec<String> = labels
+        .iter()
+        .map(|(key, value)| format!("{key}=\"{}\"", escape_label(value)))
+        .collect();
+    if let Some(le) = le {
+        pairs.push(format!("le=\"{le}\""));
+    }
+    format!("{{{}}}", pairs.join(","))
+}
+
+fn format_value(value: f64) -> String {
+    if value.is_nan() {
+        "NaN".to_string()
+    } else if value == f64::INFINITY {
+        "+Inf".to_string()
+    } else if value == f64::NEG_INFINITY {
+        "-Inf".to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+fn escape_help(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn recorder(label_policy: LabelPolicy) -> PrometheusRecorder {
+        PrometheusRecorder::new(&PrometheusConfig {
+            label_policy,
+            default_buckets: vec![1.0, 10.0],
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn renders_counters_gauges_and_histograms() {
+        let recorder = recorder(LabelPolicy::default());
+        metrics::with_local_recorder(&recorder, || {
+            metrics::describe_gauge!("users", "Connected users");
+            metrics::gauge!("users").set(3.0);
+            metrics::counter!("sent_total", "channel" => "a\"b").increment(2);
+            metrics::histogram!("latency").record(0.5);
+            metrics::histogram!("latency").record(5.0);
+            metrics::histogram!("latency").record(50.0);
+        });
+
+        let output = recorder.render();
+        assert!(output.contains("# HELP users Connected users\n# TYPE users gauge\nusers 3\n"));
+        assert!(output.contains("# TYPE sent_total counter\nsent_total{channel=\"a\\\"b\"} 2\n"));
+        assert!(output.contains("latency_bucket{le=\"1\"} 1\n"));
+        assert!(output.contains("latency_bucket{le=\"10\"} 2\n"));
+        assert!(output.contains("latency_bucket{le=\"+Inf\"} 3\n"));
+        assert!(output.contains("latency_sum 55.5\nlatency_count 3\n"));
+    }
+
+    #[test]
+    fn per_user_policy_bounds_cardinality() {
+        let recorder = recorder(LabelPolicy::PerUser { max_users: 2 });
+        metrics::with_local_recorder(&recorder, || {
+            for user_id in 1..=4 {
+                metrics::gauge!("rtt", "user_id" => user_id.to_string()).set(user_id as f64);
+            }
+        });
+
+        let output = recorder.render();
+        assert!(output.contains("rtt{user_id=\"1\"} 1\n"));
+        assert!(output.contains("rtt{user_id=\"2\"} 2\n"));
+        assert!(output.contains("rtt{user_id=\"other\"} 4\n"));
+        assert!(!output.contains("user_id=\"3\""));
+
+        // Forgetting a user frees its slot for the next one
+        recorder.forget_user(1);
+        metrics::with_local_recorder(&recorder, || {
+            metrics::gauge!("rtt", "user_id" => "5").set(5.0);
+        });
+        let output = recorder.render();
+        assert!(!output.contains("user_id=\"1\""));
+        assert!(output.contains("rtt{user_id=\"5\"} 5\n"));
+    }
+
+    #[test]
+    fn server_only_policy_drops_user_series() {
+        let recorder = recorder(LabelPolicy::ServerOnly);
+        metrics::with_local_recorder(&recorder, || {
+            metrics::gauge!("rtt", "user_id" => "1").set(1.0);
+            metrics::gauge!("users").set(1.0);
+        });
+
+        let output = recorder.render();
+        assert!(!output.contains("rtt"));
+        assert!(output.contains("users 1\n"));
+    }
+}
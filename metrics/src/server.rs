@@ -1,5 +1,7 @@
+use std::time::Duration;
+
 use naia_shared::ConnectionStats;
-use crate::names;
+use crate::{channel::emit_channel_stats, names};
 
 /// Emit the three server-wide aggregate gauges.
 ///
@@ -10,7 +12,16 @@ pub fn emit_server_aggregates(user_count: usize, entity_count: usize, room_count
     metrics::gauge!(names::SERVER_TOTAL_ROOMS).set(room_count as f64);
 }
 
-/// Emit the six per-connection gauges for one user.
+/// Record the server's tick duration in the tick duration histogram.
+///
+/// Pass [`Server::average_tick_duration`] once per tick.
+pub fn emit_server_tick_duration(average_tick_duration: Duration) {
+    metrics::histogram!(names::SERVER_TICK_DURATION_MS)
+        .record(average_tick_duration.as_secs_f64() * 1000.0);
+}
+
+/// Emit the seven per-connection gauges for one user, and the
+/// `naia_channel_*` metrics of each of its channels.
 ///
 /// `user_id` is `UserKey::to_u64()`. Call once per connected user per tick.
 pub fn emit_server_connection_stats(stats: &ConnectionStats, user_id: u64) {
@@ -20,5 +31,7 @@ pub fn emit_server_connection_stats(stats: &ConnectionStats, user_id: u64) {
     metrics::gauge!(names::SERVER_CONN_JITTER_MS,   "user_id" => id.clone()).set(stats.jitter_ms as f64);
     metrics::gauge!(names::SERVER_CONN_PACKET_LOSS, "user_id" => id.clone()).set(stats.packet_loss_pct as f64);
     metrics::gauge!(names::SERVER_CONN_KBPS_SENT,   "user_id" => id.clone()).set(stats.kbps_sent as f64);
-    metrics::gauge!(names::SERVER_CONN_KBPS_RECV,   "user_id" => id).set(stats.kbps_recv as f64);
+    metrics::gauge!(names::SERVER_CONN_KBPS_RECV,   "user_id" => id.clone()).set(stats.kbps_recv as f64);
+    metrics::gauge!(names::SERVER_CONN_KBPS_TARGET, "user_id" => id.clone()).set(stats.kbps_target as f64);
+    emit_channel_stats(&stats.channels, Some(&id));
 }
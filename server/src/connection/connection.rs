@@ -1,13 +1,14 @@
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
 use std::{hash::Hash, net::SocketAddr};
 
 use log::warn;
 
 use naia_shared::{
-    BaseConnection, BigMapKey, BitReader, BitWriter, ChannelKinds, ComponentKind, ComponentKinds,
+    AuthorityGroupRequest, BaseConnection, BigMapKey, BitReader, BitWriter, ChannelKinds, ChannelStats, ComponentKind, ComponentKinds,
     ConnectionConfig, EntityAndGlobalEntityConverter, EntityCommand, EntityEvent, GlobalEntity,
-    GlobalEntitySpawner, HostType, Instant, MessageIndex, MessageKinds, OutgoingPriorityHook,
-    PacketType, Serde, SerdeErr, StandardHeader, Tick, Timer, WorldMutType, WorldRefType, MTU_SIZE_BYTES,
+    GlobalEntitySpawner, HostType, Instant, MessageContainer, MessageIndex, MessageKind, MessageKinds,
+    OutgoingPriorityHook,
+    PacketType, Serde, SerdeErr, StandardHeader, Tick, Timer, UpdateValidator, WorldMutType, WorldRefType, MTU_SIZE_BYTES,
 };
 
 use crate::{
@@ -68,6 +69,8 @@ pub struct Connection {
     tick_buffer: TickBufferReceiver,
     pub manual_disconnect: bool,
     timeout_timer: Timer,
+    // Received group authority requests, awaiting the world server
+    authority_group_requests: Vec<Vec<Option<GlobalEntity>>>,
 }
 
 impl Connection {
@@ -94,6 +97,7 @@ impl Connection {
             tick_buffer: TickBufferReceiver::new(channel_kinds),
             manual_disconnect: false,
             timeout_timer: Timer::new(connection_config.disconnection_timeout_duration),
+            authority_group_requests: Vec::new(),
         }
     }
 
@@ -163,19 +167,23 @@ impl Connection {
         Ok(())
     }
 
-    /// Receive & process stored packet data
+    /// Receive & process stored packet data. A `read_only` connection's
+    /// messages, requests and entities are dropped; only its authority
+    /// requests are processed, so they can be answered.
     #[allow(clippy::too_many_arguments)]
     pub fn process_packets<E: Copy + Eq + Hash + Send + Sync, W: WorldMutType<E>>(
         &mut self,
         message_kinds: &MessageKinds,
         component_kinds: &ComponentKinds,
         client_authoritative_entities: bool,
+        read_only: bool,
         now: &Instant,
         global_entity_map: &mut dyn GlobalEntitySpawner<E>,
         global_world_manager: &mut GlobalWorldManager,
         global_request_manager: &mut GlobalRequestManager,
         global_response_manager: &mut GlobalResponseManager,
         world: &mut W,
+        validator: Option<&mut dyn UpdateValidator>,
         incoming_events: &mut WorldEvents<E>,
     ) -> Vec<EntityEvent> {
         // Receive Message Events
@@ -187,8 +195,16 @@ impl Connection {
             entity_converter,
             entity_waitlist,
         );
+        let group_request_kind = MessageKind::of::<AuthorityGroupRequest>();
         for (channel_kind, messages) in messages {
             for message in messages {
+                if message.kind() == group_request_kind {
+                    self.receive_authority_group_request(message);
+                    continue;
+                }
+                if read_only {
+                    continue;
+                }
                 incoming_events.push_message(&self.user_key, &channel_kind, message);
             }
         }
@@ -197,6 +213,9 @@ impl Connection {
         let (requests, responses) = self.base.message_manager.receive_requests_and_responses();
         // Requests
         for (channel_kind, requests) in requests {
+            if read_only {
+                continue;
+            }
             for (local_response_id, request) in requests {
                 let global_response_id = global_response_manager.create_response_id(
                     &self.user_key,
@@ -217,19 +236,41 @@ impl Connection {
         }
 
         // Receive World Events
-        if client_authoritative_entities {
+        if !client_authoritative_entities {
+            Vec::new()
+        } else if read_only {
+            self.base.world_manager.take_incoming_host_events(
+                global_entity_map,
+                global_world_manager,
+                world,
+            )
+        } else {
             self.base.world_manager.take_incoming_events(
                 global_entity_map,
                 global_world_manager,
                 component_kinds,
                 world,
+                validator,
                 now,
             )
-        } else {
-            Vec::new()
         }
     }
 
+    fn receive_authority_group_request(&mut self, message: MessageContainer) {
+        let Ok(request) = message.to_boxed_any().downcast::<AuthorityGroupRequest>() else {
+            return;
+        };
+        match request.read_entities(self.base.world_manager.entity_converter()) {
+            Ok(global_entities) => self.authority_group_requests.push(global_entities),
+            Err(_) => warn!("Failed to read authority group request from client"),
+        }
+    }
+
+    /// Drains the group authority requests received since the last call
+    pub fn take_authority_group_requests(&mut self) -> Vec<Vec<Option<GlobalEntity>>> {
+        std::mem::take(&mut self.authority_group_requests)
+    }
+
     pub fn tick_buffer_messages(&mut self, tick: &Tick, messages: &mut TickBufferMessages) {
         let channel_messages = self.tick_buffer.receive_messages(tick);
         for (channel_kind, received_messages) in channel_messages {
@@ -239,6 +280,13 @@ impl Connection {
         }
     }
 
+    /// Returns the traffic of every channel, by channel protocol name
+    pub fn channel_stats(&self, channel_kinds: &ChannelKinds) -> BTreeMap<String, ChannelStats> {
+        let mut stats = self.base.message_manager.channel_stats(channel_kinds);
+        stats.extend(self.tick_buffer.channel_stats(channel_kinds));
+        stats
+    }
+
     // Outgoing data
     #[allow(clippy::too_many_arguments)]
     pub fn send_packets<E: Copy + Eq + Hash + Send + Sync, W: WorldRefType<E>>(
@@ -254,6 +302,16 @@ impl Connection {
         time_manager: &TimeManager,
         priority_hook: &mut dyn OutgoingPriorityHook,
     ) {
+        let span = naia_shared::naia_span!(
+            DEBUG,
+            "send_user_packets",
+            user = self.user_key.to_u64();
+            packets,
+            bytes,
+            entity_commands,
+            entity_updates
+        );
+        let bytes_before = io.outgoing_bytes_last_tick();
         let rtt_millis = self.ping_manager.rtt_average;
 
         #[cfg(feature = "bench_instrumentation")]
@@ -286,7 +344,13 @@ impl Connection {
         }
 
         // Phase A: tick the outbound token-bucket bandwidth accumulator
-        // before the send cycle. Refreshes budget + one-packet overshoot.
+        // before the send cycle. Refreshes budget + one-packet overshoot,
+        // after letting the congestion controller (if enabled) set the rate.
+        self.base.update_congestion_control(
+            now,
+            self.ping_manager.rtt_average,
+            self.ping_manager.jitter_average,
+        );
         self.base.accumulate_bandwidth(now);
 
         // Phase B: advance the per-user priority accumulator for every dirty
@@ -307,7 +371,8 @@ impl Connection {
 
         #[cfg(feature = "bench_instrumentation")]
         let t = std::time::Instant::now();
-        let mut any_sent = false;
+        let commands_before = host_world_events.len();
+        let mut packets = 0;
         loop {
             if self.send_packet(
                 channel_kinds,
@@ -323,12 +388,12 @@ impl Connection {
                 &mut update_events,
                 Some(&entity_priority_order),
             ) {
-                any_sent = true;
+                packets += 1;
             } else {
                 break;
             }
         }
-        if any_sent {
+        if packets > 0 {
             self.base.mark_sent();
         }
 
@@ -336,14 +401,24 @@ impl Connection {
         // no longer in `update_events` had its bundle fully drained onto the
         // wire — apply the canonical reset-on-send rule (III.7.5).
         let current_tick = time_manager.current_tick();
+        let mut entity_updates = 0;
         for entity in &initial_dirty {
             if !update_events.contains_key(entity) {
                 priority_hook.reset_after_send(entity, current_tick as u32);
+                entity_updates += 1;
             }
         }
         #[cfg(feature = "bench_instrumentation")]
         bench_send_counters::NS_SEND_PACKET_LOOP
             .fetch_add(t.elapsed().as_nanos() as u64, std::sync::atomic::Ordering::Relaxed);
+
+        span.record("packets", packets);
+        span.record("bytes", io.outgoing_bytes_last_tick() - bytes_before);
+        span.record(
+            "entity_commands",
+            commands_before.saturating_sub(host_world_events.len()) as u64,
+        );
+        span.record("entity_updates", entity_updates);
     }
 
     /// Send any message, component actions and component updates to the client
@@ -390,6 +465,8 @@ impl Connection {
             false.ser(&mut writer); // Updates finish bit
             false.ser(&mut writer); // Actions finish bit
 
+            self.base.profile_packet(&writer);
+
             // send packet
             if io.send_packet(&self.address, writer.to_packet()).is_err() {
                 warn!(
@@ -432,6 +509,8 @@ impl Connection {
                 entity_priority_order,
             );
 
+            self.base.profile_packet(&writer);
+
             // send packet, measuring actual size before the move so we can
             // spend exactly what went on the wire.
             let packet = writer.to_packet();
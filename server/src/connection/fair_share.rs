@@ -0,0 +1,59 @@
+/// Splits an aggregate outbound budget between connections by max-min
+/// fairness (water-filling): a connection that asks for less than an equal
+/// share gets everything it asks for, and what it leaves unused is divided
+/// equally among the rest.
+///
+/// Returns one cap per entry of `desired`, in the same order, or `None` if
+/// the desired rates already fit within `total` and no cap is needed.
+pub fn fair_shares(total: f64, desired: &[f64]) -> Option<Vec<f64>> {
+    if desired.iter().sum::<f64>() <= total {
+        return None;
+    }
+
+    let mut order: Vec<usize> = (0..desired.len()).collect();
+    order.sort_by(|a, b| desired[*a].total_cmp(&desired[*b]));
+
+    let mut shares = vec![0.0; desired.len()];
+    let mut remaining = total.max(0.0);
+    for (served, index) in order.iter().enumerate() {
+        let equal_share = remaining / (desired.len() - served) as f64;
+        let share = desired[*index].min(equal_share);
+        shares[*index] = share;
+        remaining -= share;
+    }
+    Some(shares)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::fair_shares;
+
+    #[test]
+    fn no_caps_when_demand_fits() {
+        assert_eq!(fair_shares(100.0, &[30.0, 40.0, 30.0]), None);
+    }
+
+    #[test]
+    fn equal_demands_split_equally() {
+        assert_eq!(
+            fair_shares(90.0, &[64.0, 64.0, 64.0]),
+            Some(vec![30.0, 30.0, 30.0])
+        );
+    }
+
+    #[test]
+    fn small_demands_are_met_and_leftover_is_shared() {
+        // 10 fits under the 40 equal share; the other two split the 110 left
+        assert_eq!(
+            fair_shares(120.0, &[100.0, 10.0, 200.0]),
+            Some(vec![55.0, 10.0, 55.0])
+        );
+    }
+
+    #[test]
+    fn shares_never_exceed_total() {
+        let shares = fair_shares(1000.0, &[400.0, 900.0, 50.0, 300.0]).unwrap();
+        assert!(shares.iter().sum::<f64>() <= 1000.0 + f64::EPSILON);
+        assert_eq!(shares[2], 50.0);
+    }
+}
@@ -1,6 +1,9 @@
-use std::{net::SocketAddr, panic, time::Duration};
+use std::{collections::HashMap, net::SocketAddr, panic, time::Duration};
 
-use naia_shared::{CompressionConfig, Decoder, Encoder, OutgoingPacket, OwnedBitReader};
+use naia_shared::{
+    record_packet_received, record_packet_sent, CompressionMode, Decoder, Encoder, HostType,
+    OutgoingPacket, OwnedBitReader, PacketCodec,
+};
 
 use super::bandwidth_monitor::BandwidthMonitor;
 use crate::{
@@ -16,6 +19,9 @@ pub struct Io {
     incoming_bandwidth_monitor: Option<BandwidthMonitor>,
     outgoing_encoder: Option<Encoder>,
     incoming_decoder: Option<Decoder>,
+    /// Server-to-client codec per address, as negotiated in the handshake.
+    /// Addresses without one are sent uncompressed packets.
+    codecs: HashMap<SocketAddr, PacketCodec>,
     /// Bytes sent during the most recent `send_all_packets` tick.
     /// Reset at the start of each `send_all_packets` via
     /// `reset_outgoing_bytes_this_tick`, incremented in `send_packet`.
@@ -24,25 +30,19 @@ pub struct Io {
 }
 
 impl Io {
+    /// `outgoing_compression` / `incoming_compression` are the modes of the
+    /// server-to-client and client-to-server directions; `None` sends or
+    /// reads packets as-is.
     pub fn new(
         bandwidth_measure_duration: &Option<Duration>,
-        compression_config: &Option<CompressionConfig>,
+        outgoing_compression: Option<&CompressionMode>,
+        incoming_compression: Option<&CompressionMode>,
     ) -> Self {
         let outgoing_bandwidth_monitor = bandwidth_measure_duration.map(BandwidthMonitor::new);
         let incoming_bandwidth_monitor = bandwidth_measure_duration.map(BandwidthMonitor::new);
 
-        let outgoing_encoder = compression_config.as_ref().and_then(|config| {
-            config
-                .server_to_client
-                .as_ref()
-                .map(|mode| Encoder::new(mode.clone()))
-        });
-        let incoming_decoder = compression_config.as_ref().and_then(|config| {
-            config
-                .client_to_server
-                .as_ref()
-                .map(|mode| Decoder::new(mode.clone()))
-        });
+        let outgoing_encoder = outgoing_compression.map(|mode| Encoder::new(mode.clone()));
+        let incoming_decoder = incoming_compression.map(|mode| Decoder::new(mode.clone()));
 
         Self {
             packet_sender: None,
@@ -51,6 +51,7 @@ impl Io {
             incoming_bandwidth_monitor,
             outgoing_encoder,
             incoming_decoder,
+            codecs: HashMap::new(),
             outgoing_bytes_this_tick: 0,
         }
     }
@@ -89,14 +90,16 @@ impl Io {
         let mut payload = packet.slice();
 
         // Compression
+        let codec = self.codec(address);
         if let Some(encoder) = &mut self.outgoing_encoder {
-            payload = encoder.encode(payload);
+            payload = encoder.encode(payload, codec);
         }
 
         // Bandwidth monitoring
         if let Some(monitor) = &mut self.outgoing_bandwidth_monitor {
             monitor.record_packet(address, payload.len());
         }
+        record_packet_sent(HostType::Server, payload.len());
 
         // Per-tick byte counter (always tracked; cheap)
         self.outgoing_bytes_this_tick =
@@ -122,6 +125,7 @@ impl Io {
                 if let Some(monitor) = &mut self.incoming_bandwidth_monitor {
                     monitor.record_packet(&address, payload.len());
                 }
+                record_packet_received(HostType::Server, payload.len());
 
                 // Decompression
                 if let Some(decoder) = &mut self.incoming_decoder {
@@ -135,6 +139,33 @@ impl Io {
         }
     }
 
+    /// Sets the codec negotiated for packets to `address`
+    pub fn set_codec(&mut self, address: &SocketAddr, codec: PacketCodec) {
+        self.codecs.insert(*address, codec);
+    }
+
+    /// Forgets the codec of a disconnected `address`
+    pub fn remove_codec(&mut self, address: &SocketAddr) {
+        self.codecs.remove(address);
+    }
+
+    /// The codec for packets to `address`
+    pub fn codec(&self, address: &SocketAddr) -> PacketCodec {
+        self.codecs.get(address).copied().unwrap_or_default()
+    }
+
+    /// Takes the dictionary trained from outgoing packets in
+    /// `CompressionMode::Training`, once training has completed
+    pub fn take_trained_dictionary(&mut self) -> Option<Vec<u8>> {
+        self.outgoing_encoder.as_mut()?.take_trained_dictionary()
+    }
+
+    /// Fraction of the training sample target recorded so far, in
+    /// `CompressionMode::Training`
+    pub fn dictionary_training_progress(&self) -> Option<f32> {
+        self.outgoing_encoder.as_ref()?.training_progress()
+    }
+
     pub fn bandwidth_monitor_enabled(&self) -> bool {
         self.outgoing_bandwidth_monitor.is_some() && self.incoming_bandwidth_monitor.is_some()
     }
@@ -1,6 +1,7 @@
 pub mod bandwidth_monitor;
 #[allow(clippy::module_inception)]
 pub mod connection;
+pub mod fair_share;
 pub mod io;
 pub mod ping_config;
 pub mod ping_manager;
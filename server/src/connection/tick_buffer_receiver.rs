@@ -1,8 +1,8 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use naia_shared::{
-    BitReader, ChannelKind, ChannelKinds, ChannelMode, LocalEntityAndGlobalEntityConverter,
-    MessageContainer, MessageKinds, Serde, SerdeErr, Tick,
+    BitReader, ChannelKind, ChannelKinds, ChannelMode, ChannelStats,
+    LocalEntityAndGlobalEntityConverter, MessageContainer, MessageKinds, Serde, SerdeErr, Tick,
 };
 
 use crate::connection::tick_buffer_receiver_channel::TickBufferReceiverChannel;
@@ -45,12 +45,15 @@ impl TickBufferReceiver {
                 break;
             }
 
+            let start_bits = reader.bits_read() - 1;
+
             // read channel index
             let channel_kind = ChannelKind::de(channel_kinds, reader)?;
 
             // continue read inside channel
             let channel = self.channel_receivers.get_mut(&channel_kind).unwrap();
             channel.read_messages(converter, message_kinds, host_tick, remote_tick, reader)?;
+            channel.record_bits_received(reader.bits_read() - start_bits);
         }
 
         Ok(())
@@ -84,4 +87,19 @@ impl TickBufferReceiver {
         }
         output
     }
+
+    /// Returns the traffic of every tick-buffered channel, by channel
+    /// protocol name.
+    pub fn channel_stats(&self, channel_kinds: &ChannelKinds) -> BTreeMap<String, ChannelStats> {
+        self.channel_receivers
+            .iter()
+            .map(|(channel_kind, channel)| {
+                let name = channel_kinds
+                    .channel_name(channel_kind)
+                    .unwrap_or("unknown")
+                    .to_string();
+                (name, channel.channel_stats())
+            })
+            .collect()
+    }
 }
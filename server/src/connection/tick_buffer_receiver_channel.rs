@@ -1,9 +1,9 @@
 use std::collections::{HashMap, VecDeque};
 
 use naia_shared::{
-    sequence_greater_than, BitReader, LocalEntityAndGlobalEntityConverter, MessageContainer,
-    MessageKinds, Serde, SerdeErr, ShortMessageIndex, Tick, TickBufferSettings,
-    UnsignedVariableInteger,
+    sequence_greater_than, BitReader, ChannelCounters, ChannelStats,
+    LocalEntityAndGlobalEntityConverter, MessageContainer, MessageKinds, Serde, SerdeErr,
+    ShortMessageIndex, Tick, TickBufferSettings, UnsignedVariableInteger,
 };
 
 /// Receive updates from the client and store them in a buffer along with the corresponding
@@ -11,6 +11,7 @@ use naia_shared::{
 pub struct TickBufferReceiverChannel {
     settings: TickBufferSettings,
     incoming_messages: IncomingMessages,
+    counters: ChannelCounters,
 }
 
 impl TickBufferReceiverChannel {
@@ -18,12 +19,25 @@ impl TickBufferReceiverChannel {
         Self {
             settings,
             incoming_messages: IncomingMessages::new(),
+            counters: ChannelCounters::new(),
         }
     }
 
     /// Read the stored buffer-data corresponding to the given [`Tick`]
     pub fn receive_messages(&mut self, host_tick: &Tick) -> Vec<MessageContainer> {
-        self.incoming_messages.collect(host_tick)
+        let (messages, expired) = self.incoming_messages.collect(host_tick);
+        self.counters.record_received(messages.len());
+        self.counters.record_dropped_expired(expired);
+        messages
+    }
+
+    pub fn record_bits_received(&mut self, bits: u32) {
+        self.counters.record_bits_received(bits);
+    }
+
+    pub fn channel_stats(&self) -> ChannelStats {
+        self.counters
+            .stats(None, Some(self.settings.message_capacity))
     }
 
     /// Directly insert a message into the tick buffer (test_utils only)
@@ -209,8 +223,10 @@ impl IncomingMessages {
         }
     }
 
-    /// Delete from the buffer all data that is older than the provided [`Tick`]
-    fn prune_outdated_commands(&mut self, host_tick: &Tick) {
+    /// Delete from the buffer all data that is older than the provided
+    /// [`Tick`], returning how many messages were deleted
+    fn prune_outdated_commands(&mut self, host_tick: &Tick) -> usize {
+        let mut pruned = 0;
         loop {
             let mut pop = false;
             if let Some((front_tick, _)) = self.buffer.front() {
@@ -219,16 +235,20 @@ impl IncomingMessages {
                 }
             }
             if pop {
-                self.buffer.pop_front();
+                if let Some((_, messages)) = self.buffer.pop_front() {
+                    pruned += messages.len();
+                }
             } else {
                 break;
             }
         }
+        pruned
     }
 
-    /// Retrieve from the buffer data corresponding to the provided [`Tick`]
-    pub fn collect(&mut self, host_tick: &Tick) -> Vec<MessageContainer> {
-        self.prune_outdated_commands(host_tick);
+    /// Retrieve from the buffer data corresponding to the provided [`Tick`],
+    /// along with how many messages for earlier ticks were never retrieved
+    pub fn collect(&mut self, host_tick: &Tick) -> (Vec<MessageContainer>, usize) {
+        let expired = self.prune_outdated_commands(host_tick);
 
         // now get the newest applicable command
         let mut output = Vec::new();
@@ -246,6 +266,6 @@ impl IncomingMessages {
             }
         }
 
-        output
+        (output, expired)
     }
 }
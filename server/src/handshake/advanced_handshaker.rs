@@ -5,7 +5,8 @@ use ring::{hmac, rand};
 
 use naia_shared::{
     handshake::{HandshakeHeader, RejectReason},
-    BitReader, BitWriter, OutgoingPacket, PacketType, ProtocolId, Serde, SerdeErr, StandardHeader,
+    BitReader, BitWriter, CompressionCapabilities, OutgoingPacket, PacketType, ProtocolId, Serde,
+    SerdeErr, StandardHeader,
 };
 
 use crate::{
@@ -22,6 +23,8 @@ pub struct HandshakeManager {
     authenticated_unidentified_users: HashMap<IdentityToken, UserKey>,
     identity_token_map: HashMap<UserKey, IdentityToken>,
     been_handshaked_users: HashMap<SocketAddr, UserKey>,
+    compression_capabilities: CompressionCapabilities,
+    client_compression: HashMap<UserKey, CompressionCapabilities>,
 
     connection_hash_key: hmac::Key,
     // Bounded LRU cache; caps at MAX_PENDING_CONNECTIONS to prevent OOM from
@@ -63,6 +66,7 @@ impl Handshaker for HandshakeManager {
             // to ensure been_handshaked_users doesn't leak on pre-finalization drops.
             self.been_handshaked_users.retain(|_, v| v != user_key);
         }
+        self.client_compression.remove(user_key);
     }
 
     fn maintain_handshake(
@@ -81,7 +85,9 @@ impl Handshaker for HandshakeManager {
                         Self::write_reject_response(RejectReason::ProtocolMismatch).to_packet();
                     return Ok(HandshakeAction::SendPacket(reject_response));
                 }
-                if let Ok((timestamp, id_token)) = self.recv_challenge_request(reader) {
+                if let Ok((timestamp, id_token, client_compression)) =
+                    self.recv_challenge_request(reader)
+                {
                     if let Some(user_key) = self.authenticated_unidentified_users.remove(&id_token)
                     {
                         // remove identity token from map
@@ -92,6 +98,7 @@ impl Handshaker for HandshakeManager {
                         // User is authenticated and identified
                         self.authenticated_and_identified_users
                             .insert(*address, user_key);
+                        self.client_compression.insert(user_key, client_compression);
                     } else {
                         // commented out because it's pretty common to get multiple ClientChallengeRequest which would trigger this
                         //warn!("Server Error: User not authenticated for: {:?}, with token: {}", address, identity_token);
@@ -168,7 +175,12 @@ impl Handshaker for HandshakeManager {
         }
     }
 
+    fn take_client_compression(&mut self, user_key: &UserKey) -> CompressionCapabilities {
+        self.client_compression.remove(user_key).unwrap_or_default()
+    }
+
     fn reset(&mut self) {
+        self.client_compression.clear();
         self.authenticated_and_identified_users.clear();
         self.authenticated_unidentified_users.clear();
         self.identity_token_map.clear();
@@ -186,7 +198,10 @@ impl Handshaker for HandshakeManager {
 }
 
 impl HandshakeManager {
-    pub fn new(protocol_id: ProtocolId) -> Self {
+    pub fn new(
+        protocol_id: ProtocolId,
+        compression_capabilities: CompressionCapabilities,
+    ) -> Self {
         let connection_hash_key =
             hmac::Key::generate(hmac::HMAC_SHA256, &rand::SystemRandom::new()).unwrap();
 
@@ -196,6 +211,8 @@ impl HandshakeManager {
             authenticated_unidentified_users: HashMap::new(),
             identity_token_map: HashMap::new(),
             been_handshaked_users: HashMap::new(),
+            compression_capabilities,
+            client_compression: HashMap::new(),
 
             connection_hash_key,
             address_to_timestamp_map: CacheMap::with_capacity(MAX_PENDING_CONNECTIONS),
@@ -207,11 +224,12 @@ impl HandshakeManager {
     fn recv_challenge_request(
         &mut self,
         reader: &mut BitReader,
-    ) -> Result<(Timestamp, IdentityToken), SerdeErr> {
+    ) -> Result<(Timestamp, IdentityToken, CompressionCapabilities), SerdeErr> {
         let timestamp = Timestamp::de(reader)?;
         let identity_token = IdentityToken::de(reader)?;
+        let client_compression = CompressionCapabilities::de(reader).unwrap_or_default();
 
-        Ok((timestamp, identity_token))
+        Ok((timestamp, identity_token, client_compression))
     }
 
     // Step 2 of Handshake
@@ -232,6 +250,8 @@ impl HandshakeManager {
             .get_unchecked(timestamp)
             .ser(&mut writer);
 
+        self.compression_capabilities.ser(&mut writer);
+
         writer
     }
 
@@ -1,6 +1,6 @@
 use std::net::SocketAddr;
 
-use naia_shared::{BitReader, IdentityToken, OutgoingPacket, SerdeErr};
+use naia_shared::{BitReader, CompressionCapabilities, IdentityToken, OutgoingPacket, SerdeErr};
 
 use crate::UserKey;
 
@@ -29,6 +29,10 @@ pub trait Handshaker: Send + Sync {
         has_connection: bool,
     ) -> Result<HandshakeAction, SerdeErr>;
 
+    /// Takes the compression capabilities a user's client reported
+    /// in its first handshake request
+    fn take_client_compression(&mut self, user_key: &UserKey) -> CompressionCapabilities;
+
     fn reset(&mut self);
 
     /// Write a disconnect packet to send to a client
@@ -4,7 +4,8 @@ use log::warn;
 
 use naia_shared::{
     handshake::{HandshakeHeader, RejectReason},
-    BitReader, BitWriter, IdentityToken, PacketType, ProtocolId, Serde, SerdeErr, StandardHeader,
+    BitReader, BitWriter, CompressionCapabilities, IdentityToken, PacketType, ProtocolId, Serde,
+    SerdeErr, StandardHeader,
 };
 
 use crate::{
@@ -17,6 +18,8 @@ pub struct HandshakeManager {
     authenticated_and_identified_users: HashMap<SocketAddr, UserKey>,
     authenticated_unidentified_users: HashMap<IdentityToken, UserKey>,
     identity_token_map: HashMap<UserKey, IdentityToken>,
+    compression_capabilities: CompressionCapabilities,
+    client_compression: HashMap<UserKey, CompressionCapabilities>,
 }
 
 impl Handshaker for HandshakeManager {
@@ -36,6 +39,7 @@ impl Handshaker for HandshakeManager {
         if let Some(address) = address_opt {
             self.authenticated_and_identified_users.remove(&address);
         }
+        self.client_compression.remove(user_key);
     }
 
     fn maintain_handshake(
@@ -59,10 +63,11 @@ impl Handshaker for HandshakeManager {
                     return Ok(HandshakeAction::SendPacket(reject_response));
                 }
                 if has_connection {
-                    let identify_response = Self::write_identity_response().to_packet();
+                    let identify_response = self.write_identity_response().to_packet();
                     Ok(HandshakeAction::SendPacket(identify_response))
                 } else {
-                    let Ok(id_token) = self.recv_identify_request(reader) else {
+                    let Ok((id_token, client_compression)) = self.recv_identify_request(reader)
+                    else {
                         return Ok(HandshakeAction::None);
                     };
                     let Some(user_key) = self.authenticated_unidentified_users.remove(&id_token)
@@ -79,9 +84,10 @@ impl Handshaker for HandshakeManager {
                     // User is authenticated
                     self.authenticated_and_identified_users
                         .insert(*address, user_key);
+                    self.client_compression.insert(user_key, client_compression);
 
                     // send identify response
-                    let identify_response = Self::write_identity_response().to_packet();
+                    let identify_response = self.write_identity_response().to_packet();
                     Ok(HandshakeAction::FinalizeConnection(
                         user_key,
                         identify_response,
@@ -113,10 +119,15 @@ impl Handshaker for HandshakeManager {
         }
     }
 
+    fn take_client_compression(&mut self, user_key: &UserKey) -> CompressionCapabilities {
+        self.client_compression.remove(user_key).unwrap_or_default()
+    }
+
     fn reset(&mut self) {
         self.authenticated_and_identified_users.clear();
         self.authenticated_unidentified_users.clear();
         self.identity_token_map.clear();
+        self.client_compression.clear();
     }
 
     fn write_disconnect(&self) -> naia_shared::OutgoingPacket {
@@ -128,25 +139,36 @@ impl Handshaker for HandshakeManager {
 }
 
 impl HandshakeManager {
-    pub fn new(protocol_id: ProtocolId) -> Self {
+    pub fn new(
+        protocol_id: ProtocolId,
+        compression_capabilities: CompressionCapabilities,
+    ) -> Self {
         Self {
             protocol_id,
             authenticated_and_identified_users: HashMap::new(),
             authenticated_unidentified_users: HashMap::new(),
             identity_token_map: HashMap::new(),
+            compression_capabilities,
+            client_compression: HashMap::new(),
         }
     }
 
     // Step 1 of Handshake
-    fn recv_identify_request(&mut self, reader: &mut BitReader) -> Result<IdentityToken, SerdeErr> {
-        IdentityToken::de(reader)
+    fn recv_identify_request(
+        &mut self,
+        reader: &mut BitReader,
+    ) -> Result<(IdentityToken, CompressionCapabilities), SerdeErr> {
+        let identity_token = IdentityToken::de(reader)?;
+        let client_compression = CompressionCapabilities::de(reader).unwrap_or_default();
+        Ok((identity_token, client_compression))
     }
 
     // Step 2 of Handshake
-    fn write_identity_response() -> BitWriter {
+    fn write_identity_response(&self) -> BitWriter {
         let mut writer = BitWriter::new();
         StandardHeader::new(PacketType::Handshake, 0, 0, 0).ser(&mut writer);
         HandshakeHeader::ServerIdentifyResponse.ser(&mut writer);
+        self.compression_capabilities.ser(&mut writer);
 
         writer
     }
@@ -1,10 +1,13 @@
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{HashMap, HashSet, VecDeque},
     hash::Hash,
 };
 
 use naia_shared::{
-    ComponentKind, EntityAndGlobalEntityConverter, GlobalEntity, Replicate, Tick, WorldRefType,
+    sequence_greater_than, ComponentKind, ComponentKinds, EntityAndGlobalEntityConverter,
+    EntityDoesNotExistError, FileBitWriter, GlobalEntity, HostEntity, Interpolate,
+    LocalEntityAndGlobalEntityConverter, LocalEntityAndGlobalEntityConverterMut, OwnedLocalEntity,
+    RemoteEntity, Replicate, ReplicatedComponent, Tick, WorldRefType,
 };
 
 use crate::world::global_world_manager::GlobalWorldManager;
@@ -12,6 +15,50 @@ use crate::world::global_world_manager::GlobalWorldManager;
 /// Per-entity snapshot: one cloned component value per replicated component.
 pub type EntitySnapshot = HashMap<ComponentKind, Box<dyn Replicate>>;
 
+/// Per-tick snapshot: every entity alive at some retained tick, with its
+/// components at this tick or the reason it has none.
+pub type TickSnapshot = HashMap<GlobalEntity, Result<EntitySnapshot, EntityAbsence>>;
+
+/// Why an entity in a [`TickSnapshot`] has no components at that tick.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EntityAbsence {
+    /// The entity was spawned after this tick.
+    NotYetSpawned,
+    /// The entity had already been despawned by this tick.
+    Despawned,
+    /// The entity existed, but none of its components were recorded: the
+    /// component filter excludes them all, or it had none.
+    Filtered,
+}
+
+/// The range of recorded ticks an entity was alive for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EntityLifetime {
+    /// The first tick the entity was recorded on, or `None` if it already
+    /// existed on the first tick the Historian recorded.
+    pub spawned: Option<Tick>,
+    /// The first tick recorded without the entity, or `None` while it's
+    /// alive.
+    pub despawned: Option<Tick>,
+}
+
+impl EntityLifetime {
+    /// Returns `true` if the entity was alive at `tick`.
+    pub fn is_alive_at(&self, tick: Tick) -> bool {
+        let spawned = self
+            .spawned
+            .is_none_or(|spawned| !sequence_greater_than(spawned, tick));
+        let not_despawned = self
+            .despawned
+            .is_none_or(|despawned| sequence_greater_than(despawned, tick));
+        spawned && not_despawned
+    }
+}
+
+/// Blends two values of one registered component kind, or returns `None`
+/// if the pair should snap instead.
+type InterpolateFn = fn(&dyn Replicate, &dyn Replicate, f32) -> Option<Box<dyn Replicate>>;
+
 /// Rolling per-tick snapshot buffer for server-side lag compensation.
 ///
 /// # Purpose
@@ -29,7 +76,7 @@ pub type EntitySnapshot = HashMap<ComponentKind, Box<dyn Replicate>>;
 /// # use naia_server::{Server, UserKey};
 /// # use naia_shared::{Tick, WorldRefType};
 /// # fn example<E: Copy + Eq + std::hash::Hash + Send + Sync, W: WorldRefType<E>>(
-/// #     server: &mut Server<E>, world: &W, current_tick: Tick, fire_tick: Tick
+/// #     server: &mut Server<E>, world: W, current_tick: Tick, fire_tick: Tick
 /// # ) {
 /// // Opt-in once at startup:
 /// server.enable_historian(64);
@@ -41,28 +88,50 @@ pub type EntitySnapshot = HashMap<ComponentKind, Box<dyn Replicate>>;
 /// // On receiving a fire command with client tick T:
 /// if let Some(historian) = server.historian() {
 ///     if let Some(snapshot) = historian.snapshot_at_tick(fire_tick) {
-///         // snapshot: HashMap<GlobalEntity, EntitySnapshot>
-///         // Iterate snapshot, perform spatial query + hit test, apply damage.
+///         for entity_snapshot in snapshot.values() {
+///             // Entities that weren't alive at `fire_tick` can't be hit
+///             let Ok(components) = entity_snapshot else {
+///                 continue;
+///             };
+///             // Perform spatial query + hit test on `components`, apply damage.
+///         }
 ///     }
 /// }
 /// # }
 /// ```
 ///
+/// Clients render between two ticks, so [`snapshot_at`](Self::snapshot_at)
+/// also takes the fraction of the way to the next tick, blending every
+/// component kind registered with
+/// [`interpolate_component`](Self::interpolate_component). To run existing
+/// collision code against that state instead of reading the snapshot, use
+/// `Server::rewind_world`.
+///
 /// # Notes
 ///
-/// - Components are cloned via `Replicate::copy_to_box()` at snapshot time —
-///   cheap for small structs (position, health), potentially heavy for large
-///   components.  Use `enable_historian_filtered` to restrict snapshotting to
-///   only the component kinds relevant to your hit detection logic.
-/// - The buffer auto-evicts snapshots older than `max_ticks`.
-/// - Entity additions/removals are reflected in the next snapshot; the
-///   Historian does NOT back-fill missing entities into past snapshots.
+/// - Only changes are stored: a component's value is cloned via
+///   `Replicate::copy_to_box()` on the ticks where it differs from the
+///   previous recorded value, so a mostly static world costs little per tick.
+///   Detecting a change still serializes each snapshotted component once per
+///   tick; use `enable_historian_filtered` to restrict snapshotting to only
+///   the component kinds relevant to your hit detection logic.
+/// - The buffer auto-evicts ticks older than `max_ticks`.
+/// - Each tick records which entities spawned and despawned on it
+///   ([`spawned_at`](Self::spawned_at), [`despawned_at`](Self::despawned_at)),
+///   and [`entity_lifetime`](Self::entity_lifetime) gives the range an entity
+///   was alive for. Snapshots list an entity on ticks before it spawned and
+///   after it despawned too, as an [`EntityAbsence`], so hit detection can
+///   tell "not there yet" and "already gone" from "filtered out".
 pub struct Historian {
     max_ticks: u16,
-    snapshots: std::collections::VecDeque<(Tick, HashMap<GlobalEntity, EntitySnapshot>)>,
+    /// Every recorded tick, oldest first.
+    ticks: VecDeque<TickRecord>,
+    lifetimes: HashMap<GlobalEntity, EntityLifetime>,
+    timelines: HashMap<GlobalEntity, HashMap<ComponentKind, Timeline>>,
     /// If `Some`, only components whose `ComponentKind` is in this set are
     /// captured. If `None`, all replicated components are captured (default).
     component_filter: Option<HashSet<ComponentKind>>,
+    interpolators: HashMap<ComponentKind, InterpolateFn>,
 }
 
 impl Historian {
@@ -70,8 +139,11 @@ impl Historian {
     pub fn new(max_ticks: u16) -> Self {
         Self {
             max_ticks,
-            snapshots: std::collections::VecDeque::new(),
+            ticks: VecDeque::new(),
+            lifetimes: HashMap::new(),
+            timelines: HashMap::new(),
             component_filter: None,
+            interpolators: HashMap::new(),
         }
     }
 
@@ -100,72 +172,251 @@ impl Historian {
     /// ```
     pub fn new_filtered(max_ticks: u16, filter: impl IntoIterator<Item = ComponentKind>) -> Self {
         Self {
-            max_ticks,
-            snapshots: std::collections::VecDeque::new(),
             component_filter: Some(filter.into_iter().collect()),
+            ..Self::new(max_ticks)
         }
     }
 
+    /// Blend component `C` in [`snapshot_at`](Self::snapshot_at) using its
+    /// [`Interpolate`] implementation. Unregistered kinds keep the value of
+    /// the earlier tick.
+    pub fn interpolate_component<C: ReplicatedComponent + Interpolate>(&mut self) -> &mut Self {
+        self.interpolators
+            .insert(ComponentKind::of::<C>(), interpolate_boxed::<C>);
+        self
+    }
+
     /// Record a snapshot of every replicated entity's components at `tick`.
     ///
     /// Call this after all game-state mutations for the tick have been applied
     /// and before `send_all_packets`, so the snapshot reflects authoritative
-    /// game state.
+    /// game state. Ticks must be recorded in order; recording a tick older
+    /// than the newest one is ignored, and recording the newest one again
+    /// replaces it.
     pub fn record_tick<E: Copy + Eq + Hash + Send + Sync, W: WorldRefType<E>>(
         &mut self,
         tick: Tick,
+        component_kinds: &ComponentKinds,
         global_world_manager: &GlobalWorldManager,
         global_entity_map: &impl EntityAndGlobalEntityConverter<E>,
         world: &W,
     ) {
-        let mut tick_snapshot: HashMap<GlobalEntity, EntitySnapshot> = HashMap::new();
+        let first_tick = match self.ticks.back() {
+            Some(newest) if sequence_greater_than(newest.tick, tick) => return,
+            Some(newest) if newest.tick == tick => {
+                self.undo_lifecycle();
+                self.ticks.len() == 1
+            }
+            newest => {
+                let first_tick = newest.is_none();
+                self.ticks.push_back(TickRecord::new(tick));
+                first_tick
+            }
+        };
 
+        let mut alive: HashSet<GlobalEntity> = HashSet::new();
+        let mut recorded: HashSet<(GlobalEntity, ComponentKind)> = HashSet::new();
         for &global_entity in global_world_manager.all_global_entities() {
             let Ok(world_entity) = global_entity_map.global_entity_to_entity(&global_entity) else {
                 continue;
             };
+            alive.insert(global_entity);
             let Some(kinds) = global_world_manager.component_kinds(&global_entity) else {
                 continue;
             };
-            let mut entity_snapshot = EntitySnapshot::new();
             for kind in kinds {
                 if let Some(ref filter) = self.component_filter {
                     if !filter.contains(&kind) {
                         continue;
                     }
                 }
-                if let Some(component_ref) = world.component_of_kind(&world_entity, &kind) {
-                    entity_snapshot.insert(kind, component_ref.copy_to_box());
+                let Some(component_ref) = world.component_of_kind(&world_entity, &kind) else {
+                    continue;
+                };
+                // A copy is host-owned, so it can be written whoever owns
+                // the live component
+                let value = component_ref.copy_to_box();
+                let fingerprint = Fingerprint::of(value.as_ref(), component_kinds);
+                self.timelines
+                    .entry(global_entity)
+                    .or_default()
+                    .entry(kind)
+                    .or_default()
+                    .record(tick, Some((value, fingerprint)));
+                recorded.insert((global_entity, kind));
+            }
+        }
+
+        self.record_lifecycle(tick, first_tick, &alive);
+
+        // Components and entities that are gone from the world end their
+        // timelines at this tick
+        for (global_entity, timelines) in &mut self.timelines {
+            for (kind, timeline) in timelines.iter_mut() {
+                if !recorded.contains(&(*global_entity, *kind)) {
+                    timeline.record(tick, None);
                 }
             }
-            if !entity_snapshot.is_empty() {
-                tick_snapshot.insert(global_entity, entity_snapshot);
+        }
+
+        self.evict(tick);
+    }
+
+    /// Notes the entities that spawned and despawned on the newest tick.
+    /// Entities alive on the very first recorded tick have no known spawn.
+    fn record_lifecycle(&mut self, tick: Tick, first_tick: bool, alive: &HashSet<GlobalEntity>) {
+        let record = self.ticks.back_mut().expect("tick was just recorded");
+        for global_entity in alive {
+            if self.lifetimes.contains_key(global_entity) {
+                continue;
+            }
+            let spawned = (!first_tick).then_some(tick);
+            if spawned.is_some() {
+                record.spawned.push(*global_entity);
+            }
+            self.lifetimes.insert(
+                *global_entity,
+                EntityLifetime {
+                    spawned,
+                    despawned: None,
+                },
+            );
+        }
+        for (global_entity, lifetime) in &mut self.lifetimes {
+            if lifetime.despawned.is_none() && !alive.contains(global_entity) {
+                lifetime.despawned = Some(tick);
+                record.despawned.push(*global_entity);
             }
         }
+    }
 
-        self.snapshots.push_back((tick, tick_snapshot));
+    /// Forgets the lifecycle changes of the newest tick, before recording it
+    /// again.
+    fn undo_lifecycle(&mut self) {
+        let record = self.ticks.back_mut().expect("newest tick exists");
+        for global_entity in record.spawned.drain(..) {
+            self.lifetimes.remove(&global_entity);
+        }
+        for global_entity in record.despawned.drain(..) {
+            if let Some(lifetime) = self.lifetimes.get_mut(&global_entity) {
+                lifetime.despawned = None;
+            }
+        }
+    }
 
-        // Evict snapshots older than max_ticks relative to the current tick.
+    /// Evicts ticks older than `max_ticks` relative to `tick`, keeping each
+    /// timeline's value as of the oldest remaining tick, and the lifetime of
+    /// every entity alive at some remaining tick.
+    fn evict(&mut self, tick: Tick) {
         let max_ticks = self.max_ticks as u32;
-        while let Some(&(oldest_tick, _)) = self.snapshots.front() {
-            let age = (tick as u32).wrapping_sub(oldest_tick as u32);
+        while let Some(oldest) = self.ticks.front() {
+            let age = (tick as u32).wrapping_sub(oldest.tick as u32) as u16 as u32;
             if age > max_ticks {
-                self.snapshots.pop_front();
+                self.ticks.pop_front();
             } else {
                 break;
             }
         }
+        let Some(oldest_tick) = self.ticks.front().map(|oldest| oldest.tick) else {
+            self.lifetimes.clear();
+            self.timelines.clear();
+            return;
+        };
+        self.lifetimes.retain(|_, lifetime| {
+            lifetime
+                .despawned
+                .is_none_or(|despawned| sequence_greater_than(despawned, oldest_tick))
+        });
+        self.timelines.retain(|_, timelines| {
+            timelines.retain(|_, timeline| timeline.compact(oldest_tick));
+            !timelines.is_empty()
+        });
+    }
+
+    fn has_tick(&self, tick: Tick) -> bool {
+        self.tick_record(tick).is_some()
+    }
+
+    fn tick_record(&self, tick: Tick) -> Option<&TickRecord> {
+        self.ticks.iter().find(|record| record.tick == tick)
     }
 
     /// Returns the snapshot for the exact given tick, or `None` if it has
     /// been evicted or never recorded.
-    pub fn snapshot_at_tick(&self, tick: Tick) -> Option<&HashMap<GlobalEntity, EntitySnapshot>> {
-        for (t, snapshot) in &self.snapshots {
-            if *t == tick {
-                return Some(snapshot);
+    ///
+    /// Every entity alive at some retained tick is listed: with its
+    /// components if it was alive at `tick` and any were recorded, and with
+    /// an [`EntityAbsence`] otherwise.
+    pub fn snapshot_at_tick(&self, tick: Tick) -> Option<TickSnapshot> {
+        if !self.has_tick(tick) {
+            return None;
+        }
+        let mut snapshot = TickSnapshot::new();
+        for (global_entity, lifetime) in &self.lifetimes {
+            let entity = if lifetime
+                .spawned
+                .is_some_and(|spawned| sequence_greater_than(spawned, tick))
+            {
+                Err(EntityAbsence::NotYetSpawned)
+            } else if !lifetime.is_alive_at(tick) {
+                Err(EntityAbsence::Despawned)
+            } else {
+                let entity_snapshot: EntitySnapshot = self
+                    .timelines
+                    .get(global_entity)
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|(kind, timeline)| {
+                        timeline
+                            .value_at(tick)
+                            .map(|value| (*kind, value.copy_to_box()))
+                    })
+                    .collect();
+                if entity_snapshot.is_empty() {
+                    Err(EntityAbsence::Filtered)
+                } else {
+                    Ok(entity_snapshot)
+                }
+            };
+            snapshot.insert(*global_entity, entity);
+        }
+        Some(snapshot)
+    }
+
+    /// Returns the world `fraction` of the way from `tick` to the tick after
+    /// it, or `None` if `tick` has been evicted or never recorded.
+    ///
+    /// Component kinds registered with
+    /// [`interpolate_component`](Self::interpolate_component) are blended
+    /// when present on both ticks, unless `Interpolate::should_snap` says the
+    /// jump is a discontinuity. Everything else, and everything when the next
+    /// tick hasn't been recorded yet, keeps its value at `tick`. `fraction` is
+    /// clamped to `[0.0, 1.0]`.
+    pub fn snapshot_at(&self, tick: Tick, fraction: f32) -> Option<TickSnapshot> {
+        let mut snapshot = self.snapshot_at_tick(tick)?;
+        let fraction = fraction.clamp(0.0, 1.0);
+        let next_tick = tick.wrapping_add(1);
+        if fraction == 0.0 || self.interpolators.is_empty() || !self.has_tick(next_tick) {
+            return Some(snapshot);
+        }
+        for (global_entity, entity_snapshot) in &mut snapshot {
+            let Ok(entity_snapshot) = entity_snapshot else {
+                continue;
+            };
+            let timelines = &self.timelines[global_entity];
+            for (kind, value) in entity_snapshot.iter_mut() {
+                let Some(interpolate) = self.interpolators.get(kind) else {
+                    continue;
+                };
+                let Some(next_value) = timelines[kind].value_at(next_tick) else {
+                    continue;
+                };
+                if let Some(blended) = interpolate(value.as_ref(), next_value, fraction) {
+                    *value = blended;
+                }
             }
         }
-        None
+        Some(snapshot)
     }
 
     /// Returns the snapshot that was current `time_ago_ms` milliseconds in the
@@ -179,27 +430,270 @@ impl Historian {
         time_ago_ms: u32,
         current_tick: Tick,
         tick_duration_ms: f32,
-    ) -> Option<&HashMap<GlobalEntity, EntitySnapshot>> {
-        if self.snapshots.is_empty() {
-            return None;
-        }
+    ) -> Option<TickSnapshot> {
+        let oldest_tick = self.ticks.front()?.tick;
         let ticks_ago = (time_ago_ms as f32 / tick_duration_ms).round() as u32;
         let target_tick = (current_tick as u32).wrapping_sub(ticks_ago) as u16;
-        // Try exact match first, then fall back to nearest available.
-        if let Some(snap) = self.snapshot_at_tick(target_tick) {
-            return Some(snap);
-        }
-        // Return the oldest snapshot as the best approximation.
-        self.snapshots.front().map(|(_, s)| s)
+        // Try exact match first, then fall back to the oldest available.
+        self.snapshot_at_tick(target_tick)
+            .or_else(|| self.snapshot_at_tick(oldest_tick))
+    }
+
+    /// Entities first recorded on `tick`, or `None` if `tick` has been
+    /// evicted or never recorded. Entities that already existed on the first
+    /// recorded tick aren't listed as spawned.
+    pub fn spawned_at(&self, tick: Tick) -> Option<&[GlobalEntity]> {
+        self.tick_record(tick)
+            .map(|record| record.spawned.as_slice())
     }
 
-    /// Number of snapshots currently retained.
+    /// Entities recorded missing on `tick` for the first time, or `None` if
+    /// `tick` has been evicted or never recorded.
+    pub fn despawned_at(&self, tick: Tick) -> Option<&[GlobalEntity]> {
+        self.tick_record(tick)
+            .map(|record| record.despawned.as_slice())
+    }
+
+    /// Returns the ticks `global_entity` was alive for, or `None` if it was
+    /// never recorded or despawned before every retained tick.
+    pub fn entity_lifetime(&self, global_entity: &GlobalEntity) -> Option<EntityLifetime> {
+        self.lifetimes.get(global_entity).copied()
+    }
+
+    /// Number of ticks currently retained.
     pub fn len(&self) -> usize {
-        self.snapshots.len()
+        self.ticks.len()
     }
 
-    /// Returns `true` if no snapshots are currently stored.
+    /// Returns `true` if no ticks are currently stored.
     pub fn is_empty(&self) -> bool {
-        self.snapshots.is_empty()
+        self.ticks.is_empty()
+    }
+
+    /// Number of component values currently stored across all retained
+    /// ticks. A component that never changes is stored once, however many
+    /// ticks it spans.
+    pub fn stored_values(&self) -> usize {
+        self.timelines
+            .values()
+            .flat_map(|timelines| timelines.values())
+            .map(|timeline| {
+                timeline
+                    .changes
+                    .iter()
+                    .filter(|(_, value)| value.is_some())
+                    .count()
+            })
+            .sum()
+    }
+}
+
+/// One recorded tick and the entities that spawned and despawned on it.
+struct TickRecord {
+    tick: Tick,
+    spawned: Vec<GlobalEntity>,
+    despawned: Vec<GlobalEntity>,
+}
+
+impl TickRecord {
+    fn new(tick: Tick) -> Self {
+        Self {
+            tick,
+            spawned: Vec::new(),
+            despawned: Vec::new(),
+        }
+    }
+}
+
+fn interpolate_boxed<C: ReplicatedComponent + Interpolate>(
+    from: &dyn Replicate,
+    to: &dyn Replicate,
+    fraction: f32,
+) -> Option<Box<dyn Replicate>> {
+    let from = from.to_any().downcast_ref::<C>()?;
+    let to = to.to_any().downcast_ref::<C>()?;
+    if from.should_snap(to) {
+        return None;
+    }
+    Some(Box::new(from.interpolate(to, fraction)))
+}
+
+/// The values one component of one entity took, each stored from the tick it
+/// changed on. `None` marks ticks where the component was absent.
+#[derive(Default)]
+struct Timeline {
+    changes: VecDeque<(Tick, Option<Box<dyn Replicate>>)>,
+    latest: Option<Fingerprint>,
+}
+
+impl Timeline {
+    fn record(&mut self, tick: Tick, value: Option<(Box<dyn Replicate>, Fingerprint)>) {
+        let (value, fingerprint) = match value {
+            Some((value, fingerprint)) => (Some(value), Some(fingerprint)),
+            None => (None, None),
+        };
+        if self.changes.back().is_some_and(|(last, _)| *last == tick) {
+            // Recording the newest tick again replaces what it stored
+            self.changes.pop_back();
+        } else if fingerprint == self.latest {
+            return;
+        }
+        self.changes.push_back((tick, value));
+        self.latest = fingerprint;
+    }
+
+    /// Drops changes superseded before `oldest_tick`. Returns `false` once
+    /// nothing at or after `oldest_tick` is left to look up.
+    fn compact(&mut self, oldest_tick: Tick) -> bool {
+        while self
+            .changes
+            .get(1)
+            .is_some_and(|(tick, _)| !sequence_greater_than(*tick, oldest_tick))
+        {
+            self.changes.pop_front();
+        }
+        match self.changes.front() {
+            None => false,
+            Some((_, None)) => self.changes.len() > 1,
+            Some(_) => true,
+        }
+    }
+
+    fn value_at(&self, tick: Tick) -> Option<&dyn Replicate> {
+        self.changes
+            .iter()
+            .rev()
+            .find(|(changed, _)| !sequence_greater_than(*changed, tick))
+            .and_then(|(_, value)| value.as_deref())
+    }
+}
+
+/// A component's serialized bits plus the entities its relations point at,
+/// compared to tell whether it changed since the previous tick.
+#[derive(PartialEq, Eq)]
+struct Fingerprint {
+    bytes: Vec<u8>,
+    relation_targets: Vec<GlobalEntity>,
+}
+
+impl Fingerprint {
+    fn of(value: &dyn Replicate, component_kinds: &ComponentKinds) -> Self {
+        let mut writer = FileBitWriter::new();
+        let mut converter = FingerprintConverter::default();
+        value.write(component_kinds, &mut writer, &mut converter);
+        Self {
+            bytes: writer.to_vec(),
+            relation_targets: converter.relation_targets,
+        }
+    }
+}
+
+/// Converter used while fingerprinting a component: gives each relation
+/// target an id in order of first use and remembers which entity it was.
+#[derive(Default)]
+struct FingerprintConverter {
+    relation_targets: Vec<GlobalEntity>,
+}
+
+impl LocalEntityAndGlobalEntityConverter for FingerprintConverter {
+    fn global_entity_to_host_entity(
+        &self,
+        global_entity: &GlobalEntity,
+    ) -> Result<HostEntity, EntityDoesNotExistError> {
+        self.relation_targets
+            .iter()
+            .position(|target| target == global_entity)
+            .and_then(|index| u16::try_from(index).ok())
+            .map(HostEntity::new)
+            .ok_or(EntityDoesNotExistError)
+    }
+
+    fn global_entity_to_remote_entity(
+        &self,
+        _: &GlobalEntity,
+    ) -> Result<RemoteEntity, EntityDoesNotExistError> {
+        Err(EntityDoesNotExistError)
+    }
+
+    fn global_entity_to_owned_entity(
+        &self,
+        global_entity: &GlobalEntity,
+    ) -> Result<OwnedLocalEntity, EntityDoesNotExistError> {
+        self.global_entity_to_host_entity(global_entity)
+            .map(|host_entity| host_entity.copy_to_owned())
+    }
+
+    fn host_entity_to_global_entity(
+        &self,
+        _: &HostEntity,
+    ) -> Result<GlobalEntity, EntityDoesNotExistError> {
+        Err(EntityDoesNotExistError)
+    }
+
+    fn static_host_entity_to_global_entity(
+        &self,
+        _: &HostEntity,
+    ) -> Result<GlobalEntity, EntityDoesNotExistError> {
+        Err(EntityDoesNotExistError)
+    }
+
+    fn remote_entity_to_global_entity(
+        &self,
+        _: &RemoteEntity,
+    ) -> Result<GlobalEntity, EntityDoesNotExistError> {
+        Err(EntityDoesNotExistError)
+    }
+
+    fn apply_entity_redirect(&self, entity: &OwnedLocalEntity) -> OwnedLocalEntity {
+        *entity
+    }
+}
+
+impl LocalEntityAndGlobalEntityConverterMut for FingerprintConverter {
+    fn get_or_reserve_entity(
+        &mut self,
+        global_entity: &GlobalEntity,
+    ) -> Result<OwnedLocalEntity, EntityDoesNotExistError> {
+        if let Ok(owned_entity) = self.global_entity_to_owned_entity(global_entity) {
+            return Ok(owned_entity);
+        }
+        let id = u16::try_from(self.relation_targets.len()).map_err(|_| EntityDoesNotExistError)?;
+        self.relation_targets.push(*global_entity);
+        Ok(HostEntity::new(id).copy_to_owned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lifetime_covers_spawn_up_to_despawn() {
+        let lifetime = EntityLifetime {
+            spawned: Some(10),
+            despawned: Some(20),
+        };
+        assert!(!lifetime.is_alive_at(9));
+        assert!(lifetime.is_alive_at(10));
+        assert!(lifetime.is_alive_at(19));
+        assert!(!lifetime.is_alive_at(20));
+
+        let pre_existing = EntityLifetime {
+            spawned: None,
+            despawned: None,
+        };
+        assert!(pre_existing.is_alive_at(0));
+    }
+
+    #[test]
+    fn lifetime_handles_tick_wraparound() {
+        let lifetime = EntityLifetime {
+            spawned: Some(u16::MAX - 1),
+            despawned: Some(2),
+        };
+        assert!(lifetime.is_alive_at(u16::MAX));
+        assert!(lifetime.is_alive_at(1));
+        assert!(!lifetime.is_alive_at(2));
+        assert!(!lifetime.is_alive_at(u16::MAX - 2));
     }
 }
@@ -0,0 +1,112 @@
+use std::{
+    io,
+    net::{SocketAddr, TcpListener, ToSocketAddrs},
+    sync::{Arc, Mutex},
+    thread,
+};
+
+use naia_shared::{serve_http_endpoint, HttpResponse};
+use serde::Serialize;
+
+use super::ServerSnapshot;
+
+const SNAPSHOT_PATH: &str = "/snapshot";
+
+/// A local HTTP endpoint serving the latest published [`ServerSnapshot`] as
+/// JSON at `GET /snapshot`.
+///
+/// The server can't be shared with the serving thread, so the snapshot is
+/// taken on the server's thread and handed over with
+/// [`publish`](SnapshotEndpoint::publish). Taking a snapshot walks every user
+/// and entity: publish on an interval, such as once a second, rather than
+/// every tick. Until the first publish, requests get a 503.
+pub struct SnapshotEndpoint {
+    latest: Arc<Mutex<Option<String>>>,
+    local_addr: SocketAddr,
+}
+
+impl SnapshotEndpoint {
+    /// Binds `addr` and starts serving from a background thread, which runs
+    /// for the rest of the process. Bind to a loopback address such as
+    /// `127.0.0.1:9465` to keep the endpoint private to the machine.
+    pub fn start<A: ToSocketAddrs>(addr: A) -> io::Result<Self> {
+        let listener = TcpListener::bind(addr)?;
+        let local_addr = listener.local_addr()?;
+        let latest = Arc::new(Mutex::new(None));
+        let served = latest.clone();
+        thread::Builder::new()
+            .name("naia-snapshot".to_string())
+            .spawn(move || serve_http_endpoint(listener, |path| respond(path, &served)))?;
+        Ok(Self { latest, local_addr })
+    }
+
+    /// The address the endpoint is listening on.
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    /// Replaces the snapshot being served.
+    pub fn publish<E: Serialize>(&self, snapshot: &ServerSnapshot<E>) -> serde_json::Result<()> {
+        let json = serde_json::to_string(snapshot)?;
+        *self.latest.lock().unwrap_or_else(|e| e.into_inner()) = Some(json);
+        Ok(())
+    }
+}
+
+fn respond(path: &str, latest: &Mutex<Option<String>>) -> HttpResponse {
+    if path != SNAPSHOT_PATH {
+        return HttpResponse::not_found();
+    }
+    match latest.lock().unwrap_or_else(|e| e.into_inner()).clone() {
+        Some(json) => HttpResponse::ok("application/json", json),
+        None => HttpResponse {
+            status: "503 Service Unavailable",
+            content_type: "text/plain",
+            body: "no snapshot published yet".to_string(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{Read, Write},
+        net::TcpStream,
+    };
+
+    use super::*;
+    use crate::introspection::{AuthorityHolder, EntityAuthoritySnapshot};
+
+    fn get(endpoint: &SnapshotEndpoint, path: &str) -> String {
+        let mut stream = TcpStream::connect(endpoint.local_addr()).unwrap();
+        write!(stream, "GET {path} HTTP/1.1\r\nHost: localhost\r\n\r\n").unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        response
+    }
+
+    #[test]
+    fn serves_latest_published_snapshot() {
+        let endpoint = SnapshotEndpoint::start("127.0.0.1:0").unwrap();
+        assert!(get(&endpoint, "/snapshot").starts_with("HTTP/1.1 503"));
+
+        let snapshot = ServerSnapshot::<u32> {
+            tick: 7,
+            users: Vec::new(),
+            rooms: Vec::new(),
+            delegated_entities: vec![EntityAuthoritySnapshot {
+                entity: 3,
+                holder: AuthorityHolder::User(1),
+                status: Some(naia_shared::EntityAuthStatus::Denied),
+            }],
+        };
+        endpoint.publish(&snapshot).unwrap();
+
+        let response = get(&endpoint, "/snapshot");
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(response.ends_with(
+            r#"{"tick":7,"users":[],"rooms":[],"delegated_entities":[{"entity":3,"holder":{"User":1},"status":"Denied"}]}"#
+        ));
+        assert!(get(&endpoint, "/other").starts_with("HTTP/1.1 404"));
+    }
+}
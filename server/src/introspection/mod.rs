@@ -0,0 +1,113 @@
+//! Read-only introspection of a running server, for ops tooling and
+//! dashboards.
+//!
+//! [`Server::snapshot`](crate::Server::snapshot) gathers which users are in
+//! which rooms, what each user has in scope, per-user queue depths and the
+//! authority over delegated entities into one [`ServerSnapshot`]. With the
+//! `serde` feature the snapshot is `Serialize`, and with
+//! `introspection_http`, [`SnapshotEndpoint`] serves it as JSON over a local
+//! HTTP endpoint.
+
+#[cfg(feature = "introspection_http")]
+mod http;
+
+use std::{collections::BTreeMap, net::SocketAddr};
+
+use naia_shared::{EntityAuthStatus, Tick};
+
+#[cfg(feature = "introspection_http")]
+pub use http::SnapshotEndpoint;
+
+/// A read-only view of the server's users, rooms, scopes and delegated
+/// entity authority at one point in time.
+///
+/// User and room keys are `UserKey::to_u64()` / `RoomKey::to_u64()`, matching
+/// the `user_id` label of naia's metrics.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ServerSnapshot<E> {
+    /// The server tick the snapshot was taken on.
+    pub tick: Tick,
+    /// Every user, connected or still handshaking, ordered by key.
+    pub users: Vec<UserSnapshot<E>>,
+    /// Every room, ordered by key.
+    pub rooms: Vec<RoomSnapshot<E>>,
+    /// Every entity with delegated authority, whose authority users can
+    /// contend for.
+    pub delegated_entities: Vec<EntityAuthoritySnapshot<E>>,
+}
+
+/// One user in a [`ServerSnapshot`].
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct UserSnapshot<E> {
+    /// The user's key.
+    pub user_key: u64,
+    /// The user's address.
+    pub address: SocketAddr,
+    /// Whether the handshake has completed.
+    pub connected: bool,
+    /// Whether the user is an observer, seeing the whole world regardless of
+    /// rooms.
+    pub observer: bool,
+    /// Keys of the rooms the user is in, ascending.
+    pub rooms: Vec<u64>,
+    /// Entities currently replicated to the user.
+    pub entities_in_scope: Vec<E>,
+    /// Messages queued per channel, keyed by channel protocol name. Reliable
+    /// messages stay queued until acknowledged.
+    pub channel_queue_depths: BTreeMap<String, usize>,
+    /// Entity components with changes not yet sent to the user.
+    pub pending_component_updates: usize,
+    /// Round-trip time average in milliseconds. `0.0` until connected.
+    pub rtt_ms: f32,
+}
+
+/// One room in a [`ServerSnapshot`].
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct RoomSnapshot<E> {
+    /// The room's key.
+    pub room_key: u64,
+    /// Keys of the users in the room, ascending.
+    pub users: Vec<u64>,
+    /// The entities in the room.
+    pub entities: Vec<E>,
+}
+
+/// Authority over one delegated entity in a [`ServerSnapshot`].
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct EntityAuthoritySnapshot<E> {
+    /// The entity.
+    pub entity: E,
+    /// Who holds authority.
+    pub holder: AuthorityHolder,
+    /// Authority status as the server sees it, including in-flight
+    /// transitions.
+    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_auth_status"))]
+    pub status: Option<EntityAuthStatus>,
+}
+
+/// Who holds authority over a delegated entity.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum AuthorityHolder {
+    /// Nobody: any user may request it.
+    Available,
+    /// The server.
+    Server,
+    /// The user with this key.
+    User(u64),
+}
+
+#[cfg(feature = "serde")]
+fn serialize_auth_status<S: serde::Serializer>(
+    status: &Option<EntityAuthStatus>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match status {
+        Some(status) => serializer.serialize_some(&format!("{status:?}")),
+        None => serializer.serialize_none(),
+    }
+}
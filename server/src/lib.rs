@@ -39,6 +39,7 @@
 //! | [`EntityRef`] | Read-only entity handle |
 //! | [`RoomMut`] | Manages room membership |
 //! | [`UserScopeMut`] | Fine-grained entity-per-user visibility |
+//! | [`SpatialInterest`] | Optional automatic distance-based scoping |
 //! | [`ReplicationConfig`] | Controls publicity and scope-exit behaviour |
 //! | [`Publicity`] | The three visibility states (Private / Public / Delegated) |
 
@@ -67,7 +68,10 @@ pub mod shared {
 
 /// Bevy-specific serialization derive support (re-export of [`naia_shared::SerdeBevyServer`]).
 pub use naia_shared::SerdeBevyServer as SerdeBevy;
-pub use naia_shared::{ConnectionStats, DisconnectReason, EntityPriorityMut, EntityPriorityRef};
+pub use naia_shared::{
+    BandwidthProfile, ChannelStats, ConnectionStats, DisconnectReason, EntityPriorityMut,
+    EntityPriorityRef, SentBits, UpdateValidation,
+};
 
 mod connection;
 mod error;
@@ -75,13 +79,20 @@ mod events;
 mod handshake;
 /// Lag-compensation snapshot buffer that stores per-tick world state for rollback hit detection.
 pub mod historian;
+/// Read-only snapshots of users, rooms, scopes and entity authority for ops tooling.
+pub mod introspection;
 mod request;
 mod room;
 mod server;
+/// Grid-based automatic scoping driven by entity positions and user viewpoints.
+pub mod spatial_interest;
 mod time_manager;
 mod user;
 mod user_scope;
+mod validation;
 mod world;
+/// Saving every replicated entity, room and resource to bytes, and restoring them into a fresh server.
+pub mod world_snapshot;
 
 cfg_if! {
     if #[cfg(feature = "interior_visibility")] {
@@ -90,7 +101,12 @@ cfg_if! {
 }
 
 pub use connection::tick_buffer_messages::TickBufferMessages;
-pub use historian::Historian;
+pub use historian::{EntityAbsence, EntityLifetime, Historian};
+#[cfg(feature = "introspection_http")]
+pub use introspection::SnapshotEndpoint;
+pub use introspection::{
+    AuthorityHolder, EntityAuthoritySnapshot, RoomSnapshot, ServerSnapshot, UserSnapshot,
+};
 #[cfg(feature = "bench_instrumentation")]
 pub use connection::connection::bench_send_counters;
 pub use error::NaiaServerError;
@@ -104,6 +120,8 @@ pub use events::{
 };
 pub use room::{RoomKey, RoomMut, RoomRef};
 pub use server::{MainServer, Server, ServerConfig, WorldServer};
+pub use spatial_interest::{SpatialInterest, SpatialInterestConfig, SpatialPosition};
+pub use world_snapshot::{RestoredWorld, WorldSnapshotError};
 
 #[cfg(feature = "e2e_debug")]
 pub use server::world_server::{
@@ -116,5 +134,5 @@ pub use user::{MainUser, MainUserRef, UserKey, UserMut, UserRef, WorldUser};
 pub use user_scope::{UserScopeMut, UserScopeRef};
 pub use world::{
     entity_mut::EntityMut, entity_owner::EntityOwner, entity_ref::EntityRef,
-    replication_config::{Publicity, ReplicationConfig, ScopeExit},
+    replication_config::{AuthorityPolicy, Publicity, ReplicationConfig, ScopeExit, UpdateMode},
 };
@@ -3,8 +3,8 @@ use std::{collections::HashMap, net::SocketAddr, panic, time::Duration};
 use log::{info, warn};
 
 use naia_shared::{
-    BigMap, BitReader, FakeEntityConverter, MessageKinds, PacketType, Protocol, ProtocolId, Serde,
-    SocketConfig, StandardHeader,
+    BigMap, BitReader, CompressionCapabilities, CompressionMode, FakeEntityConverter, MessageKinds,
+    PacketCodec, PacketType, Protocol, ProtocolId, Serde, SocketConfig, StandardHeader,
 };
 
 use crate::{
@@ -29,6 +29,8 @@ pub struct MainServer {
     io: Io,
     auth_io: Option<(Box<dyn AuthSender>, Box<dyn AuthReceiver>)>,
     handshake_manager: Box<dyn Handshaker>,
+    /// Server-to-client mode, negotiated into a codec for each client
+    outgoing_compression: Option<CompressionMode>,
     // Users
     users: BigMap<UserKey, MainUser>,
     user_connections: HashMap<SocketAddr, UserKey>,
@@ -58,9 +60,19 @@ impl MainServer {
             ..
         } = protocol;
 
+        let outgoing_compression = compression
+            .as_ref()
+            .and_then(|config| config.server_to_client.clone());
+        let incoming_compression = compression
+            .as_ref()
+            .and_then(|config| config.client_to_server.as_ref());
+        let compression_capabilities = CompressionCapabilities::local(incoming_compression);
+        // Handshake packets are exchanged before a codec is negotiated, so
+        // no address is given one here and they're sent uncompressed
         let io = Io::new(
             &server_config.connection.bandwidth_measure_duration,
-            &compression,
+            outgoing_compression.as_ref(),
+            incoming_compression,
         );
 
         Self {
@@ -72,7 +84,11 @@ impl MainServer {
             // Connection
             io,
             auth_io: None,
-            handshake_manager: Box::new(HandshakeManager::new(protocol_id)),
+            handshake_manager: Box::new(HandshakeManager::new(
+                protocol_id,
+                compression_capabilities,
+            )),
+            outgoing_compression,
             // Users
             users: BigMap::new(),
             user_connections: HashMap::new(),
@@ -155,6 +171,32 @@ impl MainServer {
         }
     }
 
+    /// Accepts an incoming Client User as an observer: it sees the whole
+    /// world read-only instead of being scoped by rooms
+    pub fn accept_observer_connection(&mut self, user_key: &UserKey) {
+        let Some(user) = self.users.get_mut(user_key) else {
+            warn!("unknown user is finalizing connection...");
+            return;
+        };
+        user.set_observer();
+        self.accept_connection(user_key);
+    }
+
+    /// Returns the server-to-client codec negotiated with the User's client
+    pub(crate) fn user_compression_codec(&self, user_key: &UserKey) -> PacketCodec {
+        self.users
+            .get(user_key)
+            .map(|user| user.compression_codec())
+            .unwrap_or_default()
+    }
+
+    /// Returns whether the User was accepted as an observer
+    pub(crate) fn user_is_observer(&self, user_key: &UserKey) -> bool {
+        self.users
+            .get(user_key)
+            .is_some_and(|user| user.is_observer())
+    }
+
     /// Rejects an incoming Client User, terminating their attempt to establish
     /// a connection with the Server
     pub fn reject_connection(&mut self, user_key: &UserKey) {
@@ -184,6 +226,10 @@ impl MainServer {
             return;
         };
         user.set_address(user_address);
+        let client_compression = self.handshake_manager.take_client_compression(user_key);
+        if let Some(mode) = &self.outgoing_compression {
+            user.set_compression_codec(mode.negotiate(&client_compression));
+        }
 
         self.user_connections.insert(user.address(), *user_key);
 
@@ -219,12 +265,13 @@ impl MainServer {
         }
     }
 
-    /// Return a list of all currently connected Users' keys
+    /// Return a list of all currently connected Users' keys, not counting
+    /// observers
     pub fn user_keys(&self) -> Vec<UserKey> {
         let mut output = Vec::new();
 
         for (user_key, user) in self.users.iter() {
-            if !user.has_address() {
+            if !user.has_address() || user.is_observer() {
                 continue;
             }
             if self.user_connections.contains_key(&user.address()) {
@@ -235,9 +282,12 @@ impl MainServer {
         output
     }
 
-    /// Get the number of Users currently connected
+    /// Get the number of Users currently connected, not counting observers
     pub fn users_count(&self) -> usize {
-        self.users.len()
+        self.users
+            .iter()
+            .filter(|(_, user)| !user.is_observer())
+            .count()
     }
 
     /// Get a User's Socket Address, given the associated UserKey
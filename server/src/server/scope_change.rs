@@ -13,4 +13,8 @@ pub(super) enum ScopeChange {
     EntityEnteredRoom(GlobalEntity, RoomKey),
     /// Explicit include/exclude via UserScope API.
     ScopeToggled(UserKey, GlobalEntity, bool),
+    /// Observer connected — check every entity for this user.
+    ObserverConnected(UserKey),
+    /// Entity became visible outside its owner — check it for every observer.
+    EntitySpawned(GlobalEntity),
 }
@@ -7,7 +7,7 @@ use naia_shared::{
     ResponseSendKey, SocketConfig, Tick, WorldMutType, WorldRefType,
 };
 
-use crate::Historian;
+use crate::{Historian, RestoredWorld, WorldSnapshotError};
 
 use crate::{
     connection::tick_buffer_messages::TickBufferMessages,
@@ -16,9 +16,10 @@ use crate::{
     transport::Socket,
     transport::{PacketChannel, PacketSender},
     world::{entity_mut::EntityMut, entity_ref::EntityRef},
-    ConnectEvent, ConnectionStats, DisconnectEvent, EntityOwner, Events, MainEvents,
-    NaiaServerError, ReplicationConfig, RoomKey, RoomMut, RoomRef, ServerConfig, TickEvents,
-    UserKey, UserMut, UserRef, UserScopeMut, UserScopeRef,
+    BandwidthProfile, ConnectEvent, ConnectionStats, DisconnectEvent, EntityOwner, Events, MainEvents,
+    NaiaServerError, ReplicationConfig, RoomKey, RoomMut, RoomRef, ServerConfig, ServerSnapshot, SpatialInterest,
+    SpatialInterestConfig, SpatialPosition, TickEvents, UserKey, UserMut, UserRef, UserScopeMut,
+    UpdateValidation, UserScopeRef,
 };
 
 /// The naia server — accepts connections, replicates entities, and routes
@@ -84,7 +85,7 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
                 protocol_id,
             ),
             outstanding_main_events: MainEvents::default(),
-            world_server: WorldServer::new(server_config, protocol),
+            world_server: WorldServer::new_with_protocol_id(server_config, protocol, protocol_id),
             to_world_sender_opt: None,
         }
     }
@@ -127,7 +128,14 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
         // handle connects
         for user_key in main_events.read::<ConnectEvent>() {
             let user_address = self.main_server.user_address(&user_key).unwrap();
-            self.world_server.receive_user(user_key, user_address);
+            let observer = self.main_server.user_is_observer(&user_key);
+            let compression_codec = self.main_server.user_compression_codec(&user_key);
+            self.world_server.receive_user(
+                user_key,
+                user_address,
+                observer,
+                compression_codec,
+            );
         }
 
         // handle queued disconnects (from verified disconnect handshake packets)
@@ -218,6 +226,19 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
         self.main_server.accept_connection(user_key);
     }
 
+    /// Accepts an incoming connection request as an observer.
+    ///
+    /// Observers are meant for admin tools and spectator cameras: every
+    /// entity the server replicates is in their scope, without adding them
+    /// to rooms, and room and [`UserScopeMut`](crate::UserScopeMut) settings
+    /// don't apply to them. They are read-only — authority requests are
+    /// denied and their tick-buffered messages are dropped — and their
+    /// packets are sent after every player's each tick, within
+    /// [`ServerConfig::observer_bandwidth`](crate::ServerConfig::observer_bandwidth).
+    pub fn accept_observer_connection(&mut self, user_key: &UserKey) {
+        self.main_server.accept_observer_connection(user_key);
+    }
+
     /// Rejects an incoming connection request.
     ///
     /// Call this inside a [`ConnectEvent`] handler to refuse the user. The
@@ -634,6 +655,27 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
         self.world_server.entity_give_authority(origin_user, world_entity)
     }
 
+    /// Grants `user_key` authority over every entity in `world_entities`, as
+    /// [`entity_give_authority`](Server::entity_give_authority) would for
+    /// each. Fails without changing anything unless every entity is
+    /// delegated and in the user's scope.
+    pub fn give_authority_group(
+        &mut self,
+        user_key: &UserKey,
+        world_entities: &[E],
+    ) -> Result<(), AuthorityError> {
+        self.world_server
+            .entity_give_authority_group(user_key, world_entities)
+    }
+
+    /// Takes authority over every entity in `world_entities` back to the
+    /// server, as [`entity_take_authority`](Server::entity_take_authority)
+    /// would for each. Fails without changing anything unless every entity
+    /// is delegated.
+    pub fn take_authority_group(&mut self, world_entities: &[E]) -> Result<(), AuthorityError> {
+        self.world_server.entity_take_authority_group(world_entities)
+    }
+
     /// Updates the [`ReplicationConfig`] for a registered entity.
     ///
     /// Changes take effect on the next [`send_all_packets`](Server::send_all_packets)
@@ -785,21 +827,34 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
         }
     }
 
-    /// Returns the keys of all currently connected users.
+    /// Returns the keys of all currently connected users. Observers aren't
+    /// players, so they're left out; see [`observer_keys`](Self::observer_keys).
     pub fn user_keys(&self) -> Vec<UserKey> {
         self.main_server.user_keys()
     }
 
-    /// Returns the number of currently connected users.
+    /// Returns the number of currently connected users, not counting
+    /// observers.
     pub fn users_count(&self) -> usize {
         self.main_server.users_count()
     }
 
-    /// Returns the number of users that have fully connected (handshake complete).
+    /// Returns the number of users that have fully connected (handshake
+    /// complete), not counting observers.
     pub fn user_count(&self) -> usize {
         self.world_server.user_count()
     }
 
+    /// Returns the keys of all connected observers.
+    pub fn observer_keys(&self) -> Vec<UserKey> {
+        self.world_server.observer_keys()
+    }
+
+    /// Returns the number of connected observers.
+    pub fn observer_count(&self) -> usize {
+        self.world_server.observer_count()
+    }
+
     /// Returns the total number of replicated entities currently tracked by the server.
     pub fn entity_count(&self) -> usize {
         self.world_server.entity_count()
@@ -991,6 +1046,55 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
         self.world_server.connection_stats(user_key)
     }
 
+    // Bandwidth profiling
+
+    /// Returns where the bits sent to the given user went — header, acks,
+    /// each channel, component, field and entity — or `None` if the user is
+    /// not connected or `ConnectionConfig::bandwidth_profiling` is off.
+    pub fn bandwidth_profile(&self, user_key: &UserKey) -> Option<BandwidthProfile> {
+        self.world_server.bandwidth_profile(user_key)
+    }
+
+    /// Returns where the bits sent to all users went since profiling started
+    /// or was last reset, including users who have since disconnected.
+    pub fn global_bandwidth_profile(&self) -> BandwidthProfile {
+        self.world_server.global_bandwidth_profile()
+    }
+
+    /// Clears every user's bandwidth profile, and the global one.
+    pub fn reset_bandwidth_profiles(&mut self) {
+        self.world_server.reset_bandwidth_profiles();
+    }
+
+    // Introspection
+
+    /// Takes a read-only [`ServerSnapshot`] of which users are in which
+    /// rooms, what each user has in scope, per-user queue depths and the
+    /// authority over delegated entities. Walks every user and entity, so
+    /// take it on an interval rather than every tick.
+    pub fn snapshot(&self) -> ServerSnapshot<E> {
+        self.world_server.snapshot()
+    }
+
+    // Compression
+
+    /// Takes the dictionary trained from packets sent to clients in
+    /// `CompressionMode::Training`. Returns `None` until the target number
+    /// of samples has been recorded, and after the dictionary has been taken.
+    ///
+    /// Store the bytes and pass them to `CompressionMode::Dictionary` on both
+    /// the server and its clients in a later build; peers identify it by its
+    /// [`DictionaryId`](naia_shared::DictionaryId).
+    pub fn take_trained_dictionary(&mut self) -> Option<Vec<u8>> {
+        self.world_server.take_trained_dictionary()
+    }
+
+    /// Fraction of the `CompressionMode::Training` sample target recorded so
+    /// far, from `0.0` to `1.0`. `None` when not training.
+    pub fn dictionary_training_progress(&self) -> Option<f32> {
+        self.world_server.dictionary_training_progress()
+    }
+
     // Historian — lag-compensation snapshot buffer
 
     /// Enable the per-tick snapshot buffer for server-side lag compensation.
@@ -1041,6 +1145,207 @@ impl<E: Copy + Eq + Hash + Send + Sync> Server<E> {
         self.world_server.historian()
     }
 
+    /// Returns a mutable reference to the Historian, or `None` if not
+    /// enabled. Use it to register interpolated components with
+    /// [`Historian::interpolate_component`].
+    pub fn historian_mut(&mut self) -> Option<&mut Historian> {
+        self.world_server.historian_mut()
+    }
+
+    /// Temporarily rewinds `world` to the historian's state `fraction` of the
+    /// way from `tick` to the next tick, runs `f` against it, and restores
+    /// the live values afterwards, so existing collision code can test hits
+    /// against what a client saw. Returns `None` without calling `f` if the
+    /// historian is disabled or `tick` has been evicted.
+    ///
+    /// Only components of server-owned entities that still exist are
+    /// rewound; entities spawned since `tick` are left as they are, so check
+    /// [`Historian::entity_lifetime`] to skip them in `f`. The live values
+    /// are restored even if `f` panics, and rewinding doesn't queue anything
+    /// to send: changes made before the call are still sent, and the rewind
+    /// itself, like any change `f` makes to a rewound component, is not.
+    ///
+    /// ```no_run
+    /// # use naia_server::Server;
+    /// # use naia_shared::{Tick, WorldMutType};
+    /// # fn example<E: Copy + Eq + std::hash::Hash + Send + Sync, W: WorldMutType<E>>(
+    /// #     server: &Server<E>, world: W, fire_tick: Tick, fraction: f32
+    /// # ) {
+    /// let hit = server.rewind_world(world, fire_tick, fraction, |world| {
+    ///     // run the usual raycast against `world`
+    ///     false
+    /// });
+    /// # }
+    /// ```
+    pub fn rewind_world<W: WorldMutType<E>, R>(
+        &self,
+        world: W,
+        tick: Tick,
+        fraction: f32,
+        f: impl FnOnce(&mut W) -> R,
+    ) -> Option<R> {
+        self.world_server.rewind_world(world, tick, fraction, f)
+    }
+
+    // World snapshots — save and restore across server restarts
+
+    /// Serializes every server-owned replicated entity to bytes: its
+    /// components, [`ReplicationConfig`], room membership and resource
+    /// registration, tagged with this server's protocol ID.
+    ///
+    /// Write the bytes wherever you like and pass them to
+    /// [`restore_world`](Server::restore_world) after a restart.
+    /// Client-owned entities are not saved.
+    pub fn save_world<W: WorldRefType<E>>(&self, world: W) -> Result<Vec<u8>, WorldSnapshotError> {
+        self.world_server.save_world(world)
+    }
+
+    /// Spawns everything saved by [`save_world`](Server::save_world) into
+    /// `world`, creating new rooms for the saved ones. `EntityProperty`
+    /// relations between saved entities point at the restored entities.
+    ///
+    /// Fails without changing anything if the snapshot was taken with a
+    /// different protocol ([`WorldSnapshotError::ProtocolMismatch`]), is
+    /// corrupt, or holds a resource that is already inserted.
+    pub fn restore_world<W: WorldMutType<E>>(
+        &mut self,
+        world: W,
+        bytes: &[u8],
+    ) -> Result<RestoredWorld<E>, WorldSnapshotError> {
+        self.world_server.restore_world(world, bytes)
+    }
+
+    // Demo recording — what a user received, for replays
+
+    /// Starts recording everything replicated to `user_key`: spawns,
+    /// despawns, component inserts, updates and removes, messages, and the
+    /// server tick each arrived on.
+    ///
+    /// The world is diffed against the previous recording at the end of
+    /// every [`send_all_packets`](Server::send_all_packets), so the demo
+    /// holds one frame per tick. Returns `false` if the user doesn't exist
+    /// or is already being recorded.
+    pub fn start_demo_recording(&mut self, user_key: &UserKey) -> bool {
+        self.world_server.start_demo_recording(user_key)
+    }
+
+    /// Stops recording `user_key` and returns the demo bytes, ready to be
+    /// written to a file and played back with `naia_client::DemoPlayer`.
+    /// Returns `None` if the user wasn't being recorded. A recording still
+    /// running when its user disconnects is discarded.
+    pub fn stop_demo_recording(&mut self, user_key: &UserKey) -> Option<Vec<u8>> {
+        self.world_server.stop_demo_recording(user_key)
+    }
+
+    /// Returns `true` if `user_key` is being recorded.
+    pub fn is_recording_demo(&self, user_key: &UserKey) -> bool {
+        self.world_server.is_recording_demo(user_key)
+    }
+
+    // Spatial interest — automatic distance-based scoping
+
+    /// Enables automatic spatial scoping, reading positions from component `R`.
+    ///
+    /// Once a user has a viewpoint (see
+    /// [`set_user_viewpoint`](Server::set_user_viewpoint)), entities carrying
+    /// `R` that share a room with them are only in scope while within
+    /// `config.enter_radius` / `config.exit_radius` of it. An explicit
+    /// [`user_scope_mut`](Server::user_scope_mut) include or exclude
+    /// overrides this in either direction. Scope is updated
+    /// automatically inside [`send_all_packets`](Server::send_all_packets);
+    /// there is no need to poll
+    /// [`scope_checks_pending`](Server::scope_checks_pending) for these
+    /// entities. Calling again replaces the manager and clears all viewpoints.
+    ///
+    /// ```no_run
+    /// # use naia_server::{Server, SpatialInterestConfig, SpatialPosition, UserKey};
+    /// # use naia_shared::ReplicatedComponent;
+    /// # fn example<E, Position>(server: &mut Server<E>, user_key: &UserKey, avatar: &E)
+    /// # where
+    /// #     E: Copy + Eq + std::hash::Hash + Send + Sync,
+    /// #     Position: ReplicatedComponent + SpatialPosition,
+    /// # {
+    /// server.enable_spatial_interest::<Position>(SpatialInterestConfig::new(100.0, 120.0));
+    /// server.set_user_viewpoint_entity(user_key, avatar);
+    /// # }
+    /// ```
+    pub fn enable_spatial_interest<R: ReplicatedComponent + SpatialPosition>(
+        &mut self,
+        config: SpatialInterestConfig,
+    ) {
+        self.world_server.enable_spatial_interest::<R>(config);
+    }
+
+    /// Disables spatial scoping and returns every user to room scoping.
+    pub fn disable_spatial_interest(&mut self) {
+        self.world_server.disable_spatial_interest();
+    }
+
+    /// Returns the spatial interest manager, or `None` if not enabled.
+    pub fn spatial_interest(&self) -> Option<&SpatialInterest> {
+        self.world_server.spatial_interest()
+    }
+
+    /// Sets a fixed viewpoint for the user. No-op if spatial interest is not
+    /// enabled.
+    pub fn set_user_viewpoint(&mut self, user_key: &UserKey, x: f32, y: f32) {
+        self.world_server.set_user_viewpoint(user_key, x, y);
+    }
+
+    /// Makes the user's viewpoint follow the position component of
+    /// `world_entity` (typically their avatar). No-op if spatial interest is
+    /// not enabled.
+    pub fn set_user_viewpoint_entity(&mut self, user_key: &UserKey, world_entity: &E) {
+        self.world_server
+            .set_user_viewpoint_entity(user_key, world_entity);
+    }
+
+    /// Removes the user's viewpoint, returning them to plain room scoping.
+    pub fn clear_user_viewpoint(&mut self, user_key: &UserKey) {
+        self.world_server.clear_user_viewpoint(user_key);
+    }
+
+    // Update validation — checks on client-authoritative updates
+
+    /// Checks every update to component `R` sent by a client holding
+    /// authority before it is applied, replacing any previous validator for `R`.
+    ///
+    /// The validator receives the sending user, the current value and the
+    /// value the update would produce. Returning
+    /// [`UpdateValidation::Reject`](crate::UpdateValidation::Reject) discards
+    /// the update; returning
+    /// [`UpdateValidation::Clamp`](crate::UpdateValidation::Clamp) applies the
+    /// validator's changes to the proposed value instead. In both cases the
+    /// server's value is sent back to the client over channel `C`, which
+    /// must be reliable and able to send to clients.
+    ///
+    /// ```no_run
+    /// # use naia_server::{Server, UpdateValidation};
+    /// # use naia_shared::{Channel, ReplicatedComponent};
+    /// # fn example<E, Position, Corrections>(server: &mut Server<E>)
+    /// # where
+    /// #     E: Copy + Eq + std::hash::Hash + Send + Sync,
+    /// #     Position: ReplicatedComponent,
+    /// #     Corrections: Channel,
+    /// # {
+    /// server.set_component_validator::<Position, Corrections>(|_user_key, _current, _proposed| {
+    ///     UpdateValidation::Accept
+    /// });
+    /// # }
+    /// ```
+    pub fn set_component_validator<R: ReplicatedComponent, C: Channel>(
+        &mut self,
+        validator: impl Fn(&UserKey, &R, &mut R) -> UpdateValidation + Send + Sync + 'static,
+    ) {
+        self.world_server.set_component_validator::<R, C>(validator);
+    }
+
+    /// Stops checking updates to component `R`. Returns whether a validator
+    /// was set.
+    pub fn remove_component_validator<R: ReplicatedComponent>(&mut self) -> bool {
+        self.world_server.remove_component_validator::<R>()
+    }
+
     /// Despawns the entity from the replication layer without touching the world.
     ///
     /// # Adapter use only
@@ -1,6 +1,6 @@
 use std::{default::Default, time::Duration};
 
-use naia_shared::ConnectionConfig;
+use naia_shared::{BandwidthConfig, ConnectionConfig};
 
 use crate::connection::ping_config::PingConfig;
 
@@ -21,6 +21,17 @@ pub struct ServerConfig {
     /// This prevents unauthenticated clients from holding server memory
     /// indefinitely. Default: 10 seconds.
     pub pending_auth_timeout: Duration,
+    /// Outbound bandwidth budget of each observer connection, used instead
+    /// of `connection.bandwidth` for users accepted with
+    /// `accept_observer_connection`.
+    pub observer_bandwidth: BandwidthConfig,
+    /// Server-wide outbound cap in bytes per second, shared by all
+    /// connections, observers included. When the connections' budgets add
+    /// up to more than this, each gets a max-min fair share: users asking
+    /// for less than an equal share keep their budget, the rest split what
+    /// is left. `None` (the default) leaves every connection at its own
+    /// budget.
+    pub aggregate_bytes_per_sec: Option<u32>,
 }
 
 impl Default for ServerConfig {
@@ -30,6 +41,8 @@ impl Default for ServerConfig {
             connection: ConnectionConfig::default(),
             ping: PingConfig::default(),
             pending_auth_timeout: Duration::from_secs(10),
+            observer_bandwidth: BandwidthConfig::default(),
+            aggregate_bytes_per_sec: None,
         }
     }
 }
@@ -1,5 +1,5 @@
 use std::{
-    collections::{hash_set::Iter, HashMap},
+    collections::{hash_set::Iter, HashMap, HashSet},
     net::SocketAddr,
 };
 
@@ -21,6 +21,9 @@ pub(super) struct UserStore {
     /// whose connection handshake has not yet completed. Removed when the
     /// handshake finalizes.
     disconnected_users: HashMap<SocketAddr, UserKey>,
+    /// Keys of the `users` accepted as observers, so work done for every
+    /// observer doesn't scan every user.
+    observers: HashSet<UserKey>,
 }
 
 impl UserStore {
@@ -28,6 +31,7 @@ impl UserStore {
         Self {
             users: HashMap::new(),
             disconnected_users: HashMap::new(),
+            observers: HashSet::new(),
         }
     }
 
@@ -46,19 +50,21 @@ impl UserStore {
     }
 
     pub(super) fn insert(&mut self, key: UserKey, user: WorldUser) {
+        if user.is_observer() {
+            self.observers.insert(key);
+        } else {
+            self.observers.remove(&key);
+        }
         self.users.insert(key, user);
     }
 
     /// Remove the `WorldUser` record. Does NOT touch `disconnected_users`
     /// (that entry is removed in `take_disconnected` at handshake time).
     pub(super) fn remove(&mut self, key: &UserKey) -> Option<WorldUser> {
+        self.observers.remove(key);
         self.users.remove(key)
     }
 
-    pub(super) fn len(&self) -> usize {
-        self.users.len()
-    }
-
     pub(super) fn iter(&self) -> impl Iterator<Item = (&UserKey, &WorldUser)> {
         self.users.iter()
     }
@@ -67,6 +73,14 @@ impl UserStore {
         self.users.keys().copied().collect()
     }
 
+    pub(super) fn observer_keys_copied(&self) -> Vec<UserKey> {
+        self.observers.iter().copied().collect()
+    }
+
+    pub(super) fn has_observers(&self) -> bool {
+        !self.observers.is_empty()
+    }
+
     // ── Convenience queries ───────────────────────────────────────────────
 
     pub(super) fn address(&self, key: &UserKey) -> Option<SocketAddr> {
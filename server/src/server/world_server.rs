@@ -1,6 +1,6 @@
 use std::{
-    any::Any,
-    collections::{hash_set::Iter, HashMap, HashSet, VecDeque},
+    any::{Any, TypeId},
+    collections::{hash_set::Iter, BTreeMap, HashMap, HashSet, VecDeque},
     hash::Hash,
     net::SocketAddr,
     panic,
@@ -10,26 +10,39 @@ use std::{
 use log::{info, warn};
 
 use naia_shared::{
-    handshake::HandshakeHeader, AuthorityError, BitReader, BitWriter, Channel, ChannelKind,
-    ConnectionStats, DisconnectReason,
-    ChannelKinds, ComponentKind, ComponentKinds, EntityAndGlobalEntityConverter, EntityAuthStatus,
-    EntityDoesNotExistError, EntityEvent, EntityPriorityMut, EntityPriorityRef, GlobalEntity,
+    handshake::HandshakeHeader, AuthorityError, BigMapKey, BitReader, BitWriter, Channel, ChannelKind,
+    BandwidthProfile, ComponentCorrection, ConnectionStats, DemoRecorder, DisconnectReason, EntityProperty,
+    UpdateValidation, UpdateValidator,
+    ChannelKinds, ComponentKind, ComponentKinds, DiffMask, EntityAndGlobalEntityConverter, EntityAuthStatus,
+    EntityDoesNotExistError, EntityEvent, EntityPriorityMut, EntityPriorityRef, FileBitWriter, GlobalEntity,
     GlobalEntityMap, GlobalEntitySpawner, GlobalPriorityState, GlobalRequestId, GlobalResponseId,
-    OutgoingPriorityHook, UserPriorityState,
-    GlobalWorldManagerType, HostType, Instant, Message, MessageContainer, MessageKinds, PacketType,
+    OutgoingPriorityHook, PacketCodec, ProtocolId, UserPriorityState,
+    GlobalWorldManagerType, HostType, Instant, Message, MessageContainer, MessageKinds, MutReceiver, PacketType,
     Protocol, Replicate, ReplicatedComponent, Request, ResourceAlreadyExists, ResourceRegistry,
     Response, ResponseReceiveKey, ResponseSendKey, Serde, SerdeErr, SharedGlobalWorldManager,
     StandardHeader, Tick, Timer, WorldMutType, WorldRefType,
 };
 
 use crate::{
-    connection::{connection::Connection, io::Io, tick_buffer_messages::TickBufferMessages},
+    connection::{
+        connection::Connection, fair_share::fair_shares, io::Io,
+        tick_buffer_messages::TickBufferMessages,
+    },
     events::{world_events::WorldEvents, TickEvents},
     handshake::HandshakeManager,
+    introspection::{
+        AuthorityHolder, EntityAuthoritySnapshot, RoomSnapshot, ServerSnapshot, UserSnapshot,
+    },
     request::{GlobalRequestManager, GlobalResponseManager},
     room::Room,
     server::scope_checks_cache::ScopeChecksCache,
+    spatial_interest::{SpatialInterest, SpatialInterestConfig, SpatialPosition, Viewpoint},
     time_manager::TimeManager,
+    validation::ComponentValidators,
+    world_snapshot::{
+        self, EntityRecord, RestoredWorld, SnapshotReadConverter, SnapshotWriteConverter,
+        WorldSnapshotError,
+    },
     transport::{PacketReceiver, PacketSender},
     world::{
         entity_mut::EntityMut, entity_owner::EntityOwner, entity_ref::EntityRef,
@@ -107,6 +120,38 @@ impl<'a, E: Copy + Eq + Hash + Send + Sync> OutgoingPriorityHook
     }
 }
 
+/// Holds the world while it's rewound, and puts back the live values and
+/// their unsent-change masks when dropped, even if the closure panics.
+struct RewindGuard<E: Copy, W: WorldMutType<E>> {
+    world: W,
+    rewound: Vec<RewoundComponent<E>>,
+}
+
+struct RewoundComponent<E> {
+    world_entity: E,
+    kind: ComponentKind,
+    live_value: Box<dyn Replicate>,
+    /// Each user's receiver and the mask it had before the rewind
+    masks: Vec<(MutReceiver, DiffMask)>,
+}
+
+impl<E: Copy, W: WorldMutType<E>> Drop for RewindGuard<E, W> {
+    fn drop(&mut self) {
+        for rewound in self.rewound.drain(..) {
+            if let Some(mut component) = self
+                .world
+                .component_mut_of_kind(&rewound.world_entity, &rewound.kind)
+            {
+                component.mirror(rewound.live_value.as_ref());
+            }
+            // Restoring the live value isn't a change any user needs sent
+            for (receiver, mask) in &rewound.masks {
+                receiver.restore_mask(mask);
+            }
+        }
+    }
+}
+
 /// A server that uses either UDP or WebRTC communication to send/receive
 /// messages to/from connected clients, and syncs registered entities to
 /// clients to whom they are in-scope
@@ -114,6 +159,7 @@ pub struct WorldServer<E: Copy + Eq + Hash + Send + Sync> {
     server_config: ServerConfig,
 
     // Protocol
+    protocol_id: ProtocolId,
     channel_kinds: ChannelKinds,
     message_kinds: MessageKinds,
     component_kinds: ComponentKinds,
@@ -164,14 +210,38 @@ pub struct WorldServer<E: Copy + Eq + Hash + Send + Sync> {
     // Optional lag-compensation snapshot buffer. None until enable_historian()
     // is called; record_historian_tick() is a no-op when None.
     historian: Option<crate::historian::Historian>,
+    // Optional uniform-grid interest manager. None until
+    // enable_spatial_interest() is called; when Some, it runs at the top of
+    // every send_all_packets() and gates the room-default scope rule.
+    spatial_interest: Option<SpatialInterest>,
+    // Checks on component updates from clients holding authority, per
+    // component kind. Empty unless set_component_validator() is called.
+    component_validators: ComponentValidators,
+    // Demo recordings of what each user receives. Empty unless
+    // start_demo_recording() is called; recorded at the end of every
+    // send_all_packets().
+    demo_recorders: HashMap<UserKey, DemoRecorder>,
+    // Bandwidth profiles of users who have disconnected, so the global
+    // profile outlives them. Empty unless `bandwidth_profiling` is set.
+    retired_bandwidth_profile: BandwidthProfile,
 }
 
 
 impl<E: Copy + Eq + Hash + Send + Sync> WorldServer<E> {
     /// Create a new WorldServer
     pub fn new<P: Into<Protocol>>(server_config: ServerConfig, protocol: P) -> Self {
-        let protocol: Protocol = protocol.into();
-
+        let mut protocol: Protocol = protocol.into();
+        protocol.lock();
+        let protocol_id = protocol.protocol_id();
+        Self::new_with_protocol_id(server_config, protocol, protocol_id)
+    }
+
+    /// Creates a new `WorldServer` using a pre-computed protocol ID (used by adapters sharing a protocol).
+    pub fn new_with_protocol_id(
+        server_config: ServerConfig,
+        protocol: Protocol,
+        protocol_id: ProtocolId,
+    ) -> Self {
         let Protocol {
             channel_kinds,
             message_kinds,
@@ -186,9 +256,13 @@ impl<E: Copy + Eq + Hash + Send + Sync> WorldServer<E> {
         let ping_timer = Timer::new(server_config.ping.ping_interval);
         let timeout_timer = Timer::new(server_config.connection.disconnection_timeout_duration);
 
+        // Incoming packets arrive from the MainServer already decoded
         let io = Io::new(
             &server_config.connection.bandwidth_measure_duration,
-            &compression,
+            compression
+                .as_ref()
+                .and_then(|config| config.server_to_client.as_ref()),
+            None,
         );
 
         let time_manager = TimeManager::new(tick_interval);
@@ -198,6 +272,7 @@ impl<E: Copy + Eq + Hash + Send + Sync> WorldServer<E> {
         Self {
             // Config
             server_config,
+            protocol_id,
             channel_kinds,
             message_kinds,
             component_kinds,
@@ -233,6 +308,10 @@ impl<E: Copy + Eq + Hash + Send + Sync> WorldServer<E> {
             scope_checks_cache: ScopeChecksCache::new(),
             resource_registry: ResourceRegistry::new(),
             historian: None,
+            spatial_interest: None,
+            component_validators: ComponentValidators::new(),
+            demo_recorders: HashMap::new(),
+            retired_bandwidth_profile: BandwidthProfile::default(),
         }
     }
 
@@ -252,9 +331,20 @@ impl<E: Copy + Eq + Hash + Send + Sync> WorldServer<E> {
     }
 
     /// Registers a newly-accepted user so the world server can track their scope (adapter use only).
-    pub fn receive_user(&mut self, user_key: UserKey, user_addr: SocketAddr) {
-        self.user_store.insert(user_key, WorldUser::new(user_addr));
+    ///
+    /// `compression_codec` is the server-to-client codec negotiated with the
+    /// user's client during the handshake.
+    pub fn receive_user(
+        &mut self,
+        user_key: UserKey,
+        user_addr: SocketAddr,
+        observer: bool,
+        compression_codec: PacketCodec,
+    ) {
+        self.user_store
+            .insert(user_key, WorldUser::new(user_addr, observer));
         self.user_store.register_disconnected(user_addr, user_key);
+        self.io.set_codec(&user_addr, compression_codec);
         // Auto-include of Replicated Resources happens in
         // `finalize_connection` — that's the point at which a Connection
         // exists in `user_connections` (required by `apply_scope_for_user`
@@ -268,8 +358,12 @@ impl<E: Copy + Eq + Hash + Send + Sync> WorldServer<E> {
         };
 
         use std::collections::hash_map::Entry;
+        let mut connection_config = self.server_config.connection.clone();
+        if self.user_is_observer(user_key) {
+            connection_config.bandwidth = self.server_config.observer_bandwidth.clone();
+        }
         let new_connection = Connection::new(
-            &self.server_config.connection,
+            &connection_config,
             &self.server_config.ping,
             user_address,
             user_key,
@@ -301,11 +395,24 @@ impl<E: Copy + Eq + Hash + Send + Sync> WorldServer<E> {
             self.user_scope_set_entity(user_key, &world_entity, true);
         }
 
+        if self.user_is_observer(user_key) {
+            self.scope_change_queue
+                .push_back(ScopeChange::ObserverConnected(*user_key));
+        }
+
         self.incoming_world_events.push_connection(user_key);
     }
 
     /// Maintain connection with a client and read all incoming packet data
     pub fn receive_all_packets(&mut self) {
+        let span = naia_shared::naia_span!(
+            INFO,
+            "receive_all_packets",
+            users = self.user_connections.len();
+            packets
+        );
+        let mut packets = 0;
+
         // Tick bandwidth monitors to clear expired packets
         self.io.tick_bandwidth_monitors();
 
@@ -320,6 +427,8 @@ impl<E: Copy + Eq + Hash + Send + Sync> WorldServer<E> {
         loop {
             match self.io.recv_reader() {
                 Ok(Some((address, owned_reader))) => {
+                    packets += 1;
+
                     // receive packet
                     let mut reader = owned_reader.borrow();
 
@@ -427,25 +536,36 @@ impl<E: Copy + Eq + Hash + Send + Sync> WorldServer<E> {
                 connection.process_received_commands();
             }
         }
+
+        span.record("packets", packets);
     }
 
     /// Decodes and applies all buffered incoming packets for this frame.
     pub fn process_all_packets<W: WorldMutType<E>>(&mut self, mut world: W, now: &Instant) {
-        self.process_disconnects(&mut world);
+        let _span = naia_shared::naia_span!(
+            INFO,
+            "process_all_packets",
+            connections = self.addrs_with_new_packets.len()
+        );
+        self.process_disconnects(&mut world, now);
 
         let addresses = std::mem::take(&mut self.addrs_with_new_packets);
         for address in addresses {
             self.process_packets(&address, &mut world, now);
         }
+
+        self.expire_authority_leases(now);
     }
 
     /// Drains and returns all pending world events for this frame.
     pub fn take_world_events(&mut self) -> WorldEvents<E> {
+        let _span = naia_shared::naia_span!(INFO, "take_world_events");
         std::mem::replace(&mut self.incoming_world_events, WorldEvents::<E>::new())
     }
 
     /// Advances the tick clock and returns any new tick events for this frame.
     pub fn take_tick_events(&mut self, now: &Instant) -> TickEvents {
+        let _span = naia_shared::naia_span!(INFO, "take_tick_events");
         // tick event
         if self.time_manager.recv_server_tick(now) {
             self.incoming_tick_events
@@ -483,6 +603,10 @@ impl<E: Copy + Eq + Hash + Send + Sync> WorldServer<E> {
         let Some(connection) = self.user_connections.get_mut(&user.address()) else {
             return Err(NaiaServerError::UserNotFound);
         };
+        let demo_message = self
+            .demo_recorders
+            .contains_key(user_key)
+            .then(|| message.clone());
         let mut converter = connection
             .base
             .world_manager
@@ -493,7 +617,15 @@ impl<E: Copy + Eq + Hash + Send + Sync> WorldServer<E> {
             channel_kind,
             message,
         );
-        if accepted { Ok(()) } else { Err(NaiaServerError::MessageQueueFull) }
+        if !accepted {
+            return Err(NaiaServerError::MessageQueueFull);
+        }
+        if let (Some(recorder), Some(message)) =
+            (self.demo_recorders.get_mut(user_key), demo_message)
+        {
+            recorder.record_message(channel_kind, message);
+        }
+        Ok(())
     }
 
     /// Sends a message to all connected users using the given channel.
@@ -516,7 +648,8 @@ impl<E: Copy + Eq + Hash + Send + Sync> WorldServer<E> {
         // a heap allocation. At 1,262 CCU this drops from 1,262 clone_box()
         // allocations per broadcast to 1.
         let container = MessageContainer::new(message_box);
-        let user_keys: Vec<UserKey> = self.user_keys().to_vec();
+        let mut user_keys = self.user_keys();
+        user_keys.append(&mut self.observer_keys());
         for user_key in user_keys {
             let _ = self.send_message_inner(&user_key, channel_kind, container.clone());
         }
@@ -636,9 +769,18 @@ impl<E: Copy + Eq + Hash + Send + Sync> WorldServer<E> {
     /// Drains and returns all tick-buffered messages sent by clients for the given tick.
     pub fn receive_tick_buffer_messages(&mut self, tick: &Tick) -> TickBufferMessages {
         let mut tick_buffer_messages = TickBufferMessages::new();
+        let mut dropped_messages = TickBufferMessages::new();
         for (_user_address, connection) in self.user_connections.iter_mut() {
-            // receive messages from anyone
-            connection.tick_buffer_messages(tick, &mut tick_buffer_messages);
+            // receive messages from anyone but observers, which are read-only
+            let observer = self
+                .user_store
+                .get(&connection.user_key)
+                .is_some_and(|user| user.is_observer());
+            if observer {
+                connection.tick_buffer_messages(tick, &mut dropped_messages);
+            } else {
+                connection.tick_buffer_messages(tick, &mut tick_buffer_messages);
+            }
         }
         tick_buffer_messages
     }
@@ -684,19 +826,50 @@ impl<E: Copy + Eq + Hash + Send + Sync> WorldServer<E> {
         {
             SERVER_SEND_ALL_PACKETS_CALLS.fetch_add(1, Ordering::Relaxed);
         }
+        let span = naia_shared::naia_span!(
+            INFO,
+            "send_all_packets",
+            users = self.user_connections.len();
+            bytes
+        );
         let now = Instant::now();
 
         // Zero per-tick byte counter so outgoing_bytes_last_tick() reports
         // only the bytes sent during THIS tick (readable after send_packets).
         self.io.reset_outgoing_bytes_this_tick();
 
-        // update entity scopes
-        self.update_entity_scopes(&world);
+        {
+            let _span = naia_shared::naia_span!(
+                INFO,
+                "update_entity_scopes",
+                queued_changes = self.scope_change_queue.len()
+            );
+
+            // spatial interest pushes its enter/exit transitions into the scope
+            // change queue, so it must run before the queue is drained
+            self.update_spatial_interest(&world, &now);
+
+            // update entity scopes
+            self.update_entity_scopes(&world);
+        }
 
         // loop through all connections, send packet
-        let mut user_addresses: Vec<SocketAddr> = self.user_connections.keys().copied().collect();
+        let mut user_addresses = Vec::new();
+        let mut observer_addresses = Vec::new();
+        for (address, connection) in &self.user_connections {
+            if self.user_is_observer(&connection.user_key) {
+                observer_addresses.push(*address);
+            } else {
+                user_addresses.push(*address);
+            }
+        }
         // shuffle order of connections in order to avoid priority among users
         fastrand::shuffle(&mut user_addresses);
+        fastrand::shuffle(&mut observer_addresses);
+        // observers go last, so they never hold up players
+        user_addresses.append(&mut observer_addresses);
+
+        self.share_aggregate_bandwidth(&user_addresses);
 
         for user_address in user_addresses {
             let connection = self.user_connections.get_mut(&user_address).unwrap();
@@ -757,6 +930,10 @@ impl<E: Copy + Eq + Hash + Send + Sync> WorldServer<E> {
                 }
             }
         }
+
+        self.record_demo_tick(&world);
+
+        span.record("bytes", self.io.outgoing_bytes_last_tick());
     }
 
     // Entities
@@ -776,12 +953,23 @@ impl<E: Copy + Eq + Hash + Send + Sync> WorldServer<E> {
         let global_entity = self.global_entity_map.spawn(*world_entity, None);
         self.global_world_manager
             .insert_entity_record(&global_entity, EntityOwner::Server);
+        self.offer_to_observers(&global_entity);
     }
 
     fn spawn_static_entity_inner(&mut self, world_entity: &E) {
         let global_entity = self.global_entity_map.spawn(*world_entity, None);
         self.global_world_manager
             .insert_static_entity_record(&global_entity, EntityOwner::Server);
+        self.offer_to_observers(&global_entity);
+    }
+
+    /// Observers aren't reached through rooms, so each entity that becomes
+    /// visible is offered to them directly
+    fn offer_to_observers(&mut self, global_entity: &GlobalEntity) {
+        if self.user_store.has_observers() {
+            self.scope_change_queue
+                .push_back(ScopeChange::EntitySpawned(*global_entity));
+        }
     }
 
     /// This is used only for Bevy adapter crates, do not use otherwise!
@@ -1085,7 +1273,14 @@ impl<E: Copy + Eq + Hash + Send + Sync> WorldServer<E> {
             // When server takes authority, send Denied to clients whose state will change:
             // - If there was a client holder (Granted→Denied): send only to that client
             // - If no holder (Available→Denied): send to all clients in scope
+            // - Any queued requesters (Requested→Denied)
             self.send_take_authority_messages(&global_entity, previous_owner);
+            for queued_user in self
+                .global_world_manager
+                .clear_authority_requests(&global_entity)
+            {
+                self.send_set_authority(&queued_user, &global_entity, EntityAuthStatus::Denied);
+            }
             self.incoming_world_events.push_auth_reset(world_entity);
         }
         result.map(|_| ())
@@ -1171,6 +1366,116 @@ impl<E: Copy + Eq + Hash + Send + Sync> WorldServer<E> {
         }
     }
 
+    /// Follows a release of authority held by `previous_holder`: hands it to
+    /// the next queued requester if there is one, otherwise resets it to
+    /// Available for everyone.
+    fn send_release_authority_messages(
+        &mut self,
+        global_entity: &GlobalEntity,
+        previous_holder: Option<UserKey>,
+        now: &Instant,
+    ) {
+        if !self.entity_grant_next_queued_request(global_entity, previous_holder, now) {
+            self.send_reset_authority_messages(global_entity);
+        }
+    }
+
+    /// Grants authority to the first queued user still able to take it.
+    /// Returns false if nobody in the queue could.
+    fn entity_grant_next_queued_request(
+        &mut self,
+        global_entity: &GlobalEntity,
+        previous_holder: Option<UserKey>,
+        now: &Instant,
+    ) -> bool {
+        let Ok(world_entity) = self
+            .global_entity_map
+            .global_entity_to_entity(global_entity)
+        else {
+            return false;
+        };
+        while let Some(next_user) = self
+            .global_world_manager
+            .pop_authority_request(global_entity)
+        {
+            if !self.user_scope_has_entity(&next_user, &world_entity) {
+                continue;
+            }
+            if self
+                .global_world_manager
+                .client_request_authority(global_entity, &AuthOwner::Client(next_user))
+                .is_err()
+            {
+                // Drop this request and try the next one in line
+                continue;
+            }
+
+            // Everyone else was already Denied (or is still queued, and stays
+            // Requested), so only the new and previous holders change state
+            self.send_set_authority(&next_user, global_entity, EntityAuthStatus::Granted);
+            if let Some(previous_holder) = previous_holder {
+                self.send_set_authority(&previous_holder, global_entity, EntityAuthStatus::Denied);
+            }
+            self.global_world_manager
+                .start_authority_lease(global_entity, now);
+            self.incoming_world_events
+                .push_auth_grant(&next_user, &world_entity);
+            return true;
+        }
+        false
+    }
+
+    /// Sends a single SetAuthority to `user_key`, if the entity is mapped on
+    /// their connection.
+    fn send_set_authority(
+        &mut self,
+        user_key: &UserKey,
+        global_entity: &GlobalEntity,
+        status: EntityAuthStatus,
+    ) {
+        let Some(user) = self.user_store.get(user_key) else {
+            return;
+        };
+        let Some(connection) = self.user_connections.get_mut(&user.address()) else {
+            return;
+        };
+        if connection
+            .base
+            .world_manager
+            .has_global_entity(global_entity)
+        {
+            connection
+                .base
+                .world_manager
+                .host_send_set_auth(global_entity, status);
+        }
+    }
+
+    /// Ends every lease whose holder has gone a full lease without writing
+    /// to the entity, as if they had released it.
+    fn expire_authority_leases(&mut self, now: &Instant) {
+        for global_entity in self.global_world_manager.expired_authority_leases(now) {
+            let Some(holder) = self
+                .global_world_manager
+                .entity_client_authority_holder(&global_entity)
+            else {
+                continue;
+            };
+            let Ok(world_entity) = self
+                .global_entity_map
+                .global_entity_to_entity(&global_entity)
+            else {
+                continue;
+            };
+            if self
+                .entity_release_authority_at(Some(&holder), &world_entity, now)
+                .is_ok()
+            {
+                self.incoming_world_events.push_auth_reset(&world_entity);
+            }
+        }
+    }
+
     /// Applies a new [`ReplicationConfig`] to an entity, changing its visibility and authority model.
     pub fn configure_entity_replication<W: WorldMutType<E>>(
         &mut self,
@@ -1292,9 +1597,13 @@ impl<E: Copy + Eq + Hash + Send + Sync> WorldServer<E> {
             }
         }
 
-        // Always persist the scope_exit field regardless of whether publicity changed
+        // Always persist the scope_exit, update_mode and authority fields regardless of whether publicity changed
         self.global_world_manager
             .entity_set_scope_exit(&global_entity, config.scope_exit);
+        self.global_world_manager
+            .entity_set_update_mode(&global_entity, config.update_mode);
+        self.global_world_manager
+            .entity_set_authority_policy(&global_entity, config.authority);
     }
 
     /// This is used only for Bevy adapter crates, do not use otherwise!
@@ -1302,6 +1611,15 @@ impl<E: Copy + Eq + Hash + Send + Sync> WorldServer<E> {
         &mut self,
         origin_user: &UserKey,
         world_entity: &E,
+    ) -> Result<(), AuthorityError> {
+        self.entity_give_authority_at(origin_user, world_entity, &Instant::now())
+    }
+
+    fn entity_give_authority_at(
+        &mut self,
+        origin_user: &UserKey,
+        world_entity: &E,
+        now: &Instant,
     ) -> Result<(), AuthorityError> {
         let global_entity = self
             .global_entity_map
@@ -1319,6 +1637,10 @@ impl<E: Copy + Eq + Hash + Send + Sync> WorldServer<E> {
             return Err(AuthorityError::NotInScope);
         }
 
+        if self.user_is_observer(origin_user) {
+            return Err(AuthorityError::Observer);
+        }
+
         // Use the server-priority give path so we override any current
         // holder (per contract [entity-authority-10]). The previous
         // `client_request_authority` path failed with NotAvailable
@@ -1338,6 +1660,13 @@ impl<E: Copy + Eq + Hash + Send + Sync> WorldServer<E> {
             return Ok(());
         }
 
+        // A sovereign assignment supersedes the queue. Queued users get
+        // Denied in the fan-out below, like everyone else.
+        self.global_world_manager
+            .clear_authority_requests(&global_entity);
+        self.global_world_manager
+            .start_authority_lease(&global_entity, now);
+
         // entity authority was granted for origin user
         // for any users that have this entity in scope, send an `update_authority_status` message
 
@@ -1395,6 +1724,7 @@ impl<E: Copy + Eq + Hash + Send + Sync> WorldServer<E> {
         &mut self,
         requester_user: &UserKey,
         world_entity: &E,
+        now: &Instant,
     ) -> Result<(), AuthorityError> {
         let global_entity = self
             .global_entity_map
@@ -1405,10 +1735,54 @@ impl<E: Copy + Eq + Hash + Send + Sync> WorldServer<E> {
             return Err(AuthorityError::NotInScope);
         }
 
+        if self.user_is_observer(requester_user) {
+            // Observers are read-only; resolve their pending Requested status
+            if self.global_world_manager.entity_is_delegated(&global_entity) {
+                self.send_set_authority(requester_user, &global_entity, EntityAuthStatus::Denied);
+            }
+            return Err(AuthorityError::Observer);
+        }
+
         let requester = AuthOwner::from_user_key(Some(requester_user));
+        let result = self
+            .global_world_manager
+            .client_request_authority(&global_entity, &requester);
+        if let Err(AuthorityError::NotAvailable) = result {
+            let policy = self
+                .global_world_manager
+                .entity_authority_policy(&global_entity);
+            if policy.queue_requests {
+                // Acknowledge the place in line; the requester stays Requested
+                // until the authority is handed over to them
+                if self
+                    .global_world_manager
+                    .enqueue_authority_request(&global_entity, requester_user)
+                {
+                    self.send_set_authority(
+                        requester_user,
+                        &global_entity,
+                        EntityAuthStatus::Requested,
+                    );
+                }
+                return Ok(());
+            }
+            // Resolve the requester's pending Requested status
+            self.send_set_authority(requester_user, &global_entity, EntityAuthStatus::Denied);
+        }
+        result?;
         self.global_world_manager
-            .client_request_authority(&global_entity, &requester)?;
+            .start_authority_lease(&global_entity, now);
+        self.send_client_grant_messages(&global_entity, requester_user);
+
+        self.incoming_world_events
+            .push_auth_grant(requester_user, world_entity);
+
+        Ok(())
+    }
 
+    /// Tells every user with the entity in scope that `holder` was just
+    /// granted authority over it
+    fn send_client_grant_messages(&mut self, global_entity: &GlobalEntity, holder: &UserKey) {
         for (user_key, user) in self.user_store.iter() {
             let Some(connection) = self.user_connections.get_mut(&user.address()) else {
                 continue;
@@ -1416,11 +1790,11 @@ impl<E: Copy + Eq + Hash + Send + Sync> WorldServer<E> {
             if !connection
                 .base
                 .world_manager
-                .has_global_entity(&global_entity)
+                .has_global_entity(global_entity)
             {
                 continue;
             }
-            let new_status = if requester_user == user_key {
+            let new_status = if holder == user_key {
                 EntityAuthStatus::Granted
             } else {
                 EntityAuthStatus::Denied
@@ -1428,12 +1802,131 @@ impl<E: Copy + Eq + Hash + Send + Sync> WorldServer<E> {
             connection
                 .base
                 .world_manager
-                .host_send_set_auth(&global_entity, new_status);
+                .host_send_set_auth(global_entity, new_status);
         }
+    }
 
-        self.incoming_world_events
-            .push_auth_grant(requester_user, world_entity);
+    /// Resolves a client's request for authority over a group of entities:
+    /// either every entity is granted, or none is and each is settled back
+    /// to its current status for the requester.
+    fn entity_handle_client_request_authority_group(
+        &mut self,
+        requester_user: &UserKey,
+        requested: Vec<Option<GlobalEntity>>,
+        now: &Instant,
+    ) {
+        let mut all_resolved = true;
+        let mut global_entities: Vec<GlobalEntity> = Vec::new();
+        for global_entity in requested {
+            match global_entity {
+                Some(global_entity) => {
+                    if !global_entities.contains(&global_entity) {
+                        global_entities.push(global_entity);
+                    }
+                }
+                None => all_resolved = false,
+            }
+        }
+
+        let mut world_entities = Vec::with_capacity(global_entities.len());
+        for global_entity in &global_entities {
+            match self.global_entity_map.global_entity_to_entity(global_entity) {
+                Ok(world_entity) => {
+                    if !self.user_scope_has_entity(requester_user, &world_entity) {
+                        all_resolved = false;
+                    }
+                    world_entities.push(Some(world_entity));
+                }
+                Err(_) => {
+                    all_resolved = false;
+                    world_entities.push(None);
+                }
+            }
+        }
+
+        let granted = all_resolved
+            && !self.user_is_observer(requester_user)
+            && self
+                .global_world_manager
+                .client_request_authority_group(&global_entities, requester_user)
+                .is_ok();
+
+        for (global_entity, world_entity) in global_entities.iter().zip(world_entities) {
+            let Some(world_entity) = world_entity else {
+                continue;
+            };
+            if granted {
+                self.global_world_manager
+                    .start_authority_lease(global_entity, now);
+                self.send_client_grant_messages(global_entity, requester_user);
+                self.incoming_world_events
+                    .push_auth_grant(requester_user, &world_entity);
+            } else {
+                if self.global_world_manager.entity_is_delegated(global_entity) {
+                    // Settle the requester's pending Requested status
+                    self.global_world_manager
+                        .dequeue_authority_request(global_entity, requester_user);
+                    let status = if self.global_world_manager.entity_has_holder(global_entity) {
+                        EntityAuthStatus::Denied
+                    } else {
+                        EntityAuthStatus::Available
+                    };
+                    self.send_set_authority(requester_user, global_entity, status);
+                }
+                // A member undelegated since the request can't carry a status;
+                // the requester settles it on DisableDelegation instead
+                self.incoming_world_events
+                    .push_auth_denied(requester_user, &world_entity);
+            }
+        }
+    }
+
+    /// Gives `origin_user` authority over every entity in `world_entities`.
+    /// Nothing changes unless all of them are delegated and in the user's
+    /// scope.
+    pub fn entity_give_authority_group(
+        &mut self,
+        origin_user: &UserKey,
+        world_entities: &[E],
+    ) -> Result<(), AuthorityError> {
+        for world_entity in world_entities {
+            self.check_authority_group_member(world_entity)?;
+            if !self.user_scope_has_entity(origin_user, world_entity) {
+                return Err(AuthorityError::NotInScope);
+            }
+        }
+        if self.user_is_observer(origin_user) {
+            return Err(AuthorityError::Observer);
+        }
+        let now = Instant::now();
+        for world_entity in world_entities {
+            self.entity_give_authority_at(origin_user, world_entity, &now)?;
+        }
+        Ok(())
+    }
+
+    /// Takes authority over every entity in `world_entities` back to the
+    /// server. Nothing changes unless all of them are delegated.
+    pub fn entity_take_authority_group(
+        &mut self,
+        world_entities: &[E],
+    ) -> Result<(), AuthorityError> {
+        for world_entity in world_entities {
+            self.check_authority_group_member(world_entity)?;
+        }
+        for world_entity in world_entities {
+            self.entity_take_authority(world_entity)?;
+        }
+        Ok(())
+    }
 
+    fn check_authority_group_member(&self, world_entity: &E) -> Result<(), AuthorityError> {
+        let Ok(global_entity) = self.global_entity_map.entity_to_global_entity(world_entity) else {
+            return Err(AuthorityError::NotDelegated);
+        };
+        if !self.global_world_manager.entity_is_delegated(&global_entity) {
+            return Err(AuthorityError::NotDelegated);
+        }
         Ok(())
     }
 
@@ -1464,21 +1957,49 @@ impl<E: Copy + Eq + Hash + Send + Sync> WorldServer<E> {
         &mut self,
         origin_user: Option<&UserKey>,
         world_entity: &E,
+    ) -> Result<(), AuthorityError> {
+        self.entity_release_authority_at(origin_user, world_entity, &Instant::now())
+    }
+
+    fn entity_release_authority_at(
+        &mut self,
+        origin_user: Option<&UserKey>,
+        world_entity: &E,
+        now: &Instant,
     ) -> Result<(), AuthorityError> {
         let releaser = AuthOwner::from_user_key(origin_user);
         let global_entity = self
             .global_entity_map
             .entity_to_global_entity(world_entity)
             .unwrap();
+        let previous_holder = self
+            .global_world_manager
+            .entity_client_authority_holder(&global_entity);
         let result = self
             .global_world_manager
             .client_release_authority(&global_entity, &releaser);
         if result.is_ok() {
-            self.send_reset_authority_messages(&global_entity);
+            self.send_release_authority_messages(&global_entity, previous_holder, now);
         }
         result
     }
 
+    /// Withdraws `user_key`'s queued request for authority over the entity,
+    /// if they have one. Returns whether they were queued.
+    fn entity_cancel_authority_request(&mut self, user_key: &UserKey, world_entity: &E) -> bool {
+        let Ok(global_entity) = self.global_entity_map.entity_to_global_entity(world_entity) else {
+            return false;
+        };
+        if !self
+            .global_world_manager
+            .dequeue_authority_request(&global_entity, user_key)
+        {
+            return false;
+        }
+        self.send_set_authority(user_key, &global_entity, EntityAuthStatus::Denied);
+        true
+    }
+
     /// Enable delegation for a server-owned entity
     ///
     /// This enables delegation for the given entity, allowing authority to be
@@ -1601,11 +2122,24 @@ impl<E: Copy + Eq + Hash + Send + Sync> WorldServer<E> {
         }
     }
 
-    /// Return a list of all currently connected Users' keys
+    /// Return a list of all currently connected Users' keys, not counting
+    /// observers
     pub fn user_keys(&self) -> Vec<UserKey> {
+        self.connected_user_keys(false)
+    }
+
+    /// Return a list of all currently connected observers' keys
+    pub fn observer_keys(&self) -> Vec<UserKey> {
+        self.connected_user_keys(true)
+    }
+
+    fn connected_user_keys(&self, observers: bool) -> Vec<UserKey> {
         let mut output = Vec::new();
 
         for (user_key, user) in self.user_store.iter() {
+            if user.is_observer() != observers {
+                continue;
+            }
             if self.user_connections.contains_key(&user.address()) {
                 output.push(*user_key);
             }
@@ -1614,16 +2148,25 @@ impl<E: Copy + Eq + Hash + Send + Sync> WorldServer<E> {
         output
     }
 
-    /// Get the number of Users currently connected
+    /// Get the number of Users currently connected, not counting observers
     pub fn users_count(&self) -> usize {
-        self.user_store.len()
+        self.user_store
+            .iter()
+            .filter(|(_, user)| !user.is_observer())
+            .count()
     }
 
-    /// Returns the number of users that have fully connected (handshake complete).
+    /// Returns the number of users that have fully connected (handshake
+    /// complete), not counting observers.
     pub fn user_count(&self) -> usize {
         self.user_keys().len()
     }
 
+    /// Returns the number of observers that have fully connected.
+    pub fn observer_count(&self) -> usize {
+        self.observer_keys().len()
+    }
+
     /// Returns the total number of replicated entities currently tracked by the server.
     pub fn entity_count(&self) -> usize {
         self.global_entity_map.entity_count()
@@ -1841,6 +2384,7 @@ impl<E: Copy + Eq + Hash + Send + Sync> WorldServer<E> {
         if let Some(historian) = &mut self.historian {
             historian.record_tick(
                 tick,
+                &self.component_kinds,
                 &self.global_world_manager,
                 &self.global_entity_map,
                 &world,
@@ -1854,28 +2398,642 @@ impl<E: Copy + Eq + Hash + Send + Sync> WorldServer<E> {
         self.historian.as_ref()
     }
 
-    /// Returns a snapshot of per-connection diagnostics for the given user.
-    ///
-    /// Returns `None` if the user is not connected. All fields are rolling
-    /// averages or short-window estimates computed on demand; no per-tick
-    /// allocation occurs.
-    pub fn connection_stats(&self, user_key: &UserKey) -> Option<ConnectionStats> {
-        let user = self.user_store.get(user_key)?;
-        let connection = self.user_connections.get(&user.address())?;
-        let pm = &connection.ping_manager;
-        Some(ConnectionStats {
-            rtt_ms: pm.rtt_average,
-            rtt_p50_ms: pm.rtt_p50_ms(),
-            rtt_p99_ms: pm.rtt_p99_ms(),
-            jitter_ms: pm.jitter_average,
-            packet_loss_pct: connection.base.packet_loss_pct(),
-            kbps_sent: self.io.outgoing_bandwidth_to_client(&user.address()),
-            kbps_recv: self.io.incoming_bandwidth_from_client(&user.address()),
-        })
+    /// Returns a mutable reference to the Historian, or `None` if it has not
+    /// been enabled via `enable_historian()`.
+    pub fn historian_mut(&mut self) -> Option<&mut crate::historian::Historian> {
+        self.historian.as_mut()
     }
 
-    // Crate-Public methods
-
+    /// Writes the historian's state `fraction` of the way from `tick` to the
+    /// next tick into `world`, runs `f` against it, then writes the live
+    /// values back. Returns `None` without calling `f` if the historian is
+    /// disabled or `tick` isn't retained.
+    ///
+    /// Only components of server-owned entities that still exist, and that
+    /// the snapshot holds a value for, are rewound.
+    pub fn rewind_world<W: WorldMutType<E>, R>(
+        &self,
+        world: W,
+        tick: Tick,
+        fraction: f32,
+        f: impl FnOnce(&mut W) -> R,
+    ) -> Option<R> {
+        let snapshot = self.historian.as_ref()?.snapshot_at(tick, fraction)?;
+
+        let mut guard = RewindGuard {
+            world,
+            rewound: Vec::new(),
+        };
+        for (global_entity, entity_snapshot) in &snapshot {
+            let Ok(entity_snapshot) = entity_snapshot else {
+                continue;
+            };
+            let is_server_owned = self
+                .global_world_manager
+                .entity_owner(global_entity)
+                .is_some_and(|owner| owner.is_server());
+            if !is_server_owned {
+                continue;
+            }
+            let Ok(world_entity) = self.global_entity_map.global_entity_to_entity(global_entity)
+            else {
+                continue;
+            };
+            for (kind, historical) in entity_snapshot {
+                let Some(mut component) = guard.world.component_mut_of_kind(&world_entity, kind)
+                else {
+                    continue;
+                };
+                let masks = self
+                    .global_world_manager
+                    .component_receivers(global_entity, kind)
+                    .into_iter()
+                    .map(|receiver| {
+                        let mask = receiver.mask_snapshot();
+                        (receiver, mask)
+                    })
+                    .collect();
+                guard.rewound.push(RewoundComponent {
+                    world_entity,
+                    kind: *kind,
+                    live_value: component.copy_to_box(),
+                    masks,
+                });
+                component.mirror(historical.as_ref());
+            }
+        }
+
+        Some(f(&mut guard.world))
+    }
+
+    // Demo recording
+
+    /// Starts recording everything replicated to `user_key` into a demo.
+    /// Returns `false` if the user doesn't exist or is already being
+    /// recorded.
+    pub fn start_demo_recording(&mut self, user_key: &UserKey) -> bool {
+        if !self.user_store.contains(user_key) || self.demo_recorders.contains_key(user_key) {
+            return false;
+        }
+        let recorder = DemoRecorder::new(self.protocol_id, self.time_manager.tick_interval());
+        self.demo_recorders.insert(*user_key, recorder);
+        true
+    }
+
+    /// Stops recording `user_key` and returns the demo, or `None` if the
+    /// user wasn't being recorded.
+    pub fn stop_demo_recording(&mut self, user_key: &UserKey) -> Option<Vec<u8>> {
+        self.demo_recorders
+            .remove(user_key)
+            .map(DemoRecorder::finish)
+    }
+
+    /// Returns `true` if `user_key` is being recorded.
+    pub fn is_recording_demo(&self, user_key: &UserKey) -> bool {
+        self.demo_recorders.contains_key(user_key)
+    }
+
+    /// Records, for each user with a demo recording, the entities currently
+    /// in their scope. Users without a connection are skipped.
+    fn record_demo_tick<W: WorldRefType<E>>(&mut self, world: &W) {
+        if self.demo_recorders.is_empty() {
+            return;
+        }
+        let tick = self.time_manager.current_tick();
+        for (user_key, recorder) in self.demo_recorders.iter_mut() {
+            let Some(connection) = self
+                .user_store
+                .get(user_key)
+                .and_then(|user| self.user_connections.get(&user.address()))
+            else {
+                continue;
+            };
+            let entities = self
+                .global_world_manager
+                .all_global_entities()
+                .filter(|global_entity| {
+                    connection.base.world_manager.has_global_entity(global_entity)
+                })
+                .filter(|global_entity| {
+                    // The user's own entities weren't received, they were sent
+                    !matches!(
+                        self.global_world_manager.entity_owner(global_entity),
+                        Some(
+                            EntityOwner::Client(owner)
+                            | EntityOwner::ClientWaiting(owner)
+                            | EntityOwner::ClientPublic(owner)
+                        ) if owner == *user_key
+                    )
+                })
+                .filter_map(|global_entity| {
+                    let world_entity = self
+                        .global_entity_map
+                        .global_entity_to_entity(global_entity)
+                        .ok()?;
+                    let kinds = self
+                        .global_world_manager
+                        .component_kinds(global_entity)
+                        .unwrap_or_default();
+                    Some((*global_entity, world_entity, kinds))
+                })
+                .collect();
+            recorder.record_tick(
+                &self.component_kinds,
+                &self.message_kinds,
+                &self.channel_kinds,
+                tick,
+                world,
+                entities,
+            );
+        }
+    }
+
+    // World snapshots — save and restore across server restarts
+
+    /// Serializes every server-owned replicated entity, with its components,
+    /// [`ReplicationConfig`], room membership and resource registration.
+    /// Client-owned entities are left out, and relations pointing at them
+    /// are saved empty.
+    pub fn save_world<W: WorldRefType<E>>(&self, world: W) -> Result<Vec<u8>, WorldSnapshotError> {
+        let mut global_entities: Vec<GlobalEntity> = self
+            .global_world_manager
+            .all_global_entities()
+            .filter(|global_entity| {
+                self.global_world_manager
+                    .entity_owner(global_entity)
+                    .is_some_and(|owner| owner.is_server())
+            })
+            .filter(|global_entity| {
+                self.global_entity_map
+                    .global_entity_to_entity(global_entity)
+                    .is_ok_and(|world_entity| world.has_entity(&world_entity))
+            })
+            .copied()
+            .collect();
+        global_entities.sort_by_key(|global_entity| global_entity.to_u64());
+        let entity_indices: HashMap<GlobalEntity, usize> = global_entities
+            .iter()
+            .enumerate()
+            .map(|(index, global_entity)| (*global_entity, index))
+            .collect();
+
+        // Components go first into their own buffer, since writing them
+        // decides which entities relations need to address
+        let mut converter = SnapshotWriteConverter::new(&entity_indices);
+        let mut component_writer = FileBitWriter::new();
+        let mut component_names = HashSet::new();
+        for global_entity in &global_entities {
+            let world_entity = self
+                .global_entity_map
+                .global_entity_to_entity(global_entity)
+                .unwrap();
+            let mut kinds = self
+                .global_world_manager
+                .component_kinds(global_entity)
+                .unwrap_or_default();
+            kinds.retain(|kind| world.has_component_of_kind(&world_entity, kind));
+            kinds.sort_by_key(|kind| self.component_kinds.net_id_of(kind));
+            world_snapshot::write_count(kinds.len(), &mut component_writer);
+            for kind in kinds {
+                let component = world
+                    .component_of_kind(&world_entity, &kind)
+                    .expect("component kind was just checked");
+                component.write(&self.component_kinds, &mut component_writer, &mut converter);
+                component_names.insert(self.component_kinds.kind_to_name(&kind));
+            }
+        }
+        let relation_targets = converter.into_relation_targets()?;
+        let mut component_names: Vec<String> = component_names.into_iter().collect();
+        component_names.sort();
+
+        let mut writer = FileBitWriter::new();
+        world_snapshot::write_header(&mut writer, self.protocol_id, &component_names);
+        world_snapshot::write_count(global_entities.len(), &mut writer);
+        for global_entity in &global_entities {
+            EntityRecord {
+                is_static: self.global_world_manager.entity_is_static(global_entity),
+                resource: self
+                    .resource_registry
+                    .type_for(global_entity)
+                    .map(ComponentKind::from),
+                config: self
+                    .global_world_manager
+                    .entity_replication_config(global_entity)
+                    .unwrap_or(ReplicationConfig::public()),
+            }
+            .ser(&self.component_kinds, &mut writer);
+        }
+        let room_keys = self.room_store.keys();
+        world_snapshot::write_count(room_keys.len(), &mut writer);
+        for room_key in room_keys {
+            room_key.to_u64().ser(&mut writer);
+            let mut members: Vec<usize> = self
+                .room_entities(&room_key)
+                .filter_map(|global_entity| entity_indices.get(global_entity).copied())
+                .collect();
+            members.sort();
+            world_snapshot::write_entity_indices(&members, &mut writer);
+        }
+        world_snapshot::write_entity_indices(&relation_targets, &mut writer);
+        component_writer.to_vec().ser(&mut writer);
+
+        Ok(writer.to_vec())
+    }
+
+    /// Spawns the entities, rooms and resources saved by
+    /// [`save_world`](Self::save_world). Relations between saved entities
+    /// point at their restored counterparts.
+    ///
+    /// Fails without changing anything if the snapshot was taken with a
+    /// different protocol, is corrupt, or holds a resource already inserted
+    /// on this server.
+    pub fn restore_world<W: WorldMutType<E>>(
+        &mut self,
+        mut world: W,
+        bytes: &[u8],
+    ) -> Result<RestoredWorld<E>, WorldSnapshotError> {
+        let mut reader = BitReader::new(bytes);
+        world_snapshot::read_header(&mut reader, self.protocol_id, &self.component_kinds)?;
+        let entity_count = world_snapshot::read_count(&mut reader)?;
+        let records =
+            world_snapshot::read_records(&mut reader, &self.component_kinds, entity_count)?;
+        let room_count = world_snapshot::read_count(&mut reader)?;
+        let mut rooms = Vec::new();
+        for _ in 0..room_count {
+            let room_key = RoomKey::from_u64(u64::de(&mut reader)?);
+            let members = world_snapshot::read_entity_indices(&mut reader, entity_count)?;
+            rooms.push((room_key, members));
+        }
+        let relation_targets = world_snapshot::read_entity_indices(&mut reader, entity_count)?;
+        let component_bytes = Vec::<u8>::de(&mut reader)?;
+        for record in &records {
+            if let Some(kind) = &record.resource {
+                if self.resource_registry.entity_for_raw(&TypeId::from(*kind)).is_some() {
+                    return Err(WorldSnapshotError::ResourceAlreadyExists(
+                        self.component_kinds.kind_to_name(kind),
+                    ));
+                }
+            }
+        }
+
+        // Spawn every entity before reading components, so relations resolve
+        let mut world_entities = Vec::with_capacity(records.len());
+        let mut global_entities = Vec::with_capacity(records.len());
+        for record in &records {
+            let world_entity = world.spawn_entity();
+            if record.is_static {
+                self.spawn_static_entity_inner(&world_entity);
+            } else {
+                self.spawn_entity_inner(&world_entity);
+            }
+            let global_entity = self
+                .global_entity_map
+                .entity_to_global_entity(&world_entity)
+                .expect("entity just spawned must be in global map");
+            if let Some(kind) = &record.resource {
+                self.resource_registry
+                    .insert_raw(TypeId::from(*kind), global_entity)
+                    .expect("resources were checked above");
+            }
+            world_entities.push(world_entity);
+            global_entities.push(global_entity);
+        }
+
+        let converter = SnapshotReadConverter::new(
+            relation_targets
+                .iter()
+                .map(|index| global_entities[*index])
+                .collect(),
+        );
+        if let Err(error) =
+            self.restore_components(&mut world, &world_entities, &component_bytes, &converter)
+        {
+            for (world_entity, global_entity) in world_entities.iter().zip(&global_entities) {
+                self.resource_registry.remove_by_entity(global_entity);
+                self.despawn_entity_worldless(world_entity);
+                world.despawn_entity(world_entity);
+            }
+            return Err(error);
+        }
+
+        let user_keys: Vec<UserKey> = self.user_store.keys_copied();
+        for (world_entity, record) in world_entities.iter().zip(&records) {
+            if record.config != ReplicationConfig::public() {
+                self.configure_entity_replication(&mut world, world_entity, record.config);
+            }
+            if record.resource.is_some() {
+                for user_key in &user_keys {
+                    self.user_scope_set_entity(user_key, world_entity, true);
+                }
+            }
+        }
+
+        let mut room_keys = HashMap::new();
+        for (saved_key, members) in rooms {
+            let room_key = self.create_room().key();
+            for index in members {
+                self.room_add_entity(&room_key, &world_entities[index]);
+            }
+            room_keys.insert(saved_key, room_key);
+        }
+
+        Ok(RestoredWorld {
+            entities: world_entities,
+            rooms: room_keys,
+        })
+    }
+
+    fn restore_components<W: WorldMutType<E>>(
+        &mut self,
+        world: &mut W,
+        world_entities: &[E],
+        bytes: &[u8],
+        converter: &SnapshotReadConverter,
+    ) -> Result<(), WorldSnapshotError> {
+        let mut reader = BitReader::new(bytes);
+        for world_entity in world_entities {
+            for _ in 0..world_snapshot::read_count(&mut reader)? {
+                let mut component = self.component_kinds.read(&mut reader, converter)?;
+                // Read components are remote-owned; the server owns them now
+                component.host_own();
+                self.insert_component_worldless(world_entity, component.as_mut());
+                world.insert_boxed_component(world_entity, component);
+            }
+        }
+        Ok(())
+    }
+
+    // Spatial Interest — automatic grid-based scoping
+
+    /// Enable automatic spatial scoping, reading entity positions from
+    /// component `R`. Calling again replaces the manager and drops every
+    /// user's viewpoint.
+    pub fn enable_spatial_interest<R: ReplicatedComponent + SpatialPosition>(
+        &mut self,
+        config: SpatialInterestConfig,
+    ) {
+        self.disable_spatial_interest();
+        self.spatial_interest = Some(SpatialInterest::new::<R>(config));
+    }
+
+    /// Disable spatial scoping. Entities it had included are handed back to
+    /// the normal room rules on the next `send_all_packets`.
+    pub fn disable_spatial_interest(&mut self) {
+        let Some(spatial_interest) = &self.spatial_interest else {
+            return;
+        };
+        let user_keys: Vec<UserKey> = self
+            .user_store
+            .keys_copied()
+            .into_iter()
+            .filter(|user_key| spatial_interest.has_viewpoint(user_key))
+            .collect();
+        for user_key in user_keys {
+            self.clear_user_viewpoint(&user_key);
+        }
+        self.spatial_interest = None;
+    }
+
+    /// Returns the spatial interest manager, or `None` if it has not been
+    /// enabled via `enable_spatial_interest()`.
+    pub fn spatial_interest(&self) -> Option<&SpatialInterest> {
+        self.spatial_interest.as_ref()
+    }
+
+    // Update Validation
+
+    /// Check every update to component `R` sent by a client holding
+    /// authority before it is applied, replacing any previous validator
+    /// for `R`. The server's value is sent back over channel `C` after a
+    /// rejection or clamp.
+    pub fn set_component_validator<R: ReplicatedComponent, C: Channel>(
+        &mut self,
+        validator: impl Fn(&UserKey, &R, &mut R) -> UpdateValidation + Send + Sync + 'static,
+    ) {
+        let channel_kind = ChannelKind::of::<C>();
+        let channel_settings = self.channel_kinds.channel(&channel_kind);
+        if !channel_settings.reliable() || !channel_settings.can_send_to_client() {
+            panic!("Component corrections can only be sent over Reliable Channels which can send to Clients");
+        }
+        self.component_validators.insert::<R>(channel_kind, validator);
+    }
+
+    /// Stop checking updates to component `R`. Returns whether a validator
+    /// was set.
+    pub fn remove_component_validator<R: ReplicatedComponent>(&mut self) -> bool {
+        self.component_validators.remove(&ComponentKind::of::<R>())
+    }
+
+    /// Set a fixed viewpoint for the user. No-op if spatial interest is
+    /// disabled.
+    pub fn set_user_viewpoint(&mut self, user_key: &UserKey, x: f32, y: f32) {
+        self.set_user_viewpoint_inner(user_key, Viewpoint::Position(x, y));
+    }
+
+    /// Make the user's viewpoint follow an entity's position component
+    /// (typically their avatar). No-op if spatial interest is disabled.
+    pub fn set_user_viewpoint_entity(&mut self, user_key: &UserKey, world_entity: &E) {
+        let Ok(global_entity) = self.global_entity_map.entity_to_global_entity(world_entity)
+        else {
+            warn!("set_user_viewpoint_entity: entity is not replicated");
+            return;
+        };
+        self.set_user_viewpoint_inner(user_key, Viewpoint::Entity(global_entity));
+    }
+
+    fn set_user_viewpoint_inner(&mut self, user_key: &UserKey, viewpoint: Viewpoint) {
+        if !self.user_store.contains(user_key) {
+            return;
+        }
+        let Some(spatial_interest) = &mut self.spatial_interest else {
+            return;
+        };
+        if spatial_interest.set_viewpoint(user_key, viewpoint) {
+            // The spatial gate now applies to this user: room-default
+            // entities already in scope must be re-evaluated.
+            self.requeue_user_rooms(user_key);
+        }
+    }
+
+    /// Remove the user's viewpoint, returning them to plain room scoping.
+    pub fn clear_user_viewpoint(&mut self, user_key: &UserKey) {
+        let Some(spatial_interest) = &mut self.spatial_interest else {
+            return;
+        };
+        if spatial_interest.clear_viewpoint(user_key).is_none() {
+            return;
+        }
+        self.requeue_user_rooms(user_key);
+    }
+
+    /// Returns a snapshot of per-connection diagnostics for the given user.
+    ///
+    /// Returns `None` if the user is not connected. All fields are rolling
+    /// averages or short-window estimates computed on demand; no per-tick
+    /// allocation occurs.
+    pub fn connection_stats(&self, user_key: &UserKey) -> Option<ConnectionStats> {
+        let user = self.user_store.get(user_key)?;
+        let connection = self.user_connections.get(&user.address())?;
+        let pm = &connection.ping_manager;
+        Some(ConnectionStats {
+            rtt_ms: pm.rtt_average,
+            rtt_p50_ms: pm.rtt_p50_ms(),
+            rtt_p99_ms: pm.rtt_p99_ms(),
+            jitter_ms: pm.jitter_average,
+            packet_loss_pct: connection.base.packet_loss_pct(),
+            kbps_sent: self.io.outgoing_bandwidth_to_client(&user.address()),
+            kbps_recv: self.io.incoming_bandwidth_from_client(&user.address()),
+            kbps_target: (connection.base.bandwidth_target_bytes_per_sec() * 8.0 / 1000.0) as f32,
+            channels: connection.channel_stats(&self.channel_kinds),
+        })
+    }
+
+    /// Returns where the bits sent to the given user went, or `None` if the
+    /// user is not connected or `ConnectionConfig::bandwidth_profiling` is
+    /// off.
+    pub fn bandwidth_profile(&self, user_key: &UserKey) -> Option<BandwidthProfile> {
+        let user = self.user_store.get(user_key)?;
+        let connection = self.user_connections.get(&user.address())?;
+        let profiler = connection.base.bandwidth_profiler.as_ref()?;
+        Some(profiler.profile(&self.component_kinds, &self.channel_kinds))
+    }
+
+    /// Returns where the bits sent to all users went, including users who
+    /// have since disconnected. Empty when
+    /// `ConnectionConfig::bandwidth_profiling` is off.
+    pub fn global_bandwidth_profile(&self) -> BandwidthProfile {
+        let mut global = self.retired_bandwidth_profile.clone();
+        for connection in self.user_connections.values() {
+            if let Some(profiler) = &connection.base.bandwidth_profiler {
+                global.merge(&profiler.profile(&self.component_kinds, &self.channel_kinds));
+            }
+        }
+        global
+    }
+
+    // Introspection
+
+    /// Takes a read-only [`ServerSnapshot`] of every user, room and
+    /// delegated entity, for ops tooling and dashboards.
+    ///
+    /// This walks every user and entity, so take it on an interval rather
+    /// than every tick.
+    pub fn snapshot(&self) -> ServerSnapshot<E> {
+        let mut users: Vec<UserSnapshot<E>> = self
+            .user_store
+            .iter()
+            .map(|(user_key, user)| {
+                let mut rooms: Vec<u64> = user.room_keys().iter().map(|key| key.to_u64()).collect();
+                rooms.sort_unstable();
+                let connection = self.user_connections.get(&user.address());
+                let (entities_in_scope, channel_queue_depths, pending_component_updates, rtt_ms) =
+                    match connection {
+                        Some(connection) => (
+                            self.global_world_manager
+                                .all_global_entities()
+                                .filter(|global_entity| {
+                                    connection.base.world_manager.has_global_entity(global_entity)
+                                })
+                                .filter_map(|global_entity| {
+                                    self.global_entity_map
+                                        .global_entity_to_entity(global_entity)
+                                        .ok()
+                                })
+                                .collect(),
+                            connection
+                                .channel_stats(&self.channel_kinds)
+                                .into_iter()
+                                .map(|(name, stats)| (name, stats.queue_depth))
+                                .collect(),
+                            connection.base.world_manager.dirty_update_count(),
+                            connection.ping_manager.rtt_average,
+                        ),
+                        None => (Vec::new(), BTreeMap::new(), 0, 0.0),
+                    };
+                UserSnapshot {
+                    user_key: user_key.to_u64(),
+                    address: user.address(),
+                    connected: connection.is_some(),
+                    observer: user.is_observer(),
+                    rooms,
+                    entities_in_scope,
+                    channel_queue_depths,
+                    pending_component_updates,
+                    rtt_ms,
+                }
+            })
+            .collect();
+        users.sort_unstable_by_key(|user| user.user_key);
+
+        let mut rooms: Vec<RoomSnapshot<E>> = self
+            .room_store
+            .keys()
+            .into_iter()
+            .map(|room_key| {
+                let mut users: Vec<u64> = self
+                    .room_user_keys(&room_key)
+                    .map(|user_key| user_key.to_u64())
+                    .collect();
+                users.sort_unstable();
+                RoomSnapshot {
+                    room_key: room_key.to_u64(),
+                    users,
+                    entities: self
+                        .room_entities(&room_key)
+                        .filter_map(|global_entity| {
+                            self.global_entity_map
+                                .global_entity_to_entity(global_entity)
+                                .ok()
+                        })
+                        .collect(),
+                }
+            })
+            .collect();
+        rooms.sort_unstable_by_key(|room| room.room_key);
+
+        let delegated_entities = self
+            .global_world_manager
+            .entity_auth_owners()
+            .filter_map(|(global_entity, owner)| {
+                let entity = self
+                    .global_entity_map
+                    .global_entity_to_entity(global_entity)
+                    .ok()?;
+                let holder = match owner {
+                    AuthOwner::None => AuthorityHolder::Available,
+                    AuthOwner::Server => AuthorityHolder::Server,
+                    AuthOwner::Client(user_key) => AuthorityHolder::User(user_key.to_u64()),
+                };
+                Some(EntityAuthoritySnapshot {
+                    entity,
+                    holder,
+                    status: self
+                        .global_world_manager
+                        .entity_authority_status(global_entity),
+                })
+            })
+            .collect();
+
+        ServerSnapshot {
+            tick: self.time_manager.current_tick(),
+            users,
+            rooms,
+            delegated_entities,
+        }
+    }
+
+    /// Clears every user's bandwidth profile, and the global one.
+    pub fn reset_bandwidth_profiles(&mut self) {
+        self.retired_bandwidth_profile = BandwidthProfile::default();
+        for connection in self.user_connections.values_mut() {
+            if let Some(profiler) = &mut connection.base.bandwidth_profiler {
+                profiler.reset();
+            }
+        }
+    }
+
+    // Crate-Public methods
+
     //// Entities
 
     /// Despawns the Entity, if it exists.
@@ -1914,6 +3072,9 @@ impl<E: Copy + Eq + Hash + Send + Sync> WorldServer<E> {
         // affected room.
         self.scope_checks_cache
             .on_entity_despawned(*world_entity);
+        if let Some(spatial_interest) = &mut self.spatial_interest {
+            spatial_interest.remove_entity(&global_entity);
+        }
         self.cleanup_entity_replication(&global_entity);
         self.global_world_manager
             .remove_entity_record(&global_entity);
@@ -1974,6 +3135,21 @@ impl<E: Copy + Eq + Hash + Send + Sync> WorldServer<E> {
             .entity_to_global_entity(world_entity)
             .unwrap();
 
+        self.user_scope_set_global_entity(
+            user_key,
+            global_entity,
+            is_contained,
+            &Instant::now(),
+        );
+    }
+
+    fn user_scope_set_global_entity(
+        &mut self,
+        user_key: &UserKey,
+        global_entity: GlobalEntity,
+        is_contained: bool,
+        now: &Instant,
+    ) {
         // Per [entity-authority-12]: If the authority-holding client loses scope for E,
         // the server MUST release/reset authority for E.
         // Check if user is being removed from scope and is the authority holder
@@ -1989,10 +3165,14 @@ impl<E: Copy + Eq + Hash + Send + Sync> WorldServer<E> {
                 .client_release_authority(&global_entity, &releaser)
                 .is_ok()
             {
-                // Notify other clients that authority is now Available
-                self.send_reset_authority_messages(&global_entity);
+                // Notify other clients that authority is now Available (or handed over)
+                self.send_release_authority_messages(&global_entity, Some(*user_key), now);
             }
         }
+        if !is_contained {
+            self.global_world_manager
+                .dequeue_authority_request(&global_entity, user_key);
+        }
 
         // Per [entity-publication]: silently ignore explicit include() for Private entities
         // when the user is not the owner — mirrors the guard in user_scope_has_entity().
@@ -2064,6 +3244,11 @@ impl<E: Copy + Eq + Hash + Send + Sync> WorldServer<E> {
             return false;
         }
 
+        // Observers see every entity that isn't private
+        if self.user_is_observer(user_key) {
+            return true;
+        }
+
         // Check explicit include/exclude
         if let Some(in_scope) = self.entity_scope_map.get(user_key, &global_entity) {
             if *in_scope {
@@ -2089,6 +3274,11 @@ impl<E: Copy + Eq + Hash + Send + Sync> WorldServer<E> {
             }
             return *in_scope;
         }
+        // Spatially-managed pairs are out of scope unless they're inside the
+        // user's interest set
+        if self.entity_is_spatially_gated(user_key, &global_entity) {
+            return false;
+        }
         // Default: in-scope if user and entity share a room
         let Some(user) = self.user_store.get(user_key) else {
             return false;
@@ -2315,6 +3505,7 @@ impl<E: Copy + Eq + Hash + Send + Sync> WorldServer<E> {
                 self.scope_change_queue
                     .push_back(ScopeChange::EntityEnteredRoom(*global_entity, room_key));
             }
+            self.offer_to_observers(global_entity);
         }
         result
     }
@@ -2690,14 +3881,89 @@ impl<E: Copy + Eq + Hash + Send + Sync> WorldServer<E> {
         self.user_store.rooms_count(user_key)
     }
 
+    /// Overrides the User's outbound bandwidth budget, or restores the
+    /// configured one with `None`
+    pub(crate) fn user_set_bandwidth_override(
+        &mut self,
+        user_key: &UserKey,
+        bytes_per_sec: Option<u32>,
+    ) {
+        let Some(address) = self.user_address(user_key) else {
+            return;
+        };
+        if let Some(connection) = self.user_connections.get_mut(&address) {
+            connection.base.set_bandwidth_override(bytes_per_sec);
+        }
+    }
+
+    /// Returns the User's outbound bandwidth budget override, if any
+    pub(crate) fn user_bandwidth_override(&self, user_key: &UserKey) -> Option<u32> {
+        let address = self.user_address(user_key)?;
+        self.user_connections
+            .get(&address)?
+            .base
+            .bandwidth_override()
+    }
+
+    /// Caps each connection at its fair share of
+    /// `ServerConfig::aggregate_bytes_per_sec`, or lifts the caps if the
+    /// connections' budgets fit within it
+    fn share_aggregate_bandwidth(&mut self, addresses: &[SocketAddr]) {
+        let Some(total) = self.server_config.aggregate_bytes_per_sec else {
+            return;
+        };
+        let desired: Vec<f64> = addresses
+            .iter()
+            .map(|address| {
+                self.user_connections[address]
+                    .base
+                    .bandwidth_desired_bytes_per_sec()
+            })
+            .collect();
+        let shares = fair_shares(total as f64, &desired);
+        for (index, address) in addresses.iter().enumerate() {
+            let cap = shares.as_ref().map(|shares| shares[index]);
+            self.user_connections
+                .get_mut(address)
+                .unwrap()
+                .base
+                .set_bandwidth_cap(cap);
+        }
+    }
+
+    /// Returns the codec negotiated for packets to the User
+    pub(crate) fn user_compression_codec(&self, user_key: &UserKey) -> Option<PacketCodec> {
+        self.user_address(user_key).map(|address| self.io.codec(&address))
+    }
+
+    /// Takes the dictionary trained from outgoing packets in
+    /// `CompressionMode::Training`, once training has completed
+    pub fn take_trained_dictionary(&mut self) -> Option<Vec<u8>> {
+        self.io.take_trained_dictionary()
+    }
+
+    /// Fraction of the `CompressionMode::Training` sample target recorded so
+    /// far, or `None` when not training
+    pub fn dictionary_training_progress(&self) -> Option<f32> {
+        self.io.dictionary_training_progress()
+    }
+
+    /// Returns whether the User was accepted as an observer
+    pub(crate) fn user_is_observer(&self, user_key: &UserKey) -> bool {
+        self.user_store
+            .get(user_key)
+            .is_some_and(|user| user.is_observer())
+    }
+
     pub(crate) fn user_disconnect<W: WorldMutType<E>>(
         &mut self,
         user_key: &UserKey,
         reason: DisconnectReason,
         world: &mut W,
+        now: &Instant,
     ) {
         if self.client_authoritative_entities {
-            self.despawn_all_remote_entities(user_key, world);
+            self.despawn_all_remote_entities(user_key, world, now);
             if let Some(all_owned_entities) =
                 self.global_world_manager.user_all_owned_entities(user_key)
             {
@@ -2708,7 +3974,8 @@ impl<E: Copy + Eq + Hash + Send + Sync> WorldServer<E> {
                         .global_entity_map
                         .global_entity_to_entity(&global_entity)
                     {
-                        let _ = self.entity_release_authority(Some(user_key), &world_entity);
+                        let _ =
+                            self.entity_release_authority_at(Some(user_key), &world_entity, now);
                     }
                 }
             }
@@ -2746,13 +4013,27 @@ impl<E: Copy + Eq + Hash + Send + Sync> WorldServer<E> {
         let user_addr = user.address();
 
         info!("deleting authenticated user for {}", user.address());
-        self.user_connections.remove(&user_addr);
+        if let Some(connection) = self.user_connections.remove(&user_addr) {
+            if let Some(profiler) = &connection.base.bandwidth_profiler {
+                self.retired_bandwidth_profile
+                    .merge(&profiler.profile(&self.component_kinds, &self.channel_kinds));
+            }
+        }
 
         // Drop this user's entire per-user priority layer so entries never
         // leak across user sessions.
         self.user_priorities.remove(user_key);
 
+        self.global_world_manager
+            .remove_user_authority_requests(user_key);
+
+        // An unfinished demo recording goes with its user
+        self.demo_recorders.remove(user_key);
+
         self.entity_scope_map.remove_user(user_key);
+        if let Some(spatial_interest) = &mut self.spatial_interest {
+            spatial_interest.clear_viewpoint(user_key);
+        }
 
         // Clean up all user data
         for room_key in user.room_keys() {
@@ -2770,6 +4051,7 @@ impl<E: Copy + Eq + Hash + Send + Sync> WorldServer<E> {
         if self.io.bandwidth_monitor_enabled() {
             self.io.deregister_client(&user.address());
         }
+        self.io.remove_codec(&user.address());
 
         self.global_request_manager.purge_user(user_key);
         self.global_response_manager.purge_user(user_key);
@@ -2782,6 +4064,7 @@ impl<E: Copy + Eq + Hash + Send + Sync> WorldServer<E> {
         &mut self,
         user_key: &UserKey,
         world: &mut W,
+        now: &Instant,
     ) {
         let Some(user) = self.user_store.get(user_key) else {
             panic!("Attempting to despawn entities for a nonexistent user");
@@ -2797,7 +4080,7 @@ impl<E: Copy + Eq + Hash + Send + Sync> WorldServer<E> {
             &self.global_world_manager,
             remote_global_entities,
         );
-        self.process_entity_events(world, user_key, entity_events);
+        self.process_entity_events(world, user_key, entity_events, now);
     }
 
     //// Rooms
@@ -2946,10 +4229,10 @@ impl<E: Copy + Eq + Hash + Send + Sync> WorldServer<E> {
         Ok(())
     }
 
-    fn process_disconnects<W: WorldMutType<E>>(&mut self, world: &mut W) {
+    fn process_disconnects<W: WorldMutType<E>>(&mut self, world: &mut W, now: &Instant) {
         let user_disconnects = std::mem::take(&mut self.outstanding_disconnects);
         for (user_key, reason) in user_disconnects {
-            self.user_disconnect(&user_key, reason, world);
+            self.user_disconnect(&user_key, reason, world, now);
         }
     }
 
@@ -2960,27 +4243,121 @@ impl<E: Copy + Eq + Hash + Send + Sync> WorldServer<E> {
         now: &Instant,
     ) {
         // Packets requiring established connection
-        let (user_key, entity_events) = {
+        let (user_key, entity_events, corrections) = {
             let Some(connection) = self.user_connections.get_mut(address) else {
                 return;
             };
-            (
-                connection.user_key,
-                connection.process_packets(
-                    &self.message_kinds,
-                    &self.component_kinds,
-                    self.client_authoritative_entities,
-                    now,
-                    &mut self.global_entity_map,
-                    &mut self.global_world_manager,
-                    &mut self.global_request_manager,
-                    &mut self.global_response_manager,
-                    world,
-                    &mut self.incoming_world_events,
-                ),
-            )
+            let user_key = connection.user_key;
+            // Observers are read-only: nothing they send reaches the world
+            let read_only = self
+                .user_store
+                .get(&user_key)
+                .is_some_and(|user| user.is_observer());
+            let mut validator = (!self.component_validators.is_empty())
+                .then(|| self.component_validators.for_user(user_key));
+            let mut validator_mut: Option<&mut dyn UpdateValidator> = None;
+            if let Some(validator) = validator.as_mut() {
+                validator_mut = Some(validator);
+            }
+            let entity_events = connection.process_packets(
+                &self.message_kinds,
+                &self.component_kinds,
+                self.client_authoritative_entities,
+                read_only,
+                now,
+                &mut self.global_entity_map,
+                &mut self.global_world_manager,
+                &mut self.global_request_manager,
+                &mut self.global_response_manager,
+                world,
+                validator_mut,
+                &mut self.incoming_world_events,
+            );
+            let corrections = validator
+                .map(|validator| validator.take_corrections())
+                .unwrap_or_default();
+            (user_key, entity_events, corrections)
         };
-        self.process_entity_events(world, &user_key, entity_events);
+        self.renew_authority_leases(&user_key, &entity_events, now);
+        self.process_entity_events(world, &user_key, entity_events, now);
+        self.send_component_corrections(world, &user_key, corrections);
+        if let Some(connection) = self.user_connections.get_mut(address) {
+            for group in connection.take_authority_group_requests() {
+                self.entity_handle_client_request_authority_group(&user_key, group, now);
+            }
+        }
+    }
+
+    /// Any update from a leased holder counts as activity and restarts its lease
+    fn renew_authority_leases(
+        &mut self,
+        user_key: &UserKey,
+        entity_events: &[EntityEvent],
+        now: &Instant,
+    ) {
+        for event in entity_events {
+            if let EntityEvent::UpdateComponent(_, global_entity, _) = event {
+                if self
+                    .global_world_manager
+                    .user_is_authority_holder(user_key, global_entity)
+                {
+                    self.global_world_manager
+                        .renew_authority_lease(global_entity, now);
+                }
+            }
+        }
+    }
+
+    /// Sends the server's value of each component back to the user whose
+    /// update to it failed validation, so their copy stops diverging
+    fn send_component_corrections<W: WorldMutType<E>>(
+        &mut self,
+        world: &W,
+        user_key: &UserKey,
+        corrections: Vec<(GlobalEntity, ComponentKind)>,
+    ) {
+        for (global_entity, component_kind) in corrections {
+            let Some(channel_kind) = self.component_validators.channel_kind(&component_kind)
+            else {
+                continue;
+            };
+            let Ok(world_entity) = self.global_entity_map.global_entity_to_entity(&global_entity)
+            else {
+                continue;
+            };
+            let Some(component) = world.component_of_kind(&world_entity, &component_kind) else {
+                continue;
+            };
+            // Write a detached copy, since the received component's properties
+            // are owned by the client and can't be written
+            let component = component.copy_to_box();
+
+            let Some(user) = self.user_store.get(user_key) else {
+                return;
+            };
+            let Some(connection) = self.user_connections.get_mut(&user.address()) else {
+                return;
+            };
+            let mut entity = EntityProperty::new_for_message();
+            entity.set(&self.global_entity_map, &world_entity);
+            let mut converter = connection
+                .base
+                .world_manager
+                .entity_converter_mut(&self.global_world_manager);
+            let correction =
+                ComponentCorrection::new(
+                entity,
+                component.as_ref(),
+                &self.component_kinds,
+                &mut converter,
+            );
+            connection.base.message_manager.send_message(
+                &self.message_kinds,
+                &mut converter,
+                &channel_kind,
+                MessageContainer::new(Box::new(correction)),
+            );
+        }
     }
 
     fn process_entity_events<W: WorldMutType<E>>(
@@ -2988,6 +4365,7 @@ impl<E: Copy + Eq + Hash + Send + Sync> WorldServer<E> {
         world: &mut W,
         user_key: &UserKey,
         response_events: Vec<EntityEvent>,
+        now: &Instant,
     ) {
         let mut deferred_events = Vec::new();
         for response_event in response_events {
@@ -3021,7 +4399,7 @@ impl<E: Copy + Eq + Hash + Send + Sync> WorldServer<E> {
                         .push_despawn(user_key, &world_entity);
                     deferred_events.push(EntityEvent::Despawn(global_entity));
                 }
-                EntityEvent::InsertComponent(global_entity, component_kind) => {
+                EntityEvent::InsertComponent(_tick, global_entity, component_kind) => {
                     let world_entity = self
                         .global_entity_map
                         .global_entity_to_entity(&global_entity)
@@ -3157,7 +4535,10 @@ impl<E: Copy + Eq + Hash + Send + Sync> WorldServer<E> {
                         .global_entity_map
                         .global_entity_to_entity(&global_entity)
                         .unwrap();
-                    if self.entity_handle_client_request_authority(user_key, &world_entity).is_err() {
+                    if self
+                        .entity_handle_client_request_authority(user_key, &world_entity, now)
+                        .is_err()
+                    {
                         self.incoming_world_events.push_auth_denied(user_key, &world_entity);
                     }
                 }
@@ -3167,8 +4548,10 @@ impl<E: Copy + Eq + Hash + Send + Sync> WorldServer<E> {
                         .global_entity_map
                         .global_entity_to_entity(&global_entity)
                         .unwrap();
-                    if self
-                        .entity_release_authority(Some(user_key), &world_entity)
+                    if self.entity_cancel_authority_request(user_key, &world_entity) {
+                        // A queued user gave up their place in line
+                    } else if self
+                        .entity_release_authority_at(Some(user_key), &world_entity, now)
                         .is_ok()
                     {
                         self.incoming_world_events.push_auth_reset(&world_entity);
@@ -3342,6 +4725,10 @@ impl<E: Copy + Eq + Hash + Send + Sync> WorldServer<E> {
                 let Some(user) = self.user_store.get(&removed_user) else {
                     continue;
                 };
+                if user.is_observer() {
+                    // observers see the entity regardless of rooms
+                    continue;
+                }
                 let Some(connection) = self.user_connections.get_mut(&user.address()) else {
                     continue;
                 };
@@ -3383,6 +4770,7 @@ impl<E: Copy + Eq + Hash + Send + Sync> WorldServer<E> {
 
         // Loop 2: process queued scope changes.
         self.drain_scope_change_queue(world);
+
     }
 
     fn drain_scope_change_queue<W: WorldRefType<E>>(&mut self, world: &W) {
@@ -3409,6 +4797,9 @@ impl<E: Copy + Eq + Hash + Send + Sync> WorldServer<E> {
                     let Some(user) = self.user_store.get(&user_key) else {
                         continue;
                     };
+                    if user.is_observer() {
+                        continue;
+                    }
                     let user_rooms = user.room_keys().clone();
                     let Some(connection) =
                         self.user_connections.get_mut(&user.address().clone())
@@ -3459,6 +4850,21 @@ impl<E: Copy + Eq + Hash + Send + Sync> WorldServer<E> {
                 ScopeChange::ScopeToggled(user_key, global_entity, _is_included) => {
                     self.apply_scope_for_user(world, &user_key, &global_entity);
                 }
+                ScopeChange::ObserverConnected(user_key) => {
+                    let entity_list: Vec<GlobalEntity> = self
+                        .global_world_manager
+                        .all_global_entities()
+                        .copied()
+                        .collect();
+                    for global_entity in &entity_list {
+                        self.apply_scope_for_user(world, &user_key, global_entity);
+                    }
+                }
+                ScopeChange::EntitySpawned(global_entity) => {
+                    for user_key in &self.user_store.observer_keys_copied() {
+                        self.apply_scope_for_user(world, user_key, &global_entity);
+                    }
+                }
             }
         }
     }
@@ -3551,10 +4957,17 @@ impl<E: Copy + Eq + Hash + Send + Sync> WorldServer<E> {
             .unwrap_or(false)
             && !is_resource
             && entity_is_roomless;
+        // Spatial interest narrows the room default: a tracked entity only
+        // reaches a user with a viewpoint while inside their interest set.
+        let spatially_gated = self
+            .spatial_interest
+            .as_ref()
+            .is_some_and(|s| s.gates_out(user_key, global_entity));
         let should_be_in_scope = match explicit {
+            _ if user.is_observer() => true,
             Some(true) if server_owned_roomless_non_resource => false,
             Some(in_scope) => in_scope,
-            None => is_resource || in_common_room,
+            None => is_resource || (in_common_room && !spatially_gated),
         };
         if should_be_in_scope {
             if currently_in_scope {
@@ -3634,6 +5047,94 @@ impl<E: Copy + Eq + Hash + Send + Sync> WorldServer<E> {
         }
     }
 
+    // Spatial Interest
+
+    fn update_spatial_interest<W: WorldRefType<E>>(&mut self, world: &W, now: &Instant) {
+        let Some(spatial_interest) = &mut self.spatial_interest else {
+            return;
+        };
+
+        let position_kind = spatial_interest.position_kind();
+        let mut positions = Vec::new();
+        for global_entity in self.global_world_manager.all_global_entities() {
+            let Ok(world_entity) = self.global_entity_map.global_entity_to_entity(global_entity)
+            else {
+                continue;
+            };
+            let Some(component) = world.component_of_kind(&world_entity, &position_kind) else {
+                continue;
+            };
+            if let Some(position) = spatial_interest.read_position(&*component) {
+                positions.push((*global_entity, position));
+            }
+        }
+
+        let update = spatial_interest.update(positions);
+
+        for (user_key, global_entity) in update.entered {
+            self.scope_change_queue
+                .push_back(ScopeChange::ScopeToggled(user_key, global_entity, true));
+        }
+        for (user_key, global_entity) in update.exited {
+            self.spatial_release_entity(&user_key, &global_entity, now);
+        }
+        for (user_key, global_entity) in update.refresh {
+            self.scope_change_queue
+                .push_back(ScopeChange::ScopeToggled(user_key, global_entity, false));
+        }
+    }
+
+    /// Re-evaluate an entity that left the user's interest set, releasing
+    /// authority if that takes it out of the user's scope.
+    fn spatial_release_entity(
+        &mut self,
+        user_key: &UserKey,
+        global_entity: &GlobalEntity,
+        now: &Instant,
+    ) {
+        // Per [entity-authority-12], as in user_scope_set_entity()
+        if self
+            .global_world_manager
+            .user_is_authority_holder(user_key, global_entity)
+        {
+            let still_in_scope = self
+                .global_entity_map
+                .global_entity_to_entity(global_entity)
+                .map(|world_entity| self.user_scope_has_entity(user_key, &world_entity))
+                .unwrap_or(false);
+            if !still_in_scope
+                && self
+                    .global_world_manager
+                    .client_release_authority(global_entity, &AuthOwner::Client(*user_key))
+                    .is_ok()
+            {
+                self.send_release_authority_messages(global_entity, Some(*user_key), now);
+            }
+        }
+        self.global_world_manager
+            .dequeue_authority_request(global_entity, user_key);
+
+        self.scope_change_queue
+            .push_back(ScopeChange::ScopeToggled(*user_key, *global_entity, false));
+    }
+
+    fn entity_is_spatially_gated(&self, user_key: &UserKey, global_entity: &GlobalEntity) -> bool {
+        self.spatial_interest
+            .as_ref()
+            .is_some_and(|s| s.gates_out(user_key, global_entity))
+    }
+
+    /// Re-evaluate every entity in every room the user belongs to.
+    fn requeue_user_rooms(&mut self, user_key: &UserKey) {
+        let Some(user) = self.user_store.get(user_key) else {
+            return;
+        };
+        for room_key in user.room_keys() {
+            self.scope_change_queue
+                .push_back(ScopeChange::UserEnteredRoom(*user_key, *room_key));
+        }
+    }
+
     fn handle_disconnects(&mut self) {
         if self.timeout_timer.ringing() {
             self.timeout_timer.reset();
@@ -0,0 +1,641 @@
+use std::collections::{HashMap, HashSet};
+
+use naia_shared::{ComponentKind, GlobalEntity, Replicate, ReplicatedComponent};
+
+use crate::UserKey;
+
+/// Implemented by the replicated component that carries an entity's position
+/// for spatial interest management.
+///
+/// The grid is two-dimensional: return the pair of axes that matter for
+/// visibility (e.g. `(x, z)` for a 3D game on a ground plane).
+pub trait SpatialPosition {
+    /// Returns the entity's position on the interest grid.
+    fn spatial_position(&self) -> (f32, f32);
+}
+
+/// Tuning parameters for [`SpatialInterest`].
+///
+/// An entity enters a user's interest set once it is within `enter_radius`
+/// of the user's viewpoint and only leaves once it is farther than
+/// `exit_radius`. Keeping `exit_radius` larger than `enter_radius` prevents
+/// entities on the boundary from spawning and despawning every tick. All
+/// three values must be finite.
+#[derive(Clone, Debug)]
+pub struct SpatialInterestConfig {
+    /// Side length of one grid cell, in world units. A value close to
+    /// `exit_radius` keeps each query to a handful of cells.
+    pub cell_size: f32,
+    /// Distance at which an entity enters a user's scope.
+    pub enter_radius: f32,
+    /// Distance at which an entity leaves a user's scope. Must be
+    /// `>= enter_radius`.
+    pub exit_radius: f32,
+}
+
+impl SpatialInterestConfig {
+    /// Creates a config with the given radii and a cell size equal to
+    /// `exit_radius`.
+    pub fn new(enter_radius: f32, exit_radius: f32) -> Self {
+        Self {
+            cell_size: exit_radius,
+            enter_radius,
+            exit_radius,
+        }
+    }
+}
+
+impl Default for SpatialInterestConfig {
+    fn default() -> Self {
+        Self::new(100.0, 120.0)
+    }
+}
+
+/// Where a user is looking from.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum Viewpoint {
+    /// A fixed point on the grid.
+    Position(f32, f32),
+    /// Follows the tracked position of an entity (usually the user's avatar).
+    Entity(GlobalEntity),
+}
+
+/// Scope transitions produced by one [`SpatialInterest::update`] pass.
+#[derive(Default)]
+pub(crate) struct SpatialUpdate {
+    /// Entities that moved into a user's interest set.
+    pub entered: Vec<(UserKey, GlobalEntity)>,
+    /// Entities that left a user's interest set.
+    pub exited: Vec<(UserKey, GlobalEntity)>,
+    /// Pairs whose gating changed without an enter/exit (an entity started
+    /// or stopped being tracked) — re-evaluate scope only.
+    pub refresh: Vec<(UserKey, GlobalEntity)>,
+}
+
+type Cell = (i32, i32);
+
+struct TrackedEntity {
+    cell: Cell,
+    position: (f32, f32),
+}
+
+struct UserInterest {
+    viewpoint: Viewpoint,
+    // Where the interest set was last fully computed from; `None` forces a
+    // full pass
+    origin: Option<(f32, f32)>,
+    inside: HashSet<GlobalEntity>,
+}
+
+/// Uniform-grid spatial interest manager.
+///
+/// Enabled via [`Server::enable_spatial_interest`](crate::Server::enable_spatial_interest).
+/// Every entity carrying the designated position component is bucketed into a
+/// grid cell each tick. A user whose viewpoint moved queries only the cells
+/// overlapping their exit radius; a user whose viewpoint stayed put only
+/// re-checks the entities that moved. Per-tick cost is `O(entities)` for the
+/// grid refresh plus `O(nearby entities)` per moving viewpoint, independent
+/// of world size.
+///
+/// # Scope semantics
+///
+/// For a user with a viewpoint, a tracked entity that shares a room with the
+/// user is in scope only while it is inside the user's interest set. An
+/// explicit include or exclude through
+/// [`UserScopeMut`](crate::UserScopeMut) overrides the interest set in
+/// either direction. Users without a viewpoint, and entities without the
+/// position component, keep the normal room rules. Entities at a non-finite
+/// position are not tracked.
+pub struct SpatialInterest {
+    config: SpatialInterestConfig,
+    position_kind: ComponentKind,
+    position_fn: fn(&dyn Replicate) -> Option<(f32, f32)>,
+    cells: HashMap<Cell, Vec<GlobalEntity>>,
+    entities: HashMap<GlobalEntity, TrackedEntity>,
+    users: HashMap<UserKey, UserInterest>,
+}
+
+impl SpatialInterest {
+    /// Creates a manager that reads positions from component `R`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any value is non-finite, `exit_radius < enter_radius` or
+    /// `cell_size <= 0`.
+    pub fn new<R: ReplicatedComponent + SpatialPosition>(config: SpatialInterestConfig) -> Self {
+        assert_valid(&config);
+        Self {
+            config,
+            position_kind: ComponentKind::of::<R>(),
+            position_fn: |component| {
+                component
+                    .to_any()
+                    .downcast_ref::<R>()
+                    .map(|position| position.spatial_position())
+            },
+            cells: HashMap::new(),
+            entities: HashMap::new(),
+            users: HashMap::new(),
+        }
+    }
+
+    /// Returns the active configuration.
+    pub fn config(&self) -> &SpatialInterestConfig {
+        &self.config
+    }
+
+    /// Returns the component kind positions are read from.
+    pub fn position_kind(&self) -> ComponentKind {
+        self.position_kind
+    }
+
+    /// Number of entities currently tracked on the grid.
+    pub fn tracked_entities_count(&self) -> usize {
+        self.entities.len()
+    }
+
+    /// Returns `true` if the entity is currently in the user's interest set.
+    pub fn is_inside(&self, user_key: &UserKey, entity: &GlobalEntity) -> bool {
+        self.users
+            .get(user_key)
+            .map(|user| user.inside.contains(entity))
+            .unwrap_or(false)
+    }
+
+    /// Number of entities in the user's interest set, or `None` if the user
+    /// has no viewpoint.
+    pub fn interest_count(&self, user_key: &UserKey) -> Option<usize> {
+        self.users.get(user_key).map(|user| user.inside.len())
+    }
+
+    /// Returns `true` if the user has a viewpoint.
+    pub fn has_viewpoint(&self, user_key: &UserKey) -> bool {
+        self.users.contains_key(user_key)
+    }
+
+    /// `true` if the spatial gate keeps this (user, entity) pair out of room
+    /// scope: the user has a viewpoint and the entity is tracked on the grid
+    /// but outside the user's interest set.
+    pub(crate) fn gates_out(&self, user_key: &UserKey, entity: &GlobalEntity) -> bool {
+        let Some(user) = self.users.get(user_key) else {
+            return false;
+        };
+        self.entities.contains_key(entity) && !user.inside.contains(entity)
+    }
+
+    pub(crate) fn read_position(&self, component: &dyn Replicate) -> Option<(f32, f32)> {
+        (self.position_fn)(component)
+    }
+
+    /// Sets the user's viewpoint. Returns `true` if the user had no viewpoint
+    /// before (i.e. the spatial gate just started applying to them).
+    pub(crate) fn set_viewpoint(&mut self, user_key: &UserKey, viewpoint: Viewpoint) -> bool {
+        if let Some(user) = self.users.get_mut(user_key) {
+            user.viewpoint = viewpoint;
+            user.origin = None;
+            return false;
+        }
+        self.users.insert(
+            *user_key,
+            UserInterest {
+                viewpoint,
+                origin: None,
+                inside: HashSet::new(),
+            },
+        );
+        true
+    }
+
+    /// Removes the user's viewpoint and returns the entities that were inside
+    /// their interest set, or `None` if the user had no viewpoint.
+    pub(crate) fn clear_viewpoint(&mut self, user_key: &UserKey) -> Option<HashSet<GlobalEntity>> {
+        self.users.remove(user_key).map(|user| user.inside)
+    }
+
+    /// Drops all state for a despawned entity. No transitions are produced —
+    /// the entity's scope entries are cleaned up by the despawn itself.
+    pub(crate) fn remove_entity(&mut self, entity: &GlobalEntity) {
+        if let Some(tracked) = self.entities.remove(entity) {
+            self.cell_remove(tracked.cell, entity);
+        }
+        for user in self.users.values_mut() {
+            user.inside.remove(entity);
+        }
+    }
+
+    /// Refreshes the grid from this tick's positions and updates every
+    /// user's interest set.
+    ///
+    /// Interest is purely spatial; the caller combines it with room rules and
+    /// explicit scope.
+    pub(crate) fn update(
+        &mut self,
+        positions: impl IntoIterator<Item = (GlobalEntity, (f32, f32))>,
+    ) -> SpatialUpdate {
+        let mut output = SpatialUpdate::default();
+
+        // 1. Refresh the grid.
+        let mut seen: HashSet<GlobalEntity> = HashSet::with_capacity(self.entities.len());
+        let mut moved: Vec<GlobalEntity> = Vec::new();
+        let mut newly_tracked: Vec<GlobalEntity> = Vec::new();
+        for (entity, position) in positions {
+            let Some(cell) = self.cell_of(position) else {
+                // A non-finite position can't be placed; treat it as untracked
+                continue;
+            };
+            seen.insert(entity);
+            match self.entities.get_mut(&entity) {
+                Some(tracked) => {
+                    if tracked.position == position {
+                        continue;
+                    }
+                    tracked.position = position;
+                    moved.push(entity);
+                    if tracked.cell != cell {
+                        let old_cell = tracked.cell;
+                        tracked.cell = cell;
+                        self.cell_remove(old_cell, &entity);
+                        self.cells.entry(cell).or_default().push(entity);
+                    }
+                }
+                None => {
+                    self.entities.insert(entity, TrackedEntity { cell, position });
+                    self.cells.entry(cell).or_default().push(entity);
+                    moved.push(entity);
+                    newly_tracked.push(entity);
+                }
+            }
+        }
+        let untracked: Vec<GlobalEntity> = self
+            .entities
+            .keys()
+            .filter(|entity| !seen.contains(*entity))
+            .copied()
+            .collect();
+        for entity in &untracked {
+            let tracked = self.entities.remove(entity).unwrap();
+            self.cell_remove(tracked.cell, entity);
+            for (user_key, user) in self.users.iter_mut() {
+                if user.inside.remove(entity) {
+                    output.exited.push((*user_key, *entity));
+                } else {
+                    output.refresh.push((*user_key, *entity));
+                }
+            }
+        }
+
+        // 2. Update interest sets: a full query around a viewpoint that
+        // moved, otherwise only the entities that moved.
+        let enter_sq = self.config.enter_radius * self.config.enter_radius;
+        let exit_sq = self.config.exit_radius * self.config.exit_radius;
+        let user_keys: Vec<UserKey> = self.users.keys().copied().collect();
+        for user_key in user_keys {
+            let user = self.users.get(&user_key).unwrap();
+            let Some(origin) = self.resolve(&user.viewpoint) else {
+                // Viewpoint entity isn't tracked this tick; hold the
+                // previous interest set rather than flushing it.
+                continue;
+            };
+            let is_within = |entity: &GlobalEntity, dist_sq: f32| {
+                let radius_sq = if user.inside.contains(entity) {
+                    exit_sq
+                } else {
+                    enter_sq
+                };
+                dist_sq <= radius_sq
+            };
+
+            let (entered, exited) = if user.origin == Some(origin) {
+                let mut entered = Vec::new();
+                let mut exited = Vec::new();
+                for entity in &moved {
+                    let position = self.entities.get(entity).unwrap().position;
+                    let within = is_within(entity, distance_sq(origin, position));
+                    if within && !user.inside.contains(entity) {
+                        entered.push(*entity);
+                    } else if !within && user.inside.contains(entity) {
+                        exited.push(*entity);
+                    }
+                }
+                (entered, exited)
+            } else {
+                let mut next_inside: HashSet<GlobalEntity> = HashSet::new();
+                self.for_each_in_radius(origin, self.config.exit_radius, |entity, dist_sq| {
+                    if is_within(entity, dist_sq) {
+                        next_inside.insert(*entity);
+                    }
+                });
+                let exited = user.inside.difference(&next_inside).copied().collect();
+                let entered = next_inside.difference(&user.inside).copied().collect();
+                (entered, exited)
+            };
+
+            let user = self.users.get_mut(&user_key).unwrap();
+            user.origin = Some(origin);
+            for entity in exited {
+                user.inside.remove(&entity);
+                output.exited.push((user_key, entity));
+            }
+            for entity in entered {
+                user.inside.insert(entity);
+                output.entered.push((user_key, entity));
+            }
+            // A newly tracked entity that didn't enter is now gated out —
+            // any room-default spawn from before it had a position must be
+            // re-evaluated.
+            for entity in &newly_tracked {
+                if !user.inside.contains(entity) {
+                    output.refresh.push((user_key, *entity));
+                }
+            }
+        }
+
+        output
+    }
+
+    fn resolve(&self, viewpoint: &Viewpoint) -> Option<(f32, f32)> {
+        match viewpoint {
+            Viewpoint::Position(x, y) => (x.is_finite() && y.is_finite()).then_some((*x, *y)),
+            Viewpoint::Entity(entity) => self.entities.get(entity).map(|tracked| tracked.position),
+        }
+    }
+
+    /// The cell containing a position, or `None` if it isn't finite.
+    fn cell_of(&self, (x, y): (f32, f32)) -> Option<Cell> {
+        if !x.is_finite() || !y.is_finite() {
+            return None;
+        }
+        let cell_size = self.config.cell_size;
+        Some(((x / cell_size).floor() as i32, (y / cell_size).floor() as i32))
+    }
+
+    fn cell_remove(&mut self, cell: Cell, entity: &GlobalEntity) {
+        let Some(bucket) = self.cells.get_mut(&cell) else {
+            return;
+        };
+        if let Some(index) = bucket.iter().position(|e| e == entity) {
+            bucket.swap_remove(index);
+        }
+        if bucket.is_empty() {
+            self.cells.remove(&cell);
+        }
+    }
+
+    fn for_each_in_radius(
+        &self,
+        origin: (f32, f32),
+        radius: f32,
+        mut f: impl FnMut(&GlobalEntity, f32),
+    ) {
+        if !radius.is_finite() {
+            return;
+        }
+        let (Some((min_x, min_y)), Some((max_x, max_y))) = (
+            self.cell_of((origin.0 - radius, origin.1 - radius)),
+            self.cell_of((origin.0 + radius, origin.1 + radius)),
+        ) else {
+            return;
+        };
+        let mut visit = |bucket: &Vec<GlobalEntity>| {
+            for entity in bucket {
+                let position = self.entities.get(entity).unwrap().position;
+                f(entity, distance_sq(origin, position));
+            }
+        };
+
+        // Far from the origin cells are sparse; walk the occupied ones
+        // instead of the whole range
+        let range_cells = (max_x as i64 - min_x as i64 + 1) * (max_y as i64 - min_y as i64 + 1);
+        if range_cells > self.cells.len() as i64 {
+            for ((cx, cy), bucket) in &self.cells {
+                if (min_x..=max_x).contains(cx) && (min_y..=max_y).contains(cy) {
+                    visit(bucket);
+                }
+            }
+            return;
+        }
+        for cx in min_x..=max_x {
+            for cy in min_y..=max_y {
+                if let Some(bucket) = self.cells.get(&(cx, cy)) {
+                    visit(bucket);
+                }
+            }
+        }
+    }
+}
+
+fn assert_valid(config: &SpatialInterestConfig) {
+    assert!(
+        config.enter_radius.is_finite() && config.exit_radius.is_finite(),
+        "SpatialInterestConfig: enter_radius and exit_radius must be finite"
+    );
+    assert!(
+        config.exit_radius >= config.enter_radius,
+        "SpatialInterestConfig: exit_radius must be >= enter_radius"
+    );
+    assert!(
+        config.cell_size.is_finite() && config.cell_size > 0.0,
+        "SpatialInterestConfig: cell_size must be positive and finite"
+    );
+}
+
+fn distance_sq(a: (f32, f32), b: (f32, f32)) -> f32 {
+    let dx = b.0 - a.0;
+    let dy = b.1 - a.1;
+    dx * dx + dy * dy
+}
+
+#[cfg(test)]
+mod tests {
+    use naia_shared::{BigMapKey, GlobalEntity};
+
+    use super::*;
+
+    fn uk(n: u64) -> UserKey {
+        UserKey::from_u64(n)
+    }
+
+    fn ge(n: u64) -> GlobalEntity {
+        GlobalEntity::from_u64(n)
+    }
+
+    fn interest(enter: f32, exit: f32) -> SpatialInterest {
+        SpatialInterest {
+            config: SpatialInterestConfig::new(enter, exit),
+            position_kind: ComponentKind::from(std::any::TypeId::of::<()>()),
+            position_fn: |_| None,
+            cells: HashMap::new(),
+            entities: HashMap::new(),
+            users: HashMap::new(),
+        }
+    }
+
+    fn sorted(mut v: Vec<(UserKey, GlobalEntity)>) -> Vec<(UserKey, GlobalEntity)> {
+        v.sort_by_key(|(u, e)| (u.to_u64(), e.to_u64()));
+        v
+    }
+
+    #[test]
+    fn entity_enters_within_enter_radius() {
+        let mut si = interest(10.0, 15.0);
+        si.set_viewpoint(&uk(0), Viewpoint::Position(0.0, 0.0));
+        let out = si.update([(ge(1), (5.0, 0.0)), (ge(2), (12.0, 0.0))]);
+        assert_eq!(out.entered, vec![(uk(0), ge(1))]);
+        assert!(si.is_inside(&uk(0), &ge(1)));
+        assert!(!si.is_inside(&uk(0), &ge(2)));
+    }
+
+    #[test]
+    fn hysteresis_keeps_entity_until_exit_radius() {
+        let mut si = interest(10.0, 15.0);
+        si.set_viewpoint(&uk(0), Viewpoint::Position(0.0, 0.0));
+        si.update([(ge(1), (5.0, 0.0))]);
+
+        // Between the radii: stays inside.
+        let out = si.update([(ge(1), (12.0, 0.0))]);
+        assert!(out.entered.is_empty() && out.exited.is_empty());
+        assert!(si.is_inside(&uk(0), &ge(1)));
+
+        // Past the exit radius: leaves.
+        let out = si.update([(ge(1), (16.0, 0.0))]);
+        assert_eq!(out.exited, vec![(uk(0), ge(1))]);
+
+        // Back between the radii: does not re-enter.
+        let out = si.update([(ge(1), (12.0, 0.0))]);
+        assert!(out.entered.is_empty());
+    }
+
+    #[test]
+    fn still_viewpoint_only_rechecks_moved_entities() {
+        let mut si = interest(10.0, 15.0);
+        si.set_viewpoint(&uk(0), Viewpoint::Position(0.0, 0.0));
+        si.update([(ge(1), (5.0, 0.0)), (ge(2), (30.0, 0.0))]);
+
+        let out = si.update([(ge(1), (5.0, 0.0)), (ge(2), (3.0, 0.0))]);
+        assert_eq!(out.entered, vec![(uk(0), ge(2))]);
+        assert!(out.exited.is_empty() && out.refresh.is_empty());
+
+        let out = si.update([(ge(1), (20.0, 0.0)), (ge(2), (3.0, 0.0))]);
+        assert_eq!(out.exited, vec![(uk(0), ge(1))]);
+        assert!(out.entered.is_empty());
+    }
+
+    #[test]
+    fn non_finite_positions_are_not_tracked() {
+        let mut si = interest(10.0, 15.0);
+        si.set_viewpoint(&uk(0), Viewpoint::Position(0.0, 0.0));
+        si.update([(ge(1), (1.0, 0.0))]);
+        assert!(si.is_inside(&uk(0), &ge(1)));
+
+        let out = si.update([(ge(1), (f32::NAN, 0.0)), (ge(2), (f32::INFINITY, 0.0))]);
+        assert_eq!(out.exited, vec![(uk(0), ge(1))]);
+        assert_eq!(si.tracked_entities_count(), 0);
+        assert!(si.cells.is_empty());
+
+        // A non-finite viewpoint holds the previous interest set
+        si.update([(ge(1), (1.0, 0.0))]);
+        si.set_viewpoint(&uk(0), Viewpoint::Position(f32::NAN, 0.0));
+        let out = si.update([(ge(1), (2.0, 0.0))]);
+        assert!(out.exited.is_empty());
+        assert!(si.is_inside(&uk(0), &ge(1)));
+    }
+
+    #[test]
+    fn far_viewpoint_walks_occupied_cells() {
+        let mut si = interest(10.0, 10.0);
+        si.config.cell_size = 1e-3;
+        si.set_viewpoint(&uk(0), Viewpoint::Position(1e6, -1e6));
+        si.update([(ge(1), (1e6 + 1.0, -1e6)), (ge(2), (0.0, 0.0))]);
+        assert!(si.is_inside(&uk(0), &ge(1)));
+        assert!(!si.is_inside(&uk(0), &ge(2)));
+    }
+
+    #[test]
+    #[should_panic(expected = "must be finite")]
+    fn non_finite_radius_is_rejected() {
+        assert_valid(&SpatialInterestConfig::new(10.0, f32::INFINITY));
+    }
+
+    #[test]
+    fn entity_viewpoint_follows_entity() {
+        let mut si = interest(10.0, 10.0);
+        si.set_viewpoint(&uk(0), Viewpoint::Entity(ge(0)));
+        si.update([(ge(0), (0.0, 0.0)), (ge(1), (50.0, 0.0))]);
+        assert!(!si.is_inside(&uk(0), &ge(1)));
+        let out = si.update([(ge(0), (45.0, 0.0)), (ge(1), (50.0, 0.0))]);
+        assert_eq!(out.entered, vec![(uk(0), ge(1))]);
+    }
+
+    #[test]
+    fn untracked_entity_exits_and_refreshes() {
+        let mut si = interest(10.0, 10.0);
+        si.set_viewpoint(&uk(0), Viewpoint::Position(0.0, 0.0));
+        si.set_viewpoint(&uk(1), Viewpoint::Position(100.0, 0.0));
+        si.update([(ge(1), (0.0, 0.0))]);
+        let out = si.update(std::iter::empty());
+        assert_eq!(out.exited, vec![(uk(0), ge(1))]);
+        assert_eq!(out.refresh, vec![(uk(1), ge(1))]);
+        assert_eq!(si.tracked_entities_count(), 0);
+    }
+
+    #[test]
+    fn newly_tracked_out_of_range_entity_is_refreshed() {
+        let mut si = interest(10.0, 10.0);
+        si.set_viewpoint(&uk(0), Viewpoint::Position(0.0, 0.0));
+        let out = si.update([(ge(1), (0.0, 0.0)), (ge(2), (100.0, 0.0))]);
+        assert_eq!(out.refresh, vec![(uk(0), ge(2))]);
+    }
+
+    #[test]
+    fn negative_coordinates_and_cell_moves() {
+        let mut si = interest(3.0, 3.0);
+        si.config.cell_size = 2.0;
+        si.set_viewpoint(&uk(0), Viewpoint::Position(-5.0, -5.0));
+        si.update([(ge(1), (-6.5, -4.0))]);
+        assert!(si.is_inside(&uk(0), &ge(1)));
+        si.update([(ge(1), (5.0, 5.0))]);
+        assert!(!si.is_inside(&uk(0), &ge(1)));
+        assert_eq!(si.cells.len(), 1);
+    }
+
+    /// Brute-force cross-check of the grid query against every pair.
+    #[test]
+    fn grid_matches_brute_force() {
+        let mut si = interest(20.0, 20.0);
+        si.config.cell_size = 7.0;
+        let mut rng_state: u64 = 0x1234_5678_9abc_def0;
+        let mut next = || {
+            rng_state ^= rng_state << 13;
+            rng_state ^= rng_state >> 7;
+            rng_state ^= rng_state << 17;
+            (rng_state % 2000) as f32 / 10.0 - 100.0
+        };
+        for u in 0..8 {
+            si.set_viewpoint(&uk(u), Viewpoint::Position(next(), next()));
+        }
+        for _ in 0..5 {
+            let positions: Vec<(GlobalEntity, (f32, f32))> =
+                (0..300).map(|e| (ge(e), (next(), next()))).collect();
+            si.update(positions.clone());
+            for u in 0..8 {
+                let Viewpoint::Position(vx, vy) = si.users[&uk(u)].viewpoint else {
+                    unreachable!()
+                };
+                let mut expected: Vec<(UserKey, GlobalEntity)> = positions
+                    .iter()
+                    .filter(|(_, (x, y))| (x - vx).powi(2) + (y - vy).powi(2) <= 400.0)
+                    .map(|(e, _)| (uk(u), *e))
+                    .collect();
+                expected = sorted(expected);
+                let actual = sorted(
+                    si.users[&uk(u)]
+                        .inside
+                        .iter()
+                        .map(|e| (uk(u), *e))
+                        .collect(),
+                );
+                assert_eq!(actual, expected);
+            }
+        }
+    }
+}
@@ -11,6 +11,7 @@ pub struct TimeManager {
     current_tick: Tick,
     last_tick_game_instant: GameInstant,
     last_tick_instant: Instant,
+    tick_interval: Duration,
     tick_interval_millis: f32,
     tick_duration_avg: f32,
     tick_duration_avg_min: f32,
@@ -32,6 +33,7 @@ impl TimeManager {
             current_tick: 0,
             last_tick_game_instant,
             last_tick_instant,
+            tick_interval,
             tick_interval_millis,
             tick_duration_avg,
             tick_duration_avg_min: tick_duration_avg,
@@ -65,6 +67,11 @@ impl TimeManager {
         self.current_tick
     }
 
+    /// Gets the tick interval configured in the Protocol
+    pub fn tick_interval(&self) -> Duration {
+        self.tick_interval
+    }
+
     pub fn current_tick_instant(&self) -> GameInstant {
         self.last_tick_game_instant
     }
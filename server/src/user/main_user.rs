@@ -1,5 +1,7 @@
 use std::{net::SocketAddr, time::Instant};
 
+use naia_shared::PacketCodec;
+
 use crate::{server::MainServer, UserKey};
 
 // MainUser
@@ -11,6 +13,8 @@ pub struct MainUser {
     data_addr: Option<SocketAddr>,
     /// Tracks when the user was created so a pending-auth timeout can be enforced.
     pub(crate) created_at: Instant,
+    observer: bool,
+    compression_codec: PacketCodec,
 }
 
 impl MainUser {
@@ -20,6 +24,8 @@ impl MainUser {
             auth_addr: Some(auth_addr),
             data_addr: None,
             created_at: Instant::now(),
+            observer: false,
+            compression_codec: PacketCodec::default(),
         }
     }
 
@@ -47,6 +53,24 @@ impl MainUser {
         self.auth_addr
     }
 
+    /// Returns `true` if the user was accepted as an observer.
+    pub fn is_observer(&self) -> bool {
+        self.observer
+    }
+
+    pub(crate) fn set_observer(&mut self) {
+        self.observer = true;
+    }
+
+    /// Returns the server-to-client codec negotiated with the user's client.
+    pub fn compression_codec(&self) -> PacketCodec {
+        self.compression_codec
+    }
+
+    pub(crate) fn set_compression_codec(&mut self, codec: PacketCodec) {
+        self.compression_codec = codec;
+    }
+
     pub(crate) fn take_auth_address(&mut self) -> SocketAddr {
         self.auth_addr.take().unwrap()
     }
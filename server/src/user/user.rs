@@ -1,6 +1,6 @@
 use std::{collections::hash_set::Iter, hash::Hash, net::SocketAddr};
 
-use naia_shared::BigMapKey;
+use naia_shared::{BigMapKey, PacketCodec};
 
 use crate::{server::WorldServer, RoomKey};
 
@@ -53,6 +53,33 @@ impl<'s, E: Copy + Eq + Hash + Send + Sync> UserRef<'s, E> {
         self.server.user_rooms_count(&self.key).unwrap()
     }
 
+    /// Returns `true` if the user was accepted as an observer.
+    pub fn is_observer(&self) -> bool {
+        self.server.user_is_observer(&self.key)
+    }
+
+    /// Returns the codec packets to this user are compressed with, as
+    /// negotiated with their client during the handshake.
+    pub fn compression_codec(&self) -> PacketCodec {
+        self.server
+            .user_compression_codec(&self.key)
+            .unwrap_or_default()
+    }
+
+    /// Returns `true` if packets to this user are compressed with the
+    /// server-to-client dictionary, i.e. their client reported holding the
+    /// same one during the handshake. Otherwise they fall back to plain
+    /// compression at the same level.
+    pub fn uses_compression_dictionary(&self) -> bool {
+        self.compression_codec() == PacketCodec::ZstdDictionary
+    }
+
+    /// Returns the user's outbound bandwidth budget override in bytes per
+    /// second, if [`UserMut::set_bandwidth_override`] set one.
+    pub fn bandwidth_override(&self) -> Option<u32> {
+        self.server.user_bandwidth_override(&self.key)
+    }
+
     /// Returns an iterator over the [`RoomKey`]s of all rooms the user belongs to.
     pub fn room_keys(&self) -> impl Iterator<Item = &RoomKey> {
         self.server.user_room_keys(&self.key).unwrap()
@@ -62,7 +89,8 @@ impl<'s, E: Copy + Eq + Hash + Send + Sync> UserRef<'s, E> {
 /// Scoped mutable handle for a connected user.
 ///
 /// Obtained from [`Server::user_mut`]. Lets you move the user between rooms,
-/// read their network address, and queue a disconnect.
+/// adjust their bandwidth budget, read their network address, and queue a
+/// disconnect.
 pub struct UserMut<'s, E: Copy + Eq + Hash + Send + Sync> {
     server: &'s mut WorldServer<E>,
     key: UserKey,
@@ -91,6 +119,31 @@ impl<'s, E: Copy + Eq + Hash + Send + Sync> UserMut<'s, E> {
         self.server.user_queue_disconnect(&self.key, naia_shared::DisconnectReason::Kicked);
     }
 
+    // Bandwidth
+
+    /// Sets this user's outbound bandwidth budget to `target_bytes_per_sec`,
+    /// replacing `BandwidthConfig::target_bytes_per_sec` (and any congestion
+    /// control) for their connection until cleared. A server-wide
+    /// `ServerConfig::aggregate_bytes_per_sec` still applies on top.
+    pub fn set_bandwidth_override(&mut self, target_bytes_per_sec: u32) -> &mut Self {
+        self.server.user_set_bandwidth_override(&self.key, Some(target_bytes_per_sec));
+
+        self
+    }
+
+    /// Restores the configured outbound bandwidth budget for this user.
+    pub fn clear_bandwidth_override(&mut self) -> &mut Self {
+        self.server.user_set_bandwidth_override(&self.key, None);
+
+        self
+    }
+
+    /// Returns the user's outbound bandwidth budget override in bytes per
+    /// second, if one is set.
+    pub fn bandwidth_override(&self) -> Option<u32> {
+        self.server.user_bandwidth_override(&self.key)
+    }
+
     // Rooms
 
     /// Adds the user to the given room.
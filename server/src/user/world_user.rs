@@ -4,19 +4,22 @@ use crate::RoomKey;
 
 // User
 
-/// World-layer user record: tracks data address and cached room membership.
+/// World-layer user record: tracks data address, cached room membership and
+/// whether the user is an observer.
 #[derive(Clone)]
 pub struct WorldUser {
     data_addr: SocketAddr,
     rooms_cache: HashSet<RoomKey>,
+    observer: bool,
 }
 
 impl WorldUser {
     /// Creates a new `WorldUser` registered at the given data-channel address.
-    pub fn new(address: SocketAddr) -> Self {
+    pub fn new(address: SocketAddr, observer: bool) -> Self {
         Self {
             data_addr: address,
             rooms_cache: HashSet::new(),
+            observer,
         }
     }
 
@@ -25,6 +28,12 @@ impl WorldUser {
         self.data_addr
     }
 
+    /// Returns `true` if the user sees the whole world read-only, regardless
+    /// of rooms and [`UserScopeMut`](crate::UserScopeMut).
+    pub fn is_observer(&self) -> bool {
+        self.observer
+    }
+
     // Rooms
 
     pub(crate) fn cache_room(&mut self, room_key: &RoomKey) {
@@ -0,0 +1,121 @@
+use std::collections::HashMap;
+
+use naia_shared::{
+    ChannelKind, ComponentKind, GlobalEntity, Replicate, ReplicatedComponent, UpdateValidation,
+    UpdateValidator,
+};
+
+use crate::UserKey;
+
+type ValidateFn =
+    dyn Fn(&UserKey, &dyn Replicate, &mut dyn Replicate) -> UpdateValidation + Send + Sync;
+
+struct ComponentValidator {
+    // Channel the server's value is sent back on after a rejection or clamp
+    channel_kind: ChannelKind,
+    validate: Box<ValidateFn>,
+}
+
+/// Validators for component updates sent by clients holding authority,
+/// keyed by component kind.
+pub(crate) struct ComponentValidators {
+    validators: HashMap<ComponentKind, ComponentValidator>,
+}
+
+impl ComponentValidators {
+    pub fn new() -> Self {
+        Self {
+            validators: HashMap::new(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.validators.is_empty()
+    }
+
+    pub fn insert<C: ReplicatedComponent>(
+        &mut self,
+        channel_kind: ChannelKind,
+        validate: impl Fn(&UserKey, &C, &mut C) -> UpdateValidation + Send + Sync + 'static,
+    ) {
+        let validate =
+            move |user_key: &UserKey, current: &dyn Replicate, proposed: &mut dyn Replicate| {
+                let (Some(current), Some(proposed)) = (
+                    current.to_any().downcast_ref::<C>(),
+                    proposed.to_any_mut().downcast_mut::<C>(),
+                ) else {
+                    return UpdateValidation::Accept;
+                };
+                validate(user_key, current, proposed)
+            };
+        self.validators.insert(
+            ComponentKind::of::<C>(),
+            ComponentValidator {
+                channel_kind,
+                validate: Box::new(validate),
+            },
+        );
+    }
+
+    pub fn remove(&mut self, component_kind: &ComponentKind) -> bool {
+        self.validators.remove(component_kind).is_some()
+    }
+
+    pub fn channel_kind(&self, component_kind: &ComponentKind) -> Option<ChannelKind> {
+        self.validators
+            .get(component_kind)
+            .map(|validator| validator.channel_kind)
+    }
+
+    /// Returns the validator for updates received from `user_key`.
+    pub fn for_user(&self, user_key: UserKey) -> UserUpdateValidator<'_> {
+        UserUpdateValidator {
+            validators: self,
+            user_key,
+            corrections: Vec::new(),
+        }
+    }
+}
+
+/// Runs [`ComponentValidators`] over one user's incoming updates, recording
+/// the components whose server value must be sent back to them.
+pub(crate) struct UserUpdateValidator<'a> {
+    validators: &'a ComponentValidators,
+    user_key: UserKey,
+    corrections: Vec<(GlobalEntity, ComponentKind)>,
+}
+
+impl UserUpdateValidator<'_> {
+    pub fn take_corrections(self) -> Vec<(GlobalEntity, ComponentKind)> {
+        self.corrections
+    }
+
+    fn run(&self, current: &dyn Replicate, proposed: &mut dyn Replicate) -> UpdateValidation {
+        let Some(validator) = self.validators.validators.get(&current.kind()) else {
+            return UpdateValidation::Accept;
+        };
+        (validator.validate)(&self.user_key, current, proposed)
+    }
+}
+
+impl UpdateValidator for UserUpdateValidator<'_> {
+    fn validates(&self, component_kind: &ComponentKind) -> bool {
+        self.validators.validators.contains_key(component_kind)
+    }
+
+    fn validate(
+        &mut self,
+        global_entity: &GlobalEntity,
+        current: &dyn Replicate,
+        proposed: &mut dyn Replicate,
+    ) -> UpdateValidation {
+        let validation = self.run(current, proposed);
+        if validation != UpdateValidation::Accept {
+            let correction = (*global_entity, current.kind());
+            if !self.corrections.contains(&correction) {
+                self.corrections.push(correction);
+            }
+        }
+        validation
+    }
+}
@@ -7,8 +7,8 @@ use log::warn;
 
 use naia_shared::{
     AuthorityError, BigMapKey, ComponentKind, ComponentKinds, EntityAuthAccessor, EntityAuthStatus,
-    GlobalDiffHandler, GlobalEntity, GlobalWorldManagerType, InScopeEntities, MutChannelType,
-    PropertyMutator, Replicate,
+    GlobalDiffHandler, GlobalEntity, GlobalWorldManagerType, InScopeEntities, Instant,
+    MutChannelType, MutReceiver, PropertyMutator, Replicate,
 };
 
 use super::global_entity_record::GlobalEntityRecord;
@@ -17,7 +17,7 @@ use crate::{
         mut_channel::MutChannelData,
         server_auth_handler::{AuthOwner, ServerAuthHandler},
     },
-    EntityOwner, Publicity, ReplicationConfig, ScopeExit, UserKey,
+    AuthorityPolicy, EntityOwner, Publicity, ReplicationConfig, ScopeExit, UpdateMode, UserKey,
 };
 
 pub struct GlobalWorldManager {
@@ -201,6 +201,19 @@ impl GlobalWorldManager {
             .deregister_component(global_entity, component_kind);
     }
 
+    /// The per-user receivers tracking unsent changes to a component.
+    pub fn component_receivers(
+        &self,
+        global_entity: &GlobalEntity,
+        component_kind: &ComponentKind,
+    ) -> Vec<MutReceiver> {
+        self.diff_handler
+            .as_ref()
+            .read()
+            .expect("Haven't initialized DiffHandler")
+            .receivers(global_entity, component_kind)
+    }
+
     // Public
 
     pub(crate) fn entity_publish(&mut self, global_entity: &GlobalEntity) -> bool {
@@ -293,6 +306,33 @@ impl GlobalWorldManager {
         }
     }
 
+    pub(crate) fn entity_set_update_mode(
+        &mut self,
+        global_entity: &GlobalEntity,
+        update_mode: UpdateMode,
+    ) {
+        if let Some(record) = self.entity_records.get_mut(global_entity) {
+            record.replication_config.update_mode = update_mode;
+        }
+    }
+
+    pub(crate) fn entity_set_authority_policy(
+        &mut self,
+        global_entity: &GlobalEntity,
+        authority: AuthorityPolicy,
+    ) {
+        if let Some(record) = self.entity_records.get_mut(global_entity) {
+            record.replication_config.authority = authority;
+        }
+    }
+
+    pub(crate) fn entity_authority_policy(&self, global_entity: &GlobalEntity) -> AuthorityPolicy {
+        self.entity_records
+            .get(global_entity)
+            .map(|record| record.replication_config.authority)
+            .unwrap_or_default()
+    }
+
     pub(crate) fn entity_is_delegated(&self, global_entity: &GlobalEntity) -> bool {
         if let Some(record) = self.entity_records.get(global_entity) {
             return record.replication_config.publicity == Publicity::Delegated;
@@ -365,6 +405,21 @@ impl GlobalWorldManager {
             .client_request_authority(global_entity, requester)
     }
 
+    pub(crate) fn client_request_authority_group(
+        &mut self,
+        global_entities: &[GlobalEntity],
+        requester: &UserKey,
+    ) -> Result<(), AuthorityError> {
+        if !global_entities
+            .iter()
+            .all(|global_entity| self.entity_is_delegated(global_entity))
+        {
+            return Err(AuthorityError::NotDelegated);
+        }
+        self.auth_handler
+            .client_request_authority_group(global_entities, requester)
+    }
+
     /// Server-priority give_authority — sovereign assignment that
     /// overrides any current holder. See
     /// `ServerAuthHandler::server_give_authority_to_client` for the
@@ -393,6 +448,11 @@ impl GlobalWorldManager {
             .client_release_authority(global_entity, releaser)
     }
 
+    /// Every delegated entity with its current authority owner
+    pub(crate) fn entity_auth_owners(&self) -> impl Iterator<Item = (&GlobalEntity, &AuthOwner)> {
+        self.auth_handler.auth_owners()
+    }
+
     pub(crate) fn user_all_owned_entities(
         &self,
         user_key: &UserKey,
@@ -418,6 +478,62 @@ impl GlobalWorldManager {
         self.auth_handler.entity_has_holder(global_entity)
     }
 
+    pub(crate) fn enqueue_authority_request(
+        &mut self,
+        global_entity: &GlobalEntity,
+        user_key: &UserKey,
+    ) -> bool {
+        self.auth_handler.enqueue_request(global_entity, user_key)
+    }
+
+    pub(crate) fn dequeue_authority_request(
+        &mut self,
+        global_entity: &GlobalEntity,
+        user_key: &UserKey,
+    ) -> bool {
+        self.auth_handler.dequeue_request(global_entity, user_key)
+    }
+
+    pub(crate) fn pop_authority_request(
+        &mut self,
+        global_entity: &GlobalEntity,
+    ) -> Option<UserKey> {
+        self.auth_handler.pop_request(global_entity)
+    }
+
+    pub(crate) fn clear_authority_requests(
+        &mut self,
+        global_entity: &GlobalEntity,
+    ) -> Vec<UserKey> {
+        self.auth_handler.clear_requests(global_entity)
+    }
+
+    pub(crate) fn remove_user_authority_requests(&mut self, user_key: &UserKey) {
+        self.auth_handler.remove_user_requests(user_key);
+    }
+
+    /// Starts the entity's authority lease if its policy has one
+    pub(crate) fn start_authority_lease(&mut self, global_entity: &GlobalEntity, now: &Instant) {
+        if let Some(lease) = self.entity_authority_policy(global_entity).lease {
+            self.auth_handler.start_lease(global_entity, lease, now);
+        }
+    }
+
+    pub(crate) fn renew_authority_lease(&mut self, global_entity: &GlobalEntity, now: &Instant) {
+        self.auth_handler.renew_lease(global_entity, now);
+    }
+
+    pub(crate) fn expired_authority_leases(&self, now: &Instant) -> Vec<GlobalEntity> {
+        self.auth_handler.expired_leases(now)
+    }
+
+    pub(crate) fn entity_client_authority_holder(
+        &self,
+        global_entity: &GlobalEntity,
+    ) -> Option<UserKey> {
+        self.auth_handler.client_holder(global_entity)
+    }
+
     pub(crate) fn pause_entity_replication(&mut self, global_entity: &GlobalEntity) {
         let Some(record) = self.entity_records.get_mut(global_entity) else {
             warn!("pause_entity_replication: entity record does not exist; entity may have been despawned");
@@ -508,6 +624,13 @@ impl GlobalWorldManagerType for GlobalWorldManager {
             .map(|r| r.is_static)
             .unwrap_or(false)
     }
+
+    fn entity_uses_baseline_delta(&self, global_entity: &GlobalEntity) -> bool {
+        self.entity_records
+            .get(global_entity)
+            .map(|r| r.replication_config.update_mode == UpdateMode::BaselineDelta)
+            .unwrap_or(false)
+    }
 }
 
 impl InScopeEntities<GlobalEntity> for GlobalWorldManager {
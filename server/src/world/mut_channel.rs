@@ -48,4 +48,8 @@ impl MutChannelType for MutChannelData {
             receiver.mutate(property_index);
         }
     }
+
+    fn receivers(&self) -> Vec<MutReceiver> {
+        self.receivers.clone()
+    }
 }
@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 pub use naia_shared::Publicity;
 
 /// What happens to a client's view of an entity when it leaves their scope.
@@ -24,20 +26,73 @@ pub enum ScopeExit {
     Persist,
 }
 
+/// How component updates for an entity are delivered to clients.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub enum UpdateMode {
+    /// Changed fields are sent once and retransmitted only if the packet
+    /// carrying them is detected as lost.
+    ///
+    /// This is the default and is the most bandwidth-efficient choice for
+    /// entities that change occasionally.
+    #[default]
+    Retransmit,
+    /// Every update carries all fields changed since the last update the
+    /// client acknowledged (its baseline), so a lost packet is healed by the
+    /// next one instead of by a retransmission of stale values.
+    ///
+    /// Use `BaselineDelta` for high-churn entities (e.g. fast-moving
+    /// avatars) whose fields change nearly every tick.
+    BaselineDelta,
+}
+
+/// How authority over a [`Delegated`](Publicity::Delegated) entity is held
+/// and handed between clients.
+///
+/// The default holds authority until the client releases it or the server
+/// takes it, and denies requests made while the entity is held.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub struct AuthorityPolicy {
+    /// If set, a client holding authority loses it after this long without
+    /// sending an update to any of the entity's components. Each update
+    /// renews the lease.
+    pub lease: Option<Duration>,
+    /// If true, requests made while the entity is held wait in a
+    /// first-in-first-out queue instead of being denied, and authority is
+    /// handed to the front of the queue whenever the holder loses it.
+    pub queue_requests: bool,
+}
+
+impl AuthorityPolicy {
+    /// Creates the default policy: no lease and no request queue.
+    pub const fn new() -> Self {
+        Self {
+            lease: None,
+            queue_requests: false,
+        }
+    }
+}
+
 /// Replication configuration for a server entity.
 ///
-/// Two orthogonal axes govern how the entity behaves on connected clients:
+/// Four orthogonal axes govern how the entity behaves on connected clients:
 ///
 /// - [`publicity`](ReplicationConfig::publicity) — controls *who* can see or
 ///   mutate the entity. See [`Publicity`] for the full state machine.
 /// - [`scope_exit`](ReplicationConfig::scope_exit) — controls what happens on
 ///   a client when the entity leaves that user's scope. See [`ScopeExit`].
+/// - [`update_mode`](ReplicationConfig::update_mode) — controls how component
+///   updates survive packet loss. See [`UpdateMode`].
+/// - [`authority`](ReplicationConfig::authority) — controls how delegated
+///   authority expires and is handed over. See [`AuthorityPolicy`].
 ///
 /// Use the const constructors [`public`](ReplicationConfig::public),
 /// [`private`](ReplicationConfig::private), and
 /// [`delegated`](ReplicationConfig::delegated) as starting points, then chain
-/// [`persist_on_scope_exit`](ReplicationConfig::persist_on_scope_exit) to
-/// override the scope-exit behaviour.
+/// [`persist_on_scope_exit`](ReplicationConfig::persist_on_scope_exit),
+/// [`baseline_delta`](ReplicationConfig::baseline_delta),
+/// [`authority_lease`](ReplicationConfig::authority_lease) or
+/// [`queue_authority_requests`](ReplicationConfig::queue_authority_requests)
+/// to override the remaining axes.
 ///
 /// # Examples
 ///
@@ -48,6 +103,14 @@ pub enum ScopeExit {
 ///
 /// // Delegated entity with default (despawn) scope-exit:
 /// let cfg = ReplicationConfig::delegated();
+///
+/// // Fast-moving public entity delta-encoded against the client's baseline:
+/// let cfg = ReplicationConfig::public().baseline_delta();
+///
+/// // Delegated entity whose authority lapses after 5s idle, with requests queued:
+/// let cfg = ReplicationConfig::delegated()
+///     .authority_lease(std::time::Duration::from_secs(5))
+///     .queue_authority_requests();
 /// ```
 #[derive(Copy, Clone, PartialEq, Eq, Debug)]
 pub struct ReplicationConfig {
@@ -55,6 +118,10 @@ pub struct ReplicationConfig {
     pub publicity: Publicity,
     /// Behaviour when the entity leaves a user's scope.
     pub scope_exit: ScopeExit,
+    /// How component updates are delivered under packet loss.
+    pub update_mode: UpdateMode,
+    /// How delegated authority expires and is handed over.
+    pub authority: AuthorityPolicy,
 }
 
 impl ReplicationConfig {
@@ -66,6 +133,8 @@ impl ReplicationConfig {
         Self {
             publicity: Publicity::Public,
             scope_exit: ScopeExit::Despawn,
+            update_mode: UpdateMode::Retransmit,
+            authority: AuthorityPolicy::new(),
         }
     }
 
@@ -78,6 +147,8 @@ impl ReplicationConfig {
         Self {
             publicity: Publicity::Private,
             scope_exit: ScopeExit::Despawn,
+            update_mode: UpdateMode::Retransmit,
+            authority: AuthorityPolicy::new(),
         }
     }
 
@@ -93,6 +164,8 @@ impl ReplicationConfig {
         Self {
             publicity: Publicity::Delegated,
             scope_exit: ScopeExit::Despawn,
+            update_mode: UpdateMode::Retransmit,
+            authority: AuthorityPolicy::new(),
         }
     }
 
@@ -108,4 +181,41 @@ impl ReplicationConfig {
             ..self
         }
     }
+
+    /// Returns a copy of this config with [`update_mode`](UpdateMode) set to
+    /// [`BaselineDelta`](UpdateMode::BaselineDelta), leaving the other axes
+    /// unchanged.
+    ///
+    /// Each update then carries every field changed since the client's last
+    /// acknowledged update, so lost packets are never retransmitted.
+    pub const fn baseline_delta(self) -> Self {
+        Self {
+            update_mode: UpdateMode::BaselineDelta,
+            ..self
+        }
+    }
+
+    /// Returns a copy of this config whose delegated authority lapses after
+    /// `lease` without an update from the holding client.
+    pub const fn authority_lease(self, lease: Duration) -> Self {
+        Self {
+            authority: AuthorityPolicy {
+                lease: Some(lease),
+                ..self.authority
+            },
+            ..self
+        }
+    }
+
+    /// Returns a copy of this config that queues authority requests made
+    /// while the entity is held, granting them in the order they arrived.
+    pub const fn queue_authority_requests(self) -> Self {
+        Self {
+            authority: AuthorityPolicy {
+                queue_requests: true,
+                ..self.authority
+            },
+            ..self
+        }
+    }
 }
@@ -1,10 +1,12 @@
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{HashMap, HashSet, VecDeque},
     hash::Hash,
+    time::Duration,
 };
 
 use naia_shared::{
     AuthorityError, EntityAuthAccessor, EntityAuthStatus, GlobalEntity, HostAuthHandler, HostType,
+    Instant,
 };
 
 use crate::UserKey;
@@ -29,6 +31,10 @@ pub struct ServerAuthHandler {
     host_auth_handler: HostAuthHandler,
     entity_auth_map: HashMap<GlobalEntity, AuthOwner>,
     user_to_entity_map: HashMap<UserKey, HashSet<GlobalEntity>>,
+    // Users waiting for authority over an entity, in request order
+    request_queues: HashMap<GlobalEntity, VecDeque<UserKey>>,
+    // Lease length and last activity of each leased client holder
+    leases: HashMap<GlobalEntity, (Duration, Instant)>,
 }
 
 impl ServerAuthHandler {
@@ -37,6 +43,8 @@ impl ServerAuthHandler {
             host_auth_handler: HostAuthHandler::new(),
             entity_auth_map: HashMap::new(),
             user_to_entity_map: HashMap::new(),
+            request_queues: HashMap::new(),
+            leases: HashMap::new(),
         }
     }
 
@@ -53,6 +61,8 @@ impl ServerAuthHandler {
     pub fn deregister_entity(&mut self, entity: &GlobalEntity) {
         self.host_auth_handler.deregister_entity(entity);
         self.entity_auth_map.remove(entity);
+        self.request_queues.remove(entity);
+        self.leases.remove(entity);
     }
 
     pub(crate) fn authority_status(&self, entity: &GlobalEntity) -> Option<EntityAuthStatus> {
@@ -106,6 +116,27 @@ impl ServerAuthHandler {
         }
     }
 
+    /// Grants `requester` authority over every entity in `entities`, or over
+    /// none of them if any is not delegated or already held.
+    pub(crate) fn client_request_authority_group(
+        &mut self,
+        entities: &[GlobalEntity],
+        requester: &UserKey,
+    ) -> Result<(), AuthorityError> {
+        for entity in entities {
+            match self.entity_auth_map.get(entity) {
+                None => return Err(AuthorityError::NotDelegated),
+                Some(AuthOwner::None) => {}
+                Some(_) => return Err(AuthorityError::NotAvailable),
+            }
+        }
+        let requester = AuthOwner::Client(*requester);
+        for entity in entities {
+            self.client_request_authority(entity, &requester)?;
+        }
+        Ok(())
+    }
+
     pub(crate) fn client_release_authority(
         &mut self,
         entity: &GlobalEntity,
@@ -203,6 +234,8 @@ impl ServerAuthHandler {
             return false;
         }
 
+        self.leases.remove(entity);
+
         if let AuthOwner::Client(user_key) = owner {
             let mut remove_user = false;
             if let Some(entities) = self.user_to_entity_map.get_mut(&user_key) {
@@ -220,6 +253,98 @@ impl ServerAuthHandler {
         true
     }
 
+    /// Adds `user_key` to the back of the entity's request queue. Returns
+    /// false if they are already queued or already hold authority.
+    pub(crate) fn enqueue_request(&mut self, entity: &GlobalEntity, user_key: &UserKey) -> bool {
+        if self.user_is_authority_holder(user_key, entity) {
+            return false;
+        }
+        let queue = self.request_queues.entry(*entity).or_default();
+        if queue.contains(user_key) {
+            return false;
+        }
+        queue.push_back(*user_key);
+        true
+    }
+
+    /// Removes `user_key` from the entity's request queue. Returns whether
+    /// they were queued.
+    pub(crate) fn dequeue_request(&mut self, entity: &GlobalEntity, user_key: &UserKey) -> bool {
+        let Some(queue) = self.request_queues.get_mut(entity) else {
+            return false;
+        };
+        let Some(index) = queue.iter().position(|queued| queued == user_key) else {
+            return false;
+        };
+        queue.remove(index);
+        if queue.is_empty() {
+            self.request_queues.remove(entity);
+        }
+        true
+    }
+
+    /// Removes and returns the user at the front of the entity's request queue.
+    pub(crate) fn pop_request(&mut self, entity: &GlobalEntity) -> Option<UserKey> {
+        let queue = self.request_queues.get_mut(entity)?;
+        let user_key = queue.pop_front();
+        if queue.is_empty() {
+            self.request_queues.remove(entity);
+        }
+        user_key
+    }
+
+    /// Empties the entity's request queue, returning who was waiting.
+    pub(crate) fn clear_requests(&mut self, entity: &GlobalEntity) -> Vec<UserKey> {
+        self.request_queues
+            .remove(entity)
+            .map(Vec::from)
+            .unwrap_or_default()
+    }
+
+    /// Removes `user_key` from every request queue.
+    pub(crate) fn remove_user_requests(&mut self, user_key: &UserKey) {
+        self.request_queues.retain(|_, queue| {
+            queue.retain(|queued| queued != user_key);
+            !queue.is_empty()
+        });
+    }
+
+    /// Starts a lease on the current holder's authority, ending after
+    /// `lease` without a renewal.
+    pub(crate) fn start_lease(&mut self, entity: &GlobalEntity, lease: Duration, now: &Instant) {
+        self.leases.insert(*entity, (lease, now.clone()));
+    }
+
+    /// Restarts the lease on the entity, if it has one.
+    pub(crate) fn renew_lease(&mut self, entity: &GlobalEntity, now: &Instant) {
+        if let Some((_, last_activity)) = self.leases.get_mut(entity) {
+            *last_activity = now.clone();
+        }
+    }
+
+    /// Returns each leased entity whose holder has gone a full lease without
+    /// renewing it.
+    pub(crate) fn expired_leases(&self, now: &Instant) -> Vec<GlobalEntity> {
+        self.leases
+            .iter()
+            .filter(|(_, (lease, last_activity))| last_activity.elapsed(now) >= *lease)
+            .map(|(entity, _)| *entity)
+            .collect()
+    }
+
+    /// Returns the client currently holding authority over the entity.
+    pub(crate) fn client_holder(&self, entity: &GlobalEntity) -> Option<UserKey> {
+        match self.entity_auth_map.get(entity) {
+            Some(AuthOwner::Client(user_key)) => Some(*user_key),
+            _ => None,
+        }
+    }
+
+    /// Every delegated entity with its current authority owner
+    pub(crate) fn auth_owners(&self) -> impl Iterator<Item = (&GlobalEntity, &AuthOwner)> {
+        self.entity_auth_map.iter()
+    }
+
     pub(crate) fn user_all_owned_entities(
         &self,
         user_key: &UserKey,
@@ -242,3 +367,89 @@ impl ServerAuthHandler {
             .unwrap_or(false)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use naia_shared::BigMapKey;
+
+    use super::*;
+
+    fn uk(n: u64) -> UserKey {
+        UserKey::from_u64(n)
+    }
+
+    fn ge(n: u64) -> GlobalEntity {
+        GlobalEntity::from_u64(n)
+    }
+
+    #[test]
+    fn requests_are_queued_in_order_once() {
+        let mut handler = ServerAuthHandler::new();
+        let entity = ge(1);
+        handler.register_entity(&entity);
+        handler
+            .client_request_authority(&entity, &AuthOwner::Client(uk(1)))
+            .unwrap();
+
+        assert!(!handler.enqueue_request(&entity, &uk(1)));
+        assert!(handler.enqueue_request(&entity, &uk(2)));
+        assert!(handler.enqueue_request(&entity, &uk(3)));
+        assert!(!handler.enqueue_request(&entity, &uk(2)));
+
+        assert!(handler.dequeue_request(&entity, &uk(2)));
+        assert_eq!(handler.pop_request(&entity), Some(uk(3)));
+        assert_eq!(handler.pop_request(&entity), None);
+    }
+
+    #[test]
+    fn group_request_is_all_or_nothing() {
+        let mut handler = ServerAuthHandler::new();
+        let (first, second) = (ge(1), ge(2));
+        handler.register_entity(&first);
+        handler.register_entity(&second);
+        handler
+            .client_request_authority(&second, &AuthOwner::Client(uk(1)))
+            .unwrap();
+
+        assert_eq!(
+            handler.client_request_authority_group(&[first, second], &uk(2)),
+            Err(AuthorityError::NotAvailable)
+        );
+        assert!(!handler.entity_has_holder(&first));
+
+        handler
+            .client_release_authority(&second, &AuthOwner::Client(uk(1)))
+            .unwrap();
+        handler
+            .client_request_authority_group(&[first, second], &uk(2))
+            .unwrap();
+        assert!(handler.user_is_authority_holder(&uk(2), &first));
+        assert!(handler.user_is_authority_holder(&uk(2), &second));
+    }
+
+    #[test]
+    fn lease_expires_unless_renewed_and_ends_on_release() {
+        let mut handler = ServerAuthHandler::new();
+        let entity = ge(1);
+        let holder = AuthOwner::Client(uk(1));
+        handler.register_entity(&entity);
+        handler.client_request_authority(&entity, &holder).unwrap();
+
+        let start = Instant::now();
+        handler.start_lease(&entity, Duration::from_millis(100), &start);
+
+        let mut later = start.clone();
+        later.add_millis(60);
+        assert!(handler.expired_leases(&later).is_empty());
+        handler.renew_lease(&entity, &later);
+
+        let mut much_later = start.clone();
+        much_later.add_millis(120);
+        assert!(handler.expired_leases(&much_later).is_empty());
+        much_later.add_millis(60);
+        assert_eq!(handler.expired_leases(&much_later), vec![entity]);
+
+        handler.client_release_authority(&entity, &holder).unwrap();
+        assert!(handler.expired_leases(&much_later).is_empty());
+    }
+}
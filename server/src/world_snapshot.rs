@@ -0,0 +1,575 @@
+use std::{
+    collections::{HashMap, HashSet},
+    error::Error,
+    fmt,
+    time::Duration,
+};
+
+use naia_shared::{
+    BitReader, BitWrite, ComponentKind, ComponentKinds, EntityDoesNotExistError, GlobalEntity,
+    HostEntity, LocalEntityAndGlobalEntityConverter, LocalEntityAndGlobalEntityConverterMut,
+    OwnedLocalEntity, ProtocolId, RemoteEntity, Serde, SerdeErr, UnsignedInteger,
+    UnsignedVariableInteger,
+};
+
+use crate::{AuthorityPolicy, Publicity, ReplicationConfig, RoomKey, ScopeExit, UpdateMode};
+
+const MAGIC: [u8; 4] = *b"NWSN";
+const FORMAT_VERSION: u8 = 1;
+
+/// Errors returned by [`Server::save_world`] and [`Server::restore_world`].
+///
+/// [`Server::save_world`]: crate::Server::save_world
+/// [`Server::restore_world`]: crate::Server::restore_world
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WorldSnapshotError {
+    /// The bytes don't start with a naia world snapshot header.
+    NotASnapshot,
+    /// The snapshot was written with a format version this build can't read.
+    UnsupportedVersion(u8),
+    /// The snapshot was taken by a server running a different protocol.
+    ProtocolMismatch {
+        /// This server's protocol ID.
+        expected: ProtocolId,
+        /// The protocol ID recorded in the snapshot.
+        found: ProtocolId,
+    },
+    /// The snapshot names a component this server's protocol doesn't register.
+    UnknownComponent(String),
+    /// A resource in the snapshot is already inserted on this server.
+    ResourceAlreadyExists(String),
+    /// Component relations point at more distinct entities than a snapshot
+    /// can address (65536).
+    TooManyRelationTargets,
+    /// The snapshot is truncated or otherwise corrupt.
+    Malformed,
+}
+
+impl fmt::Display for WorldSnapshotError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            WorldSnapshotError::NotASnapshot => write!(f, "not a naia world snapshot"),
+            WorldSnapshotError::UnsupportedVersion(version) => {
+                write!(f, "unsupported world snapshot version: {}", version)
+            }
+            WorldSnapshotError::ProtocolMismatch { expected, found } => write!(
+                f,
+                "world snapshot protocol mismatch: expected {}, found {}",
+                expected, found
+            ),
+            WorldSnapshotError::UnknownComponent(name) => {
+                write!(f, "world snapshot contains unknown component: {}", name)
+            }
+            WorldSnapshotError::ResourceAlreadyExists(name) => {
+                write!(f, "world snapshot resource already exists: {}", name)
+            }
+            WorldSnapshotError::TooManyRelationTargets => {
+                write!(f, "too many entities referenced by component relations")
+            }
+            WorldSnapshotError::Malformed => write!(f, "malformed world snapshot"),
+        }
+    }
+}
+
+impl Error for WorldSnapshotError {}
+
+impl From<SerdeErr> for WorldSnapshotError {
+    fn from(_: SerdeErr) -> Self {
+        WorldSnapshotError::Malformed
+    }
+}
+
+/// The result of a successful [`Server::restore_world`].
+///
+/// [`Server::restore_world`]: crate::Server::restore_world
+#[derive(Debug)]
+pub struct RestoredWorld<E> {
+    /// The spawned entities, in the order they were saved.
+    pub entities: Vec<E>,
+    /// Maps each room key recorded in the snapshot to the room created for it.
+    pub rooms: HashMap<RoomKey, RoomKey>,
+}
+
+/// Per-entity header: everything about an entity except its components.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct EntityRecord {
+    pub(crate) is_static: bool,
+    /// The resource kind, if this is the hidden entity carrying a resource
+    pub(crate) resource: Option<ComponentKind>,
+    pub(crate) config: ReplicationConfig,
+}
+
+impl EntityRecord {
+    pub(crate) fn ser(&self, component_kinds: &ComponentKinds, writer: &mut dyn BitWrite) {
+        self.is_static.ser(writer);
+        self.resource.is_some().ser(writer);
+        if let Some(kind) = &self.resource {
+            kind.ser(component_kinds, writer);
+        }
+        write_config(&self.config, writer);
+    }
+
+    pub(crate) fn de(
+        component_kinds: &ComponentKinds,
+        reader: &mut BitReader,
+    ) -> Result<Self, WorldSnapshotError> {
+        let is_static = bool::de(reader)?;
+        let resource = if bool::de(reader)? {
+            Some(ComponentKind::de(component_kinds, reader)?)
+        } else {
+            None
+        };
+        let config = read_config(reader)?;
+        Ok(Self {
+            is_static,
+            resource,
+            config,
+        })
+    }
+}
+
+/// Reads the `count` entity records of a snapshot. A resource kind may only
+/// appear once, since a server holds at most one of each resource.
+pub(crate) fn read_records(
+    reader: &mut BitReader,
+    component_kinds: &ComponentKinds,
+    count: usize,
+) -> Result<Vec<EntityRecord>, WorldSnapshotError> {
+    let mut records = Vec::new();
+    let mut resource_kinds = HashSet::new();
+    for _ in 0..count {
+        let record = EntityRecord::de(component_kinds, reader)?;
+        if let Some(kind) = record.resource {
+            if !resource_kinds.insert(kind) {
+                return Err(WorldSnapshotError::Malformed);
+            }
+        }
+        records.push(record);
+    }
+    Ok(records)
+}
+
+/// Writes the magic bytes, format version, protocol ID and the names of the
+/// component kinds the snapshot contains.
+pub(crate) fn write_header(
+    writer: &mut dyn BitWrite,
+    protocol_id: ProtocolId,
+    component_names: &[String],
+) {
+    for byte in MAGIC {
+        byte.ser(writer);
+    }
+    FORMAT_VERSION.ser(writer);
+    protocol_id.ser(writer);
+    write_count(component_names.len(), writer);
+    for name in component_names {
+        name.ser(writer);
+    }
+}
+
+/// Reads and checks a header written by [`write_header`] against this
+/// server's protocol.
+pub(crate) fn read_header(
+    reader: &mut BitReader,
+    protocol_id: ProtocolId,
+    component_kinds: &ComponentKinds,
+) -> Result<(), WorldSnapshotError> {
+    for expected in MAGIC {
+        let byte = u8::de(reader).map_err(|_| WorldSnapshotError::NotASnapshot)?;
+        if byte != expected {
+            return Err(WorldSnapshotError::NotASnapshot);
+        }
+    }
+    let version = u8::de(reader)?;
+    if version != FORMAT_VERSION {
+        return Err(WorldSnapshotError::UnsupportedVersion(version));
+    }
+    let found = ProtocolId::de(reader)?;
+    if found != protocol_id {
+        return Err(WorldSnapshotError::ProtocolMismatch {
+            expected: protocol_id,
+            found,
+        });
+    }
+    let known_names = component_kinds.all_names();
+    for _ in 0..read_count(reader)? {
+        let name = String::de(reader)?;
+        if !known_names.contains(&name) {
+            return Err(WorldSnapshotError::UnknownComponent(name));
+        }
+    }
+    Ok(())
+}
+
+pub(crate) fn write_count(count: usize, writer: &mut dyn BitWrite) {
+    UnsignedVariableInteger::<7>::new(count as u64).ser(writer);
+}
+
+pub(crate) fn read_count(reader: &mut BitReader) -> Result<usize, SerdeErr> {
+    Ok(UnsignedVariableInteger::<7>::de(reader)?.get() as usize)
+}
+
+/// Writes a list of indices into the snapshot's entity list.
+pub(crate) fn write_entity_indices(indices: &[usize], writer: &mut dyn BitWrite) {
+    write_count(indices.len(), writer);
+    for index in indices {
+        write_count(*index, writer);
+    }
+}
+
+/// Reads a list written by [`write_entity_indices`], rejecting any index that
+/// doesn't refer to one of the snapshot's `entity_count` entities.
+pub(crate) fn read_entity_indices(
+    reader: &mut BitReader,
+    entity_count: usize,
+) -> Result<Vec<usize>, WorldSnapshotError> {
+    let count = read_count(reader)?;
+    let mut indices = Vec::new();
+    for _ in 0..count {
+        let index = read_count(reader)?;
+        if index >= entity_count {
+            return Err(WorldSnapshotError::Malformed);
+        }
+        indices.push(index);
+    }
+    Ok(indices)
+}
+
+fn write_config(config: &ReplicationConfig, writer: &mut dyn BitWrite) {
+    let publicity: u8 = match config.publicity {
+        Publicity::Private => 0,
+        Publicity::Public => 1,
+        Publicity::Delegated => 2,
+    };
+    UnsignedInteger::<2>::new(publicity).ser(writer);
+    (config.scope_exit == ScopeExit::Persist).ser(writer);
+    (config.update_mode == UpdateMode::BaselineDelta).ser(writer);
+    config.authority.lease.is_some().ser(writer);
+    if let Some(lease) = config.authority.lease {
+        UnsignedVariableInteger::<7>::new(lease.as_millis() as u64).ser(writer);
+    }
+    config.authority.queue_requests.ser(writer);
+}
+
+fn read_config(reader: &mut BitReader) -> Result<ReplicationConfig, WorldSnapshotError> {
+    let publicity = match UnsignedInteger::<2>::de(reader)?.get() {
+        0 => Publicity::Private,
+        1 => Publicity::Public,
+        2 => Publicity::Delegated,
+        _ => return Err(WorldSnapshotError::Malformed),
+    };
+    let scope_exit = if bool::de(reader)? {
+        ScopeExit::Persist
+    } else {
+        ScopeExit::Despawn
+    };
+    let update_mode = if bool::de(reader)? {
+        UpdateMode::BaselineDelta
+    } else {
+        UpdateMode::Retransmit
+    };
+    let lease = if bool::de(reader)? {
+        let millis = UnsignedVariableInteger::<7>::de(reader)?.get() as u64;
+        Some(Duration::from_millis(millis))
+    } else {
+        None
+    };
+    let queue_requests = bool::de(reader)?;
+    Ok(ReplicationConfig {
+        publicity,
+        scope_exit,
+        update_mode,
+        authority: AuthorityPolicy {
+            lease,
+            queue_requests,
+        },
+    })
+}
+
+/// Converter used while writing components to a snapshot.
+///
+/// Each saved entity that a component relation points at is given a snapshot
+/// id on first use; relations to entities outside the snapshot (client-owned
+/// entities, say) are written empty.
+pub(crate) struct SnapshotWriteConverter<'a> {
+    entity_indices: &'a HashMap<GlobalEntity, usize>,
+    relation_ids: HashMap<GlobalEntity, u16>,
+    relation_targets: Vec<usize>,
+    overflowed: bool,
+}
+
+impl<'a> SnapshotWriteConverter<'a> {
+    pub(crate) fn new(entity_indices: &'a HashMap<GlobalEntity, usize>) -> Self {
+        Self {
+            entity_indices,
+            relation_ids: HashMap::new(),
+            relation_targets: Vec::new(),
+            overflowed: false,
+        }
+    }
+
+    /// Returns the entity index behind each snapshot id, in id order.
+    pub(crate) fn into_relation_targets(self) -> Result<Vec<usize>, WorldSnapshotError> {
+        if self.overflowed {
+            return Err(WorldSnapshotError::TooManyRelationTargets);
+        }
+        Ok(self.relation_targets)
+    }
+}
+
+impl LocalEntityAndGlobalEntityConverter for SnapshotWriteConverter<'_> {
+    fn global_entity_to_host_entity(
+        &self,
+        global_entity: &GlobalEntity,
+    ) -> Result<HostEntity, EntityDoesNotExistError> {
+        self.relation_ids
+            .get(global_entity)
+            .map(|id| HostEntity::new(*id))
+            .ok_or(EntityDoesNotExistError)
+    }
+
+    fn global_entity_to_remote_entity(
+        &self,
+        _: &GlobalEntity,
+    ) -> Result<RemoteEntity, EntityDoesNotExistError> {
+        Err(EntityDoesNotExistError)
+    }
+
+    fn global_entity_to_owned_entity(
+        &self,
+        global_entity: &GlobalEntity,
+    ) -> Result<OwnedLocalEntity, EntityDoesNotExistError> {
+        self.global_entity_to_host_entity(global_entity)
+            .map(|host_entity| host_entity.copy_to_owned())
+    }
+
+    fn host_entity_to_global_entity(
+        &self,
+        _: &HostEntity,
+    ) -> Result<GlobalEntity, EntityDoesNotExistError> {
+        Err(EntityDoesNotExistError)
+    }
+
+    fn static_host_entity_to_global_entity(
+        &self,
+        _: &HostEntity,
+    ) -> Result<GlobalEntity, EntityDoesNotExistError> {
+        Err(EntityDoesNotExistError)
+    }
+
+    fn remote_entity_to_global_entity(
+        &self,
+        _: &RemoteEntity,
+    ) -> Result<GlobalEntity, EntityDoesNotExistError> {
+        Err(EntityDoesNotExistError)
+    }
+
+    fn apply_entity_redirect(&self, entity: &OwnedLocalEntity) -> OwnedLocalEntity {
+        *entity
+    }
+}
+
+impl LocalEntityAndGlobalEntityConverterMut for SnapshotWriteConverter<'_> {
+    fn get_or_reserve_entity(
+        &mut self,
+        global_entity: &GlobalEntity,
+    ) -> Result<OwnedLocalEntity, EntityDoesNotExistError> {
+        if let Some(id) = self.relation_ids.get(global_entity) {
+            return Ok(HostEntity::new(*id).copy_to_owned());
+        }
+        let index = *self
+            .entity_indices
+            .get(global_entity)
+            .ok_or(EntityDoesNotExistError)?;
+        let Ok(id) = u16::try_from(self.relation_targets.len()) else {
+            self.overflowed = true;
+            return Err(EntityDoesNotExistError);
+        };
+        self.relation_ids.insert(*global_entity, id);
+        self.relation_targets.push(index);
+        Ok(HostEntity::new(id).copy_to_owned())
+    }
+}
+
+/// Converter used while reading components from a snapshot: resolves each
+/// snapshot id written by [`SnapshotWriteConverter`] to the entity spawned
+/// for it.
+pub(crate) struct SnapshotReadConverter {
+    relation_targets: Vec<GlobalEntity>,
+}
+
+impl SnapshotReadConverter {
+    pub(crate) fn new(relation_targets: Vec<GlobalEntity>) -> Self {
+        Self { relation_targets }
+    }
+}
+
+impl LocalEntityAndGlobalEntityConverter for SnapshotReadConverter {
+    fn global_entity_to_host_entity(
+        &self,
+        _: &GlobalEntity,
+    ) -> Result<HostEntity, EntityDoesNotExistError> {
+        Err(EntityDoesNotExistError)
+    }
+
+    fn global_entity_to_remote_entity(
+        &self,
+        _: &GlobalEntity,
+    ) -> Result<RemoteEntity, EntityDoesNotExistError> {
+        Err(EntityDoesNotExistError)
+    }
+
+    fn global_entity_to_owned_entity(
+        &self,
+        _: &GlobalEntity,
+    ) -> Result<OwnedLocalEntity, EntityDoesNotExistError> {
+        Err(EntityDoesNotExistError)
+    }
+
+    fn host_entity_to_global_entity(
+        &self,
+        _: &HostEntity,
+    ) -> Result<GlobalEntity, EntityDoesNotExistError> {
+        Err(EntityDoesNotExistError)
+    }
+
+    fn static_host_entity_to_global_entity(
+        &self,
+        _: &HostEntity,
+    ) -> Result<GlobalEntity, EntityDoesNotExistError> {
+        Err(EntityDoesNotExistError)
+    }
+
+    fn remote_entity_to_global_entity(
+        &self,
+        remote_entity: &RemoteEntity,
+    ) -> Result<GlobalEntity, EntityDoesNotExistError> {
+        if remote_entity.is_static() {
+            return Err(EntityDoesNotExistError);
+        }
+        self.relation_targets
+            .get(remote_entity.value() as usize)
+            .copied()
+            .ok_or(EntityDoesNotExistError)
+    }
+
+    fn apply_entity_redirect(&self, entity: &OwnedLocalEntity) -> OwnedLocalEntity {
+        *entity
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use naia_shared::{BigMapKey, FileBitWriter, Property, Replicate};
+
+    use super::*;
+
+    #[derive(Replicate)]
+    pub struct Score {
+        pub value: Property<u32>,
+    }
+
+    #[test]
+    fn replication_config_round_trips() {
+        let configs = [
+            ReplicationConfig::public(),
+            ReplicationConfig::public()
+                .persist_on_scope_exit()
+                .baseline_delta(),
+            ReplicationConfig::delegated()
+                .authority_lease(Duration::from_millis(1500))
+                .queue_authority_requests(),
+        ];
+        for config in configs {
+            let mut writer = FileBitWriter::new();
+            write_config(&config, &mut writer);
+            let bytes = writer.to_vec();
+            let mut reader = BitReader::new(&bytes);
+            assert_eq!(read_config(&mut reader), Ok(config));
+        }
+    }
+
+    #[test]
+    fn header_rejects_other_protocols() {
+        let component_kinds = ComponentKinds::new();
+        let mut writer = FileBitWriter::new();
+        write_header(&mut writer, ProtocolId::new(1), &[]);
+        let bytes = writer.to_vec();
+
+        let mut reader = BitReader::new(&bytes);
+        assert_eq!(
+            read_header(&mut reader, ProtocolId::new(1), &component_kinds),
+            Ok(())
+        );
+        let mut reader = BitReader::new(&bytes);
+        assert_eq!(
+            read_header(&mut reader, ProtocolId::new(2), &component_kinds),
+            Err(WorldSnapshotError::ProtocolMismatch {
+                expected: ProtocolId::new(2),
+                found: ProtocolId::new(1),
+            })
+        );
+        let mut reader = BitReader::new(b"nope");
+        assert_eq!(
+            read_header(&mut reader, ProtocolId::new(1), &component_kinds),
+            Err(WorldSnapshotError::NotASnapshot)
+        );
+    }
+
+    #[test]
+    fn duplicate_resource_records_are_malformed() {
+        let mut component_kinds = ComponentKinds::new();
+        component_kinds.add_component::<Score>();
+        let resource = EntityRecord {
+            is_static: false,
+            resource: Some(ComponentKind::of::<Score>()),
+            config: ReplicationConfig::public(),
+        };
+        let mut writer = FileBitWriter::new();
+        resource.ser(&component_kinds, &mut writer);
+        resource.ser(&component_kinds, &mut writer);
+        let bytes = writer.to_vec();
+
+        let mut reader = BitReader::new(&bytes);
+        assert_eq!(
+            read_records(&mut reader, &component_kinds, 1),
+            Ok(vec![resource])
+        );
+        let mut reader = BitReader::new(&bytes);
+        assert_eq!(
+            read_records(&mut reader, &component_kinds, 2),
+            Err(WorldSnapshotError::Malformed)
+        );
+    }
+
+    #[test]
+    fn relations_resolve_to_restored_entities() {
+        let saved = [GlobalEntity::from_u64(10), GlobalEntity::from_u64(20)];
+        let entity_indices: HashMap<GlobalEntity, usize> =
+            saved.iter().enumerate().map(|(i, e)| (*e, i)).collect();
+        let mut write_converter = SnapshotWriteConverter::new(&entity_indices);
+
+        // The first entity referenced gets snapshot id 0, and keeps it
+        let second = write_converter.get_or_reserve_entity(&saved[1]).ok();
+        assert_eq!(second, Some(HostEntity::new(0).copy_to_owned()));
+        assert_eq!(
+            write_converter.get_or_reserve_entity(&saved[1]).ok(),
+            second
+        );
+        assert!(write_converter
+            .get_or_reserve_entity(&GlobalEntity::from_u64(30))
+            .is_err());
+        let relation_targets = write_converter.into_relation_targets().unwrap();
+        assert_eq!(relation_targets, vec![1]);
+
+        let restored = [GlobalEntity::from_u64(100), GlobalEntity::from_u64(200)];
+        let read_converter =
+            SnapshotReadConverter::new(relation_targets.iter().map(|i| restored[*i]).collect());
+        assert_eq!(
+            read_converter
+                .remote_entity_to_global_entity(&RemoteEntity::new(0))
+                .ok(),
+            Some(restored[1])
+        );
+    }
+}
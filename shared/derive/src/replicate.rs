@@ -1,8 +1,9 @@
 use proc_macro2::{Punct, Spacing, Span, TokenStream};
 use quote::{format_ident, quote};
 use syn::{
-    parse_macro_input, Data, DeriveInput, Fields, GenericArgument, GenericParam, Generics, Ident,
-    Index, LitStr, Member, PathArguments, Type,
+    meta::ParseNestedMeta, parse_macro_input, parse_quote, Attribute, Data, DeriveInput, Fields,
+    GenericArgument, GenericParam, Generics, Ident, Index, LitFloat, LitStr, Member, PathArguments,
+    PathSegment, Token, Type,
 };
 
 use crate::{
@@ -15,14 +16,28 @@ const UNNAMED_FIELD_PREFIX: &str = "unnamed_field_";
 pub struct NormalProperty {
     pub variable_name: Ident,
     pub inner_type: Type,
+    /// Path of the field's property type with its generics, e.g.
+    /// `Property::<T>` or `PropertyVec::<T>`
+    pub property_type: TokenStream,
+    /// True for `PropertyVec` and `PropertyMap` fields, which write element
+    /// deltas in updates
+    pub is_collection: bool,
     pub uppercase_variable_name: Ident,
     pub index: usize,
+    /// Expression building the `Quantize` impl from a field-level
+    /// `#[replicate(quantize(..))]`, `quaternion(..)` or `normalized(..)`
+    pub quantizer: Option<TokenStream>,
 }
 
 pub struct EntityProperty {
     pub variable_name: Ident,
     pub uppercase_variable_name: Ident,
     pub index: usize,
+    /// `EntityProperty` or `EntitySet`
+    pub property_type: TokenStream,
+    /// True for `EntitySet` fields, whose waiting members don't hold back
+    /// the component
+    pub is_set: bool,
 }
 
 pub struct NonReplicatedProperty {
@@ -58,7 +73,7 @@ pub fn replicate_impl(
     let is_immutable = is_immutable_attr(&input);
 
     // Helper Properties
-    let properties = get_properties(&input);
+    let properties = get_properties(&input, &shared_crate_name);
     let struct_type = get_struct_type(&input);
     let (untyped_generics, typed_generics, turbofish) = get_generics(&input);
 
@@ -146,6 +161,7 @@ pub fn replicate_impl(
     let set_mutator_method = get_set_mutator_method(&properties, &struct_type);
     let publish_method = get_publish_method(&enum_name, &properties, &struct_type);
     let unpublish_method = get_unpublish_method(&properties, &struct_type);
+    let host_own_method = get_host_own_method(&enum_name, &properties, &struct_type);
     let enable_delegation_method =
         get_enable_delegation_method(&enum_name, &properties, &struct_type);
     let disable_delegation_method = get_disable_delegation_method(&properties, &struct_type);
@@ -165,6 +181,7 @@ pub fn replicate_impl(
     };
     let relations_waiting_method = get_relations_waiting_method(&properties, &struct_type);
     let relations_complete_method = get_relations_complete_method(&properties, &struct_type);
+    let relations_pending_method = get_relations_pending_method(&properties, &struct_type);
     let split_update_method =
         get_split_update_method(&replica_name, &properties, &untyped_generics);
 
@@ -187,7 +204,7 @@ pub fn replicate_impl(
                 DiffMask, PropertyMutate, PropertyMutator, ComponentUpdate,
                 ReplicaDynRef, ReplicaDynMut, LocalEntityAndGlobalEntityConverter, LocalEntityAndGlobalEntityConverterMut, ComponentKind, Named,
                 BitReader, BitWrite, BitWriter, OwnedBitReader, SerdeErr, Serde, EntityAuthAccessor, RemoteEntity,
-                EntityProperty, GlobalEntity, Replicate, Property, ComponentKinds, ReplicateBuilder, ComponentFieldUpdate,
+                EntityProperty, EntitySet, GlobalEntity, Replicate, Property, PropertyVec, PropertyMap, CollectionAcks, ComponentKinds, ReplicateBuilder, ComponentFieldUpdate,
             };
             use super::*;
 
@@ -247,6 +264,7 @@ pub fn replicate_impl(
                 #mirror_single_field_method
                 #publish_method
                 #unpublish_method
+                #host_own_method
                 #enable_delegation_method
                 #disable_delegation_method
                 #localize_method
@@ -257,6 +275,7 @@ pub fn replicate_impl(
                 #read_apply_field_update_method
                 #relations_waiting_method
                 #relations_complete_method
+                #relations_pending_method
             }
             impl #typed_generics Clone for #replica_name #untyped_generics {
                 #clone_method
@@ -329,11 +348,30 @@ fn get_field_name(property: &Property, struct_type: &StructType) -> Member {
 }
 
 impl Property {
-    pub fn normal(index: usize, variable_name: Ident, inner_type: Type) -> Self {
+    pub fn normal(
+        index: usize,
+        variable_name: Ident,
+        property_kind: PropertyKind,
+        quantizer: Option<TokenStream>,
+    ) -> Self {
+        let PropertyKind {
+            inner_type,
+            property_type,
+            is_collection,
+        } = property_kind;
+        if is_collection && quantizer.is_some() {
+            panic!(
+                "Field `{}`: PropertyVec and PropertyMap fields cannot be quantized",
+                variable_name
+            );
+        }
         Self::Normal(NormalProperty {
             index,
             variable_name: variable_name.clone(),
             inner_type,
+            property_type,
+            is_collection,
+            quantizer,
             uppercase_variable_name: Ident::new(
                 variable_name.to_string().to_uppercase().as_str(),
                 Span::call_site(),
@@ -341,10 +379,17 @@ impl Property {
         })
     }
 
-    pub fn entity(index: usize, variable_name: Ident) -> Self {
+    pub fn entity(index: usize, variable_name: Ident, is_set: bool) -> Self {
+        let property_type = if is_set {
+            quote! { EntitySet }
+        } else {
+            quote! { EntityProperty }
+        };
         Self::Entity(EntityProperty {
             index,
             variable_name: variable_name.clone(),
+            property_type,
+            is_set,
             uppercase_variable_name: Ident::new(
                 variable_name.to_string().to_uppercase().as_str(),
                 Span::call_site(),
@@ -391,7 +436,7 @@ impl Property {
     }
 }
 
-fn get_properties(input: &DeriveInput) -> Vec<Property> {
+fn get_properties(input: &DeriveInput, shared_crate_name: &TokenStream) -> Vec<Property> {
     let mut fields = Vec::new();
 
     if let Data::Struct(data_struct) = &input.data {
@@ -402,28 +447,32 @@ fn get_properties(input: &DeriveInput) -> Vec<Property> {
                         if let Type::Path(type_path) = &field.ty {
                             if let Some(property_seg) = type_path.path.segments.first() {
                                 let property_type = property_seg.ident.clone();
-                                // EntityProperty
-                                if property_type == "EntityProperty" {
+                                // EntityProperty, EntitySet
+                                if property_type == "EntityProperty" || property_type == "EntitySet"
+                                {
                                     fields.push(Property::entity(
                                         fields.len(),
                                         variable_name.clone(),
+                                        property_type == "EntitySet",
                                     ));
                                     continue;
-                                // Property
-                                } else if property_type == "Property" {
-                                    if let PathArguments::AngleBracketed(angle_args) =
-                                        &property_seg.arguments
-                                    {
-                                        if let Some(GenericArgument::Type(inner_type)) =
-                                            angle_args.args.first()
-                                        {
-                                            fields.push(Property::normal(
-                                                fields.len(),
-                                                variable_name.clone(),
-                                                inner_type.clone(),
-                                            ));
-                                            continue;
-                                        }
+                                // Property, PropertyVec, PropertyMap
+                                } else if property_type == "Property"
+                                    || property_type == "PropertyVec"
+                                    || property_type == "PropertyMap"
+                                {
+                                    if let Some(property_kind) = get_property_kind(property_seg) {
+                                        fields.push(Property::normal(
+                                            fields.len(),
+                                            variable_name.clone(),
+                                            property_kind,
+                                            get_quantizer(
+                                                &field.attrs,
+                                                variable_name,
+                                                shared_crate_name,
+                                            ),
+                                        ));
+                                        continue;
                                     }
                                 // Non-replicated Property
                                 } else {
@@ -444,22 +493,20 @@ fn get_properties(input: &DeriveInput) -> Vec<Property> {
                             let property_type = property_seg.ident.clone();
                             let variable_name =
                                 get_variable_name_for_unnamed_field(index, property_type.span());
-                            if property_type == "EntityProperty" {
-                                fields.push(Property::entity(fields.len(), variable_name));
+                            if property_type == "EntityProperty" || property_type == "EntitySet" {
+                                let is_set = property_type == "EntitySet";
+                                fields.push(Property::entity(fields.len(), variable_name, is_set));
+                                continue;
+                            } else if let Some(property_kind) = get_property_kind(property_seg) {
+                                let quantizer =
+                                    get_quantizer(&field.attrs, &variable_name, shared_crate_name);
+                                fields.push(Property::normal(
+                                    fields.len(),
+                                    variable_name,
+                                    property_kind,
+                                    quantizer,
+                                ));
                                 continue;
-                            } else if let PathArguments::AngleBracketed(angle_args) =
-                                &property_seg.arguments
-                            {
-                                if let Some(GenericArgument::Type(inner_type)) =
-                                    angle_args.args.first()
-                                {
-                                    fields.push(Property::normal(
-                                        fields.len(),
-                                        variable_name,
-                                        inner_type.clone(),
-                                    ));
-                                    continue;
-                                }
                             }
                         }
                     }
@@ -474,6 +521,209 @@ fn get_properties(input: &DeriveInput) -> Vec<Property> {
     fields
 }
 
+pub struct PropertyKind {
+    inner_type: Type,
+    property_type: TokenStream,
+    is_collection: bool,
+}
+
+/// Reads the value type and property path of a `Property<T>`,
+/// `PropertyVec<T>` or `PropertyMap<K, V>` field. `PropertyVec` and
+/// `PropertyMap` take the `Vec` or `HashMap` they hold as value type.
+fn get_property_kind(property_seg: &PathSegment) -> Option<PropertyKind> {
+    let PathArguments::AngleBracketed(angle_args) = &property_seg.arguments else {
+        return None;
+    };
+    let mut type_args = angle_args.args.iter().filter_map(|arg| match arg {
+        GenericArgument::Type(arg_type) => Some(arg_type.clone()),
+        _ => None,
+    });
+    let first = type_args.next()?;
+
+    if property_seg.ident == "PropertyVec" {
+        return Some(PropertyKind {
+            inner_type: parse_quote! { Vec<#first> },
+            property_type: quote! { PropertyVec::<#first> },
+            is_collection: true,
+        });
+    }
+    if property_seg.ident == "PropertyMap" {
+        let second = type_args.next()?;
+        return Some(PropertyKind {
+            inner_type: parse_quote! { std::collections::HashMap<#first, #second> },
+            property_type: quote! { PropertyMap::<#first, #second> },
+            is_collection: true,
+        });
+    }
+    Some(PropertyKind {
+        property_type: quote! { Property::<#first> },
+        inner_type: first,
+        is_collection: false,
+    })
+}
+
+/// Parses a field-level wire encoding attribute into an expression building
+/// its quantizer:
+///
+/// - `#[replicate(quantize(min = -100.0, max = 100.0, bits = 12))]` or
+///   `#[replicate(quantize(min = 0.0, max = 1.0, precision = 0.01))]` for
+///   `Property<f32>` and `Property<[f32; N]>`
+/// - `#[replicate(quaternion(bits = 10))]` for `Property<[f32; 4]>`
+/// - `#[replicate(normalized(bits = 12))]` for `Property<[f32; 2]>` and
+///   `Property<[f32; 3]>`
+fn get_quantizer(
+    attrs: &[Attribute],
+    field_name: &Ident,
+    shared_crate_name: &TokenStream,
+) -> Option<TokenStream> {
+    let mut quantizer = None;
+    for attr in attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("replicate"))
+    {
+        let result = attr.parse_nested_meta(|meta| {
+            if quantizer.is_some() {
+                return Err(meta.error("only one wire encoding is allowed per field"));
+            }
+            let args = parse_quantizer_args(&meta)?;
+            let encoding = meta.path.get_ident().map(|ident| ident.to_string());
+            quantizer = Some(match encoding.as_deref() {
+                Some("quantize") => {
+                    let (Some(min), Some(max)) = (args.min, args.max) else {
+                        return Err(meta.error("`quantize` requires both `min` and `max`"));
+                    };
+                    if min >= max {
+                        return Err(meta.error("`quantize` requires `min` < `max`"));
+                    }
+                    let min_tokens = float_tokens(min);
+                    let max_tokens = float_tokens(max);
+                    match (args.bits, args.precision) {
+                        (Some(bits), None) => {
+                            if !(1..=32).contains(&bits) {
+                                return Err(meta.error("`quantize` supports 1 to 32 bits"));
+                            }
+                            quote! {
+                                #shared_crate_name::QuantizedFloat::new(#min_tokens, #max_tokens, #bits)
+                            }
+                        }
+                        (None, Some(precision)) => {
+                            if precision <= 0.0 {
+                                return Err(meta.error("`precision` must be positive"));
+                            }
+                            let precision = float_tokens(precision);
+                            quote! {
+                                #shared_crate_name::QuantizedFloat::with_precision(
+                                    #min_tokens,
+                                    #max_tokens,
+                                    #precision,
+                                )
+                            }
+                        }
+                        _ => {
+                            return Err(meta
+                                .error("`quantize` requires exactly one of `bits` or `precision`"))
+                        }
+                    }
+                }
+                Some("quaternion") => {
+                    let bits = args.bits_only(&meta, "quaternion", 2..=30)?;
+                    quote! { #shared_crate_name::SmallestThree::new(#bits) }
+                }
+                Some("normalized") => {
+                    let bits = args.bits_only(&meta, "normalized", 2..=32)?;
+                    quote! { #shared_crate_name::NormalizedVector::new(#bits) }
+                }
+                _ => return Err(meta.error(
+                    "unknown field attribute, expected `quantize`, `quaternion` or `normalized`",
+                )),
+            });
+            Ok(())
+        });
+        if let Err(err) = result {
+            panic!(
+                "invalid #[replicate(..)] on field `{}`: {}",
+                field_name, err
+            );
+        }
+    }
+    quantizer
+}
+
+#[derive(Default)]
+struct QuantizerArgs {
+    min: Option<f32>,
+    max: Option<f32>,
+    bits: Option<u8>,
+    precision: Option<f32>,
+}
+
+impl QuantizerArgs {
+    fn bits_only(
+        &self,
+        meta: &ParseNestedMeta,
+        encoding: &str,
+        range: std::ops::RangeInclusive<u8>,
+    ) -> syn::Result<u8> {
+        if self.min.is_some() || self.max.is_some() || self.precision.is_some() {
+            return Err(meta.error(format!("`{}` only accepts `bits`", encoding)));
+        }
+        let Some(bits) = self.bits else {
+            return Err(meta.error(format!("`{}` requires `bits`", encoding)));
+        };
+        if !range.contains(&bits) {
+            return Err(meta.error(format!(
+                "`{}` supports {} to {} bits",
+                encoding,
+                range.start(),
+                range.end()
+            )));
+        }
+        Ok(bits)
+    }
+}
+
+fn parse_quantizer_args(meta: &ParseNestedMeta) -> syn::Result<QuantizerArgs> {
+    let mut args = QuantizerArgs::default();
+    meta.parse_nested_meta(|arg| {
+        if arg.path.is_ident("min") {
+            args.min = Some(parse_number(&arg)?);
+        } else if arg.path.is_ident("max") {
+            args.max = Some(parse_number(&arg)?);
+        } else if arg.path.is_ident("precision") {
+            args.precision = Some(parse_number(&arg)?);
+        } else if arg.path.is_ident("bits") {
+            args.bits = Some(arg.value()?.parse::<syn::LitInt>()?.base10_parse()?);
+        } else {
+            return Err(arg.error("expected `min`, `max`, `bits` or `precision`"));
+        }
+        Ok(())
+    })?;
+    Ok(args)
+}
+
+fn parse_number(meta: &ParseNestedMeta) -> syn::Result<f32> {
+    let input = meta.value()?;
+    let negative = input.parse::<Option<Token![-]>>()?.is_some();
+    let value = if input.peek(syn::LitInt) {
+        input.parse::<syn::LitInt>()?.base10_parse::<f32>()?
+    } else {
+        input.parse::<LitFloat>()?.base10_parse::<f32>()?
+    };
+    if !value.is_finite() {
+        return Err(meta.error("expected a finite number"));
+    }
+    Ok(if negative { -value } else { value })
+}
+
+fn float_tokens(value: f32) -> TokenStream {
+    let literal = LitFloat::new(&format!("{:?}f32", value.abs()), Span::call_site());
+    if value < 0.0 {
+        quote! { -#literal }
+    } else {
+        quote! { #literal }
+    }
+}
+
 fn get_property_enum_definition(enum_name: &Ident, properties: &[Property]) -> TokenStream {
     if properties.is_empty() {
         return quote! {
@@ -727,6 +977,33 @@ fn get_unpublish_method(properties: &[Property], struct_type: &StructType) -> To
     }
 }
 
+fn get_host_own_method(
+    enum_name: &Ident,
+    properties: &[Property],
+    struct_type: &StructType,
+) -> TokenStream {
+    let mut output = quote! {};
+
+    for property in properties.iter().filter(|p| p.is_replicated()) {
+        let field_name = get_field_name(property, struct_type);
+        let uppercase_variant_name = property.uppercase_variable_name();
+        let new_output_right = quote! {
+                self.#field_name.host_own(#enum_name::#uppercase_variant_name as u8);
+        };
+        let new_output_result = quote! {
+            #output
+            #new_output_right
+        };
+        output = new_output_result;
+    }
+
+    quote! {
+        fn host_own(&mut self) {
+            #output
+        }
+    }
+}
+
 fn get_enable_delegation_method(
     enum_name: &Ident,
     properties: &[Property],
@@ -843,18 +1120,18 @@ pub fn get_new_complete_method(
         let new_output_right = match property {
             Property::Normal(property) => {
                 let field_name = &property.variable_name;
-                let field_type = &property.inner_type;
+                let property_type = &property.property_type;
                 let uppercase_variant_name = &property.uppercase_variable_name;
 
                 match *struct_type {
                     StructType::Struct => {
                         quote! {
-                            #field_name: Property::<#field_type>::host_owned(#field_name, #enum_name::#uppercase_variant_name as u8)
+                            #field_name: #property_type::host_owned(#field_name, #enum_name::#uppercase_variant_name as u8)
                         }
                     }
                     StructType::TupleStruct => {
                         quote! {
-                            Property::<#field_type>::host_owned(#field_name, #enum_name::#uppercase_variant_name as u8)
+                            #property_type::host_owned(#field_name, #enum_name::#uppercase_variant_name as u8)
                         }
                     }
                     _ => {
@@ -864,17 +1141,18 @@ pub fn get_new_complete_method(
             }
             Property::Entity(property) => {
                 let field_name = &property.variable_name;
+                let property_type = &property.property_type;
                 let uppercase_variant_name = &property.uppercase_variable_name;
 
                 match *struct_type {
                     StructType::Struct => {
                         quote! {
-                             #field_name: EntityProperty::new_for_component(#enum_name::#uppercase_variant_name as u8)
+                             #field_name: #property_type::new_for_component(#enum_name::#uppercase_variant_name as u8)
                         }
                     }
                     StructType::TupleStruct => {
                         quote! {
-                            EntityProperty::new_for_component(#enum_name::#uppercase_variant_name as u8)
+                            #property_type::new_for_component(#enum_name::#uppercase_variant_name as u8)
                         }
                     }
                     _ => {
@@ -974,14 +1252,20 @@ pub fn get_builder_read_method(
         let field_name = property.variable_name();
         let new_output_right = match property {
             Property::Normal(inner_property) => {
-                let field_type = &inner_property.inner_type;
-                quote! {
-                    let #field_name = Property::<#field_type>::new_read(reader)?;
+                let property_type = &inner_property.property_type;
+                match &inner_property.quantizer {
+                    Some(quantizer) => quote! {
+                        let #field_name = #property_type::new_read_quantized(reader, &#quantizer)?;
+                    },
+                    None => quote! {
+                        let #field_name = #property_type::new_read(reader)?;
+                    },
                 }
             }
-            Property::Entity(_) => {
+            Property::Entity(inner_property) => {
+                let property_type = &inner_property.property_type;
                 quote! {
-                    let #field_name = EntityProperty::new_read(reader, converter)?;
+                    let #field_name = #property_type::new_read(reader, converter)?;
                 }
             }
             Property::NonReplicated(inner_property) => {
@@ -1040,24 +1324,33 @@ pub fn get_read_create_update_method(
     for property in properties.iter() {
         let new_output_right = match property {
             Property::Normal(inner_property) => {
-                let field_type = &inner_property.inner_type;
+                let property_type = &inner_property.property_type;
+                let read_write = match &inner_property.quantizer {
+                    Some(quantizer) => quote! {
+                        #property_type::read_write_quantized(reader, &mut update_writer, &#quantizer)?;
+                    },
+                    None => quote! {
+                        #property_type::read_write(reader, &mut update_writer)?;
+                    },
+                };
                 quote! {
                     {
                         let should_read = bool::de(reader)?;
                         should_read.ser(&mut update_writer);
                         if should_read {
-                            Property::<#field_type>::read_write(reader, &mut update_writer)?;
+                            #read_write
                         }
                     }
                 }
             }
-            Property::Entity(_) => {
+            Property::Entity(inner_property) => {
+                let property_type = &inner_property.property_type;
                 quote! {
                     {
                         let should_read = bool::de(reader)?;
                         should_read.ser(&mut update_writer);
                         if should_read {
-                            EntityProperty::read_write(reader, &mut update_writer)?;
+                            #property_type::read_write(reader, &mut update_writer)?;
                         }
                     }
                 }
@@ -1098,16 +1391,46 @@ fn get_split_update_method(
     for property in properties.iter() {
         let new_output_right = match property {
             Property::Normal(inner_property) => {
-                let field_type = &inner_property.inner_type;
+                let property_type = &inner_property.property_type;
+                let read_write = match &inner_property.quantizer {
+                    Some(quantizer) => quote! {
+                        #property_type::read_write_quantized(reader, &mut ready_writer, &#quantizer)?;
+                    },
+                    None => quote! {
+                        #property_type::read_write(reader, &mut ready_writer)?;
+                    },
+                };
                 quote! {
                     let should_read = bool::de(reader)?;
                     should_read.ser(&mut ready_writer);
                     if should_read {
-                        Property::<#field_type>::read_write(reader, &mut ready_writer)?;
+                        #read_write
                         ready_did_write = true;
                     }
                 }
             }
+            Property::Entity(inner_property) if inner_property.is_set => {
+                let index = inner_property.index as u8;
+                quote! {
+                    let should_read = bool::de(reader)?;
+                    if should_read {
+                        // copy set to read which members are waiting
+                        let set_copy = EntitySet::new_read(reader, converter)?;
+
+                        // the set applies now, and each waiting member resolves on its own
+                        for waiting_entity in set_copy.waiting_remote_entities() {
+                            waiting_did_write = true;
+                            waiting_updates.push((waiting_entity, ComponentFieldUpdate::new(#index, BitWriter::new().to_owned_reader())));
+                        }
+
+                        ready_did_write = true;
+                        true.ser(&mut ready_writer);
+                        set_copy.write_local_entities(converter, &mut ready_writer);
+                    } else {
+                        false.ser(&mut ready_writer);
+                    }
+                }
+            }
             Property::Entity(inner_property) => {
                 let index = inner_property.index as u8;
                 quote! {
@@ -1196,17 +1519,27 @@ fn get_read_apply_update_method(properties: &[Property], struct_type: &StructTyp
     for property in properties.iter() {
         let field_name = get_field_name(property, struct_type);
         let new_output_right = match property {
-            Property::Normal(_) => {
+            Property::Normal(inner_property) => {
+                let property_type = &inner_property.property_type;
+                let read = match &inner_property.quantizer {
+                    Some(quantizer) => quote! {
+                        #property_type::read_quantized(&mut self.#field_name, reader, &#quantizer)?;
+                    },
+                    None => quote! {
+                        #property_type::read(&mut self.#field_name, reader)?;
+                    },
+                };
                 quote! {
                     if bool::de(reader)? {
-                        Property::read(&mut self.#field_name, reader)?;
+                        #read
                     }
                 }
             }
-            Property::Entity(_) => {
+            Property::Entity(inner_property) => {
+                let property_type = &inner_property.property_type;
                 quote! {
                     if bool::de(reader)? {
-                        EntityProperty::read(&mut self.#field_name, reader, converter)?;
+                        #property_type::read(&mut self.#field_name, reader, converter)?;
                     }
                 }
             }
@@ -1243,6 +1576,14 @@ fn get_read_apply_field_update_method(
             Property::Normal(_) | Property::NonReplicated(_) => {
                 continue;
             }
+            Property::Entity(inner_property) if inner_property.is_set => {
+                let index = inner_property.index as u8;
+                quote! {
+                    #index => {
+                        self.#field_name.resolve_waiting(converter);
+                    }
+                }
+            }
             Property::Entity(inner_property) => {
                 let index = inner_property.index as u8;
                 quote! {
@@ -1278,14 +1619,11 @@ fn get_write_method(properties: &[Property], struct_type: &StructType) -> TokenS
     for property in properties.iter() {
         let field_name = get_field_name(property, struct_type);
         let new_output_right = match property {
-            Property::Normal(_) => {
-                quote! {
-                    Property::write(&self.#field_name, writer);
-                }
-            }
-            Property::Entity(_) => {
+            Property::Normal(inner_property) => get_property_write(inner_property, &field_name),
+            Property::Entity(inner_property) => {
+                let property_type = &inner_property.property_type;
                 quote! {
-                    EntityProperty::write(&self.#field_name, writer, converter);
+                    #property_type::write(&self.#field_name, writer, converter);
                 }
             }
             Property::NonReplicated(_) => {
@@ -1308,6 +1646,18 @@ fn get_write_method(properties: &[Property], struct_type: &StructType) -> TokenS
     }
 }
 
+fn get_property_write(property: &NormalProperty, field_name: &Member) -> TokenStream {
+    let property_type = &property.property_type;
+    match &property.quantizer {
+        Some(quantizer) => quote! {
+            #property_type::write_quantized(&self.#field_name, &#quantizer, writer);
+        },
+        None => quote! {
+            #property_type::write(&self.#field_name, writer);
+        },
+    }
+}
+
 fn get_write_update_method(
     enum_name: &Ident,
     properties: &[Property],
@@ -1320,21 +1670,30 @@ fn get_write_update_method(
         let new_output_right = match property {
             Property::Normal(property) => {
                 let uppercase_variant_name = &property.uppercase_variable_name;
+                let write = if property.is_collection {
+                    let property_type = &property.property_type;
+                    quote! {
+                        #property_type::write_acked(&self.#field_name, #enum_name::#uppercase_variant_name as u8, acks, writer);
+                    }
+                } else {
+                    get_property_write(property, &field_name)
+                };
                 quote! {
                     if let Some(true) = diff_mask.bit(#enum_name::#uppercase_variant_name as u8) {
                         true.ser(writer);
-                        Property::write(&self.#field_name, writer);
+                        #write
                     } else {
                         false.ser(writer);
                     }
                 }
             }
             Property::Entity(property) => {
+                let property_type = &property.property_type;
                 let uppercase_variant_name = &property.uppercase_variable_name;
                 quote! {
                     if let Some(true) = diff_mask.bit(#enum_name::#uppercase_variant_name as u8) {
                         true.ser(writer);
-                        EntityProperty::write(&self.#field_name, writer, converter);
+                        #property_type::write(&self.#field_name, writer, converter);
                     } else {
                         false.ser(writer);
                     }
@@ -1352,8 +1711,24 @@ fn get_write_update_method(
         output = new_output_result;
     }
 
+    let has_collections = properties
+        .iter()
+        .any(|property| matches!(property, Property::Normal(property) if property.is_collection));
+    if !has_collections {
+        return quote! {
+            fn write_update(&self, diff_mask: &DiffMask, writer: &mut dyn BitWrite, converter: &mut dyn LocalEntityAndGlobalEntityConverterMut) {
+                #output
+            }
+        };
+    }
+
+    // Collection fields write element deltas against the connection's acked
+    // versions, so the plain update writes them whole
     quote! {
         fn write_update(&self, diff_mask: &DiffMask, writer: &mut dyn BitWrite, converter: &mut dyn LocalEntityAndGlobalEntityConverterMut) {
+            self.write_update_acked(diff_mask, writer, converter, &mut CollectionAcks::new());
+        }
+        fn write_update_acked(&self, diff_mask: &DiffMask, writer: &mut dyn BitWrite, converter: &mut dyn LocalEntityAndGlobalEntityConverterMut, acks: &mut CollectionAcks) {
             #output
         }
     }
@@ -1409,7 +1784,8 @@ fn get_relations_waiting_method(fields: &[Property], struct_type: &StructType) -
     let mut body = quote! {};
 
     for field in fields.iter() {
-        if let Property::Entity(_) = field {
+        // EntitySet members wait on their own, see `relations_pending`
+        if let Property::Entity(EntityProperty { is_set: false, .. }) = field {
             let field_name = get_field_name(field, struct_type);
             let body_add_right = quote! {
                 if let Some(local_entity) = self.#field_name.waiting_remote_entity() {
@@ -1440,10 +1816,16 @@ fn get_relations_complete_method(fields: &[Property], struct_type: &StructType)
     let mut body = quote! {};
 
     for field in fields.iter() {
-        if let Property::Entity(_) = field {
+        if let Property::Entity(property) = field {
             let field_name = get_field_name(field, struct_type);
-            let body_add_right = quote! {
-                self.#field_name.waiting_complete(converter);
+            let body_add_right = if property.is_set {
+                quote! {
+                    self.#field_name.resolve_waiting(converter);
+                }
+            } else {
+                quote! {
+                    self.#field_name.waiting_complete(converter);
+                }
             };
             let new_body = quote! {
                 #body
@@ -1460,6 +1842,38 @@ fn get_relations_complete_method(fields: &[Property], struct_type: &StructType)
     }
 }
 
+fn get_relations_pending_method(fields: &[Property], struct_type: &StructType) -> TokenStream {
+    let mut body = quote! {};
+
+    for field in fields.iter() {
+        if let Property::Entity(EntityProperty { is_set: true, index, .. }) = field {
+            let field_name = get_field_name(field, struct_type);
+            let index = *index as u8;
+            let body_add_right = quote! {
+                for remote_entity in self.#field_name.waiting_remote_entities() {
+                    output.push((remote_entity, #index));
+                }
+            };
+            let new_body = quote! {
+                #body
+                #body_add_right
+            };
+            body = new_body;
+        }
+    }
+
+    quote! {
+        fn relations_pending(&self) -> Option<Vec<(RemoteEntity, u8)>> {
+            let mut output = Vec::new();
+            #body
+            if output.is_empty() {
+                return None;
+            }
+            return Some(output);
+        }
+    }
+}
+
 pub fn get_builder_box_clone_method(input_generics: &Generics) -> TokenStream {
     let fn_impl = if input_generics.gt_token.is_none() {
         quote! { Self }
@@ -28,6 +28,10 @@ impl<'b> BitReader<'b> {
         self.buffer.len()
     }
 
+    pub fn bits_read(&self) -> u32 {
+        (self.state.buffer_index * 8) as u32 - self.state.scratch_bits
+    }
+
     pub fn to_owned(&self) -> OwnedBitReader {
         OwnedBitReader {
             state: self.state,
@@ -73,6 +77,7 @@ impl<'b> BitReader<'b> {
 
 // OwnedBitReader
 
+#[derive(Clone)]
 pub struct OwnedBitReader {
     state: BitReaderState,
     buffer: Box<[u8]>,
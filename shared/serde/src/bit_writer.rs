@@ -105,6 +105,10 @@ impl BitWriter {
     pub fn bits_free(&self) -> u32 {
         self.max_bits - self.current_bits
     }
+
+    pub fn bits_written(&self) -> u32 {
+        self.current_bits
+    }
 }
 
 impl BitWrite for BitWriter {
@@ -11,6 +11,7 @@ mod file_bit_writer;
 mod impls;
 mod number;
 mod outgoing_packet;
+mod quantize;
 mod serde;
 
 pub use bit_counter::BitCounter;
@@ -25,6 +26,7 @@ pub use number::{
     UnsignedVariableInteger,
 };
 pub use outgoing_packet::OutgoingPacket;
+pub use quantize::{bits_for_precision, NormalizedVector, Quantize, QuantizedFloat, SmallestThree};
 pub use serde::{
     ConstBitLength, Serde, Serde as SerdeBevyClient, Serde as SerdeBevyServer,
     Serde as SerdeBevyShared, Serde as SerdeInternal,
@@ -0,0 +1,439 @@
+use crate::{bit_reader::BitReader, bit_writer::BitWrite, error::SerdeErr};
+
+/// A wire encoding for values of type `T` that replaces `T`'s own [`Serde`]
+/// impl. Quantizers are lossy: the value read back is the nearest value the
+/// encoding can represent, while the sender keeps full precision locally.
+///
+/// [`Serde`]: crate::Serde
+pub trait Quantize<T> {
+    /// Writes `value` into the outgoing bit stream
+    fn ser(&self, value: &T, writer: &mut dyn BitWrite);
+    /// Reads a quantized value back out of the incoming bit stream
+    fn de(&self, reader: &mut BitReader) -> Result<T, SerdeErr>;
+    /// Number of bits `ser` writes for `value`
+    fn bit_length(&self, value: &T) -> u32;
+}
+
+// QuantizedFloat
+
+/// Encodes floats in `[min, max]` as fixed-width integers of `bits` bits.
+/// Values outside the range are clamped; NaN encodes as `min`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct QuantizedFloat {
+    min: f32,
+    max: f32,
+    bits: u8,
+}
+
+impl QuantizedFloat {
+    /// Creates a quantizer over `[min, max]` using `bits` bits per value
+    pub fn new(min: f32, max: f32, bits: u8) -> Self {
+        if min.is_nan() || max.is_nan() || min >= max {
+            panic!("QuantizedFloat range must satisfy min < max (got {min}..{max})");
+        }
+        if bits == 0 || bits > 32 {
+            panic!("QuantizedFloat must use between 1 and 32 bits (got {bits})");
+        }
+        Self { min, max, bits }
+    }
+
+    /// Creates a quantizer over `[min, max]` using the fewest bits that keep
+    /// adjacent representable values at most `precision` apart
+    pub fn with_precision(min: f32, max: f32, precision: f32) -> Self {
+        if precision.is_nan() || precision <= 0.0 {
+            panic!("QuantizedFloat precision must be positive (got {precision})");
+        }
+        Self::new(min, max, bits_for_precision(min, max, precision))
+    }
+
+    /// Number of bits written per value
+    pub fn bits(&self) -> u8 {
+        self.bits
+    }
+
+    fn quantize(&self, value: f32) -> u32 {
+        quantize_unit(
+            (value as f64 - self.min as f64) / (self.max as f64 - self.min as f64),
+            self.bits,
+        )
+    }
+
+    fn dequantize(&self, raw: u32) -> f32 {
+        let unit = dequantize_unit(raw, self.bits);
+        (self.min as f64 + unit * (self.max as f64 - self.min as f64)) as f32
+    }
+}
+
+impl Quantize<f32> for QuantizedFloat {
+    fn ser(&self, value: &f32, writer: &mut dyn BitWrite) {
+        write_bits(writer, self.quantize(*value), self.bits);
+    }
+
+    fn de(&self, reader: &mut BitReader) -> Result<f32, SerdeErr> {
+        Ok(self.dequantize(read_bits(reader, self.bits)?))
+    }
+
+    fn bit_length(&self, _value: &f32) -> u32 {
+        self.bits as u32
+    }
+}
+
+impl<const N: usize> Quantize<[f32; N]> for QuantizedFloat {
+    fn ser(&self, value: &[f32; N], writer: &mut dyn BitWrite) {
+        for element in value {
+            Quantize::<f32>::ser(self, element, writer);
+        }
+    }
+
+    fn de(&self, reader: &mut BitReader) -> Result<[f32; N], SerdeErr> {
+        let mut output = [0.0; N];
+        for element in &mut output {
+            *element = Quantize::<f32>::de(self, reader)?;
+        }
+        Ok(output)
+    }
+
+    fn bit_length(&self, _value: &[f32; N]) -> u32 {
+        self.bits as u32 * N as u32
+    }
+}
+
+/// The fewest bits (capped at 32) whose step over `[min, max]` is no larger
+/// than `precision`
+pub fn bits_for_precision(min: f32, max: f32, precision: f32) -> u8 {
+    let steps = ((max as f64 - min as f64) / precision as f64).ceil();
+    let mut bits: u8 = 1;
+    while bits < 32 && (((1_u64 << bits) - 1) as f64) < steps {
+        bits += 1;
+    }
+    bits
+}
+
+// SmallestThree
+
+const SMALLEST_THREE_LIMIT: f32 = std::f32::consts::FRAC_1_SQRT_2;
+
+/// Encodes a unit quaternion `[x, y, z, w]` with the "smallest three"
+/// scheme: the index of the largest component in 2 bits, then the other
+/// three in `bits` bits each. The sign is normalized so the dropped component
+/// is positive, which is safe since `q` and `-q` are the same rotation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SmallestThree {
+    bits: u8,
+}
+
+impl SmallestThree {
+    /// Creates a quaternion quantizer using `bits` bits per stored component
+    pub fn new(bits: u8) -> Self {
+        if !(2..=30).contains(&bits) {
+            panic!("SmallestThree must use between 2 and 30 bits per component (got {bits})");
+        }
+        Self { bits }
+    }
+
+    /// Number of bits written per stored component
+    pub fn bits(&self) -> u8 {
+        self.bits
+    }
+
+    fn component(&self) -> QuantizedFloat {
+        QuantizedFloat::new(-SMALLEST_THREE_LIMIT, SMALLEST_THREE_LIMIT, self.bits)
+    }
+}
+
+impl Quantize<[f32; 4]> for SmallestThree {
+    fn ser(&self, value: &[f32; 4], writer: &mut dyn BitWrite) {
+        let quaternion = normalize(*value).unwrap_or([0.0, 0.0, 0.0, 1.0]);
+
+        let mut largest = 0;
+        for (index, element) in quaternion.iter().enumerate().skip(1) {
+            if element.abs() > quaternion[largest].abs() {
+                largest = index;
+            }
+        }
+        let sign = if quaternion[largest] < 0.0 { -1.0 } else { 1.0 };
+
+        write_bits(writer, largest as u32, 2);
+        let component = self.component();
+        for (index, element) in quaternion.iter().enumerate() {
+            if index != largest {
+                Quantize::<f32>::ser(&component, &(element * sign), writer);
+            }
+        }
+    }
+
+    fn de(&self, reader: &mut BitReader) -> Result<[f32; 4], SerdeErr> {
+        let largest = read_bits(reader, 2)? as usize;
+        let component = self.component();
+
+        let mut output = [0.0; 4];
+        let mut sum_squares = 0.0;
+        for (index, element) in output.iter_mut().enumerate() {
+            if index != largest {
+                *element = Quantize::<f32>::de(&component, reader)?;
+                sum_squares += *element * *element;
+            }
+        }
+        output[largest] = (1.0 - sum_squares).max(0.0).sqrt();
+
+        Ok(normalize(output).unwrap_or([0.0, 0.0, 0.0, 1.0]))
+    }
+
+    fn bit_length(&self, _value: &[f32; 4]) -> u32 {
+        2 + 3 * self.bits as u32
+    }
+}
+
+// NormalizedVector
+
+/// Encodes unit-length direction vectors. `[f32; 3]` uses an octahedral
+/// mapping onto two components of `bits` bits each; `[f32; 2]` stores the
+/// angle in `bits` bits. Inputs are normalized before encoding, and a zero
+/// vector encodes as the +Z (3D) or +X (2D) axis.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NormalizedVector {
+    bits: u8,
+}
+
+impl NormalizedVector {
+    /// Creates a direction quantizer using `bits` bits per stored component
+    pub fn new(bits: u8) -> Self {
+        if !(2..=32).contains(&bits) {
+            panic!("NormalizedVector must use between 2 and 32 bits per component (got {bits})");
+        }
+        Self { bits }
+    }
+
+    /// Number of bits written per stored component
+    pub fn bits(&self) -> u8 {
+        self.bits
+    }
+
+    fn component(&self, limit: f32) -> QuantizedFloat {
+        QuantizedFloat::new(-limit, limit, self.bits)
+    }
+}
+
+impl Quantize<[f32; 3]> for NormalizedVector {
+    fn ser(&self, value: &[f32; 3], writer: &mut dyn BitWrite) {
+        let [x, y, z] = *value;
+        let l1 = x.abs() + y.abs() + z.abs();
+        let (mut u, mut v) = if l1 > 0.0 && l1.is_finite() {
+            (x / l1, y / l1)
+        } else {
+            (0.0, 0.0)
+        };
+        if l1 > 0.0 && z < 0.0 {
+            (u, v) = ((1.0 - v.abs()) * sign(u), (1.0 - u.abs()) * sign(v));
+        }
+
+        let component = self.component(1.0);
+        Quantize::<f32>::ser(&component, &u, writer);
+        Quantize::<f32>::ser(&component, &v, writer);
+    }
+
+    fn de(&self, reader: &mut BitReader) -> Result<[f32; 3], SerdeErr> {
+        let component = self.component(1.0);
+        let u: f32 = Quantize::<f32>::de(&component, reader)?;
+        let v: f32 = Quantize::<f32>::de(&component, reader)?;
+
+        let z = 1.0 - u.abs() - v.abs();
+        let (x, y) = if z < 0.0 {
+            ((1.0 - v.abs()) * sign(u), (1.0 - u.abs()) * sign(v))
+        } else {
+            (u, v)
+        };
+
+        Ok(normalize([x, y, z]).unwrap_or([0.0, 0.0, 1.0]))
+    }
+
+    fn bit_length(&self, _value: &[f32; 3]) -> u32 {
+        2 * self.bits as u32
+    }
+}
+
+impl Quantize<[f32; 2]> for NormalizedVector {
+    fn ser(&self, value: &[f32; 2], writer: &mut dyn BitWrite) {
+        let [x, y] = *value;
+        let angle = if x.is_finite() && y.is_finite() {
+            y.atan2(x)
+        } else {
+            0.0
+        };
+        let component = self.component(std::f32::consts::PI);
+        Quantize::<f32>::ser(&component, &angle, writer);
+    }
+
+    fn de(&self, reader: &mut BitReader) -> Result<[f32; 2], SerdeErr> {
+        let component = self.component(std::f32::consts::PI);
+        let angle: f32 = Quantize::<f32>::de(&component, reader)?;
+        Ok([angle.cos(), angle.sin()])
+    }
+
+    fn bit_length(&self, _value: &[f32; 2]) -> u32 {
+        self.bits as u32
+    }
+}
+
+// Helpers
+
+fn sign(value: f32) -> f32 {
+    if value < 0.0 {
+        -1.0
+    } else {
+        1.0
+    }
+}
+
+fn normalize<const N: usize>(value: [f32; N]) -> Option<[f32; N]> {
+    let length = value
+        .iter()
+        .map(|element| element * element)
+        .sum::<f32>()
+        .sqrt();
+    if !length.is_finite() || length <= 0.0 {
+        return None;
+    }
+    Some(value.map(|element| element / length))
+}
+
+fn quantize_unit(unit: f64, bits: u8) -> u32 {
+    let steps = ((1_u64 << bits) - 1) as f64;
+    let unit = if unit.is_nan() {
+        0.0
+    } else {
+        unit.clamp(0.0, 1.0)
+    };
+    (unit * steps).round() as u32
+}
+
+fn dequantize_unit(raw: u32, bits: u8) -> f64 {
+    let steps = ((1_u64 << bits) - 1) as f64;
+    raw as f64 / steps
+}
+
+fn write_bits(writer: &mut dyn BitWrite, mut value: u32, bits: u8) {
+    for _ in 0..bits {
+        writer.write_bit(value & 1 != 0);
+        value >>= 1;
+    }
+}
+
+fn read_bits(reader: &mut BitReader, bits: u8) -> Result<u32, SerdeErr> {
+    let mut output = 0_u32;
+    for index in 0..bits as u32 {
+        if reader.read_bit()? {
+            output |= 1 << index;
+        }
+    }
+    Ok(output)
+}
+
+// Tests
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        bit_reader::BitReader,
+        bit_writer::BitWriter,
+        quantize::{bits_for_precision, NormalizedVector, Quantize, QuantizedFloat, SmallestThree},
+    };
+
+    fn round_trip<T, Q: Quantize<T>>(quantizer: &Q, value: T) -> (T, u32) {
+        let mut writer = BitWriter::new();
+        quantizer.ser(&value, &mut writer);
+        let bits = quantizer.bit_length(&value);
+        let buffer = writer.to_bytes();
+        let mut reader = BitReader::new(&buffer);
+        (quantizer.de(&mut reader).unwrap(), bits)
+    }
+
+    fn assert_close<const N: usize>(a: [f32; N], b: [f32; N], tolerance: f32) {
+        for (a, b) in a.iter().zip(b.iter()) {
+            assert!((a - b).abs() <= tolerance, "{a} vs {b}");
+        }
+    }
+
+    #[test]
+    fn float_round_trip_within_step() {
+        let quantizer = QuantizedFloat::new(-100.0, 100.0, 12);
+        let step = 200.0 / 4095.0;
+        for value in [-100.0, -37.25, 0.0, 12.5, 99.99, 100.0] {
+            let (out, bits) = round_trip(&quantizer, value);
+            assert!((out - value).abs() <= step / 2.0 + f32::EPSILON);
+            assert_eq!(bits, 12);
+        }
+    }
+
+    #[test]
+    fn float_clamps_out_of_range_and_nan() {
+        let quantizer = QuantizedFloat::new(0.0, 1.0, 8);
+        assert_eq!(round_trip(&quantizer, 5.0).0, 1.0);
+        assert_eq!(round_trip(&quantizer, -5.0).0, 0.0);
+        assert_eq!(round_trip(&quantizer, f32::NAN).0, 0.0);
+    }
+
+    #[test]
+    fn float_array_quantizes_each_element() {
+        let quantizer = QuantizedFloat::new(-10.0, 10.0, 16);
+        let value = [1.5, -2.25, 9.0];
+        let (out, bits) = round_trip(&quantizer, value);
+        assert_close(out, value, 0.001);
+        assert_eq!(bits, 48);
+    }
+
+    #[test]
+    fn precision_picks_fewest_bits() {
+        assert_eq!(bits_for_precision(0.0, 1.0, 0.5), 2);
+        assert_eq!(bits_for_precision(0.0, 1.0, 0.01), 7);
+        assert_eq!(bits_for_precision(-100.0, 100.0, 0.01), 15);
+        assert_eq!(QuantizedFloat::with_precision(0.0, 1.0, 0.01).bits(), 7);
+    }
+
+    #[test]
+    fn smallest_three_round_trip() {
+        let quantizer = SmallestThree::new(10);
+        let half = 0.5_f32;
+        let cases = [
+            [0.0, 0.0, 0.0, 1.0],
+            [half, half, half, half],
+            [-0.2, 0.7, -0.1, -0.68],
+            [0.9, 0.1, 0.3, 0.2],
+        ];
+        for value in cases {
+            let value = super::normalize(value).unwrap();
+            let (out, bits) = round_trip(&quantizer, value);
+            assert_eq!(bits, 32);
+            // q and -q are the same rotation.
+            let dot: f32 = value.iter().zip(out.iter()).map(|(a, b)| a * b).sum();
+            assert!(dot.abs() > 0.9999, "{value:?} -> {out:?}");
+        }
+    }
+
+    #[test]
+    fn normalized_vector_3d_round_trip() {
+        let quantizer = NormalizedVector::new(12);
+        let cases = [
+            [0.0, 0.0, 1.0],
+            [0.0, 0.0, -1.0],
+            [1.0, 2.0, -3.0],
+            [-0.3, 0.5, 0.1],
+            [0.0, -1.0, 0.0],
+        ];
+        for value in cases {
+            let expected = super::normalize(value).unwrap();
+            let (out, bits) = round_trip(&quantizer, value);
+            assert_eq!(bits, 24);
+            assert_close(out, expected, 0.002);
+        }
+        assert_close(round_trip(&quantizer, [0.0; 3]).0, [0.0, 0.0, 1.0], 0.002);
+    }
+
+    #[test]
+    fn normalized_vector_2d_round_trip() {
+        let quantizer = NormalizedVector::new(10);
+        let (out, bits) = round_trip(&quantizer, [3.0, -4.0]);
+        assert_eq!(bits, 10);
+        assert_close(out, [0.6, -0.8], 0.004);
+    }
+}
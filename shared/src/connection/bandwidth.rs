@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 /// Per-connection outbound bandwidth budget. Applied symmetrically to
 /// server-outbound and client-outbound send loops.
 ///
@@ -10,6 +12,11 @@ pub struct BandwidthConfig {
     /// `target_bytes_per_sec × dt` each tick; surplus carries into the next
     /// tick (Fiedler token-bucket).
     pub target_bytes_per_sec: u32,
+    /// Opt-in congestion control. When `Some`, each connection starts at
+    /// `target_bytes_per_sec` and adapts its rate to the link from its RTT,
+    /// jitter and packet loss, within the configured floor and ceiling.
+    /// `None` (the default) keeps the rate fixed.
+    pub congestion_control: Option<CongestionControlConfig>,
 }
 
 impl BandwidthConfig {
@@ -21,6 +28,52 @@ impl Default for BandwidthConfig {
     fn default() -> Self {
         Self {
             target_bytes_per_sec: Self::DEFAULT_TARGET_BYTES_PER_SEC,
+            congestion_control: None,
+        }
+    }
+}
+
+/// Tuning for the per-connection AIMD congestion controller.
+///
+/// Every `adjust_interval` the controller looks at the connection:
+/// - **Congested** if packet loss is above `loss_threshold` and rising, or
+///   the RTT exceeds the lowest RTT seen recently by more than
+///   `delay_threshold_ms` (or twice the jitter, if larger). The rate is
+///   multiplied by `decrease_factor`, at most once per RTT.
+/// - **Limited** if the send loop deferred packets for lack of budget since
+///   the last adjustment and the link isn't congested. The rate grows by
+///   `increase_bytes_per_sec`.
+/// - Otherwise the rate holds, so an idle connection doesn't inflate it.
+#[derive(Clone, Debug)]
+pub struct CongestionControlConfig {
+    /// The rate never drops below this many bytes per second.
+    pub min_bytes_per_sec: u32,
+    /// The rate never rises above this many bytes per second.
+    pub max_bytes_per_sec: u32,
+    /// Bytes per second added on each adjustment while the budget is the
+    /// bottleneck.
+    pub increase_bytes_per_sec: u32,
+    /// Factor the rate is multiplied by on congestion, in `(0.0, 1.0)`.
+    pub decrease_factor: f32,
+    /// Packet-loss fraction above which rising loss counts as congestion.
+    pub loss_threshold: f32,
+    /// Queueing delay, in milliseconds above the base RTT, that counts as
+    /// congestion.
+    pub delay_threshold_ms: f32,
+    /// How often the rate is re-evaluated.
+    pub adjust_interval: Duration,
+}
+
+impl Default for CongestionControlConfig {
+    fn default() -> Self {
+        Self {
+            min_bytes_per_sec: 8_000,
+            max_bytes_per_sec: 256_000,
+            increase_bytes_per_sec: 4_000,
+            decrease_factor: 0.7,
+            loss_threshold: 0.02,
+            delay_threshold_ms: 40.0,
+            adjust_interval: Duration::from_millis(250),
         }
     }
 }
@@ -1,6 +1,6 @@
 use naia_socket_shared::Instant;
 
-use crate::connection::bandwidth::BandwidthConfig;
+use crate::connection::{bandwidth::BandwidthConfig, congestion_controller::CongestionController};
 
 /// Token-bucket bandwidth accumulator. Drives the unified priority-sort send
 /// loop: budget accumulates as `target_bytes_per_sec × dt`; each successful
@@ -10,11 +10,22 @@ use crate::connection::bandwidth::BandwidthConfig;
 /// Surplus carries into the next tick (Fiedler token-bucket). Per-packet
 /// overshoot is permitted once per tick so that a minimum of one packet can
 /// always egress even under a tiny budget — see `can_spend()` semantics.
+///
+/// With `BandwidthConfig::congestion_control` set, the refill rate follows a
+/// [`CongestionController`] instead of staying at `target_bytes_per_sec`.
+/// A runtime override replaces that rate outright, and a rate cap (the
+/// connection's share of a server-wide aggregate budget) bounds it from above.
 pub(crate) struct BandwidthAccumulator {
     budget_bytes: f64,
     target_bytes_per_sec: f64,
     last_accumulate: Option<Instant>,
     sent_this_tick: bool,
+    congestion_controller: Option<CongestionController>,
+    rate_override: Option<f64>,
+    rate_cap: Option<f64>,
+    /// Whether a send was deferred for lack of budget since the congestion
+    /// controller last adjusted the rate.
+    limited_since_adjust: bool,
     // Telemetry (D13 always-on).
     bytes_sent_this_tick: u64,
     bytes_sent_last_tick: u64,
@@ -26,11 +37,22 @@ pub(crate) struct BandwidthAccumulator {
 
 impl BandwidthAccumulator {
     pub(crate) fn new(config: &BandwidthConfig) -> Self {
+        let congestion_controller = config
+            .congestion_control
+            .as_ref()
+            .map(|cc_config| CongestionController::new(cc_config, config.target_bytes_per_sec));
+        let target_bytes_per_sec = congestion_controller
+            .as_ref()
+            .map_or(config.target_bytes_per_sec as f64, |cc| cc.bytes_per_sec());
         Self {
             budget_bytes: 0.0,
-            target_bytes_per_sec: config.target_bytes_per_sec as f64,
+            target_bytes_per_sec,
             last_accumulate: None,
             sent_this_tick: false,
+            congestion_controller,
+            rate_override: None,
+            rate_cap: None,
+            limited_since_adjust: false,
             bytes_sent_this_tick: 0,
             bytes_sent_last_tick: 0,
             #[cfg(feature = "bench_instrumentation")]
@@ -41,13 +63,13 @@ impl BandwidthAccumulator {
     }
 
     /// Called once per outbound send cycle (tick). Adds
-    /// `target_bytes_per_sec × (now - last_accumulate)` to the budget.
+    /// `target_bytes_per_sec() × (now - last_accumulate)` to the budget.
     /// Also resets `sent_this_tick` so the one-packet overshoot budget is
     /// available again for the new cycle.
     pub(crate) fn accumulate(&mut self, now: &Instant) {
         if let Some(prev) = &self.last_accumulate {
             let dt_secs = prev.elapsed(now).as_secs_f64();
-            self.budget_bytes += self.target_bytes_per_sec * dt_secs;
+            self.budget_bytes += self.target_bytes_per_sec() * dt_secs;
         }
         self.last_accumulate = Some(now.clone());
         self.sent_this_tick = false;
@@ -61,6 +83,58 @@ impl BandwidthAccumulator {
         }
     }
 
+    /// Feeds the connection's link signals to the congestion controller, if
+    /// enabled, and adopts the rate it settles on. Call once per send cycle,
+    /// before `accumulate`. Does nothing while a rate override is set.
+    pub(crate) fn update_congestion_control(
+        &mut self,
+        now: &Instant,
+        rtt_ms: f32,
+        jitter_ms: f32,
+        packet_loss: f32,
+    ) {
+        if self.rate_override.is_some() {
+            return;
+        }
+        let Some(controller) = &mut self.congestion_controller else {
+            return;
+        };
+        if controller.update(now, rtt_ms, jitter_ms, packet_loss, self.limited_since_adjust) {
+            self.limited_since_adjust = false;
+            self.target_bytes_per_sec = controller.bytes_per_sec();
+        }
+    }
+
+    /// Rate this connection asks for, in bytes per second: the override if
+    /// set, otherwise the configured target or the congestion controller's
+    /// rate. Ignores the rate cap.
+    pub(crate) fn desired_bytes_per_sec(&self) -> f64 {
+        self.rate_override.unwrap_or(self.target_bytes_per_sec)
+    }
+
+    /// Current refill rate in bytes per second: the desired rate, bounded by
+    /// the rate cap if one is set.
+    pub(crate) fn target_bytes_per_sec(&self) -> f64 {
+        let desired = self.desired_bytes_per_sec();
+        self.rate_cap.map_or(desired, |cap| desired.min(cap))
+    }
+
+    /// Replaces the refill rate with `bytes_per_sec`, or restores the
+    /// configured / congestion-controlled rate with `None`.
+    pub(crate) fn set_rate_override(&mut self, bytes_per_sec: Option<u32>) {
+        self.rate_override = bytes_per_sec.map(|rate| rate as f64);
+    }
+
+    /// The rate override, if one is set.
+    pub(crate) fn rate_override(&self) -> Option<u32> {
+        self.rate_override.map(|rate| rate as u32)
+    }
+
+    /// Bounds the refill rate from above, or lifts the bound with `None`.
+    pub(crate) fn set_rate_cap(&mut self, bytes_per_sec: Option<f64>) {
+        self.rate_cap = bytes_per_sec;
+    }
+
     /// Returns true iff a send of approximately `estimated_bytes` is allowed.
     /// When the accumulator is positive, at least one MTU-sized packet can
     /// always go (overshoot permitted so the bucket can go negative by up to
@@ -98,10 +172,18 @@ impl BandwidthAccumulator {
         self.bytes_sent_last_tick
     }
 
-    /// Record that a packet was deferred due to the budget gate. Always a no-op
-    /// unless `bench_instrumentation` is enabled.
+    /// Record that a packet was deferred due to the budget gate. Counted only
+    /// when `bench_instrumentation` is enabled. Marks the connection as
+    /// budget-limited for the congestion controller unless the rate cap is
+    /// what held it back: the aggregate cap is no sign the link has room.
     #[inline]
     pub(crate) fn record_deferred(&mut self) {
+        let capped = self
+            .rate_cap
+            .is_some_and(|cap| cap < self.desired_bytes_per_sec());
+        if !capped {
+            self.limited_since_adjust = true;
+        }
         #[cfg(feature = "bench_instrumentation")]
         {
             self.packets_deferred_this_tick = self.packets_deferred_this_tick.saturating_add(1);
@@ -126,9 +208,12 @@ impl BandwidthAccumulator {
 
 #[cfg(test)]
 mod tests {
+    use std::time::Duration;
+
     use naia_socket_shared::Instant;
 
     use super::*;
+    use crate::connection::bandwidth::CongestionControlConfig;
 
     fn init_clock() {
         #[cfg(feature = "test_time")]
@@ -146,6 +231,7 @@ mod tests {
         init_clock();
         let cfg = BandwidthConfig {
             target_bytes_per_sec: 64_000,
+            ..Default::default()
         };
         let acc = BandwidthAccumulator::new(&cfg);
         assert_eq!(acc.remaining(), 0.0);
@@ -156,6 +242,7 @@ mod tests {
         init_clock();
         let cfg = BandwidthConfig {
             target_bytes_per_sec: 64_000,
+            ..Default::default()
         };
         let mut acc = BandwidthAccumulator::new(&cfg);
         let t0 = Instant::now();
@@ -169,6 +256,7 @@ mod tests {
         init_clock();
         let cfg = BandwidthConfig {
             target_bytes_per_sec: 64_000,
+            ..Default::default()
         };
         let mut acc = BandwidthAccumulator::new(&cfg);
         let t0 = Instant::now();
@@ -183,6 +271,7 @@ mod tests {
         init_clock();
         let cfg = BandwidthConfig {
             target_bytes_per_sec: 64_000,
+            ..Default::default()
         };
         let mut acc = BandwidthAccumulator::new(&cfg);
         let t0 = Instant::now();
@@ -198,6 +287,7 @@ mod tests {
         init_clock();
         let cfg = BandwidthConfig {
             target_bytes_per_sec: 64_000,
+            ..Default::default()
         };
         let mut acc = BandwidthAccumulator::new(&cfg);
         let t0 = Instant::now();
@@ -213,6 +303,7 @@ mod tests {
         init_clock();
         let cfg = BandwidthConfig {
             target_bytes_per_sec: 64_000,
+            ..Default::default()
         };
         let mut acc = BandwidthAccumulator::new(&cfg);
         let t0 = Instant::now();
@@ -231,6 +322,7 @@ mod tests {
         init_clock();
         let cfg = BandwidthConfig {
             target_bytes_per_sec: 64_000,
+            ..Default::default()
         };
         let mut acc = BandwidthAccumulator::new(&cfg);
         let t0 = Instant::now();
@@ -248,7 +340,10 @@ mod tests {
     #[test]
     fn telemetry_bytes_sent_snapshots_per_tick() {
         init_clock();
-        let cfg = BandwidthConfig { target_bytes_per_sec: 64_000 };
+        let cfg = BandwidthConfig {
+            target_bytes_per_sec: 64_000,
+            ..Default::default()
+        };
         let mut acc = BandwidthAccumulator::new(&cfg);
         let t0 = Instant::now();
         acc.accumulate(&t0);
@@ -275,6 +370,7 @@ mod tests {
         init_clock();
         let cfg = BandwidthConfig {
             target_bytes_per_sec: 64_000,
+            ..Default::default()
         };
         let mut acc = BandwidthAccumulator::new(&cfg);
         let t0 = Instant::now();
@@ -285,4 +381,70 @@ mod tests {
         // Budget now negative; overshoot already spent this tick.
         assert!(!acc.can_spend(1));
     }
+
+    #[test]
+    fn rate_override_replaces_and_restores_target() {
+        init_clock();
+        let cfg = BandwidthConfig {
+            target_bytes_per_sec: 64_000,
+            ..Default::default()
+        };
+        let mut acc = BandwidthAccumulator::new(&cfg);
+        acc.set_rate_override(Some(8_000));
+        assert_eq!(acc.rate_override(), Some(8_000));
+        let t0 = Instant::now();
+        acc.accumulate(&t0);
+        let t1 = advance(&t0, 1000);
+        acc.accumulate(&t1);
+        assert!((acc.remaining() - 8_000.0).abs() < 1.0);
+
+        acc.set_rate_override(None);
+        assert_eq!(acc.target_bytes_per_sec(), 64_000.0);
+    }
+
+    #[test]
+    fn rate_cap_bounds_target_but_not_desired_rate() {
+        init_clock();
+        let cfg = BandwidthConfig {
+            target_bytes_per_sec: 64_000,
+            ..Default::default()
+        };
+        let mut acc = BandwidthAccumulator::new(&cfg);
+        acc.set_rate_cap(Some(16_000.0));
+        assert_eq!(acc.desired_bytes_per_sec(), 64_000.0);
+        assert_eq!(acc.target_bytes_per_sec(), 16_000.0);
+
+        // A cap above the desired rate has no effect
+        acc.set_rate_override(Some(10_000));
+        assert_eq!(acc.target_bytes_per_sec(), 10_000.0);
+    }
+
+    #[test]
+    fn deferrals_under_the_rate_cap_do_not_grow_the_rate() {
+        init_clock();
+        let cfg = BandwidthConfig {
+            target_bytes_per_sec: 40_000,
+            congestion_control: Some(CongestionControlConfig {
+                min_bytes_per_sec: 10_000,
+                max_bytes_per_sec: 100_000,
+                increase_bytes_per_sec: 5_000,
+                adjust_interval: Duration::from_millis(100),
+                ..Default::default()
+            }),
+        };
+        let mut acc = BandwidthAccumulator::new(&cfg);
+        let t0 = Instant::now();
+
+        // Held back by the aggregate cap: the rate holds
+        acc.set_rate_cap(Some(20_000.0));
+        acc.record_deferred();
+        acc.update_congestion_control(&t0, 50.0, 2.0, 0.0);
+        assert_eq!(acc.desired_bytes_per_sec(), 40_000.0);
+
+        // Held back by its own rate: the rate grows
+        acc.set_rate_cap(None);
+        acc.record_deferred();
+        acc.update_congestion_control(&advance(&t0, 100), 50.0, 2.0, 0.0);
+        assert_eq!(acc.desired_bytes_per_sec(), 45_000.0);
+    }
 }
@@ -0,0 +1,311 @@
+use std::collections::{BTreeMap, HashMap};
+
+use crate::{ChannelKind, ChannelKinds, ComponentKind, ComponentKinds, GlobalEntity};
+
+/// Bits written for one kind of traffic, with the share spent re-sending
+/// reliable data the remote host hadn't acknowledged in time.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SentBits {
+    /// All bits written, retransmissions included.
+    pub bits: u64,
+    /// Bits spent on retransmissions, a subset of `bits`.
+    pub retransmitted_bits: u64,
+}
+
+impl SentBits {
+    fn merge(&mut self, other: &SentBits) {
+        self.bits += other.bits;
+        self.retransmitted_bits += other.retransmitted_bits;
+    }
+}
+
+/// Where the bits of outgoing data packets went, as recorded by the
+/// bandwidth profiler since it was enabled or last reset.
+///
+/// `total_bits` splits exactly into `header_bits`, `ack_bits`, the
+/// `channels`, `entity_commands` and `entity_update_bits`. `components`,
+/// `fields` and `entities` are further views of the world traffic, so they
+/// overlap. Bits are counted before compression.
+///
+/// Enable with `ConnectionConfig::bandwidth_profiling`, and obtain via
+/// `Server::bandwidth_profile(&user_key)`, `Server::global_bandwidth_profile()`
+/// or `Client::bandwidth_profile()`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BandwidthProfile {
+    /// Data packets written.
+    pub packets: u64,
+    /// All bits of those packets.
+    pub total_bits: u64,
+    /// Packet type and index, tick, and the framing bits between sections.
+    pub header_bits: u64,
+    /// Ack index and ack bitfield.
+    pub ack_bits: u64,
+    /// Messages per channel, by channel protocol name, including the
+    /// channel's framing bits.
+    pub channels: BTreeMap<String, SentBits>,
+    /// Entity commands: spawns, despawns, component inserts and removals,
+    /// and authority changes.
+    pub entity_commands: SentBits,
+    /// Component updates. Dropped updates are re-sent as fresh updates, so
+    /// these are never counted as retransmissions.
+    pub entity_update_bits: u64,
+    /// Component inserts and updates, by component protocol name.
+    pub components: BTreeMap<String, u64>,
+    /// Component updates per field, by component protocol name and field
+    /// index (the property's position in the component, as in its
+    /// `DiffMask`). Inserts write whole components and aren't split by field.
+    pub fields: BTreeMap<(String, u8), u64>,
+    /// Entity commands and component updates, by entity. Use the
+    /// `EntityAndGlobalEntityConverter` of the server or client to map these
+    /// back to world entities.
+    pub entities: HashMap<GlobalEntity, u64>,
+}
+
+impl BandwidthProfile {
+    /// Adds `other`'s counts into this profile.
+    pub fn merge(&mut self, other: &BandwidthProfile) {
+        self.packets += other.packets;
+        self.total_bits += other.total_bits;
+        self.header_bits += other.header_bits;
+        self.ack_bits += other.ack_bits;
+        for (name, bits) in &other.channels {
+            self.channels.entry(name.clone()).or_default().merge(bits);
+        }
+        self.entity_commands.merge(&other.entity_commands);
+        self.entity_update_bits += other.entity_update_bits;
+        for (name, bits) in &other.components {
+            *self.components.entry(name.clone()).or_default() += bits;
+        }
+        for (field, bits) in &other.fields {
+            *self.fields.entry(field.clone()).or_default() += bits;
+        }
+        for (entity, bits) in &other.entities {
+            *self.entities.entry(*entity).or_default() += bits;
+        }
+    }
+
+    /// Bits spent on retransmissions across all channels and entity
+    /// commands.
+    pub fn retransmitted_bits(&self) -> u64 {
+        self.channels
+            .values()
+            .map(|bits| bits.retransmitted_bits)
+            .sum::<u64>()
+            + self.entity_commands.retransmitted_bits
+    }
+
+    /// The `count` entities with the most bits, most expensive first.
+    pub fn top_entities(&self, count: usize) -> Vec<(GlobalEntity, u64)> {
+        let mut entities: Vec<(GlobalEntity, u64)> = self
+            .entities
+            .iter()
+            .map(|(entity, bits)| (*entity, *bits))
+            .collect();
+        entities.sort_by_key(|(_, bits)| std::cmp::Reverse(*bits));
+        entities.truncate(count);
+        entities
+    }
+}
+
+/// Per-connection recorder behind [`BandwidthProfile`], fed by the packet,
+/// message and world writers while `ConnectionConfig::bandwidth_profiling`
+/// is set.
+#[derive(Default)]
+pub struct BandwidthProfiler {
+    packets: u64,
+    total_bits: u64,
+    header_bits: u64,
+    ack_bits: u64,
+    channels: HashMap<ChannelKind, SentBits>,
+    entity_commands: SentBits,
+    entity_update_bits: u64,
+    components: HashMap<ComponentKind, u64>,
+    fields: HashMap<(ComponentKind, u8), u64>,
+    entities: HashMap<GlobalEntity, u64>,
+    /// Bits of the packet being written attributed to a section so far;
+    /// the rest of the packet is header
+    packet_section_bits: u64,
+}
+
+impl BandwidthProfiler {
+    /// Creates an empty profiler.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records `bits` written for `channel_kind`'s messages and framing.
+    pub fn record_channel(&mut self, channel_kind: &ChannelKind, bits: u32) {
+        self.channels.entry(*channel_kind).or_default().bits += bits as u64;
+        self.packet_section_bits += bits as u64;
+    }
+
+    /// Records `bits` of `channel_kind`'s messages as retransmissions. They
+    /// must also be recorded with [`record_channel`](Self::record_channel).
+    pub fn record_channel_retransmission(&mut self, channel_kind: &ChannelKind, bits: u32) {
+        self.channels
+            .entry(*channel_kind)
+            .or_default()
+            .retransmitted_bits += bits as u64;
+    }
+
+    /// Records `bits` written for the entity command section.
+    pub fn record_entity_commands(&mut self, bits: u32) {
+        self.entity_commands.bits += bits as u64;
+        self.packet_section_bits += bits as u64;
+    }
+
+    /// Records one entity command of `bits` about `entity`.
+    pub fn record_entity_command(
+        &mut self,
+        entity: &GlobalEntity,
+        bits: u32,
+        retransmission: bool,
+    ) {
+        self.record_entity(entity, bits);
+        if retransmission {
+            self.entity_commands.retransmitted_bits += bits as u64;
+        }
+    }
+
+    /// Records `bits` written for the component update section.
+    pub fn record_entity_updates(&mut self, bits: u32) {
+        self.entity_update_bits += bits as u64;
+        self.packet_section_bits += bits as u64;
+    }
+
+    /// Records `bits` written about `entity`.
+    pub fn record_entity(&mut self, entity: &GlobalEntity, bits: u32) {
+        *self.entities.entry(*entity).or_default() += bits as u64;
+    }
+
+    /// Records `bits` of a `component_kind` insert or update.
+    pub fn record_component(&mut self, component_kind: &ComponentKind, bits: u32) {
+        *self.components.entry(*component_kind).or_default() += bits as u64;
+    }
+
+    /// Records `bits` of an update to field `field_index` of a
+    /// `component_kind`.
+    pub fn record_field(&mut self, component_kind: &ComponentKind, field_index: u8, bits: u32) {
+        *self
+            .fields
+            .entry((*component_kind, field_index))
+            .or_default() += bits as u64;
+    }
+
+    /// Finishes a data packet of `total_bits`, `ack_bits` of which are
+    /// acks. Whatever wasn't recorded against a section is header.
+    pub fn finish_packet(&mut self, total_bits: u32, ack_bits: u32) {
+        self.packets += 1;
+        self.total_bits += total_bits as u64;
+        self.ack_bits += ack_bits as u64;
+        self.header_bits +=
+            (total_bits as u64).saturating_sub(ack_bits as u64 + self.packet_section_bits);
+        self.packet_section_bits = 0;
+    }
+
+    /// Clears everything recorded so far.
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Snapshot of everything recorded so far, with kinds resolved to their
+    /// protocol names.
+    pub fn profile(
+        &self,
+        component_kinds: &ComponentKinds,
+        channel_kinds: &ChannelKinds,
+    ) -> BandwidthProfile {
+        let channel_name = |channel_kind: &ChannelKind| {
+            channel_kinds
+                .channel_name(channel_kind)
+                .unwrap_or("unknown")
+                .to_string()
+        };
+        let mut profile = BandwidthProfile {
+            packets: self.packets,
+            total_bits: self.total_bits,
+            header_bits: self.header_bits,
+            ack_bits: self.ack_bits,
+            entity_commands: self.entity_commands,
+            entity_update_bits: self.entity_update_bits,
+            entities: self.entities.clone(),
+            ..Default::default()
+        };
+        for (channel_kind, bits) in &self.channels {
+            profile
+                .channels
+                .entry(channel_name(channel_kind))
+                .or_default()
+                .merge(bits);
+        }
+        for (component_kind, bits) in &self.components {
+            *profile
+                .components
+                .entry(component_kinds.kind_to_name(component_kind))
+                .or_default() += bits;
+        }
+        for ((component_kind, field_index), bits) in &self.fields {
+            *profile
+                .fields
+                .entry((component_kinds.kind_to_name(component_kind), *field_index))
+                .or_default() += bits;
+        }
+        profile
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BigMapKey;
+
+    #[test]
+    fn unattributed_packet_bits_are_header() {
+        let mut profiler = BandwidthProfiler::new();
+        profiler.record_entity_updates(100);
+        profiler.record_entity_commands(40);
+        profiler.finish_packet(200, 48);
+        profiler.finish_packet(60, 48);
+
+        let profile = profiler.profile(&ComponentKinds::new(), &ChannelKinds::new());
+        assert_eq!(profile.packets, 2);
+        assert_eq!(profile.total_bits, 260);
+        assert_eq!(profile.ack_bits, 96);
+        assert_eq!(profile.header_bits, 12 + 12);
+        assert_eq!(
+            profile.header_bits
+                + profile.ack_bits
+                + profile.entity_commands.bits
+                + profile.entity_update_bits,
+            profile.total_bits
+        );
+    }
+
+    #[test]
+    fn merge_sums_and_top_entities_sorts() {
+        let first = GlobalEntity::from_u64(1);
+        let second = GlobalEntity::from_u64(2);
+
+        let mut profiler = BandwidthProfiler::new();
+        profiler.record_entity(&first, 10);
+        profiler.record_entity_command(&second, 30, true);
+        profiler.record_entity_commands(30);
+        profiler.finish_packet(100, 48);
+        let profile = profiler.profile(&ComponentKinds::new(), &ChannelKinds::new());
+
+        let mut global = BandwidthProfile::default();
+        global.merge(&profile);
+        global.merge(&profile);
+        assert_eq!(global.packets, 2);
+        assert_eq!(global.entity_commands.bits, 60);
+        assert_eq!(global.retransmitted_bits(), 60);
+        assert_eq!(global.top_entities(1), vec![(second, 60)]);
+        assert_eq!(global.top_entities(5), vec![(second, 60), (first, 20)]);
+
+        profiler.reset();
+        assert_eq!(
+            profiler.profile(&ComponentKinds::new(), &ChannelKinds::new()),
+            BandwidthProfile::default()
+        );
+    }
+}
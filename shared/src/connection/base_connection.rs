@@ -4,10 +4,11 @@ use std::{
     net::SocketAddr,
 };
 
-use naia_serde::{BitReader, BitWriter, Serde, SerdeErr};
+use naia_serde::{BitReader, BitWriter, ConstBitLength, Serde, SerdeErr};
 use naia_socket_shared::Instant;
 
 use crate::connection::bandwidth_accumulator::BandwidthAccumulator;
+use crate::connection::bandwidth_profiler::BandwidthProfiler;
 use crate::world::local::local_world_manager::LocalWorldManager;
 use crate::world::world_reader::WorldReader;
 use crate::world::world_writer::WorldWriter;
@@ -29,6 +30,9 @@ pub struct BaseConnection {
     pub message_manager: MessageManager,
     /// Manages entity-level replication state for this connection.
     pub world_manager: LocalWorldManager,
+    /// Attributes outgoing bits when `ConnectionConfig::bandwidth_profiling`
+    /// is set.
+    pub bandwidth_profiler: Option<BandwidthProfiler>,
     ack_manager: AckManager,
     heartbeat_timer: Timer,
     bandwidth_accumulator: BandwidthAccumulator,
@@ -52,6 +56,9 @@ impl BaseConnection {
                 user_key,
                 global_world_manager,
             ),
+            bandwidth_profiler: connection_config
+                .bandwidth_profiling
+                .then(BandwidthProfiler::new),
             ack_manager: AckManager::new(),
             heartbeat_timer: Timer::new(connection_config.heartbeat_interval),
             bandwidth_accumulator: BandwidthAccumulator::new(&connection_config.bandwidth),
@@ -66,6 +73,44 @@ impl BaseConnection {
         self.bandwidth_accumulator.accumulate(now);
     }
 
+    /// Feed the latest RTT and jitter, plus this connection's packet loss, to
+    /// the congestion controller when `BandwidthConfig::congestion_control`
+    /// is set. Call once per send cycle, before `accumulate_bandwidth`.
+    pub fn update_congestion_control(&mut self, now: &Instant, rtt_ms: f32, jitter_ms: f32) {
+        let packet_loss = self.ack_manager.packet_loss_pct();
+        self.bandwidth_accumulator
+            .update_congestion_control(now, rtt_ms, jitter_ms, packet_loss);
+    }
+
+    /// Current outbound budget rate in bytes per second — the override,
+    /// configured target or congestion controller's rate, bounded by the
+    /// rate cap.
+    pub fn bandwidth_target_bytes_per_sec(&self) -> f64 {
+        self.bandwidth_accumulator.target_bytes_per_sec()
+    }
+
+    /// Outbound rate this connection asks for, ignoring the rate cap.
+    pub fn bandwidth_desired_bytes_per_sec(&self) -> f64 {
+        self.bandwidth_accumulator.desired_bytes_per_sec()
+    }
+
+    /// Overrides the outbound budget rate, or restores the configured one
+    /// with `None`. Congestion control is suspended while an override is set.
+    pub fn set_bandwidth_override(&mut self, bytes_per_sec: Option<u32>) {
+        self.bandwidth_accumulator.set_rate_override(bytes_per_sec);
+    }
+
+    /// The outbound budget override, if one is set.
+    pub fn bandwidth_override(&self) -> Option<u32> {
+        self.bandwidth_accumulator.rate_override()
+    }
+
+    /// Bounds the outbound budget rate from above, or lifts the bound with
+    /// `None`. Used to hand out shares of a server-wide aggregate budget.
+    pub fn set_bandwidth_cap(&mut self, bytes_per_sec: Option<f64>) {
+        self.bandwidth_accumulator.set_rate_cap(bytes_per_sec);
+    }
+
     /// Check whether a packet of `estimated_bytes` is permitted under the
     /// current budget. Allows one MTU-sized overshoot per tick when the
     /// budget is positive but short.
@@ -161,6 +206,16 @@ impl BaseConnection {
         header
     }
 
+    /// Finishes profiling a data packet written into `writer`, attributing
+    /// whatever the sections didn't record to the header and acks.
+    pub fn profile_packet(&mut self, writer: &BitWriter) {
+        if let Some(profiler) = &mut self.bandwidth_profiler {
+            let ack_bits =
+                <PacketIndex as ConstBitLength>::const_bit_length() + u32::const_bit_length();
+            profiler.finish_packet(writer.bits_written(), ack_bits);
+        }
+    }
+
     /// Get the next outgoing packet's index
     pub fn next_packet_index(&self) -> PacketIndex {
         self.ack_manager.next_sender_packet_index()
@@ -202,6 +257,7 @@ impl BaseConnection {
             writer,
             packet_index,
             has_written,
+            self.bandwidth_profiler.as_mut(),
         );
     }
 
@@ -249,6 +305,7 @@ impl BaseConnection {
                 host_world_events,
                 update_events,
                 entity_priority_order,
+                self.bandwidth_profiler.as_mut(),
             );
         }
     }
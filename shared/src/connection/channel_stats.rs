@@ -0,0 +1,171 @@
+/// Snapshot of one channel's traffic on a connection, since it connected.
+///
+/// Part of [`ConnectionStats`](crate::ConnectionStats), keyed by channel
+/// protocol name. Counts cover the channel's direction(s) on this side: a
+/// server-to-client channel only has received counts on the client.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ChannelStats {
+    /// Messages queued for sending.
+    pub messages_sent: u64,
+    /// Messages delivered to the application.
+    pub messages_received: u64,
+    /// Bytes written for this channel, retransmissions and framing
+    /// included, before compression.
+    pub bytes_sent: u64,
+    /// Bytes read for this channel, duplicates and framing included.
+    pub bytes_received: u64,
+    /// Messages currently queued. On reliable channels, messages stay queued
+    /// until acknowledged, which is what `max_queue_depth` limits.
+    pub queue_depth: usize,
+    /// The channel's `ReliableSettings::max_queue_depth`, or the message
+    /// capacity of a tick-buffered channel. `None` on channels without a
+    /// limit.
+    pub max_queue_depth: Option<usize>,
+    /// Messages re-sent because they weren't acknowledged in time.
+    pub retransmissions: u64,
+    /// Average time from first sending a reliable message to its
+    /// acknowledgement, in milliseconds (EWMA). `0.0` until the first ack, and
+    /// on unreliable channels.
+    pub avg_ack_ms: f32,
+    /// Messages rejected or evicted because the channel's queue was full.
+    pub dropped_backpressure: u64,
+    /// Tick-buffered messages discarded because their tick passed before
+    /// they could be sent or delivered.
+    pub dropped_expired: u64,
+}
+
+impl ChannelStats {
+    /// Adds `other`'s counts into these stats, as when a channel's sending
+    /// and receiving halves are tracked separately.
+    pub fn merge(&mut self, other: &ChannelStats) {
+        self.messages_sent += other.messages_sent;
+        self.messages_received += other.messages_received;
+        self.bytes_sent += other.bytes_sent;
+        self.bytes_received += other.bytes_received;
+        self.queue_depth += other.queue_depth;
+        self.max_queue_depth = self.max_queue_depth.or(other.max_queue_depth);
+        self.retransmissions += other.retransmissions;
+        if self.avg_ack_ms == 0.0 {
+            self.avg_ack_ms = other.avg_ack_ms;
+        }
+        self.dropped_backpressure += other.dropped_backpressure;
+        self.dropped_expired += other.dropped_expired;
+    }
+}
+
+/// Queue and delivery state reported by a channel sender.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct SenderStats {
+    /// Messages currently queued.
+    pub queue_depth: usize,
+    /// Messages re-sent because they weren't acknowledged in time.
+    pub retransmissions: u64,
+    /// Average time from first send to acknowledgement, in milliseconds.
+    pub avg_ack_ms: f32,
+    /// Queued messages evicted to make room for newer ones.
+    pub evicted: u64,
+}
+
+/// Running per-channel counters behind [`ChannelStats`], kept by the message
+/// manager and the tick buffers.
+#[derive(Clone, Default)]
+pub struct ChannelCounters {
+    messages_sent: u64,
+    messages_received: u64,
+    bits_sent: u64,
+    bits_received: u64,
+    dropped_backpressure: u64,
+    dropped_expired: u64,
+}
+
+impl ChannelCounters {
+    /// Creates zeroed counters.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records `count` messages queued for sending.
+    pub fn record_sent(&mut self, count: usize) {
+        self.messages_sent += count as u64;
+    }
+
+    /// Records `count` messages delivered to the application.
+    pub fn record_received(&mut self, count: usize) {
+        self.messages_received += count as u64;
+    }
+
+    /// Records `bits` written for the channel.
+    pub fn record_bits_sent(&mut self, bits: u32) {
+        self.bits_sent += bits as u64;
+    }
+
+    /// Records `bits` read for the channel.
+    pub fn record_bits_received(&mut self, bits: u32) {
+        self.bits_received += bits as u64;
+    }
+
+    /// Records `count` messages rejected or evicted by a full queue.
+    pub fn record_dropped_backpressure(&mut self, count: usize) {
+        self.dropped_backpressure += count as u64;
+    }
+
+    /// Records `count` tick-buffered messages discarded as expired.
+    pub fn record_dropped_expired(&mut self, count: usize) {
+        self.dropped_expired += count as u64;
+    }
+
+    /// Snapshot of these counters, combined with the channel sender's state
+    /// if this side sends on the channel.
+    pub fn stats(&self, sender: Option<SenderStats>, max_queue_depth: Option<usize>) -> ChannelStats {
+        let sender = sender.unwrap_or_default();
+        ChannelStats {
+            messages_sent: self.messages_sent,
+            messages_received: self.messages_received,
+            bytes_sent: self.bits_sent.div_ceil(8),
+            bytes_received: self.bits_received.div_ceil(8),
+            queue_depth: sender.queue_depth,
+            max_queue_depth,
+            retransmissions: sender.retransmissions,
+            avg_ack_ms: sender.avg_ack_ms,
+            dropped_backpressure: self.dropped_backpressure + sender.evicted,
+            dropped_expired: self.dropped_expired,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stats_combine_counters_and_sender() {
+        let mut counters = ChannelCounters::new();
+        counters.record_sent(3);
+        counters.record_bits_sent(17);
+        counters.record_dropped_backpressure(1);
+
+        let sender = SenderStats {
+            queue_depth: 2,
+            retransmissions: 4,
+            avg_ack_ms: 50.0,
+            evicted: 5,
+        };
+        let stats = counters.stats(Some(sender), Some(8));
+        assert_eq!(stats.messages_sent, 3);
+        assert_eq!(stats.bytes_sent, 3);
+        assert_eq!(stats.queue_depth, 2);
+        assert_eq!(stats.max_queue_depth, Some(8));
+        assert_eq!(stats.retransmissions, 4);
+        assert_eq!(stats.dropped_backpressure, 6);
+
+        let mut received = ChannelCounters::new();
+        received.record_received(7);
+        received.record_bits_received(80);
+        let mut merged = stats.clone();
+        merged.merge(&received.stats(None, None));
+        assert_eq!(merged.messages_received, 7);
+        assert_eq!(merged.bytes_received, 10);
+        assert_eq!(merged.avg_ack_ms, 50.0);
+        assert_eq!(merged.max_queue_depth, Some(8));
+    }
+}
@@ -1,4 +1,10 @@
-/// Per-direction zstd compression settings for a connection.
+use naia_serde::SerdeInternal;
+
+/// Per-direction packet compression settings for a connection.
+///
+/// Both peers must agree on which directions are compressed (`Some` vs
+/// `None`); the codec and dictionary used within a direction are negotiated
+/// during the handshake.
 #[derive(Clone)]
 pub struct CompressionConfig {
     /// Compression applied to packets flowing from server to client.
@@ -20,7 +26,11 @@ impl CompressionConfig {
     }
 }
 
-/// Selects the zstd compression strategy applied to a direction of traffic.
+/// Selects the compression strategy applied to a direction of traffic.
+///
+/// `Default`, `Dictionary` and `Training` use zstd and need the
+/// `zstd_support` feature. `Deflate` is pure Rust, builds for wasm, and needs
+/// the `deflate_support` feature.
 #[derive(Clone, Eq, PartialEq)]
 pub enum CompressionMode {
     /// Compression mode using default zstd dictionary.
@@ -30,10 +40,222 @@ pub enum CompressionMode {
     /// Compression mode using custom dictionary.
     /// 1st i32 parameter here is the compression level from -7 (fastest) to 22
     /// (smallest). 2nd `Vec<u8>` parameter here is the dictionary itself.
+    ///
+    /// The dictionary is only used with peers that hold the same one, as
+    /// identified by its [`DictionaryId`] during the handshake. Packets to any
+    /// other peer fall back to `Default` at the same level.
     Dictionary(i32, Vec<u8>),
     /// Dictionary training mode.
     /// 1st usize parameter here describes the desired number of samples
     /// (packets) to train on. Obviously, the more samples trained on, the
-    /// better theoretical compression.
+    /// better theoretical compression. Packets are sent uncompressed while
+    /// training; once enough samples are in, the dictionary can be taken with
+    /// `Server::take_trained_dictionary` / `Client::take_trained_dictionary`.
     Training(usize),
+    /// Compression mode using DEFLATE, implemented in pure Rust so that
+    /// browser clients can use it.
+    /// 1st u8 parameter here is the compression level from 0 (none) to 10
+    /// (smallest).
+    Deflate(u8),
+}
+
+/// DEFLATE level used when a zstd mode falls back for a peer that can't
+/// decode zstd
+const FALLBACK_DEFLATE_LEVEL: u8 = 6;
+
+impl CompressionMode {
+    /// The id of this mode's dictionary, if it is `Dictionary`.
+    pub fn dictionary_id(&self) -> Option<DictionaryId> {
+        match self {
+            Self::Dictionary(_, dictionary) => Some(DictionaryId::of(dictionary)),
+            Self::Default(_) | Self::Training(_) | Self::Deflate(_) => None,
+        }
+    }
+
+    /// The DEFLATE level to compress at when sending `Deflate` packets.
+    pub fn deflate_level(&self) -> u8 {
+        match self {
+            Self::Deflate(level) => *level,
+            Self::Default(_) | Self::Dictionary(_, _) | Self::Training(_) => FALLBACK_DEFLATE_LEVEL,
+        }
+    }
+
+    /// Picks the codec for packets in this mode to a peer whose decoder has
+    /// the given capabilities. zstd modes fall back to DEFLATE, and any mode
+    /// falls back to sending packets uncompressed, when the two builds share
+    /// nothing better.
+    pub fn negotiate(&self, peer: &CompressionCapabilities) -> PacketCodec {
+        let local = CompressionCapabilities::local(None);
+        let zstd = local.zstd && peer.zstd;
+        let deflate = local.deflate && peer.deflate;
+        match self {
+            // Training sends uncompressed samples regardless
+            Self::Training(_) => PacketCodec::Uncompressed,
+            Self::Dictionary(_, _) if zstd && peer.dictionary == self.dictionary_id() => {
+                PacketCodec::ZstdDictionary
+            }
+            Self::Default(_) | Self::Dictionary(_, _) if zstd => PacketCodec::Zstd,
+            Self::Default(_) | Self::Dictionary(_, _) | Self::Deflate(_) if deflate => {
+                PacketCodec::Deflate
+            }
+            Self::Default(_) | Self::Dictionary(_, _) | Self::Deflate(_) => {
+                PacketCodec::Uncompressed
+            }
+        }
+    }
+}
+
+/// How packets to one peer are compressed, as negotiated during the handshake.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PacketCodec {
+    /// Sent as-is behind the compression flag: the peers share no codec, or
+    /// the handshake hasn't completed yet.
+    #[default]
+    Uncompressed,
+    /// zstd without a dictionary.
+    Zstd,
+    /// zstd with the dictionary both peers hold.
+    ZstdDictionary,
+    /// Pure-Rust DEFLATE.
+    Deflate,
+}
+
+/// What one peer's packet decoder can read, sent during the handshake so the
+/// other side can pick a [`PacketCodec`] it understands.
+#[derive(SerdeInternal, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CompressionCapabilities {
+    /// Built with `zstd_support`.
+    pub zstd: bool,
+    /// Built with `deflate_support`.
+    pub deflate: bool,
+    /// The zstd dictionary of the decoder's `CompressionMode::Dictionary`.
+    pub dictionary: Option<DictionaryId>,
+}
+
+impl CompressionCapabilities {
+    /// The capabilities of this build's decoder for packets compressed in
+    /// `incoming` mode.
+    pub fn local(incoming: Option<&CompressionMode>) -> Self {
+        Self {
+            zstd: cfg!(feature = "zstd_support"),
+            deflate: cfg!(feature = "deflate_support"),
+            dictionary: incoming.and_then(CompressionMode::dictionary_id),
+        }
+    }
+}
+
+/// Identifies a compression dictionary by a hash of its bytes, so peers can
+/// tell whether they hold the same dictionary without exchanging it.
+#[derive(SerdeInternal, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DictionaryId(u64);
+
+impl DictionaryId {
+    /// Computes the id of the given dictionary bytes (BLAKE3, truncated to
+    /// 64 bits).
+    pub fn of(dictionary: &[u8]) -> Self {
+        let hash = blake3::hash(dictionary);
+        let mut bytes = [0u8; 8];
+        bytes.copy_from_slice(&hash.as_bytes()[..8]);
+        Self(u64::from_le_bytes(bytes))
+    }
+
+    /// Get the raw u64 value.
+    pub fn value(&self) -> u64 {
+        self.0
+    }
+}
+
+impl std::fmt::Display for DictionaryId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "DictionaryId({:016x})", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use naia_serde::{BitReader, BitWriter, Serde};
+
+    use super::*;
+
+    fn peer(zstd: bool, deflate: bool, dictionary: Option<&[u8]>) -> CompressionCapabilities {
+        CompressionCapabilities {
+            zstd,
+            deflate,
+            dictionary: dictionary.map(DictionaryId::of),
+        }
+    }
+
+    #[test]
+    fn dictionary_id_depends_only_on_bytes() {
+        assert_eq!(DictionaryId::of(b"abc"), DictionaryId::of(b"abc"));
+        assert_ne!(DictionaryId::of(b"abc"), DictionaryId::of(b"abd"));
+        assert_eq!(CompressionMode::Default(3).dictionary_id(), None);
+        assert_eq!(
+            CompressionMode::Dictionary(1, b"abc".to_vec()).dictionary_id(),
+            CompressionMode::Dictionary(9, b"abc".to_vec()).dictionary_id()
+        );
+    }
+
+    #[test]
+    fn capabilities_round_trip() {
+        let capabilities = peer(true, false, Some(b"dict"));
+        let mut writer = BitWriter::new();
+        capabilities.ser(&mut writer);
+        let bytes = writer.to_bytes();
+        let mut reader = BitReader::new(&bytes);
+        assert_eq!(
+            CompressionCapabilities::de(&mut reader).unwrap(),
+            capabilities
+        );
+    }
+
+    #[test]
+    fn nothing_shared_is_uncompressed() {
+        let nothing = peer(false, false, None);
+        assert_eq!(
+            CompressionMode::Default(3).negotiate(&nothing),
+            PacketCodec::Uncompressed
+        );
+        assert_eq!(
+            CompressionMode::Deflate(6).negotiate(&nothing),
+            PacketCodec::Uncompressed
+        );
+        assert_eq!(
+            CompressionMode::Training(10).negotiate(&peer(true, true, None)),
+            PacketCodec::Uncompressed
+        );
+    }
+
+    #[cfg(feature = "zstd_support")]
+    #[test]
+    fn dictionary_is_used_only_when_shared() {
+        let mode = CompressionMode::Dictionary(3, b"dict".to_vec());
+        assert_eq!(
+            mode.negotiate(&peer(true, false, Some(b"dict"))),
+            PacketCodec::ZstdDictionary
+        );
+        assert_eq!(
+            mode.negotiate(&peer(true, false, Some(b"other"))),
+            PacketCodec::Zstd
+        );
+        assert_eq!(mode.negotiate(&peer(true, false, None)), PacketCodec::Zstd);
+    }
+
+    #[cfg(feature = "deflate_support")]
+    #[test]
+    fn zstd_falls_back_to_deflate() {
+        let wasm_client = peer(false, true, None);
+        assert_eq!(
+            CompressionMode::Default(3).negotiate(&wasm_client),
+            PacketCodec::Deflate
+        );
+        assert_eq!(
+            CompressionMode::Dictionary(3, b"dict".to_vec()).negotiate(&wasm_client),
+            PacketCodec::Deflate
+        );
+        assert_eq!(
+            CompressionMode::Deflate(6).negotiate(&wasm_client),
+            PacketCodec::Deflate
+        );
+    }
 }
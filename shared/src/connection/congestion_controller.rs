@@ -0,0 +1,256 @@
+use std::time::Duration;
+
+use naia_socket_shared::Instant;
+
+use crate::connection::bandwidth::CongestionControlConfig;
+
+/// How long an RTT sample counts towards the base (uncongested) RTT. The
+/// base RTT is the lowest sample from the current or previous window, so a
+/// route change that raises the true RTT is picked up within two windows.
+const BASE_RTT_WINDOW: Duration = Duration::from_secs(10);
+
+/// Per-connection AIMD rate controller. Adapts the outbound token-bucket
+/// rate from the RTT, jitter and packet-loss signals reported in
+/// `ConnectionStats`; see [`CongestionControlConfig`] for the rules.
+pub(crate) struct CongestionController {
+    config: CongestionControlConfig,
+    bytes_per_sec: f64,
+    last_adjust: Option<Instant>,
+    last_decrease: Option<Instant>,
+    /// Loss fraction seen at the previous adjustment. The loss window spans
+    /// 64 packets, so one burst stays visible for a while; only rising loss
+    /// counts as new congestion.
+    last_loss: f32,
+    base_rtt: BaseRtt,
+}
+
+impl CongestionController {
+    pub(crate) fn new(config: &CongestionControlConfig, initial_bytes_per_sec: u32) -> Self {
+        let bytes_per_sec = (initial_bytes_per_sec as f64).clamp(
+            config.min_bytes_per_sec as f64,
+            config.max_bytes_per_sec.max(config.min_bytes_per_sec) as f64,
+        );
+        Self {
+            config: config.clone(),
+            bytes_per_sec,
+            last_adjust: None,
+            last_decrease: None,
+            last_loss: 0.0,
+            base_rtt: BaseRtt::new(),
+        }
+    }
+
+    /// Current rate, in bytes per second.
+    pub(crate) fn bytes_per_sec(&self) -> f64 {
+        self.bytes_per_sec
+    }
+
+    /// Feeds the latest link signals. `limited` is whether the send loop ran
+    /// out of budget since the last adjustment. Returns `true` if an
+    /// adjustment was due, in which case the caller starts a new `limited`
+    /// window.
+    pub(crate) fn update(
+        &mut self,
+        now: &Instant,
+        rtt_ms: f32,
+        jitter_ms: f32,
+        packet_loss: f32,
+        limited: bool,
+    ) -> bool {
+        self.base_rtt.sample(now, rtt_ms);
+
+        if let Some(last_adjust) = &self.last_adjust {
+            if last_adjust.elapsed(now) < self.config.adjust_interval {
+                return false;
+            }
+        }
+        self.last_adjust = Some(now.clone());
+
+        let loss_rising = packet_loss > self.config.loss_threshold && packet_loss > self.last_loss;
+        let queueing_delay = rtt_ms - self.base_rtt.get();
+        let delayed = queueing_delay > self.config.delay_threshold_ms.max(2.0 * jitter_ms);
+        self.last_loss = packet_loss;
+
+        let min = self.config.min_bytes_per_sec as f64;
+        let max = self
+            .config
+            .max_bytes_per_sec
+            .max(self.config.min_bytes_per_sec) as f64;
+        if loss_rising || delayed {
+            // React at most once per round trip: the signals lag the rate by
+            // about one RTT
+            let rtt = Duration::from_secs_f32(rtt_ms.max(0.0) / 1000.0);
+            let recovering = self
+                .last_decrease
+                .as_ref()
+                .is_some_and(|last_decrease| last_decrease.elapsed(now) < rtt);
+            if !recovering {
+                self.bytes_per_sec =
+                    (self.bytes_per_sec * self.config.decrease_factor as f64).clamp(min, max);
+                self.last_decrease = Some(now.clone());
+            }
+        } else if limited && packet_loss <= self.config.loss_threshold {
+            self.bytes_per_sec =
+                (self.bytes_per_sec + self.config.increase_bytes_per_sec as f64).clamp(min, max);
+        }
+        true
+    }
+}
+
+/// Windowed minimum of the RTT samples.
+struct BaseRtt {
+    window_start: Option<Instant>,
+    current_min: f32,
+    previous_min: f32,
+}
+
+impl BaseRtt {
+    fn new() -> Self {
+        Self {
+            window_start: None,
+            current_min: f32::MAX,
+            previous_min: f32::MAX,
+        }
+    }
+
+    fn sample(&mut self, now: &Instant, rtt_ms: f32) {
+        match &self.window_start {
+            Some(window_start) if window_start.elapsed(now) < BASE_RTT_WINDOW => {}
+            _ => {
+                self.window_start = Some(now.clone());
+                self.previous_min = self.current_min;
+                self.current_min = f32::MAX;
+            }
+        }
+        self.current_min = self.current_min.min(rtt_ms);
+    }
+
+    fn get(&self) -> f32 {
+        self.current_min.min(self.previous_min)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use naia_socket_shared::Instant;
+
+    use super::*;
+
+    fn init_clock() {
+        #[cfg(feature = "test_time")]
+        naia_socket_shared::TestClock::init(0);
+    }
+
+    fn config() -> CongestionControlConfig {
+        CongestionControlConfig {
+            min_bytes_per_sec: 10_000,
+            max_bytes_per_sec: 100_000,
+            increase_bytes_per_sec: 5_000,
+            decrease_factor: 0.5,
+            loss_threshold: 0.05,
+            delay_threshold_ms: 30.0,
+            adjust_interval: Duration::from_millis(100),
+        }
+    }
+
+    fn later(t: &Instant, ms: u32) -> Instant {
+        let mut out = t.clone();
+        out.add_millis(ms);
+        out
+    }
+
+    #[test]
+    fn initial_rate_is_clamped_to_bounds() {
+        assert_eq!(
+            CongestionController::new(&config(), 500_000).bytes_per_sec(),
+            100_000.0
+        );
+        assert_eq!(
+            CongestionController::new(&config(), 1_000).bytes_per_sec(),
+            10_000.0
+        );
+    }
+
+    #[test]
+    fn grows_only_while_limited() {
+        init_clock();
+        let t0 = Instant::now();
+        let mut cc = CongestionController::new(&config(), 40_000);
+        assert!(cc.update(&t0, 50.0, 2.0, 0.0, true));
+        assert_eq!(cc.bytes_per_sec(), 45_000.0);
+
+        // Too soon: no adjustment
+        assert!(!cc.update(&later(&t0, 50), 50.0, 2.0, 0.0, true));
+        assert_eq!(cc.bytes_per_sec(), 45_000.0);
+
+        // Not limited: the rate holds
+        assert!(cc.update(&later(&t0, 100), 50.0, 2.0, 0.0, false));
+        assert_eq!(cc.bytes_per_sec(), 45_000.0);
+    }
+
+    #[test]
+    fn growth_stops_at_ceiling() {
+        init_clock();
+        let t0 = Instant::now();
+        let mut cc = CongestionController::new(&config(), 98_000);
+        cc.update(&t0, 50.0, 2.0, 0.0, true);
+        assert_eq!(cc.bytes_per_sec(), 100_000.0);
+    }
+
+    #[test]
+    fn rising_loss_halves_the_rate_once_per_rtt() {
+        init_clock();
+        let t0 = Instant::now();
+        let mut cc = CongestionController::new(&config(), 80_000);
+        cc.update(&t0, 200.0, 2.0, 0.0, true);
+        assert_eq!(cc.bytes_per_sec(), 85_000.0);
+
+        cc.update(&later(&t0, 100), 200.0, 2.0, 0.1, true);
+        assert_eq!(cc.bytes_per_sec(), 42_500.0);
+
+        // Still inside the RTT of the last decrease
+        cc.update(&later(&t0, 200), 200.0, 2.0, 0.2, true);
+        assert_eq!(cc.bytes_per_sec(), 42_500.0);
+
+        cc.update(&later(&t0, 400), 200.0, 2.0, 0.3, true);
+        assert_eq!(cc.bytes_per_sec(), 21_250.0);
+    }
+
+    #[test]
+    fn steady_old_loss_holds_the_rate() {
+        init_clock();
+        let t0 = Instant::now();
+        let mut cc = CongestionController::new(&config(), 80_000);
+        cc.update(&t0, 50.0, 2.0, 0.1, true);
+        assert_eq!(cc.bytes_per_sec(), 40_000.0);
+
+        // Loss still in the window but no longer rising: neither shrink nor grow
+        cc.update(&later(&t0, 100), 50.0, 2.0, 0.1, true);
+        assert_eq!(cc.bytes_per_sec(), 40_000.0);
+    }
+
+    #[test]
+    fn queueing_delay_shrinks_the_rate_to_the_floor() {
+        init_clock();
+        let t0 = Instant::now();
+        let mut cc = CongestionController::new(&config(), 15_000);
+        cc.update(&t0, 50.0, 2.0, 0.0, false);
+        assert_eq!(cc.bytes_per_sec(), 15_000.0);
+
+        // RTT 60 ms above the base
+        cc.update(&later(&t0, 100), 110.0, 2.0, 0.0, true);
+        assert_eq!(cc.bytes_per_sec(), 10_000.0);
+    }
+
+    #[test]
+    fn jitter_widens_the_delay_tolerance() {
+        init_clock();
+        let t0 = Instant::now();
+        let mut cc = CongestionController::new(&config(), 40_000);
+        cc.update(&t0, 50.0, 40.0, 0.0, false);
+
+        // 60 ms over the base is within 2 × 40 ms of jitter
+        cc.update(&later(&t0, 100), 110.0, 40.0, 0.0, true);
+        assert_eq!(cc.bytes_per_sec(), 45_000.0);
+    }
+}
@@ -18,6 +18,11 @@ pub struct ConnectionConfig {
     /// Outbound bandwidth budget (token-bucket cap) applied by the unified
     /// priority-sort send loop. Distinct from `bandwidth_measure_duration`.
     pub bandwidth: BandwidthConfig,
+    /// Attribute every bit of outgoing data packets to its header, acks,
+    /// channel, component, field and entity, for
+    /// `Server::bandwidth_profile` / `Client::bandwidth_profile`. Costs a
+    /// few extra bit-counting passes per component update.
+    pub bandwidth_profiling: bool,
 }
 
 impl ConnectionConfig {
@@ -34,6 +39,7 @@ impl ConnectionConfig {
             heartbeat_interval,
             bandwidth_measure_duration,
             bandwidth: BandwidthConfig::default(),
+            bandwidth_profiling: false,
         }
     }
 }
@@ -52,6 +58,8 @@ impl Default for ConnectionConfig {
             // to avoid the per-packet accounting cost in production builds.
             bandwidth_measure_duration: None,
             bandwidth: BandwidthConfig::default(),
+            // false: profiling is opt-in for the same reason
+            bandwidth_profiling: false,
         }
     }
 }
@@ -1,3 +1,7 @@
+use std::collections::BTreeMap;
+
+use crate::ChannelStats;
+
 /// Snapshot of per-connection network diagnostics.
 ///
 /// All fields are rolling averages or short-window estimates; they are
@@ -20,4 +24,12 @@ pub struct ConnectionStats {
     pub kbps_sent: f32,
     /// Rolling-average incoming bandwidth in kilobits per second.
     pub kbps_recv: f32,
+    /// Outbound bandwidth budget in kilobits per second: the configured
+    /// `BandwidthConfig::target_bytes_per_sec`, the rate the congestion
+    /// controller has settled on, or a per-user override on the server,
+    /// bounded by the connection's share of any aggregate cap.
+    pub kbps_target: f32,
+    /// Per-channel traffic since the connection was established, by channel
+    /// protocol name.
+    pub channels: BTreeMap<String, ChannelStats>,
 }
@@ -1,85 +1,93 @@
-cfg_if! {
-    if #[cfg(feature = "zstd_support")]
-    {
-        use zstd::bulk::Decompressor;
+use log::warn;
 
-        use super::compression_config::CompressionMode;
+#[cfg(feature = "deflate_support")]
+use super::deflate_codec;
+#[cfg(feature = "zstd_support")]
+use super::zstd_codec::ZstdDecompressor;
+use super::{
+    compression_config::CompressionMode,
+    encoder::{FLAG_DEFLATE, FLAG_RAW, FLAG_ZSTD, FLAG_ZSTD_DICTIONARY},
+};
 
-        #[derive(Clone)]
-        pub struct Decoder {
-            result: Vec<u8>,
-            decoder: Option<Decompressor<'static>>,
+/// Packet decoder: decompresses payloads written by `Encoder`, whichever
+/// codec the flag byte names.
+pub struct Decoder {
+    compression_mode: CompressionMode,
+    result: Vec<u8>,
+    #[cfg(feature = "zstd_support")]
+    zstd: ZstdDecompressor,
+}
+
+impl Decoder {
+    /// Creates a decoder for packets compressed with `compression_mode`.
+    pub fn new(compression_mode: CompressionMode) -> Self {
+        Self {
+            #[cfg(feature = "zstd_support")]
+            zstd: match &compression_mode {
+                CompressionMode::Dictionary(_, dictionary) => {
+                    ZstdDecompressor::new(Some(dictionary))
+                }
+                CompressionMode::Default(_)
+                | CompressionMode::Training(_)
+                | CompressionMode::Deflate(_) => ZstdDecompressor::new(None),
+            },
+            compression_mode,
+            result: Vec::new(),
         }
+    }
 
-        impl Decoder {
-            pub fn new(compression_mode: CompressionMode) -> Self {
-                let decoder = match compression_mode {
-                    CompressionMode::Training(_) => None,
-                    CompressionMode::Default(_) => {
-                        Some(Decompressor::new().expect("error creating Decompressor"))
-                    }
-                    CompressionMode::Dictionary(_, dictionary) => Some(
-                        Decompressor::with_dictionary(&dictionary).expect("error creating Decompressor"),
-                    ),
-                };
+    /// Decodes a packet written by `Encoder::encode`. Packets that can't
+    /// be decoded — compressed with a codec or dictionary this side doesn't
+    /// have, or corrupt — decode to an empty payload, which the caller drops.
+    pub fn decode(&mut self, payload: &[u8]) -> &[u8] {
+        // First byte is the compression flag (written by encoder)
+        let Some((&flag, data)) = payload.split_first() else {
+            self.result = Vec::new();
+            return &self.result;
+        };
 
-                Self {
-                    decoder,
-                    result: Vec::new(),
-                }
+        let decoded = match flag {
+            FLAG_RAW => Some(data.to_vec()),
+            FLAG_ZSTD => self.decompress_zstd(data, false),
+            FLAG_ZSTD_DICTIONARY => self.decompress_zstd(data, true),
+            FLAG_DEFLATE => self.decompress_deflate(data),
+            _ => {
+                warn!("Dropping packet with unknown compression flag {}", flag);
+                None
             }
+        };
+        self.result = decoded.unwrap_or_default();
+        &self.result
+    }
 
-            pub fn decode(&mut self, payload: &[u8]) -> &[u8] {
-                // First byte is the is_compressed flag (written by encoder)
-                let (is_compressed, data) = match payload.split_first() {
-                    Some((&flag, rest)) => (flag != 0, rest),
-                    None => {
-                        self.result = Vec::new();
-                        return &self.result;
-                    }
-                };
+    #[cfg(feature = "zstd_support")]
+    fn decompress_zstd(&mut self, data: &[u8], use_dictionary: bool) -> Option<Vec<u8>> {
+        self.zstd.decompress(data, use_dictionary)
+    }
 
-                if is_compressed {
-                    if let Some(decoder) = &mut self.decoder {
-                        self.result = decoder
-                            .decompress(
-                                data,
-                                Decompressor::<'static>::upper_bound(data)
-                                    .expect("upper bound decode error"),
-                            )
-                            .expect("decode error");
-                        return &self.result;
-                    }
-                }
-                // Not compressed (or no decoder configured): return raw data
-                self.result = data.to_vec();
-                &self.result
-            }
-        }
+    #[cfg(not(feature = "zstd_support"))]
+    fn decompress_zstd(&mut self, _data: &[u8], _use_dictionary: bool) -> Option<Vec<u8>> {
+        warn!("Dropping zstd-compressed packet: `zstd_support` is disabled");
+        None
     }
-    else
-    {
-        use super::compression_config::CompressionMode;
 
-        /// Packet decoder (no-op variant: passes payload through unchanged).
-        #[derive(Clone)]
-        pub struct Decoder {
-            result: Vec<u8>,
-        }
+    #[cfg(feature = "deflate_support")]
+    fn decompress_deflate(&mut self, data: &[u8]) -> Option<Vec<u8>> {
+        deflate_codec::decompress(data)
+    }
 
-        impl Decoder {
-            /// Creates a no-op decoder (compression mode is ignored in this build variant).
-            pub fn new(_: CompressionMode) -> Self {
-                Self {
-                    result: Vec::new(),
-                }
-            }
+    #[cfg(not(feature = "deflate_support"))]
+    fn decompress_deflate(&mut self, _data: &[u8]) -> Option<Vec<u8>> {
+        warn!("Dropping DEFLATE-compressed packet: `deflate_support` is disabled");
+        None
+    }
+}
 
-            /// Returns the payload unchanged (no-op decompression).
-            pub fn decode(&mut self, payload: &[u8]) -> &[u8] {
-                self.result = payload.to_vec();
-                &self.result
-            }
-        }
+impl Clone for Decoder {
+    fn clone(&self) -> Self {
+        // zstd contexts can't be cloned; build fresh ones for the same mode
+        let mut decoder = Self::new(self.compression_mode.clone());
+        decoder.result = self.result.clone();
+        decoder
     }
 }
@@ -0,0 +1,23 @@
+//! DEFLATE compression (`deflate_support`), in pure Rust so it also builds
+//! for wasm clients.
+
+use log::warn;
+
+use miniz_oxide::{deflate::compress_to_vec, inflate::decompress_to_vec_with_limit};
+use naia_serde::MTU_SIZE_BYTES;
+
+pub(crate) fn compress(payload: &[u8], level: u8) -> Vec<u8> {
+    compress_to_vec(payload, level)
+}
+
+/// Decompresses `data`, or returns `None` if it is corrupt or inflates past
+/// the largest packet a peer can send.
+pub(crate) fn decompress(data: &[u8]) -> Option<Vec<u8>> {
+    match decompress_to_vec_with_limit(data, MTU_SIZE_BYTES) {
+        Ok(result) => Some(result),
+        Err(error) => {
+            warn!("Dropping packet that failed to decompress: {}", error);
+            None
+        }
+    }
+}
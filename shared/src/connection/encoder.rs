@@ -1,166 +1,268 @@
-cfg_if! {
-    if #[cfg(feature = "zstd_support")]
-    {
-        use std::fs;
+use log::warn;
 
-        use log::info;
+use super::compression_config::{CompressionMode, PacketCodec};
+#[cfg(feature = "deflate_support")]
+use super::deflate_codec;
+#[cfg(feature = "zstd_support")]
+use super::zstd_codec::{DictionaryTrainer, ZstdCompressor};
 
-        use zstd::{bulk::Compressor, dict::from_continuous};
+/// First byte of an encoded packet: the payload follows uncompressed.
+pub(crate) const FLAG_RAW: u8 = 0;
+/// First byte of an encoded packet: zstd-compressed without a dictionary.
+pub(crate) const FLAG_ZSTD: u8 = 1;
+/// First byte of an encoded packet: zstd-compressed with the dictionary
+/// both peers agreed on during the handshake.
+pub(crate) const FLAG_ZSTD_DICTIONARY: u8 = 2;
+/// First byte of an encoded packet: DEFLATE-compressed.
+pub(crate) const FLAG_DEFLATE: u8 = 3;
 
-        use super::compression_config::CompressionMode;
+/// Packet encoder: compresses payloads with the codec negotiated for each
+/// peer, behind a one-byte flag saying which codec was used.
+pub struct Encoder {
+    compression_mode: CompressionMode,
+    result: Vec<u8>,
+    /// `None` while training or in `Deflate` mode
+    #[cfg(feature = "zstd_support")]
+    zstd: Option<ZstdCompressor>,
+    #[cfg(feature = "zstd_support")]
+    trainer: Option<DictionaryTrainer>,
+}
 
-        #[derive(Clone)]
-        pub struct Encoder {
-            result: Vec<u8>,
-            encoder: EncoderType,
+impl Encoder {
+    /// Creates an encoder for the given compression mode.
+    pub fn new(compression_mode: CompressionMode) -> Self {
+        match &compression_mode {
+            CompressionMode::Deflate(_) if !cfg!(feature = "deflate_support") => {
+                warn!("`deflate_support` is disabled: packets will be sent uncompressed");
+            }
+            CompressionMode::Deflate(_) => {}
+            _ if !cfg!(feature = "zstd_support") => {
+                warn!("`zstd_support` is disabled: packets will fall back to DEFLATE or be sent uncompressed");
+            }
+            _ => {}
         }
 
-        impl Encoder {
-            pub fn new(compression_mode: CompressionMode) -> Self {
-                let encoder = match compression_mode {
-                    CompressionMode::Training(sample_size) => {
-                        EncoderType::DictionaryTrainer(DictionaryTrainer::new(sample_size))
-                    }
-                    CompressionMode::Default(compression_level) => EncoderType::Compressor(
-                        Compressor::new(compression_level).expect("error creating Compressor"),
-                    ),
-                    CompressionMode::Dictionary(compression_level, dictionary) => EncoderType::Compressor(
-                        Compressor::with_dictionary(compression_level, &dictionary)
-                            .expect("error creating Compressor with dictionary"),
-                    ),
-                };
-
-                Self {
-                    result: Vec::new(),
-                    encoder,
+        Self {
+            #[cfg(feature = "zstd_support")]
+            zstd: match &compression_mode {
+                CompressionMode::Default(level) => Some(ZstdCompressor::new(*level, None)),
+                CompressionMode::Dictionary(level, dictionary) => {
+                    Some(ZstdCompressor::new(*level, Some(dictionary)))
                 }
-            }
-
-            pub fn encode(&mut self, payload: &[u8]) -> &[u8] {
-                match &mut self.encoder {
-                    EncoderType::DictionaryTrainer(trainer) => {
-                        trainer.record_bytes(payload);
-                        // Training mode: emit uncompressed with is_compressed=0 prefix
-                        self.result = Vec::with_capacity(1 + payload.len());
-                        self.result.push(0u8);
-                        self.result.extend_from_slice(payload);
-                        return &self.result;
-                    }
-                    EncoderType::Compressor(encoder) => {
-                        let compressed = encoder.compress(payload).expect("encode error");
-                        if compressed.len() < payload.len() {
-                            // Compression is beneficial: is_compressed=1 prefix + compressed bytes
-                            self.result = Vec::with_capacity(1 + compressed.len());
-                            self.result.push(1u8);
-                            self.result.extend_from_slice(&compressed);
-                        } else {
-                            // Compression is not beneficial: is_compressed=0 prefix + original bytes
-                            self.result = Vec::with_capacity(1 + payload.len());
-                            self.result.push(0u8);
-                            self.result.extend_from_slice(payload);
-                        }
-                        return &self.result;
-                    }
+                CompressionMode::Training(_) | CompressionMode::Deflate(_) => None,
+            },
+            #[cfg(feature = "zstd_support")]
+            trainer: match &compression_mode {
+                CompressionMode::Training(sample_size) => {
+                    Some(DictionaryTrainer::new(*sample_size))
                 }
-            }
+                _ => None,
+            },
+            compression_mode,
+            result: Vec::new(),
         }
+    }
 
-        #[derive(Clone)]
-        pub enum EncoderType {
-            Compressor(Compressor<'static>),
-            DictionaryTrainer(DictionaryTrainer),
+    /// Encodes `payload` with `codec`, which should come from
+    /// `CompressionMode::negotiate` for the receiving peer. Falls back to
+    /// sending the payload uncompressed when compressing doesn't shrink it.
+    pub fn encode(&mut self, payload: &[u8], codec: PacketCodec) -> &[u8] {
+        #[cfg(feature = "zstd_support")]
+        if let Some(trainer) = &mut self.trainer {
+            // Training mode: emit uncompressed
+            trainer.record_bytes(payload);
+            return self.write(FLAG_RAW, payload);
         }
 
-        #[derive(Clone)]
-        pub struct DictionaryTrainer {
-            sample_data: Vec<u8>,
-            sample_sizes: Vec<usize>,
-            next_alert_size: usize,
-            target_sample_size: usize,
-            training_complete: bool,
+        let compressed = match codec {
+            PacketCodec::Uncompressed => None,
+            PacketCodec::Zstd => self.compress_zstd(payload, false),
+            PacketCodec::ZstdDictionary => self.compress_zstd(payload, true),
+            PacketCodec::Deflate => self.compress_deflate(payload),
+        };
+        match compressed {
+            Some((flag, compressed)) if compressed.len() < payload.len() => {
+                self.write(flag, &compressed)
+            }
+            // Compression is not beneficial: send the original bytes
+            _ => self.write(FLAG_RAW, payload),
         }
+    }
 
-        impl DictionaryTrainer {
-            /// `target_sample_size` here describes the number of samples (packets) to
-            /// train on. Obviously, the more samples trained on, the better
-            /// theoretical compression.
-            pub fn new(target_sample_size: usize) -> Self {
-                Self {
-                    target_sample_size,
-                    sample_data: Vec::new(),
-                    sample_sizes: Vec::new(),
-                    next_alert_size: 0,
-                    training_complete: false,
-                }
-            }
+    fn write(&mut self, flag: u8, bytes: &[u8]) -> &[u8] {
+        self.result = Vec::with_capacity(1 + bytes.len());
+        self.result.push(flag);
+        self.result.extend_from_slice(bytes);
+        &self.result
+    }
 
-            pub fn record_bytes(&mut self, bytes: &[u8]) {
-                if self.training_complete {
-                    return;
-                }
+    #[cfg(feature = "zstd_support")]
+    fn compress_zstd(&mut self, payload: &[u8], use_dictionary: bool) -> Option<(u8, Vec<u8>)> {
+        let (used_dictionary, compressed) = self.zstd.as_mut()?.compress(payload, use_dictionary);
+        let flag = if used_dictionary {
+            FLAG_ZSTD_DICTIONARY
+        } else {
+            FLAG_ZSTD
+        };
+        Some((flag, compressed))
+    }
 
-                self.sample_data.extend_from_slice(bytes);
-                self.sample_sizes.push(bytes.len());
+    #[cfg(not(feature = "zstd_support"))]
+    fn compress_zstd(&mut self, _payload: &[u8], _use_dictionary: bool) -> Option<(u8, Vec<u8>)> {
+        None
+    }
 
-                let current_sample_size = self.sample_sizes.len();
+    #[cfg(feature = "deflate_support")]
+    fn compress_deflate(&mut self, payload: &[u8]) -> Option<(u8, Vec<u8>)> {
+        let level = self.compression_mode.deflate_level();
+        Some((FLAG_DEFLATE, deflate_codec::compress(payload, level)))
+    }
 
-                if current_sample_size >= self.next_alert_size {
-                    let percent =
-                        ((self.next_alert_size as f32) / (self.target_sample_size as f32)) * 100.0;
-                    info!("Dictionary training: {}% complete", percent);
+    #[cfg(not(feature = "deflate_support"))]
+    fn compress_deflate(&mut self, _payload: &[u8]) -> Option<(u8, Vec<u8>)> {
+        None
+    }
 
-                    self.next_alert_size += self.target_sample_size / 20;
-                }
+    /// Takes the dictionary trained in `Training` mode, once enough
+    /// samples have been recorded. Always `None` without `zstd_support`.
+    pub fn take_trained_dictionary(&mut self) -> Option<Vec<u8>> {
+        #[cfg(feature = "zstd_support")]
+        {
+            self.trainer.as_mut()?.take_dictionary()
+        }
+        #[cfg(not(feature = "zstd_support"))]
+        {
+            None
+        }
+    }
+
+    /// Fraction of the target sample count recorded so far, in `Training`
+    /// mode. Always `None` without `zstd_support`.
+    pub fn training_progress(&self) -> Option<f32> {
+        #[cfg(feature = "zstd_support")]
+        {
+            self.trainer.as_ref().map(DictionaryTrainer::progress)
+        }
+        #[cfg(not(feature = "zstd_support"))]
+        {
+            None
+        }
+    }
+}
 
-                if current_sample_size >= self.target_sample_size {
-                    info!("Dictionary training complete!");
-                    info!(
-                        "Samples: {} ({} KB)",
-                        self.sample_sizes.len(),
-                        self.sample_data.len()
-                    );
-                    info!("Dictionary processing sample data...");
+impl Clone for Encoder {
+    fn clone(&self) -> Self {
+        // zstd contexts can't be cloned; build fresh ones for the same mode
+        #[allow(unused_mut)]
+        let mut encoder = Self::new(self.compression_mode.clone());
+        encoder.result = self.result.clone();
+        #[cfg(feature = "zstd_support")]
+        {
+            encoder.trainer = self.trainer.clone();
+        }
+        encoder
+    }
+}
 
-                    // We have enough sample data to train the dictionary!
-                    let target_dict_size = self.sample_data.len() / 100;
-                    let dictionary =
-                        from_continuous(&self.sample_data, &self.sample_sizes, target_dict_size)
-                            .expect("Error while training dictionary");
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::connection::decoder::Decoder;
 
-                    // Now need to ... write it to a file I guess
-                    fs::write("dictionary.txt", dictionary)
-                        .expect("Error while writing dictionary to file");
+    fn sample(i: usize) -> Vec<u8> {
+        format!(
+            "position x={} y={} velocity=0.5 health=100 name=player_{}",
+            i,
+            i * 2,
+            i % 7
+        )
+        .into_bytes()
+    }
 
-                    info!("Dictionary written to `dictionary.txt`!");
+    #[test]
+    fn uncompressed_round_trips() {
+        let mode = CompressionMode::Default(3);
+        let mut encoder = Encoder::new(mode.clone());
+        let mut decoder = Decoder::new(mode);
 
-                    self.training_complete = true;
-                }
-            }
-        }
+        let payload = sample(1);
+        let encoded = encoder.encode(&payload, PacketCodec::Uncompressed).to_vec();
+        assert_eq!(encoded[0], FLAG_RAW);
+        assert_eq!(decoder.decode(&encoded), payload.as_slice());
     }
-    else
-    {
-        use super::compression_config::CompressionMode;
 
-        /// Packet encoder (no-op variant: passes payload through unchanged).
-        #[derive(Clone)]
-        pub struct Encoder {
-            result: Vec<u8>
-        }
+    #[test]
+    fn unknown_flag_is_dropped() {
+        let mut decoder = Decoder::new(CompressionMode::Default(3));
+        assert!(decoder.decode(&[200, 1, 2, 3]).is_empty());
+        assert!(decoder.decode(&[]).is_empty());
+    }
 
-        impl Encoder {
-            /// Creates a no-op encoder (compression mode is ignored in this build variant).
-            pub fn new(_: CompressionMode) -> Self {
-                Self {
-                    result: Vec::new(),
-                }
-            }
+    #[cfg(feature = "deflate_support")]
+    #[test]
+    fn deflate_round_trips() {
+        // a zstd receiver decodes DEFLATE too: the flag says which codec
+        let mut encoder = Encoder::new(CompressionMode::Deflate(6));
+        let mut decoder = Decoder::new(CompressionMode::Default(3));
 
-            /// Returns the payload unchanged (no-op compression).
-            pub fn encode(&mut self, payload: &[u8]) -> &[u8] {
-                self.result = payload.to_vec();
-                &self.result
-            }
+        let payload = sample(5_000).repeat(4);
+        let encoded = encoder.encode(&payload, PacketCodec::Deflate).to_vec();
+        assert_eq!(encoded[0], FLAG_DEFLATE);
+        assert!(encoded.len() < payload.len());
+        assert_eq!(decoder.decode(&encoded), payload.as_slice());
+
+        // corrupt data is dropped
+        assert!(decoder.decode(&[FLAG_DEFLATE, 0xff, 0xff]).is_empty());
+    }
+
+    #[cfg(feature = "zstd_support")]
+    fn trained_dictionary() -> Vec<u8> {
+        let mut encoder = Encoder::new(CompressionMode::Training(2_000));
+        assert_eq!(encoder.training_progress(), Some(0.0));
+        for i in 0..2_000 {
+            let encoded = encoder.encode(&sample(i), PacketCodec::Uncompressed);
+            assert_eq!(encoded[0], FLAG_RAW);
         }
+        assert_eq!(encoder.training_progress(), Some(1.0));
+        let dictionary = encoder
+            .take_trained_dictionary()
+            .expect("training complete");
+        assert!(encoder.take_trained_dictionary().is_none());
+        dictionary
+    }
+
+    #[cfg(feature = "zstd_support")]
+    #[test]
+    fn trained_dictionary_round_trips() {
+        let dictionary = trained_dictionary();
+        let mode = CompressionMode::Dictionary(3, dictionary);
+        let mut encoder = Encoder::new(mode.clone());
+        let mut decoder = Decoder::new(mode);
+
+        let payload = sample(5_000);
+        let encoded = encoder
+            .encode(&payload, PacketCodec::ZstdDictionary)
+            .to_vec();
+        assert_eq!(encoded[0], FLAG_ZSTD_DICTIONARY);
+        assert_eq!(decoder.decode(&encoded), payload.as_slice());
+    }
+
+    #[cfg(feature = "zstd_support")]
+    #[test]
+    fn peer_without_dictionary_reads_fallback_packets() {
+        let mode = CompressionMode::Dictionary(3, trained_dictionary());
+        let mut encoder = Encoder::new(mode);
+        let mut decoder = Decoder::new(CompressionMode::Default(3));
+
+        let payload = sample(5_000).repeat(4);
+        let encoded = encoder.encode(&payload, PacketCodec::Zstd).to_vec();
+        assert_eq!(encoded[0], FLAG_ZSTD);
+        assert_eq!(decoder.decode(&encoded), payload.as_slice());
+
+        // A dictionary packet it can't read is dropped, not garbage
+        let encoded = Encoder::new(CompressionMode::Dictionary(3, trained_dictionary()))
+            .encode(&payload, PacketCodec::ZstdDictionary)
+            .to_vec();
+        assert!(decoder.decode(&encoded).is_empty());
     }
 }
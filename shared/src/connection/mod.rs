@@ -2,14 +2,20 @@ pub mod ack_manager;
 pub mod bandwidth;
 pub mod bandwidth_accumulator;
 pub mod bandwidth_monitor;
+pub mod bandwidth_profiler;
 pub mod base_connection;
+pub mod channel_stats;
 pub mod compression_config;
+pub mod congestion_controller;
 pub mod connection_config;
 pub mod connection_stats;
 pub mod decoder;
+#[cfg(feature = "deflate_support")]
+mod deflate_codec;
 pub mod encoder;
 pub mod entity_priority;
 pub mod loss_monitor;
+pub mod packet_metrics;
 pub mod packet_notifiable;
 pub mod packet_type;
 pub mod ping_store;
@@ -18,3 +24,5 @@ pub mod priority_state;
 mod priority_accumulator_integration_tests;
 pub mod sequence_buffer;
 pub mod standard_header;
+#[cfg(feature = "zstd_support")]
+mod zstd_codec;
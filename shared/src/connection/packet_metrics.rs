@@ -0,0 +1,28 @@
+use crate::HostType;
+
+/// Records the wire size of a packet this host sent, after compression, in
+/// the `naia_packet_size_bytes` histogram. A no-op without the
+/// `observability` feature.
+pub fn record_packet_sent(host_type: HostType, bytes: usize) {
+    record_packet_size(host_type, "sent", bytes);
+}
+
+/// Records the wire size of a packet this host received, before
+/// decompression, in the `naia_packet_size_bytes` histogram. A no-op without
+/// the `observability` feature.
+pub fn record_packet_received(host_type: HostType, bytes: usize) {
+    record_packet_size(host_type, "received", bytes);
+}
+
+#[cfg_attr(not(feature = "observability"), allow(unused_variables))]
+fn record_packet_size(host_type: HostType, direction: &'static str, bytes: usize) {
+    #[cfg(feature = "observability")]
+    {
+        let host = match host_type {
+            HostType::Server => "server",
+            HostType::Client => "client",
+        };
+        metrics::histogram!(crate::PACKET_SIZE_BYTES, "host" => host, "direction" => direction)
+            .record(bytes as f64);
+    }
+}
@@ -41,7 +41,10 @@ fn advance(t: &Instant, ms: u32) -> Instant {
 #[test]
 fn a_bdd_1_bandwidth_cap_bounds_tick_bytes() {
     init_clock();
-    let cfg = BandwidthConfig { target_bytes_per_sec: 64_000 };
+    let cfg = BandwidthConfig {
+        target_bytes_per_sec: 64_000,
+        ..Default::default()
+    };
     let mut acc = BandwidthAccumulator::new(&cfg);
 
     // Initial warm-up: baseline tick accrues nothing.
@@ -88,7 +91,10 @@ fn a_bdd_1_bandwidth_cap_bounds_tick_bytes() {
 #[test]
 fn a_bdd_2_queue_drains_over_ticks() {
     init_clock();
-    let cfg = BandwidthConfig { target_bytes_per_sec: 64_000 };
+    let cfg = BandwidthConfig {
+        target_bytes_per_sec: 64_000,
+        ..Default::default()
+    };
     let mut acc = BandwidthAccumulator::new(&cfg);
     const MTU: u32 = 430;
 
@@ -0,0 +1,170 @@
+//! zstd compression (`zstd_support`), with optional dictionaries and
+//! dictionary training.
+
+use log::{info, warn};
+
+use naia_serde::MTU_SIZE_BYTES;
+use parking_lot::Mutex;
+use zstd::{
+    bulk::{Compressor, Decompressor},
+    dict::from_continuous,
+};
+
+// zstd contexts are `Send` but not `Sync`; the mutexes make encoders and
+// decoders `Sync` so they can live in Bevy resources. They're only accessed
+// through `&mut self`, so they're never locked.
+
+pub(crate) struct ZstdCompressor {
+    compressor: Mutex<Compressor<'static>>,
+    dictionary_compressor: Option<Mutex<Compressor<'static>>>,
+}
+
+impl ZstdCompressor {
+    pub fn new(level: i32, dictionary: Option<&[u8]>) -> Self {
+        Self {
+            compressor: Mutex::new(Compressor::new(level).expect("error creating Compressor")),
+            dictionary_compressor: dictionary.map(|dictionary| {
+                Mutex::new(
+                    Compressor::with_dictionary(level, dictionary)
+                        .expect("error creating Compressor with dictionary"),
+                )
+            }),
+        }
+    }
+
+    /// Compresses `payload`, with the dictionary if `use_dictionary` is set
+    /// and one is configured. Returns whether the dictionary was used.
+    pub fn compress(&mut self, payload: &[u8], use_dictionary: bool) -> (bool, Vec<u8>) {
+        match &mut self.dictionary_compressor {
+            Some(dictionary_compressor) if use_dictionary => (
+                true,
+                dictionary_compressor
+                    .get_mut()
+                    .compress(payload)
+                    .expect("encode error"),
+            ),
+            _ => (
+                false,
+                self.compressor
+                    .get_mut()
+                    .compress(payload)
+                    .expect("encode error"),
+            ),
+        }
+    }
+}
+
+pub(crate) struct ZstdDecompressor {
+    decompressor: Mutex<Decompressor<'static>>,
+    dictionary_decompressor: Option<Mutex<Decompressor<'static>>>,
+}
+
+impl ZstdDecompressor {
+    pub fn new(dictionary: Option<&[u8]>) -> Self {
+        Self {
+            decompressor: Mutex::new(Decompressor::new().expect("error creating Decompressor")),
+            dictionary_decompressor: dictionary.map(|dictionary| {
+                Mutex::new(
+                    Decompressor::with_dictionary(dictionary).expect("error creating Decompressor"),
+                )
+            }),
+        }
+    }
+
+    /// Decompresses `data`, or returns `None` if it was compressed with a
+    /// dictionary this side doesn't hold, or is corrupt.
+    pub fn decompress(&mut self, data: &[u8], use_dictionary: bool) -> Option<Vec<u8>> {
+        let decompressor = if use_dictionary {
+            let Some(decompressor) = &mut self.dictionary_decompressor else {
+                warn!("Dropping packet compressed with an unknown dictionary");
+                return None;
+            };
+            decompressor.get_mut()
+        } else {
+            self.decompressor.get_mut()
+        };
+
+        // `upper_bound` needs zstd's `experimental` feature; without it,
+        // fall back to the largest packet a peer can send
+        let capacity = Decompressor::<'static>::upper_bound(data).unwrap_or(MTU_SIZE_BYTES);
+        match decompressor.decompress(data, capacity) {
+            Ok(result) => Some(result),
+            Err(error) => {
+                warn!("Dropping packet that failed to decompress: {}", error);
+                None
+            }
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct DictionaryTrainer {
+    sample_data: Vec<u8>,
+    sample_sizes: Vec<usize>,
+    target_sample_size: usize,
+    training_complete: bool,
+    dictionary: Option<Vec<u8>>,
+}
+
+impl DictionaryTrainer {
+    /// `target_sample_size` here describes the number of samples (packets) to
+    /// train on. Obviously, the more samples trained on, the better
+    /// theoretical compression.
+    pub fn new(target_sample_size: usize) -> Self {
+        Self {
+            target_sample_size,
+            sample_data: Vec::new(),
+            sample_sizes: Vec::new(),
+            training_complete: false,
+            dictionary: None,
+        }
+    }
+
+    pub fn record_bytes(&mut self, bytes: &[u8]) {
+        if self.training_complete {
+            return;
+        }
+
+        self.sample_data.extend_from_slice(bytes);
+        self.sample_sizes.push(bytes.len());
+
+        if self.sample_sizes.len() < self.target_sample_size {
+            return;
+        }
+
+        // We have enough sample data to train the dictionary!
+        let target_dict_size = self.sample_data.len() / 100;
+        match from_continuous(&self.sample_data, &self.sample_sizes, target_dict_size) {
+            Ok(dictionary) => {
+                info!(
+                    "Dictionary training complete: {} samples ({} bytes) -> {} byte dictionary",
+                    self.sample_sizes.len(),
+                    self.sample_data.len(),
+                    dictionary.len()
+                );
+                self.dictionary = Some(dictionary);
+            }
+            Err(error) => {
+                warn!("Dictionary training failed: {}", error);
+            }
+        }
+        self.training_complete = true;
+        self.sample_data = Vec::new();
+        self.sample_sizes = Vec::new();
+    }
+
+    /// Fraction of the target sample count recorded so far, from `0.0`
+    /// to `1.0`.
+    pub fn progress(&self) -> f32 {
+        if self.training_complete || self.target_sample_size == 0 {
+            return 1.0;
+        }
+        self.sample_sizes.len() as f32 / self.target_sample_size as f32
+    }
+
+    /// Takes the trained dictionary, if training has completed and it
+    /// hasn't been taken yet.
+    pub fn take_dictionary(&mut self) -> Option<Vec<u8>> {
+        self.dictionary.take()
+    }
+}
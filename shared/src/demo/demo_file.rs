@@ -0,0 +1,287 @@
+use std::{error::Error, fmt, time::Duration};
+
+use naia_serde::{
+    BitReader, BitWrite, FileBitWriter, Serde, SerdeErr, UnsignedInteger, UnsignedVariableInteger,
+};
+
+use crate::{
+    ChannelKind, ComponentKind, ComponentUpdate, LocalEntityAndGlobalEntityConverter,
+    MessageContainer, Protocol, ProtocolId, Replicate, Tick,
+};
+
+const MAGIC: [u8; 4] = *b"NDMO";
+const FORMAT_VERSION: u8 = 1;
+
+pub(crate) const OP_SPAWN: u8 = 0;
+pub(crate) const OP_DESPAWN: u8 = 1;
+pub(crate) const OP_INSERT: u8 = 2;
+pub(crate) const OP_UPDATE: u8 = 3;
+pub(crate) const OP_REMOVE: u8 = 4;
+pub(crate) const OP_MESSAGE: u8 = 5;
+
+/// Errors returned when loading a recorded demo.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DemoError {
+    /// The bytes don't start with a naia demo header.
+    NotADemo,
+    /// The demo was written with a format version this build can't read.
+    UnsupportedVersion(u8),
+    /// The demo was recorded under a different protocol.
+    ProtocolMismatch {
+        /// The protocol ID of the playback side.
+        expected: ProtocolId,
+        /// The protocol ID recorded in the demo.
+        found: ProtocolId,
+    },
+    /// The demo is truncated or otherwise corrupt.
+    Malformed,
+}
+
+impl fmt::Display for DemoError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            DemoError::NotADemo => write!(f, "not a naia demo"),
+            DemoError::UnsupportedVersion(version) => {
+                write!(f, "unsupported demo version: {}", version)
+            }
+            DemoError::ProtocolMismatch { expected, found } => write!(
+                f,
+                "demo protocol mismatch: expected {}, found {}",
+                expected, found
+            ),
+            DemoError::Malformed => write!(f, "malformed demo"),
+        }
+    }
+}
+
+impl Error for DemoError {}
+
+impl From<SerdeErr> for DemoError {
+    fn from(_: SerdeErr) -> Self {
+        DemoError::Malformed
+    }
+}
+
+/// A recorded demo: the replication stream one user received, split into
+/// one frame per server tick.
+pub struct DemoFile {
+    protocol_id: ProtocolId,
+    tick_interval: Duration,
+    frames: Vec<DemoFrame>,
+}
+
+impl DemoFile {
+    pub(crate) fn new(protocol_id: ProtocolId, tick_interval: Duration) -> Self {
+        Self {
+            protocol_id,
+            tick_interval,
+            frames: Vec::new(),
+        }
+    }
+
+    /// Parses a demo written by [`DemoRecorder::finish`], checking that it
+    /// was recorded under `protocol_id`.
+    ///
+    /// [`DemoRecorder::finish`]: crate::DemoRecorder::finish
+    pub fn read(bytes: &[u8], protocol_id: ProtocolId) -> Result<Self, DemoError> {
+        let mut reader = BitReader::new(bytes);
+        for expected in MAGIC {
+            let byte = u8::de(&mut reader).map_err(|_| DemoError::NotADemo)?;
+            if byte != expected {
+                return Err(DemoError::NotADemo);
+            }
+        }
+        let version = u8::de(&mut reader)?;
+        if version != FORMAT_VERSION {
+            return Err(DemoError::UnsupportedVersion(version));
+        }
+        let found = ProtocolId::de(&mut reader)?;
+        if found != protocol_id {
+            return Err(DemoError::ProtocolMismatch {
+                expected: protocol_id,
+                found,
+            });
+        }
+        let micros = UnsignedVariableInteger::<7>::de(&mut reader)?.get() as u64;
+        let mut demo = Self::new(protocol_id, Duration::from_micros(micros));
+        while bool::de(&mut reader)? {
+            let tick = Tick::de(&mut reader)?;
+            let bytes = Vec::<u8>::de(&mut reader)?;
+            demo.frames.push(DemoFrame { tick, bytes });
+        }
+        Ok(demo)
+    }
+
+    /// Serializes the demo into the bytes [`DemoFile::read`] accepts.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut writer = FileBitWriter::new();
+        for byte in MAGIC {
+            byte.ser(&mut writer);
+        }
+        FORMAT_VERSION.ser(&mut writer);
+        self.protocol_id.ser(&mut writer);
+        UnsignedVariableInteger::<7>::new(self.tick_interval.as_micros() as u64).ser(&mut writer);
+        for frame in &self.frames {
+            true.ser(&mut writer);
+            frame.tick.ser(&mut writer);
+            frame.bytes.ser(&mut writer);
+        }
+        false.ser(&mut writer);
+        writer.to_vec()
+    }
+
+    /// The server's tick interval while the demo was recorded.
+    pub fn tick_interval(&self) -> Duration {
+        self.tick_interval
+    }
+
+    /// The recorded frames, in the order they were received.
+    pub fn frames(&self) -> &[DemoFrame] {
+        &self.frames
+    }
+
+    pub(crate) fn push_frame(&mut self, tick: Tick, bytes: Vec<u8>) {
+        self.frames.push(DemoFrame { tick, bytes });
+    }
+}
+
+/// Everything one user received during a single server tick.
+pub struct DemoFrame {
+    tick: Tick,
+    bytes: Vec<u8>,
+}
+
+impl DemoFrame {
+    /// The server tick this frame was received on.
+    pub fn tick(&self) -> Tick {
+        self.tick
+    }
+
+    /// Returns a reader over this frame's operations.
+    pub fn reader(&self) -> DemoFrameReader<'_> {
+        DemoFrameReader {
+            reader: BitReader::new(&self.bytes),
+        }
+    }
+}
+
+/// One replication event inside a [`DemoFrame`]. Entities are identified by
+/// the id the recorder gave them, which is reused after a despawn.
+pub enum DemoOp {
+    /// An entity came into scope.
+    Spawn(u16),
+    /// An entity left scope or was despawned.
+    Despawn(u16),
+    /// A component was inserted, with its full state.
+    Insert(u16, Box<dyn Replicate>),
+    /// Some fields of a component changed.
+    Update(u16, ComponentUpdate),
+    /// A component was removed.
+    Remove(u16, ComponentKind),
+    /// A message was received.
+    Message(ChannelKind, MessageContainer),
+}
+
+/// Reads the operations of a [`DemoFrame`] one at a time.
+///
+/// Operations must be read in order: a component relation can only resolve
+/// to an entity whose [`DemoOp::Spawn`] has already been applied, so
+/// `converter` is passed on each call.
+pub struct DemoFrameReader<'a> {
+    reader: BitReader<'a>,
+}
+
+impl DemoFrameReader<'_> {
+    /// Reads the next operation, or `None` at the end of the frame.
+    /// Relations are resolved through `converter`, which maps each recorded
+    /// entity id (as a [`RemoteEntity`]) to its playback entity.
+    ///
+    /// [`RemoteEntity`]: crate::RemoteEntity
+    pub fn next_op(
+        &mut self,
+        protocol: &Protocol,
+        converter: &dyn LocalEntityAndGlobalEntityConverter,
+    ) -> Result<Option<DemoOp>, SerdeErr> {
+        let reader = &mut self.reader;
+        if !bool::de(reader)? {
+            return Ok(None);
+        }
+        let op = match UnsignedInteger::<3>::de(reader)?.get() as u8 {
+            OP_SPAWN => DemoOp::Spawn(read_id(reader)?),
+            OP_DESPAWN => DemoOp::Despawn(read_id(reader)?),
+            OP_INSERT => {
+                let id = read_id(reader)?;
+                DemoOp::Insert(id, protocol.component_kinds.read(reader, converter)?)
+            }
+            OP_UPDATE => {
+                let id = read_id(reader)?;
+                DemoOp::Update(id, protocol.component_kinds.read_create_update(reader)?)
+            }
+            OP_REMOVE => {
+                let id = read_id(reader)?;
+                DemoOp::Remove(id, ComponentKind::de(&protocol.component_kinds, reader)?)
+            }
+            OP_MESSAGE => {
+                let channel_kind = ChannelKind::de(&protocol.channel_kinds, reader)?;
+                let message = protocol.message_kinds.read(reader, converter)?;
+                DemoOp::Message(channel_kind, message)
+            }
+            _ => return Err(SerdeErr),
+        };
+        Ok(Some(op))
+    }
+}
+
+/// Writes the tag that starts an operation, followed by its entity id if it
+/// has one.
+pub(crate) fn write_op(writer: &mut dyn BitWrite, tag: u8, id: Option<u16>) {
+    true.ser(writer);
+    UnsignedInteger::<3>::new(tag).ser(writer);
+    if let Some(id) = id {
+        UnsignedVariableInteger::<7>::new(id).ser(writer);
+    }
+}
+
+/// Writes the marker that ends a frame's operations.
+pub(crate) fn write_end(writer: &mut dyn BitWrite) {
+    false.ser(writer);
+}
+
+fn read_id(reader: &mut BitReader) -> Result<u16, SerdeErr> {
+    u16::try_from(UnsignedVariableInteger::<7>::de(reader)?.get()).map_err(|_| SerdeErr)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn header_round_trips() {
+        let mut demo = DemoFile::new(ProtocolId::new(7), Duration::from_millis(50));
+        demo.push_frame(3, vec![1, 2, 3]);
+        let bytes = demo.to_bytes();
+
+        let read = DemoFile::read(&bytes, ProtocolId::new(7)).expect("demo reads");
+        assert_eq!(read.tick_interval(), Duration::from_millis(50));
+        assert_eq!(read.frames().len(), 1);
+        assert_eq!(read.frames()[0].tick(), 3);
+    }
+
+    #[test]
+    fn read_rejects_foreign_bytes() {
+        let demo = DemoFile::new(ProtocolId::new(7), Duration::from_millis(50));
+        let bytes = demo.to_bytes();
+
+        assert_eq!(
+            DemoFile::read(&bytes, ProtocolId::new(8)).err(),
+            Some(DemoError::ProtocolMismatch {
+                expected: ProtocolId::new(8),
+                found: ProtocolId::new(7),
+            })
+        );
+        assert_eq!(
+            DemoFile::read(b"not a demo", ProtocolId::new(7)).err(),
+            Some(DemoError::NotADemo)
+        );
+    }
+}
@@ -0,0 +1,309 @@
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    hash::Hash,
+    time::Duration,
+};
+
+use log::warn;
+use naia_serde::FileBitWriter;
+
+use crate::{
+    demo::demo_file::{
+        write_end, write_op, DemoFile, OP_DESPAWN, OP_INSERT, OP_MESSAGE, OP_REMOVE, OP_SPAWN,
+        OP_UPDATE,
+    },
+    BigMapKey, ChannelKind, ChannelKinds, ComponentKind, ComponentKinds, DiffMask,
+    EntityDoesNotExistError, GlobalEntity, HostEntity, LocalEntityAndGlobalEntityConverter,
+    LocalEntityAndGlobalEntityConverterMut, MessageContainer, MessageKinds, OwnedLocalEntity,
+    ProtocolId, RemoteEntity, Replicate, Tick, WorldRefType,
+};
+
+/// Records the replication stream one user receives into a [`DemoFile`].
+///
+/// The recorder works from world state rather than packets: each
+/// [`record_tick`](Self::record_tick) compares the entities and components a
+/// user can see against what was recorded before and writes the spawns,
+/// despawns, inserts, per-field updates and removes in between. Messages
+/// handed to [`record_message`](Self::record_message) are written into the
+/// next frame.
+pub struct DemoRecorder {
+    demo: DemoFile,
+    frame: Option<(Tick, FileBitWriter)>,
+    ids: HashMap<GlobalEntity, u16>,
+    free_ids: VecDeque<u16>,
+    next_id: u32,
+    entities: HashMap<GlobalEntity, HashMap<ComponentKind, RecordedComponent>>,
+    pending_messages: Vec<(ChannelKind, MessageContainer)>,
+}
+
+/// The last recorded state of a component: its full encoding, and the
+/// encoding of each field on its own, used to build update masks.
+struct RecordedComponent {
+    full: Vec<u8>,
+    fields: Vec<Vec<u8>>,
+}
+
+impl DemoRecorder {
+    /// Creates a recorder for a server running `protocol_id` at `tick_interval`.
+    pub fn new(protocol_id: ProtocolId, tick_interval: Duration) -> Self {
+        Self {
+            demo: DemoFile::new(protocol_id, tick_interval),
+            frame: None,
+            ids: HashMap::new(),
+            free_ids: VecDeque::new(),
+            next_id: 0,
+            entities: HashMap::new(),
+            pending_messages: Vec::new(),
+        }
+    }
+
+    /// Queues a message the user received, to be written into the next frame.
+    pub fn record_message(&mut self, channel_kind: &ChannelKind, message: MessageContainer) {
+        self.pending_messages.push((*channel_kind, message));
+    }
+
+    /// Records the state the user sees at `tick`. `entities` lists every
+    /// entity in the user's scope with the component kinds replicated for it.
+    ///
+    /// May be called several times per tick; later calls append to the same
+    /// frame.
+    pub fn record_tick<E: Copy + Eq + Hash + Send + Sync, W: WorldRefType<E>>(
+        &mut self,
+        component_kinds: &ComponentKinds,
+        message_kinds: &MessageKinds,
+        channel_kinds: &ChannelKinds,
+        tick: Tick,
+        world: &W,
+        mut entities: Vec<(GlobalEntity, E, Vec<ComponentKind>)>,
+    ) {
+        if self.frame.as_ref().map(|(frame_tick, _)| *frame_tick) != Some(tick) {
+            self.close_frame();
+            self.frame = Some((tick, FileBitWriter::new()));
+        }
+        entities.sort_by_key(|(global_entity, _, _)| global_entity.to_u64());
+
+        let Self {
+            frame,
+            ids,
+            free_ids,
+            next_id,
+            entities: recorded,
+            pending_messages,
+            ..
+        } = self;
+        let (_, writer) = frame.as_mut().expect("frame was just opened");
+
+        // Despawns first, so their ids can be reused by this tick's spawns
+        let in_scope: HashSet<GlobalEntity> = entities.iter().map(|(ge, _, _)| *ge).collect();
+        let mut gone: Vec<GlobalEntity> = recorded
+            .keys()
+            .filter(|global_entity| !in_scope.contains(global_entity))
+            .copied()
+            .collect();
+        gone.sort_by_key(|global_entity| global_entity.to_u64());
+        for global_entity in gone {
+            recorded.remove(&global_entity);
+            let id = ids
+                .remove(&global_entity)
+                .expect("recorded entity has an id");
+            write_op(writer, OP_DESPAWN, Some(id));
+            free_ids.push_back(id);
+        }
+
+        // Spawns before any component, so relations between entities that
+        // come into scope together resolve
+        for (global_entity, _, _) in &entities {
+            if ids.contains_key(global_entity) {
+                continue;
+            }
+            let id = match free_ids.pop_front() {
+                Some(id) => id,
+                None => match u16::try_from(*next_id) {
+                    Ok(id) => {
+                        *next_id += 1;
+                        id
+                    }
+                    Err(_) => {
+                        warn!("Demo recorder is out of entity ids, skipping entity");
+                        continue;
+                    }
+                },
+            };
+            ids.insert(*global_entity, id);
+            recorded.insert(*global_entity, HashMap::new());
+            write_op(writer, OP_SPAWN, Some(id));
+        }
+
+        let mut converter = DemoWriteConverter { ids };
+        for (global_entity, world_entity, mut kinds) in entities {
+            let Some(components) = recorded.get_mut(&global_entity) else {
+                continue;
+            };
+            let id = converter.ids[&global_entity];
+            kinds.retain(|kind| world.has_component_of_kind(&world_entity, kind));
+            kinds.sort_by_key(|kind| component_kinds.net_id_of(kind));
+
+            let mut removed: Vec<ComponentKind> = components
+                .keys()
+                .filter(|kind| !kinds.contains(kind))
+                .copied()
+                .collect();
+            removed.sort_by_key(|kind| component_kinds.net_id_of(kind));
+            for kind in removed {
+                components.remove(&kind);
+                write_op(writer, OP_REMOVE, Some(id));
+                kind.ser(component_kinds, writer);
+            }
+
+            for kind in kinds {
+                // Work on a detached copy: replicas a client received hold
+                // remote-owned properties, which refuse to be written
+                let component = world
+                    .component_of_kind(&world_entity, &kind)
+                    .expect("component kind was just checked")
+                    .copy_to_box();
+                let full = write_full(&*component, component_kinds, &mut converter);
+                let immutable = component_kinds.kind_is_immutable(&kind);
+                match components.get_mut(&kind) {
+                    None => {
+                        write_op(writer, OP_INSERT, Some(id));
+                        component.write(component_kinds, writer, &mut converter);
+                        let fields = if immutable {
+                            Vec::new()
+                        } else {
+                            write_fields(&*component, &mut converter)
+                        };
+                        components.insert(kind, RecordedComponent { full, fields });
+                    }
+                    Some(previous) if previous.full != full && !immutable => {
+                        let fields = write_fields(&*component, &mut converter);
+                        let mut diff_mask = DiffMask::new(component.diff_mask_size());
+                        for (index, field) in fields.iter().enumerate() {
+                            if previous.fields.get(index) != Some(field) {
+                                diff_mask.set_bit(index as u8, true);
+                            }
+                        }
+                        write_op(writer, OP_UPDATE, Some(id));
+                        kind.ser(component_kinds, writer);
+                        component.write_update(&diff_mask, writer, &mut converter);
+                        *previous = RecordedComponent { full, fields };
+                    }
+                    Some(_) => {}
+                }
+            }
+        }
+
+        for (channel_kind, message) in pending_messages.drain(..) {
+            write_op(writer, OP_MESSAGE, None);
+            channel_kind.ser(channel_kinds, writer);
+            message.write(message_kinds, writer, &mut converter);
+        }
+    }
+
+    /// Ends the recording and returns the demo, in the format
+    /// [`DemoFile::read`] accepts.
+    pub fn finish(mut self) -> Vec<u8> {
+        self.close_frame();
+        self.demo.to_bytes()
+    }
+
+    fn close_frame(&mut self) {
+        if let Some((tick, mut writer)) = self.frame.take() {
+            write_end(&mut writer);
+            self.demo.push_frame(tick, writer.to_vec());
+        }
+    }
+}
+
+fn write_full(
+    component: &dyn Replicate,
+    component_kinds: &ComponentKinds,
+    converter: &mut DemoWriteConverter,
+) -> Vec<u8> {
+    let mut writer = FileBitWriter::new();
+    component.write(component_kinds, &mut writer, converter);
+    writer.to_vec()
+}
+
+/// Encodes each field of `component` on its own, so two states can be
+/// compared field by field.
+fn write_fields(component: &dyn Replicate, converter: &mut DemoWriteConverter) -> Vec<Vec<u8>> {
+    let mask_size = component.diff_mask_size();
+    (0..u16::from(mask_size) * 8)
+        .map(|index| {
+            let mut diff_mask = DiffMask::new(mask_size);
+            diff_mask.set_bit(index as u8, true);
+            let mut writer = FileBitWriter::new();
+            component.write_update(&diff_mask, &mut writer, converter);
+            writer.to_vec()
+        })
+        .collect()
+}
+
+/// Converter used while recording: relations are written as the recorded id
+/// of their target, and relations to entities outside the user's scope are
+/// written empty.
+struct DemoWriteConverter<'a> {
+    ids: &'a HashMap<GlobalEntity, u16>,
+}
+
+impl LocalEntityAndGlobalEntityConverter for DemoWriteConverter<'_> {
+    fn global_entity_to_host_entity(
+        &self,
+        global_entity: &GlobalEntity,
+    ) -> Result<HostEntity, EntityDoesNotExistError> {
+        self.ids
+            .get(global_entity)
+            .map(|id| HostEntity::new(*id))
+            .ok_or(EntityDoesNotExistError)
+    }
+
+    fn global_entity_to_remote_entity(
+        &self,
+        _: &GlobalEntity,
+    ) -> Result<RemoteEntity, EntityDoesNotExistError> {
+        Err(EntityDoesNotExistError)
+    }
+
+    fn global_entity_to_owned_entity(
+        &self,
+        global_entity: &GlobalEntity,
+    ) -> Result<OwnedLocalEntity, EntityDoesNotExistError> {
+        self.global_entity_to_host_entity(global_entity)
+            .map(|host_entity| host_entity.copy_to_owned())
+    }
+
+    fn host_entity_to_global_entity(
+        &self,
+        _: &HostEntity,
+    ) -> Result<GlobalEntity, EntityDoesNotExistError> {
+        Err(EntityDoesNotExistError)
+    }
+
+    fn static_host_entity_to_global_entity(
+        &self,
+        _: &HostEntity,
+    ) -> Result<GlobalEntity, EntityDoesNotExistError> {
+        Err(EntityDoesNotExistError)
+    }
+
+    fn remote_entity_to_global_entity(
+        &self,
+        _: &RemoteEntity,
+    ) -> Result<GlobalEntity, EntityDoesNotExistError> {
+        Err(EntityDoesNotExistError)
+    }
+
+    fn apply_entity_redirect(&self, entity: &OwnedLocalEntity) -> OwnedLocalEntity {
+        *entity
+    }
+}
+
+impl LocalEntityAndGlobalEntityConverterMut for DemoWriteConverter<'_> {
+    fn get_or_reserve_entity(
+        &mut self,
+        global_entity: &GlobalEntity,
+    ) -> Result<OwnedLocalEntity, EntityDoesNotExistError> {
+        self.global_entity_to_owned_entity(global_entity)
+    }
+}
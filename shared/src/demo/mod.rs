@@ -0,0 +1,14 @@
+//! Demo recording: the replication stream one user received, saved so it
+//! can be played back later without a server.
+//!
+//! - `DemoRecorder`: diffs the world a user sees each tick into frames of
+//!   spawns, despawns, component inserts, updates and removes, plus the
+//!   messages the user received. Used by both the server and the client.
+//! - `DemoFile`: the recorded frames, and the reader the client's playback
+//!   driver applies them with.
+
+pub mod demo_file;
+pub mod demo_recorder;
+
+pub use demo_file::{DemoError, DemoFile, DemoFrame, DemoFrameReader, DemoOp};
+pub use demo_recorder::DemoRecorder;
@@ -0,0 +1,125 @@
+use std::{
+    io::{self, BufRead, BufReader, Write},
+    net::{TcpListener, TcpStream},
+    time::Duration,
+};
+
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// A response from a [`serve_http_endpoint`] handler.
+pub struct HttpResponse {
+    /// Status line after the version, e.g. `"200 OK"`.
+    pub status: &'static str,
+    /// Value of the `Content-Type` header.
+    pub content_type: &'static str,
+    /// Response body.
+    pub body: String,
+}
+
+impl HttpResponse {
+    /// A `200 OK` response.
+    pub fn ok(content_type: &'static str, body: String) -> Self {
+        Self {
+            status: "200 OK",
+            content_type,
+            body,
+        }
+    }
+
+    /// An empty `404 Not Found` response.
+    pub fn not_found() -> Self {
+        Self {
+            status: "404 Not Found",
+            content_type: "text/plain",
+            body: String::new(),
+        }
+    }
+}
+
+/// Answers HTTP requests on `listener` one connection at a time, for as long
+/// as the process runs.
+///
+/// `GET` requests are answered with `handler(path)`, the path stripped of any
+/// query string; anything else gets a 404. Headers and bodies are ignored.
+/// Meant for small local endpoints such as metrics and diagnostics, run on a
+/// thread of their own.
+pub fn serve_http_endpoint<F: Fn(&str) -> HttpResponse>(listener: TcpListener, handler: F) {
+    for stream in listener.incoming() {
+        let Ok(stream) = stream else {
+            continue;
+        };
+        // A client hanging up mid-request only affects that request
+        let _ = respond(stream, &handler);
+    }
+}
+
+fn respond<F: Fn(&str) -> HttpResponse>(stream: TcpStream, handler: &F) -> io::Result<()> {
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    let mut reader = BufReader::new(&stream);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+
+    // The request body, if any, is ignored along with the headers
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 || header.trim_end().is_empty() {
+            break;
+        }
+    }
+
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default();
+    let target = parts.next().unwrap_or_default();
+    let path = target.split('?').next().unwrap_or_default();
+
+    let response = if method == "GET" {
+        handler(path)
+    } else {
+        HttpResponse::not_found()
+    };
+
+    let mut stream = &stream;
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        response.status,
+        response.content_type,
+        response.body.len(),
+        response.body
+    )?;
+    stream.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{io::Read, net::SocketAddr, thread};
+
+    use super::*;
+
+    fn request(addr: SocketAddr, request_line: &str) -> String {
+        let mut stream = TcpStream::connect(addr).unwrap();
+        write!(stream, "{request_line}\r\nHost: localhost\r\n\r\n").unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        response
+    }
+
+    #[test]
+    fn routes_get_paths_to_the_handler() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        thread::spawn(move || {
+            serve_http_endpoint(listener, |path| match path {
+                "/hello" => HttpResponse::ok("text/plain", "hi".to_string()),
+                _ => HttpResponse::not_found(),
+            })
+        });
+
+        assert_eq!(
+            request(addr, "GET /hello?x=1 HTTP/1.1"),
+            "HTTP/1.1 200 OK\r\nContent-Type: text/plain\r\nContent-Length: 2\r\nConnection: close\r\n\r\nhi"
+        );
+        assert!(request(addr, "POST /hello HTTP/1.1").starts_with("HTTP/1.1 404 Not Found\r\n"));
+        assert!(request(addr, "GET /other HTTP/1.1").starts_with("HTTP/1.1 404 Not Found\r\n"));
+    }
+}
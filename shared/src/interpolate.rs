@@ -0,0 +1,35 @@
+/// A value that can be blended between two snapshots. Used by the client's
+/// snapshot interpolation, and by the server's `Historian` to sample the
+/// world between two recorded ticks.
+pub trait Interpolate: Clone {
+    /// Returns the value `fraction` of the way from `self` to `next`.
+    ///
+    /// `fraction` is in `[0.0, 1.0]` while interpolating and slightly above
+    /// `1.0` while extrapolating past the newest snapshot, so implementations
+    /// should not clamp it.
+    fn interpolate(&self, next: &Self, fraction: f32) -> Self;
+
+    /// Returns `true` if the jump from `self` to `next` is a discontinuity
+    /// (e.g. a teleport) that must not be blended. The default never snaps.
+    fn should_snap(&self, _next: &Self) -> bool {
+        false
+    }
+}
+
+impl Interpolate for f32 {
+    fn interpolate(&self, next: &Self, fraction: f32) -> Self {
+        self + (next - self) * fraction
+    }
+}
+
+impl Interpolate for f64 {
+    fn interpolate(&self, next: &Self, fraction: f32) -> Self {
+        self + (next - self) * f64::from(fraction)
+    }
+}
+
+impl<const N: usize> Interpolate for [f32; N] {
+    fn interpolate(&self, next: &Self, fraction: f32) -> Self {
+        std::array::from_fn(|i| self[i].interpolate(&next[i], fraction))
+    }
+}
@@ -24,10 +24,11 @@ cfg_if! {
 pub use naia_derive::{Channel, Message, MessageBevy, Replicate, ReplicateBevy};
 pub use naia_serde::{
     BitCounter, BitReader, BitWrite, BitWriter, ConstBitLength, FileBitWriter, OutgoingPacket, OwnedBitReader,
-    Serde, SerdeBevyClient, SerdeBevyServer, SerdeBevyShared, SerdeErr, SerdeFloatConversion,
-    SerdeIntegerConversion, SerdeInternal, SignedFloat, SignedInteger, SignedVariableFloat,
-    SignedVariableInteger, UnsignedFloat, UnsignedInteger, UnsignedVariableFloat,
-    UnsignedVariableInteger, MTU_SIZE_BITS, MTU_SIZE_BYTES,
+    NormalizedVector, Quantize, QuantizedFloat, Serde, SerdeBevyClient, SerdeBevyServer,
+    SerdeBevyShared, SerdeErr, SerdeFloatConversion, SerdeIntegerConversion, SerdeInternal,
+    SignedFloat, SignedInteger, SignedVariableFloat, SignedVariableInteger, SmallestThree,
+    UnsignedFloat, UnsignedInteger, UnsignedVariableFloat, UnsignedVariableInteger, MTU_SIZE_BITS,
+    MTU_SIZE_BYTES,
 };
 pub use naia_socket_shared::{
     generate_identity_token, link_condition_logic, IdentityToken, Instant, LinkConditionerConfig,
@@ -46,6 +47,12 @@ pub use bevy_ecs;
 ))]
 pub use naia_socket_shared::TestClock;
 
+// Re-export tracing for `naia_span!`, whose expansion in naia-server and
+// naia-client refers to it as `$crate::__tracing`.
+#[cfg(feature = "observability")]
+#[doc(hidden)]
+pub use tracing as __tracing;
+
 mod backends;
 mod bigmap;
 mod connection;
@@ -65,8 +72,16 @@ pub const SERVER_COMPONENT_INSERTS_TOTAL: &str = "naia_server_component_inserts_
 /// Observability counter name for total component removes issued by the server.
 #[cfg(feature = "observability")]
 pub const SERVER_COMPONENT_REMOVES_TOTAL: &str = "naia_server_component_removes_total";
+/// Observability histogram name for packet sizes on the wire, in bytes,
+/// labelled by `host` and `direction`.
+#[cfg(feature = "observability")]
+pub const PACKET_SIZE_BYTES: &str = "naia_packet_size_bytes";
 mod constants;
+mod demo;
 mod game_time;
+#[cfg(feature = "http_endpoint")]
+mod http_endpoint;
+mod interpolate;
 /// Standard handshake protocol module for client identification and connection timing exchange.
 pub mod handshake;
 mod key_generator;
@@ -75,6 +90,7 @@ mod named;
 mod protocol;
 mod protocol_id;
 mod sequence_list;
+mod trace_span;
 mod types;
 mod world;
 mod wrapping_number;
@@ -97,10 +113,14 @@ cfg_if! {
 pub use backends::{Timer, Timestamp};
 pub use connection::{
     ack_manager::AckManager,
-    bandwidth::BandwidthConfig,
+    bandwidth::{BandwidthConfig, CongestionControlConfig},
     bandwidth_monitor::BandwidthMonitor,
+    bandwidth_profiler::{BandwidthProfile, BandwidthProfiler, SentBits},
     base_connection::BaseConnection,
-    compression_config::{CompressionConfig, CompressionMode},
+    channel_stats::{ChannelCounters, ChannelStats, SenderStats},
+    compression_config::{
+        CompressionCapabilities, CompressionConfig, CompressionMode, DictionaryId, PacketCodec,
+    },
     connection_config::ConnectionConfig,
     connection_stats::ConnectionStats,
     decoder::Decoder,
@@ -108,6 +128,7 @@ pub use connection::{
     entity_priority::{EntityPriorityMut, EntityPriorityRef},
     loss_monitor::LossMonitor,
     priority_state::{GlobalPriorityState, OutgoingPriorityHook, UserPriorityState},
+    packet_metrics::{record_packet_received, record_packet_sent},
     packet_notifiable::PacketNotifiable,
     packet_type::PacketType,
     ping_store::{PingIndex, PingStore},
@@ -139,13 +160,18 @@ pub use messages::{
         GlobalRequestId, GlobalResponseId, Request, Response, ResponseReceiveKey, ResponseSendKey,
     },
 };
+pub use demo::{DemoError, DemoFile, DemoFrame, DemoFrameReader, DemoOp, DemoRecorder};
 pub use named::Named;
 pub use world::{
     component::{
+        collection_property::DEFAULT_COLLECTION_BACKLOG,
         component_kinds::{ComponentKind, ComponentKinds},
         entity_property::EntityProperty,
+        entity_set::EntitySet,
         property::Property,
+        property_map::{MapChange, PropertyMap},
         property_mutate::{PropertyMutate, PropertyMutator},
+        property_vec::{PropertyVec, VecChange},
         replica_ref::{
             ReplicaDynMut, ReplicaDynMutTrait, ReplicaDynMutWrapper, ReplicaDynRef,
             ReplicaDynRefTrait, ReplicaDynRefWrapper, ReplicaMutTrait, ReplicaMutWrapper,
@@ -158,6 +184,7 @@ pub use world::{
     },
     delegation::{
         auth_channel::EntityAuthAccessor,
+        authority_group_request::AuthorityGroupRequest,
         entity_auth_status::{EntityAuthStatus, HostEntityAuthStatus},
         host_auth_handler::HostAuthHandler,
     },
@@ -176,7 +203,11 @@ pub use world::{
         in_scope_entities::InScopeEntities,
     },
     host::host_world_manager::HostWorldManager,
-    remote::remote_world_manager::RemoteWorldManager,
+    remote::{
+        component_correction::ComponentCorrection,
+        remote_world_manager::RemoteWorldManager,
+        update_validator::{UpdateValidation, UpdateValidator},
+    },
     resource::{ResourceKinds, ResourceRegistry, resource_registry::ResourceAlreadyExists},
     shared_global_world_manager::SharedGlobalWorldManager,
     world_type::{WorldMutType, WorldRefType},
@@ -184,12 +215,17 @@ pub use world::{
 
 pub use bigmap::{BigMap, BigMapKey};
 pub use game_time::{GameDuration, GameInstant, GAME_TIME_LIMIT};
+#[cfg(feature = "http_endpoint")]
+pub use http_endpoint::{serve_http_endpoint, HttpResponse};
 pub use key_generator::KeyGenerator;
 pub use messages::channels::senders::request_sender::{
     LocalRequestOrResponseId, RequestOrResponse,
 };
+#[cfg(feature = "serde_messages")]
+pub use messages::serde_message::{SerdeMessage, SerdeMessageError, SerdePayload};
 pub use protocol::{Protocol, ProtocolPlugin};
 pub use protocol_id::ProtocolId;
+pub use trace_span::TraceSpan;
 pub use types::{DisconnectReason, HostType, MessageIndex, PacketIndex, ShortMessageIndex, Tick};
 pub use world::entity_command::EntityCommand;
 pub use world::publicity::Publicity;
@@ -207,6 +243,7 @@ pub use world::sync::host_entity_channel::HostEntityChannel;
 #[cfg(feature = "e2e_debug")]
 pub use world::sync::remote_entity_channel::EntityChannelState;
 pub use world::sync::remote_entity_channel::RemoteEntityChannel;
+pub use world::update::collection_acks::CollectionAcks;
 pub use world::update::component_update::{ComponentFieldUpdate, ComponentUpdate};
 pub use world::update::diff_mask::DiffMask;
 pub use world::update::global_diff_handler::GlobalDiffHandler;
@@ -219,6 +256,7 @@ pub use world::local::local_world_manager::bench_take_events_counters;
 pub use world::local::local_world_manager::cmd_emission_counters;
 #[cfg(feature = "bench_instrumentation")]
 pub use world::update::user_diff_handler::dirty_scan_counters;
+pub use interpolate::Interpolate;
 pub use wrapping_number::{
     sequence_equal_or_greater_than, sequence_equal_or_less_than, sequence_greater_than,
     sequence_less_than, wrapping_diff,
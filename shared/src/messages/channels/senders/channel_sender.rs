@@ -6,7 +6,7 @@ use crate::messages::request::GlobalRequestId;
 use crate::{
     messages::{message_container::MessageContainer, message_kinds::MessageKinds},
     types::MessageIndex,
-    LocalEntityAndGlobalEntityConverterMut, LocalResponseId,
+    LocalEntityAndGlobalEntityConverterMut, LocalResponseId, SenderStats,
 };
 
 /// Core send-side trait implemented by every channel sender variant.
@@ -22,17 +22,22 @@ pub trait ChannelSender<P>: Send + Sync {
     fn has_messages(&self) -> bool;
     /// Called when it receives acknowledgement that a Message has been received
     fn notify_message_delivered(&mut self, message_index: &MessageIndex);
+    /// Returns the state of the queue and of deliveries so far
+    fn delivery_stats(&self) -> SenderStats;
 }
 
 /// Extended sender trait for message channels that writes wire bits and supports request/response lifecycle.
 pub trait MessageChannelSender: ChannelSender<MessageContainer> {
-    /// Gets Messages from the internal buffer and writes it to the BitWriter
+    /// Gets Messages from the internal buffer and writes it to the BitWriter.
+    /// When `retransmitted_bits` is given, adds the bits of any Messages
+    /// being re-sent to it.
     fn write_messages(
         &mut self,
         message_kinds: &MessageKinds,
         converter: &mut dyn LocalEntityAndGlobalEntityConverterMut,
         writer: &mut BitWriter,
         has_written: &mut bool,
+        retransmitted_bits: Option<&mut u32>,
     ) -> Option<Vec<MessageIndex>>;
 
     /// Queues a Request to be transmitted to the remote host into an internal buffer
@@ -1,4 +1,4 @@
-use std::collections::VecDeque;
+use std::collections::{HashSet, VecDeque};
 
 use naia_serde::{BitWrite, BitWriter, Serde, UnsignedVariableInteger};
 
@@ -13,12 +13,16 @@ use crate::{
 pub struct IndexedMessageWriter;
 
 impl IndexedMessageWriter {
+    /// Writes as many of `outgoing_messages` as fit. When `retransmissions`
+    /// is given, the bits of messages in its set of re-sent indices are
+    /// added to its counter.
     pub fn write_messages(
         message_kinds: &MessageKinds,
         outgoing_messages: &mut VecDeque<(MessageIndex, MessageContainer)>,
         converter: &mut dyn LocalEntityAndGlobalEntityConverterMut,
         writer: &mut BitWriter,
         has_written: &mut bool,
+        mut retransmissions: Option<(&HashSet<MessageIndex>, &mut u32)>,
     ) -> Option<Vec<MessageIndex>> {
         let mut last_written_id: Option<MessageIndex> = None;
         let mut message_indices = Vec::new();
@@ -55,6 +59,12 @@ impl IndexedMessageWriter {
 
             *has_written = true;
 
+            if let Some((resent_messages, retransmitted_bits)) = &mut retransmissions {
+                if resent_messages.contains(message_index) {
+                    **retransmitted_bits += counter.bits_needed();
+                }
+            }
+
             // write MessageContinue bit
             true.ser(writer);
             // write data
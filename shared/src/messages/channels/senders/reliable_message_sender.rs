@@ -13,7 +13,7 @@ use crate::{
         message_kinds::MessageKinds,
     },
     types::MessageIndex,
-    LocalEntityAndGlobalEntityConverterMut, LocalResponseId, ReliableSender,
+    LocalEntityAndGlobalEntityConverterMut, LocalResponseId, ReliableSender, SenderStats,
 };
 
 // Sender
@@ -47,6 +47,10 @@ impl ChannelSender<MessageContainer> for ReliableMessageSender {
     fn notify_message_delivered(&mut self, message_index: &MessageIndex) {
         self.reliable_sender.notify_message_delivered(message_index);
     }
+
+    fn delivery_stats(&self) -> SenderStats {
+        self.reliable_sender.delivery_stats()
+    }
 }
 
 impl MessageChannelSender for ReliableMessageSender {
@@ -56,6 +60,7 @@ impl MessageChannelSender for ReliableMessageSender {
         converter: &mut dyn LocalEntityAndGlobalEntityConverterMut,
         writer: &mut BitWriter,
         has_written: &mut bool,
+        retransmitted_bits: Option<&mut u32>,
     ) -> Option<Vec<MessageIndex>> {
         IndexedMessageWriter::write_messages(
             message_kinds,
@@ -63,6 +68,7 @@ impl MessageChannelSender for ReliableMessageSender {
             converter,
             writer,
             has_written,
+            retransmitted_bits.map(|bits| (&self.reliable_sender.resent_messages, bits)),
         )
     }
 
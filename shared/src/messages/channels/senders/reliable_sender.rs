@@ -1,8 +1,14 @@
-use std::{collections::VecDeque, mem, time::Duration};
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    mem,
+    time::Duration,
+};
 
 use naia_socket_shared::Instant;
 
-use crate::{messages::channels::senders::channel_sender::ChannelSender, types::MessageIndex};
+use crate::{
+    messages::channels::senders::channel_sender::ChannelSender, types::MessageIndex, SenderStats,
+};
 
 /// Retransmit-on-timeout sender that tracks unacknowledged messages and re-queues them after an RTT-based interval.
 pub struct ReliableSender<P: Send + Sync> {
@@ -18,6 +24,13 @@ pub struct ReliableSender<P: Send + Sync> {
     min_last_sent: Option<Instant>,
     has_unsent: bool,
     max_queue_depth: Option<usize>,
+    // Messages queued again after their resend timeout, until delivered
+    pub(crate) resent_messages: HashSet<MessageIndex>,
+    // When each undelivered message was first sent, to time its ack
+    first_sent: HashMap<MessageIndex, Instant>,
+    retransmissions: u64,
+    // EWMA of first-send-to-ack time, 0.0 until the first ack
+    avg_ack_ms: f32,
 }
 
 impl<P: Send + Sync> ReliableSender<P> {
@@ -31,6 +44,10 @@ impl<P: Send + Sync> ReliableSender<P> {
             min_last_sent: None,
             has_unsent: false,
             max_queue_depth,
+            resent_messages: HashSet::new(),
+            first_sent: HashMap::new(),
+            retransmissions: 0,
+            avg_ack_ms: 0.0,
         }
     }
 
@@ -51,6 +68,12 @@ impl<P: Send + Sync> ReliableSender<P> {
         }
     }
 
+    /// Returns whether `message_index` has been queued again because it
+    /// wasn't acknowledged in time.
+    pub fn is_resend(&self, message_index: &MessageIndex) -> bool {
+        self.resent_messages.contains(message_index)
+    }
+
     /// Drains and returns all messages currently staged for transmission this tick.
     pub fn take_next_messages(&mut self) -> VecDeque<(MessageIndex, P)> {
         mem::take(&mut self.outgoing_messages)
@@ -76,6 +99,18 @@ impl<P: Send + Sync> ReliableSender<P> {
             }
 
             if found {
+                if !self.resent_messages.is_empty() {
+                    self.resent_messages.remove(message_index);
+                }
+                if let Some(first_sent) = self.first_sent.remove(message_index) {
+                    let ack_ms = first_sent.elapsed(&Instant::now()).as_secs_f32() * 1000.0;
+                    self.avg_ack_ms = if self.avg_ack_ms == 0.0 {
+                        ack_ms
+                    } else {
+                        (0.9 * self.avg_ack_ms) + (0.1 * ack_ms)
+                    };
+                }
+
                 // replace found message with nothing
                 let container = self.sending_messages.get_mut(index).unwrap();
                 let output = container.take();
@@ -127,9 +162,12 @@ impl<P: Send + Sync + Clone> ChannelSender<P> for ReliableSender<P> {
             if let Some(last_sent) = last_sent_opt {
                 if last_sent.elapsed(now) >= resend_duration {
                     should_send = true;
+                    self.resent_messages.insert(*message_index);
+                    self.retransmissions += 1;
                 }
             } else {
                 should_send = true;
+                self.first_sent.insert(*message_index, now.clone());
             }
             if should_send {
                 self.outgoing_messages
@@ -154,4 +192,13 @@ impl<P: Send + Sync + Clone> ChannelSender<P> for ReliableSender<P> {
     fn notify_message_delivered(&mut self, message_index: &MessageIndex) {
         self.deliver_message(message_index);
     }
+
+    fn delivery_stats(&self) -> SenderStats {
+        SenderStats {
+            queue_depth: self.sending_messages.len(),
+            retransmissions: self.retransmissions,
+            avg_ack_ms: self.avg_ack_ms,
+            evicted: 0,
+        }
+    }
 }
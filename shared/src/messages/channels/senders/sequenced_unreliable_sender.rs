@@ -15,7 +15,7 @@ use crate::{
         message_kinds::MessageKinds,
     },
     types::MessageIndex,
-    LocalEntityAndGlobalEntityConverterMut, LocalResponseId,
+    LocalEntityAndGlobalEntityConverterMut, LocalResponseId, SenderStats,
 };
 
 pub struct SequencedUnreliableSender {
@@ -23,6 +23,8 @@ pub struct SequencedUnreliableSender {
     outgoing_messages: VecDeque<(MessageIndex, MessageContainer)>,
     /// Next message id to use (not yet used in the buffer)
     next_send_message_index: MessageIndex,
+    /// Messages evicted from a full buffer
+    evicted: u64,
 }
 
 impl SequencedUnreliableSender {
@@ -30,6 +32,7 @@ impl SequencedUnreliableSender {
         Self {
             outgoing_messages: VecDeque::new(),
             next_send_message_index: 0,
+            evicted: 0,
         }
     }
 }
@@ -42,6 +45,7 @@ impl ChannelSender<MessageContainer> for SequencedUnreliableSender {
     fn send_message(&mut self, message: MessageContainer) -> bool {
         if self.outgoing_messages.len() >= MAX_QUEUE_DEPTH {
             self.outgoing_messages.pop_front();
+            self.evicted += 1;
         }
         self.outgoing_messages
             .push_back((self.next_send_message_index, message));
@@ -60,6 +64,14 @@ impl ChannelSender<MessageContainer> for SequencedUnreliableSender {
     fn notify_message_delivered(&mut self, _: &MessageIndex) {
         // not necessary for an unreliable channel
     }
+
+    fn delivery_stats(&self) -> SenderStats {
+        SenderStats {
+            queue_depth: self.outgoing_messages.len(),
+            evicted: self.evicted,
+            ..Default::default()
+        }
+    }
 }
 
 impl MessageChannelSender for SequencedUnreliableSender {
@@ -71,6 +83,7 @@ impl MessageChannelSender for SequencedUnreliableSender {
         converter: &mut dyn LocalEntityAndGlobalEntityConverterMut,
         writer: &mut BitWriter,
         has_written: &mut bool,
+        _: Option<&mut u32>,
     ) -> Option<Vec<MessageIndex>> {
         IndexedMessageWriter::write_messages(
             message_kinds,
@@ -78,6 +91,7 @@ impl MessageChannelSender for SequencedUnreliableSender {
             converter,
             writer,
             has_written,
+            None,
         )
     }
 
@@ -12,17 +12,20 @@ use crate::{
         message_kinds::MessageKinds,
     },
     types::MessageIndex,
-    LocalEntityAndGlobalEntityConverterMut, LocalResponseId,
+    LocalEntityAndGlobalEntityConverterMut, LocalResponseId, SenderStats,
 };
 
 pub struct UnorderedUnreliableSender {
     outgoing_messages: VecDeque<MessageContainer>,
+    // Messages evicted from a full queue
+    evicted: u64,
 }
 
 impl UnorderedUnreliableSender {
     pub fn new() -> Self {
         Self {
             outgoing_messages: VecDeque::new(),
+            evicted: 0,
         }
     }
 
@@ -52,6 +55,7 @@ impl ChannelSender<MessageContainer> for UnorderedUnreliableSender {
     fn send_message(&mut self, message: MessageContainer) -> bool {
         if self.outgoing_messages.len() >= MAX_QUEUE_DEPTH {
             self.outgoing_messages.pop_front();
+            self.evicted += 1;
         }
         self.outgoing_messages.push_back(message);
         true
@@ -68,6 +72,14 @@ impl ChannelSender<MessageContainer> for UnorderedUnreliableSender {
     fn notify_message_delivered(&mut self, _: &MessageIndex) {
         // not necessary for an unreliable channel
     }
+
+    fn delivery_stats(&self) -> SenderStats {
+        SenderStats {
+            queue_depth: self.outgoing_messages.len(),
+            evicted: self.evicted,
+            ..Default::default()
+        }
+    }
 }
 
 impl MessageChannelSender for UnorderedUnreliableSender {
@@ -77,6 +89,7 @@ impl MessageChannelSender for UnorderedUnreliableSender {
         converter: &mut dyn LocalEntityAndGlobalEntityConverterMut,
         writer: &mut BitWriter,
         has_written: &mut bool,
+        _: Option<&mut u32>,
     ) -> Option<Vec<MessageIndex>> {
         loop {
             if self.outgoing_messages.is_empty() {
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use log::error;
 use naia_serde::{BitReader, BitWrite, BitWriter, Serde, SerdeErr};
@@ -6,6 +6,10 @@ use naia_socket_shared::Instant;
 
 use crate::world::local::local_world_manager::LocalWorldManager;
 use crate::{
+    connection::{
+        bandwidth_profiler::BandwidthProfiler,
+        channel_stats::{ChannelCounters, ChannelStats},
+    },
     constants::FRAGMENTATION_LIMIT_BITS,
     messages::{
         channels::{
@@ -53,6 +57,7 @@ pub struct MessageManager {
     channel_names: HashMap<ChannelKind, String>,
     packet_to_message_map: HashMap<PacketIndex, Vec<(ChannelKind, Vec<MessageIndex>)>>,
     message_fragmenter: MessageFragmenter,
+    channel_counters: HashMap<ChannelKind, ChannelCounters>,
 }
 
 impl MessageManager {
@@ -179,6 +184,7 @@ impl MessageManager {
             channel_names,
             packet_to_message_map: HashMap::new(),
             message_fragmenter: MessageFragmenter::new(),
+            channel_counters: HashMap::new(),
         }
     }
 
@@ -226,9 +232,21 @@ impl MessageManager {
                     all_accepted = false;
                 }
             }
+            self.record_send(channel_kind, all_accepted);
             all_accepted
         } else {
-            channel.send_message(message)
+            let accepted = channel.send_message(message);
+            self.record_send(channel_kind, accepted);
+            accepted
+        }
+    }
+
+    fn record_send(&mut self, channel_kind: &ChannelKind, accepted: bool) {
+        let counters = self.channel_counters.entry(*channel_kind).or_default();
+        if accepted {
+            counters.record_sent(1);
+        } else {
+            counters.record_dropped_backpressure(1);
         }
     }
 
@@ -245,6 +263,7 @@ impl MessageManager {
             panic!("Channel not configured correctly! Cannot send message.");
         };
         channel.send_outgoing_request(message_kinds, converter, global_request_id, request);
+        self.record_send(channel_kind, true);
     }
 
     /// Queues a response keyed by `local_response_id` into the given channel's send buffer.
@@ -260,6 +279,7 @@ impl MessageManager {
             panic!("Channel not configured correctly! Cannot send message.");
         };
         channel.send_outgoing_response(message_kinds, converter, local_response_id, response);
+        self.record_send(channel_kind, true);
     }
 
     /// Advances all channel senders, re-queuing any messages due for retransmission given current RTT.
@@ -281,6 +301,8 @@ impl MessageManager {
     }
 
     /// Encodes all pending outgoing messages across all channels into `writer`, ordered by channel criticality.
+    /// Records each channel's bits into `profiler`, when given.
+    #[allow(clippy::too_many_arguments)]
     pub fn write_messages(
         &mut self,
         channel_kinds: &ChannelKinds,
@@ -289,6 +311,7 @@ impl MessageManager {
         writer: &mut BitWriter,
         packet_index: PacketIndex,
         has_written: &mut bool,
+        mut profiler: Option<&mut BandwidthProfiler>,
     ) {
         // Phase A: walk channels in descending criticality order so High
         // (e.g. TickBuffered) wins packet space over Normal wins over Low
@@ -330,6 +353,9 @@ impl MessageManager {
                 break;
             }
 
+            let start_bits = writer.bits_written();
+            let mut retransmitted_bits = 0;
+
             // reserve MessageContinue bit
             writer.reserve_bits(1);
             // write ChannelContinue bit
@@ -337,9 +363,13 @@ impl MessageManager {
             // write ChannelIndex
             channel_kind.ser(channel_kinds, writer);
             // write Messages
-            if let Some(message_indices) =
-                channel.write_messages(message_kinds, converter, writer, has_written)
-            {
+            if let Some(message_indices) = channel.write_messages(
+                message_kinds,
+                converter,
+                writer,
+                has_written,
+                profiler.is_some().then_some(&mut retransmitted_bits),
+            ) {
                 self.packet_to_message_map
                     .entry(packet_index)
                     .or_default();
@@ -350,6 +380,16 @@ impl MessageManager {
             // write MessageContinue finish bit, release
             writer.release_bits(1);
             false.ser(writer);
+
+            let channel_bits = writer.bits_written() - start_bits;
+            self.channel_counters
+                .entry(*channel_kind)
+                .or_default()
+                .record_bits_sent(channel_bits);
+            if let Some(profiler) = profiler.as_deref_mut() {
+                profiler.record_channel(channel_kind, channel_bits);
+                profiler.record_channel_retransmission(channel_kind, retransmitted_bits);
+            }
         }
 
         // write ChannelContinue finish bit, release
@@ -373,6 +413,8 @@ impl MessageManager {
                 break;
             }
 
+            let start_bits = reader.bits_read() - 1;
+
             // read channel id
             let channel_kind = ChannelKind::de(channel_kinds, reader)?;
 
@@ -383,6 +425,11 @@ impl MessageManager {
                 return Err(SerdeErr);
             };
             channel.read_messages(message_kinds, local_world_manager, reader)?;
+
+            self.channel_counters
+                .entry(channel_kind)
+                .or_default()
+                .record_bits_received(reader.bits_read() - start_bits);
         }
 
         Ok(())
@@ -401,6 +448,12 @@ impl MessageManager {
         for (channel_kind, channel) in &mut self.channel_receivers {
             let messages =
                 channel.receive_messages(message_kinds, now, entity_waitlist, entity_converter);
+            if !messages.is_empty() {
+                self.channel_counters
+                    .entry(*channel_kind)
+                    .or_default()
+                    .record_received(messages.len());
+            }
             output.push((*channel_kind, messages));
         }
         output
@@ -423,6 +476,12 @@ impl MessageManager {
             }
 
             let (requests, responses) = channel.receive_requests_and_responses();
+            if !requests.is_empty() || !responses.is_empty() {
+                self.channel_counters
+                    .entry(*channel_kind)
+                    .or_default()
+                    .record_received(requests.len() + responses.len());
+            }
             if !requests.is_empty() {
                 request_output.push((*channel_kind, requests));
             }
@@ -444,6 +503,38 @@ impl MessageManager {
         }
         (request_output, response_output)
     }
+
+    // Stats
+
+    /// Returns the traffic of every message channel this side sends or
+    /// receives on, by channel protocol name.
+    pub fn channel_stats(&self, channel_kinds: &ChannelKinds) -> BTreeMap<String, ChannelStats> {
+        let mut output = BTreeMap::new();
+        for (channel_kind, settings) in &self.channel_settings {
+            let sender = self.channel_senders.get(channel_kind);
+            if sender.is_none() && !self.channel_receivers.contains_key(channel_kind) {
+                continue;
+            }
+            let max_queue_depth = match &settings.mode {
+                ChannelMode::UnorderedReliable(reliable)
+                | ChannelMode::SequencedReliable(reliable)
+                | ChannelMode::OrderedReliable(reliable) => reliable.max_queue_depth,
+                _ => None,
+            };
+            let stats = self
+                .channel_counters
+                .get(channel_kind)
+                .cloned()
+                .unwrap_or_default()
+                .stats(sender.map(|sender| sender.delivery_stats()), max_queue_depth);
+            let name = channel_kinds
+                .channel_name(channel_kind)
+                .unwrap_or("unknown")
+                .to_string();
+            output.insert(name, stats);
+        }
+        output
+    }
 }
 
 impl PacketNotifiable for MessageManager {
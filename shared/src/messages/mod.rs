@@ -5,6 +5,8 @@ pub mod message_container;
 pub mod message_kinds;
 pub mod message_manager;
 pub mod request;
+#[cfg(feature = "serde_messages")]
+pub mod serde_message;
 
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,274 @@
+use std::{any::Any, collections::HashSet, fmt, marker::PhantomData};
+
+use bincode::Options;
+use naia_serde::{BitReader, BitWrite, Serde, SerdeErr, UnsignedVariableInteger};
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{
+    messages::{
+        message::{Message, MessageBuilder},
+        message_kinds::{MessageKind, MessageKinds},
+    },
+    named::Named,
+    world::entity::entity_converters::LocalEntityAndGlobalEntityConverterMut,
+    LocalEntityAndGlobalEntityConverter, MessageContainer, RemoteEntity,
+};
+
+/// Payload types of [`SerdeMessage`]: anything `serde` can serialize and
+/// deserialize, such as third-party timestamps and UUIDs or deep domain
+/// structs.
+pub trait SerdePayload: Serialize + DeserializeOwned + Clone + Send + Sync + 'static {}
+
+impl<T: Serialize + DeserializeOwned + Clone + Send + Sync + 'static> SerdePayload for T {}
+
+/// A message carrying any `serde` value, for types that can't derive
+/// `Message` because their fields don't implement naia's `Serde`.
+///
+/// The value is encoded once, on construction, with `bincode`'s
+/// variable-length integer encoding and written as a length-prefixed byte
+/// string. `bit_length` is exact, so oversized values are fragmented on
+/// reliable channels and counted by bandwidth accounting like any other
+/// message. Derived messages are smaller: fields are bit-packed rather than
+/// byte-aligned, and there is no length prefix.
+///
+/// Register each payload type with
+/// `protocol.add_message::<SerdeMessage<T>>()`. Its protocol name is the
+/// payload's full type path, so client and server must share the type,
+/// typically through a common protocol crate.
+pub struct SerdeMessage<T: SerdePayload> {
+    value: T,
+    encoded: Vec<u8>,
+}
+
+impl<T: SerdePayload> SerdeMessage<T> {
+    /// Encodes `value`. Fails if its `Serialize` impl does, or if it
+    /// serializes a sequence or map without a known length.
+    pub fn new(value: T) -> Result<Self, SerdeMessageError> {
+        let encoded = encoding().serialize(&value).map_err(SerdeMessageError)?;
+        Ok(Self { value, encoded })
+    }
+
+    /// The carried value.
+    pub fn get(&self) -> &T {
+        &self.value
+    }
+
+    /// Takes the carried value.
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+
+    /// Size of the encoded value in bytes, excluding the length prefix.
+    pub fn encoded_len(&self) -> usize {
+        self.encoded.len()
+    }
+
+    fn length_prefix(&self) -> UnsignedVariableInteger<9> {
+        UnsignedVariableInteger::new(self.encoded.len() as u64)
+    }
+}
+
+impl<T: SerdePayload> Clone for SerdeMessage<T> {
+    fn clone(&self) -> Self {
+        Self {
+            value: self.value.clone(),
+            encoded: self.encoded.clone(),
+        }
+    }
+}
+
+impl<T: SerdePayload + fmt::Debug> fmt::Debug for SerdeMessage<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("SerdeMessage").field(&self.value).finish()
+    }
+}
+
+impl<T: SerdePayload> Named for SerdeMessage<T> {
+    fn name(&self) -> String {
+        Self::protocol_name().to_string()
+    }
+
+    fn protocol_name() -> &'static str {
+        std::any::type_name::<Self>()
+    }
+}
+
+impl<T: SerdePayload> Message for SerdeMessage<T> {
+    fn kind(&self) -> MessageKind {
+        MessageKind::of::<Self>()
+    }
+
+    fn to_boxed_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
+
+    fn create_builder() -> Box<dyn MessageBuilder>
+    where
+        Self: Sized,
+    {
+        Box::new(SerdeMessageBuilder::<T>(PhantomData))
+    }
+
+    fn bit_length(
+        &self,
+        message_kinds: &MessageKinds,
+        _converter: &mut dyn LocalEntityAndGlobalEntityConverterMut,
+    ) -> u32 {
+        message_kinds.kind_bit_length()
+            + self.length_prefix().bit_length()
+            + (self.encoded.len() as u32) * 8
+    }
+
+    fn is_fragment(&self) -> bool {
+        false
+    }
+
+    fn is_request(&self) -> bool {
+        false
+    }
+
+    fn write(
+        &self,
+        message_kinds: &MessageKinds,
+        writer: &mut dyn BitWrite,
+        _converter: &mut dyn LocalEntityAndGlobalEntityConverterMut,
+    ) {
+        self.kind().ser(message_kinds, writer);
+        self.length_prefix().ser(writer);
+        for byte in &self.encoded {
+            writer.write_byte(*byte);
+        }
+    }
+
+    fn relations_waiting(&self) -> Option<HashSet<RemoteEntity>> {
+        None
+    }
+
+    fn relations_complete(&mut self, _converter: &dyn LocalEntityAndGlobalEntityConverter) {}
+}
+
+// Don't trust the length prefix of an incoming message with the allocation
+const MAX_PREALLOCATED_BYTES: usize = 1024;
+
+struct SerdeMessageBuilder<T: SerdePayload>(PhantomData<T>);
+
+impl<T: SerdePayload> MessageBuilder for SerdeMessageBuilder<T> {
+    fn read(
+        &self,
+        reader: &mut BitReader,
+        _converter: &dyn LocalEntityAndGlobalEntityConverter,
+    ) -> Result<MessageContainer, SerdeErr> {
+        let length = UnsignedVariableInteger::<9>::de(reader)?.get() as usize;
+        let mut encoded = Vec::with_capacity(length.min(MAX_PREALLOCATED_BYTES));
+        for _ in 0..length {
+            encoded.push(u8::de(reader)?);
+        }
+        let value = encoding().deserialize(&encoded).map_err(|_| SerdeErr)?;
+        Ok(MessageContainer::new(Box::new(SerdeMessage::<T> {
+            value,
+            encoded,
+        })))
+    }
+
+    fn box_clone(&self) -> Box<dyn MessageBuilder> {
+        Box::new(Self(PhantomData))
+    }
+}
+
+fn encoding() -> impl Options {
+    bincode::DefaultOptions::new()
+}
+
+/// Why a value couldn't be encoded into a [`SerdeMessage`].
+#[derive(Debug)]
+pub struct SerdeMessageError(bincode::Error);
+
+impl fmt::Display for SerdeMessageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "failed to encode serde message: {}", self.0)
+    }
+}
+
+impl std::error::Error for SerdeMessageError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&*self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use naia_serde::BitWriter;
+    use serde::Deserialize;
+
+    use super::*;
+    use crate::FakeEntityConverter;
+
+    #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+    struct Inventory {
+        owner: String,
+        items: BTreeMap<u32, (String, Option<i64>)>,
+    }
+
+    fn inventory() -> Inventory {
+        Inventory {
+            owner: "alice".to_string(),
+            items: BTreeMap::from([
+                (1, ("sword".to_string(), None)),
+                (2, ("potion".to_string(), Some(-3))),
+            ]),
+        }
+    }
+
+    fn message_kinds() -> MessageKinds {
+        let mut message_kinds = MessageKinds::new();
+        message_kinds.add_message::<SerdeMessage<u8>>();
+        message_kinds.add_message::<SerdeMessage<Inventory>>();
+        message_kinds
+    }
+
+    #[test]
+    fn round_trips_with_exact_bit_length() {
+        let message_kinds = message_kinds();
+        let message = SerdeMessage::new(inventory()).unwrap();
+
+        let mut writer = BitWriter::new();
+        message.write(&message_kinds, &mut writer, &mut FakeEntityConverter);
+        assert_eq!(
+            writer.bits_written(),
+            message.bit_length(&message_kinds, &mut FakeEntityConverter)
+        );
+
+        let bytes = writer.to_bytes();
+        let mut reader = BitReader::new(&bytes);
+        let read = message_kinds
+            .read(&mut reader, &FakeEntityConverter)
+            .unwrap()
+            .to_boxed_any()
+            .downcast::<SerdeMessage<Inventory>>()
+            .unwrap();
+        assert_eq!(read.get(), &inventory());
+        assert_eq!(read.encoded_len(), message.encoded_len());
+    }
+
+    #[test]
+    fn small_integers_encode_compactly() {
+        let message = SerdeMessage::new(200u64).unwrap();
+        assert_eq!(message.encoded_len(), 1);
+    }
+
+    #[test]
+    fn truncated_payload_fails_to_read() {
+        let message_kinds = message_kinds();
+        let message = SerdeMessage::new(inventory()).unwrap();
+        let mut writer = BitWriter::new();
+        message.write(&message_kinds, &mut writer, &mut FakeEntityConverter);
+        let bytes = writer.to_bytes();
+
+        let mut reader = BitReader::new(&bytes[..bytes.len() / 2]);
+        assert!(message_kinds
+            .read(&mut reader, &FakeEntityConverter)
+            .is_err());
+    }
+}
@@ -19,7 +19,7 @@ use crate::{
         component::{component_kinds::ComponentKinds, replicate::Replicate},
         resource::ResourceKinds,
     },
-    Request, RequestOrResponse,
+    AuthorityGroupRequest, ComponentCorrection, Request, RequestOrResponse,
 };
 
 /// Extension point for registering channels, messages, and components into a `Protocol`.
@@ -128,6 +128,12 @@ impl Protocol {
     /// Enables client-authoritative entity mode, allowing clients to own and update replicated entities. Builder-style.
     pub fn enable_client_authoritative_entities(&mut self) -> &mut Self {
         self.check_lock();
+        if !self.client_authoritative_entities {
+            // Carries the server's value back after a rejected client update
+            self.message_kinds.add_message::<ComponentCorrection>();
+            // Carries a client's all-or-nothing request for authority over several entities
+            self.message_kinds.add_message::<AuthorityGroupRequest>();
+        }
         self.client_authoritative_entities = true;
         self
     }
@@ -0,0 +1,65 @@
+/// Enters a `tracing` span, returning a [`TraceSpan`] that exits it when
+/// dropped. Compiles to an empty guard without the `observability` feature,
+/// leaving the field expressions unevaluated.
+///
+/// Takes a level, a name and `field = value` pairs, optionally followed by
+/// `; field, ...` naming fields recorded later with [`TraceSpan::record`]:
+///
+/// ```ignore
+/// let span = naia_span!(DEBUG, "send_user_packets", user = user_id; bytes);
+/// span.record("bytes", bytes_sent);
+/// ```
+#[cfg(feature = "observability")]
+#[macro_export]
+macro_rules! naia_span {
+    ($level:ident, $name:literal $(, $field:ident = $value:expr)* $(; $($pending:ident),+)?) => {
+        $crate::TraceSpan::enter($crate::__tracing::span!(
+            $crate::__tracing::Level::$level,
+            $name
+            $(, $field = $value)*
+            $($(, $pending = $crate::__tracing::field::Empty)+)?
+        ))
+    };
+}
+
+/// Enters a `tracing` span, returning a [`TraceSpan`] that exits it when
+/// dropped. Compiles to an empty guard without the `observability` feature,
+/// leaving the field expressions unevaluated.
+#[cfg(not(feature = "observability"))]
+#[macro_export]
+macro_rules! naia_span {
+    ($($arg:tt)*) => {
+        $crate::TraceSpan::disabled()
+    };
+}
+
+/// A span entered by [`naia_span!`], exited when dropped. Without the
+/// `observability` feature it holds nothing and recording is a no-op.
+#[must_use = "the span is exited as soon as it's dropped"]
+pub struct TraceSpan {
+    #[cfg(feature = "observability")]
+    span: tracing::span::EnteredSpan,
+}
+
+impl TraceSpan {
+    #[cfg(feature = "observability")]
+    #[doc(hidden)]
+    pub fn enter(span: tracing::Span) -> Self {
+        Self {
+            span: span.entered(),
+        }
+    }
+
+    #[cfg(not(feature = "observability"))]
+    #[doc(hidden)]
+    pub fn disabled() -> Self {
+        Self {}
+    }
+
+    /// Records `value` for a field named after the `;` in [`naia_span!`].
+    #[cfg_attr(not(feature = "observability"), allow(unused_variables))]
+    pub fn record(&self, field: &'static str, value: u64) {
+        #[cfg(feature = "observability")]
+        self.span.record(field, value);
+    }
+}
@@ -0,0 +1,445 @@
+use std::{
+    collections::VecDeque,
+    ops::{Deref, DerefMut},
+};
+
+use log::warn;
+use naia_serde::{BitReader, BitWrite, BitWriter, Serde, SerdeErr, UnsignedVariableInteger};
+
+use crate::world::{
+    component::{property::Property, property_mutate::PropertyMutator},
+    delegation::auth_channel::EntityAuthAccessor,
+    update::collection_acks::CollectionAcks,
+};
+
+/// Number of element operations a collection property keeps for delta
+/// replication before falling back to a full resend
+pub const DEFAULT_COLLECTION_BACKLOG: usize = 32;
+
+/// Number of received element changes buffered until drained. Past this the
+/// buffer collapses into a single reset change.
+const MAX_PENDING_CHANGES: usize = 1024;
+
+/// An element operation on a replicated collection
+pub(crate) trait CollectionOp: Serde {
+    type Value: Serde;
+    type Change: Clone;
+
+    /// Whether `ops` can be applied in order to `value`
+    fn validate(ops: &[Self], value: &Self::Value) -> bool;
+
+    /// Applies the operation, returning the resulting change if any
+    fn apply(self, value: &mut Self::Value) -> Option<Self::Change>;
+
+    /// The change reported when the whole collection was replaced
+    fn reset_change() -> Self::Change;
+}
+
+/// Shared implementation of `PropertyVec` and `PropertyMap`: a `Property`
+/// holding the whole collection, plus the versioned log of element
+/// operations used to send deltas and the buffer of received changes.
+#[derive(Clone)]
+pub(crate) struct CollectionProperty<O: CollectionOp> {
+    inner: Property<O::Value>,
+    log: OpLog<O>,
+    changes: Vec<O::Change>,
+}
+
+impl<O: CollectionOp> CollectionProperty<O> {
+    pub fn new_local(value: O::Value) -> Self {
+        Self::new(Property::new_local(value), 0)
+    }
+
+    pub fn host_owned(value: O::Value, mutator_index: u8) -> Self {
+        Self::new(Property::host_owned(value, mutator_index), 0)
+    }
+
+    pub fn new_read(reader: &mut BitReader) -> Result<Self, SerdeErr> {
+        match Payload::<O>::de(reader)? {
+            Payload::Full(version, value) => Ok(Self::new(Property::remote_owned(value), version)),
+            Payload::Delta(..) => Err(SerdeErr),
+        }
+    }
+
+    fn new(inner: Property<O::Value>, version: u64) -> Self {
+        Self {
+            inner,
+            log: OpLog::new(version),
+            changes: Vec::new(),
+        }
+    }
+
+    pub fn set_mutator(&mut self, mutator: &PropertyMutator) {
+        self.inner.set_mutator(mutator);
+    }
+
+    pub fn set_max_backlog(&mut self, max_backlog: usize) {
+        self.log.set_max_backlog(max_backlog);
+    }
+
+    pub fn drain_changes(&mut self) -> Vec<O::Change> {
+        std::mem::take(&mut self.changes)
+    }
+
+    // Local mutation
+
+    /// Applies a local operation and records it for delta replication
+    pub fn apply_local(&mut self, op: O) -> Option<O::Change> {
+        if self.inner.is_remote() {
+            panic!("Remote collection Property should never be modified locally.");
+        }
+        let change = op.clone().apply(self.inner.deref_mut());
+        self.log.record(op);
+        change
+    }
+
+    // Serialization / deserialization
+
+    /// Writes the whole collection
+    pub fn write(&self, writer: &mut dyn BitWrite) {
+        let value = self.inner.outgoing_value();
+        false.ser(writer);
+        write_version(self.log.version(), writer);
+        value.ser(writer);
+    }
+
+    /// Writes the operations since the version `acks` holds for this field
+    /// when that is smaller than the whole collection, and records the
+    /// version written
+    pub fn write_acked(&self, field: u8, acks: &mut CollectionAcks, writer: &mut dyn BitWrite) {
+        let version = self.log.version();
+        acks.record_written(field, version);
+
+        let value = self.inner.outgoing_value();
+        if let Some(acked) = acks.acked(field) {
+            if let Some(ops) = self.log.since(acked) {
+                let ops: Vec<&O> = ops.collect();
+                let delta_bits = version_bit_length(acked)
+                    + length_bit_length(ops.len())
+                    + ops.iter().map(|op| op.bit_length()).sum::<u32>();
+                let full_bits = version_bit_length(version) + value.bit_length();
+                if delta_bits < full_bits {
+                    true.ser(writer);
+                    write_version(acked, writer);
+                    write_length(ops.len(), writer);
+                    for op in ops {
+                        op.ser(writer);
+                    }
+                    return;
+                }
+            }
+        }
+
+        self.write(writer);
+    }
+
+    pub fn read_write(reader: &mut BitReader, writer: &mut BitWriter) -> Result<(), SerdeErr> {
+        Payload::<O>::de(reader)?.ser(writer);
+        Ok(())
+    }
+
+    pub fn read(&mut self, reader: &mut BitReader) -> Result<(), SerdeErr> {
+        match Payload::<O>::de(reader)? {
+            Payload::Full(version, value) => {
+                self.receive_full(version, value);
+                Ok(())
+            }
+            Payload::Delta(from, ops) => self.receive_delta(from, ops),
+        }
+    }
+
+    fn receive_full(&mut self, version: u64, value: O::Value) {
+        if version <= self.log.version() {
+            // stale, a newer state has already been applied
+            return;
+        }
+        if self.inner.receive_with(|inner| *inner = value) {
+            self.log.reset_to(version);
+            self.changes.clear();
+            self.changes.push(O::reset_change());
+        }
+    }
+
+    fn receive_delta(&mut self, from: u64, ops: Vec<O>) -> Result<(), SerdeErr> {
+        let current = self.log.version();
+        if from > current {
+            warn!(
+                "Collection Property delta starts at version {} but only version {} was applied. Waiting for a full update.",
+                from, current
+            );
+            return Ok(());
+        }
+        let ops: Vec<O> = ops.into_iter().skip((current - from) as usize).collect();
+        if ops.is_empty() {
+            return Ok(());
+        }
+        if !O::validate(&ops, &self.inner) {
+            return Err(SerdeErr);
+        }
+
+        let mut changes = Vec::new();
+        let logged = ops.clone();
+        let applied = self.inner.receive_with(|inner| {
+            for op in ops {
+                changes.extend(op.apply(inner));
+            }
+        });
+        if !applied {
+            return Ok(());
+        }
+        for (offset, op) in logged.into_iter().enumerate() {
+            self.log.adopt(current + 1 + offset as u64, op);
+        }
+        for change in changes {
+            self.push_change(change);
+        }
+        Ok(())
+    }
+
+    fn push_change(&mut self, change: O::Change) {
+        if self.changes.len() >= MAX_PENDING_CHANGES {
+            self.changes.clear();
+            self.changes.push(O::reset_change());
+            return;
+        }
+        self.changes.push(change);
+    }
+
+    // Comparison
+
+    pub fn equals(&self, other: &Self) -> bool {
+        self.inner.equals(&other.inner)
+    }
+
+    /// Replaces the whole collection with another's, so every connection
+    /// gets a full resend
+    pub fn mirror(&mut self, other: &Self) {
+        self.inner.mirror(&other.inner);
+        self.log.reset();
+    }
+
+    // Ownership changes keep the version, so the log stays valid for every
+    // connection across them
+
+    pub fn remote_publish(&mut self, mutator_index: u8, mutator: &PropertyMutator) {
+        self.inner.remote_publish(mutator_index, mutator);
+    }
+
+    pub fn host_own(&mut self, mutator_index: u8) {
+        self.inner.host_own(mutator_index);
+    }
+
+    pub fn remote_unpublish(&mut self) {
+        self.inner.remote_unpublish();
+    }
+
+    pub fn enable_delegation(
+        &mut self,
+        accessor: &EntityAuthAccessor,
+        mutator_opt: Option<(u8, &PropertyMutator)>,
+    ) {
+        self.inner.enable_delegation(accessor, mutator_opt);
+    }
+
+    pub fn disable_delegation(&mut self) {
+        self.inner.disable_delegation();
+    }
+
+    pub fn localize(&mut self) {
+        self.inner.localize();
+    }
+}
+
+impl<O: CollectionOp> Deref for CollectionProperty<O> {
+    type Target = O::Value;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+/// The wire form of a collection, shared by creation and updates
+enum Payload<O: CollectionOp> {
+    /// Version and whole value
+    Full(u64, O::Value),
+    /// Version the operations apply to, and the operations
+    Delta(u64, Vec<O>),
+}
+
+impl<O: CollectionOp> Payload<O> {
+    fn de(reader: &mut BitReader) -> Result<Self, SerdeErr> {
+        if bool::de(reader)? {
+            let from = read_version(reader)?;
+            let count = read_length(reader)?;
+            let mut ops = Vec::with_capacity(count.min(DEFAULT_COLLECTION_BACKLOG));
+            for _ in 0..count {
+                ops.push(O::de(reader)?);
+            }
+            Ok(Self::Delta(from, ops))
+        } else {
+            let version = read_version(reader)?;
+            Ok(Self::Full(version, O::Value::de(reader)?))
+        }
+    }
+
+    fn ser(&self, writer: &mut dyn BitWrite) {
+        match self {
+            Self::Full(version, value) => {
+                false.ser(writer);
+                write_version(*version, writer);
+                value.ser(writer);
+            }
+            Self::Delta(from, ops) => {
+                true.ser(writer);
+                write_version(*from, writer);
+                write_length(ops.len(), writer);
+                for op in ops {
+                    op.ser(writer);
+                }
+            }
+        }
+    }
+}
+
+/// Versioned log of the element operations applied to a collection. Every
+/// operation advances the version by one; a connection that acked version
+/// `v` is brought up to date by the operations after `v`, as long as those
+/// are still in the log.
+#[derive(Clone)]
+struct OpLog<O> {
+    version: u64,
+    ops: VecDeque<(u64, O)>,
+    max_backlog: usize,
+}
+
+impl<O> OpLog<O> {
+    fn new(version: u64) -> Self {
+        Self {
+            version,
+            ops: VecDeque::new(),
+            max_backlog: DEFAULT_COLLECTION_BACKLOG,
+        }
+    }
+
+    fn version(&self) -> u64 {
+        self.version
+    }
+
+    fn set_max_backlog(&mut self, max_backlog: usize) {
+        self.max_backlog = max_backlog;
+        self.trim();
+    }
+
+    /// Records a local operation as the next version
+    fn record(&mut self, op: O) {
+        self.version += 1;
+        self.ops.push_back((self.version, op));
+        self.trim();
+    }
+
+    /// Records an operation received from the remote host at its version
+    fn adopt(&mut self, version: u64, op: O) {
+        self.version = version;
+        self.ops.push_back((version, op));
+        self.trim();
+    }
+
+    /// Drops the log and moves to a new version, so every connection gets a
+    /// full resend next
+    fn reset(&mut self) {
+        self.reset_to(self.version + 1);
+    }
+
+    /// Drops the log and takes `version` as the current one
+    fn reset_to(&mut self, version: u64) {
+        self.version = version;
+        self.ops.clear();
+    }
+
+    /// The operations after `acked`, or `None` if some of them have already
+    /// left the log
+    fn since(&self, acked: u64) -> Option<impl Iterator<Item = &O>> {
+        if acked > self.version {
+            return None;
+        }
+        if acked < self.version {
+            let (oldest, _) = self.ops.front()?;
+            if *oldest > acked + 1 {
+                return None;
+            }
+        }
+        Some(
+            self.ops
+                .iter()
+                .filter(move |(version, _)| *version > acked)
+                .map(|(_, op)| op),
+        )
+    }
+
+    fn trim(&mut self) {
+        while self.ops.len() > self.max_backlog {
+            self.ops.pop_front();
+        }
+    }
+}
+
+fn write_version(version: u64, writer: &mut dyn BitWrite) {
+    UnsignedVariableInteger::<7>::new(version).ser(writer);
+}
+
+fn read_version(reader: &mut BitReader) -> Result<u64, SerdeErr> {
+    Ok(UnsignedVariableInteger::<7>::de(reader)?.get() as u64)
+}
+
+fn version_bit_length(version: u64) -> u32 {
+    UnsignedVariableInteger::<7>::new(version).bit_length()
+}
+
+pub(crate) fn write_length(length: usize, writer: &mut dyn BitWrite) {
+    UnsignedVariableInteger::<5>::new(length as u64).ser(writer);
+}
+
+pub(crate) fn read_length(reader: &mut BitReader) -> Result<usize, SerdeErr> {
+    Ok(UnsignedVariableInteger::<5>::de(reader)?.get() as usize)
+}
+
+pub(crate) fn length_bit_length(length: usize) -> u32 {
+    UnsignedVariableInteger::<5>::new(length as u64).bit_length()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::OpLog;
+
+    #[test]
+    fn since_returns_ops_after_acked() {
+        let mut log = OpLog::new(0);
+        for op in 1..=5 {
+            log.record(op);
+        }
+        let ops: Vec<i32> = log.since(2).unwrap().copied().collect();
+        assert_eq!(ops, vec![3, 4, 5]);
+        assert_eq!(log.since(5).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn since_fails_once_backlog_is_trimmed() {
+        let mut log = OpLog::new(0);
+        log.set_max_backlog(3);
+        for op in 1..=5 {
+            log.record(op);
+        }
+        assert!(log.since(1).is_none());
+        assert_eq!(log.since(2).unwrap().count(), 3);
+    }
+
+    #[test]
+    fn reset_forces_full_resend() {
+        let mut log = OpLog::new(0);
+        log.record(1);
+        log.reset();
+        assert_eq!(log.version(), 2);
+        assert!(log.since(1).is_none());
+        assert_eq!(log.since(2).unwrap().count(), 0);
+    }
+}
@@ -1,7 +1,7 @@
 use std::hash::Hash;
 
 use log::{info, warn};
-use naia_serde::{BitCounter, BitReader, BitWrite, BitWriter, Serde, SerdeErr};
+use naia_serde::{BitCounter, BitReader, BitWrite, BitWriter, ConstBitLength, Serde, SerdeErr};
 
 use crate::world::local::local_entity::OwnedLocalEntity;
 use crate::{
@@ -65,10 +65,15 @@ impl EntityRelation {
             EntityRelation::Delegated(inner) => {
                 inner.write(writer, converter);
             }
-            EntityRelation::RemoteCreated(_)
-            | EntityRelation::RemoteWaiting(_)
-            | EntityRelation::Local(_)
-            | EntityRelation::Invalid => {
+            // Received relations are only written back out when re-recording
+            // what was replicated, e.g. into a demo
+            EntityRelation::RemoteCreated(inner) => {
+                inner.write(writer, converter);
+            }
+            EntityRelation::RemoteWaiting(_) => {
+                false.ser(writer);
+            }
+            EntityRelation::Local(_) | EntityRelation::Invalid => {
                 panic!(
                     "EntityProperty of inner type: `{:}` should never be written.",
                     self.name()
@@ -99,10 +104,9 @@ impl EntityRelation {
             EntityRelation::HostCreated(inner) => inner.bit_length(converter),
             EntityRelation::Delegated(inner) => inner.bit_length(converter),
             EntityRelation::RemotePublic(inner) => inner.bit_length(converter),
-            EntityRelation::RemoteCreated(_)
-            | EntityRelation::RemoteWaiting(_)
-            | EntityRelation::Local(_)
-            | EntityRelation::Invalid => {
+            EntityRelation::RemoteCreated(inner) => inner.bit_length(converter),
+            EntityRelation::RemoteWaiting(_) => <bool as ConstBitLength>::const_bit_length(),
+            EntityRelation::Local(_) | EntityRelation::Invalid => {
                 panic!(
                     "EntityProperty of inner type: `{:}` should never be written, so no need for their bit length.", self.name()
                 );
@@ -539,6 +543,27 @@ impl EntityProperty {
         }
     }
 
+    /// Migrate Remote Property to Host-Owned version, as if the host had
+    /// created it. A relation that never resolved becomes empty.
+    pub fn host_own(&mut self, mutator_index: u8) {
+        let inner_value = match &self.inner {
+            EntityRelation::RemoteCreated(inner) => inner.global_entity,
+            EntityRelation::RemoteWaiting(_) | EntityRelation::Invalid => None,
+            EntityRelation::HostCreated(_)
+            | EntityRelation::RemotePublic(_)
+            | EntityRelation::Local(_)
+            | EntityRelation::Delegated(_) => {
+                panic!(
+                    "EntityProperty of type: `{:?}` should never be made host-owned.",
+                    self.inner.name()
+                );
+            }
+        };
+        let mut new_inner = HostCreatedRelation::with_mutator(mutator_index);
+        new_inner.global_entity = inner_value;
+        self.inner = EntityRelation::HostCreated(new_inner);
+    }
+
     /// Migrate Remote Property to Public version
     pub fn remote_unpublish(&mut self) {
         match &mut self.inner {
@@ -744,6 +769,43 @@ impl EntityProperty {
     ) {
         self.inner.write_local_entity(converter, writer);
     }
+
+    // Used by EntitySet, which keeps one EntityProperty per member
+
+    pub(crate) fn new_remote_empty() -> Self {
+        Self {
+            inner: EntityRelation::RemoteCreated(RemoteCreatedRelation::new_empty()),
+        }
+    }
+
+    /// Neither references an entity nor waits on one
+    pub(crate) fn is_empty(&self) -> bool {
+        self.inner.get_global_entity().is_none() && self.inner.waiting_remote_entity().is_none()
+    }
+
+    /// False while this host holds authority over a Delegated property, when
+    /// incoming values are ignored
+    pub(crate) fn can_read(&self) -> bool {
+        match &self.inner {
+            EntityRelation::Delegated(inner) => inner.can_read(),
+            _ => true,
+        }
+    }
+
+    /// Queues the property for sending without changing its value
+    pub(crate) fn mutate(&mut self) {
+        match &mut self.inner {
+            EntityRelation::HostCreated(inner) => inner.mutate(),
+            EntityRelation::Delegated(inner) => inner.mutate(),
+            EntityRelation::Local(_) => {}
+            EntityRelation::RemoteCreated(_)
+            | EntityRelation::RemoteWaiting(_)
+            | EntityRelation::RemotePublic(_)
+            | EntityRelation::Invalid => {
+                panic!("Remote EntityProperty should never be set manually.");
+            }
+        }
+    }
 }
 
 // HostOwnedRelation
@@ -862,6 +924,34 @@ impl RemoteCreatedRelation {
         Self { global_entity }
     }
 
+    pub fn write(
+        &self,
+        writer: &mut dyn BitWrite,
+        converter: &mut dyn LocalEntityAndGlobalEntityConverterMut,
+    ) {
+        let Some(global_entity) = &self.global_entity else {
+            false.ser(writer);
+            return;
+        };
+        let Ok(local_entity) = converter.get_or_reserve_entity(global_entity) else {
+            false.ser(writer);
+            return;
+        };
+
+        // Must reverse the LocalEntity because the Host<->Remote
+        // relationship inverts after this data goes over the wire
+        let reversed_local_entity = local_entity.to_reversed();
+
+        true.ser(writer);
+        reversed_local_entity.ser(writer);
+    }
+
+    pub fn bit_length(&self, converter: &mut dyn LocalEntityAndGlobalEntityConverterMut) -> u32 {
+        let mut bit_counter = BitCounter::new(0, 0, u32::MAX);
+        self.write(&mut bit_counter, converter);
+        bit_counter.bits_needed()
+    }
+
     pub fn write_local_entity(
         &self,
         converter: &dyn LocalEntityAndGlobalEntityConverter,
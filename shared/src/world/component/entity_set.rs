@@ -0,0 +1,353 @@
+use std::hash::Hash;
+
+use naia_serde::{BitCounter, BitReader, BitWrite, BitWriter, Serde, SerdeErr};
+
+use crate::{
+    world::{
+        component::{
+            collection_property::{read_length, write_length, DEFAULT_COLLECTION_BACKLOG},
+            entity_property::EntityProperty,
+        },
+        entity::{
+            entity_converters::{
+                EntityAndGlobalEntityConverter, LocalEntityAndGlobalEntityConverter,
+                LocalEntityAndGlobalEntityConverterMut,
+            },
+            global_entity::GlobalEntity,
+        },
+    },
+    EntityAuthAccessor, PropertyMutator, RemoteEntity,
+};
+
+/// A component field that references any number of other entities, such as
+/// the members of a squad or the items in a container.
+///
+/// Each member goes through the same relation lifecycle as an
+/// [`EntityProperty`]. On the receiving side, a member whose entity is not
+/// yet in scope waits for it on its own: the component is inserted and
+/// updated without it, and the member resolves once its entity arrives.
+/// [`unresolved_count`](Self::unresolved_count) reports how many members are
+/// still waiting.
+#[derive(Clone)]
+pub struct EntitySet {
+    // An empty relation in the set's current state, which new members are
+    // cloned from, so they follow publish / delegation changes
+    template: EntityProperty,
+    members: Vec<EntityProperty>,
+}
+
+impl EntitySet {
+    /// Creates an empty `EntitySet` for use inside a `Component` at the given property index.
+    pub fn new_for_component(mutator_index: u8) -> Self {
+        Self {
+            template: EntityProperty::new_for_component(mutator_index),
+            members: Vec::new(),
+        }
+    }
+
+    /// Deserializes a new `EntitySet` from the remote host's bit stream.
+    pub fn new_read(
+        reader: &mut BitReader,
+        converter: &dyn LocalEntityAndGlobalEntityConverter,
+    ) -> Result<Self, SerdeErr> {
+        let template = EntityProperty::new_remote_empty();
+        let members = Self::read_members(&template, reader, converter)?;
+        Ok(Self { template, members })
+    }
+
+    /// Passes through an entity-set bit field from `reader` to `writer` without resolving entities.
+    pub fn read_write(reader: &mut BitReader, writer: &mut BitWriter) -> Result<(), SerdeErr> {
+        let length = read_length(reader)?;
+        write_length(length, writer);
+        for _ in 0..length {
+            EntityProperty::read_write(reader, writer)?;
+        }
+        Ok(())
+    }
+
+    /// Replaces the members with those in the remote host's bit stream.
+    pub fn read(
+        &mut self,
+        reader: &mut BitReader,
+        converter: &dyn LocalEntityAndGlobalEntityConverter,
+    ) -> Result<(), SerdeErr> {
+        let members = Self::read_members(&self.template, reader, converter)?;
+        if self.template.can_read() {
+            self.members = members;
+        }
+        Ok(())
+    }
+
+    fn read_members(
+        template: &EntityProperty,
+        reader: &mut BitReader,
+        converter: &dyn LocalEntityAndGlobalEntityConverter,
+    ) -> Result<Vec<EntityProperty>, SerdeErr> {
+        let length = read_length(reader)?;
+        // The length comes off the wire, so it can't size the allocation alone
+        let mut members = Vec::with_capacity(length.min(DEFAULT_COLLECTION_BACKLOG));
+        for _ in 0..length {
+            let mut member = template.clone();
+            member.read(reader, converter)?;
+            // members the remote host could not write arrive empty
+            if !member.is_empty() {
+                members.push(member);
+            }
+        }
+        Ok(members)
+    }
+
+    /// Resolves every waiting member whose entity has arrived, leaving the rest waiting.
+    pub fn resolve_waiting(&mut self, converter: &dyn LocalEntityAndGlobalEntityConverter) {
+        for member in self.members.iter_mut() {
+            let Some(remote_entity) = member.waiting_remote_entity() else {
+                continue;
+            };
+            // Apply entity redirects for migrated entities, as EntityProperty does
+            let owned_entity = remote_entity.copy_to_owned();
+            let redirected_entity = converter.apply_entity_redirect(&owned_entity);
+            if redirected_entity.convert_to_global(converter).is_ok() {
+                member.waiting_complete(converter);
+            }
+        }
+    }
+
+    /// Returns the `RemoteEntity` of every member still waiting to resolve.
+    pub fn waiting_remote_entities(&self) -> Vec<RemoteEntity> {
+        self.members
+            .iter()
+            .filter_map(|member| member.waiting_remote_entity())
+            .collect()
+    }
+
+    /// Returns how many members are still waiting for their entity to come into scope.
+    pub fn unresolved_count(&self) -> usize {
+        self.members
+            .iter()
+            .filter(|member| member.waiting_remote_entity().is_some())
+            .count()
+    }
+
+    // Ownership transitions
+
+    /// Migrate Remote EntitySet to Public version
+    pub fn remote_publish(&mut self, mutator_index: u8, mutator: &PropertyMutator) {
+        self.template.remote_publish(mutator_index, mutator);
+        for member in self.members.iter_mut() {
+            member.remote_publish(mutator_index, mutator);
+        }
+    }
+
+    /// Migrate Remote EntitySet to Host-Owned version
+    pub fn host_own(&mut self, mutator_index: u8) {
+        self.template.host_own(mutator_index);
+        for member in self.members.iter_mut() {
+            member.host_own(mutator_index);
+        }
+    }
+
+    /// Migrate Remote EntitySet to Private version
+    pub fn remote_unpublish(&mut self) {
+        self.template.remote_unpublish();
+        for member in self.members.iter_mut() {
+            member.remote_unpublish();
+        }
+    }
+
+    /// Migrate Host/RemotePublic EntitySet to Delegated version
+    pub fn enable_delegation(
+        &mut self,
+        accessor: &EntityAuthAccessor,
+        mutator_opt: Option<(u8, &PropertyMutator)>,
+    ) {
+        self.template.enable_delegation(accessor, mutator_opt);
+        for member in self.members.iter_mut() {
+            member.enable_delegation(accessor, mutator_opt);
+        }
+    }
+
+    /// Migrate Delegated EntitySet to Host-Owned (Public) version
+    pub fn disable_delegation(&mut self) {
+        self.template.disable_delegation();
+        for member in self.members.iter_mut() {
+            member.disable_delegation();
+        }
+    }
+
+    /// Migrate Host EntitySet to Local version
+    pub fn localize(&mut self) {
+        self.template.localize();
+        for member in self.members.iter_mut() {
+            member.localize();
+        }
+    }
+
+    /// Sets the property mutator used to mark this field dirty on membership changes.
+    pub fn set_mutator(&mut self, mutator: &PropertyMutator) {
+        self.template.set_mutator(mutator);
+        for member in self.members.iter_mut() {
+            member.set_mutator(mutator);
+        }
+    }
+
+    // Serialization / deserialization
+
+    /// Returns the serialized bit length of this set given `converter`.
+    pub fn bit_length(&self, converter: &mut dyn LocalEntityAndGlobalEntityConverterMut) -> u32 {
+        let mut bit_counter = BitCounter::new(0, 0, u32::MAX);
+        self.write(&mut bit_counter, converter);
+        bit_counter.bits_needed()
+    }
+
+    /// Writes every member's entity reference into `writer`.
+    pub fn write(
+        &self,
+        writer: &mut dyn BitWrite,
+        converter: &mut dyn LocalEntityAndGlobalEntityConverterMut,
+    ) {
+        write_length(self.members.len(), writer);
+        for member in self.members.iter() {
+            member.write(writer, converter);
+        }
+    }
+
+    /// Writes every member as a local entity, waiting ones included; used
+    /// when splitting component updates on the receive side.
+    pub fn write_local_entities(
+        &self,
+        converter: &dyn LocalEntityAndGlobalEntityConverter,
+        writer: &mut BitWriter,
+    ) {
+        write_length(self.members.len(), writer);
+        for member in self.members.iter() {
+            if let Some(remote_entity) = member.waiting_remote_entity() {
+                true.ser(writer);
+                remote_entity.copy_to_owned().ser(writer);
+            } else {
+                member.write_local_entity(converter, writer);
+            }
+        }
+    }
+
+    // Members
+
+    /// Returns the world entities of the resolved members.
+    pub fn get<E: Copy + Eq + Hash + Sync + Send>(
+        &self,
+        converter: &dyn EntityAndGlobalEntityConverter<E>,
+    ) -> Vec<E> {
+        self.get_inner()
+            .iter()
+            .filter_map(|global_entity| converter.global_entity_to_entity(global_entity).ok())
+            .collect()
+    }
+
+    /// Returns the raw `GlobalEntity` of every resolved member.
+    pub fn get_inner(&self) -> Vec<GlobalEntity> {
+        self.members
+            .iter()
+            .filter_map(|member| member.get_inner())
+            .collect()
+    }
+
+    /// Returns whether `entity` is a resolved member.
+    pub fn contains<E: Copy + Eq + Hash + Sync + Send>(
+        &self,
+        converter: &dyn EntityAndGlobalEntityConverter<E>,
+        entity: &E,
+    ) -> bool {
+        let Ok(global_entity) = converter.entity_to_global_entity(entity) else {
+            return false;
+        };
+        self.position(&global_entity).is_some()
+    }
+
+    /// Returns the number of members, waiting ones included.
+    pub fn len(&self) -> usize {
+        self.members.len()
+    }
+
+    /// Returns true if the set has no members.
+    pub fn is_empty(&self) -> bool {
+        self.members.is_empty()
+    }
+
+    /// Adds `entity` to the set. Returns false if it was already a member.
+    pub fn insert<E: Copy + Eq + Hash + Sync + Send>(
+        &mut self,
+        converter: &dyn EntityAndGlobalEntityConverter<E>,
+        entity: &E,
+    ) -> bool {
+        if self.contains(converter, entity) {
+            return false;
+        }
+        let mut member = self.template.clone();
+        member.set(converter, entity);
+        if member.is_empty() {
+            return false;
+        }
+        self.members.push(member);
+        true
+    }
+
+    /// Removes `entity` from the set. Returns false if it was not a member.
+    pub fn remove<E: Copy + Eq + Hash + Sync + Send>(
+        &mut self,
+        converter: &dyn EntityAndGlobalEntityConverter<E>,
+        entity: &E,
+    ) -> bool {
+        let Ok(global_entity) = converter.entity_to_global_entity(entity) else {
+            return false;
+        };
+        let Some(index) = self.position(&global_entity) else {
+            return false;
+        };
+        self.members.remove(index);
+        self.template.mutate();
+        true
+    }
+
+    /// Removes every member.
+    pub fn clear(&mut self) {
+        self.members.clear();
+        self.template.mutate();
+    }
+
+    /// Copies the resolved members of `other` into `self`, preserving `self`'s relation type.
+    pub fn mirror(&mut self, other: &EntitySet) {
+        self.members = other
+            .members
+            .iter()
+            .filter_map(|other_member| {
+                let mut member = self.template.clone();
+                member.mirror(other_member);
+                (!member.is_empty()).then_some(member)
+            })
+            .collect();
+        self.template.mutate();
+    }
+
+    fn position(&self, global_entity: &GlobalEntity) -> Option<usize> {
+        self.members
+            .iter()
+            .position(|member| member.get_inner().as_ref() == Some(global_entity))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use naia_serde::UnsignedVariableInteger;
+
+    use super::*;
+    use crate::FakeEntityConverter;
+
+    #[test]
+    fn oversized_length_fails_without_preallocating() {
+        let mut writer = BitWriter::new();
+        UnsignedVariableInteger::<5>::new(u32::MAX as u64).ser(&mut writer);
+        let bytes = writer.to_bytes();
+        let mut reader = BitReader::new(&bytes);
+
+        assert!(EntitySet::new_read(&mut reader, &FakeEntityConverter).is_err());
+    }
+}
@@ -1,6 +1,10 @@
+pub mod collection_property;
 pub mod component_kinds;
 pub mod entity_property;
+pub mod entity_set;
 pub mod property;
+pub mod property_map;
 pub mod property_mutate;
+pub mod property_vec;
 pub mod replica_ref;
 pub mod replicate;
@@ -1,7 +1,7 @@
 use log::warn;
 use std::ops::{Deref, DerefMut};
 
-use naia_serde::{BitReader, BitWrite, BitWriter, Serde, SerdeErr};
+use naia_serde::{BitReader, BitWrite, BitWriter, Quantize, Serde, SerdeErr};
 
 use crate::world::{
     component::property_mutate::PropertyMutator, delegation::auth_channel::EntityAuthAccessor,
@@ -56,9 +56,24 @@ impl<T: Serde> Property<T> {
     pub fn new_read(reader: &mut BitReader) -> Result<Self, SerdeErr> {
         let inner_value = Self::read_inner(reader)?;
 
-        Ok(Self {
-            inner: PropertyImpl::RemoteOwned(RemoteOwnedProperty::new(inner_value)),
-        })
+        Ok(Self::remote_owned(inner_value))
+    }
+
+    pub(crate) fn remote_owned(value: T) -> Self {
+        Self {
+            inner: PropertyImpl::RemoteOwned(RemoteOwnedProperty::new(value)),
+        }
+    }
+
+    /// Like [`new_read`](Self::new_read), for a value written with
+    /// [`write_quantized`](Self::write_quantized)
+    pub fn new_read_quantized<Q: Quantize<T>>(
+        reader: &mut BitReader,
+        quantizer: &Q,
+    ) -> Result<Self, SerdeErr> {
+        let inner_value = quantizer.de(reader)?;
+
+        Ok(Self::remote_owned(inner_value))
     }
 
     /// Set an PropertyMutator to track changes to the Property
@@ -83,23 +98,13 @@ impl<T: Serde> Property<T> {
 
     /// Writes contained value into outgoing byte stream
     pub fn write(&self, writer: &mut dyn BitWrite) {
-        match &self.inner {
-            PropertyImpl::HostOwned(inner) => {
-                inner.write(writer);
-            }
-            PropertyImpl::RemoteOwned(_) => {
-                panic!("Remote Private Property should never be written.");
-            }
-            PropertyImpl::RemotePublic(inner) => {
-                inner.write(writer);
-            }
-            PropertyImpl::Local(_) => {
-                panic!("Local Property should never be written.");
-            }
-            PropertyImpl::Delegated(inner) => {
-                inner.write(writer);
-            }
-        }
+        self.outgoing_value().ser(writer);
+    }
+
+    /// Writes contained value into outgoing byte stream using `quantizer`
+    /// instead of the value's own Serde impl
+    pub fn write_quantized<Q: Quantize<T>>(&self, quantizer: &Q, writer: &mut dyn BitWrite) {
+        quantizer.ser(self.outgoing_value(), writer);
     }
 
     /// Reads from a stream and immediately writes to a stream
@@ -109,27 +114,87 @@ impl<T: Serde> Property<T> {
         Ok(())
     }
 
+    /// Like [`read_write`](Self::read_write), for a value written with
+    /// [`write_quantized`](Self::write_quantized). The buffered copy keeps
+    /// the quantized encoding.
+    pub fn read_write_quantized<Q: Quantize<T>>(
+        reader: &mut BitReader,
+        writer: &mut BitWriter,
+        quantizer: &Q,
+    ) -> Result<(), SerdeErr> {
+        let value = quantizer.de(reader)?;
+        quantizer.ser(&value, writer);
+        Ok(())
+    }
+
     /// Given a cursor into incoming packet data, updates the Property with the
     /// synced value
     pub fn read(&mut self, reader: &mut BitReader) -> Result<(), SerdeErr> {
+        let value = Self::read_inner(reader)?;
+        self.receive(value);
+        Ok(())
+    }
+
+    /// Like [`read`](Self::read), for a value written with
+    /// [`write_quantized`](Self::write_quantized)
+    pub fn read_quantized<Q: Quantize<T>>(
+        &mut self,
+        reader: &mut BitReader,
+        quantizer: &Q,
+    ) -> Result<(), SerdeErr> {
+        let value = quantizer.de(reader)?;
+        self.receive(value);
+        Ok(())
+    }
+
+    /// The value to send, panicking if this Property should never be written
+    pub(crate) fn outgoing_value(&self) -> &T {
+        match &self.inner {
+            PropertyImpl::HostOwned(inner) => &inner.inner,
+            PropertyImpl::RemoteOwned(_) => {
+                panic!("Remote Private Property should never be written.");
+            }
+            PropertyImpl::RemotePublic(inner) => &inner.inner,
+            PropertyImpl::Local(_) => {
+                panic!("Local Property should never be written.");
+            }
+            PropertyImpl::Delegated(inner) => inner.outgoing_value(),
+        }
+    }
+
+    fn receive(&mut self, value: T) {
+        self.receive_with(|inner| *inner = value);
+    }
+
+    /// Applies a synced change to the value in place. Returns false if the
+    /// change was discarded because this side holds authority.
+    pub(crate) fn receive_with(&mut self, apply: impl FnOnce(&mut T)) -> bool {
         match &mut self.inner {
+            // A copy detached from any world, such as one used to check an
+            // incoming update before it is applied
+            PropertyImpl::HostOwned(inner) if inner.mutator.is_none() => {
+                apply(&mut inner.inner);
+                true
+            }
             PropertyImpl::HostOwned(_) => {
                 panic!("Host Property should never read.");
             }
-            PropertyImpl::RemoteOwned(inner) => {
-                inner.read(reader)?;
-            }
-            PropertyImpl::RemotePublic(inner) => {
-                inner.read(reader)?;
-            }
+            PropertyImpl::RemoteOwned(inner) => inner.receive_with(apply),
+            PropertyImpl::RemotePublic(inner) => inner.receive_with(apply),
             PropertyImpl::Local(_) => {
                 panic!("Local Property should never read.");
             }
-            PropertyImpl::Delegated(inner) => {
-                inner.read(reader)?;
-            }
+            PropertyImpl::Delegated(inner) => inner.receive_with(apply),
         }
-        Ok(())
+    }
+
+    /// Whether the value is owned by the remote host, so local changes
+    /// would never be sent
+    pub(crate) fn is_remote(&self) -> bool {
+        matches!(
+            self.inner,
+            PropertyImpl::RemoteOwned(_) | PropertyImpl::RemotePublic(_)
+        )
     }
 
     fn read_inner(reader: &mut BitReader) -> Result<T, SerdeErr> {
@@ -154,15 +219,19 @@ impl<T: Serde> Property<T> {
     }
 
     /// Set value to the value of another Property, queues for update if value
-    /// changes
+    /// changes. A received Property takes the value as if it had arrived in
+    /// an update, e.g. when a server-side validator clamps it.
     pub fn mirror(&mut self, other: &Self) {
         let other_inner = other.inner();
         match &mut self.inner {
             PropertyImpl::HostOwned(inner) => {
                 inner.mirror(other_inner);
             }
-            PropertyImpl::RemoteOwned(_) | PropertyImpl::RemotePublic(_) => {
-                panic!("Remote Property should never be set manually.");
+            PropertyImpl::RemoteOwned(inner) => {
+                inner.receive_with(|inner| *inner = other_inner.clone());
+            }
+            PropertyImpl::RemotePublic(inner) => {
+                inner.receive_with(|inner| *inner = other_inner.clone());
             }
             PropertyImpl::Delegated(inner) => {
                 inner.mirror(other_inner);
@@ -199,6 +268,30 @@ impl<T: Serde> Property<T> {
         }
     }
 
+    /// Migrate Remote Property to Host-Owned version, as if the host had
+    /// created it
+    pub fn host_own(&mut self, mutator_index: u8) {
+        match &mut self.inner {
+            PropertyImpl::RemoteOwned(inner) => {
+                let inner_value = inner.inner.clone();
+                self.inner =
+                    PropertyImpl::HostOwned(HostOwnedProperty::new(inner_value, mutator_index));
+            }
+            PropertyImpl::HostOwned(_) => {
+                panic!("Host Property should never be made host-owned twice.");
+            }
+            PropertyImpl::RemotePublic(_) => {
+                panic!("Public Remote Property should never be made host-owned.");
+            }
+            PropertyImpl::Local(_) => {
+                panic!("Local Property should never be made host-owned.");
+            }
+            PropertyImpl::Delegated(_) => {
+                panic!("Delegated Property should never be made host-owned.");
+            }
+        }
+    }
+
     /// Migrate Remote Property to Private version
     pub fn remote_unpublish(&mut self) {
         match &mut self.inner {
@@ -367,10 +460,6 @@ impl<T: Serde> HostOwnedProperty<T> {
         self.mutator = Some(mutator.clone_new());
     }
 
-    pub fn write(&self, writer: &mut dyn BitWrite) {
-        self.inner.ser(writer);
-    }
-
     pub fn mirror(&mut self, other: &T) {
         self.mutate();
         self.inner = other.clone();
@@ -412,9 +501,9 @@ impl<T: Serde> RemoteOwnedProperty<T> {
         Self { inner: value }
     }
 
-    pub fn read(&mut self, reader: &mut BitReader) -> Result<(), SerdeErr> {
-        self.inner = Property::read_inner(reader)?;
-        Ok(())
+    pub fn receive_with(&mut self, apply: impl FnOnce(&mut T)) -> bool {
+        apply(&mut self.inner);
+        true
     }
 }
 
@@ -435,14 +524,10 @@ impl<T: Serde> RemotePublicProperty<T> {
         }
     }
 
-    pub fn read(&mut self, reader: &mut BitReader) -> Result<(), SerdeErr> {
-        self.inner = Property::read_inner(reader)?;
+    pub fn receive_with(&mut self, apply: impl FnOnce(&mut T)) -> bool {
+        apply(&mut self.inner);
         self.mutate();
-        Ok(())
-    }
-
-    pub fn write(&self, writer: &mut dyn BitWrite) {
-        self.inner.ser(writer);
+        true
     }
 
     fn mutate(&mut self) {
@@ -474,24 +559,22 @@ impl<T: Serde> DelegatedProperty<T> {
         }
     }
 
-    pub fn read(&mut self, reader: &mut BitReader) -> Result<(), SerdeErr> {
-        let value = Property::read_inner(reader)?;
-
-        if self.can_read() {
-            self.inner = value;
-            if self.can_mutate() {
-                self.mutate();
-            }
+    pub fn receive_with(&mut self, apply: impl FnOnce(&mut T)) -> bool {
+        if !self.can_read() {
+            return false;
         }
-
-        Ok(())
+        apply(&mut self.inner);
+        if self.can_mutate() {
+            self.mutate();
+        }
+        true
     }
 
-    pub fn write(&self, writer: &mut dyn BitWrite) {
+    pub fn outgoing_value(&self) -> &T {
         if !self.can_write() {
             panic!("Must have Authority over Entity before performing this operation. Current Authority: {:?}", self.auth_accessor.auth_status());
         }
-        self.inner.ser(writer);
+        &self.inner
     }
 
     pub fn mirror(&mut self, other: &T) {
@@ -0,0 +1,319 @@
+use std::{collections::HashMap, hash::Hash, ops::Deref};
+
+use naia_serde::{BitReader, BitWrite, BitWriter, Serde, SerdeErr, UnsignedInteger};
+
+use crate::world::{
+    component::{
+        collection_property::{CollectionOp, CollectionProperty},
+        property_mutate::PropertyMutator,
+    },
+    delegation::auth_channel::EntityAuthAccessor,
+    update::collection_acks::CollectionAcks,
+};
+
+/// A replicated `HashMap` whose updates carry only the entry operations
+/// since the remote host's last acked version, falling back to the whole
+/// map when the backlog of operations runs out or the delta would be larger.
+///
+/// Mutate it through its methods; it derefs to `HashMap<K, V>` for reading.
+/// On the receiving side, [`drain_changes`](Self::drain_changes) yields the
+/// entry changes applied since the last call.
+#[derive(Clone)]
+pub struct PropertyMap<K: Serde + Eq + Hash, V: Serde> {
+    inner: CollectionProperty<MapOp<K, V>>,
+}
+
+/// An entry change applied to a [`PropertyMap`] by the remote host
+#[derive(Clone, Debug, PartialEq)]
+pub enum MapChange<K, V> {
+    /// An entry was added
+    Inserted {
+        /// Key of the new entry
+        key: K,
+        /// Value of the new entry
+        value: V,
+    },
+    /// The value of an existing entry was replaced
+    Updated {
+        /// Key of the entry
+        key: K,
+        /// The value before
+        old: V,
+        /// The value after
+        new: V,
+    },
+    /// An entry was removed
+    Removed {
+        /// Key of the removed entry
+        key: K,
+        /// Value of the removed entry
+        value: V,
+    },
+    /// Every entry was removed
+    Cleared,
+    /// The whole map was replaced, or more changes arrived than could be
+    /// buffered. Read the current value instead.
+    Reset,
+}
+
+impl<K: Serde + Eq + Hash, V: Serde> PropertyMap<K, V> {
+    /// Create a new Local PropertyMap
+    pub fn new_local(value: HashMap<K, V>) -> Self {
+        Self {
+            inner: CollectionProperty::new_local(value),
+        }
+    }
+
+    /// Create a new host-owned PropertyMap
+    pub fn host_owned(value: HashMap<K, V>, mutator_index: u8) -> Self {
+        Self {
+            inner: CollectionProperty::host_owned(value, mutator_index),
+        }
+    }
+
+    /// Given a cursor into incoming packet data, initializes the PropertyMap
+    /// with the synced value
+    pub fn new_read(reader: &mut BitReader) -> Result<Self, SerdeErr> {
+        Ok(Self {
+            inner: CollectionProperty::new_read(reader)?,
+        })
+    }
+
+    /// Set an PropertyMutator to track changes to the PropertyMap
+    pub fn set_mutator(&mut self, mutator: &PropertyMutator) {
+        self.inner.set_mutator(mutator);
+    }
+
+    /// Sets how many entry operations are kept for delta updates. A remote
+    /// host further behind than this receives the whole map.
+    pub fn set_max_backlog(&mut self, max_backlog: usize) {
+        self.inner.set_max_backlog(max_backlog);
+    }
+
+    /// Takes the entry changes received from the remote host since the last
+    /// call, oldest first
+    pub fn drain_changes(&mut self) -> Vec<MapChange<K, V>> {
+        self.inner.drain_changes()
+    }
+
+    // Mutation
+
+    /// Inserts or replaces the value for `key`, returning the old value
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        match self.inner.apply_local(MapOp::Insert(key, value)) {
+            Some(MapChange::Updated { old, .. }) => Some(old),
+            _ => None,
+        }
+    }
+
+    /// Removes the entry for `key`, returning its value
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        if !self.contains_key(key) {
+            return None;
+        }
+        match self.inner.apply_local(MapOp::Remove(key.clone())) {
+            Some(MapChange::Removed { value, .. }) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Removes every entry
+    pub fn clear(&mut self) {
+        self.inner.apply_local(MapOp::Clear);
+    }
+
+    // Serialization / deserialization
+
+    /// Writes the whole map into outgoing byte stream
+    pub fn write(&self, writer: &mut dyn BitWrite) {
+        self.inner.write(writer);
+    }
+
+    /// Writes the operations since the version `acks` holds for `field`, or
+    /// the whole map if that is smaller or the operations are gone
+    pub fn write_acked(&self, field: u8, acks: &mut CollectionAcks, writer: &mut dyn BitWrite) {
+        self.inner.write_acked(field, acks, writer);
+    }
+
+    /// Reads from a stream and immediately writes to a stream
+    /// Used to buffer updates for later
+    pub fn read_write(reader: &mut BitReader, writer: &mut BitWriter) -> Result<(), SerdeErr> {
+        CollectionProperty::<MapOp<K, V>>::read_write(reader, writer)
+    }
+
+    /// Given a cursor into incoming packet data, updates the PropertyMap with
+    /// the synced value or operations
+    pub fn read(&mut self, reader: &mut BitReader) -> Result<(), SerdeErr> {
+        self.inner.read(reader)
+    }
+
+    /// Compare to another PropertyMap
+    pub fn equals(&self, other: &Self) -> bool {
+        self.inner.equals(&other.inner)
+    }
+
+    /// Set value to the value of another PropertyMap, queues a full update
+    pub fn mirror(&mut self, other: &Self) {
+        self.inner.mirror(&other.inner);
+    }
+
+    /// Migrate Remote PropertyMap to Public version
+    pub fn remote_publish(&mut self, mutator_index: u8, mutator: &PropertyMutator) {
+        self.inner.remote_publish(mutator_index, mutator);
+    }
+
+    /// Migrate Remote PropertyMap to Host-Owned version
+    pub fn host_own(&mut self, mutator_index: u8) {
+        self.inner.host_own(mutator_index);
+    }
+
+    /// Migrate Remote PropertyMap to Private version
+    pub fn remote_unpublish(&mut self) {
+        self.inner.remote_unpublish();
+    }
+
+    /// Migrate PropertyMap to Delegated version
+    pub fn enable_delegation(
+        &mut self,
+        accessor: &EntityAuthAccessor,
+        mutator_opt: Option<(u8, &PropertyMutator)>,
+    ) {
+        self.inner.enable_delegation(accessor, mutator_opt);
+    }
+
+    /// Migrate Delegated PropertyMap to Host-Owned (Public) version
+    pub fn disable_delegation(&mut self) {
+        self.inner.disable_delegation();
+    }
+
+    /// Migrate Host PropertyMap to Local version
+    pub fn localize(&mut self) {
+        self.inner.localize();
+    }
+}
+
+impl<K: Serde + Eq + Hash, V: Serde> Deref for PropertyMap<K, V> {
+    type Target = HashMap<K, V>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+#[derive(Clone, PartialEq)]
+enum MapOp<K, V> {
+    Insert(K, V),
+    Remove(K),
+    Clear,
+}
+
+impl<K: Serde, V: Serde> Serde for MapOp<K, V> {
+    fn ser(&self, writer: &mut dyn BitWrite) {
+        match self {
+            Self::Insert(key, value) => {
+                UnsignedInteger::<2>::new(0).ser(writer);
+                key.ser(writer);
+                value.ser(writer);
+            }
+            Self::Remove(key) => {
+                UnsignedInteger::<2>::new(1).ser(writer);
+                key.ser(writer);
+            }
+            Self::Clear => UnsignedInteger::<2>::new(2).ser(writer),
+        }
+    }
+
+    fn de(reader: &mut BitReader) -> Result<Self, SerdeErr> {
+        match UnsignedInteger::<2>::de(reader)?.get() {
+            0 => Ok(Self::Insert(K::de(reader)?, V::de(reader)?)),
+            1 => Ok(Self::Remove(K::de(reader)?)),
+            2 => Ok(Self::Clear),
+            _ => Err(SerdeErr),
+        }
+    }
+
+    fn bit_length(&self) -> u32 {
+        2 + match self {
+            Self::Insert(key, value) => key.bit_length() + value.bit_length(),
+            Self::Remove(key) => key.bit_length(),
+            Self::Clear => 0,
+        }
+    }
+}
+
+impl<K: Serde + Eq + Hash, V: Serde> CollectionOp for MapOp<K, V> {
+    type Value = HashMap<K, V>;
+    type Change = MapChange<K, V>;
+
+    fn validate(_ops: &[Self], _value: &HashMap<K, V>) -> bool {
+        true
+    }
+
+    fn apply(self, value: &mut HashMap<K, V>) -> Option<MapChange<K, V>> {
+        match self {
+            Self::Insert(key, new) => Some(match value.insert(key.clone(), new.clone()) {
+                Some(old) => MapChange::Updated { key, old, new },
+                None => MapChange::Inserted { key, value: new },
+            }),
+            Self::Remove(key) => value
+                .remove(&key)
+                .map(|value| MapChange::Removed { key, value }),
+            Self::Clear => {
+                value.clear();
+                Some(MapChange::Cleared)
+            }
+        }
+    }
+
+    fn reset_change() -> MapChange<K, V> {
+        MapChange::Reset
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use naia_serde::{BitReader, BitWriter};
+
+    use super::{MapChange, PropertyMap};
+    use crate::world::update::collection_acks::CollectionAcks;
+
+    #[test]
+    fn entry_deltas_converge() {
+        let scores: HashMap<u16, u32> = (0..40).map(|player| (player, 0)).collect();
+        let mut sender = PropertyMap::host_owned(scores, 0);
+
+        let mut acks = CollectionAcks::new();
+        let mut writer = BitWriter::new();
+        sender.write_acked(0, &mut acks, &mut writer);
+        let bytes = writer.to_bytes();
+        let mut receiver = PropertyMap::new_read(&mut BitReader::new(&bytes)).unwrap();
+        for (field, version) in acks.clone().written() {
+            acks.ack(*field, *version);
+        }
+
+        sender.insert(7, 30);
+        sender.insert(40, 5);
+        sender.remove(&0);
+        let mut writer = BitWriter::new();
+        sender.write_acked(0, &mut acks, &mut writer);
+        let bytes = writer.to_bytes();
+        receiver.read(&mut BitReader::new(&bytes)).unwrap();
+
+        assert!(bytes.len() < 20);
+        assert_eq!(*receiver, *sender);
+        assert_eq!(
+            receiver.drain_changes(),
+            vec![
+                MapChange::Updated {
+                    key: 7,
+                    old: 0,
+                    new: 30
+                },
+                MapChange::Inserted { key: 40, value: 5 },
+                MapChange::Removed { key: 0, value: 0 },
+            ]
+        );
+    }
+}
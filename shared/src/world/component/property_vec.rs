@@ -0,0 +1,424 @@
+use std::ops::Deref;
+
+use naia_serde::{BitReader, BitWrite, BitWriter, Serde, SerdeErr, UnsignedInteger};
+
+use crate::world::{
+    component::{
+        collection_property::{
+            length_bit_length, read_length, write_length, CollectionOp, CollectionProperty,
+        },
+        property_mutate::PropertyMutator,
+    },
+    delegation::auth_channel::EntityAuthAccessor,
+    update::collection_acks::CollectionAcks,
+};
+
+/// A replicated `Vec` whose updates carry only the element operations since
+/// the remote host's last acked version, falling back to the whole `Vec`
+/// when the backlog of operations runs out or the delta would be larger.
+///
+/// Mutate it through its methods; it derefs to `Vec<T>` for reading. On the
+/// receiving side, [`drain_changes`](Self::drain_changes) yields the element
+/// changes applied since the last call.
+#[derive(Clone)]
+pub struct PropertyVec<T: Serde> {
+    inner: CollectionProperty<VecOp<T>>,
+}
+
+/// An element change applied to a [`PropertyVec`] by the remote host
+#[derive(Clone, Debug, PartialEq)]
+pub enum VecChange<T> {
+    /// An element was inserted at `index`
+    Inserted {
+        /// Position of the new element
+        index: usize,
+        /// The new element
+        value: T,
+    },
+    /// The element at `index` was removed
+    Removed {
+        /// Former position of the element
+        index: usize,
+        /// The removed element
+        value: T,
+    },
+    /// The element at `index` was replaced
+    Replaced {
+        /// Position of the element
+        index: usize,
+        /// The element before
+        old: T,
+        /// The element after
+        new: T,
+    },
+    /// Every element was removed
+    Cleared,
+    /// The whole `Vec` was replaced, or more changes arrived than could be
+    /// buffered. Read the current value instead.
+    Reset,
+}
+
+impl<T: Serde> PropertyVec<T> {
+    /// Create a new Local PropertyVec
+    pub fn new_local(value: Vec<T>) -> Self {
+        Self {
+            inner: CollectionProperty::new_local(value),
+        }
+    }
+
+    /// Create a new host-owned PropertyVec
+    pub fn host_owned(value: Vec<T>, mutator_index: u8) -> Self {
+        Self {
+            inner: CollectionProperty::host_owned(value, mutator_index),
+        }
+    }
+
+    /// Given a cursor into incoming packet data, initializes the PropertyVec
+    /// with the synced value
+    pub fn new_read(reader: &mut BitReader) -> Result<Self, SerdeErr> {
+        Ok(Self {
+            inner: CollectionProperty::new_read(reader)?,
+        })
+    }
+
+    /// Set an PropertyMutator to track changes to the PropertyVec
+    pub fn set_mutator(&mut self, mutator: &PropertyMutator) {
+        self.inner.set_mutator(mutator);
+    }
+
+    /// Sets how many element operations are kept for delta updates. A remote
+    /// host further behind than this receives the whole `Vec`.
+    pub fn set_max_backlog(&mut self, max_backlog: usize) {
+        self.inner.set_max_backlog(max_backlog);
+    }
+
+    /// Takes the element changes received from the remote host since the
+    /// last call, oldest first
+    pub fn drain_changes(&mut self) -> Vec<VecChange<T>> {
+        self.inner.drain_changes()
+    }
+
+    // Mutation
+
+    /// Appends an element to the back
+    pub fn push(&mut self, value: T) {
+        let index = self.len();
+        self.inner.apply_local(VecOp::Insert(index, value));
+    }
+
+    /// Removes the last element and returns it, or `None` if empty
+    pub fn pop(&mut self) -> Option<T> {
+        let index = self.len().checked_sub(1)?;
+        Some(self.remove(index))
+    }
+
+    /// Inserts an element at `index`, shifting later elements. Panics if
+    /// `index > len`.
+    pub fn insert(&mut self, index: usize, value: T) {
+        assert!(index <= self.len(), "insertion index out of bounds");
+        self.inner.apply_local(VecOp::Insert(index, value));
+    }
+
+    /// Removes and returns the element at `index`, shifting later elements.
+    /// Panics if `index` is out of bounds.
+    pub fn remove(&mut self, index: usize) -> T {
+        assert!(index < self.len(), "removal index out of bounds");
+        match self.inner.apply_local(VecOp::Remove(index)) {
+            Some(VecChange::Removed { value, .. }) => value,
+            _ => unreachable!(),
+        }
+    }
+
+    /// Replaces the element at `index`, returning the old one. Panics if
+    /// `index` is out of bounds.
+    pub fn set(&mut self, index: usize, value: T) -> T {
+        assert!(index < self.len(), "index out of bounds");
+        match self.inner.apply_local(VecOp::Set(index, value)) {
+            Some(VecChange::Replaced { old, .. }) => old,
+            _ => unreachable!(),
+        }
+    }
+
+    /// Removes every element
+    pub fn clear(&mut self) {
+        self.inner.apply_local(VecOp::Clear);
+    }
+
+    // Serialization / deserialization
+
+    /// Writes the whole `Vec` into outgoing byte stream
+    pub fn write(&self, writer: &mut dyn BitWrite) {
+        self.inner.write(writer);
+    }
+
+    /// Writes the operations since the version `acks` holds for `field`, or
+    /// the whole `Vec` if that is smaller or the operations are gone
+    pub fn write_acked(&self, field: u8, acks: &mut CollectionAcks, writer: &mut dyn BitWrite) {
+        self.inner.write_acked(field, acks, writer);
+    }
+
+    /// Reads from a stream and immediately writes to a stream
+    /// Used to buffer updates for later
+    pub fn read_write(reader: &mut BitReader, writer: &mut BitWriter) -> Result<(), SerdeErr> {
+        CollectionProperty::<VecOp<T>>::read_write(reader, writer)
+    }
+
+    /// Given a cursor into incoming packet data, updates the PropertyVec with
+    /// the synced value or operations
+    pub fn read(&mut self, reader: &mut BitReader) -> Result<(), SerdeErr> {
+        self.inner.read(reader)
+    }
+
+    /// Compare to another PropertyVec
+    pub fn equals(&self, other: &Self) -> bool {
+        self.inner.equals(&other.inner)
+    }
+
+    /// Set value to the value of another PropertyVec, queues a full update
+    pub fn mirror(&mut self, other: &Self) {
+        self.inner.mirror(&other.inner);
+    }
+
+    /// Migrate Remote PropertyVec to Public version
+    pub fn remote_publish(&mut self, mutator_index: u8, mutator: &PropertyMutator) {
+        self.inner.remote_publish(mutator_index, mutator);
+    }
+
+    /// Migrate Remote PropertyVec to Host-Owned version
+    pub fn host_own(&mut self, mutator_index: u8) {
+        self.inner.host_own(mutator_index);
+    }
+
+    /// Migrate Remote PropertyVec to Private version
+    pub fn remote_unpublish(&mut self) {
+        self.inner.remote_unpublish();
+    }
+
+    /// Migrate PropertyVec to Delegated version
+    pub fn enable_delegation(
+        &mut self,
+        accessor: &EntityAuthAccessor,
+        mutator_opt: Option<(u8, &PropertyMutator)>,
+    ) {
+        self.inner.enable_delegation(accessor, mutator_opt);
+    }
+
+    /// Migrate Delegated PropertyVec to Host-Owned (Public) version
+    pub fn disable_delegation(&mut self) {
+        self.inner.disable_delegation();
+    }
+
+    /// Migrate Host PropertyVec to Local version
+    pub fn localize(&mut self) {
+        self.inner.localize();
+    }
+}
+
+impl<T: Serde> Deref for PropertyVec<T> {
+    type Target = Vec<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+#[derive(Clone, PartialEq)]
+enum VecOp<T> {
+    Insert(usize, T),
+    Remove(usize),
+    Set(usize, T),
+    Clear,
+}
+
+impl<T: Serde> VecOp<T> {
+    fn tag(&self) -> u64 {
+        match self {
+            Self::Insert(..) => 0,
+            Self::Remove(_) => 1,
+            Self::Set(..) => 2,
+            Self::Clear => 3,
+        }
+    }
+}
+
+impl<T: Serde> Serde for VecOp<T> {
+    fn ser(&self, writer: &mut dyn BitWrite) {
+        UnsignedInteger::<2>::new(self.tag()).ser(writer);
+        match self {
+            Self::Insert(index, value) | Self::Set(index, value) => {
+                write_length(*index, writer);
+                value.ser(writer);
+            }
+            Self::Remove(index) => write_length(*index, writer),
+            Self::Clear => {}
+        }
+    }
+
+    fn de(reader: &mut BitReader) -> Result<Self, SerdeErr> {
+        match UnsignedInteger::<2>::de(reader)?.get() {
+            0 => Ok(Self::Insert(read_length(reader)?, T::de(reader)?)),
+            1 => Ok(Self::Remove(read_length(reader)?)),
+            2 => Ok(Self::Set(read_length(reader)?, T::de(reader)?)),
+            _ => Ok(Self::Clear),
+        }
+    }
+
+    fn bit_length(&self) -> u32 {
+        2 + match self {
+            Self::Insert(index, value) | Self::Set(index, value) => {
+                length_bit_length(*index) + value.bit_length()
+            }
+            Self::Remove(index) => length_bit_length(*index),
+            Self::Clear => 0,
+        }
+    }
+}
+
+impl<T: Serde> CollectionOp for VecOp<T> {
+    type Value = Vec<T>;
+    type Change = VecChange<T>;
+
+    fn validate(ops: &[Self], value: &Vec<T>) -> bool {
+        let mut len = value.len();
+        for op in ops {
+            match op {
+                Self::Insert(index, _) if *index <= len => len += 1,
+                Self::Remove(index) if *index < len => len -= 1,
+                Self::Set(index, _) if *index < len => {}
+                Self::Clear => len = 0,
+                _ => return false,
+            }
+        }
+        true
+    }
+
+    fn apply(self, value: &mut Vec<T>) -> Option<VecChange<T>> {
+        Some(match self {
+            Self::Insert(index, new) => {
+                value.insert(index, new.clone());
+                VecChange::Inserted { index, value: new }
+            }
+            Self::Remove(index) => VecChange::Removed {
+                index,
+                value: value.remove(index),
+            },
+            Self::Set(index, new) => VecChange::Replaced {
+                index,
+                old: std::mem::replace(&mut value[index], new.clone()),
+                new,
+            },
+            Self::Clear => {
+                value.clear();
+                VecChange::Cleared
+            }
+        })
+    }
+
+    fn reset_change() -> VecChange<T> {
+        VecChange::Reset
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use naia_serde::{BitReader, BitWriter};
+
+    use super::{PropertyVec, VecChange};
+    use crate::world::update::collection_acks::CollectionAcks;
+
+    /// Sends `sender` to a new receiver and acks the version sent
+    fn replicate(sender: &PropertyVec<u16>, acks: &mut CollectionAcks) -> PropertyVec<u16> {
+        let mut sent = CollectionAcks::new();
+        let mut writer = BitWriter::new();
+        sender.write_acked(0, &mut sent, &mut writer);
+        let bytes = writer.to_bytes();
+        let receiver = PropertyVec::new_read(&mut BitReader::new(&bytes)).unwrap();
+        for (field, version) in sent.written() {
+            acks.ack(*field, *version);
+        }
+        receiver
+    }
+
+    /// Sends an update to `receiver`, acking it if `delivered`, and returns
+    /// its size in bytes
+    fn send_update(
+        sender: &PropertyVec<u16>,
+        receiver: &mut PropertyVec<u16>,
+        acks: &mut CollectionAcks,
+        delivered: bool,
+    ) -> usize {
+        let mut sent = acks.clone();
+        let mut writer = BitWriter::new();
+        sender.write_acked(0, &mut sent, &mut writer);
+        let bytes = writer.to_bytes();
+        receiver.read(&mut BitReader::new(&bytes)).unwrap();
+        if delivered {
+            for (field, version) in sent.written() {
+                acks.ack(*field, *version);
+            }
+        }
+        bytes.len()
+    }
+
+    #[test]
+    fn delta_is_smaller_than_full_and_converges() {
+        let mut sender = PropertyVec::host_owned((0..100).collect(), 0);
+        let mut acks = CollectionAcks::new();
+        let mut receiver = replicate(&sender, &mut acks);
+
+        sender.push(500);
+        sender.set(3, 7);
+        sender.remove(0);
+        let bytes = send_update(&sender, &mut receiver, &mut acks, true);
+
+        assert!(bytes < 20);
+        assert_eq!(*receiver, *sender);
+        assert_eq!(
+            receiver.drain_changes(),
+            vec![
+                VecChange::Inserted {
+                    index: 100,
+                    value: 500
+                },
+                VecChange::Replaced {
+                    index: 3,
+                    old: 3,
+                    new: 7
+                },
+                VecChange::Removed { index: 0, value: 0 },
+            ]
+        );
+    }
+
+    #[test]
+    fn falls_back_to_full_when_backlog_is_exceeded() {
+        let mut sender = PropertyVec::host_owned(vec![1, 2, 3], 0);
+        sender.set_max_backlog(2);
+        let mut acks = CollectionAcks::new();
+        let mut receiver = replicate(&sender, &mut acks);
+
+        for value in 10..15 {
+            sender.push(value);
+        }
+        send_update(&sender, &mut receiver, &mut acks, true);
+
+        assert_eq!(*receiver, vec![1, 2, 3, 10, 11, 12, 13, 14]);
+        assert_eq!(receiver.drain_changes(), vec![VecChange::Reset]);
+    }
+
+    #[test]
+    fn repeated_delta_is_applied_once() {
+        let mut sender = PropertyVec::host_owned(vec![1, 2, 3], 0);
+        let mut acks = CollectionAcks::new();
+        let mut receiver = replicate(&sender, &mut acks);
+
+        // the first update is never acked, so the second carries its push too
+        sender.push(4);
+        send_update(&sender, &mut receiver, &mut acks, false);
+        sender.push(5);
+        send_update(&sender, &mut receiver, &mut acks, true);
+
+        assert_eq!(*receiver, vec![1, 2, 3, 4, 5]);
+        assert_eq!(receiver.drain_changes().len(), 2);
+    }
+}
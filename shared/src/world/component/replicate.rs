@@ -2,6 +2,7 @@ use std::{any::Any, collections::HashSet};
 
 use naia_serde::{BitReader, BitWrite, SerdeErr};
 
+use crate::world::update::collection_acks::CollectionAcks;
 use crate::world::update::component_update::ComponentUpdate;
 use crate::world::update::diff_mask::DiffMask;
 use crate::{
@@ -124,6 +125,20 @@ pub trait Replicate: Sync + Send + 'static + Named + Any {
         writer: &mut dyn BitWrite,
         converter: &mut dyn LocalEntityAndGlobalEntityConverterMut,
     );
+    /// Like [`write_update`](Self::write_update), for a connection whose
+    /// acked `PropertyVec` and `PropertyMap` versions are in `acks`, so those
+    /// fields can be written as element deltas. Records the versions written
+    /// into `acks`.
+    fn write_update_acked(
+        &self,
+        diff_mask: &DiffMask,
+        writer: &mut dyn BitWrite,
+        converter: &mut dyn LocalEntityAndGlobalEntityConverterMut,
+        acks: &mut CollectionAcks,
+    ) {
+        let _ = acks;
+        self.write_update(diff_mask, writer, converter);
+    }
     /// Reads data from an incoming packet, sufficient to sync the in-memory
     /// Component with it's replica on the Server
     fn read_apply_update(
@@ -141,10 +156,19 @@ pub trait Replicate: Sync + Send + 'static + Named + Any {
     fn relations_waiting(&self) -> Option<HashSet<RemoteEntity>>;
     /// Converts any LocalEntities contained within the Component's EntityProperty fields to GlobalEntities
     fn relations_complete(&mut self, converter: &dyn LocalEntityAndGlobalEntityConverter);
+    /// Returns the members of the Component's EntitySet fields which are still waiting on their
+    /// entities, with the field index of each. Unlike `relations_waiting`, these do not hold the
+    /// Component back; each resolves through `read_apply_field_update` once its entity arrives
+    fn relations_pending(&self) -> Option<Vec<(RemoteEntity, u8)>> {
+        None
+    }
     /// Publish Replicate
     fn publish(&mut self, mutator: &PropertyMutator);
     /// Unpublish Replicate
     fn unpublish(&mut self);
+    /// Convert a Replicate read from a byte stream to Host-Owned, as if the
+    /// host had created it
+    fn host_own(&mut self);
     /// Enable Delegation Replicate
     fn enable_delegation(
         &mut self,
@@ -0,0 +1,79 @@
+use naia_derive::MessageInternal;
+use naia_serde::{BitReader, BitWriter, Serde, SerdeErr, UnsignedVariableInteger};
+
+use crate::{
+    world::local::local_entity::OwnedLocalEntity, GlobalEntity,
+    LocalEntityAndGlobalEntityConverter, LocalEntityAndGlobalEntityConverterMut,
+};
+
+// Don't trust the count of an incoming request with the allocation
+const MAX_PREALLOCATED_ENTITIES: usize = 64;
+
+/// Asks the server for authority over several delegated entities at once,
+/// to be granted for all of them or for none.
+#[derive(MessageInternal)]
+pub struct AuthorityGroupRequest {
+    bytes: Box<[u8]>,
+}
+
+impl AuthorityGroupRequest {
+    /// Writes the requested entities.
+    pub fn new(
+        global_entities: &[GlobalEntity],
+        converter: &mut dyn LocalEntityAndGlobalEntityConverterMut,
+    ) -> Self {
+        let mut writer = BitWriter::new();
+        UnsignedVariableInteger::<5>::new(global_entities.len() as u64).ser(&mut writer);
+        for global_entity in global_entities {
+            let Ok(local_entity) = converter.get_or_reserve_entity(global_entity) else {
+                false.ser(&mut writer);
+                continue;
+            };
+            true.ser(&mut writer);
+            // Reversed, since the Host<->Remote relationship inverts over the wire
+            local_entity.to_reversed().ser(&mut writer);
+        }
+        Self {
+            bytes: writer.to_bytes(),
+        }
+    }
+
+    /// Reads the requested entities. An entity the receiver can't resolve
+    /// reads as `None`.
+    pub fn read_entities(
+        &self,
+        converter: &dyn LocalEntityAndGlobalEntityConverter,
+    ) -> Result<Vec<Option<GlobalEntity>>, SerdeErr> {
+        let mut reader = BitReader::new(&self.bytes);
+        let count = UnsignedVariableInteger::<5>::de(&mut reader)?.get() as usize;
+        let mut global_entities = Vec::with_capacity(count.min(MAX_PREALLOCATED_ENTITIES));
+        for _ in 0..count {
+            if !bool::de(&mut reader)? {
+                global_entities.push(None);
+                continue;
+            }
+            let local_entity = OwnedLocalEntity::de(&mut reader)?;
+            let local_entity = converter.apply_entity_redirect(&local_entity);
+            global_entities.push(local_entity.convert_to_global(converter).ok());
+        }
+        Ok(global_entities)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::FakeEntityConverter;
+
+    #[test]
+    fn oversized_count_fails_without_preallocating() {
+        let mut writer = BitWriter::new();
+        UnsignedVariableInteger::<5>::new(u32::MAX as u64).ser(&mut writer);
+        false.ser(&mut writer);
+        let request = AuthorityGroupRequest {
+            bytes: writer.to_bytes(),
+        };
+
+        assert!(request.read_entities(&FakeEntityConverter).is_err());
+    }
+}
@@ -80,7 +80,8 @@ impl HostEntityAuthStatus {
             (HostType::Client, EntityAuthStatus::Requested) => false,
             (HostType::Client, EntityAuthStatus::Granted) => false,
             (HostType::Client, EntityAuthStatus::Releasing) => false,
-            (HostType::Client, EntityAuthStatus::Denied) => false,
+            // Denied requests may be queued, if the entity's policy allows it
+            (HostType::Client, EntityAuthStatus::Denied) => true,
             (HostType::Server, status) => unreachable!(
                 "can_request() is a client-side authority-flow predicate; \
                  reached with HostType::Server (status={status:?}). The server \
@@ -178,14 +179,15 @@ mod tests {
     use super::*;
 
     #[test]
-    fn client_can_request_only_when_available() {
-        let s = HostEntityAuthStatus::new(HostType::Client, EntityAuthStatus::Available);
-        assert!(s.can_request());
+    fn client_can_request_only_when_available_or_denied() {
+        for status in [EntityAuthStatus::Available, EntityAuthStatus::Denied] {
+            let s = HostEntityAuthStatus::new(HostType::Client, status);
+            assert!(s.can_request(), "client must request from {status:?}");
+        }
         for status in [
             EntityAuthStatus::Requested,
             EntityAuthStatus::Granted,
             EntityAuthStatus::Releasing,
-            EntityAuthStatus::Denied,
         ] {
             let s = HostEntityAuthStatus::new(HostType::Client, status);
             assert!(!s.can_request(), "client must not re-request from {status:?}");
@@ -1,3 +1,4 @@
 pub mod auth_channel;
+pub mod authority_group_request;
 pub mod entity_auth_status;
 pub mod host_auth_handler;
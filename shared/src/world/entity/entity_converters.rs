@@ -41,6 +41,11 @@ pub trait GlobalWorldManagerType: InScopeEntities<GlobalEntity> {
     fn entity_is_replicating(&self, global_entity: &GlobalEntity) -> bool;
     /// Returns `true` if `global_entity` was spawned as a static entity.
     fn entity_is_static(&self, global_entity: &GlobalEntity) -> bool;
+    /// Returns `true` if component updates for `global_entity` are delta-encoded
+    /// against the last acked baseline instead of retransmitted on loss.
+    fn entity_uses_baseline_delta(&self, _global_entity: &GlobalEntity) -> bool {
+        false
+    }
 }
 
 /// Bidirectional conversion between a world-type entity `E` and a `GlobalEntity`.
@@ -8,8 +8,8 @@ pub enum EntityEvent {
     Spawn(GlobalEntity),
     /// An existing entity was despawned by the remote.
     Despawn(GlobalEntity),
-    /// A component was added to an entity.
-    InsertComponent(GlobalEntity, ComponentKind),
+    /// A component was added to an entity, received at the given tick.
+    InsertComponent(Tick, GlobalEntity, ComponentKind),
     /// A component was removed from an entity; carries the last known component value.
     RemoveComponent(GlobalEntity, Box<dyn Replicate>),
     /// A component on an entity was updated at the given tick.
@@ -42,7 +42,7 @@ impl EntityEvent {
         match self {
             Self::Spawn(_) => Some(EntityMessageType::Spawn),
             Self::Despawn(_) => Some(EntityMessageType::Despawn),
-            Self::InsertComponent(_, _) => Some(EntityMessageType::InsertComponent),
+            Self::InsertComponent(_, _, _) => Some(EntityMessageType::InsertComponent),
             Self::RemoveComponent(_, _) => Some(EntityMessageType::RemoveComponent),
             Self::Publish(_) => Some(EntityMessageType::Publish),
             Self::Unpublish(_) => Some(EntityMessageType::Unpublish),
@@ -62,7 +62,7 @@ impl EntityEvent {
         match self {
             Self::Spawn(entity) => *entity,
             Self::Despawn(entity) => *entity,
-            Self::InsertComponent(entity, _) => *entity,
+            Self::InsertComponent(_, entity, _) => *entity,
             Self::RemoveComponent(entity, _) => *entity,
             Self::UpdateComponent(_, entity, _) => *entity,
             Self::Publish(entity) => *entity,
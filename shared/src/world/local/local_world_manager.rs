@@ -107,6 +107,7 @@ use log::info;
 use naia_socket_shared::Instant;
 
 use crate::world::sync::RemoteEntityChannel;
+use crate::world::update::collection_acks::CollectionAcks;
 use crate::world::update::entity_update_manager::EntityUpdateManager;
 use crate::{
     messages::channels::receivers::reliable_receiver::ReliableReceiver,
@@ -115,7 +116,11 @@ use crate::{
     world::{
         entity::entity_converters::GlobalWorldManagerType,
         host::host_world_manager::{CommandId, HostWorldManager},
-        remote::remote_entity_waitlist::{RemoteEntityWaitlist, WaitlistStore},
+        remote::{
+            remote_entity_waitlist::{RemoteEntityWaitlist, WaitlistStore},
+            remote_world_manager::IncomingComponents,
+            update_validator::UpdateValidator,
+        },
         sync::HostEntityChannel,
     },
     ChannelSender, ComponentKind, ComponentKinds, ComponentUpdate, DiffMask,
@@ -157,7 +162,7 @@ pub struct LocalWorldManager {
     paused_entities: HashSet<GlobalEntity>,
 
     // TODO: this is kind of specific to the receiver, put it somewhere else?
-    incoming_components: HashMap<(OwnedLocalEntity, ComponentKind), Box<dyn Replicate>>,
+    incoming_components: IncomingComponents,
 
     // TODO: this is kind of specific to the updater, put it somewhere else?
     incoming_updates: Vec<(Tick, OwnedLocalEntity, ComponentUpdate)>,
@@ -214,6 +219,11 @@ impl LocalWorldManager {
             .entity_converter_mut(global_world_manager, &mut self.entity_map)
     }
 
+    /// Returns the number of entity components with changes not yet sent to this connection.
+    pub fn dirty_update_count(&self) -> usize {
+        self.updater.dirty_candidates_len()
+    }
+
     /// Returns `true` if `global_entity` is currently tracked by either the host or remote engine.
     pub fn has_global_entity(&self, global_entity: &GlobalEntity) -> bool {
         let Ok(local_entity) = self.entity_map.global_entity_to_owned_entity(global_entity) else {
@@ -269,6 +279,14 @@ impl LocalWorldManager {
         component_kinds_map: &ComponentKinds,
         is_static: bool,
     ) {
+        // A HostEntity reserved by `host_reserve_entity()` (an EntityProperty
+        // written before the entity was in scope) is mapped before its channel
+        // exists, and the remote is already waiting on that id: spawn onto it.
+        let reserved = self
+            .host
+            .host_removed_reserved_entity(global_entity)
+            .is_some();
+
         // Stale-mapping detection (per [entity-delegation-15] / scope re-entry):
         // If the entity is mapped but its HostEntityChannel has already been
         // removed from the HostEngine, that means a Despawn was sent (channel
@@ -277,9 +295,8 @@ impl LocalWorldManager {
         // we allocate a *fresh* HostEntity below — otherwise the stale ACK,
         // when it arrives, would call `on_delivered_despawn_entity` and wipe
         // the new mapping (since recycled HostEntity ids would alias).
-        if let Ok(existing_host_entity) =
-            self.entity_map.global_entity_to_host_entity(global_entity)
-        {
+        let existing_host_entity = self.entity_map.global_entity_to_host_entity(global_entity);
+        if let (false, Ok(existing_host_entity)) = (reserved, existing_host_entity) {
             let channel_alive = self
                 .host
                 .get_host_world()
@@ -520,6 +537,12 @@ impl LocalWorldManager {
         }
     }
 
+    /// Whether `command_id` is being re-sent because it wasn't acknowledged
+    /// in time.
+    pub(crate) fn is_command_resend(&self, command_id: &CommandId) -> bool {
+        self.sender.is_resend(command_id)
+    }
+
     pub(crate) fn record_command_written(
         &mut self,
         packet_index: &PacketIndex,
@@ -663,12 +686,13 @@ impl LocalWorldManager {
 
     pub(crate) fn insert_received_component(
         &mut self,
+        tick: Tick,
         local_entity: &OwnedLocalEntity,
         component_kind: &ComponentKind,
         component: Box<dyn Replicate>,
     ) {
         self.incoming_components
-            .insert((*local_entity, *component_kind), component);
+            .insert((*local_entity, *component_kind), (tick, component));
     }
 
     pub(crate) fn insert_received_update(
@@ -688,8 +712,67 @@ impl LocalWorldManager {
         global_world_manager: &dyn GlobalWorldManagerType,
         component_kinds: &ComponentKinds,
         world: &mut W,
+        validator: Option<&mut dyn UpdateValidator>,
         now: &Instant,
     ) -> Vec<EntityEvent> {
+        let (incoming_host_messages, incoming_remote_messages) = self.split_incoming_messages();
+
+        let host_events = self.host.take_incoming_events(
+            spawner,
+            global_world_manager,
+            &self.entity_map,
+            world,
+            incoming_host_messages,
+        );
+        let mut remote_events = self.remote.take_incoming_events(
+            spawner,
+            global_world_manager,
+            &mut self.entity_map,
+            component_kinds,
+            world,
+            validator,
+            now,
+            &mut self.incoming_components,
+            std::mem::take(&mut self.incoming_updates),
+            incoming_remote_messages,
+        );
+
+        let mut incoming_events = host_events;
+        incoming_events.append(&mut remote_events);
+
+        incoming_events
+    }
+
+    /// Drains all buffered incoming messages and applies only those about
+    /// entities this side hosts, dropping everything about entities the
+    /// remote side spawned. For connections that may not replicate entities
+    /// of their own.
+    pub fn take_incoming_host_events<E: Copy + Eq + Hash + Send + Sync, W: WorldMutType<E>>(
+        &mut self,
+        spawner: &mut dyn GlobalEntitySpawner<E>,
+        global_world_manager: &dyn GlobalWorldManagerType,
+        world: &mut W,
+    ) -> Vec<EntityEvent> {
+        let (incoming_host_messages, _) = self.split_incoming_messages();
+        self.incoming_components.clear();
+        self.incoming_updates.clear();
+
+        self.host.take_incoming_events(
+            spawner,
+            global_world_manager,
+            &self.entity_map,
+            world,
+            incoming_host_messages,
+        )
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn split_incoming_messages(
+        &mut self,
+    ) -> (
+        Vec<(MessageIndex, EntityMessage<HostEntity>)>,
+        Vec<(MessageIndex, EntityMessage<RemoteEntity>)>,
+    ) {
         let incoming_messages = self.receiver.receive_messages();
         let mut incoming_host_messages = Vec::new();
         let mut incoming_remote_messages = Vec::new();
@@ -739,29 +822,7 @@ impl LocalWorldManager {
             }
         }
 
-        let host_events = self.host.take_incoming_events(
-            spawner,
-            global_world_manager,
-            &self.entity_map,
-            world,
-            incoming_host_messages,
-        );
-        let mut remote_events = self.remote.take_incoming_events(
-            spawner,
-            global_world_manager,
-            &mut self.entity_map,
-            component_kinds,
-            world,
-            now,
-            &mut self.incoming_components,
-            std::mem::take(&mut self.incoming_updates),
-            incoming_remote_messages,
-        );
-
-        let mut incoming_events = host_events;
-        incoming_events.append(&mut remote_events);
-
-        incoming_events
+        (incoming_host_messages, incoming_remote_messages)
     }
 
     /// Registers `global_entity` as authority-granted, enabling update tracking for its components.
@@ -841,8 +902,10 @@ impl LocalWorldManager {
         &self,
         global_entity: &GlobalEntity,
         component_kind: &ComponentKind,
+        baseline: bool,
     ) -> DiffMask {
-        self.updater.get_diff_mask(global_entity, component_kind)
+        self.updater
+            .get_diff_mask(global_entity, component_kind, baseline)
     }
 
     pub(crate) fn record_update(
@@ -852,9 +915,41 @@ impl LocalWorldManager {
         global_entity: &GlobalEntity,
         component_kind: &ComponentKind,
         diff_mask: DiffMask,
+        baseline: bool,
     ) {
-        self.updater
-            .record_update(now, packet_index, global_entity, component_kind, diff_mask);
+        self.updater.record_update(
+            now,
+            packet_index,
+            global_entity,
+            component_kind,
+            diff_mask,
+            baseline,
+        );
+    }
+
+    pub(crate) fn collection_acks(
+        &self,
+        global_entity: &GlobalEntity,
+        component_kind: &ComponentKind,
+    ) -> CollectionAcks {
+        self.updater.collection_acks(global_entity, component_kind)
+    }
+
+    pub(crate) fn record_collection_writes(
+        &mut self,
+        now: &Instant,
+        packet_index: &PacketIndex,
+        global_entity: &GlobalEntity,
+        component_kind: &ComponentKind,
+        acks: &CollectionAcks,
+    ) {
+        self.updater.record_collection_writes(
+            now,
+            packet_index,
+            global_entity,
+            component_kind,
+            acks,
+        );
     }
 
     // Joint router
@@ -1426,8 +1521,4 @@ impl LocalWorldManager {
         self.updater.diff_handler_receiver_count()
     }
 
-    #[doc(hidden)]
-    pub fn dirty_update_count(&self) -> usize {
-        self.updater.dirty_candidates_len()
-    }
 }
@@ -0,0 +1,43 @@
+use naia_derive::MessageInternal;
+use naia_serde::{BitReader, BitWriter, SerdeErr};
+
+use crate::{
+    ComponentKinds, EntityProperty, LocalEntityAndGlobalEntityConverter,
+    LocalEntityAndGlobalEntityConverterMut, Replicate,
+};
+
+/// Carries the server's value of a component back to the client whose
+/// update to it was rejected or clamped by an [`UpdateValidator`](crate::UpdateValidator).
+#[derive(MessageInternal)]
+pub struct ComponentCorrection {
+    /// The entity holding the component.
+    pub entity: EntityProperty,
+    bytes: Box<[u8]>,
+}
+
+impl ComponentCorrection {
+    /// Writes `component` in full, to be applied over the client's copy.
+    pub fn new(
+        entity: EntityProperty,
+        component: &dyn Replicate,
+        component_kinds: &ComponentKinds,
+        converter: &mut dyn LocalEntityAndGlobalEntityConverterMut,
+    ) -> Self {
+        let mut writer = BitWriter::new();
+        component.write(component_kinds, &mut writer, converter);
+        Self {
+            entity,
+            bytes: writer.to_bytes(),
+        }
+    }
+
+    /// Reads the corrected component.
+    pub fn read_component(
+        &self,
+        component_kinds: &ComponentKinds,
+        converter: &dyn LocalEntityAndGlobalEntityConverter,
+    ) -> Result<Box<dyn Replicate>, SerdeErr> {
+        let mut reader = BitReader::new(&self.bytes);
+        component_kinds.read(&mut reader, converter)
+    }
+}
@@ -1,3 +1,5 @@
+pub mod component_correction;
 pub mod remote_entity_waitlist;
 pub mod remote_world_manager;
 pub mod remote_world_waitlist;
+pub mod update_validator;
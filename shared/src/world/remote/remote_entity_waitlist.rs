@@ -156,7 +156,11 @@ impl RemoteEntityWaitlist {
         }
 
         // remove handle from required entities map
-        let entities = self.handle_to_required_entities.remove(handle).unwrap();
+        let Some(entities) = self.handle_to_required_entities.remove(handle) else {
+            // handle was already ready, so isn't waiting on any entities
+            self.ready_handles.remove(handle);
+            return;
+        };
 
         // recycle message handle
         self.handle_store.recycle_key(handle);
@@ -248,6 +252,10 @@ impl<T> WaitlistStore<T> {
         }
     }
 
+    pub fn contains(&self, handle: &WaitlistHandle) -> bool {
+        self.items.contains_key(handle)
+    }
+
     pub fn remove(&mut self, handle: &WaitlistHandle) -> Option<T> {
         self.item_handles.remove(handle);
         self.items.remove(handle)
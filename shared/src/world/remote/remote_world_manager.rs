@@ -16,6 +16,7 @@ use crate::{
         remote::{
             remote_entity_waitlist::{RemoteEntityWaitlist, WaitlistStore},
             remote_world_waitlist::RemoteWorldWaitlist,
+            update_validator::UpdateValidator,
         },
         sync::{RemoteEngine, RemoteEntityChannel},
     },
@@ -35,6 +36,10 @@ cfg_if! {
     }
 }
 
+/// Components received in entity messages, with the tick of the packet they
+/// arrived in, waiting for their message to be processed
+pub type IncomingComponents = HashMap<(OwnedLocalEntity, ComponentKind), (Tick, Box<dyn Replicate>)>;
+
 /// Manages the inbound side of entity replication — entities whose authoritative state comes from the remote peer.
 pub struct RemoteWorldManager {
     // For Server, this contains the Entities that have been received from the Client, that the Client has authority over.
@@ -247,8 +252,9 @@ impl RemoteWorldManager {
         local_entity_map: &mut LocalEntityMap,
         component_kinds: &ComponentKinds,
         world: &mut W,
+        mut validator: Option<&mut dyn UpdateValidator>,
         now: &Instant,
-        incoming_components: &mut HashMap<(OwnedLocalEntity, ComponentKind), Box<dyn Replicate>>,
+        incoming_components: &mut IncomingComponents,
         incoming_updates: Vec<(Tick, OwnedLocalEntity, ComponentUpdate)>,
         incoming_messages: Vec<(MessageIndex, EntityMessage<RemoteEntity>)>,
     ) -> Vec<EntityEvent> {
@@ -262,6 +268,7 @@ impl RemoteWorldManager {
             spawner.to_converter(),
             component_kinds,
             world,
+            validator.as_mut().map(|validator| &mut **validator as _),
             now,
             incoming_updates,
         );
@@ -286,7 +293,7 @@ impl RemoteWorldManager {
         local_entity_map: &mut LocalEntityMap,
         world: &mut W,
         now: &Instant,
-        incoming_components: &mut HashMap<(OwnedLocalEntity, ComponentKind), Box<dyn Replicate>>,
+        incoming_components: &mut IncomingComponents,
         incoming_messages: Vec<EntityMessage<RemoteEntity>>,
     ) {
         self.process_ready_messages(
@@ -314,7 +321,7 @@ impl RemoteWorldManager {
         global_world_manager: &dyn GlobalWorldManagerType,
         local_entity_map: &mut LocalEntityMap,
         world: &mut W,
-        incoming_components: &mut HashMap<(OwnedLocalEntity, ComponentKind), Box<dyn Replicate>>,
+        incoming_components: &mut IncomingComponents,
         incoming_messages: Vec<EntityMessage<RemoteEntity>>,
     ) {
         // execute the action and emit an event
@@ -360,7 +367,7 @@ impl RemoteWorldManager {
                 }
                 EntityMessage::InsertComponent(remote_entity, component_kind) => {
                     let local_entity = remote_entity.copy_to_owned();
-                    let component = incoming_components
+                    let (tick, component) = incoming_components
                         .remove(&(local_entity, component_kind))
                         .unwrap();
 
@@ -373,6 +380,7 @@ impl RemoteWorldManager {
                         self.process_insert(
                             world,
                             local_entity_map,
+                            tick,
                             &remote_entity,
                             &world_entity,
                             component,
@@ -416,10 +424,12 @@ impl RemoteWorldManager {
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn process_insert<E: Copy + Eq + Hash + Send + Sync, W: WorldMutType<E>>(
         &mut self,
         world: &mut W,
         converter: &dyn LocalEntityAndGlobalEntityConverter,
+        tick: Tick,
         entity: &RemoteEntity,
         world_entity: &E,
         component: Box<dyn Replicate>,
@@ -429,6 +439,7 @@ impl RemoteWorldManager {
 
             self.waitlist.waitlist_queue_entity(
                 &self.remote_engine,
+                tick,
                 entity,
                 component,
                 component_kind,
@@ -438,6 +449,7 @@ impl RemoteWorldManager {
             self.finish_insert(
                 world,
                 converter,
+                tick,
                 entity,
                 world_entity,
                 component,
@@ -446,10 +458,12 @@ impl RemoteWorldManager {
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn finish_insert<E: Copy + Eq + Hash + Send + Sync, W: WorldMutType<E>>(
         &mut self,
         world: &mut W,
         converter: &dyn LocalEntityAndGlobalEntityConverter,
+        tick: Tick,
         entity: &RemoteEntity,
         world_entity: &E,
         component: Box<dyn Replicate>,
@@ -461,12 +475,26 @@ impl RemoteWorldManager {
         //     &name, global_entity
         // );
 
+        // EntitySet members still waiting on their entities resolve later
+        // without holding back the insert
+        let pending_opt = component.relations_pending();
+
         world.insert_boxed_component(world_entity, component);
 
+        if let Some(pending) = pending_opt {
+            self.waitlist.waitlist_queue_pending(
+                &self.remote_engine,
+                tick,
+                entity,
+                component_kind,
+                pending,
+            );
+        }
+
         let global_entity = converter.remote_entity_to_global_entity(entity).unwrap();
 
         self.incoming_events
-            .push(EntityEvent::InsertComponent(global_entity, *component_kind));
+            .push(EntityEvent::InsertComponent(tick, global_entity, *component_kind));
     }
 
     fn process_remove<E: Copy + Eq + Hash + Send + Sync, W: WorldMutType<E>>(
@@ -497,7 +525,7 @@ impl RemoteWorldManager {
         world: &mut W,
         now: &Instant,
     ) {
-        for (entity, component_kind, component) in
+        for (tick, entity, component_kind, component) in
             self.waitlist.entities_to_insert(now, local_converter)
         {
             let global_entity = local_converter
@@ -509,6 +537,7 @@ impl RemoteWorldManager {
             self.finish_insert(
                 world,
                 local_converter,
+                tick,
                 &entity,
                 &world_entity,
                 component,
@@ -517,12 +546,14 @@ impl RemoteWorldManager {
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn process_updates<E: Copy + Eq + Hash + Send + Sync, W: WorldMutType<E>>(
         &mut self,
         local_converter: &dyn LocalEntityAndGlobalEntityConverter,
         world_converter: &dyn EntityAndGlobalEntityConverter<E>,
         component_kinds: &ComponentKinds,
         world: &mut W,
+        mut validator: Option<&mut dyn UpdateValidator>,
         now: &Instant,
         incoming_updates: Vec<(Tick, OwnedLocalEntity, ComponentUpdate)>,
     ) {
@@ -531,9 +562,10 @@ impl RemoteWorldManager {
             world_converter,
             component_kinds,
             world,
+            validator.as_mut().map(|validator| &mut **validator as _),
             incoming_updates,
         );
-        self.process_waitlist_updates(local_converter, world_converter, world, now);
+        self.process_waitlist_updates(local_converter, world_converter, world, validator, now);
     }
 
     /// Process component updates from raw bits for a given entity
@@ -543,6 +575,7 @@ impl RemoteWorldManager {
         world_converter: &dyn EntityAndGlobalEntityConverter<WE>,
         component_kinds: &ComponentKinds,
         world: &mut W,
+        validator: Option<&mut dyn UpdateValidator>,
         incoming_updates: Vec<(Tick, OwnedLocalEntity, ComponentUpdate)>,
     ) {
         for (tick, local_entity, component_kind) in self.waitlist.process_ready_updates(
@@ -551,6 +584,7 @@ impl RemoteWorldManager {
             world_converter,
             component_kinds,
             world,
+            validator,
             incoming_updates,
         ) {
             let global_entity = local_converter
@@ -569,12 +603,16 @@ impl RemoteWorldManager {
         local_converter: &dyn LocalEntityAndGlobalEntityConverter,
         world_converter: &dyn EntityAndGlobalEntityConverter<E>,
         world: &mut W,
+        validator: Option<&mut dyn UpdateValidator>,
         now: &Instant,
     ) {
-        for (tick, remote_entity, component_kind) in
-            self.waitlist
-                .process_waitlist_updates(local_converter, world_converter, world, now)
-        {
+        for (tick, remote_entity, component_kind) in self.waitlist.process_waitlist_updates(
+            local_converter,
+            world_converter,
+            world,
+            validator,
+            now,
+        ) {
             let global_entity = local_converter
                 .remote_entity_to_global_entity(&remote_entity)
                 .unwrap();
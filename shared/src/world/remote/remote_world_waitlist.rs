@@ -10,19 +10,25 @@ use naia_socket_shared::Instant;
 use crate::{
     world::{
         entity::in_scope_entities::InScopeEntities,
-        remote::remote_entity_waitlist::{RemoteEntityWaitlist, WaitlistHandle, WaitlistStore},
+        remote::{
+            remote_entity_waitlist::{RemoteEntityWaitlist, WaitlistHandle, WaitlistStore},
+            update_validator::{apply_validated_update, IncomingUpdate, UpdateValidator},
+        },
     },
     ComponentFieldUpdate, ComponentKind, ComponentKinds, ComponentUpdate,
-    EntityAndGlobalEntityConverter, LocalEntityAndGlobalEntityConverter, OwnedLocalEntity,
+    BitWriter, EntityAndGlobalEntityConverter, LocalEntityAndGlobalEntityConverter, OwnedLocalEntity,
     RemoteEntity, Replicate, Tick, WorldMutType,
 };
 
 pub struct RemoteWorldWaitlist {
     entity_waitlist: RemoteEntityWaitlist,
-    insert_waitlist_store: WaitlistStore<(RemoteEntity, Box<dyn Replicate>)>,
+    insert_waitlist_store: WaitlistStore<(Tick, RemoteEntity, Box<dyn Replicate>)>,
     insert_waitlist_map: HashMap<(RemoteEntity, ComponentKind), WaitlistHandle>,
     update_waitlist_store: WaitlistStore<(Tick, RemoteEntity, ComponentKind, ComponentFieldUpdate)>,
-    update_waitlist_map: HashMap<(RemoteEntity, ComponentKind), HashMap<u8, WaitlistHandle>>,
+    // A field has one waiting update per entity it waits on; only EntitySet
+    // fields wait on more than one
+    update_waitlist_map:
+        HashMap<(RemoteEntity, ComponentKind), HashMap<u8, Vec<WaitlistHandle>>>,
 }
 
 impl RemoteWorldWaitlist {
@@ -47,6 +53,7 @@ impl RemoteWorldWaitlist {
     pub(crate) fn waitlist_queue_entity(
         &mut self,
         in_scope_entities: &dyn InScopeEntities<RemoteEntity>,
+        tick: Tick,
         entity: &RemoteEntity,
         component: Box<dyn Replicate>,
         component_kind: &ComponentKind,
@@ -56,7 +63,7 @@ impl RemoteWorldWaitlist {
             in_scope_entities,
             entity_set,
             &mut self.insert_waitlist_store,
-            (*entity, component),
+            (tick, *entity, component),
         );
 
         self.insert_waitlist_map
@@ -67,13 +74,13 @@ impl RemoteWorldWaitlist {
         &mut self,
         now: &Instant,
         local_converter: &dyn LocalEntityAndGlobalEntityConverter,
-    ) -> Vec<(RemoteEntity, ComponentKind, Box<dyn Replicate>)> {
+    ) -> Vec<(Tick, RemoteEntity, ComponentKind, Box<dyn Replicate>)> {
         let mut output = Vec::new();
         if let Some(list) = self
             .entity_waitlist
             .collect_ready_items(now, &mut self.insert_waitlist_store)
         {
-            for (global_entity, mut component) in list {
+            for (tick, global_entity, mut component) in list {
                 let component_kind = component.kind();
 
                 // let name = component.name();
@@ -89,7 +96,7 @@ impl RemoteWorldWaitlist {
                     component.relations_complete(local_converter);
                 }
 
-                output.push((global_entity, component_kind, component));
+                output.push((tick, global_entity, component_kind, component));
             }
         }
 
@@ -122,7 +129,7 @@ impl RemoteWorldWaitlist {
         }
         // Remove Component from update waitlist if it's there
         if let Some(handle_map) = self.update_waitlist_map.remove(&(*entity, *component_kind)) {
-            for (_index, handle) in handle_map {
+            for handle in handle_map.into_values().flatten() {
                 self.update_waitlist_store.remove(&handle);
                 self.entity_waitlist.remove_waiting_handle(&handle);
             }
@@ -131,7 +138,77 @@ impl RemoteWorldWaitlist {
         false
     }
 
+    /// Queues the EntitySet members an inserted Component is still waiting
+    /// on, to resolve as their entities arrive
+    pub(crate) fn waitlist_queue_pending(
+        &mut self,
+        in_scope_entities: &dyn InScopeEntities<RemoteEntity>,
+        tick: Tick,
+        entity: &RemoteEntity,
+        component_kind: &ComponentKind,
+        pending: Vec<(RemoteEntity, u8)>,
+    ) {
+        let waiting_updates = pending
+            .into_iter()
+            .map(|(waiting_entity, field_id)| {
+                let field_update =
+                    ComponentFieldUpdate::new(field_id, BitWriter::new().to_owned_reader());
+                (waiting_entity, field_update)
+            })
+            .collect();
+        self.queue_waiting_updates(
+            in_scope_entities,
+            tick,
+            entity,
+            component_kind,
+            waiting_updates,
+        );
+    }
+
+    fn queue_waiting_updates(
+        &mut self,
+        in_scope_entities: &dyn InScopeEntities<RemoteEntity>,
+        tick: Tick,
+        remote_entity: &RemoteEntity,
+        component_kind: &ComponentKind,
+        waiting_updates: Vec<(RemoteEntity, ComponentFieldUpdate)>,
+    ) {
+        let component_field_key = (*remote_entity, *component_kind);
+        let mut replaced_fields = HashSet::new();
+        for (waiting_remote_entity, waiting_field_update) in waiting_updates {
+            let field_id = waiting_field_update.field_id();
+
+            // Have to convert the single waiting entity to a HashSet ..
+            // TODO: make this more efficient
+            let mut waiting_entities = HashSet::new();
+            waiting_entities.insert(waiting_remote_entity);
+
+            let handle = self.entity_waitlist.queue(
+                in_scope_entities,
+                &waiting_entities,
+                &mut self.update_waitlist_store,
+                (tick, *remote_entity, *component_kind, waiting_field_update),
+            );
+            let handles = self
+                .update_waitlist_map
+                .entry(component_field_key)
+                .or_default()
+                .entry(field_id)
+                .or_default();
+            // a newer update of the field replaces the ones still waiting
+            if replaced_fields.insert(field_id) {
+                for old_handle in handles.drain(..) {
+                    if self.update_waitlist_store.remove(&old_handle).is_some() {
+                        self.entity_waitlist.remove_waiting_handle(&old_handle);
+                    }
+                }
+            }
+            handles.push(handle);
+        }
+    }
+
     /// Process component updates from raw bits for a given entity
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn process_ready_updates<E: Copy + Eq + Hash + Send + Sync, W: WorldMutType<E>>(
         &mut self,
         in_scope_entities: &dyn InScopeEntities<RemoteEntity>,
@@ -139,6 +216,7 @@ impl RemoteWorldWaitlist {
         world_converter: &dyn EntityAndGlobalEntityConverter<E>,
         component_kinds: &ComponentKinds,
         world: &mut W,
+        mut validator: Option<&mut dyn UpdateValidator>,
         mut incoming_updates: Vec<(Tick, OwnedLocalEntity, ComponentUpdate)>,
     ) -> Vec<(Tick, OwnedLocalEntity, ComponentKind)> {
         let mut output = Vec::new();
@@ -174,32 +252,13 @@ impl RemoteWorldWaitlist {
                 };
                 let remote_entity = local_entity.take_remote();
 
-                for (waiting_remote_entity, waiting_field_update) in waiting_updates {
-                    let field_id = waiting_field_update.field_id();
-
-                    // Have to convert the single waiting entity to a HashSet ..
-                    // TODO: make this more efficient
-                    let mut waiting_entities = HashSet::new();
-                    waiting_entities.insert(waiting_remote_entity);
-
-                    let handle = self.entity_waitlist.queue(
-                        in_scope_entities,
-                        &waiting_entities,
-                        &mut self.update_waitlist_store,
-                        (tick, remote_entity, component_kind, waiting_field_update),
-                    );
-                    let component_field_key = (remote_entity, component_kind);
-                    self.update_waitlist_map.entry(component_field_key).or_default();
-                    let handle_map = self
-                        .update_waitlist_map
-                        .get_mut(&component_field_key)
-                        .unwrap();
-                    if let Some(old_handle) = handle_map.get(&field_id) {
-                        self.update_waitlist_store.remove(&handle);
-                        self.entity_waitlist.remove_waiting_handle(old_handle);
-                    }
-                    handle_map.insert(field_id, handle);
-                }
+                self.queue_waiting_updates(
+                    in_scope_entities,
+                    tick,
+                    &remote_entity,
+                    &component_kind,
+                    waiting_updates,
+                );
             }
             // if it exists, apply the ready part of the component update
             if let Some(ready_update) = ready_update_opt {
@@ -209,20 +268,23 @@ impl RemoteWorldWaitlist {
                 let world_entity = world_converter
                     .global_entity_to_entity(&global_entity)
                     .unwrap();
-                if world
-                    .component_apply_update(
-                        local_converter,
-                        &world_entity,
-                        &component_kind,
-                        ready_update,
-                    )
-                    .is_err()
-                {
-                    warn!("Remote World Manager: cannot read malformed component update message");
-                    continue;
+                match apply_validated_update(
+                    world,
+                    local_converter,
+                    validator.as_mut().map(|validator| &mut **validator as _),
+                    &global_entity,
+                    &world_entity,
+                    &component_kind,
+                    IncomingUpdate::Component(ready_update),
+                ) {
+                    Ok(true) => output.push((tick, local_entity, component_kind)),
+                    Ok(false) => {}
+                    Err(_) => {
+                        warn!(
+                            "Remote World Manager: cannot read malformed component update message"
+                        );
+                    }
                 }
-
-                output.push((tick, local_entity, component_kind));
             }
         }
         output
@@ -236,6 +298,7 @@ impl RemoteWorldWaitlist {
         local_converter: &dyn LocalEntityAndGlobalEntityConverter,
         world_converter: &dyn EntityAndGlobalEntityConverter<E>,
         world: &mut W,
+        mut validator: Option<&mut dyn UpdateValidator>,
         now: &Instant,
     ) -> Vec<(Tick, RemoteEntity, ComponentKind)> {
         let mut output = Vec::new();
@@ -249,7 +312,13 @@ impl RemoteWorldWaitlist {
                 let component_key = (remote_entity, component_kind);
                 let mut remove_entry = false;
                 if let Some(component_map) = self.update_waitlist_map.get_mut(&component_key) {
-                    component_map.remove(&ready_update.field_id());
+                    let field_id = ready_update.field_id();
+                    if let Some(handles) = component_map.get_mut(&field_id) {
+                        handles.retain(|handle| self.update_waitlist_store.contains(handle));
+                        if handles.is_empty() {
+                            component_map.remove(&field_id);
+                        }
+                    }
                     if component_map.is_empty() {
                         remove_entry = true;
                     }
@@ -265,20 +334,21 @@ impl RemoteWorldWaitlist {
                     .global_entity_to_entity(&global_entity)
                     .unwrap();
 
-                if world
-                    .component_apply_field_update(
-                        local_converter,
-                        &world_entity,
-                        &component_kind,
-                        ready_update,
-                    )
-                    .is_err()
-                {
-                    warn!("Remote World Manager: cannot read malformed complete waitlisted component update message");
-                    continue;
+                match apply_validated_update(
+                    world,
+                    local_converter,
+                    validator.as_mut().map(|validator| &mut **validator as _),
+                    &global_entity,
+                    &world_entity,
+                    &component_kind,
+                    IncomingUpdate::Field(ready_update),
+                ) {
+                    Ok(true) => output.push((tick, remote_entity, component_kind)),
+                    Ok(false) => {}
+                    Err(_) => {
+                        warn!("Remote World Manager: cannot read malformed complete waitlisted component update message");
+                    }
                 }
-
-                output.push((tick, remote_entity, component_kind));
             }
         }
 
@@ -0,0 +1,153 @@
+use std::{
+    hash::Hash,
+    sync::{Arc, Mutex},
+};
+
+use naia_serde::SerdeErr;
+
+use crate::{
+    ComponentFieldUpdate, ComponentKind, ComponentUpdate, GlobalEntity,
+    LocalEntityAndGlobalEntityConverter, PropertyMutate, PropertyMutator, Replicate,
+    WorldMutType,
+};
+
+/// Outcome of checking a component update received from a remote host.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UpdateValidation {
+    /// Apply the update as received.
+    Accept,
+    /// Apply the update with the validator's changes to the proposed value.
+    Clamp,
+    /// Discard the update, keeping the current value.
+    Reject,
+}
+
+/// Checks component updates received from a remote host before they are
+/// applied to the world.
+pub trait UpdateValidator {
+    /// Returns whether updates to components of `component_kind` are checked.
+    fn validates(&self, component_kind: &ComponentKind) -> bool;
+    /// Decides whether `proposed`, the state a component on `global_entity`
+    /// would have after an update, may replace `current`. May change
+    /// `proposed` when returning [`UpdateValidation::Clamp`], in which case
+    /// the changed `proposed` is what gets applied.
+    fn validate(
+        &mut self,
+        global_entity: &GlobalEntity,
+        current: &dyn Replicate,
+        proposed: &mut dyn Replicate,
+    ) -> UpdateValidation;
+}
+
+/// An incoming update to one component, whole or a single field.
+pub(crate) enum IncomingUpdate {
+    Component(ComponentUpdate),
+    Field(ComponentFieldUpdate),
+}
+
+impl IncomingUpdate {
+    fn apply_to(
+        self,
+        component: &mut dyn Replicate,
+        converter: &dyn LocalEntityAndGlobalEntityConverter,
+    ) -> Result<(), SerdeErr> {
+        match self {
+            Self::Component(update) => component.read_apply_update(converter, update),
+            Self::Field(update) => component.read_apply_field_update(converter, update),
+        }
+    }
+
+    fn apply_to_world<E: Copy + Eq + Hash + Send + Sync, W: WorldMutType<E>>(
+        self,
+        world: &mut W,
+        converter: &dyn LocalEntityAndGlobalEntityConverter,
+        world_entity: &E,
+        component_kind: &ComponentKind,
+    ) -> Result<(), SerdeErr> {
+        match self {
+            Self::Component(update) => {
+                world.component_apply_update(converter, world_entity, component_kind, update)
+            }
+            Self::Field(update) => {
+                world.component_apply_field_update(converter, world_entity, component_kind, update)
+            }
+        }
+    }
+
+    fn duplicate(&self) -> Self {
+        match self {
+            Self::Component(update) => Self::Component(update.clone()),
+            Self::Field(update) => Self::Field(update.clone()),
+        }
+    }
+}
+
+/// Records which fields of the proposed copy a validator changes.
+#[derive(Clone, Default)]
+struct ChangedFields(Arc<Mutex<Vec<u8>>>);
+
+impl ChangedFields {
+    fn take(&self) -> Vec<u8> {
+        std::mem::take(&mut *self.0.lock().unwrap_or_else(|e| e.into_inner()))
+    }
+}
+
+impl PropertyMutate for ChangedFields {
+    fn mutate(&mut self, property_index: u8) -> bool {
+        let mut changed = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        if !changed.contains(&property_index) {
+            changed.push(property_index);
+        }
+        true
+    }
+}
+
+/// Applies `update` to a component of `world_entity`, first checking it
+/// with `validator` if there is one for this kind of component. Returns
+/// false if the update was rejected and left unapplied.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn apply_validated_update<E: Copy + Eq + Hash + Send + Sync, W: WorldMutType<E>>(
+    world: &mut W,
+    converter: &dyn LocalEntityAndGlobalEntityConverter,
+    validator: Option<&mut dyn UpdateValidator>,
+    global_entity: &GlobalEntity,
+    world_entity: &E,
+    component_kind: &ComponentKind,
+    update: IncomingUpdate,
+) -> Result<bool, SerdeErr> {
+    let Some(validator) = validator.filter(|validator| validator.validates(component_kind)) else {
+        update.apply_to_world(world, converter, world_entity, component_kind)?;
+        return Ok(true);
+    };
+    let Some(current) = world
+        .component_of_kind(world_entity, component_kind)
+        .map(|component| component.copy_to_box())
+    else {
+        update.apply_to_world(world, converter, world_entity, component_kind)?;
+        return Ok(true);
+    };
+
+    // Apply the update to a copy first, so a rejected update never touches the world
+    let mut proposed = current.copy_to_box();
+    update.duplicate().apply_to(proposed.as_mut(), converter)?;
+    let changed_fields = ChangedFields::default();
+    proposed.set_mutator(&PropertyMutator::new(changed_fields.clone()));
+
+    match validator.validate(global_entity, current.as_ref(), proposed.as_mut()) {
+        UpdateValidation::Reject => Ok(false),
+        UpdateValidation::Accept => {
+            update.apply_to_world(world, converter, world_entity, component_kind)?;
+            Ok(true)
+        }
+        UpdateValidation::Clamp => {
+            update.apply_to_world(world, converter, world_entity, component_kind)?;
+            // Only the fields the validator changed are copied over
+            if let Some(mut live) = world.component_mut_of_kind(world_entity, component_kind) {
+                for field_index in changed_fields.take() {
+                    live.mirror_single_field(field_index, proposed.as_ref());
+                }
+            }
+            Ok(true)
+        }
+    }
+}
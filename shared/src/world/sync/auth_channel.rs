@@ -123,12 +123,15 @@ impl AuthChannel {
                 );
 
                 match (from_status, next_status) {
-                    (EntityAuthStatus::Available, EntityAuthStatus::Requested)
+                    (EntityAuthStatus::Available, EntityAuthStatus::Available)
+                    | (EntityAuthStatus::Available, EntityAuthStatus::Requested)
                     | (EntityAuthStatus::Available, EntityAuthStatus::Granted)
                     | (EntityAuthStatus::Available, EntityAuthStatus::Denied)
                     | (EntityAuthStatus::Requested, EntityAuthStatus::Granted)
                     | (EntityAuthStatus::Requested, EntityAuthStatus::Denied)
                     | (EntityAuthStatus::Requested, EntityAuthStatus::Available)
+                    | (EntityAuthStatus::Denied, EntityAuthStatus::Requested)
+                    | (EntityAuthStatus::Denied, EntityAuthStatus::Denied)
                     | (EntityAuthStatus::Denied, EntityAuthStatus::Granted)
                     | (EntityAuthStatus::Denied, EntityAuthStatus::Available)
                     | (EntityAuthStatus::Granted, EntityAuthStatus::Available)
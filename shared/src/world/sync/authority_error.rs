@@ -22,4 +22,6 @@ pub enum AuthorityError {
     /// `request_resource_authority` / `release_resource_authority`
     /// commands when `R` is missing from the registry.
     ResourceNotPresent,
+    /// The user is an observer, which can never hold authority.
+    Observer,
 }
@@ -0,0 +1,43 @@
+/// Per-connection replication state of a component's `PropertyVec` and
+/// `PropertyMap` fields: the latest version of each field the remote host
+/// has acked, and the versions written into the update being built.
+#[derive(Clone, Default)]
+pub struct CollectionAcks {
+    acked: Vec<(u8, u64)>,
+    written: Vec<(u8, u64)>,
+}
+
+impl CollectionAcks {
+    /// No acked versions, so every collection field is written whole
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The latest acked version of the collection field at `field`
+    pub fn acked(&self, field: u8) -> Option<u64> {
+        self.acked
+            .iter()
+            .find(|(acked_field, _)| *acked_field == field)
+            .map(|(_, version)| *version)
+    }
+
+    /// Records that `version` of the collection field at `field` was written
+    pub fn record_written(&mut self, field: u8, version: u64) {
+        self.written.push((field, version));
+    }
+
+    pub(crate) fn written(&self) -> &[(u8, u64)] {
+        &self.written
+    }
+
+    pub(crate) fn ack(&mut self, field: u8, version: u64) {
+        match self
+            .acked
+            .iter_mut()
+            .find(|(acked_field, _)| *acked_field == field)
+        {
+            Some((_, acked)) => *acked = (*acked).max(version),
+            None => self.acked.push((field, version)),
+        }
+    }
+}
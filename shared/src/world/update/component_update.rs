@@ -8,6 +8,7 @@ use crate::{
 };
 
 /// A serialised component-field update payload together with its [`ComponentKind`] tag.
+#[derive(Clone)]
 pub struct ComponentUpdate {
     /// The kind of component this update applies to.
     pub kind: ComponentKind,
@@ -36,6 +37,7 @@ impl ComponentUpdate {
 }
 
 /// A single serialised field update payload for a component, identified by field index.
+#[derive(Clone)]
 pub struct ComponentFieldUpdate {
     id: u8,
     buffer: OwnedBitReader,
@@ -5,21 +5,33 @@ use std::{
     time::Duration,
 };
 
-use crate::world::update::user_diff_handler::UserDiffHandler;
+use crate::world::update::{collection_acks::CollectionAcks, user_diff_handler::UserDiffHandler};
 use crate::{
-    ComponentKind, DiffMask, EntityAndGlobalEntityConverter, GlobalEntity, GlobalWorldManagerType,
-    Instant, PacketIndex, WorldRefType,
+    sequence_greater_than, ComponentKind, DiffMask, EntityAndGlobalEntityConverter, GlobalEntity,
+    GlobalWorldManagerType, Instant, PacketIndex, WorldRefType,
 };
 
 const DROP_UPDATE_RTT_FACTOR: f32 = 1.5;
 
 type SentUpdatesMap = HashMap<PacketIndex, (Instant, HashMap<(GlobalEntity, ComponentKind), DiffMask>)>;
+type BaselinePacketsMap = HashMap<PacketIndex, (Instant, Vec<(GlobalEntity, ComponentKind)>)>;
+type CollectionPacketsMap =
+    HashMap<PacketIndex, (Instant, Vec<(GlobalEntity, ComponentKind, Vec<(u8, u64)>)>)>;
 
 pub struct EntityUpdateManager {
     address: Option<SocketAddr>,
     diff_handler: UserDiffHandler,
     sent_updates: SentUpdatesMap,
     last_update_packet_index: PacketIndex,
+    /// Baseline-delta components: masks sent in packets not yet acked,
+    /// oldest first. Every new update re-sends their union, so the receiver
+    /// converges as soon as any one packet arrives.
+    baseline_in_flight: HashMap<(GlobalEntity, ComponentKind), Vec<(PacketIndex, DiffMask)>>,
+    baseline_packets: BaselinePacketsMap,
+    /// Collection field versions the remote host has acked, which element
+    /// deltas are written against
+    collection_acks: HashMap<(GlobalEntity, ComponentKind), CollectionAcks>,
+    collection_packets: CollectionPacketsMap,
 }
 
 impl EntityUpdateManager {
@@ -32,6 +44,10 @@ impl EntityUpdateManager {
             diff_handler: UserDiffHandler::new(global_world_manager),
             sent_updates: HashMap::new(),
             last_update_packet_index: 0,
+            baseline_in_flight: HashMap::new(),
+            baseline_packets: HashMap::new(),
+            collection_acks: HashMap::new(),
+            collection_packets: HashMap::new(),
         }
     }
 
@@ -86,12 +102,24 @@ impl EntityUpdateManager {
             .or_diff_mask(entity, component_kind, new_diff_mask);
     }
 
+    /// The fields to write for this component. For baseline-delta
+    /// components this is every field changed since the last acked update,
+    /// not just since the last send.
     pub fn get_diff_mask(
         &self,
         entity: &GlobalEntity,
         component_kind: &ComponentKind,
+        baseline: bool,
     ) -> DiffMask {
-        self.diff_handler.diff_mask_snapshot(entity, component_kind)
+        let mut diff_mask = self.diff_handler.diff_mask_snapshot(entity, component_kind);
+        if baseline {
+            if let Some(in_flight) = self.baseline_in_flight.get(&(*entity, *component_kind)) {
+                for (_, sent_mask) in in_flight {
+                    diff_mask.or(sent_mask);
+                }
+            }
+        }
+        diff_mask
     }
 
     pub fn clear_diff_mask(&mut self, entity: &GlobalEntity, component_kind: &ComponentKind) {
@@ -104,6 +132,8 @@ impl EntityUpdateManager {
     }
 
     pub fn deregister_component(&mut self, entity: &GlobalEntity, component_kind: &ComponentKind) {
+        self.baseline_in_flight.remove(&(*entity, *component_kind));
+        self.collection_acks.remove(&(*entity, *component_kind));
         self.diff_handler
             .deregister_component(entity, component_kind);
     }
@@ -113,7 +143,6 @@ impl EntityUpdateManager {
         self.diff_handler.receiver_count()
     }
 
-    #[cfg(feature = "test_utils")]
     pub fn dirty_candidates_len(&self) -> usize {
         self.diff_handler.dirty_candidates_count()
     }
@@ -140,6 +169,52 @@ impl EntityUpdateManager {
                 self.dropped_update_cleanup(packet_index);
             }
         }
+
+        {
+            let mut dropped_packets = Vec::new();
+            for (packet_index, (time_sent, _)) in &self.baseline_packets {
+                if time_sent.elapsed(now) > drop_duration {
+                    dropped_packets.push(*packet_index);
+                }
+            }
+
+            for packet_index in dropped_packets {
+                self.dropped_baseline_cleanup(packet_index);
+            }
+        }
+
+        // A lost collection delta needs no cleanup: the next update is
+        // written against the last acked version again.
+        self.collection_packets
+            .retain(|_, (time_sent, _)| time_sent.elapsed(now) <= drop_duration);
+    }
+
+    // A lost baseline-delta packet needs no retransmission while the
+    // component keeps changing, since the next update re-sends its fields.
+    // Marking them dirty again only matters once the component goes idle.
+    fn dropped_baseline_cleanup(&mut self, dropped_packet_index: PacketIndex) {
+        let Some((_, components)) = self.baseline_packets.remove(&dropped_packet_index) else {
+            return;
+        };
+        for component_index in components {
+            let Some(in_flight) = self.baseline_in_flight.get_mut(&component_index) else {
+                continue;
+            };
+            let Some(position) = in_flight
+                .iter()
+                .position(|(packet_index, _)| *packet_index == dropped_packet_index)
+            else {
+                continue;
+            };
+            let (_, diff_mask) = in_flight.remove(position);
+            if in_flight.is_empty() {
+                self.baseline_in_flight.remove(&component_index);
+            }
+            let (entity, component) = component_index;
+            if self.diff_handler_has_component(&entity, &component) {
+                self.or_diff_mask(&entity, &component, &diff_mask);
+            }
+        }
     }
 
     fn dropped_update_cleanup(&mut self, dropped_packet_index: PacketIndex) {
@@ -172,6 +247,68 @@ impl EntityUpdateManager {
 
     pub fn notify_packet_delivered(&mut self, packet_index: PacketIndex) {
         self.sent_updates.remove(&packet_index);
+
+        // The acked packet is the new baseline: it carried every field of
+        // the older in-flight packets, so those are superseded too.
+        if let Some((_, components)) = self.baseline_packets.remove(&packet_index) {
+            for component_index in components {
+                let Some(in_flight) = self.baseline_in_flight.get_mut(&component_index) else {
+                    continue;
+                };
+                in_flight.retain(|(sent_index, _)| sequence_greater_than(*sent_index, packet_index));
+                if in_flight.is_empty() {
+                    self.baseline_in_flight.remove(&component_index);
+                }
+            }
+        }
+
+        if let Some((_, components)) = self.collection_packets.remove(&packet_index) {
+            for (entity, component_kind, versions) in components {
+                if !self.diff_handler_has_component(&entity, &component_kind) {
+                    continue;
+                }
+                let acks = self
+                    .collection_acks
+                    .entry((entity, component_kind))
+                    .or_default();
+                for (field, version) in versions {
+                    acks.ack(field, version);
+                }
+            }
+        }
+    }
+
+    /// The collection field versions the remote host has acked for this
+    /// component
+    pub fn collection_acks(
+        &self,
+        entity: &GlobalEntity,
+        component_kind: &ComponentKind,
+    ) -> CollectionAcks {
+        self.collection_acks
+            .get(&(*entity, *component_kind))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Remembers the collection field versions written into `packet_index`,
+    /// to be acked once it is delivered
+    pub fn record_collection_writes(
+        &mut self,
+        now: &Instant,
+        packet_index: &PacketIndex,
+        global_entity: &GlobalEntity,
+        component_kind: &ComponentKind,
+        acks: &CollectionAcks,
+    ) {
+        if acks.written().is_empty() {
+            return;
+        }
+        self.collection_packets
+            .entry(*packet_index)
+            .or_insert_with(|| (now.clone(), Vec::new()))
+            .1
+            .push((*global_entity, *component_kind, acks.written().to_vec()));
     }
 
     pub fn record_update(
@@ -181,9 +318,24 @@ impl EntityUpdateManager {
         global_entity: &GlobalEntity,
         component_kind: &ComponentKind,
         diff_mask: DiffMask,
+        baseline: bool,
     ) {
         self.last_update_packet_index = *packet_index;
 
+        if baseline {
+            self.baseline_in_flight
+                .entry((*global_entity, *component_kind))
+                .or_default()
+                .push((*packet_index, diff_mask));
+            self.baseline_packets
+                .entry(*packet_index)
+                .or_insert_with(|| (now.clone(), Vec::new()))
+                .1
+                .push((*global_entity, *component_kind));
+            self.clear_diff_mask(global_entity, component_kind);
+            return;
+        }
+
         // place diff mask in a special transmission record - like map
         if !self.sent_updates.contains_key(packet_index) {
             self.sent_updates
@@ -74,6 +74,18 @@ impl GlobalDiffHandler {
         self.mut_receiver_builders.contains_key(&(*global_entity, *component_kind))
     }
 
+    /// Returns every receiver built for `(global_entity, component_kind)`, one per user it's replicated to.
+    pub fn receivers(
+        &self,
+        global_entity: &GlobalEntity,
+        component_kind: &ComponentKind,
+    ) -> Vec<MutReceiver> {
+        self.mut_receiver_builders
+            .get(&(*global_entity, *component_kind))
+            .map(MutReceiverBuilder::receivers)
+            .unwrap_or_default()
+    }
+
     /// Creates a `MutSender`/`MutReceiverBuilder` pair for `(global_entity, component_kind)` and returns the sender.
     pub fn register_component(
         &mut self,
@@ -1,5 +1,6 @@
 pub mod atomic_bit_set;
 pub mod atomic_diff_mask;
+pub mod collection_acks;
 pub mod component_update;
 pub mod diff_mask;
 pub mod entity_update_manager;
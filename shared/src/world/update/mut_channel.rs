@@ -266,6 +266,8 @@ pub trait MutChannelType: Send + Sync {
     fn new_receiver(&mut self, address: &Option<SocketAddr>) -> Option<MutReceiver>;
     /// Notifies all receivers that property `diff` has changed.
     fn send(&self, diff: u8);
+    /// Returns every receiver created so far.
+    fn receivers(&self) -> Vec<MutReceiver>;
 }
 
 /// Shared mutation channel that connects a component's property mutator to all interested receivers.
@@ -312,6 +314,14 @@ impl MutChannel {
         }
         false
     }
+
+    /// Returns every receiver created so far, or none if the channel lock is poisoned.
+    pub fn receivers(&self) -> Vec<MutReceiver> {
+        if let Ok(data) = self.data.as_ref().read() {
+            return data.receivers();
+        }
+        Vec::new()
+    }
 }
 
 // MutReceiver — atomic, lock-free hot path.
@@ -399,6 +409,14 @@ impl MutReceiver {
             }
         }
     }
+
+    /// Replaces the diff mask with `mask`, as taken by `mask_snapshot()`.
+    pub fn restore_mask(&self, mask: &DiffMask) {
+        self.clear_mask();
+        if !mask.is_clear() {
+            self.or_mask(mask);
+        }
+    }
 }
 
 /// Write-only handle that forwards property-mutation notifications into a [`MutChannel`].
@@ -440,6 +458,11 @@ impl MutReceiverBuilder {
     pub fn build(&self, address: &Option<SocketAddr>) -> Option<MutReceiver> {
         self.channel.new_receiver(address)
     }
+
+    /// Returns every receiver built so far.
+    pub fn receivers(&self) -> Vec<MutReceiver> {
+        self.channel.receivers()
+    }
 }
 
 #[cfg(test)]
@@ -267,7 +267,6 @@ impl UserDiffHandler {
         self.receivers.len()
     }
 
-    #[cfg(feature = "test_utils")]
     pub fn dirty_candidates_count(&self) -> usize {
         self.receivers.values().filter(|r| !r.diff_mask_is_clear()).count()
     }
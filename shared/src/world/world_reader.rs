@@ -36,7 +36,7 @@ impl WorldReader {
         Self::read_updates(world_manager, component_kinds, tick, reader)?;
 
         // read entity messages
-        Self::read_messages(world_manager, component_kinds, reader)?;
+        Self::read_messages(world_manager, component_kinds, tick, reader)?;
 
         Ok(())
     }
@@ -45,6 +45,7 @@ impl WorldReader {
     fn read_messages(
         world_manager: &mut LocalWorldManager,
         component_kinds: &ComponentKinds,
+        tick: &Tick,
         reader: &mut BitReader,
     ) -> Result<(), SerdeErr> {
         let mut last_read_id: Option<MessageIndex> = None;
@@ -56,7 +57,13 @@ impl WorldReader {
                 break;
             }
 
-            Self::read_message(world_manager, component_kinds, reader, &mut last_read_id)?;
+            Self::read_message(
+                world_manager,
+                component_kinds,
+                tick,
+                reader,
+                &mut last_read_id,
+            )?;
         }
 
         Ok(())
@@ -70,6 +77,7 @@ impl WorldReader {
     fn read_message(
         world_manager: &mut LocalWorldManager,
         component_kinds: &ComponentKinds,
+        tick: &Tick,
         reader: &mut BitReader,
         last_read_id: &mut Option<MessageIndex>,
     ) -> Result<(), SerdeErr> {
@@ -115,6 +123,7 @@ impl WorldReader {
                     let new_component = component_kinds.read(reader, converter)?;
                     let new_component_kind = new_component.kind();
                     world_manager.insert_received_component(
+                        *tick,
                         &local_entity,
                         &new_component_kind,
                         new_component,
@@ -152,6 +161,7 @@ impl WorldReader {
                     EntityMessage::InsertComponent(local_entity, new_component_kind),
                 );
                 world_manager.insert_received_component(
+                    *tick,
                     &local_entity,
                     &new_component_kind,
                     new_component,
@@ -5,14 +5,16 @@ use std::{
 };
 
 use crate::{
+    connection::bandwidth_profiler::BandwidthProfiler,
     messages::channels::senders::indexed_message_writer::IndexedMessageWriter,
     world::{
         entity::entity_converters::GlobalWorldManagerType, host::host_world_manager::CommandId,
         local::local_world_manager::LocalWorldManager,
     },
-    BitWrite, BitWriter, ComponentKind, ComponentKinds,
+    BitCounter, BitWrite, BitWriter, CollectionAcks, ComponentKind, ComponentKinds, DiffMask,
     EntityAndGlobalEntityConverter, EntityCommand, EntityMessage, EntityMessageType, GlobalEntity,
-    Instant, MessageIndex, PacketIndex, Serde, WorldRefType,
+    Instant, LocalEntityAndGlobalEntityConverterMut, MessageIndex, PacketIndex, Replicate, Serde,
+    WorldRefType,
 };
 
 pub struct WorldWriter;
@@ -41,6 +43,7 @@ impl WorldWriter {
         world_events: &mut VecDeque<(CommandId, EntityCommand)>,
         update_events: &mut HashMap<GlobalEntity, HashSet<ComponentKind>>,
         entity_priority_order: Option<&[GlobalEntity]>,
+        mut profiler: Option<&mut BandwidthProfiler>,
     ) {
         // write entity updates
         Self::write_updates(
@@ -55,6 +58,7 @@ impl WorldWriter {
             has_written,
             update_events,
             entity_priority_order,
+            profiler.as_deref_mut(),
         );
 
         // write entity commands
@@ -69,6 +73,7 @@ impl WorldWriter {
             world_manager,
             has_written,
             world_events,
+            profiler,
         );
     }
 
@@ -84,7 +89,9 @@ impl WorldWriter {
         world_manager: &mut LocalWorldManager,
         has_written: &mut bool,
         next_send_commands: &mut VecDeque<(CommandId, EntityCommand)>,
+        mut profiler: Option<&mut BandwidthProfiler>,
     ) {
+        let start_bits = writer.bits_written();
         let mut last_counted_id: Option<MessageIndex> = None;
         let mut last_written_id: Option<MessageIndex> = None;
 
@@ -145,6 +152,24 @@ impl WorldWriter {
                 next_send_commands,
             );
 
+            if let Some(profiler) = profiler.as_deref_mut() {
+                let (command_id, command) = next_send_commands.front().unwrap();
+                profiler.record_entity_command(
+                    &command.entity(),
+                    counter.bits_needed(),
+                    world_manager.is_command_resend(command_id),
+                );
+                Self::profile_command_components(
+                    profiler,
+                    component_kinds,
+                    world,
+                    entity_converter,
+                    global_world_manager,
+                    world_manager,
+                    command,
+                );
+            }
+
             // pop command we've written
             next_send_commands.pop_front();
         }
@@ -152,6 +177,44 @@ impl WorldWriter {
         // Finish commands by writing false CommandContinue bit
         writer.release_bits(1);
         false.ser(writer);
+
+        if let Some(profiler) = profiler {
+            profiler.record_entity_commands(writer.bits_written() - start_bits);
+        }
+    }
+
+    /// Records the bits of the components written by an insert or spawn
+    #[allow(clippy::too_many_arguments)]
+    fn profile_command_components<E: Copy + Eq + Hash + Send + Sync, W: WorldRefType<E>>(
+        profiler: &mut BandwidthProfiler,
+        component_kinds: &ComponentKinds,
+        world: &W,
+        entity_converter: &dyn EntityAndGlobalEntityConverter<E>,
+        global_world_manager: &dyn GlobalWorldManagerType,
+        world_manager: &mut LocalWorldManager,
+        command: &EntityCommand,
+    ) {
+        let (global_entity, written_kinds) = match command {
+            EntityCommand::InsertComponent(global_entity, component_kind) => {
+                (global_entity, std::slice::from_ref(component_kind))
+            }
+            EntityCommand::SpawnWithComponents(global_entity, comp_kind_list) => {
+                (global_entity, comp_kind_list.as_slice())
+            }
+            _ => return,
+        };
+        let Ok(world_entity) = entity_converter.global_entity_to_entity(global_entity) else {
+            return;
+        };
+        let mut converter = world_manager.entity_converter_mut(global_world_manager);
+        for component_kind in written_kinds {
+            let Some(component) = world.component_of_kind(&world_entity, component_kind) else {
+                continue;
+            };
+            let mut counter = BitCounter::new(0, 0, u32::MAX);
+            component.write(component_kinds, &mut counter, &mut converter);
+            profiler.record_component(component_kind, counter.bits_needed());
+        }
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -760,7 +823,10 @@ impl WorldWriter {
         has_written: &mut bool,
         next_send_updates: &mut HashMap<GlobalEntity, HashSet<ComponentKind>>,
         entity_priority_order: Option<&[GlobalEntity]>,
+        mut profiler: Option<&mut BandwidthProfiler>,
     ) {
+        let start_bits = writer.bits_written();
+
         // When a priority order is supplied, iterate entities in that order
         // (filtered to those with pending updates this cycle). This is the
         // intra-section k-way merge ordering for priority-sorted entity bundles.
@@ -797,6 +863,8 @@ impl WorldWriter {
                 break;
             }
 
+            let entity_start_bits = writer.bits_written();
+
             // reserve ComponentContinue bit
             writer.reserve_bits(1);
             // write UpdateContinue bit
@@ -821,16 +889,25 @@ impl WorldWriter {
                 &world_entity,
                 has_written,
                 next_send_updates,
+                profiler.as_deref_mut(),
             );
 
             // write ComponentContinue finish bit, release
             writer.release_bits(1);
             false.ser(writer);
+
+            if let Some(profiler) = profiler.as_deref_mut() {
+                profiler.record_entity(&global_entity, writer.bits_written() - entity_start_bits);
+            }
         }
 
         // write EntityContinue finish bit, release
         writer.release_bits(1);
         false.ser(writer);
+
+        if let Some(profiler) = profiler {
+            profiler.record_entity_updates(writer.bits_written() - start_bits);
+        }
     }
 
     /// For a given entity, write component value updates into a packet
@@ -848,12 +925,15 @@ impl WorldWriter {
         world_entity: &E,
         has_written: &mut bool,
         next_send_updates: &mut HashMap<GlobalEntity, HashSet<ComponentKind>>,
+        mut profiler: Option<&mut BandwidthProfiler>,
     ) {
         let mut written_component_kinds = Vec::new();
+        let baseline = global_world_manager.entity_uses_baseline_delta(global_entity);
         let component_kind_set = next_send_updates.get(global_entity).unwrap();
         for component_kind in component_kind_set {
             // get diff mask
-            let diff_mask = world_manager.get_diff_mask(global_entity, component_kind);
+            let diff_mask = world_manager.get_diff_mask(global_entity, component_kind, baseline);
+            let mut collection_acks = world_manager.collection_acks(global_entity, component_kind);
 
             let mut converter = world_manager.entity_converter_mut(global_world_manager);
 
@@ -869,7 +949,12 @@ impl WorldWriter {
             world
                 .component_of_kind(world_entity, component_kind)
                 .expect("Component does not exist in World")
-                .write_update(&diff_mask, &mut counter, &mut converter);
+                .write_update_acked(
+                    &diff_mask,
+                    &mut counter,
+                    &mut converter,
+                    &mut collection_acks.clone(),
+                );
             if counter.overflowed() {
                 // if nothing useful has been written in this packet yet,
                 // send warning about size of component being too big
@@ -887,6 +972,20 @@ impl WorldWriter {
 
             *has_written = true;
 
+            if let Some(profiler) = profiler.as_deref_mut() {
+                profiler.record_component(component_kind, counter.bits_needed());
+                Self::profile_update_fields(
+                    profiler,
+                    component_kind,
+                    &*world
+                        .component_of_kind(world_entity, component_kind)
+                        .expect("Component does not exist in World"),
+                    &diff_mask,
+                    &mut converter,
+                    &collection_acks,
+                );
+            }
+
             // write ComponentContinue bit
             true.ser(writer);
             // write component kind
@@ -895,16 +994,25 @@ impl WorldWriter {
             world
                 .component_of_kind(world_entity, component_kind)
                 .expect("Component does not exist in World")
-                .write_update(&diff_mask, writer, &mut converter);
+                .write_update_acked(&diff_mask, writer, &mut converter, &mut collection_acks);
 
             written_component_kinds.push(*component_kind);
 
+            world_manager.record_collection_writes(
+                now,
+                packet_index,
+                global_entity,
+                component_kind,
+                &collection_acks,
+            );
+
             world_manager.record_update(
                 now,
                 packet_index,
                 global_entity,
                 component_kind,
                 diff_mask,
+                baseline,
             );
         }
 
@@ -917,6 +1025,43 @@ impl WorldWriter {
         }
     }
 
+    /// Records the bits each changed field of a component update takes, by
+    /// counting the update once per field
+    fn profile_update_fields(
+        profiler: &mut BandwidthProfiler,
+        component_kind: &ComponentKind,
+        component: &dyn Replicate,
+        diff_mask: &DiffMask,
+        converter: &mut dyn LocalEntityAndGlobalEntityConverterMut,
+        collection_acks: &CollectionAcks,
+    ) {
+        let mut count_bits = |mask: &DiffMask| {
+            let mut counter = BitCounter::new(0, 0, u32::MAX);
+            component.write_update_acked(
+                mask,
+                &mut counter,
+                converter,
+                &mut collection_acks.clone(),
+            );
+            counter.bits_needed()
+        };
+
+        // every field writes a changed bit; a field costs what it adds on top
+        let unchanged_bits = count_bits(&DiffMask::new(diff_mask.byte_number()));
+        for field_index in (0..=u8::MAX).take_while(|index| diff_mask.bit(*index).is_some()) {
+            if diff_mask.bit(field_index) != Some(true) {
+                continue;
+            }
+            let mut field_mask = DiffMask::new(diff_mask.byte_number());
+            field_mask.set_bit(field_index, true);
+            profiler.record_field(
+                component_kind,
+                field_index,
+                count_bits(&field_mask) - unchanged_bits,
+            );
+        }
+    }
+
     fn warn_overflow_update(component_name: String, bits_needed: u32, bits_free: u32) {
         panic!(
             "Packet Write Error: Blocking overflow detected! Data update of Component `{component_name}` requires {bits_needed} bits, but packet only has {bits_free} bits available! Recommended to slim down this Component"
@@ -504,6 +504,8 @@ fn protocol_type_order_mismatch_fails_fast_at_handshake() {
         .add_component::<Position>()
         .add_message::<Auth>()
         .add_message::<TestMessage>()
+        .add_message::<LargeTestMessage>()
+        .add_message::<EntityCommandMessage>()
         .add_message::<TestRequest>()
         .add_message::<TestResponse>()
         // Intentionally omit ReliableChannel to create protocol_id mismatch
@@ -28,7 +28,10 @@ pub struct ClientEvents {
     publishes: Vec<EntityKey>,
     unpublishes: Vec<EntityKey>,
     auth_grants: Vec<EntityKey>,
+    auth_queue_grants: Vec<EntityKey>,
     auth_denies: Vec<EntityKey>,
+    auth_group_grants: Vec<Vec<EntityKey>>,
+    auth_group_denies: Vec<Vec<EntityKey>>,
     auth_resets: Vec<EntityKey>,
     inserts: HashMap<ComponentKind, Vec<EntityKey>>,
     removes: ClientRemovesMap,
@@ -85,6 +88,13 @@ impl ClientEvents {
             }
         }
 
+        let mut auth_queue_grants = Vec::new();
+        for entity in world_events.read::<naia_client::EntityAuthQueueGrantedEvent>() {
+            if let Some(entity_key) = register_client_entity_event(scenario, &client_key, &entity) {
+                auth_queue_grants.push(entity_key);
+            }
+        }
+
         let mut auth_denies = Vec::new();
         for entity in world_events.read::<naia_client::EntityAuthDeniedEvent>() {
             if let Some(entity_key) = register_client_entity_event(scenario, &client_key, &entity) {
@@ -92,6 +102,26 @@ impl ClientEvents {
             }
         }
 
+        let mut auth_group_grants = Vec::new();
+        for entities in world_events.read::<naia_client::EntityAuthGroupGrantedEvent>() {
+            auth_group_grants.push(
+                entities
+                    .iter()
+                    .filter_map(|entity| register_client_entity_event(scenario, &client_key, entity))
+                    .collect(),
+            );
+        }
+
+        let mut auth_group_denies = Vec::new();
+        for entities in world_events.read::<naia_client::EntityAuthGroupDeniedEvent>() {
+            auth_group_denies.push(
+                entities
+                    .iter()
+                    .filter_map(|entity| register_client_entity_event(scenario, &client_key, entity))
+                    .collect(),
+            );
+        }
+
         let mut auth_resets = Vec::new();
         for entity in world_events.read::<naia_client::EntityAuthResetEvent>() {
             if let Some(entity_key) = register_client_entity_event(scenario, &client_key, &entity) {
@@ -102,7 +132,7 @@ impl ClientEvents {
         let mut inserts = HashMap::new();
         for (component_kind, entities) in world_events.take_inserts().unwrap_or_default() {
             let mut entity_keys = Vec::new();
-            for entity in entities {
+            for (_, entity) in entities {
                 if let Some(entity_key) =
                     register_client_entity_event(scenario, &client_key, &entity)
                 {
@@ -177,7 +207,10 @@ impl ClientEvents {
             publishes,
             unpublishes,
             auth_grants,
+            auth_queue_grants,
             auth_denies,
+            auth_group_grants,
+            auth_group_denies,
             auth_resets,
             inserts,
             removes,
@@ -410,6 +443,51 @@ impl ClientEvent for ClientEntityAuthGrantedEvent {
     }
 }
 
+// EntityAuthQueueGrantedEvent
+pub struct ClientEntityAuthQueueGrantedEvent;
+impl ClientEvent for ClientEntityAuthQueueGrantedEvent {
+    type Iter = std::vec::IntoIter<EntityKey>;
+    type Item = EntityKey;
+
+    fn iter(events: &mut ClientEvents) -> Self::Iter {
+        std::mem::take(&mut events.auth_queue_grants).into_iter()
+    }
+
+    fn has(events: &ClientEvents) -> bool {
+        !events.auth_queue_grants.is_empty()
+    }
+}
+
+// EntityAuthGroupGrantedEvent
+pub struct ClientEntityAuthGroupGrantedEvent;
+impl ClientEvent for ClientEntityAuthGroupGrantedEvent {
+    type Iter = std::vec::IntoIter<Vec<EntityKey>>;
+    type Item = Vec<EntityKey>;
+
+    fn iter(events: &mut ClientEvents) -> Self::Iter {
+        std::mem::take(&mut events.auth_group_grants).into_iter()
+    }
+
+    fn has(events: &ClientEvents) -> bool {
+        !events.auth_group_grants.is_empty()
+    }
+}
+
+// EntityAuthGroupDeniedEvent
+pub struct ClientEntityAuthGroupDeniedEvent;
+impl ClientEvent for ClientEntityAuthGroupDeniedEvent {
+    type Iter = std::vec::IntoIter<Vec<EntityKey>>;
+    type Item = Vec<EntityKey>;
+
+    fn iter(events: &mut ClientEvents) -> Self::Iter {
+        std::mem::take(&mut events.auth_group_denies).into_iter()
+    }
+
+    fn has(events: &ClientEvents) -> bool {
+        !events.auth_group_denies.is_empty()
+    }
+}
+
 // EntityAuthDeniedEvent
 pub struct ClientEntityAuthDeniedEvent;
 impl ClientEvent for ClientEntityAuthDeniedEvent {
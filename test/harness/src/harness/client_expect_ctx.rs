@@ -105,6 +105,30 @@ impl<'a> ClientExpectCtx<'a> {
         state.client().connection_status()
     }
 
+    /// Get the codec packets to the server are compressed with
+    pub fn compression_codec(&self) -> naia_shared::PacketCodec {
+        let state = self.scenario.client_state(&self.client_key);
+        state.client().compression_codec()
+    }
+
+    /// Network diagnostics for the connection to the server
+    pub fn connection_stats(&self) -> Option<naia_client::ConnectionStats> {
+        let state = self.scenario.client_state(&self.client_key);
+        state.client().connection_stats()
+    }
+
+    /// Where the bits sent to the server went, as profiled by the client
+    pub fn bandwidth_profile(&self) -> Option<naia_client::BandwidthProfile> {
+        let state = self.scenario.client_state(&self.client_key);
+        state.client().bandwidth_profile()
+    }
+
+    /// Check if packets to the server are compressed with the shared dictionary
+    pub fn uses_compression_dictionary(&self) -> bool {
+        let state = self.scenario.client_state(&self.client_key);
+        state.client().uses_compression_dictionary()
+    }
+
     /// Get the last identity token provided by the server to this client
     /// Returns None if no token has been received yet
     pub fn identity_token(&self) -> Option<naia_shared::IdentityToken> {
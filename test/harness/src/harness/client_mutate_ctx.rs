@@ -3,7 +3,7 @@ use std::net::SocketAddr;
 use naia_client::{ConnectionStatus, NaiaClientError};
 use naia_demo_world::{WorldMut, WorldRef};
 use naia_shared::{
-    Channel, IdentityToken, Message, Request, Response, ResponseReceiveKey, ResponseSendKey, Tick,
+    AuthorityError, Channel, IdentityToken, Message, Request, Response, ResponseReceiveKey, ResponseSendKey, Tick,
 };
 
 use crate::harness::{
@@ -92,6 +92,35 @@ impl<'a, 'scenario: 'a> ClientMutateCtx<'a, 'scenario> {
             .client_entity_mut(&self.client_key, entity)
     }
 
+    /// Request authority over several entities as one group, sent over
+    /// channel `C`
+    pub fn request_authority_group<C: Channel>(
+        &mut self,
+        entities: &[EntityKey],
+    ) -> Result<(), AuthorityError> {
+        let scenario = self.ctx.scenario_mut();
+        let user_key = scenario
+            .client_state(&self.client_key)
+            .user_key()
+            .ok_or(AuthorityError::NotInScope)?;
+        let mut world_entities = Vec::with_capacity(entities.len());
+        for key in entities {
+            let local_entity = scenario
+                .local_entity_for(key, &user_key)
+                .ok_or(AuthorityError::NotInScope)?;
+            let state = scenario.client_state(&self.client_key);
+            let entity_ref = state
+                .client()
+                .local_entity(state.world().proxy(), &local_entity)
+                .ok_or(AuthorityError::NotInScope)?;
+            world_entities.push(entity_ref.id());
+        }
+        let state = scenario.client_state_mut(&self.client_key);
+        state
+            .client_mut()
+            .entity_request_authority_group::<C>(&world_entities)
+    }
+
     // Connection Operations
 
     /// Get server address
@@ -106,12 +135,48 @@ impl<'a, 'scenario: 'a> ClientMutateCtx<'a, 'scenario> {
         state.client().connection_status()
     }
 
+    /// Get the codec packets to the server are compressed with
+    pub fn compression_codec(&self) -> naia_shared::PacketCodec {
+        let state = self.ctx.scenario().client_state(&self.client_key);
+        state.client().compression_codec()
+    }
+
+    /// Network diagnostics for the connection to the server
+    pub fn connection_stats(&self) -> Option<naia_client::ConnectionStats> {
+        let state = self.ctx.scenario().client_state(&self.client_key);
+        state.client().connection_stats()
+    }
+
+    /// Where the bits sent to the server went, as profiled by the client
+    pub fn bandwidth_profile(&self) -> Option<naia_client::BandwidthProfile> {
+        let state = self.ctx.scenario().client_state(&self.client_key);
+        state.client().bandwidth_profile()
+    }
+
+    /// Check if packets to the server are compressed with the shared dictionary
+    pub fn uses_compression_dictionary(&self) -> bool {
+        let state = self.ctx.scenario().client_state(&self.client_key);
+        state.client().uses_compression_dictionary()
+    }
+
     /// Disconnect from server
     pub fn disconnect(&mut self) {
         let state = self.ctx.scenario_mut().client_state_mut(&self.client_key);
         state.client_mut().disconnect();
     }
 
+    /// Start recording everything this client receives into a demo
+    pub fn start_demo_recording(&mut self) {
+        let state = self.ctx.scenario_mut().client_state_mut(&self.client_key);
+        state.client_mut().start_demo_recording();
+    }
+
+    /// Stop recording and return the demo bytes
+    pub fn stop_demo_recording(&mut self) -> Option<Vec<u8>> {
+        let state = self.ctx.scenario_mut().client_state_mut(&self.client_key);
+        state.client_mut().stop_demo_recording()
+    }
+
     // Entity Operations
 
     /// Get all entities as EntityKeys
@@ -25,9 +25,10 @@ mod users;
 pub use client_entity::{ClientEntityMut, ClientEntityRef};
 pub use client_events::{
     ClientConnectEvent, ClientDespawnEntityEvent, ClientDisconnectEvent,
-    ClientEntityAuthDeniedEvent, ClientEntityAuthGrantedEvent, ClientEntityAuthResetEvent,
-    ClientErrorEvent, ClientPublishEntityEvent, ClientRejectEvent, ClientServerTickEvent,
-    ClientSpawnEntityEvent, ClientTickEvent, ClientUnpublishEntityEvent,
+    ClientEntityAuthDeniedEvent, ClientEntityAuthGrantedEvent, ClientEntityAuthGroupDeniedEvent,
+    ClientEntityAuthGroupGrantedEvent, ClientEntityAuthQueueGrantedEvent,
+    ClientEntityAuthResetEvent, ClientErrorEvent, ClientPublishEntityEvent, ClientRejectEvent,
+    ClientServerTickEvent, ClientSpawnEntityEvent, ClientTickEvent, ClientUnpublishEntityEvent,
 };
 pub use client_expect_ctx::ClientExpectCtx;
 pub use entity_owner::EntityOwner;
@@ -1,6 +1,8 @@
 use naia_demo_world::WorldRef;
-use naia_server::{RoomKey, UserRef as NaiaUserRef};
-use naia_shared::WorldRefType;
+use naia_server::{
+    BandwidthProfile, ConnectionStats, RoomKey, ServerSnapshot, UserRef as NaiaUserRef,
+};
+use naia_shared::{BigMapKey, EntityAndGlobalEntityConverter, WorldRefType};
 
 use crate::{
     harness::{
@@ -230,6 +232,51 @@ impl<'a> ServerExpectCtx<'a> {
         })
     }
 
+    /// Network diagnostics for a client's connection, as seen by the server
+    pub fn connection_stats(&self, client_key: &ClientKey) -> Option<ConnectionStats> {
+        let user_key = self.scenario.client_to_user_key(client_key)?;
+        let (server, _) = self.scenario.server_and_registry()?;
+        server.connection_stats(&user_key)
+    }
+
+    /// A read-only snapshot of the server's users, rooms, scopes and
+    /// delegated entities
+    pub fn snapshot(&self) -> ServerSnapshot<TestEntity> {
+        let (server, _) = self.scenario.server_and_registry().unwrap();
+        server.snapshot()
+    }
+
+    /// The key a client's user goes by in a server snapshot
+    pub fn snapshot_user_key(&self, client_key: &ClientKey) -> Option<u64> {
+        let user_key = self.scenario.client_to_user_key(client_key)?;
+        Some(user_key.to_u64())
+    }
+
+    /// Where the bits sent to a client went, as profiled by the server
+    pub fn bandwidth_profile(&self, client_key: &ClientKey) -> Option<BandwidthProfile> {
+        let user_key = self.scenario.client_to_user_key(client_key)?;
+        let (server, _) = self.scenario.server_and_registry()?;
+        server.bandwidth_profile(&user_key)
+    }
+
+    /// Where the bits sent to all clients went, as profiled by the server
+    pub fn global_bandwidth_profile(&self) -> BandwidthProfile {
+        let (server, _) = self.scenario.server_and_registry().unwrap();
+        server.global_bandwidth_profile()
+    }
+
+    /// Bits a bandwidth profile attributes to an entity
+    pub fn entity_bandwidth_bits(&self, profile: &BandwidthProfile, entity_key: &EntityKey) -> u64 {
+        let Some(entity) = self.scenario.entity_registry().server_entity(entity_key) else {
+            return 0;
+        };
+        let (server, _) = self.scenario.server_and_registry().unwrap();
+        let Ok(global_entity) = server.entity_to_global_entity(&entity) else {
+            return 0;
+        };
+        profile.entities.get(&global_entity).copied().unwrap_or(0)
+    }
+
     /// Read the sender-wide (global) priority gain override for an entity.
     /// Returns `None` when no override is in effect (default 1.0 applies).
     pub fn global_entity_gain(&self, entity_key: &EntityKey) -> Option<f32> {
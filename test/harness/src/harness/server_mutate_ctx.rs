@@ -1,10 +1,15 @@
+use std::collections::HashMap;
+
 use log::warn;
 
 use naia_demo_world::{WorldMut, WorldRef};
-use naia_server::{NaiaServerError, RoomKey, TickBufferMessages};
+use naia_server::{
+    Historian, NaiaServerError, RoomKey, ServerSnapshot, TickBufferMessages, WorldSnapshotError,
+};
 use naia_shared::{
-    generate_identity_token, Channel, IdentityToken, Message, Request, Response,
-    ResponseReceiveKey, ResponseSendKey, Tick, WorldRefType,
+    generate_identity_token, AuthorityError, BigMapKey, Channel, ComponentKind, ConnectionStats,
+    EntityAndGlobalEntityConverter, GlobalEntity, IdentityToken, Message, Request, Response,
+    ResponseReceiveKey, ResponseSendKey, Tick, WorldMutType, WorldRefType,
 };
 
 use crate::harness::{
@@ -15,6 +20,7 @@ use crate::harness::{
     user_scope::{UserScopeMut, UserScopeRef},
     ClientKey, EntityKey,
 };
+use crate::TestEntity;
 
 /// Lightweight handle for server-side mutations
 /// Provides direct pass-through to core Server API with EntityKey resolution
@@ -110,6 +116,41 @@ impl<'a, 'scenario: 'a> ServerMutateCtx<'a, 'scenario> {
         (entity_key, result)
     }
 
+    /// Give a client authority over several entities as one group
+    pub fn give_authority_group(
+        &mut self,
+        client_key: &ClientKey,
+        entities: &[EntityKey],
+    ) -> Result<(), AuthorityError> {
+        let scenario = self.ctx.scenario_mut();
+        let user_key = scenario
+            .client_to_user_key(client_key)
+            .ok_or(AuthorityError::NotInScope)?;
+        let world_entities = self.server_entities(entities)?;
+        let (server, _, _, _) = self.ctx.scenario_mut().split_for_server_mut();
+        server.give_authority_group(&user_key, &world_entities)
+    }
+
+    /// Take authority over several entities back to the server as one group
+    pub fn take_authority_group(&mut self, entities: &[EntityKey]) -> Result<(), AuthorityError> {
+        let world_entities = self.server_entities(entities)?;
+        let (server, _, _, _) = self.ctx.scenario_mut().split_for_server_mut();
+        server.take_authority_group(&world_entities)
+    }
+
+    fn server_entities(&self, entities: &[EntityKey]) -> Result<Vec<TestEntity>, AuthorityError> {
+        entities
+            .iter()
+            .map(|key| {
+                self.ctx
+                    .scenario()
+                    .entity_registry()
+                    .server_entity(key)
+                    .ok_or(AuthorityError::NotInScope)
+            })
+            .collect()
+    }
+
     /// Despawn an entity by EntityKey
     pub fn despawn(&mut self, key: &EntityKey) {
         if let Some(mut entity_mut) = self.entity_mut(key) {
@@ -173,6 +214,51 @@ impl<'a, 'scenario: 'a> ServerMutateCtx<'a, 'scenario> {
         Some(UserScopeMut::new(scope, registry))
     }
 
+    /// Enable automatic spatial scoping keyed off component `R`.
+    pub fn enable_spatial_interest<R>(&mut self, config: naia_server::SpatialInterestConfig)
+    where
+        R: naia_shared::ReplicatedComponent + naia_server::SpatialPosition,
+    {
+        let (server, _, _, _) = self.ctx.scenario_mut().split_for_server_mut();
+        server.enable_spatial_interest::<R>(config);
+    }
+
+    /// Set a fixed spatial-interest viewpoint for a client's user.
+    pub fn set_user_viewpoint(&mut self, client_key: &ClientKey, x: f32, y: f32) {
+        let scenario = self.ctx.scenario_mut();
+        let Some(user_key) = scenario.client_to_user_key(client_key) else {
+            return;
+        };
+        let (server, _, _, _) = scenario.split_for_server_mut();
+        server.set_user_viewpoint(&user_key, x, y);
+    }
+
+    /// Remove a client's spatial-interest viewpoint.
+    pub fn clear_user_viewpoint(&mut self, client_key: &ClientKey) {
+        let scenario = self.ctx.scenario_mut();
+        let Some(user_key) = scenario.client_to_user_key(client_key) else {
+            return;
+        };
+        let (server, _, _, _) = scenario.split_for_server_mut();
+        server.clear_user_viewpoint(&user_key);
+    }
+
+    /// Check client-authoritative updates to component `R`, sending
+    /// corrections over channel `C`.
+    pub fn set_component_validator<R, C>(
+        &mut self,
+        validator: impl Fn(&naia_server::UserKey, &R, &mut R) -> naia_server::UpdateValidation
+            + Send
+            + Sync
+            + 'static,
+    ) where
+        R: naia_shared::ReplicatedComponent,
+        C: naia_shared::Channel,
+    {
+        let (server, _, _, _) = self.ctx.scenario_mut().split_for_server_mut();
+        server.set_component_validator::<R, C>(validator);
+    }
+
     // Entity Operations
 
     /// Get all entities as EntityKeys
@@ -187,6 +273,143 @@ impl<'a, 'scenario: 'a> ServerMutateCtx<'a, 'scenario> {
             .collect()
     }
 
+    /// Save every server-owned entity, room and resource to bytes
+    pub fn save_world(&self) -> Result<Vec<u8>, WorldSnapshotError> {
+        let scenario = self.ctx.scenario();
+        let (server, _) = scenario.server_and_registry().unwrap();
+        server.save_world(scenario.server_world_ref())
+    }
+
+    /// Restore bytes from `save_world`, registering an EntityKey for each
+    /// restored entity other than resources, in save order. Also returns the
+    /// saved-to-restored room key map.
+    pub fn restore_world(
+        &mut self,
+        bytes: &[u8],
+    ) -> Result<(Vec<EntityKey>, HashMap<RoomKey, RoomKey>), WorldSnapshotError> {
+        let scenario = self.ctx.scenario_mut();
+        let (server, world, registry, _) = scenario.split_for_server_mut();
+        let restored = server.restore_world(world.proxy_mut(), bytes)?;
+        let mut entity_keys = Vec::new();
+        for entity in &restored.entities {
+            if server.is_resource_entity(entity) {
+                continue;
+            }
+            let entity_key = registry.allocate_entity_key();
+            registry.register_server_entity(&entity_key, entity);
+            entity_keys.push(entity_key);
+        }
+        Ok((entity_keys, restored.rooms))
+    }
+
+    /// Get the server world's entity behind an EntityKey
+    pub fn world_entity(&self, key: &EntityKey) -> Option<TestEntity> {
+        self.ctx.scenario().entity_registry().server_entity(key)
+    }
+
+    /// Get the GlobalEntity the server assigned to an EntityKey
+    pub fn global_entity(&self, key: &EntityKey) -> Option<GlobalEntity> {
+        let scenario = self.ctx.scenario();
+        let entity = scenario.entity_registry().server_entity(key)?;
+        let (server, _) = scenario.server_and_registry().unwrap();
+        server.entity_to_global_entity(&entity).ok()
+    }
+
+    /// Enable the server's lag-compensation Historian
+    pub fn enable_historian(&mut self, max_ticks: u16) {
+        let (server, _, _, _) = self.ctx.scenario_mut().split_for_server_mut();
+        server.enable_historian(max_ticks);
+    }
+
+    /// Enable the Historian, recording only the component kinds in `filter`
+    pub fn enable_historian_filtered(
+        &mut self,
+        max_ticks: u16,
+        filter: impl IntoIterator<Item = ComponentKind>,
+    ) {
+        let (server, _, _, _) = self.ctx.scenario_mut().split_for_server_mut();
+        server.enable_historian_filtered(max_ticks, filter);
+    }
+
+    /// Get the Historian, if enabled
+    pub fn historian(&self) -> Option<&Historian> {
+        let (server, _) = self.ctx.scenario().server_and_registry().unwrap();
+        server.historian()
+    }
+
+    /// Get mutable access to the Historian, if enabled
+    pub fn historian_mut(&mut self) -> Option<&mut Historian> {
+        let (server, _, _, _) = self.ctx.scenario_mut().split_for_server_mut();
+        server.historian_mut()
+    }
+
+    /// Record the server world into the Historian at `tick`
+    pub fn record_historian_tick(&mut self, tick: Tick) {
+        let (server, world, _, _) = self.ctx.scenario_mut().split_for_server_mut();
+        server.record_historian_tick(world.proxy(), tick);
+    }
+
+    /// Run `f` against the server world rewound to `fraction` past `tick`
+    pub fn rewind_world<R>(
+        &mut self,
+        tick: Tick,
+        fraction: f32,
+        f: impl FnOnce(&mut WorldMut<'_>) -> R,
+    ) -> Option<R> {
+        let (server, world, _, _) = self.ctx.scenario_mut().split_for_server_mut();
+        server.rewind_world(world.proxy_mut(), tick, fraction, f)
+    }
+
+    /// Count of components with changes not yet sent, across all users
+    pub fn dirty_update_count(&self) -> usize {
+        self.ctx.scenario().total_dirty_update_count()
+    }
+
+    /// Start recording everything replicated to a client's user into a demo
+    pub fn start_demo_recording(&mut self, client_key: &ClientKey) -> bool {
+        let scenario = self.ctx.scenario_mut();
+        let Some(user_key) = scenario.client_to_user_key(client_key) else {
+            return false;
+        };
+        let (server, _, _, _) = scenario.split_for_server_mut();
+        server.start_demo_recording(&user_key)
+    }
+
+    /// Stop recording a client's user and return the demo bytes
+    pub fn stop_demo_recording(&mut self, client_key: &ClientKey) -> Option<Vec<u8>> {
+        let scenario = self.ctx.scenario_mut();
+        let user_key = scenario.client_to_user_key(client_key)?;
+        let (server, _, _, _) = scenario.split_for_server_mut();
+        server.stop_demo_recording(&user_key)
+    }
+
+    /// Network diagnostics for a client's connection, as seen by the server
+    pub fn connection_stats(&self, client_key: &ClientKey) -> Option<ConnectionStats> {
+        let scenario = self.ctx.scenario();
+        let user_key = scenario.client_to_user_key(client_key)?;
+        let (server, _) = scenario.server_and_registry().unwrap();
+        server.connection_stats(&user_key)
+    }
+
+    /// A read-only snapshot of the server's users, rooms, scopes and
+    /// delegated entities
+    pub fn snapshot(&self) -> ServerSnapshot<TestEntity> {
+        let (server, _) = self.ctx.scenario().server_and_registry().unwrap();
+        server.snapshot()
+    }
+
+    /// The key a client's user goes by in a server snapshot
+    pub fn snapshot_user_key(&self, client_key: &ClientKey) -> Option<u64> {
+        let user_key = self.ctx.scenario().client_to_user_key(client_key)?;
+        Some(user_key.to_u64())
+    }
+
+    /// Clear every client's bandwidth profile, and the global one
+    pub fn reset_bandwidth_profiles(&mut self) {
+        let (server, _, _, _) = self.ctx.scenario_mut().split_for_server_mut();
+        server.reset_bandwidth_profiles();
+    }
+
     /// Server-side outgoing bytes sent during the last completed tick.
     /// Used by wire-level tests (e.g. per-field-diff assertion).
     pub fn server_outgoing_bytes_last_tick(&self) -> u64 {
@@ -363,6 +586,17 @@ impl<'a, 'scenario: 'a> ServerMutateCtx<'a, 'scenario> {
         server.accept_connection(&user_key);
     }
 
+    /// Accept connection for a client as an observer
+    ///
+    /// Requires that the ClientKey has been mapped to a UserKey (via reading AuthEvent).
+    /// Panics if the mapping doesn't exist.
+    pub fn accept_observer_connection(&mut self, client_key: &ClientKey) {
+        let scenario = self.ctx.scenario_mut();
+        let user_key = scenario.client_to_user_key(client_key).unwrap();
+        let (server, _, _, _) = scenario.split_for_server_mut();
+        server.accept_observer_connection(&user_key);
+    }
+
     /// Reject connection
     ///
     /// # Note
@@ -477,6 +711,52 @@ impl<'a, 'scenario: 'a> ServerMutateCtx<'a, 'scenario> {
         }
     }
 
+    /// Add `member` to the EntitySet picked by `field` from `key`'s component
+    pub fn entity_set_insert<C: naia_shared::ReplicatedComponent>(
+        &mut self,
+        key: &EntityKey,
+        member: &EntityKey,
+        field: impl FnOnce(&mut C) -> &mut naia_shared::EntitySet,
+    ) -> bool {
+        self.with_entity_set(key, member, field, |entity_set, server, member| {
+            entity_set.insert(server, member)
+        })
+    }
+
+    /// Remove `member` from the EntitySet picked by `field` from `key`'s component
+    pub fn entity_set_remove<C: naia_shared::ReplicatedComponent>(
+        &mut self,
+        key: &EntityKey,
+        member: &EntityKey,
+        field: impl FnOnce(&mut C) -> &mut naia_shared::EntitySet,
+    ) -> bool {
+        self.with_entity_set(key, member, field, |entity_set, server, member| {
+            entity_set.remove(server, member)
+        })
+    }
+
+    fn with_entity_set<C: naia_shared::ReplicatedComponent>(
+        &mut self,
+        key: &EntityKey,
+        member: &EntityKey,
+        field: impl FnOnce(&mut C) -> &mut naia_shared::EntitySet,
+        f: impl FnOnce(&mut naia_shared::EntitySet, &naia_server::Server<TestEntity>, &TestEntity) -> bool,
+    ) -> bool {
+        let scenario = self.ctx.scenario_mut();
+        let registry = scenario.entity_registry();
+        let (Some(entity), Some(member)) =
+            (registry.server_entity(key), registry.server_entity(member))
+        else {
+            return false;
+        };
+        let (server, world, _, _) = scenario.split_for_server_mut();
+        let mut world_mut = world.proxy_mut();
+        let Some(mut component) = world_mut.component_mut::<C>(&entity) else {
+            return false;
+        };
+        f(field(&mut component), server, &member)
+    }
+
     /// Send message to user.
     ///
     /// Best-effort wrapper: if the user has disconnected between the harness
@@ -38,6 +38,26 @@ impl<'a> UserRef<'a> {
     pub fn room_keys(&self) -> Vec<RoomKey> {
         self.user.room_keys().copied().collect()
     }
+
+    /// Check if this user was accepted as an observer
+    pub fn is_observer(&self) -> bool {
+        self.user.is_observer()
+    }
+
+    /// Get this user's bandwidth budget override, if any
+    pub fn bandwidth_override(&self) -> Option<u32> {
+        self.user.bandwidth_override()
+    }
+
+    /// Get the codec packets to this user are compressed with
+    pub fn compression_codec(&self) -> naia_shared::PacketCodec {
+        self.user.compression_codec()
+    }
+
+    /// Check if packets to this user are compressed with the shared dictionary
+    pub fn uses_compression_dictionary(&self) -> bool {
+        self.user.uses_compression_dictionary()
+    }
 }
 
 /// Harness wrapper for UserMut that works with ClientKey instead of UserKey
@@ -67,6 +87,23 @@ impl<'a> UserMut<'a> {
         self.user.disconnect();
     }
 
+    /// Override this user's outbound bandwidth budget
+    pub fn set_bandwidth_override(&mut self, target_bytes_per_sec: u32) -> &mut Self {
+        self.user.set_bandwidth_override(target_bytes_per_sec);
+        self
+    }
+
+    /// Restore this user's configured outbound bandwidth budget
+    pub fn clear_bandwidth_override(&mut self) -> &mut Self {
+        self.user.clear_bandwidth_override();
+        self
+    }
+
+    /// Get this user's bandwidth budget override, if any
+    pub fn bandwidth_override(&self) -> Option<u32> {
+        self.user.bandwidth_override()
+    }
+
     /// Enter a room
     pub fn enter_room(&mut self, room_key: &RoomKey) -> &mut Self {
         self.user.enter_room(room_key);
@@ -20,13 +20,14 @@ pub use harness::{
 //client events
 pub use harness::{
     ClientConnectEvent, ClientDespawnEntityEvent, ClientDisconnectEvent,
-    ClientEntityAuthDeniedEvent, ClientEntityAuthGrantedEvent, ClientEntityAuthResetEvent,
-    ClientErrorEvent, ClientPublishEntityEvent, ClientRejectEvent, ClientServerTickEvent,
-    ClientSpawnEntityEvent, ClientTickEvent, ClientUnpublishEntityEvent,
+    ClientEntityAuthDeniedEvent, ClientEntityAuthGrantedEvent, ClientEntityAuthGroupDeniedEvent,
+    ClientEntityAuthGroupGrantedEvent, ClientEntityAuthQueueGrantedEvent,
+    ClientEntityAuthResetEvent, ClientErrorEvent, ClientPublishEntityEvent, ClientRejectEvent,
+    ClientServerTickEvent, ClientSpawnEntityEvent, ClientTickEvent, ClientUnpublishEntityEvent,
 };
 pub use test_protocol::{
-    protocol, Auth, EntityCommandMessage, ImmutableLabel, LargeTestMessage, Position,
-    TestMatchState, TestPlayerSelection, TestScore, Velocity,
+    protocol, Auth, EntityCommandMessage, ImmutableLabel, Inventory, LargeTestMessage, Position,
+    QuantizedTransform, Squad, TestMatchState, TestPlayerSelection, TestScore, Velocity,
 };
 
 // Re-export demo_world types for tests
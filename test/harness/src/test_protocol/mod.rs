@@ -1,7 +1,7 @@
 /// Minimal test protocol for E2E testing
 use naia_shared::{
-    Channel, ChannelDirection, ChannelMode, EntityProperty, Message, Property, Protocol,
-    ReliableSettings, Replicate, TickBufferSettings,
+    Channel, ChannelDirection, ChannelMode, EntityProperty, EntitySet, Interpolate, Message,
+    Property, PropertyMap, PropertyVec, Protocol, ReliableSettings, Replicate, TickBufferSettings,
 };
 
 #[derive(Message, PartialEq, Eq, Hash)]
@@ -139,6 +139,21 @@ impl Position {
     }
 }
 
+impl Interpolate for Position {
+    fn interpolate(&self, next: &Self, fraction: f32) -> Self {
+        Self::new(
+            self.x.interpolate(&next.x, fraction),
+            self.y.interpolate(&next.y, fraction),
+        )
+    }
+}
+
+impl naia_server::SpatialPosition for Position {
+    fn spatial_position(&self) -> (f32, f32) {
+        (*self.x, *self.y)
+    }
+}
+
 #[derive(Replicate)]
 pub struct Velocity {
     pub vx: Property<f32>,
@@ -151,6 +166,51 @@ impl Velocity {
     }
 }
 
+/// Component whose fields are quantized on the wire, covering every
+/// field-level encoding the Replicate derive supports.
+#[derive(Replicate)]
+pub struct QuantizedTransform {
+    #[replicate(quantize(min = -100.0, max = 100.0, precision = 0.01))]
+    pub translation: Property<[f32; 3]>,
+    #[replicate(quaternion(bits = 10))]
+    pub rotation: Property<[f32; 4]>,
+    #[replicate(normalized(bits = 12))]
+    pub facing: Property<[f32; 3]>,
+    #[replicate(quantize(min = 0, max = 10, bits = 8))]
+    pub scale: Property<f32>,
+}
+
+impl QuantizedTransform {
+    pub fn new(translation: [f32; 3], rotation: [f32; 4], facing: [f32; 3], scale: f32) -> Self {
+        Self::new_complete(translation, rotation, facing, scale)
+    }
+}
+
+/// Component with collection fields, replicated as element deltas.
+#[derive(Replicate)]
+pub struct Inventory {
+    pub items: PropertyVec<u16>,
+    pub counts: PropertyMap<u16, u32>,
+}
+
+impl Inventory {
+    pub fn new(items: Vec<u16>) -> Self {
+        Self::new_complete(items, Default::default())
+    }
+}
+
+/// Component referencing a group of other entities.
+#[derive(Replicate)]
+pub struct Squad {
+    pub members: EntitySet,
+}
+
+impl Default for Squad {
+    fn default() -> Self {
+        Self::new_complete()
+    }
+}
+
 /// Marker component that is replicated immutably.
 /// Used by Phase 5 spike tests to verify zero GlobalDiffHandler allocation.
 #[derive(Replicate)]
@@ -206,6 +266,9 @@ pub fn protocol() -> Protocol {
         .add_component::<Position>()
         .add_component::<Velocity>()
         .add_component::<ImmutableLabel>()
+        .add_component::<QuantizedTransform>()
+        .add_component::<Inventory>()
+        .add_component::<Squad>()
         .add_resource::<TestScore>()
         .add_resource::<TestMatchState>()
         .add_resource::<TestPlayerSelection>()
@@ -0,0 +1,260 @@
+//! End-to-end integration tests for group authority requests on delegated
+//! entities.
+//!
+//! A client requests authority over several entities as one transaction; the
+//! server grants all of them or none, and the client sees a single group
+//! event. The server can give and take authority over a group the same way.
+
+mod common;
+
+use naia_server::ReplicationConfig;
+use naia_shared::{AuthorityError, EntityAuthStatus};
+use naia_test_harness::{
+    test_protocol::ReliableChannel, ClientEntityAuthGrantedEvent, ClientEntityAuthGroupDeniedEvent,
+    ClientEntityAuthGroupGrantedEvent, ClientKey, EntityKey, Position, Scenario,
+};
+
+/// Spawns `count` delegated entities and waits until every client sees them
+/// as Available
+fn spawn_delegated(
+    scenario: &mut Scenario,
+    client_keys: &[ClientKey],
+    count: usize,
+) -> Vec<EntityKey> {
+    let room_key = scenario.last_room();
+    let entities: Vec<EntityKey> = (0..count)
+        .map(|_| {
+            let (entity, ()) = scenario.mutate(|ctx| {
+                ctx.server(|server| {
+                    server.spawn(|mut e| {
+                        e.insert_component(Position::new(0.0, 0.0))
+                            .configure_replication(ReplicationConfig::delegated())
+                            .enter_room(&room_key);
+                    })
+                })
+            });
+            entity
+        })
+        .collect();
+    expect_authority(
+        scenario,
+        client_keys,
+        &entities,
+        EntityAuthStatus::Available,
+    );
+    entities
+}
+
+fn expect_authority(
+    scenario: &mut Scenario,
+    client_keys: &[ClientKey],
+    entities: &[EntityKey],
+    status: EntityAuthStatus,
+) {
+    scenario.expect(|ctx| {
+        client_keys
+            .iter()
+            .all(|client_key| {
+                ctx.client(*client_key, |c| {
+                    entities
+                        .iter()
+                        .all(|entity| c.entity(entity).and_then(|e| e.authority()) == Some(status))
+                })
+            })
+            .then_some(())
+    });
+}
+
+#[test]
+fn group_request_is_granted_as_one() {
+    let mut scenario = Scenario::new();
+    let client_keys = common::server_with_clients(&mut scenario, &["alice", "bob"]);
+    let (alice, bob) = (client_keys[0], client_keys[1]);
+    let entities = spawn_delegated(&mut scenario, &client_keys, 3);
+
+    scenario.mutate(|ctx| {
+        ctx.client(alice, |c| {
+            c.request_authority_group::<ReliableChannel>(&entities)
+                .expect("group request is allowed");
+        })
+    });
+
+    // Group members don't fire per-entity grant events
+    let mut single_grants = 0;
+    let granted = scenario.expect(|ctx| {
+        ctx.client(alice, |c| {
+            while c.read_event::<ClientEntityAuthGrantedEvent>().is_some() {
+                single_grants += 1;
+            }
+            c.read_event::<ClientEntityAuthGroupGrantedEvent>()
+        })
+    });
+    assert_eq!(single_grants, 0);
+    assert_eq!(granted.len(), entities.len());
+    for entity in &entities {
+        assert!(granted.contains(entity));
+    }
+    expect_authority(
+        &mut scenario,
+        &[alice],
+        &entities,
+        EntityAuthStatus::Granted,
+    );
+    expect_authority(&mut scenario, &[bob], &entities, EntityAuthStatus::Denied);
+}
+
+#[test]
+fn group_request_is_denied_if_any_entity_is_held() {
+    let mut scenario = Scenario::new();
+    let client_keys = common::server_with_clients(&mut scenario, &["alice", "bob"]);
+    let (alice, bob) = (client_keys[0], client_keys[1]);
+    let entities = spawn_delegated(&mut scenario, &client_keys, 3);
+
+    // Bob holds the turret, then Alice asks for the whole vehicle
+    let turret = entities[1];
+    scenario.mutate(|ctx| {
+        ctx.client(bob, |c| {
+            c.entity_mut(&turret)
+                .expect("entity exists")
+                .request_authority()
+                .expect("request is allowed");
+        })
+    });
+    expect_authority(&mut scenario, &[bob], &[turret], EntityAuthStatus::Granted);
+    expect_authority(&mut scenario, &[alice], &[turret], EntityAuthStatus::Denied);
+    scenario.mutate(|ctx| {
+        ctx.client(alice, |c| {
+            c.request_authority_group::<ReliableChannel>(&entities)
+                .expect("group request is allowed");
+        })
+    });
+
+    let denied = scenario.expect(|ctx| {
+        ctx.client(alice, |c| {
+            c.read_event::<ClientEntityAuthGroupDeniedEvent>()
+        })
+    });
+    assert_eq!(denied.len(), entities.len());
+
+    // Alice got none of them; Bob still holds the turret
+    let others = [entities[0], entities[2]];
+    expect_authority(
+        &mut scenario,
+        &[alice],
+        &others,
+        EntityAuthStatus::Available,
+    );
+    expect_authority(&mut scenario, &[alice], &[turret], EntityAuthStatus::Denied);
+    expect_authority(&mut scenario, &[bob], &others, EntityAuthStatus::Available);
+    scenario.mutate(|ctx| {
+        ctx.client(bob, |c| {
+            assert_eq!(
+                c.entity(&turret).and_then(|e| e.authority()),
+                Some(EntityAuthStatus::Granted)
+            );
+        })
+    });
+}
+
+#[test]
+fn server_gives_and_takes_authority_over_a_group() {
+    let mut scenario = Scenario::new();
+    let client_keys = common::server_with_clients(&mut scenario, &["alice", "bob"]);
+    let (alice, bob) = (client_keys[0], client_keys[1]);
+    let entities = spawn_delegated(&mut scenario, &client_keys, 2);
+
+    scenario.mutate(|ctx| {
+        ctx.server(|server| {
+            server
+                .give_authority_group(&alice, &entities)
+                .expect("every entity is delegated and in scope");
+        })
+    });
+    expect_authority(
+        &mut scenario,
+        &[alice],
+        &entities,
+        EntityAuthStatus::Granted,
+    );
+    expect_authority(&mut scenario, &[bob], &entities, EntityAuthStatus::Denied);
+
+    scenario.mutate(|ctx| {
+        ctx.server(|server| {
+            server
+                .take_authority_group(&entities)
+                .expect("every entity is delegated");
+        })
+    });
+    expect_authority(
+        &mut scenario,
+        &client_keys,
+        &entities,
+        EntityAuthStatus::Denied,
+    );
+}
+
+#[test]
+fn server_group_give_fails_without_changes_if_any_entity_is_undelegated() {
+    let mut scenario = Scenario::new();
+    let client_keys = common::server_with_clients(&mut scenario, &["alice"]);
+    let alice = client_keys[0];
+    let mut entities = spawn_delegated(&mut scenario, &client_keys, 1);
+
+    let room_key = scenario.last_room();
+    let (plain, ()) = scenario.mutate(|ctx| {
+        ctx.server(|server| {
+            server.spawn(|mut e| {
+                e.insert_component(Position::new(0.0, 0.0))
+                    .enter_room(&room_key);
+            })
+        })
+    });
+    scenario.expect(|ctx| ctx.client(alice, |c| c.entity(&plain).map(|_| ())));
+    entities.push(plain);
+
+    let result =
+        scenario.mutate(|ctx| ctx.server(|server| server.give_authority_group(&alice, &entities)));
+    assert_eq!(result, Err(AuthorityError::NotDelegated));
+    expect_authority(
+        &mut scenario,
+        &[alice],
+        &entities[..1],
+        EntityAuthStatus::Available,
+    );
+}
+
+#[test]
+fn group_request_is_answered_when_a_member_is_undelegated() {
+    let mut scenario = Scenario::new();
+    let client_keys = common::server_with_clients(&mut scenario, &["alice"]);
+    let alice = client_keys[0];
+    let entities = spawn_delegated(&mut scenario, &client_keys, 2);
+
+    // The server undelegates one member before the request reaches it
+    let undelegated = entities[1];
+    scenario.mutate(|ctx| {
+        ctx.client(alice, |c| {
+            c.request_authority_group::<ReliableChannel>(&entities)
+                .expect("group request is allowed");
+        });
+        ctx.server(|server| {
+            server
+                .entity_mut(&undelegated)
+                .expect("entity exists")
+                .configure_replication(ReplicationConfig::public());
+        });
+    });
+
+    let denied = scenario.expect(|ctx| {
+        ctx.client(alice, |c| {
+            c.read_event::<ClientEntityAuthGroupDeniedEvent>()
+        })
+    });
+    assert_eq!(denied.len(), entities.len());
+    expect_authority(
+        &mut scenario,
+        &[alice],
+        &entities[..1],
+        EntityAuthStatus::Available,
+    );
+}
@@ -0,0 +1,154 @@
+//! End-to-end integration tests for authority leases and queued authority
+//! requests on delegated entities.
+//!
+//! A leased holder that stops writing loses authority once the lease runs
+//! out. With queuing enabled, requests made while the entity is held wait in
+//! line and are granted in order as each holder lets go.
+
+mod common;
+
+use std::time::Duration;
+
+use naia_server::ReplicationConfig;
+use naia_shared::EntityAuthStatus;
+use naia_test_harness::{
+    ClientEntityAuthGrantedEvent, ClientEntityAuthQueueGrantedEvent, ClientEntityAuthResetEvent,
+    ClientKey, EntityKey, Position, Scenario, ServerEntityAuthResetEvent,
+};
+
+const LEASE: Duration = Duration::from_millis(500);
+
+/// Spawns a delegated entity with `config` and waits until every client sees it
+/// as Available
+fn spawn_delegated(
+    scenario: &mut Scenario,
+    client_keys: &[ClientKey],
+    config: ReplicationConfig,
+) -> EntityKey {
+    let room_key = scenario.last_room();
+    let (entity, ()) = scenario.mutate(|ctx| {
+        ctx.server(|server| {
+            server.spawn(|mut e| {
+                e.insert_component(Position::new(0.0, 0.0))
+                    .configure_replication(config)
+                    .enter_room(&room_key);
+            })
+        })
+    });
+    scenario.expect(|ctx| {
+        client_keys
+            .iter()
+            .all(|client_key| {
+                ctx.client(*client_key, |c| {
+                    c.entity(&entity).and_then(|e| e.authority())
+                        == Some(EntityAuthStatus::Available)
+                })
+            })
+            .then_some(())
+    });
+    entity
+}
+
+fn request_authority(scenario: &mut Scenario, client_key: ClientKey, entity: &EntityKey) {
+    scenario.mutate(|ctx| {
+        ctx.client(client_key, |c| {
+            c.entity_mut(entity)
+                .expect("entity exists")
+                .request_authority()
+                .expect("request is allowed");
+        })
+    });
+}
+
+fn release_authority(scenario: &mut Scenario, client_key: ClientKey, entity: &EntityKey) {
+    scenario.mutate(|ctx| {
+        ctx.client(client_key, |c| {
+            c.entity_mut(entity)
+                .expect("entity exists")
+                .release_authority()
+                .expect("client holds authority");
+        })
+    });
+}
+
+fn expect_authority(
+    scenario: &mut Scenario,
+    client_key: ClientKey,
+    entity: &EntityKey,
+    status: EntityAuthStatus,
+) {
+    scenario.expect(|ctx| {
+        ctx.client(client_key, |c| {
+            (c.entity(entity).and_then(|e| e.authority()) == Some(status)).then_some(())
+        })
+    });
+}
+
+#[test]
+fn idle_holder_loses_authority_when_lease_expires() {
+    let mut scenario = Scenario::new();
+    let client_keys = common::server_with_clients(&mut scenario, &["alice"]);
+    let alice = client_keys[0];
+    let entity = spawn_delegated(
+        &mut scenario,
+        &client_keys,
+        ReplicationConfig::delegated().authority_lease(LEASE),
+    );
+
+    request_authority(&mut scenario, alice, &entity);
+    scenario.expect(|ctx| ctx.client(alice, |c| c.read_event::<ClientEntityAuthGrantedEvent>()));
+
+    // Without any writes, the server revokes authority once the lease runs out
+    let (mut server_reset, mut client_reset) = (false, false);
+    scenario.expect(|ctx| {
+        server_reset |=
+            ctx.server(|server| server.read_event::<ServerEntityAuthResetEvent>()) == Some(entity);
+        client_reset |=
+            ctx.client(alice, |c| c.read_event::<ClientEntityAuthResetEvent>()) == Some(entity);
+        (server_reset && client_reset).then_some(())
+    });
+    expect_authority(&mut scenario, alice, &entity, EntityAuthStatus::Available);
+}
+
+#[test]
+fn queued_requests_are_granted_in_order() {
+    let mut scenario = Scenario::new();
+    let client_keys = common::server_with_clients(&mut scenario, &["alice", "bob", "carol"]);
+    let (alice, bob, carol) = (client_keys[0], client_keys[1], client_keys[2]);
+    let entity = spawn_delegated(
+        &mut scenario,
+        &client_keys,
+        ReplicationConfig::delegated().queue_authority_requests(),
+    );
+
+    request_authority(&mut scenario, alice, &entity);
+    expect_authority(&mut scenario, alice, &entity, EntityAuthStatus::Granted);
+    expect_authority(&mut scenario, bob, &entity, EntityAuthStatus::Denied);
+    expect_authority(&mut scenario, carol, &entity, EntityAuthStatus::Denied);
+
+    // Bob, then Carol, join the queue instead of being denied
+    request_authority(&mut scenario, bob, &entity);
+    request_authority(&mut scenario, carol, &entity);
+    scenario.expect(|ctx| {
+        let bob_status = ctx.client(bob, |c| c.entity(&entity).and_then(|e| e.authority()));
+        let carol_status = ctx.client(carol, |c| c.entity(&entity).and_then(|e| e.authority()));
+        (bob_status == Some(EntityAuthStatus::Requested)
+            && carol_status == Some(EntityAuthStatus::Requested))
+        .then_some(())
+    });
+
+    // Alice lets go: Bob is at the front of the queue
+    release_authority(&mut scenario, alice, &entity);
+    scenario.expect(|ctx| ctx.client(bob, |c| c.read_event::<ClientEntityAuthQueueGrantedEvent>()));
+    expect_authority(&mut scenario, alice, &entity, EntityAuthStatus::Denied);
+    expect_authority(&mut scenario, carol, &entity, EntityAuthStatus::Requested);
+
+    // Bob lets go: Carol is next
+    release_authority(&mut scenario, bob, &entity);
+    scenario.expect(|ctx| {
+        ctx.client(carol, |c| {
+            c.read_event::<ClientEntityAuthQueueGrantedEvent>()
+        })
+    });
+    expect_authority(&mut scenario, bob, &entity, EntityAuthStatus::Denied);
+}
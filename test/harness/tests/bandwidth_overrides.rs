@@ -0,0 +1,107 @@
+//! End-to-end integration tests for per-user bandwidth budget overrides and
+//! the server-wide aggregate bandwidth cap.
+//!
+//! `UserMut::set_bandwidth_override` replaces one user's budget at runtime and
+//! `clear_bandwidth_override` restores the configured one.
+//! `ServerConfig::aggregate_bytes_per_sec` splits a shared budget between all
+//! connections by max-min fairness. Each connection's resulting budget is
+//! reported as `ConnectionStats::kbps_target`.
+
+mod common;
+
+use std::time::Duration;
+
+use naia_server::ServerConfig;
+use naia_test_harness::{protocol, ClientKey, Scenario};
+
+fn server_config(aggregate_bytes_per_sec: Option<u32>) -> ServerConfig {
+    let mut config = ServerConfig::default();
+    config.connection.bandwidth_measure_duration = Some(Duration::from_secs(1));
+    config.aggregate_bytes_per_sec = aggregate_bytes_per_sec;
+    config
+}
+
+/// Runs one tick, then reads the client's outbound budget in kbps
+fn kbps_target(scenario: &mut Scenario, client_key: ClientKey) -> f32 {
+    scenario.mutate(|_| {});
+    scenario.mutate(|ctx| {
+        ctx.server(|server| {
+            server
+                .connection_stats(&client_key)
+                .expect("client is connected")
+                .kbps_target
+        })
+    })
+}
+
+#[test]
+fn override_replaces_budget_until_cleared() {
+    let mut scenario = Scenario::new();
+    scenario.server_start(server_config(None), protocol());
+    let client_key = common::connect_client(&mut scenario, "alice", None);
+    assert_eq!(kbps_target(&mut scenario, client_key), 512.0);
+
+    scenario.mutate(|ctx| {
+        ctx.server(|server| {
+            let mut user = server.user_mut(&client_key).expect("user exists");
+            user.set_bandwidth_override(128_000);
+            assert_eq!(user.bandwidth_override(), Some(128_000));
+        })
+    });
+    assert_eq!(kbps_target(&mut scenario, client_key), 1024.0);
+
+    scenario.mutate(|ctx| {
+        ctx.server(|server| {
+            server
+                .user_mut(&client_key)
+                .expect("user exists")
+                .clear_bandwidth_override();
+            let user = server.user(&client_key).expect("user exists");
+            assert_eq!(user.bandwidth_override(), None);
+        })
+    });
+    assert_eq!(kbps_target(&mut scenario, client_key), 512.0);
+}
+
+#[test]
+fn aggregate_cap_splits_budget_equally() {
+    let mut scenario = Scenario::new();
+    scenario.server_start(server_config(Some(64_000)), protocol());
+    let alice = common::connect_client(&mut scenario, "alice", None);
+    let bob = common::connect_client(&mut scenario, "bob", None);
+
+    // Both ask for the default 64 KB/s; each gets half of the 64 KB/s cap
+    assert_eq!(kbps_target(&mut scenario, alice), 256.0);
+    assert_eq!(kbps_target(&mut scenario, bob), 256.0);
+}
+
+#[test]
+fn aggregate_cap_hands_unused_share_to_others() {
+    let mut scenario = Scenario::new();
+    scenario.server_start(server_config(Some(64_000)), protocol());
+    let spectator = common::connect_client(&mut scenario, "spectator", None);
+    let player = common::connect_client(&mut scenario, "player", None);
+
+    scenario.mutate(|ctx| {
+        ctx.server(|server| {
+            server
+                .user_mut(&spectator)
+                .expect("user exists")
+                .set_bandwidth_override(8_000);
+        })
+    });
+
+    // The spectator keeps its small budget; the player gets the rest
+    assert_eq!(kbps_target(&mut scenario, spectator), 64.0);
+    assert_eq!(kbps_target(&mut scenario, player), 448.0);
+}
+
+#[test]
+fn aggregate_cap_lifts_when_budgets_fit() {
+    let mut scenario = Scenario::new();
+    scenario.server_start(server_config(Some(100_000)), protocol());
+    let client_key = common::connect_client(&mut scenario, "alice", None);
+
+    // A lone connection asking for less than the cap keeps its own budget
+    assert_eq!(kbps_target(&mut scenario, client_key), 512.0);
+}
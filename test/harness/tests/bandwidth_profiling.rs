@@ -0,0 +1,221 @@
+//! End-to-end integration tests for the bandwidth attribution profiler.
+//!
+//! With `ConnectionConfig::bandwidth_profiling` set, every data packet's bits
+//! are attributed to the header, acks, message channels, entity commands and
+//! component updates, and further broken down by component, field and
+//! entity. `Server::bandwidth_profile` reports one user's traffic and
+//! `Server::global_bandwidth_profile` all users', including those who have
+//! since disconnected.
+
+mod common;
+
+use naia_client::ClientConfig;
+use naia_server::{BandwidthProfile, ServerConfig};
+use naia_test_harness::{
+    protocol,
+    test_protocol::{ReliableChannel, TestMessage},
+    ClientKey, Position, Scenario,
+};
+
+fn client_config(bandwidth_profiling: bool) -> ClientConfig {
+    let mut config = common::test_client_config();
+    config.connection.bandwidth_profiling = bandwidth_profiling;
+    config
+}
+
+fn server_config(bandwidth_profiling: bool) -> ServerConfig {
+    let mut config = ServerConfig::default();
+    config.connection.bandwidth_profiling = bandwidth_profiling;
+    config
+}
+
+/// Bring up a server with one connected client in a single room.
+fn server_with_one_client(scenario: &mut Scenario, bandwidth_profiling: bool) -> ClientKey {
+    scenario.server_start(server_config(bandwidth_profiling), protocol());
+    let room_key = scenario.mutate(|ctx| ctx.server(|server| server.create_room().key()));
+    scenario.set_last_room(room_key);
+
+    let client_config = client_config(bandwidth_profiling);
+    common::connect_client_with(scenario, "alice", client_config, protocol(), Some(room_key))
+}
+
+fn sections_sum(profile: &BandwidthProfile) -> u64 {
+    profile.header_bits
+        + profile.ack_bits
+        + profile.channels.values().map(|bits| bits.bits).sum::<u64>()
+        + profile.entity_commands.bits
+        + profile.entity_update_bits
+}
+
+fn component_bits(profile: &BandwidthProfile, name: &str) -> u64 {
+    profile
+        .components
+        .iter()
+        .filter(|(component, _)| component.contains(name))
+        .map(|(_, bits)| *bits)
+        .sum()
+}
+
+fn field_bits(profile: &BandwidthProfile, name: &str, field_index: u8) -> u64 {
+    profile
+        .fields
+        .iter()
+        .filter(|((component, index), _)| component.contains(name) && *index == field_index)
+        .map(|(_, bits)| *bits)
+        .sum()
+}
+
+#[test]
+fn profile_attributes_entities_components_and_fields() {
+    let mut scenario = Scenario::new();
+    let client_key = server_with_one_client(&mut scenario, true);
+    let room_key = scenario.last_room();
+
+    let entity = scenario.mutate(|ctx| {
+        ctx.server(|server| {
+            server
+                .spawn(|mut e| {
+                    e.insert_component(Position::new(1.0, 2.0));
+                    e.enter_room(&room_key);
+                })
+                .0
+        })
+    });
+    scenario.expect(|ctx| ctx.client(client_key, |c| c.entity(&entity).map(|_| ())));
+
+    // Only `y` changes, so only field 1 is updated
+    scenario.mutate(|ctx| {
+        ctx.server(|server| {
+            let mut entity_mut = server.entity_mut(&entity).expect("entity exists");
+            let mut position = entity_mut.component::<Position>().expect("has position");
+            *position.y = 20.0;
+        })
+    });
+    scenario.expect(|ctx| {
+        ctx.client(client_key, |c| {
+            let entity_ref = c.entity(&entity)?;
+            let position = entity_ref.component::<Position>()?;
+            (*position.y == 20.0).then_some(())
+        })
+    });
+
+    scenario.expect(|ctx| {
+        ctx.server(|server| {
+            let profile = server.bandwidth_profile(&client_key)?;
+            assert!(profile.packets > 0);
+            assert_eq!(sections_sum(&profile), profile.total_bits);
+            assert!(profile.entity_commands.bits > 0);
+            assert!(profile.entity_update_bits > 0);
+            assert!(component_bits(&profile, "Position") > 0);
+            assert!(field_bits(&profile, "Position", 1) > 0);
+            assert_eq!(field_bits(&profile, "Position", 0), 0);
+            assert!(server.entity_bandwidth_bits(&profile, &entity) > 0);
+            Some(())
+        })
+    });
+}
+
+#[test]
+fn profile_attributes_channels() {
+    let mut scenario = Scenario::new();
+    let client_key = server_with_one_client(&mut scenario, true);
+
+    scenario.mutate(|ctx| {
+        ctx.server(|server| {
+            server.send_message::<ReliableChannel, _>(&client_key, &TestMessage::new(7));
+        })
+    });
+    scenario.mutate(|ctx| {
+        ctx.client(client_key, |c| {
+            let _ = c.send_message::<ReliableChannel, _>(&TestMessage::new(8));
+        })
+    });
+    scenario.mutate(|_| {});
+
+    scenario.expect(|ctx| {
+        ctx.server(|server| {
+            let profile = server.bandwidth_profile(&client_key)?;
+            let channel = profile
+                .channels
+                .iter()
+                .find(|(name, _)| name.contains("ReliableChannel"))?;
+            assert!(channel.1.bits > 0);
+            assert_eq!(sections_sum(&profile), profile.total_bits);
+            Some(())
+        })
+    });
+    scenario.expect(|ctx| {
+        ctx.client(client_key, |c| {
+            let profile = c.bandwidth_profile()?;
+            let channel = profile
+                .channels
+                .iter()
+                .find(|(name, _)| name.contains("ReliableChannel"))?;
+            assert!(channel.1.bits > 0);
+            assert_eq!(sections_sum(&profile), profile.total_bits);
+            Some(())
+        })
+    });
+}
+
+#[test]
+fn global_profile_keeps_disconnected_users() {
+    let mut scenario = Scenario::new();
+    let client_key = server_with_one_client(&mut scenario, true);
+    scenario.mutate(|ctx| {
+        ctx.server(|server| {
+            server.send_message::<ReliableChannel, _>(&client_key, &TestMessage::new(7));
+        })
+    });
+
+    let sent = scenario.expect(|ctx| {
+        ctx.server(|server| {
+            let total_bits = server.bandwidth_profile(&client_key)?.total_bits;
+            (total_bits > 0).then_some(total_bits)
+        })
+    });
+
+    scenario.mutate(|ctx| {
+        ctx.server(|server| {
+            server.disconnect_user(&client_key);
+        })
+    });
+    scenario.expect(|ctx| {
+        ctx.server(|server| {
+            let global = server.global_bandwidth_profile();
+            (server.bandwidth_profile(&client_key).is_none() && global.total_bits >= sent)
+                .then_some(())
+        })
+    });
+
+    scenario.mutate(|ctx| ctx.server(|server| server.reset_bandwidth_profiles()));
+    scenario.expect(|ctx| {
+        ctx.server(|server| {
+            (server.global_bandwidth_profile() == BandwidthProfile::default()).then_some(())
+        })
+    });
+}
+
+#[test]
+fn profiling_is_off_by_default() {
+    let mut scenario = Scenario::new();
+    let client_key = server_with_one_client(&mut scenario, false);
+    scenario.mutate(|_| {});
+
+    scenario.expect(|ctx| {
+        ctx.server(|server| {
+            assert!(server.bandwidth_profile(&client_key).is_none());
+            assert_eq!(
+                server.global_bandwidth_profile(),
+                BandwidthProfile::default()
+            );
+            Some(())
+        })
+    });
+    scenario.expect(|ctx| {
+        ctx.client(client_key, |c| {
+            assert!(c.bandwidth_profile().is_none());
+            Some(())
+        })
+    });
+}
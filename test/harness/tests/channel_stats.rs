@@ -0,0 +1,183 @@
+//! End-to-end integration tests for per-channel connection statistics.
+//!
+//! `ConnectionStats::channels` reports each channel's messages and bytes in
+//! both directions, the reliable queue depth against `max_queue_depth`,
+//! retransmissions, the average time to ack, and messages dropped by a full
+//! queue or an expired tick, keyed by channel protocol name.
+
+mod common;
+
+use std::time::Duration;
+
+use naia_client::ClientConfig;
+use naia_server::{ChannelStats, ServerConfig};
+use naia_shared::sequence_greater_than;
+use naia_test_harness::{
+    protocol,
+    test_protocol::{ReliableChannel, TestMessage, TickBufferedChannel},
+    ClientKey, LinkConditionerConfig, Scenario, ToTicks,
+};
+
+// `connection_stats` needs bandwidth monitoring on both sides
+fn test_client_config() -> ClientConfig {
+    let mut config = common::test_client_config();
+    config.connection.bandwidth_measure_duration = Some(Duration::from_secs(1));
+    config
+}
+
+fn server_config() -> ServerConfig {
+    let mut config = ServerConfig::default();
+    config.connection.bandwidth_measure_duration = Some(Duration::from_secs(1));
+    config
+}
+
+fn connect_client(scenario: &mut Scenario) -> ClientKey {
+    scenario.server_start(server_config(), protocol());
+    common::connect_client_with(scenario, "alice", test_client_config(), protocol(), None)
+}
+
+fn channel<'a>(
+    channels: &'a std::collections::BTreeMap<String, ChannelStats>,
+    name: &str,
+) -> &'a ChannelStats {
+    channels
+        .iter()
+        .find(|(channel, _)| channel.ends_with(name))
+        .map(|(_, stats)| stats)
+        .expect("channel is registered")
+}
+
+fn server_channel(scenario: &mut Scenario, client_key: ClientKey, name: &str) -> ChannelStats {
+    scenario.mutate(|ctx| {
+        ctx.server(|server| {
+            let stats = server
+                .connection_stats(&client_key)
+                .expect("client is connected");
+            channel(&stats.channels, name).clone()
+        })
+    })
+}
+
+fn client_channel(scenario: &mut Scenario, client_key: ClientKey, name: &str) -> ChannelStats {
+    scenario.mutate(|ctx| {
+        ctx.client(client_key, |c| {
+            let stats = c.connection_stats().expect("client is connected");
+            channel(&stats.channels, name).clone()
+        })
+    })
+}
+
+fn send_reliable(scenario: &mut Scenario, client_key: ClientKey, count: u32) {
+    scenario.mutate(|ctx| {
+        ctx.server(|server| {
+            for value in 0..count {
+                server.send_message::<ReliableChannel, _>(&client_key, &TestMessage::new(value));
+            }
+        })
+    });
+}
+
+fn expect_acked(scenario: &mut Scenario, client_key: ClientKey) {
+    scenario.expect(|ctx| {
+        ctx.server(|server| {
+            let stats = server.connection_stats(&client_key)?;
+            (channel(&stats.channels, "ReliableChannel").queue_depth == 0).then_some(())
+        })
+    });
+}
+
+#[test]
+fn reliable_channel_counts_messages_bytes_and_acks() {
+    let mut scenario = Scenario::new();
+    let client_key = connect_client(&mut scenario);
+
+    send_reliable(&mut scenario, client_key, 3);
+    expect_acked(&mut scenario, client_key);
+
+    let sent = server_channel(&mut scenario, client_key, "ReliableChannel");
+    assert_eq!(sent.messages_sent, 3);
+    assert!(sent.bytes_sent > 0);
+    assert_eq!(sent.max_queue_depth, Some(1024));
+    assert_eq!(sent.retransmissions, 0);
+    assert!(sent.avg_ack_ms > 0.0);
+    assert_eq!(sent.dropped_backpressure, 0);
+
+    let received = client_channel(&mut scenario, client_key, "ReliableChannel");
+    assert_eq!(received.messages_received, 3);
+    assert!(received.bytes_received > 0);
+}
+
+#[test]
+fn full_reliable_queue_counts_backpressure_drops() {
+    let mut scenario = Scenario::new();
+    let client_key = connect_client(&mut scenario);
+
+    // The default `max_queue_depth` is 1024
+    send_reliable(&mut scenario, client_key, 1100);
+
+    let stats = server_channel(&mut scenario, client_key, "ReliableChannel");
+    assert_eq!(stats.messages_sent, 1024);
+    assert_eq!(stats.dropped_backpressure, 76);
+    assert!(stats.queue_depth > 0);
+}
+
+#[test]
+fn lost_packets_count_retransmissions() {
+    let mut scenario = Scenario::new();
+    let client_key = connect_client(&mut scenario);
+
+    // Drop everything sent to the client until a resend has happened
+    let dropped = LinkConditionerConfig::new(0, 0, 1.0);
+    scenario.configure_link_conditioner(&client_key, None, Some(dropped));
+    send_reliable(&mut scenario, client_key, 20);
+    scenario.expect(|ctx| {
+        ctx.server(|server| {
+            let stats = server.connection_stats(&client_key)?;
+            (channel(&stats.channels, "ReliableChannel").retransmissions > 0).then_some(())
+        })
+    });
+
+    scenario.configure_link_conditioner(&client_key, None, None);
+    scenario.expect(|ctx| {
+        ctx.client(client_key, |c| {
+            let stats = c.connection_stats()?;
+            (channel(&stats.channels, "ReliableChannel").messages_received == 20).then_some(())
+        })
+    });
+    expect_acked(&mut scenario, client_key);
+}
+
+#[test]
+fn tick_buffered_channel_counts_both_sides() {
+    let mut scenario = Scenario::new();
+    let client_key = connect_client(&mut scenario);
+
+    let tick = scenario.mutate(|ctx| ctx.server(|server| server.current_tick().wrapping_add(5)));
+    scenario.mutate(|ctx| {
+        ctx.client(client_key, |c| {
+            c.send_tick_buffer_message::<TickBufferedChannel, _>(&tick, &TestMessage::new(1));
+        })
+    });
+    scenario
+        .until(50.ticks())
+        .expect_msg("server advanced past tick", |ctx| {
+            let now = ctx.server(|server| server.current_tick());
+            sequence_greater_than(now, tick).then_some(())
+        });
+    scenario.mutate(|ctx| {
+        ctx.server(|server| {
+            server
+                .receive_tick_buffer_messages(&tick)
+                .read::<TickBufferedChannel, TestMessage>()
+        })
+    });
+
+    let sent = client_channel(&mut scenario, client_key, "TickBufferedChannel");
+    assert_eq!(sent.messages_sent, 1);
+    assert!(sent.bytes_sent > 0);
+    assert_eq!(sent.max_queue_depth, Some(64));
+
+    let received = server_channel(&mut scenario, client_key, "TickBufferedChannel");
+    assert_eq!(received.messages_received, 1);
+    assert!(received.bytes_received > 0);
+}
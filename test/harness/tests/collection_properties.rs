@@ -0,0 +1,163 @@
+//! End-to-end integration tests for `PropertyVec` and `PropertyMap`.
+//!
+//! `Inventory` in the test protocol holds one of each. These tests check
+//! that element operations reach the client as deltas, that the client
+//! sees them as drained changes, and that the client converges on the
+//! server's state under packet loss.
+
+mod common;
+
+use naia_shared::{MapChange, VecChange};
+use naia_test_harness::{ClientKey, EntityKey, Inventory, LinkConditionerConfig, Scenario};
+
+fn spawn_inventory(scenario: &mut Scenario, client_key: ClientKey, items: Vec<u16>) -> EntityKey {
+    let room_key = scenario.last_room();
+    let entity = scenario.mutate(|ctx| {
+        ctx.server(|server| {
+            server
+                .spawn(|mut e| {
+                    e.insert_component(Inventory::new(items));
+                    e.enter_room(&room_key);
+                })
+                .0
+        })
+    });
+    scenario.expect(|ctx| {
+        ctx.client(client_key, |c| {
+            c.entity(&entity)?.component::<Inventory>().map(|_| ())
+        })
+    });
+    entity
+}
+
+fn mutate_inventory(scenario: &mut Scenario, entity: &EntityKey, f: impl FnOnce(&mut Inventory)) {
+    scenario.mutate(|ctx| {
+        ctx.server(|server| {
+            let mut entity_mut = server.entity_mut(entity).expect("entity exists");
+            let mut inventory = entity_mut.component::<Inventory>().expect("has inventory");
+            f(&mut inventory);
+        })
+    });
+}
+
+/// Waits until the client's inventory matches the server's
+fn expect_client_converged(scenario: &mut Scenario, client_key: ClientKey, entity: &EntityKey) {
+    let (items, counts) = scenario.mutate(|ctx| {
+        ctx.server(|server| {
+            let mut entity_mut = server.entity_mut(entity).expect("entity exists");
+            let inventory = entity_mut.component::<Inventory>().expect("has inventory");
+            ((*inventory.items).clone(), (*inventory.counts).clone())
+        })
+    });
+    scenario.expect(|ctx| {
+        ctx.client(client_key, |c| {
+            let entity_ref = c.entity(entity)?;
+            let inventory = entity_ref.component::<Inventory>()?;
+            (*inventory.items == items && *inventory.counts == counts).then_some(())
+        })
+    });
+}
+
+#[test]
+fn collection_changes_reach_client_as_drained_changes() {
+    let mut scenario = Scenario::new();
+    let client_key = common::server_with_one_client(&mut scenario);
+    let entity = spawn_inventory(&mut scenario, client_key, vec![1, 2, 3]);
+
+    mutate_inventory(&mut scenario, &entity, |inventory| {
+        inventory.items.push(4);
+        inventory.items.set(0, 10);
+        inventory.items.remove(1);
+        inventory.counts.insert(10, 5);
+        for key in 100..120 {
+            inventory.counts.insert(key, 1);
+        }
+    });
+    expect_client_converged(&mut scenario, client_key, &entity);
+
+    let (item_changes, count_changes) = scenario.mutate(|ctx| {
+        ctx.client(client_key, |c| {
+            let mut entity_mut = c.entity_mut(&entity).expect("entity exists");
+            let mut inventory = entity_mut.component::<Inventory>().expect("has inventory");
+            (
+                inventory.items.drain_changes(),
+                inventory.counts.drain_changes(),
+            )
+        })
+    });
+
+    // The first update after the spawn has no acked version to build on,
+    // so it may arrive whole
+    if item_changes != vec![VecChange::Reset] {
+        assert_eq!(
+            item_changes,
+            vec![
+                VecChange::Inserted { index: 3, value: 4 },
+                VecChange::Replaced {
+                    index: 0,
+                    old: 1,
+                    new: 10
+                },
+                VecChange::Removed { index: 1, value: 2 },
+            ]
+        );
+    }
+    assert!(
+        count_changes == vec![MapChange::Reset]
+            || count_changes.contains(&MapChange::Inserted { key: 10, value: 5 })
+    );
+
+    // Once an update is acked, later ones are element deltas
+    mutate_inventory(&mut scenario, &entity, |inventory| {
+        inventory.items.push(5);
+    });
+    expect_client_converged(&mut scenario, client_key, &entity);
+    mutate_inventory(&mut scenario, &entity, |inventory| {
+        inventory.items.pop();
+        inventory.counts.remove(&10);
+    });
+    expect_client_converged(&mut scenario, client_key, &entity);
+
+    let (item_changes, count_changes) = scenario.mutate(|ctx| {
+        ctx.client(client_key, |c| {
+            let mut entity_mut = c.entity_mut(&entity).expect("entity exists");
+            let mut inventory = entity_mut.component::<Inventory>().expect("has inventory");
+            (
+                inventory.items.drain_changes(),
+                inventory.counts.drain_changes(),
+            )
+        })
+    });
+    assert_eq!(
+        item_changes.last(),
+        Some(&VecChange::Removed { index: 3, value: 5 })
+    );
+    assert_eq!(
+        count_changes.last(),
+        Some(&MapChange::Removed { key: 10, value: 5 })
+    );
+}
+
+#[test]
+fn collections_converge_under_packet_loss() {
+    let mut scenario = Scenario::new();
+    let client_key = common::server_with_one_client(&mut scenario);
+    let entity = spawn_inventory(&mut scenario, client_key, (0..50).collect());
+
+    let lossy = LinkConditionerConfig::new(20, 5, 0.3);
+    scenario.configure_link_conditioner(&client_key, None, Some(lossy));
+
+    for step in 0..40u16 {
+        mutate_inventory(&mut scenario, &entity, |inventory| {
+            inventory.items.push(100 + step);
+            let index = usize::from(step) % inventory.items.len();
+            inventory.items.remove(index);
+            inventory.counts.insert(step % 7, u32::from(step));
+            if step % 5 == 0 {
+                inventory.counts.remove(&((step + 3) % 7));
+            }
+        });
+    }
+
+    expect_client_converged(&mut scenario, client_key, &entity);
+}
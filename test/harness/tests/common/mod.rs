@@ -0,0 +1,111 @@
+//! Fixtures shared by the integration tests in this directory: a client
+//! config with an immediate handshake, and helpers that bring up a server
+//! and connect clients to it.
+
+#![allow(dead_code)]
+
+use std::time::Duration;
+
+use naia_client::{ClientConfig, JitterBufferType};
+use naia_server::{RoomKey, ServerConfig};
+use naia_shared::Protocol;
+use naia_test_harness::{
+    protocol, Auth, ClientConnectEvent, ClientKey, Scenario, ServerAuthEvent, ServerConnectEvent,
+};
+
+/// Client config with an immediate handshake and no jitter buffer.
+pub fn test_client_config() -> ClientConfig {
+    ClientConfig {
+        send_handshake_interval: Duration::from_millis(0),
+        jitter_buffer: JitterBufferType::Bypass,
+        ..Default::default()
+    }
+}
+
+/// Start a server with the default config and test protocol, create a room
+/// (made the scenario's last room) and connect one client, "alice", to it.
+pub fn server_with_one_client(scenario: &mut Scenario) -> ClientKey {
+    server_with_clients(scenario, &["alice"])[0]
+}
+
+/// Like [`server_with_one_client`], connecting a client per name in order.
+pub fn server_with_clients(scenario: &mut Scenario, names: &[&str]) -> Vec<ClientKey> {
+    scenario.server_start(ServerConfig::default(), protocol());
+    let room_key = scenario.mutate(|ctx| ctx.server(|server| server.create_room().key()));
+    scenario.set_last_room(room_key);
+
+    names
+        .iter()
+        .map(|name| connect_client(scenario, name, Some(room_key)))
+        .collect()
+}
+
+/// Connect a client with the test config and protocol to a running server,
+/// putting it in `room_key` if given.
+pub fn connect_client(scenario: &mut Scenario, name: &str, room_key: Option<RoomKey>) -> ClientKey {
+    connect_client_with(scenario, name, test_client_config(), protocol(), room_key)
+}
+
+/// Connect a client with its own config and protocol to a running server,
+/// putting it in `room_key` if given.
+pub fn connect_client_with(
+    scenario: &mut Scenario,
+    name: &str,
+    client_config: ClientConfig,
+    protocol: Protocol,
+    room_key: Option<RoomKey>,
+) -> ClientKey {
+    connect(scenario, name, client_config, protocol, false, room_key)
+}
+
+/// Connect a client with the test config and protocol, accepted as an
+/// observer.
+pub fn connect_observer(scenario: &mut Scenario, name: &str) -> ClientKey {
+    connect(scenario, name, test_client_config(), protocol(), true, None)
+}
+
+fn connect(
+    scenario: &mut Scenario,
+    name: &str,
+    client_config: ClientConfig,
+    protocol: Protocol,
+    observer: bool,
+    room_key: Option<RoomKey>,
+) -> ClientKey {
+    let client_auth = Auth::new(name, "secret");
+    let client_key = scenario.client_start(name, client_auth, client_config, protocol);
+
+    scenario.expect(|ctx| {
+        ctx.server(|server| {
+            let (incoming_key, _) = server.read_event::<ServerAuthEvent<Auth>>()?;
+            (incoming_key == client_key).then_some(())
+        })
+    });
+    scenario.mutate(|ctx| {
+        ctx.server(|server| {
+            if observer {
+                server.accept_observer_connection(&client_key);
+            } else {
+                server.accept_connection(&client_key);
+            }
+        })
+    });
+    scenario.expect(|ctx| ctx.server(|server| server.read_event::<ServerConnectEvent>()));
+    if let Some(room_key) = room_key {
+        scenario.mutate(|ctx| {
+            ctx.server(|server| {
+                server
+                    .room_mut(&room_key)
+                    .expect("room exists")
+                    .add_user(&client_key);
+            })
+        });
+    }
+    scenario.expect(|ctx| {
+        let connected = ctx.client(client_key, |c| c.connection_status().is_connected());
+        let _ = ctx.client(client_key, |c| c.read_event::<ClientConnectEvent>());
+        connected.then_some(())
+    });
+
+    client_key
+}
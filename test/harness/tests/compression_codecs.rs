@@ -0,0 +1,104 @@
+//! End-to-end integration tests for per-connection codec negotiation.
+//!
+//! Each peer reports which codecs its decoder can read during the handshake,
+//! and the sender picks one per direction: zstd (`zstd_support`), pure-Rust
+//! DEFLATE (`deflate_support`, for wasm clients), or uncompressed. Every
+//! packet carries a flag naming its codec, so each side can decode it.
+
+mod common;
+
+use naia_server::ServerConfig;
+use naia_shared::{CompressionConfig, CompressionMode, PacketCodec, Protocol};
+use naia_test_harness::{
+    protocol, test_protocol::ReliableChannel, ClientKey, LargeTestMessage, Scenario,
+};
+
+fn compressed_protocol(
+    server_to_client: CompressionMode,
+    client_to_server: CompressionMode,
+) -> Protocol {
+    let mut protocol = protocol();
+    protocol.compression(CompressionConfig::new(
+        Some(server_to_client),
+        Some(client_to_server),
+    ));
+    protocol
+}
+
+fn connect_client(scenario: &mut Scenario, name: &str, protocol: Protocol) -> ClientKey {
+    common::connect_client_with(scenario, name, common::test_client_config(), protocol, None)
+}
+
+/// The codecs the server and the client each send with
+fn codecs(scenario: &mut Scenario, client_key: ClientKey) -> (PacketCodec, PacketCodec) {
+    scenario.mutate(|ctx| {
+        let server = ctx.server(|server| {
+            server
+                .user(&client_key)
+                .expect("user exists")
+                .compression_codec()
+        });
+        let client = ctx.client(client_key, |c| c.compression_codec());
+        (server, client)
+    })
+}
+
+/// Sends a compressible message each way and waits for both to arrive intact
+fn exchange_messages(scenario: &mut Scenario, client_key: ClientKey) {
+    scenario.mutate(|ctx| {
+        ctx.server(|server| {
+            server.send_message::<ReliableChannel, _>(&client_key, &LargeTestMessage::new(300));
+        });
+    });
+    scenario.expect(|ctx| {
+        ctx.client(client_key, |c| {
+            let message = c
+                .read_message::<ReliableChannel, LargeTestMessage>()
+                .next()?;
+            (message.payload == vec![0u8; 300]).then_some(())
+        })
+    });
+
+    scenario.mutate(|ctx| {
+        ctx.client(client_key, |c| {
+            c.send_message::<ReliableChannel, _>(&LargeTestMessage::new(200))
+                .expect("message is queued");
+        });
+    });
+    scenario.expect(|ctx| {
+        ctx.server(|server| {
+            let (sender, message) = server
+                .read_message::<ReliableChannel, LargeTestMessage>()
+                .next()?;
+            (sender == client_key && message.payload == vec![0u8; 200]).then_some(())
+        })
+    });
+}
+
+#[test]
+fn deflate_is_negotiated_and_round_trips() {
+    let mut scenario = Scenario::new();
+    let deflate = || compressed_protocol(CompressionMode::Deflate(6), CompressionMode::Deflate(6));
+    scenario.server_start(ServerConfig::default(), deflate());
+    let client_key = connect_client(&mut scenario, "alice", deflate());
+
+    assert_eq!(
+        codecs(&mut scenario, client_key),
+        (PacketCodec::Deflate, PacketCodec::Deflate)
+    );
+    exchange_messages(&mut scenario, client_key);
+}
+
+#[test]
+fn codecs_are_chosen_per_direction() {
+    let mut scenario = Scenario::new();
+    let mixed = || compressed_protocol(CompressionMode::Default(3), CompressionMode::Deflate(6));
+    scenario.server_start(ServerConfig::default(), mixed());
+    let client_key = connect_client(&mut scenario, "alice", mixed());
+
+    assert_eq!(
+        codecs(&mut scenario, client_key),
+        (PacketCodec::Zstd, PacketCodec::Deflate)
+    );
+    exchange_messages(&mut scenario, client_key);
+}
@@ -0,0 +1,72 @@
+//! End-to-end integration tests for compression dictionary negotiation.
+//!
+//! Each peer reports the `DictionaryId` of its `CompressionMode::Dictionary`
+//! per direction during the handshake, and only compresses with the
+//! dictionary when the other side reported the same one. Compression settings
+//! are not part of the protocol id, so peers with different dictionaries still
+//! connect and fall back to plain compression.
+
+mod common;
+
+use naia_server::ServerConfig;
+use naia_shared::{CompressionConfig, CompressionMode, Protocol};
+use naia_test_harness::{protocol, ClientKey, Scenario};
+
+fn compressed_protocol(dictionary: Option<&[u8]>) -> Protocol {
+    let mode = match dictionary {
+        Some(dictionary) => CompressionMode::Dictionary(3, dictionary.to_vec()),
+        None => CompressionMode::Default(3),
+    };
+    let mut protocol = protocol();
+    protocol.compression(CompressionConfig::new(Some(mode.clone()), Some(mode)));
+    protocol
+}
+
+fn connect_client(scenario: &mut Scenario, name: &str, protocol: Protocol) -> ClientKey {
+    common::connect_client_with(scenario, name, common::test_client_config(), protocol, None)
+}
+
+/// Whether the server and the client each compress with the dictionary
+fn uses_dictionary(scenario: &mut Scenario, client_key: ClientKey) -> (bool, bool) {
+    scenario.mutate(|ctx| {
+        let server = ctx.server(|server| {
+            server
+                .user(&client_key)
+                .expect("user exists")
+                .uses_compression_dictionary()
+        });
+        let client = ctx.client(client_key, |c| c.uses_compression_dictionary());
+        (server, client)
+    })
+}
+
+#[test]
+fn matching_dictionary_is_used_in_both_directions() {
+    let mut scenario = Scenario::new();
+    scenario.server_start(ServerConfig::default(), compressed_protocol(Some(b"dict")));
+    let client_key = connect_client(&mut scenario, "alice", compressed_protocol(Some(b"dict")));
+
+    assert_eq!(uses_dictionary(&mut scenario, client_key), (true, true));
+}
+
+#[test]
+fn mismatched_dictionary_falls_back_per_client() {
+    let mut scenario = Scenario::new();
+    scenario.server_start(ServerConfig::default(), compressed_protocol(Some(b"dict")));
+    let matching = connect_client(&mut scenario, "alice", compressed_protocol(Some(b"dict")));
+    let stale = connect_client(&mut scenario, "bob", compressed_protocol(Some(b"old dict")));
+    let plain = connect_client(&mut scenario, "carol", compressed_protocol(None));
+
+    assert_eq!(uses_dictionary(&mut scenario, matching), (true, true));
+    assert_eq!(uses_dictionary(&mut scenario, stale), (false, false));
+    assert_eq!(uses_dictionary(&mut scenario, plain), (false, false));
+}
+
+#[test]
+fn no_dictionary_without_compression() {
+    let mut scenario = Scenario::new();
+    scenario.server_start(ServerConfig::default(), protocol());
+    let client_key = connect_client(&mut scenario, "alice", protocol());
+
+    assert_eq!(uses_dictionary(&mut scenario, client_key), (false, false));
+}
@@ -0,0 +1,112 @@
+//! End-to-end integration tests for adaptive congestion control.
+//!
+//! With `BandwidthConfig::congestion_control` unset the outbound budget stays
+//! at `target_bytes_per_sec`. With it set, a connection that keeps running
+//! out of budget on a clean link ramps its rate up, and one whose packets
+//! start getting lost backs off, always within the configured floor and
+//! ceiling. The chosen rate is reported as `ConnectionStats::kbps_target`.
+
+mod common;
+
+use std::time::Duration;
+
+use naia_server::ServerConfig;
+use naia_shared::{BandwidthConfig, CongestionControlConfig};
+use naia_test_harness::{
+    protocol,
+    test_protocol::{TestMessage, UnreliableChannel},
+    ClientKey, LinkConditionerConfig, Scenario,
+};
+
+fn server_config(bandwidth: BandwidthConfig) -> ServerConfig {
+    let mut config = ServerConfig::default();
+    config.connection.bandwidth = bandwidth;
+    config.connection.bandwidth_measure_duration = Some(Duration::from_secs(1));
+    config
+}
+
+/// Queues more messages than the budget can carry, for `ticks` ticks
+fn flood(scenario: &mut Scenario, client_key: ClientKey, ticks: usize) {
+    for _ in 0..ticks {
+        scenario.mutate(|ctx| {
+            ctx.server(|server| {
+                for value in 0..20 {
+                    server.send_message::<UnreliableChannel, _>(
+                        &client_key,
+                        &TestMessage::new(value),
+                    );
+                }
+            })
+        });
+    }
+}
+
+fn kbps_target(scenario: &mut Scenario, client_key: ClientKey) -> f32 {
+    scenario.mutate(|ctx| {
+        ctx.server(|server| {
+            server
+                .connection_stats(&client_key)
+                .expect("client is connected")
+                .kbps_target
+        })
+    })
+}
+
+#[test]
+fn fixed_budget_without_congestion_control() {
+    let mut scenario = Scenario::new();
+    scenario.server_start(server_config(BandwidthConfig::default()), protocol());
+    let client_key = common::connect_client(&mut scenario, "alice", None);
+
+    flood(&mut scenario, client_key, 60);
+    assert_eq!(kbps_target(&mut scenario, client_key), 512.0);
+}
+
+#[test]
+fn budget_limited_connection_ramps_up_to_ceiling() {
+    let mut scenario = Scenario::new();
+    scenario.server_start(
+        server_config(BandwidthConfig {
+            target_bytes_per_sec: 2_000,
+            congestion_control: Some(CongestionControlConfig {
+                min_bytes_per_sec: 1_000,
+                max_bytes_per_sec: 6_000,
+                ..Default::default()
+            }),
+        }),
+        protocol(),
+    );
+    let client_key = common::connect_client(&mut scenario, "alice", None);
+    assert_eq!(kbps_target(&mut scenario, client_key), 16.0);
+
+    // +4000 B/s per 250 ms adjustment: 2.4 s of flooding reaches the ceiling
+    flood(&mut scenario, client_key, 150);
+    assert_eq!(kbps_target(&mut scenario, client_key), 48.0);
+}
+
+#[test]
+fn lossy_link_backs_off_to_floor() {
+    let mut scenario = Scenario::new();
+    scenario.server_start(
+        server_config(BandwidthConfig {
+            target_bytes_per_sec: 64_000,
+            congestion_control: Some(CongestionControlConfig {
+                min_bytes_per_sec: 16_000,
+                ..Default::default()
+            }),
+        }),
+        protocol(),
+    );
+    let client_key = common::connect_client(&mut scenario, "alice", None);
+
+    let lossy = LinkConditionerConfig::new(20, 5, 0.5);
+    scenario.configure_link_conditioner(&client_key, None, Some(lossy));
+    flood(&mut scenario, client_key, 150);
+
+    let target = kbps_target(&mut scenario, client_key);
+    assert!(target < 512.0, "target stayed at {target} kbps");
+    assert!(
+        target >= 128.0,
+        "target fell below the floor: {target} kbps"
+    );
+}
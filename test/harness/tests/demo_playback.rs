@@ -0,0 +1,266 @@
+//! End-to-end integration tests for recording a user's replication stream
+//! and playing it back without a server.
+//!
+//! A demo recorded on the server for one user, or on the client itself, must
+//! replay into a fresh world with the same entities, component values,
+//! relations and messages that user received, and playback must honour
+//! pause, play speed and seeking.
+
+mod common;
+
+use std::time::Duration;
+
+use naia_client::{
+    DemoPlayer, DespawnEntityEvent, InsertComponentEvent, MessageEvent, SpawnEntityEvent,
+    UpdateComponentEvent,
+};
+use naia_server::ServerConfig;
+use naia_shared::{DemoError, Replicate, WorldRefType};
+use naia_test_harness::{
+    protocol,
+    test_protocol::{ReliableChannel, TestMessage},
+    Position, Scenario, Squad, TestEntity, TestWorld,
+};
+
+/// Component only registered by the mismatched protocol
+#[derive(Replicate)]
+pub struct Marker;
+
+/// Which side records the demo
+#[derive(Clone, Copy)]
+enum Recorder {
+    Server,
+    Client,
+}
+
+/// Runs a short session while recording it and returns the demo: a unit
+/// spawns, joins a squad, moves to x = 10, a message arrives, and a second
+/// unit is spawned and despawned again.
+fn record_session(recorder: Recorder) -> Vec<u8> {
+    let mut scenario = Scenario::new();
+    scenario.server_start(ServerConfig::default(), protocol());
+    let room_key = scenario.mutate(|ctx| ctx.server(|server| server.create_room().key()));
+    let client_key = common::connect_client(&mut scenario, "alice", Some(room_key));
+
+    scenario.mutate(|ctx| match recorder {
+        Recorder::Server => ctx.server(|server| assert!(server.start_demo_recording(&client_key))),
+        Recorder::Client => ctx.client(client_key, |client| client.start_demo_recording()),
+    });
+
+    let (unit, temporary) = scenario.mutate(|ctx| {
+        ctx.server(|server| {
+            let (unit, _) = server.spawn(|mut e| {
+                e.insert_component(Position::new(1.0, 2.0))
+                    .enter_room(&room_key);
+            });
+            let (temporary, _) = server.spawn(|mut e| {
+                e.insert_component(Position::new(7.0, 7.0))
+                    .enter_room(&room_key);
+            });
+            let (squad, _) = server.spawn(|mut e| {
+                e.insert_component(Squad::default()).enter_room(&room_key);
+            });
+            assert!(server.entity_set_insert(&squad, &unit, |s: &mut Squad| &mut s.members));
+            (unit, temporary)
+        })
+    });
+    scenario.expect(|ctx| {
+        ctx.client(client_key, |c| {
+            (c.entity(&unit).is_some() && c.entity(&temporary).is_some()).then_some(())
+        })
+    });
+
+    scenario.mutate(|ctx| {
+        ctx.server(|server| {
+            let mut entity = server.entity_mut(&unit).expect("entity exists");
+            let mut position = entity.component::<Position>().expect("has position");
+            *position.x = 10.0;
+        });
+        ctx.server(|server| {
+            server.send_message::<ReliableChannel, _>(&client_key, &TestMessage::new(42));
+        });
+    });
+    scenario.expect(|ctx| {
+        ctx.client(client_key, |c| {
+            let x = c.entity(&unit)?.component::<Position>().map(|p| *p.x)?;
+            let message = c.read_message::<ReliableChannel, TestMessage>().next()?;
+            (x == 10.0 && message.value == 42).then_some(())
+        })
+    });
+
+    scenario.mutate(|ctx| ctx.server(|server| server.despawn(&temporary)));
+    scenario.expect(|ctx| ctx.client(client_key, |c| c.entity(&temporary).is_none().then_some(())));
+
+    scenario
+        .mutate(|ctx| match recorder {
+            Recorder::Server => ctx.server(|server| server.stop_demo_recording(&client_key)),
+            Recorder::Client => ctx.client(client_key, |client| client.stop_demo_recording()),
+        })
+        .expect("demo was being recorded")
+}
+
+/// Plays the whole demo, returning the events seen along the way
+struct PlaybackSummary {
+    spawns: usize,
+    despawns: usize,
+    position_inserts: usize,
+    position_updates: usize,
+    messages: Vec<u32>,
+}
+
+fn play_to_end(player: &mut DemoPlayer<TestEntity>, world: &mut TestWorld) -> PlaybackSummary {
+    let mut summary = PlaybackSummary {
+        spawns: 0,
+        despawns: 0,
+        position_inserts: 0,
+        position_updates: 0,
+        messages: Vec::new(),
+    };
+    while !player.is_finished() {
+        player.update(world.proxy_mut(), player.tick_duration());
+        let mut events = player.take_world_events();
+        summary.spawns += events.read::<SpawnEntityEvent>().count();
+        summary.despawns += events.read::<DespawnEntityEvent>().count();
+        summary.position_inserts += events.read::<InsertComponentEvent<Position>>().count();
+        summary.position_updates += events.read::<UpdateComponentEvent<Position>>().count();
+        summary.messages.extend(
+            events
+                .read::<MessageEvent<ReliableChannel, TestMessage>>()
+                .map(|message| message.value),
+        );
+    }
+    summary
+}
+
+/// Checks the world a full playback leaves behind: the squad and a unit at
+/// x = 10 that the squad resolves to.
+fn assert_final_world(player: &DemoPlayer<TestEntity>, world: &TestWorld) {
+    let entities = world.proxy().entities();
+    assert_eq!(entities.len(), 2);
+
+    let unit = entities
+        .iter()
+        .copied()
+        .find(|entity| world.proxy().has_component::<Position>(entity))
+        .expect("unit was replayed");
+    let position = world
+        .proxy()
+        .component::<Position>(&unit)
+        .map(|p| (*p.x, *p.y));
+    assert_eq!(position, Some((10.0, 2.0)));
+
+    let squad = entities
+        .iter()
+        .copied()
+        .find(|entity| world.proxy().has_component::<Squad>(entity))
+        .expect("squad was replayed");
+    let proxy = world.proxy();
+    let squad = proxy.component::<Squad>(&squad).expect("has squad");
+    assert_eq!(squad.members.get(player), vec![unit]);
+}
+
+fn assert_demo_plays_back(bytes: &[u8]) {
+    let mut player = DemoPlayer::<TestEntity>::new(protocol(), bytes).expect("demo loads");
+    let mut world = TestWorld::default();
+
+    let summary = play_to_end(&mut player, &mut world);
+    assert_eq!(summary.spawns, 3);
+    assert_eq!(summary.despawns, 1);
+    assert_eq!(summary.position_inserts, 2);
+    assert!(summary.position_updates >= 1);
+    assert_eq!(summary.messages, vec![42]);
+    assert_final_world(&player, &world);
+
+    // Seeking back to the start clears the world, and seeking to the end
+    // rebuilds it, reporting the survivors as fresh spawns
+    player.seek(world.proxy_mut(), Duration::ZERO);
+    assert!(!player.is_finished());
+    let mut events = player.take_world_events();
+    assert_eq!(events.read::<DespawnEntityEvent>().count(), 2);
+
+    player.seek(world.proxy_mut(), player.duration());
+    assert!(player.is_finished());
+    let mut events = player.take_world_events();
+    assert_eq!(events.read::<SpawnEntityEvent>().count(), 2);
+    assert_eq!(events.read::<InsertComponentEvent<Position>>().count(), 1);
+    assert_eq!(
+        events
+            .read::<MessageEvent<ReliableChannel, TestMessage>>()
+            .count(),
+        0
+    );
+    assert_final_world(&player, &world);
+}
+
+#[test]
+fn server_recorded_demo_plays_back() {
+    assert_demo_plays_back(&record_session(Recorder::Server));
+}
+
+#[test]
+fn client_recorded_demo_plays_back() {
+    assert_demo_plays_back(&record_session(Recorder::Client));
+}
+
+#[test]
+fn playback_honours_pause_and_speed() {
+    let bytes = record_session(Recorder::Server);
+    let mut player = DemoPlayer::<TestEntity>::new(protocol(), &bytes).expect("demo loads");
+    let mut world = TestWorld::default();
+
+    player.pause();
+    assert!(player.is_paused());
+    player.update(world.proxy_mut(), player.duration());
+    assert_eq!(player.position(), Duration::ZERO);
+    assert!(world.proxy().entities().is_empty());
+
+    player.play();
+    player.set_speed(2.0);
+    let step = player.tick_duration();
+    player.update(world.proxy_mut(), step);
+    assert_eq!(player.position(), step * 2);
+    assert!(!world.proxy().entities().is_empty());
+}
+
+#[test]
+fn disconnecting_discards_the_recording() {
+    let mut scenario = Scenario::new();
+    scenario.server_start(ServerConfig::default(), protocol());
+    let room_key = scenario.mutate(|ctx| ctx.server(|server| server.create_room().key()));
+    let client_key = common::connect_client(&mut scenario, "alice", Some(room_key));
+
+    scenario.mutate(|ctx| ctx.server(|server| assert!(server.start_demo_recording(&client_key))));
+    scenario.mutate(|ctx| ctx.server(|server| server.disconnect_user(&client_key)));
+    scenario.expect(|ctx| {
+        ctx.server(|server| (!server.user_exists(&client_key)).then_some(()))
+    });
+
+    let demo = scenario.mutate(|ctx| ctx.server(|server| server.stop_demo_recording(&client_key)));
+    assert!(demo.is_none());
+}
+
+#[test]
+fn out_of_range_speeds_are_ignored_or_clamped() {
+    let bytes = record_session(Recorder::Server);
+    let mut player = DemoPlayer::<TestEntity>::new(protocol(), &bytes).expect("demo loads");
+    let mut world = TestWorld::default();
+
+    player.set_speed(f32::NAN);
+    player.set_speed(f32::INFINITY);
+    assert_eq!(player.speed(), 1.0);
+
+    player.set_speed(f32::MAX);
+    assert_eq!(player.speed(), 64.0);
+    player.update(world.proxy_mut(), Duration::MAX);
+    assert_eq!(player.position(), player.duration());
+}
+
+#[test]
+fn demo_from_another_protocol_is_rejected() {
+    let bytes = record_session(Recorder::Server);
+
+    let mut other_protocol = protocol();
+    other_protocol.add_component::<Marker>();
+    let result = DemoPlayer::<TestEntity>::new(other_protocol, &bytes);
+    assert!(matches!(result, Err(DemoError::ProtocolMismatch { .. })));
+}
@@ -0,0 +1,86 @@
+//! End-to-end integration tests for `EntitySet`.
+//!
+//! `Squad` in the test protocol holds one. These tests check that a squad
+//! reaches the client while some of its members are still out of scope, and
+//! that each member resolves on its own once its entity arrives.
+
+mod common;
+
+use naia_test_harness::{ClientKey, EntityKey, Position, Scenario, Squad};
+
+/// Spawns an entity outside of every room, so no client has it in scope
+fn spawn_out_of_scope(scenario: &mut Scenario) -> EntityKey {
+    scenario.mutate(|ctx| {
+        ctx.server(|server| {
+            server
+                .spawn(|mut e| {
+                    e.insert_component(Position::new(0.0, 0.0));
+                })
+                .0
+        })
+    })
+}
+
+/// Waits until the client's squad has `len` members, `unresolved` of them
+/// still waiting on their entity
+fn expect_client_squad(
+    scenario: &mut Scenario,
+    client_key: ClientKey,
+    squad: &EntityKey,
+    len: usize,
+    unresolved: usize,
+) {
+    scenario.expect(|ctx| {
+        ctx.client(client_key, |c| {
+            let entity_ref = c.entity(squad)?;
+            let squad = entity_ref.component::<Squad>()?;
+            (squad.members.len() == len && squad.members.unresolved_count() == unresolved)
+                .then_some(())
+        })
+    });
+}
+
+#[test]
+fn entity_set_members_resolve_independently() {
+    let mut scenario = Scenario::new();
+    let client_key = common::server_with_one_client(&mut scenario);
+    let room_key = scenario.last_room();
+
+    let first = spawn_out_of_scope(&mut scenario);
+    let second = spawn_out_of_scope(&mut scenario);
+
+    let squad = scenario.mutate(|ctx| {
+        ctx.server(|server| {
+            let (squad, _) = server.spawn(|mut e| {
+                e.insert_component(Squad::default());
+                e.enter_room(&room_key);
+            });
+            for member in [&first, &second] {
+                assert!(server.entity_set_insert(&squad, member, |s: &mut Squad| &mut s.members));
+            }
+            squad
+        })
+    });
+
+    // The squad arrives while neither member is in scope
+    expect_client_squad(&mut scenario, client_key, &squad, 2, 2);
+
+    // One member coming into scope resolves only that member
+    scenario.mutate(|ctx| {
+        ctx.server(|server| {
+            server
+                .entity_mut(&first)
+                .expect("entity exists")
+                .enter_room(&room_key);
+        })
+    });
+    expect_client_squad(&mut scenario, client_key, &squad, 2, 1);
+
+    // Removing the still waiting member leaves the resolved one
+    scenario.mutate(|ctx| {
+        ctx.server(|server| {
+            assert!(server.entity_set_remove(&squad, &second, |s: &mut Squad| &mut s.members));
+        })
+    });
+    expect_client_squad(&mut scenario, client_key, &squad, 1, 0);
+}
@@ -0,0 +1,356 @@
+//! End-to-end integration tests for the lag-compensation Historian.
+//!
+//! The Historian must sample the world between two recorded ticks,
+//! blending registered components, rewind the live world for a scoped
+//! closure and restore it afterwards, store each component value only on
+//! the ticks it changes, and track when each entity spawned and despawned.
+
+mod common;
+
+use naia_server::{EntityAbsence, EntityLifetime, Historian, RoomKey, ServerConfig};
+use naia_shared::{ComponentKind, Tick, WorldRefType};
+use naia_test_harness::{protocol, ClientKey, EntityKey, Position, Scenario, Squad};
+
+/// Starts a server with a connected client and one positioned entity in a
+/// room they share.
+fn start(x: f32, y: f32) -> (Scenario, ClientKey, EntityKey) {
+    let (scenario, client_key, room_key) = start_empty();
+    spawn_positioned(scenario, client_key, room_key, x, y)
+}
+
+fn start_empty() -> (Scenario, ClientKey, RoomKey) {
+    let mut scenario = Scenario::new();
+    scenario.server_start(ServerConfig::default(), protocol());
+    let room_key = scenario.mutate(|ctx| ctx.server(|server| server.create_room().key()));
+
+    let client_key = common::connect_client(&mut scenario, "alice", Some(room_key));
+
+    (scenario, client_key, room_key)
+}
+
+fn spawn_positioned(
+    mut scenario: Scenario,
+    client_key: ClientKey,
+    room_key: RoomKey,
+    x: f32,
+    y: f32,
+) -> (Scenario, ClientKey, EntityKey) {
+    let entity = scenario.mutate(|ctx| {
+        ctx.server(|server| {
+            server
+                .spawn(|mut e| {
+                    e.insert_component(Position::new(x, y))
+                        .enter_room(&room_key);
+                })
+                .0
+        })
+    });
+    scenario.expect(|ctx| ctx.client(client_key, |c| c.entity(&entity).map(|_| ())));
+
+    (scenario, client_key, entity)
+}
+
+fn set_position(scenario: &mut Scenario, entity: &EntityKey, x: f32, y: f32) {
+    scenario.mutate(|ctx| {
+        ctx.server(|server| {
+            let mut entity = server.entity_mut(entity).expect("entity exists");
+            let mut position = entity.component::<Position>().expect("has position");
+            *position.x = x;
+            *position.y = y;
+        })
+    });
+}
+
+fn record(scenario: &mut Scenario, tick: Tick) {
+    scenario.mutate(|ctx| ctx.server(|server| server.record_historian_tick(tick)));
+}
+
+/// Reads the single entity's position out of `historian.snapshot_at`
+fn sampled_position(historian: &Historian, tick: Tick, fraction: f32) -> Option<(f32, f32)> {
+    let snapshot = historian.snapshot_at(tick, fraction)?;
+    assert_eq!(snapshot.len(), 1);
+    let components = snapshot
+        .values()
+        .next()
+        .expect("one entity")
+        .as_ref()
+        .expect("entity is present");
+    assert_eq!(components.len(), 1);
+    let position = components
+        .values()
+        .next()
+        .and_then(|component| component.to_any().downcast_ref::<Position>())
+        .expect("position was recorded");
+    Some((*position.x, *position.y))
+}
+
+#[test]
+fn snapshot_at_interpolates_registered_components() {
+    let (mut scenario, _, entity) = start(0.0, 0.0);
+    scenario.mutate(|ctx| {
+        ctx.server(|server| {
+            server.enable_historian(64);
+            server
+                .historian_mut()
+                .expect("historian enabled")
+                .interpolate_component::<Position>();
+        })
+    });
+    record(&mut scenario, 10);
+    set_position(&mut scenario, &entity, 10.0, 4.0);
+    record(&mut scenario, 11);
+
+    scenario.mutate(|ctx| {
+        ctx.server(|server| {
+            let historian = server.historian().expect("historian enabled");
+            assert_eq!(sampled_position(historian, 10, 0.0), Some((0.0, 0.0)));
+            assert_eq!(sampled_position(historian, 10, 0.25), Some((2.5, 1.0)));
+            assert_eq!(sampled_position(historian, 10, 1.0), Some((10.0, 4.0)));
+            // The newest tick has nothing to blend towards
+            assert_eq!(sampled_position(historian, 11, 0.5), Some((10.0, 4.0)));
+            assert!(historian.snapshot_at(9, 0.5).is_none());
+        })
+    });
+
+    // Without registering Position, the earlier tick's value is kept
+    scenario.mutate(|ctx| {
+        ctx.server(|server| {
+            server.enable_historian(64);
+        })
+    });
+    record(&mut scenario, 20);
+    set_position(&mut scenario, &entity, 0.0, 0.0);
+    record(&mut scenario, 21);
+    scenario.mutate(|ctx| {
+        ctx.server(|server| {
+            let historian = server.historian().expect("historian enabled");
+            assert_eq!(sampled_position(historian, 20, 0.5), Some((10.0, 4.0)));
+        })
+    });
+}
+
+#[test]
+fn rewind_world_restores_live_values() {
+    let (mut scenario, client_key, entity) = start(0.0, 0.0);
+    scenario.mutate(|ctx| {
+        ctx.server(|server| {
+            server.enable_historian(64);
+            server
+                .historian_mut()
+                .expect("historian enabled")
+                .interpolate_component::<Position>();
+        })
+    });
+    record(&mut scenario, 10);
+    set_position(&mut scenario, &entity, 10.0, 10.0);
+    record(&mut scenario, 11);
+    set_position(&mut scenario, &entity, 30.0, 30.0);
+
+    scenario.mutate(|ctx| {
+        ctx.server(|server| {
+            let world_entity = server.world_entity(&entity).expect("entity exists");
+            let rewound = server.rewind_world(10, 0.5, |world| {
+                world
+                    .component::<Position>(&world_entity)
+                    .map(|position| (*position.x, *position.y))
+            });
+            assert_eq!(rewound, Some(Some((5.0, 5.0))));
+
+            // A tick that was never recorded doesn't run the closure
+            assert!(server.rewind_world(40, 0.0, |_| ()).is_none());
+
+            let live = server
+                .entity(&entity)
+                .and_then(|e| e.component::<Position>().map(|p| (*p.x, *p.y)));
+            assert_eq!(live, Some((30.0, 30.0)));
+        })
+    });
+
+    // The client ends up on the live value, not the rewound one
+    scenario.expect(|ctx| {
+        ctx.client(client_key, |c| {
+            let position = c.entity(&entity)?.component::<Position>().map(|p| *p.x)?;
+            (position == 30.0).then_some(())
+        })
+    });
+}
+
+#[test]
+fn rewind_world_queues_nothing_to_send() {
+    let (mut scenario, client_key, entity) = start(0.0, 0.0);
+    scenario.mutate(|ctx| ctx.server(|server| server.enable_historian(64)));
+    record(&mut scenario, 10);
+    set_position(&mut scenario, &entity, 10.0, 10.0);
+    scenario.expect(|ctx| {
+        ctx.client(client_key, |c| {
+            let position = c.entity(&entity)?.component::<Position>().map(|p| *p.x)?;
+            (position == 10.0).then_some(())
+        })
+    });
+
+    scenario.mutate(|ctx| {
+        ctx.server(|server| {
+            assert_eq!(server.dirty_update_count(), 0);
+            assert!(server.rewind_world(10, 0.0, |_| ()).is_some());
+            assert_eq!(server.dirty_update_count(), 0);
+
+            // A change made before rewinding is still sent
+            {
+                let mut entity = server.entity_mut(&entity).expect("entity exists");
+                let mut position = entity.component::<Position>().expect("has position");
+                *position.x = 20.0;
+            }
+            assert!(server.rewind_world(10, 0.0, |_| ()).is_some());
+            assert_eq!(server.dirty_update_count(), 1);
+        })
+    });
+    scenario.expect(|ctx| {
+        ctx.client(client_key, |c| {
+            let position = c.entity(&entity)?.component::<Position>().map(|p| *p.x)?;
+            (position == 20.0).then_some(())
+        })
+    });
+}
+
+#[test]
+fn unchanged_components_are_stored_once() {
+    let (mut scenario, _, entity) = start(1.0, 1.0);
+    scenario.mutate(|ctx| ctx.server(|server| server.enable_historian(4)));
+
+    for tick in 0..10 {
+        record(&mut scenario, tick);
+    }
+    scenario.mutate(|ctx| {
+        ctx.server(|server| {
+            let historian = server.historian().expect("historian enabled");
+            assert_eq!(historian.len(), 5);
+            assert_eq!(historian.stored_values(), 1);
+            // The value recorded before the window is still visible within it
+            assert_eq!(sampled_position(historian, 5, 0.0), Some((1.0, 1.0)));
+            assert!(historian.snapshot_at_tick(4).is_none());
+        })
+    });
+
+    set_position(&mut scenario, &entity, 2.0, 2.0);
+    record(&mut scenario, 10);
+    scenario.mutate(|ctx| {
+        ctx.server(|server| {
+            let historian = server.historian().expect("historian enabled");
+            assert_eq!(historian.stored_values(), 2);
+            assert_eq!(sampled_position(historian, 9, 0.0), Some((1.0, 1.0)));
+            assert_eq!(sampled_position(historian, 10, 0.0), Some((2.0, 2.0)));
+        })
+    });
+
+    // Once a despawned entity ages out of the window, nothing is kept for it
+    scenario.mutate(|ctx| ctx.server(|server| server.despawn(&entity)));
+    for tick in 11..20 {
+        record(&mut scenario, tick);
+    }
+    scenario.mutate(|ctx| {
+        ctx.server(|server| {
+            let historian = server.historian().expect("historian enabled");
+            assert_eq!(historian.stored_values(), 0);
+            assert!(historian.snapshot_at_tick(19).expect("recorded").is_empty());
+        })
+    });
+}
+
+#[test]
+fn entity_lifecycle_is_recorded_per_tick() {
+    let (scenario, client_key, room_key) = start_empty();
+    let (mut scenario, _, veteran) = spawn_positioned(scenario, client_key, room_key, 0.0, 0.0);
+    scenario.mutate(|ctx| ctx.server(|server| server.enable_historian(64)));
+    record(&mut scenario, 10);
+
+    let (mut scenario, _, recruit) = spawn_positioned(scenario, client_key, room_key, 5.0, 5.0);
+    record(&mut scenario, 11);
+
+    let (veteran_id, recruit_id) = scenario.mutate(|ctx| {
+        ctx.server(|server| {
+            let ids = (
+                server.global_entity(&veteran).expect("entity exists"),
+                server.global_entity(&recruit).expect("entity exists"),
+            );
+            server.despawn(&veteran);
+            ids
+        })
+    });
+    record(&mut scenario, 12);
+
+    scenario.mutate(|ctx| {
+        ctx.server(|server| {
+            let historian = server.historian().expect("historian enabled");
+            assert_eq!(historian.spawned_at(10), Some(&[][..]));
+            assert_eq!(historian.spawned_at(11), Some(&[recruit_id][..]));
+            assert_eq!(historian.despawned_at(12), Some(&[veteran_id][..]));
+            assert!(historian.spawned_at(13).is_none());
+
+            assert_eq!(
+                historian.entity_lifetime(&veteran_id),
+                Some(EntityLifetime {
+                    spawned: None,
+                    despawned: Some(12),
+                })
+            );
+            assert_eq!(
+                historian.entity_lifetime(&recruit_id),
+                Some(EntityLifetime {
+                    spawned: Some(11),
+                    despawned: None,
+                })
+            );
+
+            let before = historian.snapshot_at_tick(10).expect("recorded");
+            assert!(before[&veteran_id].is_ok());
+            assert_eq!(
+                before[&recruit_id].as_ref().err(),
+                Some(&EntityAbsence::NotYetSpawned)
+            );
+
+            let after = historian.snapshot_at_tick(12).expect("recorded");
+            assert_eq!(
+                after[&veteran_id].as_ref().err(),
+                Some(&EntityAbsence::Despawned)
+            );
+            assert!(after[&recruit_id].is_ok());
+        })
+    });
+
+    // Once its despawn ages out of the window, the veteran is forgotten
+    for tick in 13..=80 {
+        record(&mut scenario, tick);
+    }
+    scenario.mutate(|ctx| {
+        ctx.server(|server| {
+            let historian = server.historian().expect("historian enabled");
+            assert!(historian.entity_lifetime(&veteran_id).is_none());
+            let snapshot = historian.snapshot_at_tick(80).expect("recorded");
+            assert_eq!(snapshot.len(), 1);
+        })
+    });
+}
+
+#[test]
+fn filtered_entities_are_reported_as_filtered() {
+    let (mut scenario, _, entity) = start(0.0, 0.0);
+    scenario.mutate(|ctx| {
+        ctx.server(|server| {
+            server.enable_historian_filtered(64, [ComponentKind::of::<Squad>()]);
+        })
+    });
+    record(&mut scenario, 10);
+
+    scenario.mutate(|ctx| {
+        ctx.server(|server| {
+            let global_entity = server.global_entity(&entity).expect("entity exists");
+            let historian = server.historian().expect("historian enabled");
+            let snapshot = historian.snapshot_at_tick(10).expect("recorded");
+            assert_eq!(
+                snapshot[&global_entity].as_ref().err(),
+                Some(&EntityAbsence::Filtered)
+            );
+            assert_eq!(historian.stored_values(), 0);
+        })
+    });
+}
@@ -0,0 +1,274 @@
+//! End-to-end integration tests for observer connections.
+//!
+//! A user accepted with `accept_observer_connection` must see every entity
+//! the server replicates without joining a room, ignore `UserScope`
+//! settings, and stay read-only: it can never hold authority, its
+//! messages and entities never reach the application, and it isn't counted
+//! as a player.
+
+mod common;
+
+use naia_client::Publicity;
+use naia_server::{ReplicationConfig, RoomKey, ServerConfig};
+use naia_shared::{sequence_greater_than, AuthorityError, EntityAuthStatus};
+use naia_test_harness::{
+    protocol,
+    test_protocol::{ReliableChannel, TestMessage, TickBufferedChannel},
+    EntityKey, Position, Scenario, ServerSpawnEntityEvent, ToTicks,
+};
+
+/// Spawns a positioned entity in `room_key` with `config`
+fn spawn_in_room(
+    scenario: &mut Scenario,
+    room_key: RoomKey,
+    x: f32,
+    config: ReplicationConfig,
+) -> EntityKey {
+    scenario.mutate(|ctx| {
+        ctx.server(|server| {
+            server
+                .spawn(|mut e| {
+                    e.insert_component(Position::new(x, 0.0))
+                        .configure_replication(config)
+                        .enter_room(&room_key);
+                })
+                .0
+        })
+    })
+}
+
+#[test]
+fn observer_sees_every_room_without_joining() {
+    let mut scenario = Scenario::new();
+    scenario.server_start(ServerConfig::default(), protocol());
+    let (lobby, arena) = scenario.mutate(|ctx| {
+        ctx.server(|server| (server.create_room().key(), server.create_room().key()))
+    });
+
+    let lobby_entity = spawn_in_room(&mut scenario, lobby, 1.0, ReplicationConfig::public());
+    let player = common::connect_client(&mut scenario, "player", Some(lobby));
+    let observer = common::connect_observer(&mut scenario, "observer");
+
+    scenario.mutate(|ctx| {
+        ctx.server(|server| {
+            assert!(server.user(&observer).expect("user exists").is_observer());
+            assert!(!server.user(&player).expect("user exists").is_observer());
+            assert_eq!(
+                server.user(&observer).expect("user exists").rooms_count(),
+                0
+            );
+        })
+    });
+
+    // Entities that exist before the observer connects, and entities
+    // spawned afterwards in rooms it never joined, both reach it
+    let arena_entity = spawn_in_room(&mut scenario, arena, 2.0, ReplicationConfig::public());
+    scenario.expect(|ctx| {
+        let observer_sees = ctx.client(observer, |c| {
+            c.entity(&lobby_entity).is_some() && c.entity(&arena_entity).is_some()
+        });
+        let player_sees = ctx.client(player, |c| {
+            c.entity(&lobby_entity).is_some() && c.entity(&arena_entity).is_none()
+        });
+        (observer_sees && player_sees).then_some(())
+    });
+
+    // Scope exclusions don't apply to observers
+    scenario.mutate(|ctx| {
+        ctx.server(|server| {
+            server
+                .user_scope_mut(&observer)
+                .expect("user exists")
+                .exclude(&lobby_entity);
+        })
+    });
+    scenario.mutate(|ctx| {
+        ctx.server(|server| {
+            let mut entity = server.entity_mut(&lobby_entity).expect("entity exists");
+            let mut position = entity.component::<Position>().expect("has position");
+            *position.x = 5.0;
+        })
+    });
+    scenario.expect(|ctx| {
+        ctx.client(observer, |c| {
+            let x = c
+                .entity(&lobby_entity)?
+                .component::<Position>()
+                .map(|p| *p.x)?;
+            (x == 5.0).then_some(())
+        })
+    });
+
+    // Despawns still reach the observer
+    scenario.mutate(|ctx| ctx.server(|server| server.despawn(&arena_entity)));
+    scenario.expect(|ctx| {
+        ctx.client(observer, |c| {
+            c.entity(&arena_entity).is_none().then_some(())
+        })
+    });
+}
+
+#[test]
+fn observer_never_gets_authority() {
+    let mut scenario = Scenario::new();
+    scenario.server_start(ServerConfig::default(), protocol());
+    let room_key = scenario.mutate(|ctx| ctx.server(|server| server.create_room().key()));
+    let player = common::connect_client(&mut scenario, "player", Some(room_key));
+    let observer = common::connect_observer(&mut scenario, "observer");
+
+    let entity = spawn_in_room(&mut scenario, room_key, 0.0, ReplicationConfig::delegated());
+    scenario.expect(|ctx| {
+        ctx.client(observer, |c| {
+            (c.entity(&entity)?.authority() == Some(EntityAuthStatus::Available)).then_some(())
+        })
+    });
+
+    // A request is denied, and the server keeps the entity available
+    scenario.mutate(|ctx| {
+        ctx.client(observer, |c| {
+            c.entity_mut(&entity)
+                .expect("entity exists")
+                .request_authority()
+                .expect("request is sent");
+        })
+    });
+    scenario
+        .until(20.ticks())
+        .expect_msg("request denied", |ctx| {
+            ctx.client(observer, |c| {
+                (c.entity(&entity)?.authority() == Some(EntityAuthStatus::Denied)).then_some(())
+            })
+        });
+    scenario.mutate(|ctx| {
+        ctx.server(|server| {
+            let status = server.entity(&entity).and_then(|e| e.authority());
+            assert_eq!(status, Some(EntityAuthStatus::Available));
+        })
+    });
+
+    // The server can't hand it authority either, but players still can
+    scenario.mutate(|ctx| {
+        ctx.server(|server| {
+            let mut entity_mut = server.entity_mut(&entity).expect("entity exists");
+            assert!(matches!(
+                entity_mut.give_authority(&observer),
+                Err(AuthorityError::Observer)
+            ));
+            assert!(entity_mut.give_authority(&player).is_ok());
+        })
+    });
+    scenario.expect(|ctx| {
+        ctx.client(player, |c| {
+            (c.entity(&entity)?.authority() == Some(EntityAuthStatus::Granted)).then_some(())
+        })
+    });
+}
+
+#[test]
+fn observer_tick_buffered_messages_are_dropped() {
+    let mut scenario = Scenario::new();
+    scenario.server_start(ServerConfig::default(), protocol());
+    let room_key = scenario.mutate(|ctx| ctx.server(|server| server.create_room().key()));
+    let player = common::connect_client(&mut scenario, "player", Some(room_key));
+    let observer = common::connect_observer(&mut scenario, "observer");
+
+    let tick = scenario.mutate(|ctx| ctx.server(|server| server.current_tick().wrapping_add(5)));
+    scenario.mutate(|ctx| {
+        ctx.client(player, |c| {
+            c.send_tick_buffer_message::<TickBufferedChannel, _>(&tick, &TestMessage::new(1));
+        });
+        ctx.client(observer, |c| {
+            c.send_tick_buffer_message::<TickBufferedChannel, _>(&tick, &TestMessage::new(2));
+        });
+    });
+    scenario
+        .until(50.ticks())
+        .expect_msg("server advanced past tick", |ctx| {
+            let now = ctx.server(|server| server.current_tick());
+            sequence_greater_than(now, tick).then_some(())
+        });
+
+    let messages = scenario.mutate(|ctx| {
+        ctx.server(|server| {
+            server
+                .receive_tick_buffer_messages(&tick)
+                .read::<TickBufferedChannel, TestMessage>()
+        })
+    });
+    let values: Vec<u32> = messages.iter().map(|(_, message)| message.value).collect();
+    assert_eq!(values, vec![1]);
+}
+
+#[test]
+fn observer_is_not_counted_as_a_user() {
+    let mut scenario = Scenario::new();
+    scenario.server_start(ServerConfig::default(), protocol());
+    let room_key = scenario.mutate(|ctx| ctx.server(|server| server.create_room().key()));
+    let player = common::connect_client(&mut scenario, "player", Some(room_key));
+    let _observer = common::connect_observer(&mut scenario, "observer");
+
+    scenario.mutate(|ctx| {
+        ctx.server(|server| {
+            assert_eq!(server.user_keys(), vec![player]);
+            assert_eq!(server.users_count(), 1);
+        })
+    });
+}
+
+#[test]
+fn observer_messages_and_entities_are_dropped() {
+    let mut scenario = Scenario::new();
+    scenario.server_start(ServerConfig::default(), protocol());
+    let room_key = scenario.mutate(|ctx| ctx.server(|server| server.create_room().key()));
+    let player = common::connect_client(&mut scenario, "player", Some(room_key));
+    let observer = common::connect_observer(&mut scenario, "observer");
+
+    let observer_entity = scenario.mutate(|ctx| {
+        ctx.client(observer, |c| {
+            c.send_message::<ReliableChannel, _>(&TestMessage::new(2))
+                .expect("message is queued");
+            c.spawn(|mut e| {
+                e.configure_replication(Publicity::Public)
+                    .insert_component(Position::new(2.0, 0.0));
+            })
+        })
+    });
+    let player_entity = scenario.mutate(|ctx| {
+        ctx.client(player, |c| {
+            c.send_message::<ReliableChannel, _>(&TestMessage::new(1))
+                .expect("message is queued");
+            c.spawn(|mut e| {
+                e.configure_replication(Publicity::Public)
+                    .insert_component(Position::new(1.0, 0.0));
+            })
+        })
+    });
+
+    // Only the player's message and entity arrive, however long we wait
+    let until = scenario.mutate(|ctx| ctx.server(|server| server.current_tick().wrapping_add(10)));
+    let mut values = Vec::new();
+    let mut spawners = Vec::new();
+    scenario
+        .until(100.ticks())
+        .expect_msg("server ran past the player's spawn", |ctx| {
+            ctx.server(|server| {
+                values.extend(
+                    server
+                        .read_message::<ReliableChannel, TestMessage>()
+                        .map(|(_, message)| message.value),
+                );
+                while let Some((client_key, _)) = server.read_event::<ServerSpawnEntityEvent>() {
+                    spawners.push(client_key);
+                }
+                (server.has_entity(&player_entity)
+                    && sequence_greater_than(server.current_tick(), until))
+                .then_some(())
+            })
+        });
+
+    assert_eq!(values, vec![1]);
+    assert_eq!(spawners, vec![player]);
+    scenario.mutate(|ctx| {
+        ctx.server(|server| assert!(server.entity(&observer_entity).is_none()));
+    });
+}
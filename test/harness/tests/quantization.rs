@@ -0,0 +1,102 @@
+//! End-to-end integration tests for field-level wire quantization.
+//!
+//! `QuantizedTransform` in the test protocol uses `#[replicate(quantize(..))]`,
+//! `quaternion(..)` and `normalized(..)` on its fields. These tests check that
+//! the client receives values within each encoding's step on both the initial
+//! spawn and later updates, while the server keeps full precision.
+
+mod common;
+
+use naia_test_harness::{ClientKey, EntityKey, QuantizedTransform, Scenario};
+
+fn close<const N: usize>(a: [f32; N], b: [f32; N], tolerance: f32) -> bool {
+    a.iter()
+        .zip(b.iter())
+        .all(|(a, b)| (a - b).abs() <= tolerance)
+}
+
+/// Waits until the client's copy matches `expected` within each field's
+/// quantization step.
+fn expect_client_transform(
+    scenario: &mut Scenario,
+    client_key: ClientKey,
+    entity: &EntityKey,
+    expected: &([f32; 3], [f32; 4], [f32; 3], f32),
+) {
+    let (translation, rotation, facing, scale) = *expected;
+    scenario.expect(|ctx| {
+        ctx.client(client_key, |c| {
+            let entity_ref = c.entity(entity)?;
+            let transform = entity_ref.component::<QuantizedTransform>()?;
+            let dot: f32 = rotation
+                .iter()
+                .zip(transform.rotation.iter())
+                .map(|(a, b)| a * b)
+                .sum();
+            (close(*transform.translation, translation, 0.005)
+                && dot.abs() > 0.9999
+                && close(*transform.facing, facing, 0.002)
+                && (*transform.scale - scale).abs() <= 10.0 / 255.0 / 2.0 + f32::EPSILON)
+                .then_some(())
+        })
+    });
+}
+
+#[test]
+fn quantized_fields_replicate_within_step() {
+    let mut scenario = Scenario::new();
+    let client_key = common::server_with_one_client(&mut scenario);
+    let room_key = scenario.last_room();
+
+    let spawned = (
+        [12.3456, -0.004, 99.999],
+        [0.5, 0.5, 0.5, 0.5],
+        [0.0, 1.0, 0.0],
+        1.337,
+    );
+    let entity = scenario.mutate(|ctx| {
+        ctx.server(|server| {
+            server
+                .spawn(|mut e| {
+                    e.insert_component(QuantizedTransform::new(
+                        spawned.0, spawned.1, spawned.2, spawned.3,
+                    ));
+                    e.enter_room(&room_key);
+                })
+                .0
+        })
+    });
+    expect_client_transform(&mut scenario, client_key, &entity, &spawned);
+
+    let updated = (
+        [-42.125, 7.0, 0.015],
+        [0.0, 0.0, -0.70710677, 0.70710677],
+        [0.0, 0.0, -1.0],
+        9.99,
+    );
+    scenario.mutate(|ctx| {
+        ctx.server(|server| {
+            let mut entity_mut = server.entity_mut(&entity).expect("entity exists");
+            let mut transform = entity_mut
+                .component::<QuantizedTransform>()
+                .expect("has transform");
+            *transform.translation = updated.0;
+            *transform.rotation = updated.1;
+            *transform.facing = updated.2;
+            *transform.scale = updated.3;
+        })
+    });
+    expect_client_transform(&mut scenario, client_key, &entity, &updated);
+
+    // Quantization only applies on the wire; the server keeps exact values.
+    let server_translation = scenario.mutate(|ctx| {
+        ctx.server(|server| {
+            let mut entity_mut = server.entity_mut(&entity).expect("entity exists");
+            let transform = entity_mut
+                .component::<QuantizedTransform>()
+                .expect("has transform");
+            *transform.translation
+        })
+    });
+    assert_eq!(server_translation, updated.0);
+}
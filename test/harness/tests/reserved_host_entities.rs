@@ -0,0 +1,54 @@
+//! End-to-end integration tests for relations to entities the user can't see
+//! yet.
+//!
+//! Writing an `EntityProperty` that points at an out-of-scope entity reserves
+//! a host entity id for it on that connection. When the entity later enters
+//! scope it must be spawned onto that id, so the relation resolves.
+
+mod common;
+
+use naia_test_harness::{
+    test_protocol::ReliableChannel, EntityCommandMessage, Position, Scenario, ToTicks,
+};
+
+#[test]
+fn message_to_out_of_scope_entity_arrives_once_it_is_in_scope() {
+    let mut scenario = Scenario::new();
+    let client_key = common::server_with_one_client(&mut scenario);
+    let room_key = scenario.last_room();
+
+    // The entity is outside every room when the message pointing at it is sent
+    let entity = scenario.mutate(|ctx| {
+        ctx.server(|server| {
+            let (entity, _) = server.spawn(|mut e| {
+                e.insert_component(Position::new(0.0, 0.0));
+            });
+            let mut command = EntityCommandMessage::new("follow");
+            server.set_entity_property(&mut command.target, &entity);
+            server.send_message::<ReliableChannel, _>(&client_key, &command);
+            entity
+        })
+    });
+    scenario.until(20.ticks()).expect(|_ctx| Some(()));
+
+    scenario.mutate(|ctx| {
+        ctx.server(|server| {
+            server
+                .entity_mut(&entity)
+                .expect("entity exists")
+                .enter_room(&room_key);
+        })
+    });
+    scenario.expect(|ctx| {
+        ctx.client(client_key, |c| {
+            if !c.has_entity(&entity) {
+                return None;
+            }
+            let commands: Vec<_> = c
+                .read_message::<ReliableChannel, EntityCommandMessage>()
+                .map(|message| message.command)
+                .collect();
+            (commands == vec!["follow".to_string()]).then_some(())
+        })
+    });
+}
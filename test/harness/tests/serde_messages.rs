@@ -0,0 +1,144 @@
+//! End-to-end integration tests for `SerdeMessage`, the message wrapper for
+//! values that only implement `serde`'s traits.
+//!
+//! The value is bincode-encoded into the bit stream with an exact
+//! `bit_length`, so it must round-trip in both directions, fragment when it
+//! doesn't fit in a packet, and be counted by per-channel statistics.
+
+mod common;
+
+use std::{collections::BTreeMap, time::Duration};
+
+use naia_client::ClientConfig;
+use naia_server::ServerConfig;
+use naia_shared::{Protocol, SerdeMessage};
+use naia_test_harness::{protocol, test_protocol::ReliableChannel, ClientKey, Scenario};
+use serde::{Deserialize, Serialize};
+
+/// A domain type with no naia `Serde` impl
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct Order {
+    id: u128,
+    placed_at: (i64, u32),
+    lines: Vec<(String, u32)>,
+    tags: BTreeMap<String, String>,
+    note: Option<String>,
+}
+
+impl Order {
+    fn new(id: u128, line_count: usize) -> Self {
+        Self {
+            id,
+            placed_at: (1_760_000_000, 500),
+            lines: (0..line_count)
+                .map(|index| (format!("item-{index}"), index as u32))
+                .collect(),
+            tags: BTreeMap::from([("region".to_string(), "eu".to_string())]),
+            note: Some("leave at the door".to_string()),
+        }
+    }
+}
+
+fn serde_protocol() -> Protocol {
+    let mut protocol = protocol();
+    protocol.add_message::<SerdeMessage<Order>>();
+    protocol
+}
+
+// `connection_stats` needs bandwidth monitoring on both sides
+fn test_client_config() -> ClientConfig {
+    let mut config = common::test_client_config();
+    config.connection.bandwidth_measure_duration = Some(Duration::from_secs(1));
+    config
+}
+
+fn connect_client(scenario: &mut Scenario) -> ClientKey {
+    let mut server_config = ServerConfig::default();
+    server_config.connection.bandwidth_measure_duration = Some(Duration::from_secs(1));
+    scenario.server_start(server_config, serde_protocol());
+
+    common::connect_client_with(
+        scenario,
+        "alice",
+        test_client_config(),
+        serde_protocol(),
+        None,
+    )
+}
+
+#[test]
+fn serde_message_round_trips_both_ways() {
+    let mut scenario = Scenario::new();
+    let client_key = connect_client(&mut scenario);
+
+    let order = Order::new(u128::MAX - 7, 3);
+    let message = SerdeMessage::new(order.clone()).expect("order encodes");
+    scenario.mutate(|ctx| {
+        ctx.server(|server| server.send_message::<ReliableChannel, _>(&client_key, &message));
+    });
+    scenario.expect(|ctx| {
+        ctx.client(client_key, |c| {
+            let message = c
+                .read_message::<ReliableChannel, SerdeMessage<Order>>()
+                .next()?;
+            (message.into_inner() == order).then_some(())
+        })
+    });
+
+    let reply = Order {
+        note: None,
+        ..Order::new(1, 0)
+    };
+    let message = SerdeMessage::new(reply.clone()).expect("order encodes");
+    scenario.mutate(|ctx| {
+        ctx.client(client_key, |c| {
+            c.send_message::<ReliableChannel, _>(&message)
+                .expect("message is queued");
+        })
+    });
+    scenario.expect(|ctx| {
+        ctx.server(|server| {
+            let (sender, message) = server
+                .read_message::<ReliableChannel, SerdeMessage<Order>>()
+                .next()?;
+            (sender == client_key && message.get() == &reply).then_some(())
+        })
+    });
+}
+
+#[test]
+fn oversized_serde_message_is_fragmented_and_counted() {
+    let mut scenario = Scenario::new();
+    let client_key = connect_client(&mut scenario);
+
+    // Several packets' worth of order lines
+    let order = Order::new(42, 400);
+    let message = SerdeMessage::new(order.clone()).expect("order encodes");
+    assert!(message.encoded_len() > 4000);
+    scenario.mutate(|ctx| {
+        ctx.server(|server| server.send_message::<ReliableChannel, _>(&client_key, &message));
+    });
+    scenario.expect(|ctx| {
+        ctx.client(client_key, |c| {
+            let message = c
+                .read_message::<ReliableChannel, SerdeMessage<Order>>()
+                .next()?;
+            (message.get() == &order).then_some(())
+        })
+    });
+
+    let bytes_sent = scenario.mutate(|ctx| {
+        ctx.server(|server| {
+            let stats = server
+                .connection_stats(&client_key)
+                .expect("client is connected");
+            stats
+                .channels
+                .iter()
+                .find(|(channel, _)| channel.ends_with("ReliableChannel"))
+                .map(|(_, stats)| stats.bytes_sent)
+                .expect("channel is registered")
+        })
+    });
+    assert!(bytes_sent >= message.encoded_len() as u64);
+}
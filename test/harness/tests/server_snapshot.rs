@@ -0,0 +1,180 @@
+//! End-to-end integration tests for server introspection snapshots.
+//!
+//! `Server::snapshot` reports which users are in which rooms, what each user
+//! has in scope, how many messages each user has queued per channel, and who
+//! holds authority over delegated entities.
+
+mod common;
+
+use naia_server::{
+    AuthorityHolder, ReplicationConfig, RoomKey, ServerConfig, ServerSnapshot, UserSnapshot,
+};
+use naia_shared::{BigMapKey, EntityAuthStatus};
+use naia_test_harness::{
+    protocol,
+    test_protocol::{ReliableChannel, TestMessage},
+    ClientKey, EntityKey, Position, Scenario, TestEntity,
+};
+
+fn spawn_in_room(
+    scenario: &mut Scenario,
+    room_key: RoomKey,
+    config: ReplicationConfig,
+) -> EntityKey {
+    let (entity, ()) = scenario.mutate(|ctx| {
+        ctx.server(|server| {
+            server.spawn(|mut e| {
+                e.insert_component(Position::new(0.0, 0.0))
+                    .configure_replication(config)
+                    .enter_room(&room_key);
+            })
+        })
+    });
+    entity
+}
+
+fn user_snapshot(
+    scenario: &mut Scenario,
+    client_key: ClientKey,
+) -> (UserSnapshot<TestEntity>, u64) {
+    scenario.mutate(|ctx| {
+        ctx.server(|server| {
+            let user_key = server.snapshot_user_key(&client_key).expect("user exists");
+            let user = server
+                .snapshot()
+                .users
+                .into_iter()
+                .find(|user| user.user_key == user_key)
+                .expect("user is in the snapshot");
+            (user, user_key)
+        })
+    })
+}
+
+fn reliable_queue_depth(snapshot: &ServerSnapshot<TestEntity>, user_key: u64) -> Option<usize> {
+    let user = snapshot
+        .users
+        .iter()
+        .find(|user| user.user_key == user_key)?;
+    user.channel_queue_depths
+        .iter()
+        .find(|(channel, _)| channel.ends_with("ReliableChannel"))
+        .map(|(_, depth)| *depth)
+}
+
+#[test]
+fn snapshot_reports_rooms_and_scopes() {
+    let mut scenario = Scenario::new();
+    scenario.server_start(ServerConfig::default(), protocol());
+    let room_key = scenario.mutate(|ctx| ctx.server(|server| server.create_room().key()));
+    let alice = common::connect_client(&mut scenario, "alice", Some(room_key));
+    let bob = common::connect_client(&mut scenario, "bob", None);
+
+    let entity = spawn_in_room(&mut scenario, room_key, ReplicationConfig::public());
+    scenario.expect(|ctx| ctx.client(alice, |c| c.entity(&entity).map(|_| ())));
+
+    let (alice_user, alice_key) = user_snapshot(&mut scenario, alice);
+    let (bob_user, bob_key) = user_snapshot(&mut scenario, bob);
+    let (snapshot, world_entity) = scenario.mutate(|ctx| {
+        ctx.server(|server| (server.snapshot(), server.world_entity(&entity).unwrap()))
+    });
+
+    assert!(alice_user.connected);
+    assert!(!alice_user.observer);
+    assert_eq!(alice_user.rooms, vec![room_key.to_u64()]);
+    assert_eq!(alice_user.entities_in_scope, vec![world_entity]);
+    assert!(alice_user
+        .channel_queue_depths
+        .keys()
+        .any(|channel| channel.ends_with("ReliableChannel")));
+
+    assert!(bob_user.connected);
+    assert!(bob_user.rooms.is_empty());
+    assert!(bob_user.entities_in_scope.is_empty());
+
+    let user_keys: Vec<u64> = snapshot.users.iter().map(|user| user.user_key).collect();
+    let mut expected_keys = vec![alice_key, bob_key];
+    expected_keys.sort_unstable();
+    assert_eq!(user_keys, expected_keys);
+
+    assert_eq!(snapshot.rooms.len(), 1);
+    assert_eq!(snapshot.rooms[0].room_key, room_key.to_u64());
+    assert_eq!(snapshot.rooms[0].users, vec![alice_key]);
+    assert_eq!(snapshot.rooms[0].entities, vec![world_entity]);
+    assert!(snapshot.delegated_entities.is_empty());
+}
+
+#[test]
+fn snapshot_reports_reliable_queue_depth() {
+    let mut scenario = Scenario::new();
+    scenario.server_start(ServerConfig::default(), protocol());
+    let alice = common::connect_client(&mut scenario, "alice", None);
+
+    // Messages stay queued until the client acknowledges them
+    let depth = scenario.mutate(|ctx| {
+        ctx.server(|server| {
+            for value in 0..3 {
+                server.send_message::<ReliableChannel, _>(&alice, &TestMessage::new(value));
+            }
+            let user_key = server.snapshot_user_key(&alice).unwrap();
+            reliable_queue_depth(&server.snapshot(), user_key)
+        })
+    });
+    assert_eq!(depth, Some(3));
+
+    scenario.expect(|ctx| {
+        ctx.server(|server| {
+            let user_key = server.snapshot_user_key(&alice)?;
+            (reliable_queue_depth(&server.snapshot(), user_key)? == 0).then_some(())
+        })
+    });
+}
+
+#[test]
+fn snapshot_reports_delegated_authority_holder() {
+    let mut scenario = Scenario::new();
+    scenario.server_start(ServerConfig::default(), protocol());
+    let room_key = scenario.mutate(|ctx| ctx.server(|server| server.create_room().key()));
+    let alice = common::connect_client(&mut scenario, "alice", Some(room_key));
+
+    let entity = spawn_in_room(&mut scenario, room_key, ReplicationConfig::delegated());
+    scenario.expect(|ctx| {
+        ctx.client(alice, |c| {
+            (c.entity(&entity).and_then(|e| e.authority()) == Some(EntityAuthStatus::Available))
+                .then_some(())
+        })
+    });
+
+    let (snapshot, world_entity) = scenario.mutate(|ctx| {
+        ctx.server(|server| (server.snapshot(), server.world_entity(&entity).unwrap()))
+    });
+    assert_eq!(snapshot.delegated_entities.len(), 1);
+    assert_eq!(snapshot.delegated_entities[0].entity, world_entity);
+    assert_eq!(
+        snapshot.delegated_entities[0].holder,
+        AuthorityHolder::Available
+    );
+
+    scenario.mutate(|ctx| {
+        ctx.client(alice, |c| {
+            c.entity_mut(&entity)
+                .expect("entity exists")
+                .request_authority()
+                .expect("request is allowed");
+        })
+    });
+    scenario.expect(|ctx| {
+        ctx.client(alice, |c| {
+            (c.entity(&entity).and_then(|e| e.authority()) == Some(EntityAuthStatus::Granted))
+                .then_some(())
+        })
+    });
+
+    let (_, alice_key) = user_snapshot(&mut scenario, alice);
+    let snapshot = scenario.mutate(|ctx| ctx.server(|server| server.snapshot()));
+    assert_eq!(
+        snapshot.delegated_entities[0].holder,
+        AuthorityHolder::User(alice_key)
+    );
+    assert!(snapshot.delegated_entities[0].status.is_some());
+}
@@ -0,0 +1,180 @@
+//! End-to-end integration tests for built-in spatial interest management.
+//!
+//! Covers the room-narrowing gate (out-of-range entities never spawn),
+//! enter/exit with hysteresis as entities move, room isolation, explicit
+//! scope overrides, and returning a user to plain room scoping by clearing
+//! their viewpoint.
+
+mod common;
+
+use naia_server::{ServerConfig, SpatialInterestConfig};
+use naia_test_harness::{protocol, ClientKey, EntityKey, Position, Scenario};
+
+/// Bring up a server with spatial interest enabled (enter 10, exit 15) and
+/// connect one client in a single room.
+fn server_with_one_client(scenario: &mut Scenario) -> ClientKey {
+    scenario.server_start(ServerConfig::default(), protocol());
+    let room_key = scenario.mutate(|ctx| {
+        ctx.server(|server| {
+            server.enable_spatial_interest::<Position>(SpatialInterestConfig::new(10.0, 15.0));
+            server.create_room().key()
+        })
+    });
+    scenario.set_last_room(room_key);
+
+    common::connect_client(scenario, "alice", Some(room_key))
+}
+
+fn spawn_at(scenario: &mut Scenario, x: f32, y: f32) -> EntityKey {
+    let room_key = scenario.last_room();
+    scenario.mutate(|ctx| {
+        ctx.server(|server| {
+            server
+                .spawn(|mut e| {
+                    e.insert_component(Position::new(x, y));
+                    e.enter_room(&room_key);
+                })
+                .0
+        })
+    })
+}
+
+fn move_to(scenario: &mut Scenario, entity: &EntityKey, x: f32, y: f32) {
+    scenario.mutate(|ctx| {
+        ctx.server(|server| {
+            let mut entity_mut = server.entity_mut(entity).expect("entity exists");
+            let mut position = entity_mut.component::<Position>().expect("has position");
+            *position.x = x;
+            *position.y = y;
+        })
+    });
+}
+
+fn settle(scenario: &mut Scenario, ticks: usize) {
+    for _ in 0..ticks {
+        scenario.mutate(|_| {});
+    }
+}
+
+#[test]
+fn only_entities_within_enter_radius_replicate() {
+    let mut scenario = Scenario::new();
+    let client_key = server_with_one_client(&mut scenario);
+    scenario.mutate(|ctx| ctx.server(|server| server.set_user_viewpoint(&client_key, 0.0, 0.0)));
+
+    let near = spawn_at(&mut scenario, 5.0, 0.0);
+    let far = spawn_at(&mut scenario, 50.0, 0.0);
+
+    scenario.expect(|ctx| ctx.client(client_key, |c| c.has_entity(&near)).then_some(()));
+    settle(&mut scenario, 10);
+    scenario.expect(|ctx| (!ctx.client(client_key, |c| c.has_entity(&far))).then_some(()));
+}
+
+#[test]
+fn moving_entity_enters_and_exits_with_hysteresis() {
+    let mut scenario = Scenario::new();
+    let client_key = server_with_one_client(&mut scenario);
+    scenario.mutate(|ctx| ctx.server(|server| server.set_user_viewpoint(&client_key, 0.0, 0.0)));
+
+    let mover = spawn_at(&mut scenario, 30.0, 0.0);
+    settle(&mut scenario, 10);
+    scenario.expect(|ctx| (!ctx.client(client_key, |c| c.has_entity(&mover))).then_some(()));
+
+    // Inside the enter radius: spawns.
+    move_to(&mut scenario, &mover, 8.0, 0.0);
+    scenario.expect(|ctx| ctx.client(client_key, |c| c.has_entity(&mover)).then_some(()));
+
+    // Between enter and exit radius: stays.
+    move_to(&mut scenario, &mover, 13.0, 0.0);
+    settle(&mut scenario, 10);
+    scenario.expect(|ctx| ctx.client(client_key, |c| c.has_entity(&mover)).then_some(()));
+
+    // Past the exit radius: despawns.
+    move_to(&mut scenario, &mover, 20.0, 0.0);
+    scenario.expect(|ctx| (!ctx.client(client_key, |c| c.has_entity(&mover))).then_some(()));
+}
+
+#[test]
+fn spatial_interest_never_crosses_rooms() {
+    let mut scenario = Scenario::new();
+    let client_key = server_with_one_client(&mut scenario);
+    scenario.mutate(|ctx| ctx.server(|server| server.set_user_viewpoint(&client_key, 0.0, 0.0)));
+
+    let other_room = scenario.mutate(|ctx| ctx.server(|server| server.create_room().key()));
+    let isolated = scenario.mutate(|ctx| {
+        ctx.server(|server| {
+            server
+                .spawn(|mut e| {
+                    e.insert_component(Position::new(1.0, 1.0));
+                    e.enter_room(&other_room);
+                })
+                .0
+        })
+    });
+
+    settle(&mut scenario, 10);
+    scenario.expect(|ctx| (!ctx.client(client_key, |c| c.has_entity(&isolated))).then_some(()));
+}
+
+#[test]
+fn clearing_viewpoint_restores_room_scope() {
+    let mut scenario = Scenario::new();
+    let client_key = server_with_one_client(&mut scenario);
+    scenario.mutate(|ctx| ctx.server(|server| server.set_user_viewpoint(&client_key, 0.0, 0.0)));
+
+    let far = spawn_at(&mut scenario, 100.0, 100.0);
+    settle(&mut scenario, 10);
+    scenario.expect(|ctx| (!ctx.client(client_key, |c| c.has_entity(&far))).then_some(()));
+
+    scenario.mutate(|ctx| ctx.server(|server| server.clear_user_viewpoint(&client_key)));
+    scenario.expect(|ctx| ctx.client(client_key, |c| c.has_entity(&far)).then_some(()));
+
+    // Setting a viewpoint again despawns the out-of-range entity.
+    scenario.mutate(|ctx| ctx.server(|server| server.set_user_viewpoint(&client_key, 0.0, 0.0)));
+    scenario.expect(|ctx| (!ctx.client(client_key, |c| c.has_entity(&far))).then_some(()));
+}
+
+#[test]
+fn explicit_scope_overrides_spatial_interest() {
+    let mut scenario = Scenario::new();
+    let client_key = server_with_one_client(&mut scenario);
+    scenario.mutate(|ctx| ctx.server(|server| server.set_user_viewpoint(&client_key, 0.0, 0.0)));
+
+    let near = spawn_at(&mut scenario, 5.0, 0.0);
+    let far = spawn_at(&mut scenario, 100.0, 0.0);
+    scenario.expect(|ctx| ctx.client(client_key, |c| c.has_entity(&near)).then_some(()));
+
+    // An explicit include reaches past the radius, an explicit exclude hides
+    // an entity inside it
+    scenario.mutate(|ctx| {
+        ctx.server(|server| {
+            let mut scope = server.user_scope_mut(&client_key).expect("user exists");
+            scope.include(&far);
+            scope.exclude(&near);
+        })
+    });
+    scenario.expect(|ctx| {
+        let has_far = ctx.client(client_key, |c| c.has_entity(&far));
+        let has_near = ctx.client(client_key, |c| c.has_entity(&near));
+        (has_far && !has_near).then_some(())
+    });
+
+    // Moving doesn't undo them
+    move_to(&mut scenario, &far, 150.0, 0.0);
+    move_to(&mut scenario, &near, 1.0, 0.0);
+    settle(&mut scenario, 10);
+    scenario.expect(|ctx| {
+        let has_far = ctx.client(client_key, |c| c.has_entity(&far));
+        let has_near = ctx.client(client_key, |c| c.has_entity(&near));
+        (has_far && !has_near).then_some(())
+    });
+
+    // Neither does clearing the viewpoint
+    scenario.mutate(|ctx| ctx.server(|server| server.clear_user_viewpoint(&client_key)));
+    settle(&mut scenario, 10);
+    scenario.expect(|ctx| {
+        let has_far = ctx.client(client_key, |c| c.has_entity(&far));
+        let has_near = ctx.client(client_key, |c| c.has_entity(&near));
+        (has_far && !has_near).then_some(())
+    });
+}
@@ -0,0 +1,135 @@
+//! End-to-end integration tests for the server's `tracing` spans.
+//!
+//! With the `observability` feature, each phase of the server loop runs in a
+//! span, and each user's packet writes in a `send_user_packets` span
+//! recording packets, bytes, entity commands and entity updates.
+
+mod common;
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use naia_test_harness::{Position, Scenario};
+use tracing::{
+    field::{Field, Visit},
+    span::{Attributes, Id, Record},
+    Event, Metadata, Subscriber,
+};
+
+/// Subscriber keeping every span's name and integer fields.
+#[derive(Default)]
+struct SpanRecorder {
+    spans: Mutex<Vec<RecordedSpan>>,
+}
+
+struct RecordedSpan {
+    name: &'static str,
+    fields: HashMap<&'static str, u64>,
+}
+
+struct FieldVisitor<'a>(&'a mut HashMap<&'static str, u64>);
+
+impl Visit for FieldVisitor<'_> {
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.0.insert(field.name(), value);
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.0.insert(field.name(), value as u64);
+    }
+
+    fn record_debug(&mut self, _field: &Field, _value: &dyn std::fmt::Debug) {}
+}
+
+impl Subscriber for SpanRecorder {
+    fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, attributes: &Attributes<'_>) -> Id {
+        let mut span = RecordedSpan {
+            name: attributes.metadata().name(),
+            fields: HashMap::new(),
+        };
+        attributes.record(&mut FieldVisitor(&mut span.fields));
+        let mut spans = self.spans.lock().unwrap();
+        spans.push(span);
+        Id::from_u64(spans.len() as u64)
+    }
+
+    fn record(&self, span: &Id, values: &Record<'_>) {
+        let mut spans = self.spans.lock().unwrap();
+        let span = &mut spans[span.into_u64() as usize - 1];
+        values.record(&mut FieldVisitor(&mut span.fields));
+    }
+
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+    fn event(&self, _event: &Event<'_>) {}
+
+    fn enter(&self, _span: &Id) {}
+
+    fn exit(&self, _span: &Id) {}
+}
+
+impl SpanRecorder {
+    fn named(&self, name: &str) -> Vec<HashMap<&'static str, u64>> {
+        self.spans
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|span| span.name == name)
+            .map(|span| span.fields.clone())
+            .collect()
+    }
+}
+
+#[test]
+fn server_loop_phases_and_user_writes_are_traced() {
+    let recorder = Arc::new(SpanRecorder::default());
+
+    tracing::subscriber::with_default(recorder.clone(), || {
+        let mut scenario = Scenario::new();
+        let client_key = common::server_with_one_client(&mut scenario);
+        let room_key = scenario.last_room();
+
+        let entity = scenario.mutate(|ctx| {
+            ctx.server(|server| {
+                server
+                    .spawn(|mut e| {
+                        e.insert_component(Position::new(1.0, 2.0));
+                        e.enter_room(&room_key);
+                    })
+                    .0
+            })
+        });
+        scenario.expect(|ctx| ctx.client(client_key, |c| c.entity(&entity).map(|_| ())));
+    });
+
+    for phase in [
+        "receive_all_packets",
+        "process_all_packets",
+        "take_world_events",
+        "take_tick_events",
+        "update_entity_scopes",
+    ] {
+        assert!(!recorder.named(phase).is_empty(), "no {phase} span");
+    }
+
+    let received = recorder.named("receive_all_packets");
+    assert!(received.iter().any(|fields| fields["packets"] > 0));
+    let sent = recorder.named("send_all_packets");
+    assert!(sent
+        .iter()
+        .any(|fields| fields["users"] == 1 && fields["bytes"] > 0));
+
+    let user_writes = recorder.named("send_user_packets");
+    assert!(user_writes.iter().all(|fields| fields.contains_key("user")));
+    assert!(user_writes
+        .iter()
+        .any(|fields| fields["entity_commands"] > 0
+            && fields["packets"] > 0
+            && fields["bytes"] > 0));
+}
@@ -0,0 +1,107 @@
+//! End-to-end integration tests for server-side validation of
+//! client-authoritative component updates.
+//!
+//! The client spawns an entity it owns, the server installs a validator for
+//! `Position`, and the client then writes values the validator rejects or
+//! clamps. The server must keep its own value and send it back, so both
+//! sides end up agreeing.
+
+mod common;
+
+use naia_server::UpdateValidation;
+use naia_test_harness::{test_protocol::ReliableChannel, ClientKey, EntityKey, Position, Scenario};
+
+const MAX_X: f32 = 10.0;
+
+/// Spawns a client-owned entity and waits for it to reach the server
+fn client_spawn_position(scenario: &mut Scenario, client_key: ClientKey) -> EntityKey {
+    let entity = scenario.mutate(|ctx| {
+        ctx.client(client_key, |client| {
+            client.spawn(|mut e| {
+                e.insert_component(Position::new(1.0, 1.0));
+            })
+        })
+    });
+    scenario.expect(|ctx| ctx.server(|server| server.has_entity(&entity).then_some(())));
+    entity
+}
+
+fn client_set_x(scenario: &mut Scenario, client_key: ClientKey, entity: &EntityKey, x: f32) {
+    scenario.mutate(|ctx| {
+        ctx.client(client_key, |client| {
+            let mut entity_mut = client.entity_mut(entity).expect("entity exists");
+            let mut position = entity_mut.component::<Position>().expect("has position");
+            *position.x = x;
+        })
+    });
+}
+
+/// Waits until both the server and the client hold `x`
+fn expect_both_x(scenario: &mut Scenario, client_key: ClientKey, entity: &EntityKey, x: f32) {
+    scenario.expect(|ctx| {
+        let server_x = ctx.server(|server| {
+            server
+                .entity(entity)
+                .and_then(|e| e.component::<Position>().map(|p| *p.x))
+        });
+        let client_x = ctx.client(client_key, |c| {
+            c.entity(entity)
+                .and_then(|e| e.component::<Position>().map(|p| *p.x))
+        });
+        (server_x == Some(x) && client_x == Some(x)).then_some(())
+    });
+}
+
+#[test]
+fn rejected_update_is_reverted_on_client() {
+    let mut scenario = Scenario::new();
+    let client_key = common::server_with_one_client(&mut scenario);
+    let entity = client_spawn_position(&mut scenario, client_key);
+
+    scenario.mutate(|ctx| {
+        ctx.server(|server| {
+            server.set_component_validator::<Position, ReliableChannel>(
+                |_user_key, _current, proposed| {
+                    if *proposed.x > MAX_X {
+                        UpdateValidation::Reject
+                    } else {
+                        UpdateValidation::Accept
+                    }
+                },
+            );
+        })
+    });
+
+    // An accepted update goes through unchanged
+    client_set_x(&mut scenario, client_key, &entity, 5.0);
+    expect_both_x(&mut scenario, client_key, &entity, 5.0);
+
+    // A rejected one leaves the server's value, which the client is corrected to
+    client_set_x(&mut scenario, client_key, &entity, 50.0);
+    expect_both_x(&mut scenario, client_key, &entity, 5.0);
+}
+
+#[test]
+fn clamped_update_is_applied_and_corrected() {
+    let mut scenario = Scenario::new();
+    let client_key = common::server_with_one_client(&mut scenario);
+    let entity = client_spawn_position(&mut scenario, client_key);
+
+    scenario.mutate(|ctx| {
+        ctx.server(|server| {
+            server.set_component_validator::<Position, ReliableChannel>(
+                |_user_key, _current, proposed| {
+                    if *proposed.x > MAX_X {
+                        *proposed.x = MAX_X;
+                        UpdateValidation::Clamp
+                    } else {
+                        UpdateValidation::Accept
+                    }
+                },
+            );
+        })
+    });
+
+    client_set_x(&mut scenario, client_key, &entity, 50.0);
+    expect_both_x(&mut scenario, client_key, &entity, MAX_X);
+}
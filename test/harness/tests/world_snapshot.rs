@@ -0,0 +1,144 @@
+//! End-to-end integration tests for saving a server's world and restoring it
+//! into a fresh server.
+//!
+//! A snapshot carries every server-owned entity with its components,
+//! replication config, rooms and resources. Restoring it into a new server
+//! must replicate the same world to clients, with relations between entities
+//! still intact, and must refuse snapshots taken under another protocol.
+
+mod common;
+
+use naia_server::{ReplicationConfig, RoomKey, ServerConfig, WorldSnapshotError};
+use naia_shared::{EntityAuthStatus, Replicate};
+use naia_test_harness::{protocol, EntityKey, Position, Scenario, Squad, TestScore};
+
+/// Component only registered by the mismatched protocol
+#[derive(Replicate)]
+pub struct Marker;
+
+/// Builds a world with two positioned entities, a squad holding both, a
+/// delegated entity and a resource, and returns its snapshot with the room key
+fn save_sample_world() -> (Vec<u8>, RoomKey) {
+    let mut scenario = Scenario::new();
+    scenario.server_start(ServerConfig::default(), protocol());
+
+    scenario.mutate(|ctx| {
+        ctx.server(|server| {
+            let room_key = server.create_room().key();
+            let members: Vec<EntityKey> = [(1.0, 2.0), (3.0, 4.0)]
+                .into_iter()
+                .map(|(x, y)| {
+                    server
+                        .spawn(|mut e| {
+                            e.insert_component(Position::new(x, y))
+                                .enter_room(&room_key);
+                        })
+                        .0
+                })
+                .collect();
+            let (squad, _) = server.spawn(|mut e| {
+                e.insert_component(Squad::default()).enter_room(&room_key);
+            });
+            for member in &members {
+                assert!(server.entity_set_insert(&squad, member, |s: &mut Squad| &mut s.members));
+            }
+            server.spawn(|mut e| {
+                e.insert_component(Position::new(5.0, 6.0))
+                    .configure_replication(
+                        ReplicationConfig::delegated().queue_authority_requests(),
+                    )
+                    .enter_room(&room_key);
+            });
+            assert!(server.insert_resource(TestScore::new(3, 5), false));
+
+            let bytes = server.save_world().expect("world saves");
+            (bytes, room_key)
+        })
+    })
+}
+
+#[test]
+fn restored_world_replicates_like_the_saved_one() {
+    let (bytes, saved_room) = save_sample_world();
+
+    let mut scenario = Scenario::new();
+    scenario.server_start(ServerConfig::default(), protocol());
+    let (entities, rooms) = scenario.mutate(|ctx| {
+        ctx.server(|server| server.restore_world(&bytes).expect("snapshot restores"))
+    });
+    assert_eq!(entities.len(), 4);
+    let (first, squad, delegated) = (entities[0], entities[2], entities[3]);
+    let room_key = *rooms.get(&saved_room).expect("saved room is restored");
+
+    scenario.mutate(|ctx| {
+        ctx.server(|server| {
+            assert!(server.has_resource::<TestScore>());
+            let config = server
+                .entity(&delegated)
+                .and_then(|e| e.replication_config())
+                .expect("entity exists");
+            assert_eq!(
+                config,
+                ReplicationConfig::delegated().queue_authority_requests()
+            );
+        })
+    });
+
+    let client_key = common::connect_client(&mut scenario, "alice", Some(room_key));
+    scenario.expect(|ctx| {
+        ctx.client(client_key, |c| {
+            let position = c
+                .entity(&first)?
+                .component::<Position>()
+                .map(|p| (*p.x, *p.y))?;
+            let squad_ref = c.entity(&squad)?;
+            let squad = squad_ref.component::<Squad>()?;
+            let resolved = squad.members.len() == 2 && squad.members.unresolved_count() == 0;
+            let authority = c.entity(&delegated)?.authority();
+            let score = c.resource::<TestScore, _, _>(|s| (*s.home, *s.away));
+            (position == (1.0, 2.0)
+                && resolved
+                && authority == Some(EntityAuthStatus::Available)
+                && score == Some((3, 5)))
+            .then_some(())
+        })
+    });
+
+    // Restored components are owned by the new server, so it can keep
+    // mutating them
+    scenario.mutate(|ctx| {
+        ctx.server(|server| {
+            let mut entity = server.entity_mut(&first).expect("entity exists");
+            let mut position = entity.component::<Position>().expect("has position");
+            *position.x = 10.0;
+        })
+    });
+    scenario.expect(|ctx| {
+        ctx.client(client_key, |c| {
+            let x = c.entity(&first)?.component::<Position>().map(|p| *p.x)?;
+            (x == 10.0).then_some(())
+        })
+    });
+}
+
+#[test]
+fn snapshot_from_another_protocol_is_rejected() {
+    let (bytes, _) = save_sample_world();
+
+    let mut other_protocol = protocol();
+    other_protocol.add_component::<Marker>();
+    let mut scenario = Scenario::new();
+    scenario.server_start(ServerConfig::default(), other_protocol);
+
+    scenario.mutate(|ctx| {
+        ctx.server(|server| {
+            let result = server.restore_world(&bytes);
+            assert!(matches!(
+                result,
+                Err(WorldSnapshotError::ProtocolMismatch { .. })
+            ));
+            assert!(server.entities().is_empty());
+            assert!(!server.has_resource::<TestScore>());
+        })
+    });
+}